@@ -0,0 +1,168 @@
+/* benches/circuit_breaker_record.rs */
+//! **Brief:** Benchmark of recording outcomes through a circuit breaker.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Circuit Breaker Pattern]
+//!  - [Benchmarks]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Compares `CircuitBreaker::execute` in the Closed state, whose recording
+//! takes no lock, against `LockedBaseline`, a replica of the previous recording
+//! path that took the state write lock and the observers mutex on every call.
+//! Recording still writes atomics shared by all threads (the outcome window
+//! and consecutive counters), so the contended runs are not contention-free.
+//! Each is measured on one thread and with eight threads hammering the same
+//! breaker; the contended bare/tracked pair shows what the striped request
+//! counters add under contention. Run with `cargo bench --bench circuit_breaker_record`.
+
+use aklypse::common::error::circuitbreaker::{CircuitMetrics, CircuitOperationType, CircuitTransitionEvent};
+use aklypse::common::error::{AklypseError, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerObserver, CircuitState};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::VecDeque;
+use std::sync::{Arc, Barrier, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const THREADS: usize = 8;
+
+struct NoopObserver;
+
+impl CircuitBreakerObserver for NoopObserver {
+    fn on_state_change(&self, _name: &str, _event: &CircuitTransitionEvent) {}
+    fn on_operation_attempt(&self, _name: &str, _state: CircuitState) {}
+    fn on_operation_result(&self, _name: &str, _op_type: CircuitOperationType, _duration: Duration, _error: Option<&AklypseError>) {}
+    fn on_reset(&self, _name: &str) {}
+}
+
+// The recording path before the redesign: windows and metrics behind one
+// write lock, observers behind a mutex, both taken on every call
+#[derive(Default)]
+struct LockedState {
+    consecutive_successes: usize,
+    consecutive_failures: usize,
+    results_window: VecDeque<bool>,
+    slow_call_window: VecDeque<bool>,
+    metrics: CircuitMetrics,
+}
+
+struct LockedBaseline {
+    inner: RwLock<LockedState>,
+    observers: Mutex<Vec<Arc<dyn CircuitBreakerObserver>>>,
+}
+
+impl LockedBaseline {
+    fn new() -> Self {
+        Self { inner: RwLock::new(LockedState::default()), observers: Mutex::new(Vec::new()) }
+    }
+
+    fn execute(&self, operation: impl FnOnce() -> Result<(), AklypseError>) -> Result<(), AklypseError> {
+        let state = self.inner.read().unwrap().metrics.state;
+        for observer in &*self.observers.lock().unwrap() {
+            observer.on_operation_attempt("baseline", state);
+        }
+        let start = Instant::now();
+        let result = operation();
+        self.record_success(start.elapsed());
+        result
+    }
+
+    fn record_success(&self, duration: Duration) {
+        let mut inner = self.inner.write().unwrap();
+        inner.consecutive_successes += 1;
+        inner.consecutive_failures = 0;
+        if inner.results_window.len() >= 100 {
+            inner.results_window.pop_front();
+        }
+        inner.results_window.push_back(true);
+        if inner.slow_call_window.len() >= 100 {
+            inner.slow_call_window.pop_front();
+        }
+        inner.slow_call_window.push_back(false);
+        inner.metrics.total_requests += 1;
+        inner.metrics.successful_requests += 1;
+        inner.metrics.consecutive_successes = inner.consecutive_successes as u32;
+        let failures = inner.results_window.iter().filter(|&&success| !success).count();
+        inner.metrics.failure_rate_in_window = Some(failures as f64 / inner.results_window.len() as f64);
+        let slow = inner.slow_call_window.iter().filter(|&&slow| slow).count();
+        inner.metrics.slow_call_rate_in_window = Some(slow as f64 / inner.slow_call_window.len() as f64);
+        inner.metrics.last_transition_timestamp.get_or_insert_with(SystemTime::now);
+        drop(inner);
+        for observer in &*self.observers.lock().unwrap() {
+            observer.on_operation_result("baseline", CircuitOperationType::Success, duration, None);
+        }
+    }
+}
+
+fn breaker(track_metrics: bool, observed: bool) -> Arc<CircuitBreaker> {
+    let config = CircuitBreakerConfig { track_metrics, operation_timeout: None, ..CircuitBreakerConfig::default() };
+    let breaker = CircuitBreaker::new("bench", config);
+    if observed {
+        breaker.add_observer(Arc::new(NoopObserver));
+    }
+    breaker
+}
+
+// Wall time for `iters` calls spread over `THREADS` threads
+fn contended(iters: u64, call: impl Fn() + Send + Sync + 'static) -> Duration {
+    let call = Arc::new(call);
+    let barrier = Arc::new(Barrier::new(THREADS + 1));
+    let per_thread = iters / THREADS as u64 + 1;
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let (call, barrier) = (call.clone(), barrier.clone());
+            thread::spawn(move || {
+                barrier.wait();
+                (0..per_thread).for_each(|_| call());
+            })
+        })
+        .collect();
+    barrier.wait();
+    let start = Instant::now();
+    handles.into_iter().for_each(|handle| handle.join().unwrap());
+    start.elapsed()
+}
+
+fn bench_record(c: &mut Criterion) {
+    let mut group = c.benchmark_group("circuit_breaker_record");
+
+    let bare = breaker(false, false);
+    let tracked = breaker(true, true);
+    let baseline = LockedBaseline::new();
+    group.bench_function("lock_free_bare", |b| b.iter(|| bare.execute(|| black_box(Ok(())))));
+    group.bench_function("lock_free_tracked_observed", |b| b.iter(|| tracked.execute(|| black_box(Ok(())))));
+    group.bench_function("locked_baseline", |b| b.iter(|| baseline.execute(|| black_box(Ok(())))));
+
+    group.bench_function("contended_lock_free_bare", |b| {
+        b.iter_custom(|iters| {
+            let breaker = breaker(false, false);
+            contended(iters, move || breaker.execute(|| black_box(Ok(()))).unwrap())
+        })
+    });
+    group.bench_function("contended_lock_free_tracked", |b| {
+        b.iter_custom(|iters| {
+            let breaker = breaker(true, false);
+            contended(iters, move || breaker.execute(|| black_box(Ok(()))).unwrap())
+        })
+    });
+    group.bench_function("contended_lock_free_tracked_observed", |b| {
+        b.iter_custom(|iters| {
+            let breaker = breaker(true, true);
+            contended(iters, move || breaker.execute(|| black_box(Ok(()))).unwrap())
+        })
+    });
+    group.bench_function("contended_locked_baseline", |b| {
+        b.iter_custom(|iters| {
+            let baseline = Arc::new(LockedBaseline::new());
+            baseline.observers.lock().unwrap().push(Arc::new(NoopObserver));
+            contended(iters, move || baseline.execute(|| black_box(Ok(()))).unwrap())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_record);
+criterion_main!(benches);
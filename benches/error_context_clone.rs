@@ -0,0 +1,55 @@
+/* benches/error_context_clone.rs */
+//! **Brief:** Benchmark of cloning errors carrying a large rich context.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [Benchmarks]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Compares cloning an `ErrorContext`, whose strings, collections and
+//! diagnostics are shared, against deep-copying the same data, which is what
+//! every clone cost before the context was `Arc`-backed. Run with
+//! `cargo bench --bench error_context_clone`.
+
+use aklypse::common::error::{DiagnosticResult, ErrorContext};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::collections::HashMap;
+
+fn large_context() -> ErrorContext {
+    let context = (0..32).fold(ErrorContext::new("processing order batch").with_component("orders"), |context, i| {
+        context.with_metadata(format!("order.{}", i), "x".repeat(64)).add_tag(format!("shard:{}", i % 8))
+    });
+    context.with_diagnostic_info(DiagnosticResult {
+        primary_location: None,
+        expansion_trace: Vec::new(),
+        suggested_fixes: (0..8).map(|i| format!("suggestion {}", i)).collect(),
+        original_message: Some("mismatched types".repeat(16)),
+        diagnostic_code: Some("E0308".to_string()),
+        stack_frames: Vec::new(),
+    })
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let context = large_context();
+
+    let mut group = c.benchmark_group("error_context_clone");
+    group.bench_function("shared_context", |b| b.iter(|| black_box(&context).clone()));
+    group.bench_function("deep_copy_baseline", |b| {
+        b.iter(|| {
+            let context = black_box(&context);
+            let metadata: HashMap<String, String> = (*context.metadata).clone();
+            let tags: Vec<String> = context.tags.to_vec();
+            let diagnostics = context.diagnostic_info.as_deref().cloned();
+            let strings = (context.message.to_string(), context.component.as_deref().map(str::to_string));
+            (strings, metadata, tags, diagnostics)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_clone);
+criterion_main!(benches);
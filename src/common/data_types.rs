@@ -0,0 +1,382 @@
+/* src/common/data_types.rs */
+#![warn(missing_docs)]
+//! **Brief:** Collection types enforcing size limits with structured errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Common Data Types]
+//!  - [Bounded Collections]
+//!  - [Limit Errors]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `BoundedVec`, `BoundedString` and `BoundedMap` hold at most `N` items
+//! (characters for strings). Building one from input that is already too
+//! large fails with `AklypseError::Validation` naming the field, and growing
+//! one past its limit fails with `AklypseError::ResourceExhausted` carrying
+//! the limit and the current size. With the `serde` feature they deserialize
+//! through the same checks, so oversized config and API input is rejected at
+//! the boundary.
+
+use crate::common::error::{AklypseError, ResourceExhaustedSnafu, Result, ValidationSnafu};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+
+#[track_caller]
+fn too_large(field: &str, size: usize, limit: usize, unit: &str) -> AklypseError {
+    ValidationSnafu {
+        field: field.to_string(),
+        message: format!("has {} {}, at most {} allowed", size, unit, limit),
+    }
+    .build()
+}
+
+#[track_caller]
+fn exhausted(resource: &str, limit: usize, current: usize) -> AklypseError {
+    ResourceExhaustedSnafu {
+        resource: resource.to_string(),
+        limit: limit.to_string(),
+        current: current.to_string(),
+    }
+    .build()
+}
+
+/// Vector holding at most `N` items
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BoundedVec<T, const N: usize>(Vec<T>);
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// Create an empty vector
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Wrap `items`, failing with a `Validation` error on `field` when there are more than `N`
+    #[track_caller]
+    pub fn try_from_field(field: &str, items: Vec<T>) -> Result<Self> {
+        if items.len() > N {
+            return Err(too_large(field, items.len(), N, "items"));
+        }
+        Ok(Self(items))
+    }
+
+    /// Append `item`, failing with `ResourceExhausted` when the vector is full
+    #[track_caller]
+    pub fn push(&mut self, item: T) -> Result<()> {
+        if self.0.len() >= N {
+            return Err(exhausted("bounded vector", N, self.0.len()));
+        }
+        self.0.push(item);
+        Ok(())
+    }
+
+    /// Remove and return the last item
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    /// Maximum number of items
+    pub const fn limit(&self) -> usize {
+        N
+    }
+
+    /// Number of items that can still be added
+    pub fn remaining(&self) -> usize {
+        N - self.0.len()
+    }
+
+    /// Items as a slice
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Unwrap the items
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for BoundedVec<T, N> {
+    type Error = AklypseError;
+
+    fn try_from(items: Vec<T>) -> Result<Self> {
+        Self::try_from_field("value", items)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a BoundedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// String holding at most `N` characters
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BoundedString<const N: usize>(String);
+
+impl<const N: usize> BoundedString<N> {
+    /// Create an empty string
+    pub fn new() -> Self {
+        Self(String::new())
+    }
+
+    /// Wrap `text`, failing with a `Validation` error on `field` when it has more than `N` characters
+    #[track_caller]
+    pub fn try_from_field(field: &str, text: impl Into<String>) -> Result<Self> {
+        let text = text.into();
+        let length = text.chars().count();
+        if length > N {
+            return Err(too_large(field, length, N, "characters"));
+        }
+        Ok(Self(text))
+    }
+
+    /// Append `text`, failing with `ResourceExhausted` when the result would exceed `N` characters
+    #[track_caller]
+    pub fn push_str(&mut self, text: &str) -> Result<()> {
+        let current = self.char_count();
+        if current + text.chars().count() > N {
+            return Err(exhausted("bounded string", N, current));
+        }
+        self.0.push_str(text);
+        Ok(())
+    }
+
+    /// Number of characters
+    pub fn char_count(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    /// Maximum number of characters
+    pub const fn limit(&self) -> usize {
+        N
+    }
+
+    /// Text as a string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Unwrap the text
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> Deref for BoundedString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for BoundedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const N: usize> TryFrom<String> for BoundedString<N> {
+    type Error = AklypseError;
+
+    fn try_from(text: String) -> Result<Self> {
+        Self::try_from_field("value", text)
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for BoundedString<N> {
+    type Error = AklypseError;
+
+    fn try_from(text: &str) -> Result<Self> {
+        Self::try_from_field("value", text)
+    }
+}
+
+/// Hash map holding at most `N` entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedMap<K: Eq + Hash, V, const N: usize>(HashMap<K, V>);
+
+impl<K: Eq + Hash, V, const N: usize> BoundedMap<K, V, N> {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Wrap `entries`, failing with a `Validation` error on `field` when there are more than `N`
+    #[track_caller]
+    pub fn try_from_field(field: &str, entries: HashMap<K, V>) -> Result<Self> {
+        if entries.len() > N {
+            return Err(too_large(field, entries.len(), N, "entries"));
+        }
+        Ok(Self(entries))
+    }
+
+    /// Insert `value` under `key`, returning the previous value
+    ///
+    /// Replacing an existing key always succeeds; adding a new key to a full
+    /// map fails with `ResourceExhausted`.
+    #[track_caller]
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>> {
+        if self.0.len() >= N && !self.0.contains_key(&key) {
+            return Err(exhausted("bounded map", N, self.0.len()));
+        }
+        Ok(self.0.insert(key, value))
+    }
+
+    /// Remove the entry of `key`, returning its value
+    pub fn remove<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.0.remove(key)
+    }
+
+    /// Maximum number of entries
+    pub const fn limit(&self) -> usize {
+        N
+    }
+
+    /// Entries as a map
+    pub fn as_map(&self) -> &HashMap<K, V> {
+        &self.0
+    }
+
+    /// Unwrap the entries
+    pub fn into_inner(self) -> HashMap<K, V> {
+        self.0
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> Default for BoundedMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> Deref for BoundedMap<K, V, N> {
+    type Target = HashMap<K, V>;
+
+    fn deref(&self) -> &HashMap<K, V> {
+        &self.0
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> TryFrom<HashMap<K, V>> for BoundedMap<K, V, N> {
+    type Error = AklypseError;
+
+    fn try_from(entries: HashMap<K, V>) -> Result<Self> {
+        Self::try_from_field("value", entries)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{BoundedMap, BoundedString, BoundedVec};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    impl<T: Serialize, const N: usize> Serialize for BoundedVec<T, N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for BoundedVec<T, N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Self::try_from(Vec::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+    }
+
+    impl<const N: usize> Serialize for BoundedString<N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, const N: usize> Deserialize<'de> for BoundedString<N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Self::try_from(String::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+    }
+
+    impl<K: Eq + Hash + Serialize, V: Serialize, const N: usize> Serialize for BoundedMap<K, V, N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, K: Eq + Hash + Deserialize<'de>, V: Deserialize<'de>, const N: usize> Deserialize<'de> for BoundedMap<K, V, N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Self::try_from(HashMap::deserialize(deserializer)?).map_err(D::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_vec_and_string_limits() {
+        let mut tags: BoundedVec<&str, 2> = BoundedVec::try_from_field("tags", vec!["a"]).unwrap();
+        tags.push("b").unwrap();
+        match tags.push("c") {
+            Err(AklypseError::ResourceExhausted { limit, current, .. }) => assert_eq!((limit.as_str(), current.as_str()), ("2", "2")),
+            other => panic!("expected ResourceExhausted, got {:?}", other),
+        }
+        assert_eq!(tags.as_slice(), &["a", "b"]);
+
+        match BoundedString::<4>::try_from_field("zip", "941070") {
+            Err(AklypseError::Validation { field, message, .. }) => {
+                assert_eq!(field, "zip");
+                assert_eq!(message, "has 6 characters, at most 4 allowed");
+            }
+            other => panic!("expected Validation, got {:?}", other),
+        }
+        let mut name = BoundedString::<4>::try_from("hé").unwrap();
+        name.push_str("ll").unwrap();
+        assert!(name.push_str("o").is_err());
+        assert_eq!(name.as_str(), "héll");
+    }
+
+    #[test]
+    fn test_bounded_map_replaces_when_full() {
+        let mut headers: BoundedMap<String, u32, 1> = BoundedMap::new();
+        headers.insert("a".to_string(), 1).unwrap();
+        assert_eq!(headers.insert("a".to_string(), 2).unwrap(), Some(1));
+        assert!(headers.insert("b".to_string(), 3).is_err());
+        assert_eq!(headers.get("a"), Some(&2));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_enforces_limits() {
+        let ok: BoundedVec<u8, 3> = serde_json::from_str("[1,2,3]").unwrap();
+        assert_eq!(ok.len(), 3);
+        assert!(serde_json::from_str::<BoundedVec<u8, 3>>("[1,2,3,4]").is_err());
+        assert!(serde_json::from_str::<BoundedString<3>>("\"abcd\"").is_err());
+    }
+}
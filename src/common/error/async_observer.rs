@@ -0,0 +1,238 @@
+/* src/common/error/async_observer.rs */
+#![warn(missing_docs)]
+//! **Brief:** Async circuit breaker observers driven by a background task.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Circuit Breaker Pattern]
+//!  - [Observers]
+//!  - [Background Dispatch]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `CircuitBreakerObserver` callbacks run inline, on the thread recording
+//! the outcome. An `AsyncCircuitBreakerObserver` instead receives owned
+//! `CircuitEvent`s from a background tokio task, so slow work (writing to a
+//! database, shipping metrics over the network) stays off the caller's path.
+//!
+//! `AsyncObserverDispatcher` is the bridge: it is a plain observer that only
+//! copies each event into a bounded channel, drained in order by the task
+//! it spawned. When the observer falls behind, new events are dropped and
+//! counted rather than blocking the breaker. The task ends once the
+//! dispatcher is dropped, e.g. removed from the breaker, and the channel
+//! is drained.
+
+use super::circuitbreaker::{CircuitBreakerObserver, CircuitOperationType, CircuitTransitionEvent};
+use super::{AklypseError, CircuitBreakerConfig, CircuitState};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// Events buffered by `CircuitBreaker::add_async_observer`
+pub const DEFAULT_ASYNC_OBSERVER_CAPACITY: usize = 1024;
+
+/// Future returned by `AsyncCircuitBreakerObserver::on_event`
+pub type ObserverFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Owned copy of a circuit breaker notification.
+#[derive(Debug, Clone)]
+pub enum CircuitEvent {
+    /// The state changed
+    StateChange(CircuitTransitionEvent),
+    /// An operation is about to run in `state`
+    OperationAttempt(CircuitState),
+    /// An operation completed, was rejected or timed out
+    OperationResult {
+        /// Outcome of the operation
+        op_type: CircuitOperationType,
+        /// How long the operation ran
+        duration: Duration,
+        /// Error of a failed operation
+        error: Option<Box<AklypseError>>,
+    },
+    /// The breaker was manually reset
+    Reset,
+    /// `update_config` replaced the configuration with this one
+    ConfigChange(Box<CircuitBreakerConfig>),
+    /// The reset timeout elapsed, so the next call probes the circuit
+    HalfOpenEligible,
+    /// `execute_keyed` ejected a failing key
+    KeyEjected {
+        /// The ejected key
+        key: String,
+        /// How long the key stays ejected
+        duration: Duration,
+    },
+}
+
+/// Observer trait for circuit breaker events, awaited off the hot path.
+pub trait AsyncCircuitBreakerObserver: Send + Sync {
+    /// Called for every event of the breaker `name`, one at a time and in order.
+    fn on_event<'a>(&'a self, name: &'a str, event: CircuitEvent) -> ObserverFuture<'a>;
+}
+
+/// Delivery counters of an `AsyncObserverDispatcher`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AsyncObserverStats {
+    /// Events handed to the background task
+    pub dispatched: u64,
+    /// Events dropped because the channel was full or the task was gone
+    pub dropped: u64,
+    /// Events the observer finished handling
+    pub delivered: u64,
+}
+
+/// Observer forwarding events to an `AsyncCircuitBreakerObserver` on a background task
+pub struct AsyncObserverDispatcher {
+    sender: mpsc::Sender<(String, CircuitEvent)>,
+    dispatched: AtomicU64,
+    dropped: AtomicU64,
+    delivered: Arc<AtomicU64>,
+}
+
+impl AsyncObserverDispatcher {
+    /// Spawn the task driving `observer`, buffering up to `capacity` events
+    ///
+    /// A capacity of 0 is raised to 1.
+    ///
+    /// # Panics
+    ///
+    /// Outside of a tokio runtime.
+    pub fn spawn(observer: Arc<dyn AsyncCircuitBreakerObserver>, capacity: usize) -> Arc<Self> {
+        let (sender, mut receiver) = mpsc::channel::<(String, CircuitEvent)>(capacity.max(1));
+        let delivered = Arc::new(AtomicU64::new(0));
+        let counter = delivered.clone();
+        tokio::spawn(async move {
+            while let Some((name, event)) = receiver.recv().await {
+                observer.on_event(&name, event).await;
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Arc::new(Self { sender, dispatched: AtomicU64::new(0), dropped: AtomicU64::new(0), delivered })
+    }
+
+    /// Snapshot of the delivery counters
+    pub fn stats(&self) -> AsyncObserverStats {
+        AsyncObserverStats {
+            dispatched: self.dispatched.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+        }
+    }
+
+    fn dispatch(&self, name: &str, event: CircuitEvent) {
+        match self.sender.try_send((name.to_string(), event)) {
+            Ok(()) => self.dispatched.fetch_add(1, Ordering::Relaxed),
+            Err(TrySendError::Full(_) | TrySendError::Closed(_)) => self.dropped.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+}
+
+impl CircuitBreakerObserver for AsyncObserverDispatcher {
+    fn on_state_change(&self, name: &str, event: &CircuitTransitionEvent) {
+        self.dispatch(name, CircuitEvent::StateChange(event.clone()));
+    }
+
+    fn on_operation_attempt(&self, name: &str, state: CircuitState) {
+        self.dispatch(name, CircuitEvent::OperationAttempt(state));
+    }
+
+    fn on_operation_result(&self, name: &str, op_type: CircuitOperationType, duration: Duration, error: Option<&AklypseError>) {
+        self.dispatch(name, CircuitEvent::OperationResult { op_type, duration, error: error.cloned().map(Box::new) });
+    }
+
+    fn on_reset(&self, name: &str) {
+        self.dispatch(name, CircuitEvent::Reset);
+    }
+
+    fn on_config_change(&self, name: &str, _previous: &CircuitBreakerConfig, config: &CircuitBreakerConfig) {
+        self.dispatch(name, CircuitEvent::ConfigChange(Box::new(config.clone())));
+    }
+
+    fn on_half_open_eligible(&self, name: &str) {
+        self.dispatch(name, CircuitEvent::HalfOpenEligible);
+    }
+
+    fn on_key_ejected(&self, name: &str, key: &str, duration: Duration) {
+        self.dispatch(name, CircuitEvent::KeyEjected { key: key.to_string(), duration });
+    }
+
+    fn pending_events(&self) -> u64 {
+        let stats = self.stats();
+        stats.dispatched.saturating_sub(stats.delivered)
+    }
+}
+
+impl std::fmt::Debug for AsyncObserverDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncObserverDispatcher").field("stats", &self.stats()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreaker, CircuitBreakerConfig};
+    use tokio::sync::Mutex;
+
+    // Slow observer recording the events it awaited
+    #[derive(Default)]
+    struct RecordingObserver(Mutex<Vec<String>>);
+
+    impl AsyncCircuitBreakerObserver for RecordingObserver {
+        fn on_event<'a>(&'a self, name: &'a str, event: CircuitEvent) -> ObserverFuture<'a> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                let label = match event {
+                    CircuitEvent::StateChange(event) => format!("{}:{}", name, event.to_state),
+                    CircuitEvent::OperationAttempt(_) => format!("{}:attempt", name),
+                    CircuitEvent::OperationResult { op_type, .. } => format!("{}:{:?}", name, op_type),
+                    CircuitEvent::Reset => format!("{}:reset", name),
+                    CircuitEvent::ConfigChange(config) => format!("{}:config:{}", name, config.failure_threshold),
+                    CircuitEvent::HalfOpenEligible => format!("{}:eligible", name),
+                    CircuitEvent::KeyEjected { key, .. } => format!("{}:ejected:{}", name, key),
+                };
+                self.0.lock().await.push(label);
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_reach_async_observer_in_order() {
+        let breaker = CircuitBreaker::new("ledger", CircuitBreakerConfig::default());
+        let observer = Arc::new(RecordingObserver::default());
+        let id = breaker.add_async_observer(observer.clone());
+
+        breaker.execute(|| Ok(())).unwrap();
+        breaker.reset();
+        breaker.update_config(CircuitBreakerConfig { failure_threshold: 2, ..Default::default() });
+        assert!(breaker.remove_observer(id));
+        breaker.execute(|| Ok(())).unwrap();
+
+        for _ in 0..100 {
+            if observer.0.lock().await.len() == 5 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let events = ["ledger:attempt", "ledger:Success", "ledger:Closed", "ledger:reset", "ledger:config:2"];
+        assert_eq!(*observer.0.lock().await, events);
+    }
+
+    #[tokio::test]
+    async fn test_full_channel_drops_instead_of_blocking() {
+        let observer = Arc::new(RecordingObserver::default());
+        let dispatcher = AsyncObserverDispatcher::spawn(observer, 1);
+        for _ in 0..10 {
+            dispatcher.on_reset("ledger");
+        }
+        let stats = dispatcher.stats();
+        assert_eq!(stats.dispatched + stats.dropped, 10);
+        assert!(stats.dropped > 0);
+    }
+}
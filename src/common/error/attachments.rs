@@ -0,0 +1,196 @@
+/* src/common/error/attachments.rs */
+#![warn(missing_docs)]
+//! **Brief:** Typed payloads attached to errors and retrieved by type.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [Typed Attachments]
+//!  - [Recovery Data]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Layers often hold structured data a caller further up could use to
+//! recover (the request that failed, the record that did not validate).
+//! `AklypseError::attach` stores such a value in the error's context, keyed
+//! by its type, and `AklypseError::get_attachment` hands it back without it
+//! ever being stringified into metadata.
+//!
+//! Attachments are opaque to serde and to every report format. To surface one
+//! in reports, register `debug_attachment_fields::<T>` as a field extractor.
+
+use super::types::ErrorContext;
+use super::{AklypseError, WithRichContextSnafu};
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Type-keyed map of values attached to an `ErrorContext`
+///
+/// Values are shared on clone, so attaching large payloads does not make
+/// cloning errors more expensive.
+#[derive(Clone, Default)]
+pub struct Attachments {
+    entries: HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>,
+}
+
+impl Attachments {
+    /// Create an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value`, replacing any previous value of the same type
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) {
+        self.entries.insert(TypeId::of::<T>(), (type_name::<T>(), Arc::new(value)));
+    }
+
+    /// Value of type `T`, if one was attached
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.entries.get(&TypeId::of::<T>()).and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// Whether a value of type `T` was attached
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Remove the value of type `T`, returning whether there was one
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> bool {
+        self.entries.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// Number of attached values
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is attached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Type names of the attached values, sorted
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.entries.values().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Copy the values of `other`, replacing values of the same type only when `overwrite` is set
+    pub fn extend_from(&mut self, other: &Attachments, overwrite: bool) {
+        for (type_id, entry) in &other.entries {
+            if overwrite || !self.entries.contains_key(type_id) {
+                self.entries.insert(*type_id, entry.clone());
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Attachments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.type_names()).finish()
+    }
+}
+
+impl ErrorContext {
+    /// Attach a typed payload, replacing any previous payload of the same type
+    pub fn with_attachment<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.attachments.insert(value);
+        self
+    }
+}
+
+impl AklypseError {
+    /// Attach a typed payload to the outermost context
+    ///
+    /// Errors without rich context are wrapped in one first.
+    #[track_caller]
+    pub fn attach<T: Any + Send + Sync>(self, value: T) -> Self {
+        match self {
+            AklypseError::WithRichContext { mut context, source, .. } => {
+                context.attachments.insert(value);
+                WithRichContextSnafu { context, source }.build()
+            }
+            other => other.add_context(ErrorContext::new("Attachment added").with_attachment(value)),
+        }
+    }
+
+    /// Payload of type `T` from the outermost context holding one
+    pub fn get_attachment<T: Any + Send + Sync>(&self) -> Option<&T> {
+        let mut current = self;
+        while let AklypseError::WithRichContext { context, source, .. } = current {
+            if let Some(value) = context.attachments.get::<T>() {
+                return Some(value);
+            }
+            current = source.as_ref();
+        }
+        None
+    }
+}
+
+/// Field extractor reporting attachments of type `T` with their `Debug` form
+///
+/// Register it as `config.with_field_extractor(debug_attachment_fields::<T>)`;
+/// the field key is `attachment.<type name>`.
+pub fn debug_attachment_fields<T: Any + Send + Sync + fmt::Debug>(
+    error: &(dyn std::error::Error + 'static),
+) -> Vec<(String, String)> {
+    error
+        .downcast_ref::<AklypseError>()
+        .and_then(AklypseError::get_rich_context)
+        .and_then(|context| context.attachments.get::<T>())
+        .map(|value| vec![(format!("attachment.{}", type_name::<T>()), format!("{:?}", value))])
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::reporter::{ErrorReportConfig, ErrorReporter};
+    use crate::common::error::{ErrorReport, InternalSnafu};
+
+    #[derive(Debug, PartialEq)]
+    struct FailedRequest {
+        method: &'static str,
+        path: &'static str,
+    }
+
+    fn failed_call() -> AklypseError {
+        InternalSnafu { message: "connection reset".to_string(), source: None }.build()
+    }
+
+    #[test]
+    fn test_attach_and_get_through_layers() {
+        let error = failed_call()
+            .attach(FailedRequest { method: "POST", path: "/orders" })
+            .add_context_msg("placing order")
+            .attach(3_u32);
+
+        assert_eq!(error.get_attachment::<FailedRequest>().map(|request| request.path), Some("/orders"));
+        assert_eq!(error.get_attachment::<u32>(), Some(&3));
+        assert!(error.get_attachment::<String>().is_none());
+        assert!(failed_call().get_attachment::<u32>().is_none());
+
+        let cloned = error.clone();
+        assert_eq!(cloned.get_attachment::<u32>(), Some(&3));
+    }
+
+    #[test]
+    fn test_attachments_reported_only_through_extractor() {
+        let error = failed_call().attach(FailedRequest { method: "GET", path: "/health" });
+
+        let plain = ErrorReport::from_error(&error, &ErrorReportConfig::default());
+        assert!(plain.fields.is_empty());
+        assert!(!ErrorReporter::new().report_to_string(&error, &ErrorReportConfig::default()).contains("/health"));
+
+        let config = ErrorReportConfig::default().with_field_extractor(debug_attachment_fields::<FailedRequest>);
+        let report = ErrorReport::from_error(&error, &config);
+        assert_eq!(report.fields.len(), 1);
+        assert!(report.fields[0].0.ends_with("FailedRequest"));
+        assert!(report.fields[0].1.contains("/health"));
+    }
+}
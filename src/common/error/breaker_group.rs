@@ -0,0 +1,276 @@
+/* src/common/error/breaker_group.rs */
+#![warn(missing_docs)]
+//! **Brief:** Groups of circuit breakers propagating trips to related breakers.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Circuit Breaker Pattern]
+//!  - [Breaker Groups]
+//!  - [Failure Propagation]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `BreakerGroup` ties together breakers guarding related dependencies,
+//! e.g. every breaker pointing at the same upstream host. When a member's
+//! circuit opens, the group applies a `Propagation` to each other member:
+//! trip it as well, flag it with a warning, or leave it alone. The default
+//! propagation covers every pair of members; `set_rule` overrides it for one
+//! source and target.
+//!
+//! Only Closed targets are affected, so a breaker that is probing, already
+//! open or pinned by `force_open`/`force_closed` keeps its state, and trips
+//! caused by the group never propagate further. A warning lasts until the
+//! breaker that caused it closes again.
+
+use super::circuitbreaker::{CircuitBreakerObserver, CircuitOperationType, CircuitTransitionEvent};
+use super::{AklypseError, CircuitBreaker, CircuitState, ObserverId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+use std::time::{Duration, SystemTime};
+use tracing::info;
+
+// Start of the transition reason of trips caused by a group
+const PROPAGATED_REASON: &str = "Propagated from";
+
+/// What happens to a member when another member's circuit opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Propagation {
+    /// Nothing
+    Ignore,
+    /// Flag the member with a `GroupWarning` while the source stays open
+    #[default]
+    Warn,
+    /// Trip the member
+    Trip,
+}
+
+/// A member flagged because a related breaker opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupWarning {
+    /// Name of the flagged breaker
+    pub breaker: String,
+    /// Name of the breaker whose circuit opened
+    pub source: String,
+    /// When the source opened
+    pub since: SystemTime,
+}
+
+struct Member {
+    breaker: Arc<CircuitBreaker>,
+    observer: ObserverId,
+}
+
+struct GroupState {
+    members: Vec<Member>,
+    rules: HashMap<(String, String), Propagation>,
+    warnings: Vec<GroupWarning>,
+}
+
+/// Circuit breakers whose trips propagate to each other.
+pub struct BreakerGroup {
+    name: String,
+    default_propagation: Propagation,
+    state: RwLock<GroupState>,
+}
+
+impl BreakerGroup {
+    /// Creates a new BreakerGroup applying `default_propagation` between all members
+    pub fn new(name: impl Into<String>, default_propagation: Propagation) -> Arc<Self> {
+        Arc::new(Self {
+            name: name.into(),
+            default_propagation,
+            state: RwLock::new(GroupState { members: Vec::new(), rules: HashMap::new(), warnings: Vec::new() }),
+        })
+    }
+
+    /// Name the group was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add `breaker` to the group; it leaves the group when the group is dropped
+    pub fn add(self: &Arc<Self>, breaker: Arc<CircuitBreaker>) {
+        let observer = breaker.add_observer(Arc::new(GroupObserver { group: Arc::downgrade(self) }));
+        self.write().members.push(Member { breaker, observer });
+    }
+
+    /// Apply `propagation` when `source` opens to `target` instead of the default
+    pub fn set_rule(&self, source: impl Into<String>, target: impl Into<String>, propagation: Propagation) {
+        self.write().rules.insert((source.into(), target.into()), propagation);
+    }
+
+    /// Breakers of the group
+    pub fn members(&self) -> Vec<Arc<CircuitBreaker>> {
+        self.read().members.iter().map(|member| member.breaker.clone()).collect()
+    }
+
+    /// Members currently flagged with a warning
+    pub fn warnings(&self) -> Vec<GroupWarning> {
+        self.read().warnings.clone()
+    }
+
+    /// Whether the member named `breaker` is flagged with a warning
+    pub fn is_warned(&self, breaker: &str) -> bool {
+        self.read().warnings.iter().any(|warning| warning.breaker == breaker)
+    }
+
+    fn propagation(&self, state: &GroupState, source: &str, target: &str) -> Propagation {
+        state.rules.get(&(source.to_string(), target.to_string())).copied().unwrap_or(self.default_propagation)
+    }
+
+    // Warn or trip the Closed members related to `source`, which just opened
+    fn source_opened(&self, source: &str, event: &CircuitTransitionEvent) {
+        if event.reason.starts_with(PROPAGATED_REASON) {
+            return;
+        }
+        let mut state = self.write();
+        let mut trips = Vec::new();
+        let mut warned = Vec::new();
+        for member in &state.members {
+            let target = member.breaker.name();
+            if target == source || member.breaker.state() != CircuitState::Closed {
+                continue;
+            }
+            match self.propagation(&state, source, target) {
+                Propagation::Ignore => {}
+                Propagation::Warn => warned.push(target.to_string()),
+                Propagation::Trip => trips.push(member.breaker.clone()),
+            }
+        }
+        for breaker in warned {
+            info!("Breaker group '{}' warns '{}': '{}' opened", self.name, breaker, source);
+            state.warnings.retain(|warning| warning.breaker != breaker || warning.source != source);
+            state.warnings.push(GroupWarning { breaker, source: source.to_string(), since: event.timestamp });
+        }
+        // Trip outside the lock: the targets' observers call back into the group
+        drop(state);
+        let reason = format!("{} '{}' by breaker group '{}'", PROPAGATED_REASON, source, self.name);
+        for breaker in trips {
+            breaker.trip_because(&reason);
+        }
+    }
+
+    fn source_closed(&self, source: &str) {
+        self.write().warnings.retain(|warning| warning.source != source);
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, GroupState> {
+        self.state.read().unwrap_or_else(|p| p.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, GroupState> {
+        self.state.write().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+impl Drop for BreakerGroup {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().unwrap_or_else(|p| p.into_inner());
+        for member in &state.members {
+            member.breaker.remove_observer(member.observer);
+        }
+    }
+}
+
+impl fmt::Debug for BreakerGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.read();
+        let members: Vec<_> = state.members.iter().map(|member| (member.breaker.name().to_string(), member.breaker.state())).collect();
+        f.debug_struct("BreakerGroup")
+            .field("name", &self.name)
+            .field("default_propagation", &self.default_propagation)
+            .field("members", &members)
+            .field("warnings", &state.warnings)
+            .finish()
+    }
+}
+
+// Observer a group registers on each member, holding the group weakly
+struct GroupObserver {
+    group: Weak<BreakerGroup>,
+}
+
+impl CircuitBreakerObserver for GroupObserver {
+    fn on_state_change(&self, name: &str, event: &CircuitTransitionEvent) {
+        let Some(group) = self.group.upgrade() else {
+            return;
+        };
+        match event.to_state {
+            CircuitState::Open => group.source_opened(name, event),
+            CircuitState::Closed | CircuitState::ForcedClosed => group.source_closed(name),
+            CircuitState::HalfOpen | CircuitState::ForcedOpen => {}
+        }
+    }
+
+    fn on_operation_attempt(&self, _name: &str, _state: CircuitState) {}
+
+    fn on_operation_result(&self, _name: &str, _op_type: CircuitOperationType, _duration: Duration, _error: Option<&AklypseError>) {}
+
+    fn on_reset(&self, _name: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::CircuitBreakerConfig;
+
+    fn breaker(name: &str) -> Arc<CircuitBreaker> {
+        CircuitBreaker::new(name, CircuitBreakerConfig::default())
+    }
+
+    fn transitions(breaker: &CircuitBreaker) -> usize {
+        breaker.history().iter().filter(|entry| matches!(entry, crate::common::error::CircuitHistoryEntry::Transition(_))).count()
+    }
+
+    #[test]
+    fn test_trip_cascades_once_to_related_breakers() {
+        let group = BreakerGroup::new("db-primary", Propagation::Trip);
+        let (reads, writes, forced) = (breaker("reads"), breaker("writes"), breaker("admin"));
+        for member in [&reads, &writes, &forced] {
+            group.add(member.clone());
+        }
+        forced.force_closed();
+
+        reads.trip();
+        assert_eq!((writes.state(), forced.state()), (CircuitState::Open, CircuitState::ForcedClosed));
+        assert_eq!(transitions(&reads), 1);
+        match writes.history().last() {
+            Some(crate::common::error::CircuitHistoryEntry::Transition(event)) => {
+                assert_eq!(event.reason, "Propagated from 'reads' by breaker group 'db-primary'");
+            }
+            other => panic!("expected a transition, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rules_override_default_and_warnings_clear_on_close() {
+        let group = BreakerGroup::new("api-host", Propagation::Ignore);
+        let (search, checkout, profile) = (breaker("search"), breaker("checkout"), breaker("profile"));
+        for member in [&search, &checkout, &profile] {
+            group.add(member.clone());
+        }
+        group.set_rule("search", "checkout", Propagation::Warn);
+
+        search.trip();
+        assert_eq!((checkout.state(), profile.state()), (CircuitState::Closed, CircuitState::Closed));
+        assert!(group.is_warned("checkout"));
+        assert!(!group.is_warned("profile"));
+        assert_eq!(group.warnings()[0].source, "search");
+
+        search.reset();
+        assert!(group.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_dropping_group_detaches_observers() {
+        let member = breaker("cache");
+        let group = BreakerGroup::new("edge", Propagation::Trip);
+        group.add(member.clone());
+        assert_eq!(member.observer_count(), 1);
+        drop(group);
+        assert_eq!(member.observer_count(), 0);
+    }
+}
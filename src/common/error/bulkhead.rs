@@ -0,0 +1,533 @@
+/* src/common/error/bulkhead.rs */
+#![warn(missing_docs)]
+//! **Brief:** Bulkhead limiting concurrent executions, with a bounded wait queue.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Bulkhead Pattern]
+//!  - [Fault Tolerance]
+//!  - [Concurrency Limits]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `Bulkhead` is a counting semaphore shared by threads and tokio tasks:
+//! at most `max_concurrent` executions hold a slot at once, up to
+//! `max_queued` more callers wait for one (for at most `max_wait`), and any
+//! caller beyond that fails with `AklypseError::ResourceExhausted` before
+//! its operation runs. Waiting callers are not served in arrival order.
+//!
+//! Like `CircuitBreaker`, it reports to `BulkheadObserver`s and keeps a
+//! `BulkheadMetrics` snapshot of its slots and counters.
+
+use super::{AklypseError, ResourceExhaustedSnafu, Result, ShuttingDownSnafu};
+use arc_swap::ArcSwap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Configuration for the Bulkhead.
+#[derive(Debug, Clone)]
+pub struct BulkheadConfig {
+    /// Executions allowed to hold a slot at once.
+    pub max_concurrent: usize,
+    /// Callers allowed to wait for a slot; callers beyond it are rejected.
+    pub max_queued: usize,
+    /// Longest a caller waits for a slot before being rejected; unbounded when `None`.
+    pub max_wait: Option<Duration>,
+}
+
+impl Default for BulkheadConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 10,
+            max_queued: 0,
+            max_wait: None,
+        }
+    }
+}
+
+/// Metrics collected by the bulkhead
+#[derive(Debug, Clone, Default)]
+pub struct BulkheadMetrics {
+    /// Configured number of slots
+    pub max_concurrent: usize,
+    /// Slots currently held
+    pub in_flight: usize,
+    /// Callers currently waiting for a slot
+    pub queued: usize,
+    /// Most slots ever held at once
+    pub peak_in_flight: usize,
+    /// Calls that asked for a slot
+    pub total_requests: u64,
+    /// Calls that got a slot
+    pub admitted_requests: u64,
+    /// Calls rejected because every slot and queue place was taken
+    pub rejected_requests: u64,
+    /// Calls rejected after waiting `max_wait` in the queue
+    pub wait_timeouts: u64,
+    /// Time admitted calls spent waiting, summed
+    pub total_wait: Duration,
+}
+
+/// Why the bulkhead turned a call away
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BulkheadRejection {
+    /// Every slot and queue place was taken
+    QueueFull,
+    /// The caller waited `max_wait` without getting a slot
+    WaitTimeout,
+}
+
+impl fmt::Display for BulkheadRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Observer trait for bulkhead events.
+pub trait BulkheadObserver: Send + Sync {
+    /// Called when a call gets a slot, after waiting `waited`.
+    fn on_admitted(&self, name: &str, waited: Duration, in_flight: usize);
+    /// Called when a call is turned away.
+    fn on_rejected(&self, name: &str, reason: BulkheadRejection, in_flight: usize);
+    /// Called when a slot is given back.
+    fn on_released(&self, name: &str, in_flight: usize);
+}
+
+/// Limits how many executions run at once.
+pub struct Bulkhead {
+    name: String,
+    config: BulkheadConfig,
+    state: Mutex<BulkheadMetrics>,
+    slot_freed: Condvar,
+    #[cfg(feature = "tokio")]
+    slot_freed_async: tokio::sync::Notify,
+    observers: ArcSwap<Vec<Arc<dyn BulkheadObserver>>>,
+    shutting_down: AtomicBool,
+}
+
+/// A slot held in a bulkhead, given back when dropped
+#[must_use = "the slot is released as soon as the permit is dropped"]
+pub struct BulkheadPermit<'a> {
+    bulkhead: &'a Bulkhead,
+}
+
+impl Drop for BulkheadPermit<'_> {
+    fn drop(&mut self) {
+        self.bulkhead.release();
+    }
+}
+
+impl fmt::Debug for BulkheadPermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkheadPermit").field("bulkhead", &self.bulkhead.name).finish()
+    }
+}
+
+impl Bulkhead {
+    /// Creates a new Bulkhead instance
+    pub fn new(name: impl Into<String>, config: BulkheadConfig) -> Arc<Self> {
+        let state = BulkheadMetrics { max_concurrent: config.max_concurrent, ..BulkheadMetrics::default() };
+        Arc::new(Self {
+            name: name.into(),
+            config,
+            state: Mutex::new(state),
+            slot_freed: Condvar::new(),
+            #[cfg(feature = "tokio")]
+            slot_freed_async: tokio::sync::Notify::new(),
+            observers: ArcSwap::from_pointee(Vec::new()),
+            shutting_down: AtomicBool::new(false),
+        })
+    }
+
+    /// Name the bulkhead was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add an observer to the bulkhead
+    pub fn add_observer(&self, observer: Arc<dyn BulkheadObserver>) {
+        self.observers.rcu(|observers| {
+            let mut observers = Vec::clone(observers);
+            observers.push(observer.clone());
+            observers
+        });
+    }
+
+    /// Get the current metrics of the bulkhead
+    pub fn metrics(&self) -> BulkheadMetrics {
+        self.lock().clone()
+    }
+
+    /// Stop handing out slots for good, as part of a graceful shutdown
+    ///
+    /// Later and queued callers fail with `ShuttingDown`; permits already
+    /// held stay valid until dropped.
+    pub fn shut_down(&self) {
+        let _state = self.lock();
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.slot_freed.notify_all();
+        #[cfg(feature = "tokio")]
+        self.slot_freed_async.notify_waiters();
+    }
+
+    /// Whether `shut_down` was called
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Take a slot if one is free right now, without queueing
+    pub fn try_acquire(&self) -> Result<BulkheadPermit<'_>> {
+        self.check_accepting()?;
+        let mut state = self.lock();
+        state.total_requests += 1;
+        if state.in_flight < self.config.max_concurrent {
+            return Ok(self.admit(state, Duration::ZERO));
+        }
+        Err(self.reject(state, BulkheadRejection::QueueFull))
+    }
+
+    /// Take a slot, blocking the thread in the queue while none is free
+    pub fn acquire(&self) -> Result<BulkheadPermit<'_>> {
+        self.check_accepting()?;
+        let started = Instant::now();
+        let mut state = self.lock();
+        state.total_requests += 1;
+        if state.in_flight < self.config.max_concurrent {
+            return Ok(self.admit(state, Duration::ZERO));
+        }
+        if state.queued >= self.config.max_queued {
+            return Err(self.reject(state, BulkheadRejection::QueueFull));
+        }
+
+        state.queued += 1;
+        loop {
+            state = match self.config.max_wait {
+                Some(max_wait) => {
+                    let remaining = max_wait.saturating_sub(started.elapsed());
+                    if remaining.is_zero() {
+                        break;
+                    }
+                    self.slot_freed.wait_timeout(state, remaining).unwrap_or_else(|p| p.into_inner()).0
+                }
+                None => self.slot_freed.wait(state).unwrap_or_else(|p| p.into_inner()),
+            };
+            if self.is_shutting_down() {
+                state.queued -= 1;
+                return Err(self.shutting_down_error());
+            }
+            if state.in_flight < self.config.max_concurrent {
+                state.queued -= 1;
+                return Ok(self.admit(state, started.elapsed()));
+            }
+        }
+        state.queued -= 1;
+        Err(self.reject(state, BulkheadRejection::WaitTimeout))
+    }
+
+    /// Take a slot, suspending the task in the queue while none is free
+    ///
+    /// Dropping the returned future gives up the queue place.
+    #[cfg(feature = "tokio")]
+    pub async fn acquire_async(&self) -> Result<BulkheadPermit<'_>> {
+        self.check_accepting()?;
+        let started = Instant::now();
+        {
+            let mut state = self.lock();
+            state.total_requests += 1;
+            if state.in_flight < self.config.max_concurrent {
+                return Ok(self.admit(state, Duration::ZERO));
+            }
+            if state.queued >= self.config.max_queued {
+                return Err(self.reject(state, BulkheadRejection::QueueFull));
+            }
+            state.queued += 1;
+        }
+
+        let mut place = QueuePlace { bulkhead: self, admitted: false };
+        let wait = async {
+            loop {
+                let notified = self.slot_freed_async.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+                if self.is_shutting_down() {
+                    return false;
+                }
+                if place.try_admit(started) {
+                    return true;
+                }
+                notified.await;
+            }
+        };
+        let waited = match self.config.max_wait {
+            Some(max_wait) => tokio::time::timeout(max_wait, wait).await,
+            None => Ok(wait.await),
+        };
+        match waited {
+            Ok(true) => return Ok(BulkheadPermit { bulkhead: self }),
+            Ok(false) => return Err(self.shutting_down_error()),
+            Err(_) => {}
+        }
+        drop(place);
+        let state = self.lock();
+        Err(self.reject(state, BulkheadRejection::WaitTimeout))
+    }
+
+    /// Execute an operation in a slot of the bulkhead
+    pub fn execute<F, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        let _permit = self.acquire()?;
+        operation()
+    }
+
+    /// Execute an async operation in a slot of the bulkhead
+    #[cfg(feature = "tokio")]
+    pub async fn execute_async<F, Fut, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let _permit = self.acquire_async().await?;
+        operation().await
+    }
+
+    fn check_accepting(&self) -> Result<()> {
+        if self.is_shutting_down() {
+            return Err(self.shutting_down_error());
+        }
+        Ok(())
+    }
+
+    fn shutting_down_error(&self) -> AklypseError {
+        ShuttingDownSnafu { component: format!("bulkhead '{}'", self.name) }.build()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BulkheadMetrics> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    // Count a slot as taken; observers are told after the lock is released
+    fn take_slot(&self, state: &mut BulkheadMetrics, waited: Duration) -> usize {
+        state.in_flight += 1;
+        state.peak_in_flight = state.peak_in_flight.max(state.in_flight);
+        state.admitted_requests += 1;
+        state.total_wait += waited;
+        state.in_flight
+    }
+
+    fn admit(&self, mut state: MutexGuard<'_, BulkheadMetrics>, waited: Duration) -> BulkheadPermit<'_> {
+        let in_flight = self.take_slot(&mut state, waited);
+        drop(state);
+        for observer in self.observers.load().iter() {
+            observer.on_admitted(&self.name, waited, in_flight);
+        }
+        BulkheadPermit { bulkhead: self }
+    }
+
+    fn reject(&self, mut state: MutexGuard<'_, BulkheadMetrics>, reason: BulkheadRejection) -> AklypseError {
+        match reason {
+            BulkheadRejection::QueueFull => state.rejected_requests += 1,
+            BulkheadRejection::WaitTimeout => state.wait_timeouts += 1,
+        }
+        let in_flight = state.in_flight;
+        drop(state);
+        for observer in self.observers.load().iter() {
+            observer.on_rejected(&self.name, reason, in_flight);
+        }
+        ResourceExhaustedSnafu {
+            resource: format!("concurrent calls of bulkhead '{}'", self.name),
+            limit: self.config.max_concurrent.to_string(),
+            current: in_flight.to_string(),
+        }
+        .build()
+    }
+
+    fn release(&self) {
+        let mut state = self.lock();
+        state.in_flight = state.in_flight.saturating_sub(1);
+        let in_flight = state.in_flight;
+        drop(state);
+
+        self.slot_freed.notify_one();
+        #[cfg(feature = "tokio")]
+        self.slot_freed_async.notify_one();
+        for observer in self.observers.load().iter() {
+            observer.on_released(&self.name, in_flight);
+        }
+    }
+}
+
+impl fmt::Debug for Bulkhead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bulkhead")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+// Queue place of an async waiter, given up when the wait ends or is cancelled
+#[cfg(feature = "tokio")]
+struct QueuePlace<'a> {
+    bulkhead: &'a Bulkhead,
+    admitted: bool,
+}
+
+#[cfg(feature = "tokio")]
+impl QueuePlace<'_> {
+    fn try_admit(&mut self, started: Instant) -> bool {
+        let bulkhead = self.bulkhead;
+        let mut state = bulkhead.lock();
+        if state.in_flight >= bulkhead.config.max_concurrent {
+            return false;
+        }
+        state.queued -= 1;
+        self.admitted = true;
+        let waited = started.elapsed();
+        let in_flight = bulkhead.take_slot(&mut state, waited);
+        drop(state);
+        for observer in bulkhead.observers.load().iter() {
+            observer.on_admitted(&bulkhead.name, waited, in_flight);
+        }
+        true
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for QueuePlace<'_> {
+    fn drop(&mut self) {
+        if self.admitted {
+            return;
+        }
+        self.bulkhead.lock().queued -= 1;
+        // A wakeup this waiter consumed belongs to someone else now
+        self.bulkhead.slot_freed_async.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[derive(Default)]
+    struct CountingObserver {
+        admitted: AtomicUsize,
+        rejected: AtomicUsize,
+        released: AtomicUsize,
+    }
+
+    impl BulkheadObserver for CountingObserver {
+        fn on_admitted(&self, _name: &str, _waited: Duration, _in_flight: usize) {
+            self.admitted.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_rejected(&self, _name: &str, _reason: BulkheadRejection, _in_flight: usize) {
+            self.rejected.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_released(&self, _name: &str, _in_flight: usize) {
+            self.released.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_rejects_when_slots_and_queue_are_full() {
+        let bulkhead = Bulkhead::new("exports", BulkheadConfig { max_concurrent: 1, ..Default::default() });
+        let observer = Arc::new(CountingObserver::default());
+        bulkhead.add_observer(observer.clone());
+
+        let nested = bulkhead.execute(|| {
+            let mut ran = false;
+            let inner = bulkhead.execute(|| {
+                ran = true;
+                Ok(())
+            });
+            assert!(!ran);
+            inner
+        });
+        let error = nested.unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::ResourceExhaustion);
+        assert!(matches!(error, AklypseError::ResourceExhausted { ref limit, ref current, .. } if limit == "1" && current == "1"));
+
+        let metrics = bulkhead.metrics();
+        assert_eq!((metrics.total_requests, metrics.admitted_requests, metrics.rejected_requests), (2, 1, 1));
+        assert_eq!((metrics.in_flight, metrics.peak_in_flight), (0, 1));
+        assert_eq!(observer.admitted.load(Ordering::SeqCst), 1);
+        assert_eq!(observer.rejected.load(Ordering::SeqCst), 1);
+        assert_eq!(observer.released.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_queued_callers_wait_for_a_slot() {
+        let config = BulkheadConfig { max_concurrent: 2, max_queued: 8, max_wait: None };
+        let bulkhead = Bulkhead::new("renders", config);
+        let running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let (bulkhead, running) = (bulkhead.clone(), running.clone());
+                thread::spawn(move || {
+                    bulkhead.execute(|| {
+                        assert!(running.fetch_add(1, Ordering::SeqCst) < 2);
+                        thread::sleep(Duration::from_millis(5));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        let metrics = bulkhead.metrics();
+        assert_eq!((metrics.admitted_requests, metrics.rejected_requests, metrics.queued), (6, 0, 0));
+        assert_eq!(metrics.peak_in_flight, 2);
+    }
+
+    #[test]
+    fn test_wait_timeout_rejects() {
+        let config = BulkheadConfig { max_concurrent: 1, max_queued: 1, max_wait: Some(Duration::from_millis(5)) };
+        let bulkhead = Bulkhead::new("reports", config);
+        let _held = bulkhead.try_acquire().unwrap();
+
+        let error = bulkhead.acquire().unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::ResourceExhaustion);
+        let metrics = bulkhead.metrics();
+        assert_eq!((metrics.wait_timeouts, metrics.queued), (1, 0));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_waiters_share_slots_with_threads() {
+        let config = BulkheadConfig { max_concurrent: 1, max_queued: 4, max_wait: Some(Duration::from_secs(5)) };
+        let bulkhead = Bulkhead::new("mixed", config);
+
+        let held = bulkhead.try_acquire().unwrap();
+        let waiter = {
+            let bulkhead = bulkhead.clone();
+            tokio::spawn(async move { bulkhead.execute_async(|| async { Ok(7) }).await })
+        };
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(bulkhead.metrics().queued, 1);
+        drop(held);
+
+        assert_eq!(waiter.await.unwrap().unwrap(), 7);
+        let cancelled = tokio::time::timeout(Duration::from_millis(1), async {
+            let _held = bulkhead.acquire_async().await.unwrap();
+            bulkhead.acquire_async().await.map(|_| ())
+        })
+        .await;
+        assert!(cancelled.is_err());
+        assert_eq!((bulkhead.metrics().queued, bulkhead.metrics().in_flight), (0, 0));
+    }
+}
@@ -0,0 +1,358 @@
+/* src/common/error/bus.rs */
+#![warn(missing_docs)]
+//! **Brief:** In-process error bus broadcasting errors, reports and breaker transitions.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Event Broadcasting]
+//!  - [Subscriptions]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ErrorBus` gives an application one stream of everything going wrong in
+//! the process. Anything can publish an `AklypseError` or an `ErrorReport`;
+//! each subscriber receives the events its `BusFilter` accepts (categories,
+//! minimum severity or `tracing` level, tags) over a tokio broadcast channel.
+//! Levels come from the installed `LevelMapping`, so a subscription for
+//! warnings sees what the tracing integration logs as warnings.
+//!
+//! The bus plugs into the rest of the framework as a `ReportSink` (so a
+//! reporter, and with it the panic hook, publishes every report it fans
+//! out) and as a `CircuitBreakerObserver` (publishing state transitions and
+//! failed operations). `ErrorBus::global()` is the process-wide instance.
+//!
+//! Broadcast channels are bounded: a subscriber that falls more than the
+//! capacity behind loses the oldest events, counted by `lagged()`.
+
+use super::circuitbreaker::{CircuitBreakerObserver, CircuitOperationType, CircuitTransitionEvent};
+use super::report::ErrorReport;
+use super::reporter::ReportSink;
+use super::types::{ErrorCategory, ErrorContext, ErrorSeverity};
+use super::{AklypseError, CircuitState};
+use std::io;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError, error::TryRecvError};
+use tracing::Level;
+
+/// Events buffered per subscriber by `ErrorBus::global()`
+pub const DEFAULT_BUS_CAPACITY: usize = 1024;
+
+/// Something published on the bus
+#[derive(Debug, Clone)]
+pub enum BusEvent {
+    /// An error published directly or by a circuit breaker
+    Error(Arc<AklypseError>),
+    /// A report delivered through a reporter
+    Report(Arc<ErrorReport>),
+    /// A circuit breaker changed state
+    CircuitTransition {
+        /// Name of the circuit breaker
+        name: String,
+        /// The transition
+        event: CircuitTransitionEvent,
+    },
+}
+
+impl BusEvent {
+    /// Category of the error, `None` for circuit transitions
+    pub fn category(&self) -> Option<ErrorCategory> {
+        match self {
+            Self::Error(error) => Some(error.category()),
+            Self::Report(report) => report.category,
+            Self::CircuitTransition { .. } => None,
+        }
+    }
+
+    /// Severity of the event; a circuit opening is a warning, other transitions are informational
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            Self::Error(error) => error.severity(),
+            Self::Report(report) => report.severity,
+            Self::CircuitTransition { event, .. } if matches!(event.to_state, CircuitState::Open | CircuitState::ForcedOpen) => {
+                ErrorSeverity::Warning
+            }
+            Self::CircuitTransition { .. } => ErrorSeverity::Info,
+        }
+    }
+
+    /// `tracing` level of the event's severity under the installed `LevelMapping`
+    pub fn level(&self) -> Level {
+        self.severity().as_tracing_level()
+    }
+
+    /// Whether any rich context of the event carries `tag`
+    pub fn has_tag(&self, tag: &str) -> bool {
+        match self {
+            Self::Error(error) => contexts(error).any(|context| context.has_tag(tag)),
+            Self::Report(report) => report.context.as_ref().is_some_and(|context| context.has_tag(tag)),
+            Self::CircuitTransition { .. } => false,
+        }
+    }
+}
+
+// Rich context layers of an error, outermost first
+fn contexts(error: &AklypseError) -> impl Iterator<Item = &ErrorContext> {
+    let mut current = Some(error);
+    std::iter::from_fn(move || match current? {
+        AklypseError::WithRichContext { context, source, .. } => {
+            current = Some(source);
+            Some(context)
+        }
+        _ => {
+            current = None;
+            None
+        }
+    })
+}
+
+/// Which events a subscription receives
+///
+/// An empty filter accepts everything. Category and tag lists accept an event
+/// matching any of their entries; all configured criteria must hold.
+#[derive(Debug, Clone, Default)]
+pub struct BusFilter {
+    categories: Vec<ErrorCategory>,
+    min_severity: Option<ErrorSeverity>,
+    min_level: Option<Level>,
+    tags: Vec<String>,
+}
+
+impl BusFilter {
+    /// Filter accepting every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept errors of `category` (in addition to categories added before)
+    pub fn with_category(mut self, category: ErrorCategory) -> Self {
+        self.categories.push(category);
+        self
+    }
+
+    /// Accept only events at or above `severity`
+    pub fn with_min_severity(mut self, severity: ErrorSeverity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Accept only events whose mapped level is `level` or more severe
+    pub fn with_min_level(mut self, level: Level) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Accept errors tagged `tag` (in addition to tags added before)
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Whether `event` passes the filter
+    pub fn matches(&self, event: &BusEvent) -> bool {
+        (self.categories.is_empty() || event.category().is_some_and(|category| self.categories.contains(&category)))
+            && self.min_severity.is_none_or(|min| event.severity() >= min)
+            // `tracing` orders verbose levels above severe ones
+            && self.min_level.is_none_or(|min| event.level() <= min)
+            && (self.tags.is_empty() || self.tags.iter().any(|tag| event.has_tag(tag)))
+    }
+}
+
+/// Broadcast bus for errors, reports and circuit breaker transitions
+#[derive(Debug, Clone)]
+pub struct ErrorBus {
+    sender: broadcast::Sender<BusEvent>,
+}
+
+impl ErrorBus {
+    /// Bus buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// The process-wide bus, created with `DEFAULT_BUS_CAPACITY` on first use
+    pub fn global() -> &'static ErrorBus {
+        static GLOBAL: OnceLock<ErrorBus> = OnceLock::new();
+        GLOBAL.get_or_init(|| ErrorBus::new(DEFAULT_BUS_CAPACITY))
+    }
+
+    /// Publish an event, returning how many subscribers it reached
+    pub fn publish(&self, event: BusEvent) -> usize {
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    /// Publish an error
+    pub fn publish_error(&self, error: AklypseError) -> usize {
+        self.publish(BusEvent::Error(Arc::new(error)))
+    }
+
+    /// Publish a report
+    pub fn publish_report(&self, report: ErrorReport) -> usize {
+        self.publish(BusEvent::Report(Arc::new(report)))
+    }
+
+    /// Subscribe to every event published from now on
+    pub fn subscribe(&self) -> BusSubscription {
+        self.subscribe_with(BusFilter::new())
+    }
+
+    /// Subscribe to the events `filter` accepts
+    pub fn subscribe_with(&self, filter: BusFilter) -> BusSubscription {
+        BusSubscription { receiver: self.sender.subscribe(), filter, lagged: 0 }
+    }
+
+    /// Number of live subscriptions
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl ReportSink for ErrorBus {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        self.publish_report(ErrorReport::new(rendered));
+        Ok(())
+    }
+
+    fn publish(&self, report: &ErrorReport, _rendered: &str) -> io::Result<()> {
+        self.publish_report(report.clone());
+        Ok(())
+    }
+}
+
+impl CircuitBreakerObserver for ErrorBus {
+    fn on_state_change(&self, name: &str, event: &CircuitTransitionEvent) {
+        self.publish(BusEvent::CircuitTransition { name: name.to_string(), event: event.clone() });
+    }
+
+    fn on_operation_attempt(&self, _name: &str, _state: CircuitState) {}
+
+    fn on_operation_result(
+        &self,
+        name: &str,
+        op_type: CircuitOperationType,
+        _duration: Duration,
+        error: Option<&AklypseError>,
+    ) {
+        if op_type == CircuitOperationType::Success {
+            return;
+        }
+        if let Some(error) = error {
+            let context = ErrorContext::new(format!("Circuit breaker '{}' operation {:?}", name, op_type))
+                .with_metadata("circuit_breaker", name);
+            self.publish_error(error.clone().add_context(context));
+        }
+    }
+
+    fn on_reset(&self, _name: &str) {}
+}
+
+/// Receiving end of a bus subscription
+#[derive(Debug)]
+pub struct BusSubscription {
+    receiver: broadcast::Receiver<BusEvent>,
+    filter: BusFilter,
+    lagged: u64,
+}
+
+impl BusSubscription {
+    /// Next matching event, or `None` once every publisher is gone
+    pub async fn recv(&mut self) -> Option<BusEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => self.lagged += skipped,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Next matching event already waiting, without blocking
+    pub fn try_recv(&mut self) -> Option<BusEvent> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => {}
+                Err(TryRecvError::Lagged(skipped)) => self.lagged += skipped,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Events lost because this subscription fell behind
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+
+    /// The filter applied to this subscription
+    pub fn filter(&self) -> &BusFilter {
+        &self.filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ErrorReporter, NotFoundSnafu, SinkRegistration, ValidationSnafu};
+
+    fn validation() -> AklypseError {
+        ValidationSnafu { field: "email", message: "missing @".to_string() }.build()
+    }
+
+    #[test]
+    fn test_subscriptions_filter_events() {
+        let bus = ErrorBus::new(16);
+        let mut everything = bus.subscribe();
+        let mut validation_only = bus.subscribe_with(BusFilter::new().with_category(ErrorCategory::Validation));
+        let mut billing = bus.subscribe_with(BusFilter::new().with_tag("team:billing"));
+
+        bus.publish_error(validation());
+        bus.publish_error(validation().add_context(ErrorContext::new("Charging card").with_tag_ns("team", "billing")));
+        assert_eq!(bus.subscriber_count(), 3);
+
+        assert_eq!(std::iter::from_fn(|| everything.try_recv()).count(), 2);
+        assert_eq!(std::iter::from_fn(|| validation_only.try_recv()).count(), 2);
+        assert_eq!(std::iter::from_fn(|| billing.try_recv()).count(), 1);
+    }
+
+    #[test]
+    fn test_min_level_goes_through_level_mapping() {
+        let warning = BusEvent::Error(Arc::new(validation().add_context(ErrorContext::new("Retrying").with_severity(ErrorSeverity::Warning))));
+        let info = BusEvent::Error(Arc::new(validation().add_context(ErrorContext::new("Skipped").with_severity(ErrorSeverity::Info))));
+        let critical = BusEvent::Error(Arc::new(validation().add_context(ErrorContext::new("Lost").with_severity(ErrorSeverity::Critical))));
+        assert_eq!((warning.level(), critical.level()), (Level::WARN, Level::ERROR));
+
+        let warnings = BusFilter::new().with_min_level(Level::WARN);
+        assert!(warnings.matches(&warning) && warnings.matches(&critical) && !warnings.matches(&info));
+        let errors = BusFilter::new().with_min_level(Level::ERROR);
+        assert!(!errors.matches(&warning) && errors.matches(&critical));
+    }
+
+    #[test]
+    fn test_reporter_sink_publishes_reports_and_lag_is_counted() {
+        let bus = ErrorBus::new(2);
+        let mut subscription = bus.subscribe_with(BusFilter::new().with_min_severity(ErrorSeverity::Error));
+        let reporter = ErrorReporter::builder().add_sink(SinkRegistration::new("bus", bus.clone())).build();
+
+        for _ in 0..3 {
+            reporter.report_to_sinks(&validation()).unwrap();
+        }
+        let event = subscription.try_recv().expect("report published");
+        assert!(matches!(event, BusEvent::Report(report) if report.category == Some(ErrorCategory::Validation)));
+        assert_eq!(subscription.lagged(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_async_recv_ends_when_bus_dropped() {
+        let bus = ErrorBus::new(4);
+        let mut subscription = bus.subscribe();
+        bus.publish_error(NotFoundSnafu { resource_type: "user", identifier: "42" }.build());
+        drop(bus);
+        assert!(matches!(subscription.recv().await, Some(BusEvent::Error(_))));
+        assert!(subscription.recv().await.is_none());
+    }
+}
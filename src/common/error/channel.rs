@@ -0,0 +1,246 @@
+/* src/common/error/channel.rs */
+#![warn(missing_docs)]
+//! **Brief:** Report sink publishing structured reports into a bounded channel.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Report Sinks]
+//!  - [In-Process Consumers]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ChannelSink` hands every fanned-out `ErrorReport` to an in-process
+//! consumer (a TUI, a dashboard websocket, a test) through a bounded channel.
+//! Publishing never blocks the code that reported the error: when the
+//! consumer falls behind, reports are dropped according to the sink's
+//! `OverflowPolicy` and counted in its `ChannelSinkStats`.
+
+use super::report::ErrorReport;
+use super::reporter::ReportSink;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+
+/// What a `ChannelSink` does when its channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Drop the new report and count it as dropped
+    #[default]
+    DropNewest,
+    /// Drop the new report and fail the delivery with `io::ErrorKind::WouldBlock`,
+    /// so the registration's `SinkFailurePolicy` decides how it is surfaced
+    Reject,
+}
+
+/// Delivery counters of a `ChannelSink`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ChannelSinkStats {
+    /// Reports handed to the channel
+    pub sent: u64,
+    /// Reports dropped because the channel was full
+    pub dropped_full: u64,
+    /// Reports dropped because the receiver was gone
+    pub dropped_disconnected: u64,
+}
+
+impl ChannelSinkStats {
+    /// Total number of reports that never reached the receiver
+    pub fn dropped(&self) -> u64 {
+        self.dropped_full + self.dropped_disconnected
+    }
+}
+
+enum ChannelSender {
+    Std(SyncSender<ErrorReport>),
+    #[cfg(feature = "tokio")]
+    Tokio(tokio::sync::mpsc::Sender<ErrorReport>),
+}
+
+enum SendFailure {
+    Full,
+    Disconnected,
+}
+
+impl ChannelSender {
+    fn try_send(&self, report: ErrorReport) -> std::result::Result<(), SendFailure> {
+        match self {
+            ChannelSender::Std(sender) => sender.try_send(report).map_err(|err| match err {
+                TrySendError::Full(_) => SendFailure::Full,
+                TrySendError::Disconnected(_) => SendFailure::Disconnected,
+            }),
+            #[cfg(feature = "tokio")]
+            ChannelSender::Tokio(sender) => sender.try_send(report).map_err(|err| match err {
+                tokio::sync::mpsc::error::TrySendError::Full(_) => SendFailure::Full,
+                tokio::sync::mpsc::error::TrySendError::Closed(_) => SendFailure::Disconnected,
+            }),
+        }
+    }
+}
+
+/// Sink publishing `ErrorReport` values into a bounded channel
+///
+/// Register it with `SinkRegistration::from_arc` and keep a clone of the
+/// `Arc` to read `stats()`. The sink consumes structured reports only, so it
+/// must be driven through `ErrorReporter::report_to_sinks`; plain
+/// `write_report` calls fail with `io::ErrorKind::Unsupported`.
+pub struct ChannelSink {
+    sender: ChannelSender,
+    overflow: OverflowPolicy,
+    sent: AtomicU64,
+    dropped_full: AtomicU64,
+    dropped_disconnected: AtomicU64,
+}
+
+impl ChannelSink {
+    /// Create a sink and the blocking receiver of a channel holding up to `capacity` reports
+    ///
+    /// A capacity of 0 is raised to 1.
+    pub fn bounded(capacity: usize) -> (Self, Receiver<ErrorReport>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        (Self::with_sender(ChannelSender::Std(sender)), receiver)
+    }
+
+    /// Create a sink and the async receiver of a channel holding up to `capacity` reports
+    ///
+    /// A capacity of 0 is raised to 1.
+    #[cfg(feature = "tokio")]
+    pub fn bounded_async(capacity: usize) -> (Self, tokio::sync::mpsc::Receiver<ErrorReport>) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(capacity.max(1));
+        (Self::with_sender(ChannelSender::Tokio(sender)), receiver)
+    }
+
+    fn with_sender(sender: ChannelSender) -> Self {
+        Self {
+            sender,
+            overflow: OverflowPolicy::default(),
+            sent: AtomicU64::new(0),
+            dropped_full: AtomicU64::new(0),
+            dropped_disconnected: AtomicU64::new(0),
+        }
+    }
+
+    /// Set what happens when the channel is full
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Overflow policy of this sink
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow
+    }
+
+    /// Snapshot of the delivery counters
+    pub fn stats(&self) -> ChannelSinkStats {
+        ChannelSinkStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            dropped_full: self.dropped_full.load(Ordering::Relaxed),
+            dropped_disconnected: self.dropped_disconnected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl ReportSink for ChannelSink {
+    fn write_report(&self, _rendered: &str) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "channel sink only accepts structured reports",
+        ))
+    }
+
+    fn publish(&self, report: &ErrorReport, _rendered: &str) -> io::Result<()> {
+        match self.sender.try_send(report.clone()) {
+            Ok(()) => {
+                self.sent.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(SendFailure::Full) => {
+                self.dropped_full.fetch_add(1, Ordering::Relaxed);
+                match self.overflow {
+                    OverflowPolicy::DropNewest => Ok(()),
+                    OverflowPolicy::Reject => Err(io::Error::new(
+                        io::ErrorKind::WouldBlock,
+                        "error report channel is full",
+                    )),
+                }
+            }
+            Err(SendFailure::Disconnected) => {
+                self.dropped_disconnected.fetch_add(1, Ordering::Relaxed);
+                Err(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "error report channel receiver was dropped",
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::reporter::{ErrorReporter, SinkFailurePolicy, SinkRegistration};
+    use crate::common::error::AklypseError;
+    use std::sync::Arc;
+
+    fn not_found() -> io::Error {
+        io::Error::new(io::ErrorKind::NotFound, "settings.toml missing")
+    }
+
+    #[test]
+    fn test_channel_sink_delivers_and_drops_when_full() {
+        let (sink, receiver) = ChannelSink::bounded(2);
+        let sink = Arc::new(sink);
+        let reporter = ErrorReporter::builder()
+            .add_sink(SinkRegistration::from_arc("channel", sink.clone()))
+            .build();
+
+        let ids: Vec<_> = (0..3).map(|_| reporter.report_to_sinks(&not_found()).unwrap()).collect();
+
+        let received: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].report_id, ids[0]);
+        assert_eq!(received[0].message, "settings.toml missing");
+        assert_eq!(sink.stats(), ChannelSinkStats { sent: 2, dropped_full: 1, dropped_disconnected: 0 });
+    }
+
+    #[test]
+    fn test_channel_sink_reject_policy_surfaces_failure() {
+        let (sink, _receiver) = ChannelSink::bounded(1);
+        let reporter = ErrorReporter::builder()
+            .add_sink(
+                SinkRegistration::new("channel", sink.with_overflow_policy(OverflowPolicy::Reject))
+                    .with_failure_policy(SinkFailurePolicy::Propagate),
+            )
+            .build();
+
+        assert!(reporter.report_to_sinks(&not_found()).is_ok());
+        assert!(matches!(reporter.report_to_sinks(&not_found()), Err(AklypseError::Io { .. })));
+    }
+
+    #[test]
+    fn test_channel_sink_counts_disconnected_receiver() {
+        let (sink, receiver) = ChannelSink::bounded(4);
+        drop(receiver);
+
+        let result = sink.publish(&ErrorReport::new("lost"), "lost");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(sink.stats().dropped(), 1);
+        assert_eq!(sink.write_report("text").unwrap_err().kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_async_channel_sink() {
+        let (sink, mut receiver) = ChannelSink::bounded_async(1);
+
+        sink.publish(&ErrorReport::new("first"), "").unwrap();
+        sink.publish(&ErrorReport::new("second"), "").unwrap();
+
+        assert_eq!(receiver.try_recv().unwrap().message, "first");
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(sink.stats().dropped_full, 1);
+    }
+}
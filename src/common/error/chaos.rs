@@ -0,0 +1,210 @@
+/* src/common/error/chaos.rs */
+#![warn(missing_docs)]
+//! **Brief:** Chaos configuration injecting synthetic failures, timeouts and latency into breaker calls.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Chaos Engineering]
+//!  - [Fault Tolerance]
+//!  - [Circuit Breaker Pattern]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `ChaosConfig` set on `CircuitBreakerConfig::chaos` makes the operations
+//! run by the breaker misbehave on purpose, so a team can check that its
+//! fallbacks, retries and thresholds hold up before a real outage does it
+//! for them. Each admitted call independently may be delayed by `latency`,
+//! then fail with a synthetic `Internal` error or a synthetic `Timeout`
+//! instead of running the operation. The injected outcomes go through the
+//! breaker like real ones and can trip it. Every call path that runs an
+//! operation injects, including `try_probe`, `execute_preemptive` and each
+//! operation of a batch; `acquire` does not, as the caller's work runs
+//! outside the breaker.
+//!
+//! Unlike the `testing` fault plans, chaos is configured per breaker and can
+//! be switched on and off at runtime with `CircuitBreaker::update_config`.
+//! Injected errors carry a context with `CHAOS_METADATA_KEY` so dashboards
+//! can tell them apart. Nothing is injected unless a probability is set.
+
+use super::{AklypseError, ErrorContext, InternalSnafu, Result, TimeoutSnafu};
+use crate::common::utils::jitter::{JitterRng, SeededRng};
+use std::time::Duration;
+
+/// Metadata key holding the name of the breaker a chaos fault was injected into
+pub const CHAOS_METADATA_KEY: &str = "chaos.breaker";
+
+/// Probabilities of the faults injected into a circuit breaker's calls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    /// Chance (0.0 to 1.0) that a call fails with a synthetic `Internal` error
+    pub failure_probability: f64,
+    /// Chance (0.0 to 1.0) that a call fails with a synthetic `Timeout`
+    pub timeout_probability: f64,
+    /// Chance (0.0 to 1.0) that a call is delayed by `latency` first
+    pub latency_probability: f64,
+    /// Delay added to the calls picked by `latency_probability`
+    pub latency: Duration,
+    /// Seed of the generator picking the calls; drawn from entropy when `None`
+    pub seed: Option<u64>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            failure_probability: 0.0,
+            timeout_probability: 0.0,
+            latency_probability: 0.0,
+            latency: Duration::from_millis(100),
+            seed: None,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Configuration injecting nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail calls with a synthetic `Internal` error with `probability`
+    pub fn with_failures(mut self, probability: f64) -> Self {
+        self.failure_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fail calls with a synthetic `Timeout` with `probability`
+    pub fn with_timeouts(mut self, probability: f64) -> Self {
+        self.timeout_probability = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay calls by `latency` with `probability`
+    pub fn with_latency(mut self, probability: f64, latency: Duration) -> Self {
+        self.latency_probability = probability.clamp(0.0, 1.0);
+        self.latency = latency;
+        self
+    }
+
+    /// Seed of the generator picking the calls, to replay the same faults
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // Delay and error to inject into one call of the breaker `name`, whose
+    // synthetic timeouts report `timeout`
+    pub(crate) fn decide(&self, rng: &mut SeededRng, name: &str, timeout: Duration) -> (Option<Duration>, Option<AklypseError>) {
+        let mut hit = |probability: f64| probability > 0.0 && rng.next_f64() < probability;
+        let delay = hit(self.latency_probability).then_some(self.latency);
+        let error = if hit(self.failure_probability) {
+            Some(InternalSnafu { message: format!("Chaos failure injected into circuit breaker '{}'", name), source: None }.build())
+        } else if hit(self.timeout_probability) {
+            Some(TimeoutSnafu { operation: format!("Operation in circuit breaker '{}'", name), duration: timeout }.build())
+        } else {
+            None
+        };
+        let error = error.map(|error| {
+            error.add_context(ErrorContext::new("Injected by chaos configuration").with_metadata(CHAOS_METADATA_KEY, name.to_string()))
+        });
+        (delay, error)
+    }
+}
+
+// Sleep the thread through the delay, then fail with the error if any
+pub(crate) fn inject((delay, error): (Option<Duration>, Option<AklypseError>)) -> Result<()> {
+    if let Some(delay) = delay {
+        std::thread::sleep(delay);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+// `inject` for async callers, waiting on the tokio timer
+#[cfg(feature = "tokio")]
+pub(crate) async fn inject_async((delay, error): (Option<Duration>, Option<AklypseError>)) -> Result<()> {
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+    error.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_injected_failures_trip_the_breaker() {
+        let config = CircuitBreakerConfig { failure_threshold: 3, chaos: Some(ChaosConfig::new().with_failures(1.0)), ..Default::default() };
+        let cb = CircuitBreaker::new("orders", config);
+        let runs = AtomicUsize::new(0);
+        for _ in 0..3 {
+            let error = cb.execute(|| Ok(runs.fetch_add(1, Ordering::SeqCst))).unwrap_err();
+            let context = error.get_rich_context().unwrap();
+            assert_eq!(context.metadata.get(CHAOS_METADATA_KEY).map(String::as_str), Some("orders"));
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        // Switching chaos off at runtime lets calls through again
+        cb.reset();
+        cb.update_config(CircuitBreakerConfig { chaos: None, ..cb.config().as_ref().clone() });
+        assert_eq!(cb.execute(|| Ok(runs.fetch_add(1, Ordering::SeqCst))).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_same_seed_replays_same_faults() {
+        let chaos = ChaosConfig::new().with_timeouts(0.5).with_seed(7);
+        let pattern = || {
+            let config = CircuitBreakerConfig { failure_threshold: 100, chaos: Some(chaos.clone()), ..Default::default() };
+            let cb = CircuitBreaker::new("replay", config);
+            (0..20).map(|_| cb.execute(|| Ok(())).is_err()).collect::<Vec<_>>()
+        };
+        let first = pattern();
+        assert_eq!(first, pattern());
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+
+    #[test]
+    fn test_a_new_seed_restarts_the_faults() {
+        let chaos = ChaosConfig::new().with_timeouts(0.5).with_seed(7);
+        let config = CircuitBreakerConfig { failure_threshold: 100, chaos: Some(chaos.clone()), ..Default::default() };
+        let cb = CircuitBreaker::new("reseeded", config);
+        let pattern = || (0..20).map(|_| cb.execute(|| Ok(())).is_err()).collect::<Vec<_>>();
+        let first = pattern();
+
+        cb.update_config(CircuitBreakerConfig { chaos: Some(chaos.clone().with_seed(8)), ..cb.config().as_ref().clone() });
+        cb.update_config(CircuitBreakerConfig { chaos: Some(chaos), ..cb.config().as_ref().clone() });
+        cb.reset();
+        assert_eq!(pattern(), first);
+    }
+
+    #[test]
+    fn test_probes_and_batches_are_injected() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, chaos: Some(ChaosConfig::new().with_failures(1.0)), ..Default::default() };
+        let cb = CircuitBreaker::new("paths", config);
+        let runs = AtomicUsize::new(0);
+        let run = || Ok(runs.fetch_add(1, Ordering::SeqCst));
+        assert!(cb.execute_batch(vec![run, run]).is_err());
+        assert!(cb.execute_preemptive(|| Ok(())).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let error = cb.try_probe(run).unwrap_err();
+        assert!(error.get_rich_context().unwrap().metadata.contains_key(CHAOS_METADATA_KEY));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        // The permit path leaves the caller's work alone
+        cb.reset();
+        cb.acquire().unwrap().success();
+    }
+
+    #[test]
+    fn test_injected_latency_is_caught_by_operation_timeout() {
+        let chaos = ChaosConfig::new().with_latency(1.0, Duration::from_millis(30));
+        let config = CircuitBreakerConfig { operation_timeout: Some(Duration::from_millis(10)), chaos: Some(chaos), ..Default::default() };
+        let cb = CircuitBreaker::new("slow", config);
+        assert!(matches!(cb.execute(|| Ok(())).unwrap_err(), AklypseError::Timeout { .. }));
+        assert_eq!(cb.metrics().timeout_requests, 1);
+    }
+}
@@ -16,27 +16,52 @@
 //! from cascading failures when interacting with external services or performing
 //! operations prone to repeated errors.
 
-use super::{AklypseError, Result, CircuitBreakerOpenSnafu, TimeoutSnafu}; // Use AklypseError
-use std::collections::VecDeque;
+use super::{AklypseError, ErrorCategory, ErrorContext, Result, CircuitBreakerOpenSnafu}; // Use AklypseError
+use super::pipeline::Fallback;
+#[cfg(feature = "tokio")]
+use super::async_observer::{AsyncCircuitBreakerObserver, AsyncObserverDispatcher, DEFAULT_ASYNC_OBSERVER_CAPACITY};
+use super::reporter::ErrorReportConfig;
+#[cfg(feature = "chaos")]
+use super::chaos::{self, ChaosConfig};
+use super::outlier::{OutlierDetection, OutlierTracker, OUTLIER_KEY_METADATA_KEY};
+use crate::common::utils::clock::{Clock, SystemClock};
+use crate::common::utils::deadline::{self, Deadline};
+use crate::common::utils::jitter::{Jitter, SeededRng};
+use crate::common::utils::retry::Backoff;
+use arc_swap::ArcSwap;
+use std::any::Any;
 use std::fmt;
-use std::sync::{Arc, Mutex, RwLock};
-use std::time::{Duration, Instant, SystemTime};
-use tracing::info;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
 
 #[cfg(feature = "tokio")]
 use tokio::time;
-#[cfg(feature = "rand")]
-use rand::Rng;
 
 /// Represents the state of the circuit breaker.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum CircuitState {
     /// The circuit is closed, operations are allowed.
+    #[default]
     Closed,
     /// The circuit is open, operations are rejected immediately.
     Open,
     /// The circuit is partially open, allowing a limited number of test operations.
     HalfOpen,
+    /// Pinned open by `force_open`: every operation is rejected until `reset` or `trip`.
+    ForcedOpen,
+    /// Pinned closed by `force_closed`: every operation runs and failures never open it.
+    ForcedClosed,
+}
+
+impl CircuitState {
+    /// Whether the state was pinned by `force_open` or `force_closed`
+    pub fn is_forced(self) -> bool {
+        matches!(self, CircuitState::ForcedOpen | CircuitState::ForcedClosed)
+    }
 }
 
 impl fmt::Display for CircuitState {
@@ -48,21 +73,64 @@ impl fmt::Display for CircuitState {
 /// Type of operation outcome.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CircuitOperationType {
+    /// The operation succeeded
     Success,
+    /// The operation failed
     Failure,
-    Rejected, // Rejected by circuit breaker (e.g., when Open or HalfOpen limit reached)
-    Timeout,  // Operation itself timed out
+    /// Rejected by the circuit breaker (e.g., when Open or the HalfOpen limit is reached)
+    Rejected,
+    /// The operation itself timed out
+    Timeout,
+    /// Fallback at this index recovered from the primary's error
+    FallbackSuccess(usize),
+    /// Fallback at this index failed too
+    FallbackFailure(usize),
 }
 
+/// Metadata key holding how many fallbacks failed after the primary operation
+pub const FALLBACKS_FAILED_METADATA_KEY: &str = "circuit_breaker.fallbacks_failed";
+
+/// Async recovery tried by `CircuitBreaker::execute_with_fallback_async`
+#[cfg(feature = "tokio")]
+pub type AsyncFallback<T> = Arc<
+    dyn Fn(&AklypseError) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T>> + Send>> + Send + Sync,
+>;
+
+/// Decides whether an error counts as a failure of the breaker's operation
+pub type ErrorPredicate = Arc<dyn Fn(&AklypseError) -> bool + Send + Sync>;
+
 /// Represents an event of state transition
 #[derive(Debug, Clone)]
 pub struct CircuitTransitionEvent {
+    /// State the circuit left
     pub from_state: CircuitState,
+    /// State the circuit entered
     pub to_state: CircuitState,
+    /// When the transition happened
     pub timestamp: SystemTime,
+    /// Why the circuit changed state
     pub reason: String,
 }
 
+impl CircuitTransitionEvent {
+    /// One-line description of the transition, with the timestamp rendered per `config`
+    pub fn describe(&self, config: &ErrorReportConfig) -> String {
+        format!(
+            "{} {} -> {}: {}",
+            config.format_timestamp(self.timestamp),
+            self.from_state,
+            self.to_state,
+            self.reason
+        )
+    }
+}
+
+impl fmt::Display for CircuitTransitionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(&ErrorReportConfig::default()))
+    }
+}
+
 /// Observer trait for circuit breaker events.
 ///
 /// Implement this trait to react to state changes, operation results,
@@ -82,29 +150,121 @@ pub trait CircuitBreakerObserver: Send + Sync {
     );
     /// Called when the circuit breaker is manually reset.
     fn on_reset(&self, name: &str);
+    /// Called after `update_config` replaced the configuration.
+    fn on_config_change(&self, _name: &str, _previous: &CircuitBreakerConfig, _config: &CircuitBreakerConfig) {}
+    /// Called once the reset timeout of an Open circuit elapsed, so the next call probes it.
+    fn on_half_open_eligible(&self, _name: &str) {}
+    /// Called when `execute_keyed` ejected `key` for `duration`.
+    fn on_key_ejected(&self, _name: &str, _key: &str, _duration: Duration) {}
+    /// Events received but not handled yet, for observers that queue them.
+    fn pending_events(&self) -> u64 {
+        0
+    }
+}
+
+/// One entry of a circuit breaker's event history
+#[derive(Debug, Clone)]
+pub enum CircuitHistoryEntry {
+    /// The state changed
+    Transition(CircuitTransitionEvent),
+    /// An operation completed, was rejected or timed out
+    Outcome {
+        /// Outcome of the operation
+        op_type: CircuitOperationType,
+        /// How long the operation ran
+        duration: Duration,
+        /// When the outcome was recorded
+        timestamp: SystemTime,
+        /// Error of a failed operation
+        error: Option<Box<AklypseError>>,
+    },
+}
+
+/// Handle of an observer registered with a `CircuitBreaker`, for `remove_observer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(u64);
+
+// A registered observer, kept alive by the breaker or only referenced
+#[derive(Clone)]
+enum ObserverRef {
+    Strong(Arc<dyn CircuitBreakerObserver>),
+    Weak(Weak<dyn CircuitBreakerObserver>),
+}
+
+#[derive(Clone)]
+struct ObserverEntry {
+    id: ObserverId,
+    observer: ObserverRef,
+}
+
+impl ObserverEntry {
+    fn is_alive(&self) -> bool {
+        match &self.observer {
+            ObserverRef::Strong(_) => true,
+            ObserverRef::Weak(observer) => observer.strong_count() > 0,
+        }
+    }
 }
 
 /// Metrics collected by the circuit breaker
 #[derive(Debug, Clone, Default)]
 pub struct CircuitMetrics {
+    /// Current state of the circuit
     pub state: CircuitState,
+    /// Requests seen, run or rejected
     pub total_requests: u64,
+    /// Requests whose operation succeeded
     pub successful_requests: u64,
+    /// Requests whose operation failed, timeouts excluded
     pub failed_requests: u64,
+    /// Requests rejected without running
     pub rejected_requests: u64,
+    /// Requests cut by the operation timeout
     pub timeout_requests: u64,
+    /// Failures since the last success
     pub consecutive_failures: u32,
+    /// Successes since the last failure
     pub consecutive_successes: u32,
+    /// When the last failure was recorded
     pub last_error_timestamp: Option<SystemTime>,
+    /// When the circuit last changed state
     pub last_transition_timestamp: Option<SystemTime>,
+    /// Weighted failure rate of the sliding window, once it holds enough requests
     pub failure_rate_in_window: Option<f64>,
+    /// Rate of slow calls in the sliding window, once it holds enough requests
     pub slow_call_rate_in_window: Option<f64>,
+    /// Median latency of recent operations
+    pub latency_p50: Option<Duration>,
+    /// 95th percentile latency of recent operations
+    pub latency_p95: Option<Duration>,
+    /// 99th percentile latency of recent operations
+    pub latency_p99: Option<Duration>,
+}
+
+/// Latency distribution of the operations a circuit breaker completed
+///
+/// Estimated from a log-linear histogram: each value is within about 3% of
+/// the true percentile, at microsecond resolution.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LatencySnapshot {
+    /// Operations measured
+    pub count: u64,
+    /// Mean latency
+    pub mean: Option<Duration>,
+    /// Median latency
+    pub p50: Option<Duration>,
+    /// 95th percentile latency
+    pub p95: Option<Duration>,
+    /// 99th percentile latency
+    pub p99: Option<Duration>,
+    /// Largest latency, rounded like the percentiles
+    pub max: Option<Duration>,
 }
 
 /// Configuration for the CircuitBreaker.
 ///
 /// Defines thresholds and timeouts that control the behavior of the circuit breaker.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct CircuitBreakerConfig {
     /// The number of consecutive failures after which the circuit opens.
     pub failure_threshold: usize,
@@ -116,6 +276,16 @@ pub struct CircuitBreakerConfig {
     pub success_threshold_to_close: usize,
     /// The duration the circuit stays Open before transitioning to HalfOpen.
     pub reset_timeout: Duration,
+    /// Factor growing the reset timeout each time a failed HalfOpen probe re-opens
+    /// the circuit; 1.0 keeps it fixed. The growth is forgotten once the circuit closes.
+    pub reset_timeout_multiplier: f64,
+    /// Upper bound of the grown reset timeout, before jitter.
+    pub max_reset_timeout: Duration,
+    /// Optional jitter randomizing `reset_timeout` each time the circuit opens,
+    /// so breakers tripped together do not probe together.
+    pub reset_jitter: Option<Arc<dyn Jitter>>,
+    /// Seed of the reset jitter's random source; drawn from entropy when `None`.
+    pub reset_jitter_seed: Option<u64>,
     /// The maximum number of operations allowed to execute concurrently when in HalfOpen state.
     pub half_open_max_concurrent_operations: usize,
     /// Optional timeout for individual operations executed through the circuit breaker.
@@ -124,11 +294,30 @@ pub struct CircuitBreakerConfig {
     pub sliding_window_size: usize,
     /// An optional predicate to determine if a specific `AklypseError` should be considered a failure.
     /// If `None`, all `Err` results are considered failures.
-    pub error_predicate: Option<Arc<dyn Fn(&AklypseError) -> bool + Send + Sync>>,
+    pub error_predicate: Option<ErrorPredicate>,
+    /// Weight of a failure in the sliding window's failure rate, by error category;
+    /// unlisted categories weigh 1.0 and a weight of 0.0 does not count as a failure at all.
+    /// Timeouts enforced by the breaker use the `Timeout` weight.
+    pub failure_weights: HashMap<ErrorCategory, f64>,
     /// The size of the history window for detailed metrics (not fully implemented in this version).
     pub metrics_history_size: usize, // Currently used for result_window and slow_call_window size logic
-    /// Whether to track detailed metrics.
+    /// Whether to count requests for `metrics()`; the state machine works either way.
     pub track_metrics: bool,
+    /// Number of recent transitions and outcomes kept for `history()`; 0 keeps none.
+    pub event_history_size: usize,
+    /// Clock measuring open durations and operation latencies; a `MockClock`
+    /// (feature `test-util`) lets tests step over the reset timeout.
+    pub clock: Arc<dyn Clock>,
+    /// Synthetic failures, timeouts and latency injected into the operations the breaker runs; off when `None`.
+    #[cfg(feature = "chaos")]
+    pub chaos: Option<ChaosConfig>,
+    /// When a batch run by `execute_batch` counts as one failure.
+    pub batch_failure_policy: BatchFailurePolicy,
+    /// When `execute_keyed` ejects a failing key.
+    pub outlier_detection: OutlierDetection,
+    /// Whether a panic in an `execute` operation is resumed once recorded as a
+    /// failure; when unset, the call fails with an `Internal` "panic: ..." error.
+    pub propagate_panics: bool,
     /// Threshold for an operation to be considered a "slow call".
     pub slow_call_duration_threshold: Option<Duration>,
     /// Rate of slow calls (0.0 to 1.0) in the window that can cause the circuit to open.
@@ -143,135 +332,846 @@ impl Default for CircuitBreakerConfig {
             minimum_request_threshold_for_rate: 10,
             success_threshold_to_close: 3,
             reset_timeout: Duration::from_secs(30),
+            reset_timeout_multiplier: 1.0,
+            max_reset_timeout: Duration::from_secs(600),
+            reset_jitter: None,
+            reset_jitter_seed: None,
             half_open_max_concurrent_operations: 1,
             operation_timeout: Some(Duration::from_secs(5)),
             sliding_window_size: 100,
             error_predicate: None,
+            failure_weights: HashMap::new(),
             metrics_history_size: 100, // This could influence window sizes if not for fixed `sliding_window_size`
             track_metrics: true,
+            event_history_size: 32,
+            clock: Arc::new(SystemClock),
+            #[cfg(feature = "chaos")]
+            chaos: None,
+            batch_failure_policy: BatchFailurePolicy::AnyFailure,
+            outlier_detection: OutlierDetection::default(),
+            propagate_panics: false,
             slow_call_duration_threshold: None, // e.g., Some(Duration::from_millis(500))
             slow_call_rate_threshold: None,     // e.g., Some(0.3) for 30% slow calls
         }
     }
 }
 
-#[derive(Debug)] // Added Debug derive for InnerState
+impl fmt::Debug for CircuitBreakerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("CircuitBreakerConfig");
+        debug
+            .field("failure_threshold", &self.failure_threshold)
+            .field("failure_rate_threshold", &self.failure_rate_threshold)
+            .field("minimum_request_threshold_for_rate", &self.minimum_request_threshold_for_rate)
+            .field("success_threshold_to_close", &self.success_threshold_to_close)
+            .field("reset_timeout", &self.reset_timeout)
+            .field("reset_timeout_multiplier", &self.reset_timeout_multiplier)
+            .field("max_reset_timeout", &self.max_reset_timeout)
+            .field("reset_jitter", &self.reset_jitter)
+            .field("reset_jitter_seed", &self.reset_jitter_seed)
+            .field("half_open_max_concurrent_operations", &self.half_open_max_concurrent_operations)
+            .field("operation_timeout", &self.operation_timeout)
+            .field("sliding_window_size", &self.sliding_window_size)
+            .field("error_predicate", &self.error_predicate.is_some())
+            .field("failure_weights", &self.failure_weights)
+            .field("metrics_history_size", &self.metrics_history_size)
+            .field("track_metrics", &self.track_metrics)
+            .field("event_history_size", &self.event_history_size)
+            .field("clock", &self.clock);
+        #[cfg(feature = "chaos")]
+        debug.field("chaos", &self.chaos);
+        debug
+            .field("batch_failure_policy", &self.batch_failure_policy)
+            .field("outlier_detection", &self.outlier_detection)
+            .field("propagate_panics", &self.propagate_panics)
+            .field("slow_call_duration_threshold", &self.slow_call_duration_threshold)
+            .field("slow_call_rate_threshold", &self.slow_call_rate_threshold)
+            .finish()
+    }
+}
+
+// Bits of one `OutcomeWindow` slot; the failure weight, in 1/256ths, fills
+// the bits from `SLOT_WEIGHT_SHIFT` up
+const SLOT_FILLED: u32 = 1;
+const SLOT_SLOW: u32 = 2;
+const SLOT_WEIGHT_SHIFT: u32 = 8;
+const SLOT_WEIGHT_SCALE: f64 = 256.0;
+
+// Sliding window of the most recent outcomes, recorded without locking.
+// Each slot is swapped atomically and the running counts are adjusted by the
+// difference between the old and new slot, so they never drift from the slots.
+#[derive(Debug)]
+struct OutcomeWindow {
+    slots: Box<[AtomicU32]>,
+    cursor: AtomicUsize,
+    failures: AtomicIsize, // weighted, in 1/256ths; signed: a decrement may land before its increment
+    slow: AtomicIsize,
+}
+
+impl OutcomeWindow {
+    fn new(size: usize) -> Self {
+        Self {
+            slots: (0..size.max(1)).map(|_| AtomicU32::new(0)).collect(),
+            cursor: AtomicUsize::new(0),
+            failures: AtomicIsize::new(0),
+            slow: AtomicIsize::new(0),
+        }
+    }
+
+    // `failure_weight` is 0.0 for a success
+    fn push(&self, failure_weight: f64, slow: bool) {
+        let weight = (failure_weight * SLOT_WEIGHT_SCALE).round().clamp(0.0, (u32::MAX >> SLOT_WEIGHT_SHIFT) as f64) as u32;
+        let mut outcome = SLOT_FILLED | weight << SLOT_WEIGHT_SHIFT;
+        if slow {
+            outcome |= SLOT_SLOW;
+        }
+        let index = self.cursor.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        self.replace(index, outcome);
+    }
+
+    fn replace(&self, index: usize, outcome: u32) {
+        let previous = self.slots[index].swap(outcome, Ordering::AcqRel);
+        Self::adjust(&self.failures, (previous >> SLOT_WEIGHT_SHIFT) as isize, (outcome >> SLOT_WEIGHT_SHIFT) as isize);
+        Self::adjust(&self.slow, (previous & SLOT_SLOW != 0) as isize, (outcome & SLOT_SLOW != 0) as isize);
+    }
+
+    fn adjust(count: &AtomicIsize, was: isize, is: isize) {
+        if was != is {
+            count.fetch_add(is - was, Ordering::Relaxed);
+        }
+    }
+
+    // Window of `size` slots holding the most recent outcomes of this one
+    fn resized(&self, size: usize) -> Self {
+        let resized = Self::new(size);
+        let cursor = self.cursor.load(Ordering::Relaxed);
+        let kept = self.len().min(resized.slots.len());
+        for offset in (1..=kept).rev() {
+            let outcome = self.slots[(cursor - offset) % self.slots.len()].load(Ordering::Acquire);
+            let index = resized.cursor.fetch_add(1, Ordering::Relaxed);
+            resized.replace(index, outcome);
+        }
+        resized
+    }
+
+    fn clear(&self) {
+        self.cursor.store(0, Ordering::Relaxed);
+        for index in 0..self.slots.len() {
+            self.replace(index, 0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.cursor.load(Ordering::Relaxed).min(self.slots.len())
+    }
+
+    // Sum of the failure weights in the window
+    fn failure_count(&self) -> f64 {
+        self.failures.load(Ordering::Relaxed).max(0) as f64 / SLOT_WEIGHT_SCALE
+    }
+
+    fn slow_count(&self) -> f64 {
+        self.slow.load(Ordering::Relaxed).max(0) as f64
+    }
+
+    fn rate(&self, count: f64) -> Option<f64> {
+        match self.len() {
+            0 => None,
+            len => Some(count.min(len as f64) / len as f64),
+        }
+    }
+}
+
+/// Per-call overrides for `CircuitBreaker::execute_with_options`.
+#[derive(Debug, Clone)]
+pub struct CallOptions {
+    /// Timeout of this call, replacing `operation_timeout`; `None` keeps the breaker's.
+    pub timeout: Option<Duration>,
+    /// Run the call even while the circuit is Open or HalfOpen, though never
+    /// while it is `ForcedOpen`. Such a call ignores the state it bypassed,
+    /// so its outcome is never counted.
+    pub bypass_open: bool,
+    /// Whether the outcome counts toward the breaker's state, metrics and observers.
+    /// A call that does not count still needs a Closed circuit unless it sets
+    /// `bypass_open`, since it cannot serve as a probe.
+    pub record_outcome: bool,
+}
+
+impl CallOptions {
+    /// Options behaving exactly like `execute`
+    pub fn new() -> Self {
+        Self {
+            timeout: None,
+            bypass_open: false,
+            record_outcome: true,
+        }
+    }
+
+    /// Health probe: runs whatever the state short of `ForcedOpen` and is never counted
+    pub fn health_probe() -> Self {
+        Self::new().with_bypass_open(true).with_record_outcome(false)
+    }
+
+    /// Override the operation timeout for this call
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set whether the call runs while the circuit is not Closed
+    pub fn with_bypass_open(mut self, bypass_open: bool) -> Self {
+        self.bypass_open = bypass_open;
+        self
+    }
+
+    /// Set whether the outcome counts
+    pub fn with_record_outcome(mut self, record_outcome: bool) -> Self {
+        self.record_outcome = record_outcome;
+        self
+    }
+
+    // Whether a call made while the circuit is in `state` is counted
+    fn counts_in(&self, state: CircuitState) -> bool {
+        let closed = matches!(state, CircuitState::Closed | CircuitState::ForcedClosed);
+        self.record_outcome && (closed || !self.bypass_open)
+    }
+    
+    // Whether a call that is not counted may run while the circuit is in `state`
+    fn admits_uncounted(&self, state: CircuitState) -> bool {
+        match state {
+            CircuitState::Closed | CircuitState::ForcedClosed => true,
+            CircuitState::Open | CircuitState::HalfOpen => self.bypass_open,
+            CircuitState::ForcedOpen => false,
+        }
+    }
+}
+
+impl Default for CallOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// When a batch run by `CircuitBreaker::execute_batch` counts as one failure.
+///
+/// Only errors passing the error predicate are counted; a batch that does
+/// not count as a failure is recorded as a success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchFailurePolicy {
+    /// Any failed operation fails the batch
+    #[default]
+    AnyFailure,
+    /// More than half of the operations must fail
+    MajorityFailure,
+}
+
+impl BatchFailurePolicy {
+    // Whether `failures` out of `total` operations fail the batch
+    fn fails(self, failures: usize, total: usize) -> bool {
+        match self {
+            BatchFailurePolicy::AnyFailure => failures > 0,
+            BatchFailurePolicy::MajorityFailure => failures * 2 > total,
+        }
+    }
+}
+
+/// Metadata key holding how many operations of a batch failed, as `failed/total`
+pub const BATCH_METADATA_KEY: &str = "batch.failed";
+
+// Number of `CounterStripe`s per breaker
+const COUNTER_STRIPES: usize = 8;
+
+// One cache line of request counters. Threads are spread over the stripes,
+// so concurrent counting does not bounce a single line between cores; up to
+// `COUNTER_STRIPES` threads record without sharing one, more share stripes.
+#[derive(Debug, Default)]
+#[repr(align(64))]
+struct CounterStripe {
+    total_requests: AtomicU64,
+    successful_requests: AtomicU64,
+    failed_requests: AtomicU64,
+    rejected_requests: AtomicU64,
+    timeout_requests: AtomicU64,
+}
+
+// Stripe the current thread records into, assigned round-robin on first use
+fn stripe_index() -> usize {
+    static NEXT_STRIPE: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static STRIPE: usize = next_stripe(&NEXT_STRIPE);
+    }
+    STRIPE.with(|stripe| *stripe)
+}
+
+fn next_stripe(next: &AtomicUsize) -> usize {
+    next.fetch_add(1, Ordering::Relaxed) % COUNTER_STRIPES
+}
+
+// Request counters behind `CircuitMetrics`, only touched when `track_metrics` is set
+#[derive(Debug, Default)]
+struct MetricCounters {
+    stripes: [CounterStripe; COUNTER_STRIPES],
+    last_error_nanos: AtomicU64, // since UNIX_EPOCH, 0 until the first error
+}
+
+impl MetricCounters {
+    fn record(&self, outcome: fn(&CounterStripe) -> &AtomicU64, is_error: bool) {
+        self.record_in(stripe_index(), outcome, is_error);
+    }
+
+    fn record_in(&self, stripe: usize, outcome: fn(&CounterStripe) -> &AtomicU64, is_error: bool) {
+        let stripe = &self.stripes[stripe];
+        stripe.total_requests.fetch_add(1, Ordering::Relaxed);
+        outcome(stripe).fetch_add(1, Ordering::Relaxed);
+        if is_error {
+            let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_nanos() as u64);
+            self.last_error_nanos.store(nanos.max(1), Ordering::Relaxed);
+        }
+    }
+
+    fn sum(&self, counter: fn(&CounterStripe) -> &AtomicU64) -> u64 {
+        self.stripes.iter().map(|stripe| counter(stripe).load(Ordering::Relaxed)).sum()
+    }
+
+    fn last_error_timestamp(&self) -> Option<SystemTime> {
+        match self.last_error_nanos.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(UNIX_EPOCH + Duration::from_nanos(nanos)),
+        }
+    }
+}
+
+// Log-linear latency buckets in microseconds: exact below 2^LATENCY_SUB_BITS,
+// then 2^LATENCY_SUB_BITS buckets per power of two up to 2^LATENCY_MAX_EXP (~13 days)
+const LATENCY_SUB_BITS: u32 = 4;
+const LATENCY_SUB_BUCKETS: usize = 1 << LATENCY_SUB_BITS;
+const LATENCY_MAX_EXP: u32 = 40;
+const LATENCY_BUCKETS: usize = (LATENCY_MAX_EXP - LATENCY_SUB_BITS + 2) as usize * LATENCY_SUB_BUCKETS;
+
+// HDR-style histogram of operation latencies; recording is one relaxed
+// increment of a bucket and of the sum, without a lock but shared by all threads
+#[derive(Debug)]
+struct LatencyHistogram {
+    buckets: Box<[AtomicU64]>,
+    sum_micros: AtomicU64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: (0..LATENCY_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+}
+
+impl LatencyHistogram {
+    fn record(&self, duration: Duration) {
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        self.buckets[Self::bucket(micros)].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(micros, Ordering::Relaxed);
+    }
+
+    fn bucket(micros: u64) -> usize {
+        if micros < LATENCY_SUB_BUCKETS as u64 {
+            return micros as usize;
+        }
+        let exp = (63 - micros.leading_zeros()).min(LATENCY_MAX_EXP);
+        let micros = micros.min((1 << (LATENCY_MAX_EXP + 1)) - 1);
+        let sub = (micros >> (exp - LATENCY_SUB_BITS)) as usize & (LATENCY_SUB_BUCKETS - 1);
+        (exp - LATENCY_SUB_BITS + 1) as usize * LATENCY_SUB_BUCKETS + sub
+    }
+
+    // Midpoint of the bucket's range
+    fn value(bucket: usize) -> Duration {
+        if bucket < LATENCY_SUB_BUCKETS {
+            return Duration::from_micros(bucket as u64);
+        }
+        let shift = bucket / LATENCY_SUB_BUCKETS - 1;
+        let lower = ((LATENCY_SUB_BUCKETS + bucket % LATENCY_SUB_BUCKETS) as u64) << shift;
+        Duration::from_micros(lower + ((1u64 << shift) >> 1))
+    }
+
+    fn snapshot(&self) -> LatencySnapshot {
+        let counts: Vec<u64> = self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+        let count: u64 = counts.iter().sum();
+        if count == 0 {
+            return LatencySnapshot::default();
+        }
+        let percentile = |quantile: f64| {
+            let rank = ((quantile * count as f64).ceil() as u64).max(1);
+            let mut seen = 0;
+            counts.iter().position(|&bucket_count| {
+                seen += bucket_count;
+                seen >= rank
+            })
+            .map(Self::value)
+        };
+        LatencySnapshot {
+            count,
+            mean: Some(Duration::from_micros(self.sum_micros.load(Ordering::Relaxed) / count)),
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max: counts.iter().rposition(|&bucket_count| bucket_count > 0).map(Self::value),
+        }
+    }
+}
+
+// Bookkeeping of state transitions; only locked while the state changes or
+// when an Open circuit checks whether its reset timeout elapsed
+#[derive(Debug)]
 struct InnerState {
-    state: CircuitState,
     opened_at: Option<Instant>,
+    open_duration: Duration, // reset timeout drawn when the circuit last opened
+    consecutive_reopens: u32, // HalfOpen -> Open transitions since the circuit last closed
+    jitter_rng: SeededRng,
     half_open_entered_at: Option<Instant>,
-    consecutive_failures: usize,
-    consecutive_successes: usize,
-    half_open_concurrency_count: usize,
-    results_window: VecDeque<bool>,      // true for success, false for failure
-    slow_call_window: VecDeque<bool>, // true if call was slow
-    metrics: CircuitMetrics,
-    last_state_transition_time: Instant,
+    last_transition_timestamp: Option<SystemTime>,
 }
 
 impl Default for InnerState {
     fn default() -> Self {
         Self {
-            state: CircuitState::Closed,
             opened_at: None,
+            open_duration: Duration::ZERO,
+            consecutive_reopens: 0,
+            jitter_rng: SeededRng::default(),
             half_open_entered_at: None,
-            consecutive_failures: 0,
-            consecutive_successes: 0,
-            half_open_concurrency_count: 0,
-            results_window: VecDeque::with_capacity(100),
-            slow_call_window: VecDeque::with_capacity(100),
-            metrics: CircuitMetrics::default(),
-            last_state_transition_time: Instant::now(),
+            last_transition_timestamp: None,
+        }
+    }
+}
+
+impl CircuitState {
+    fn to_u8(self) -> u8 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::Open => 1,
+            CircuitState::HalfOpen => 2,
+            CircuitState::ForcedOpen => 3,
+            CircuitState::ForcedClosed => 4,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CircuitState::Open,
+            2 => CircuitState::HalfOpen,
+            3 => CircuitState::ForcedOpen,
+            4 => CircuitState::ForcedClosed,
+            _ => CircuitState::Closed,
+        }
+    }
+}
+
+/// A call admitted by `CircuitBreaker::acquire`, waiting for its outcome.
+#[must_use = "a permit records nothing until `success` or `failure` is called"]
+pub struct CallPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    start_time: Instant,
+    probing: bool,
+    done: bool,
+}
+
+impl CallPermit<'_> {
+    /// Record the call as successful
+    pub fn success(mut self) {
+        self.finish(None);
+    }
+
+    /// Record the call as failed with `error`, subject to the error predicate and failure weights
+    pub fn failure(mut self, error: &AklypseError) {
+        self.finish(Some(error));
+    }
+
+    // Record a call the breaker stopped waiting for at `timeout`
+    fn timed_out(mut self, timeout: Duration) {
+        self.done = true;
+        if self.probing {
+            self.breaker.release_probe();
+        }
+        self.breaker.record_timeout_outcome(self.probing, timeout);
+    }
+
+    fn finish(&mut self, error: Option<&AklypseError>) {
+        self.done = true;
+        let duration = self.breaker.now().saturating_duration_since(self.start_time);
+        if self.probing {
+            self.breaker.release_probe();
+        }
+        self.breaker.record_outcome(self.probing, duration, error);
+    }
+}
+
+impl Drop for CallPermit<'_> {
+    fn drop(&mut self) {
+        if !self.done && self.probing {
+            self.breaker.release_probe();
         }
     }
 }
 
+impl fmt::Debug for CallPermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallPermit")
+            .field("breaker", &self.breaker.name)
+            .field("probing", &self.probing)
+            .finish()
+    }
+}
+
 /// A circuit breaker implementation to prevent cascading failures.
+///
+/// Recording an outcome only touches atomics and reads the observer list,
+/// which is replaced copy-on-write, so calls in the Closed state never take a
+/// lock; the transition bookkeeping is locked only while the state changes.
 pub struct CircuitBreaker {
     name: String,
-    config: CircuitBreakerConfig,
+    config: ArcSwap<CircuitBreakerConfig>,
+    state: AtomicU8,
+    consecutive_failures: AtomicUsize,
+    consecutive_successes: AtomicUsize,
+    half_open_concurrency_count: AtomicUsize,
+    window: ArcSwap<OutcomeWindow>,
+    counters: MetricCounters,
+    latencies: LatencyHistogram,
     inner: RwLock<InnerState>,
-    observers: Mutex<Vec<Arc<dyn CircuitBreakerObserver>>>,
+    observers: ArcSwap<Vec<ObserverEntry>>,
+    next_observer_id: AtomicU64,
+    history: Mutex<VecDeque<CircuitHistoryEntry>>,
+    #[cfg(feature = "chaos")]
+    chaos_rng: Mutex<SeededRng>,
+    outliers: Mutex<OutlierTracker>,
+    shutting_down: AtomicBool,
+    cooldown: CooldownTimer,
+    // Handed to cooldown timers, which must not keep the breaker alive
+    this: Weak<CircuitBreaker>,
+}
+
+// Timer announcing the end of the reset timeout, one per breaker: each
+// opening replaces the wait of the previous one
+#[derive(Default)]
+struct CooldownTimer {
+    shared: Arc<CooldownShared>,
+    #[cfg(feature = "tokio")]
+    task: Mutex<Option<tokio::task::AbortHandle>>,
+}
+
+#[derive(Default)]
+struct CooldownShared {
+    slot: Mutex<CooldownSlot>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct CooldownSlot {
+    // Opening to look at and when to look at it
+    due: Option<(Instant, Instant)>,
+    thread: bool,
+    closed: bool,
+}
+
+impl CooldownTimer {
+    // Look at the opening at `opened_at` once `due`, on the breaker's timer thread
+    fn wait_on_thread(&self, breaker: Weak<CircuitBreaker>, name: &str, opened_at: Instant, due: Instant) {
+        let mut slot = self.shared.slot.lock().unwrap_or_else(|p| p.into_inner());
+        slot.due = Some((opened_at, due));
+        if slot.thread {
+            self.shared.wake.notify_one();
+            return;
+        }
+        let shared = self.shared.clone();
+        match std::thread::Builder::new().name(format!("circuit-breaker-{}-cooldown", name)).spawn(move || shared.run(breaker)) {
+            Ok(_) => slot.thread = true,
+            Err(error) => warn!("Circuit breaker '{}' cannot time its reset timeout: {}", name, error),
+        }
+    }
+    
+    // Replace the timer task of the previous opening
+    #[cfg(feature = "tokio")]
+    fn replace_task(&self, task: tokio::task::AbortHandle) {
+        if let Some(previous) = self.task.lock().unwrap_or_else(|p| p.into_inner()).replace(task) {
+            previous.abort();
+        }
+    }
+}
+
+impl CooldownShared {
+    // Body of the timer thread, ending once the breaker is dropped
+    fn run(&self, breaker: Weak<CircuitBreaker>) {
+        let mut slot = self.slot.lock().unwrap_or_else(|p| p.into_inner());
+        while !slot.closed {
+            let Some((opened_at, due)) = slot.due else {
+                slot = self.wake.wait(slot).unwrap_or_else(|p| p.into_inner());
+                continue;
+            };
+            let now = Instant::now();
+            if now < due {
+                slot = self.wake.wait_timeout(slot, due - now).unwrap_or_else(|p| p.into_inner()).0;
+                continue;
+            }
+            slot.due = None;
+            drop(slot);
+            let next = breaker.upgrade().and_then(|breaker| breaker.cooldown_step(opened_at));
+            slot = self.slot.lock().unwrap_or_else(|p| p.into_inner());
+            if let (Some(wait), None) = (next, slot.due) {
+                slot.due = Some((opened_at, Instant::now() + wait));
+            }
+        }
+    }
+}
+
+impl Drop for CooldownTimer {
+    fn drop(&mut self) {
+        self.shared.slot.lock().unwrap_or_else(|p| p.into_inner()).closed = true;
+        self.shared.wake.notify_one();
+        #[cfg(feature = "tokio")]
+        if let Some(task) = self.task.get_mut().unwrap_or_else(|p| p.into_inner()).take() {
+            task.abort();
+        }
+    }
 }
 
 impl CircuitBreaker {
     /// Creates a new CircuitBreaker instance
     pub fn new(name: impl Into<String>, config: CircuitBreakerConfig) -> Arc<Self> {
-        Arc::new(Self {
+        let inner = InnerState {
+            open_duration: config.reset_timeout,
+            jitter_rng: SeededRng::from_seed(config.reset_jitter_seed),
+            ..InnerState::default()
+        };
+        #[cfg(feature = "chaos")]
+        let chaos_rng = SeededRng::from_seed(config.chaos.as_ref().and_then(|chaos| chaos.seed));
+        Arc::new_cyclic(|this| Self {
             name: name.into(),
-            config,
-            inner: RwLock::new(InnerState::default()),
-            observers: Mutex::new(Vec::new()),
+            state: AtomicU8::new(CircuitState::Closed.to_u8()),
+            consecutive_failures: AtomicUsize::new(0),
+            consecutive_successes: AtomicUsize::new(0),
+            half_open_concurrency_count: AtomicUsize::new(0),
+            window: ArcSwap::from_pointee(OutcomeWindow::new(config.sliding_window_size)),
+            counters: MetricCounters::default(),
+            latencies: LatencyHistogram::default(),
+            config: ArcSwap::from_pointee(config),
+            inner: RwLock::new(inner),
+            observers: ArcSwap::from_pointee(Vec::new()),
+            next_observer_id: AtomicU64::new(0),
+            history: Mutex::new(VecDeque::new()),
+            #[cfg(feature = "chaos")]
+            chaos_rng: Mutex::new(chaos_rng),
+            outliers: Mutex::new(OutlierTracker::default()),
+            shutting_down: AtomicBool::new(false),
+            cooldown: CooldownTimer::default(),
+            this: this.clone(),
         })
     }
     
+    /// Name the circuit breaker was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    /// Configuration currently in effect
+    pub fn config(&self) -> Arc<CircuitBreakerConfig> {
+        self.config.load_full()
+    }
+    
+    /// Replace the configuration at runtime, keeping the state and counters
+    ///
+    /// Calls already running keep the timeout they started with, but read
+    /// the configuration again when recording their outcome, so it may be
+    /// weighed, judged slow or trip the circuit under the new one. A new
+    /// `sliding_window_size` keeps the most recent outcomes that fit; an
+    /// outcome recorded while the window is being replaced may be left out
+    /// of it, though never out of `metrics()`. A smaller `event_history_size`
+    /// drops the oldest entries. An Open circuit keeps the reset timeout
+    /// drawn when it opened; new thresholds apply from the next recorded
+    /// outcome. A new chaos `seed` restarts the injected faults from the
+    /// start of its sequence.
+    pub fn update_config(&self, config: CircuitBreakerConfig) {
+        let config = Arc::new(config);
+        // The transition lock keeps concurrent updates from interleaving their window resizes
+        let inner = self.inner.write().unwrap();
+        let previous = self.config.swap(config.clone());
+        if config.sliding_window_size != previous.sliding_window_size {
+            self.window.store(Arc::new(self.window.load().resized(config.sliding_window_size)));
+        }
+        #[cfg(feature = "chaos")]
+        {
+            let seed = config.chaos.as_ref().and_then(|chaos| chaos.seed);
+            if seed.is_some() && seed != previous.chaos.as_ref().and_then(|chaos| chaos.seed) {
+                *self.chaos_rng.lock().unwrap_or_else(|p| p.into_inner()) = SeededRng::from_seed(seed);
+            }
+        }
+        drop(inner);
+        
+        let mut history = self.history.lock().unwrap_or_else(|p| p.into_inner());
+        while history.len() > config.event_history_size {
+            history.pop_front();
+        }
+        drop(history);
+        
+        info!("Circuit breaker '{}' configuration updated", self.name);
+        self.for_each_observer(|observer| observer.on_config_change(&self.name, &previous, &config));
+    }
+    
     /// Add an observer to the circuit breaker
-    pub fn add_observer(&self, observer: Arc<dyn CircuitBreakerObserver>) {
-        let mut observers = self.observers.lock().unwrap();
-        observers.push(observer);
+    ///
+    /// Notifications already in progress keep using the previous list.
+    pub fn add_observer(&self, observer: Arc<dyn CircuitBreakerObserver>) -> ObserverId {
+        self.register_observer(ObserverRef::Strong(observer))
+    }
+    
+    /// Add an observer without keeping it alive
+    ///
+    /// The breaker holds only `observer`; once the observer itself is
+    /// dropped it is no longer notified and leaves the list on the next
+    /// notification, so short-lived subscribers need not unsubscribe.
+    pub fn add_weak_observer(&self, observer: Weak<dyn CircuitBreakerObserver>) -> ObserverId {
+        self.register_observer(ObserverRef::Weak(observer))
+    }
+    
+    /// Add an async observer, notified from a background task
+    ///
+    /// Events are buffered up to `DEFAULT_ASYNC_OBSERVER_CAPACITY`; spawn an
+    /// `AsyncObserverDispatcher` and pass it to `add_observer` for another
+    /// capacity or to read its delivery counters. Must be called within a
+    /// tokio runtime.
+    #[cfg(feature = "tokio")]
+    pub fn add_async_observer(&self, observer: Arc<dyn AsyncCircuitBreakerObserver>) -> ObserverId {
+        self.add_observer(AsyncObserverDispatcher::spawn(observer, DEFAULT_ASYNC_OBSERVER_CAPACITY))
+    }
+    
+    /// Remove the observer registered as `id`, returning whether it was still registered
+    pub fn remove_observer(&self, id: ObserverId) -> bool {
+        let previous = self.observers.rcu(|observers| {
+            observers.iter().filter(|entry| entry.id != id).cloned().collect::<Vec<_>>()
+        });
+        previous.iter().any(|entry| entry.id == id)
+    }
+    
+    /// Number of registered observers still alive
+    pub fn observer_count(&self) -> usize {
+        self.observers.load().iter().filter(|entry| entry.is_alive()).count()
     }
     
     /// Get the current state of the circuit breaker
     pub fn state(&self) -> CircuitState {
-        let inner = self.inner.read().unwrap();
-        inner.state
+        CircuitState::from_u8(self.state.load(Ordering::SeqCst))
     }
     
     /// Get the current metrics of the circuit breaker
+    ///
+    /// The request counters stay at zero unless `track_metrics` is set.
     pub fn metrics(&self) -> CircuitMetrics {
-        let inner = self.inner.read().unwrap();
-        inner.metrics.clone()
+        let last_transition_timestamp = self.inner.read().unwrap().last_transition_timestamp;
+        let window = self.window.load();
+        let counters = &self.counters;
+        let latency = self.latencies.snapshot();
+        CircuitMetrics {
+            state: self.state(),
+            total_requests: counters.sum(|stripe| &stripe.total_requests),
+            successful_requests: counters.sum(|stripe| &stripe.successful_requests),
+            failed_requests: counters.sum(|stripe| &stripe.failed_requests),
+            rejected_requests: counters.sum(|stripe| &stripe.rejected_requests),
+            timeout_requests: counters.sum(|stripe| &stripe.timeout_requests),
+            consecutive_failures: self.consecutive_failures.load(Ordering::SeqCst) as u32,
+            consecutive_successes: self.consecutive_successes.load(Ordering::SeqCst) as u32,
+            last_error_timestamp: counters.last_error_timestamp(),
+            last_transition_timestamp,
+            failure_rate_in_window: window.rate(window.failure_count()),
+            slow_call_rate_in_window: window.rate(window.slow_count()),
+            latency_p50: latency.p50,
+            latency_p95: latency.p95,
+            latency_p99: latency.p99,
+        }
+    }
+    
+    /// Latency distribution of the successful and failed operations so far
+    ///
+    /// Covers every operation since the breaker was created, across resets;
+    /// stays empty unless `track_metrics` is set.
+    pub fn latency_snapshot(&self) -> LatencySnapshot {
+        self.latencies.snapshot()
+    }
+    
+    /// Recent transitions and operation outcomes, oldest first
+    ///
+    /// Keeps the last `event_history_size` entries whether or not observers
+    /// are attached, to show after the fact why the circuit opened.
+    /// Transitions are always kept; an outcome recorded while another thread
+    /// holds the history is skipped rather than waited for.
+    pub fn history(&self) -> Vec<CircuitHistoryEntry> {
+        self.history.lock().unwrap_or_else(|p| p.into_inner()).iter().cloned().collect()
     }
     
     /// Trip the circuit breaker manually
     pub fn trip(&self) {
+        self.trip_because("Manual trip");
+    }
+    
+    // Open the circuit whatever its state, recording `reason` on the transition
+    pub(crate) fn trip_because(&self, reason: &str) {
         let mut inner = self.inner.write().unwrap();
-        let prev_state = inner.state;
-        inner.state = CircuitState::Open;
-        inner.opened_at = Some(Instant::now());
-        inner.consecutive_failures = self.config.failure_threshold;
-        inner.consecutive_successes = 0;
+        let prev_state = self.state();
+        self.state.store(CircuitState::Open.to_u8(), Ordering::SeqCst);
+        let opened_at = self.now();
+        inner.opened_at = Some(opened_at);
+        inner.consecutive_reopens = 0;
+        inner.open_duration = self.next_open_duration(&mut inner);
+        let open_duration = inner.open_duration;
+        self.consecutive_failures.store(self.config.load().failure_threshold, Ordering::SeqCst);
+        self.consecutive_successes.store(0, Ordering::SeqCst);
         
         let event = CircuitTransitionEvent {
             from_state: prev_state,
             to_state: CircuitState::Open,
             timestamp: SystemTime::now(),
-            reason: "Manual trip".to_string(),
+            reason: reason.to_string(),
         };
-        
-        // Update metrics
-        inner.metrics.state = CircuitState::Open;
-        inner.metrics.consecutive_failures = inner.consecutive_failures as u32;
-        inner.metrics.consecutive_successes = 0;
-        inner.metrics.last_transition_timestamp = Some(SystemTime::now());
+        inner.last_transition_timestamp = Some(event.timestamp);
         
         // Drop the lock before calling observers
         drop(inner);
         
         // Notify observers
         self.notify_state_change(&event);
+        self.schedule_cooldown(opened_at, open_duration);
+    }
+    
+    /// Pin the circuit open for a maintenance window
+    ///
+    /// Every operation, including one with `CallOptions::bypass_open`, is
+    /// rejected with `CircuitBreakerOpen` and no `retry_after`; the reset
+    /// timeout never moves it to HalfOpen. The state
+    /// stays `ForcedOpen` until `reset` or `trip`.
+    pub fn force_open(&self) {
+        self.force(CircuitState::ForcedOpen, "Forced open");
+    }
+    
+    /// Pin the circuit closed, e.g. while its upstream is known to misbehave
+    ///
+    /// Every operation runs and is counted as usual, but no failure opens the
+    /// circuit. The state stays `ForcedClosed` until `reset` or `trip`.
+    pub fn force_closed(&self) {
+        self.force(CircuitState::ForcedClosed, "Forced closed");
     }
     
     /// Reset the circuit breaker to closed state
     pub fn reset(&self) {
         let mut inner = self.inner.write().unwrap();
-        let prev_state = inner.state;
-        inner.state = CircuitState::Closed;
+        let prev_state = self.state();
+        self.state.store(CircuitState::Closed.to_u8(), Ordering::SeqCst);
         inner.opened_at = None;
         inner.half_open_entered_at = None;
-        inner.consecutive_failures = 0;
-        inner.consecutive_successes = 0;
-        inner.half_open_concurrency_count = 0;
-        
-        // Update metrics
-        inner.metrics.state = CircuitState::Closed;
-        inner.metrics.consecutive_failures = 0;
-        inner.metrics.consecutive_successes = 0;
-        inner.metrics.last_transition_timestamp = Some(SystemTime::now());
+        inner.consecutive_reopens = 0;
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+        self.consecutive_successes.store(0, Ordering::SeqCst);
+        self.half_open_concurrency_count.store(0, Ordering::SeqCst);
         
         // Clear windows
-        inner.results_window.clear();
-        inner.slow_call_window.clear();
+        self.window.load().clear();
+        self.outliers.lock().unwrap_or_else(|p| p.into_inner()).clear();
         
         let event = CircuitTransitionEvent {
             from_state: prev_state,
@@ -279,6 +1179,7 @@ impl CircuitBreaker {
             timestamp: SystemTime::now(),
             reason: "Manual reset".to_string(),
         };
+        inner.last_transition_timestamp = Some(event.timestamp);
         
         // Drop the lock before calling observers
         drop(inner);
@@ -288,58 +1189,138 @@ impl CircuitBreaker {
         self.notify_reset();
     }
     
-    /// Execute an operation through the circuit breaker
-    pub fn execute<F, Ret>(&self, operation: F) -> Result<Ret>
-    where 
-        F: FnOnce() -> Result<Ret>,
-    {
-        let start_time = Instant::now();
-        let state = self.state();
-        
-        self.notify_operation_attempt(state);
-        
-        match state {
-            CircuitState::Open => {
+    /// Stop accepting calls for good, as part of a graceful shutdown
+    ///
+    /// Every later call fails with `ShuttingDown` without running; calls
+    /// already admitted finish and are recorded as usual.
+    pub fn shut_down(&self) {
+        if !self.shutting_down.swap(true, Ordering::SeqCst) {
+            info!("Circuit breaker '{}' shutting down", self.name);
+        }
+    }
+    
+    /// Whether `shut_down` was called
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+    
+    /// HalfOpen probes currently running
+    pub fn in_flight_probes(&self) -> usize {
+        self.half_open_concurrency_count.load(Ordering::SeqCst)
+    }
+    
+    /// Events the observers received but did not handle yet
+    pub fn pending_observer_events(&self) -> u64 {
+        let mut pending = 0;
+        self.for_each_observer(|observer| pending += observer.pending_events());
+        pending
+    }
+    
+    fn check_accepting(&self) -> Result<()> {
+        if self.is_shutting_down() {
+            return super::ShuttingDownSnafu { component: format!("circuit breaker '{}'", self.name) }.fail();
+        }
+        Ok(())
+    }
+    
+    /// Time left before the Open circuit lets a probe through
+    ///
+    /// Zero once the reset timeout elapsed, even if no call has moved the
+    /// circuit to HalfOpen yet; `None` unless the state is `Open`. Observers
+    /// are told the moment it reaches zero through `on_half_open_eligible`,
+    /// unless the configured clock does not follow real time.
+    pub fn time_until_half_open(&self) -> Option<Duration> {
+        if self.state() != CircuitState::Open {
+            return None;
+        }
+        self.open_remaining()
+    }
+    
+    /// Execute an operation through the circuit breaker
+    ///
+    /// Inside a `utils::deadline` scope, the operation timeout shrinks to the
+    /// time left before the deadline, and a timeout it causes carries the
+    /// deadline in its context. It still counts as a timeout against the
+    /// breaker. Once the deadline has passed, calls fail without running.
+    ///
+    /// A panicking operation is recorded as a failure, then either resumed or
+    /// returned as an `Internal` error, depending on `propagate_panics`.
+    pub fn execute<F, Ret>(&self, operation: F) -> Result<Ret>
+    where 
+        F: FnOnce() -> Result<Ret>,
+    {
+        self.execute_with_options(operation, &CallOptions::default())
+    }
+    
+    /// Execute an operation through the circuit breaker with per-call overrides
+    pub fn execute_with_options<F, Ret>(&self, operation: F, options: &CallOptions) -> Result<Ret>
+    where 
+        F: FnOnce() -> Result<Ret>,
+    {
+        let panic = PanicSlot::default();
+        let result = self.execute_unguarded(|| self.catch_panic(operation, &panic), options);
+        self.resume_panic(panic);
+        result
+    }
+    
+    fn execute_unguarded<F, Ret>(&self, operation: F, options: &CallOptions) -> Result<Ret>
+    where 
+        F: FnOnce() -> Result<Ret>,
+    {
+        self.check_accepting()?;
+        #[cfg(feature = "testing")]
+        let operation = {
+            let fault_operation = format!("circuit_breaker.{}", self.name);
+            move || crate::common::testing::faults::inject(&fault_operation).and_then(|_| operation())
+        };
+        #[cfg(feature = "chaos")]
+        let operation = move || chaos::inject(self.chaos_decision()).and_then(|_| operation());
+        let start_time = self.now();
+        let state = self.state();
+        let (timeout, deadline) = self.call_timeout(options)?;
+        #[cfg(feature = "tracing-integration")]
+        let _span = self.call_span(state).entered();
+        
+        if !options.counts_in(state) && !options.admits_uncounted(state) {
+            return Err(self.uncounted_rejection(state));
+        }
+        if !options.counts_in(state) {
+            let result = match timeout {
+                Some(timeout) => self.execute_with_timeout(operation, timeout).0,
+                None => operation(),
+            };
+            return self.bounded_by(result, deadline);
+        }
+        
+        self.notify_operation_attempt(state);
+        
+        let result = match state {
+            CircuitState::Open => {
                 // Check if reset timeout has elapsed
-                let inner = self.inner.read().unwrap();
-                let should_transition = if let Some(opened_at) = inner.opened_at {
-                    opened_at.elapsed() >= self.config.reset_timeout
-                } else {
-                    false
-                };
-                drop(inner);
+                let remaining = self.open_remaining();
                 
-                if should_transition {
+                if remaining == Some(Duration::ZERO) {
                     self.transition_to_half_open("Reset timeout elapsed");
                     // Continue with half-open logic
-                    self.execute_half_open(operation, start_time)
+                    self.execute_half_open(operation, start_time, timeout)
                 } else {
                     // Still open, reject the operation
                     self.record_rejected();
                     Err(super::CircuitBreakerOpenSnafu {
                         name: self.name.clone(),
-                        retry_after: Some(
-                            self.config.reset_timeout
-                                .checked_sub(
-                                    self.inner
-                                        .read()
-                                        .unwrap()
-                                        .opened_at
-                                        .unwrap()
-                                        .elapsed()
-                                )
-                                .unwrap_or_default()
-                        ),
+                        retry_after: Some(remaining.unwrap_or_default()),
                     }.build())
                 }
             },
             CircuitState::HalfOpen => {
-                self.execute_half_open(operation, start_time)
+                self.execute_half_open(operation, start_time, timeout)
             },
-            CircuitState::Closed => {
-                self.execute_closed(operation, start_time)
+            CircuitState::ForcedOpen => Err(self.reject_forced_open()),
+            CircuitState::Closed | CircuitState::ForcedClosed => {
+                self.execute_closed(operation, start_time, timeout)
             }
-        }
+        };
+        self.bounded_by(result, deadline)
     }
     
     /// Execute an async operation through the circuit breaker
@@ -349,335 +1330,802 @@ impl CircuitBreaker {
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<Ret>>,
     {
-        let start_time = Instant::now();
+        self.execute_with_options_async(operation, &CallOptions::default()).await
+    }
+    
+    /// Execute an async operation through the circuit breaker with per-call overrides
+    #[cfg(feature = "tokio")]
+    pub async fn execute_with_options_async<F, Fut, Ret>(&self, operation: F, options: &CallOptions) -> Result<Ret>
+    where 
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let panic = PanicSlot::default();
+        let call = self.execute_untraced_async(|| self.catch_panic_async(operation, &panic), options);
+        #[cfg(feature = "tracing-integration")]
+        let call = tracing::Instrument::instrument(call, self.call_span(self.state()));
+        let result = call.await;
+        self.resume_panic(panic);
+        result
+    }
+    
+    #[cfg(feature = "tokio")]
+    async fn execute_untraced_async<F, Fut, Ret>(&self, operation: F, options: &CallOptions) -> Result<Ret>
+    where 
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        self.check_accepting()?;
+        #[cfg(feature = "testing")]
+        let operation = {
+            let fault_operation = format!("circuit_breaker.{}", self.name);
+            move || async move {
+                crate::common::testing::faults::inject_async(&fault_operation).await?;
+                operation().await
+            }
+        };
+        #[cfg(feature = "chaos")]
+        let operation = move || async move {
+            chaos::inject_async(self.chaos_decision()).await?;
+            operation().await
+        };
+        let start_time = self.now();
         let state = self.state();
+        let (timeout, deadline) = self.call_timeout(options)?;
+        
+        if !options.counts_in(state) && !options.admits_uncounted(state) {
+            return Err(self.uncounted_rejection(state));
+        }
+        if !options.counts_in(state) {
+            let result = match timeout {
+                Some(timeout) => self.execute_with_timeout_async(operation, timeout).await.0,
+                None => operation().await,
+            };
+            return self.bounded_by(result, deadline);
+        }
         
         self.notify_operation_attempt(state);
         
-        match state {
+        let result = match state {
             CircuitState::Open => {
                 // Check if reset timeout has elapsed
-                let inner = self.inner.read().unwrap();
-                let should_transition = if let Some(opened_at) = inner.opened_at {
-                    opened_at.elapsed() >= self.config.reset_timeout
-                } else {
-                    false
-                };
-                drop(inner);
+                let remaining = self.open_remaining();
                 
-                if should_transition {
+                if remaining == Some(Duration::ZERO) {
                     self.transition_to_half_open("Reset timeout elapsed");
                     // Continue with half-open logic
-                    self.execute_half_open_async(operation, start_time).await
+                    self.execute_half_open_async(operation, start_time, timeout).await
                 } else {
                     // Still open, reject the operation
                     self.record_rejected();
                     Err(super::CircuitBreakerOpenSnafu {
                         name: self.name.clone(),
-                        retry_after: Some(
-                            self.config.reset_timeout
-                                .checked_sub(
-                                    self.inner
-                                        .read()
-                                        .unwrap()
-                                        .opened_at
-                                        .unwrap()
-                                        .elapsed()
-                                )
-                                .unwrap_or_default()
-                        ),
+                        retry_after: Some(remaining.unwrap_or_default()),
                     }.build())
                 }
             },
             CircuitState::HalfOpen => {
-                self.execute_half_open_async(operation, start_time).await
+                self.execute_half_open_async(operation, start_time, timeout).await
             },
-            CircuitState::Closed => {
-                self.execute_closed_async(operation, start_time).await
+            CircuitState::ForcedOpen => Err(self.reject_forced_open()),
+            CircuitState::Closed | CircuitState::ForcedClosed => {
+                self.execute_closed_async(operation, start_time, timeout).await
+            }
+        };
+        self.bounded_by(result, deadline)
+    }
+    
+    /// Admit one call whose outcome is reported through the returned permit
+    ///
+    /// For work that does not fit in a closure, such as a stream consumed
+    /// across several await points: the state is checked as by `execute`,
+    /// then `CallPermit::success` or `CallPermit::failure` records the
+    /// outcome. No `operation_timeout` is enforced on the caller's work, and
+    /// no chaos is injected into it since it does not run inside the breaker.
+    /// A permit dropped without an outcome records nothing, but still frees
+    /// its HalfOpen slot.
+    pub fn acquire(&self) -> Result<CallPermit<'_>> {
+        self.check_accepting()?;
+        let start_time = self.now();
+        let state = self.state();
+        self.notify_operation_attempt(state);
+        
+        let probing = match state {
+            CircuitState::Open => {
+                let remaining = self.open_remaining();
+                
+                if remaining != Some(Duration::ZERO) {
+                    self.record_rejected();
+                    return Err(super::CircuitBreakerOpenSnafu {
+                        name: self.name.clone(),
+                        retry_after: Some(remaining.unwrap_or_default()),
+                    }.build());
+                }
+                self.transition_to_half_open("Reset timeout elapsed");
+                true
             }
+            CircuitState::HalfOpen => true,
+            CircuitState::ForcedOpen => return Err(self.reject_forced_open()),
+            CircuitState::Closed | CircuitState::ForcedClosed => false,
+        };
+        if probing {
+            self.admit_probe()?;
         }
+        Ok(CallPermit { breaker: self, start_time, probing, done: false })
     }
     
-    // Private helper methods
+    /// Execute an operation on another thread, giving up on it at the operation timeout
+    ///
+    /// `execute` can only notice a timeout once the operation returns. Here
+    /// the operation runs on tokio's blocking pool, or on a new thread outside
+    /// a runtime, and the call fails with `Timeout` as soon as
+    /// `operation_timeout`, or the active deadline, elapses; the abandoned
+    /// operation keeps running and its result is dropped. An operation that
+    /// panics fails the call with `Internal`.
+    pub fn execute_preemptive<F, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret> + Send + 'static,
+        Ret: Send + 'static,
+    {
+        #[cfg(feature = "testing")]
+        let operation = {
+            let fault_operation = format!("circuit_breaker.{}", self.name);
+            move || crate::common::testing::faults::inject(&fault_operation).and_then(|_| operation())
+        };
+        let (timeout, deadline) = self.call_timeout(&CallOptions::default())?;
+        let permit = self.acquire()?;
+        #[cfg(feature = "chaos")]
+        let operation = {
+            let decision = self.chaos_decision();
+            move || chaos::inject(decision).and_then(|_| operation())
+        };
+        let (result, timed_out) = match timeout {
+            Some(timeout) => self.execute_detached(operation, timeout),
+            None => (operation(), false),
+        };
+        match (&result, timeout) {
+            (Ok(_), _) => permit.success(),
+            // Recorded once, as a timeout rather than also as a failure
+            (Err(_), Some(timeout)) if timed_out => permit.timed_out(timeout),
+            (Err(error), _) => permit.failure(error),
+        }
+        self.bounded_by(result, deadline)
+    }
     
-    // Execute operation in Closed state
-    fn execute_closed<F, Ret>(&self, operation: F, start_time: Instant) -> Result<Ret>
+    /// Run a batch of operations as one call through the circuit breaker
+    ///
+    /// The batch is admitted or rejected as a whole, then the operations run
+    /// in order, each within the operation timeout and the active deadline.
+    /// All values are returned when every operation succeeds; otherwise the
+    /// call fails with `MultipleErrors` holding the failures in order.
+    /// `batch_failure_policy` decides whether the batch is recorded as one
+    /// failure or one success.
+    pub fn execute_batch<F, Ret>(&self, operations: Vec<F>) -> Result<Vec<Ret>>
     where
         F: FnOnce() -> Result<Ret>,
     {
-        let result = if let Some(timeout) = self.config.operation_timeout {
-            self.execute_with_timeout(operation, timeout)
+        let permit = self.acquire()?;
+        let results = operations
+            .into_iter()
+            .map(|operation| {
+                #[cfg(feature = "chaos")]
+                let operation = move || chaos::inject(self.chaos_decision()).and_then(|_| operation());
+                let (timeout, deadline) = self.call_timeout(&CallOptions::default())?;
+                let result = match timeout {
+                    Some(timeout) => self.execute_with_timeout(operation, timeout).0,
+                    None => operation(),
+                };
+                self.bounded_by(result, deadline)
+            })
+            .collect();
+        self.settle_batch(permit, results)
+    }
+    
+    /// Run a batch of async operations concurrently as one call through the circuit breaker
+    ///
+    /// Like `execute_batch`, except that the operations are started together
+    /// and polled concurrently on the current task.
+    #[cfg(feature = "tokio")]
+    pub async fn execute_batch_async<F, Fut, Ret>(&self, operations: Vec<F>) -> Result<Vec<Ret>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let (timeout, deadline) = self.call_timeout(&CallOptions::default())?;
+        let permit = self.acquire()?;
+        let calls = operations.into_iter().map(|operation| async {
+            #[cfg(feature = "chaos")]
+            let operation = move || async move {
+                chaos::inject_async(self.chaos_decision()).await?;
+                operation().await
+            };
+            let result = match timeout {
+                Some(timeout) => self.execute_with_timeout_async(operation, timeout).await.0,
+                None => operation().await,
+            };
+            self.bounded_by(result, deadline.clone())
+        });
+        let results = join_all(calls.collect()).await;
+        self.settle_batch(permit, results)
+    }
+    
+    // Record a batch through its permit and aggregate its failures
+    fn settle_batch<Ret>(&self, permit: CallPermit<'_>, results: Vec<Result<Ret>>) -> Result<Vec<Ret>> {
+        let total = results.len();
+        let mut values = Vec::with_capacity(total);
+        let mut errors = Vec::new();
+        for result in results {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => errors.push(error),
+            }
+        }
+        if errors.is_empty() {
+            permit.success();
+            return Ok(values);
+        }
+        
+        let failures = errors.iter().filter(|error| self.should_count_as_failure(error)).count();
+        let failed = format!("{}/{}", errors.len(), total);
+        let error = super::MultipleErrorsSnafu { errors }.build().add_context(
+            ErrorContext::new(format!("{} operations of a batch in circuit breaker '{}' failed", failed, self.name))
+                .with_metadata(BATCH_METADATA_KEY, failed),
+        );
+        if self.config.load().batch_failure_policy.fails(failures, total) {
+            permit.failure(&error);
         } else {
-            operation()
+            permit.success();
+        }
+        Err(error)
+    }
+    
+    /// Execute an operation against the endpoint `key` of the protected dependency
+    ///
+    /// The call goes through the breaker like `execute`, and its outcome is
+    /// also tracked for `key` alone, as configured by `outlier_detection`.
+    /// While `key` is ejected, its calls fail with `CircuitBreakerOpen`
+    /// without running; they are counted as rejected.
+    pub fn execute_keyed<F, Ret>(&self, key: &str, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        self.admit_key(key)?;
+        let result = self.execute(operation);
+        self.record_key(key, result.as_ref().err());
+        result
+    }
+    
+    /// Execute an async operation against the endpoint `key` of the protected dependency
+    #[cfg(feature = "tokio")]
+    pub async fn execute_keyed_async<F, Fut, Ret>(&self, key: &str, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        self.admit_key(key)?;
+        let result = self.execute_async(operation).await;
+        self.record_key(key, result.as_ref().err());
+        result
+    }
+    
+    /// Keys currently ejected by `execute_keyed`, sorted
+    pub fn ejected_keys(&self) -> Vec<String> {
+        self.outliers.lock().unwrap_or_else(|p| p.into_inner()).ejected(self.now())
+    }
+    
+    /// Drop what `execute_keyed` knows about `key`, lifting its ejection
+    pub fn forget_key(&self, key: &str) -> bool {
+        self.outliers.lock().unwrap_or_else(|p| p.into_inner()).forget(key)
+    }
+    
+    // Reject the call if `key` is ejected
+    fn admit_key(&self, key: &str) -> Result<()> {
+        let remaining = self.outliers.lock().unwrap_or_else(|p| p.into_inner()).ejection_remaining(key, self.now());
+        let Some(retry_after) = remaining else {
+            return Ok(());
         };
-        
-        let duration = start_time.elapsed();
-        
-        match &result {
-            Ok(_) => {
-                self.record_success(duration);
+        self.record_rejected();
+        let error: AklypseError = CircuitBreakerOpenSnafu { name: self.name.clone(), retry_after: Some(retry_after) }.build();
+        Err(error.add_context(
+            ErrorContext::new(format!("Key '{}' of circuit breaker '{}' is ejected", key, self.name))
+                .with_metadata(OUTLIER_KEY_METADATA_KEY, key.to_string()),
+        ))
+    }
+    
+    // Track the outcome of a keyed call; the breaker rejecting it says nothing about the key
+    fn record_key(&self, key: &str, error: Option<&AklypseError>) {
+        let failed = match error {
+            None => false,
+            Some(error) if error.category() == ErrorCategory::CircuitBreaker => return,
+            Some(error) => self.should_count_as_failure(error),
+        };
+        let now = self.now();
+        let config = self.config.load();
+        let ejected = self.outliers.lock().unwrap_or_else(|p| p.into_inner()).record(key, failed, now, &config.outlier_detection);
+        if let Some(duration) = ejected {
+            info!("Circuit breaker '{}' ejecting key '{}' for {:?}", self.name, key, duration);
+            self.for_each_observer(|observer| observer.on_key_ejected(&self.name, key, duration));
+        }
+    }
+    
+    /// Force a single trial call now, without waiting out the reset timeout
+    ///
+    /// An Open circuit moves to HalfOpen and runs `operation` as its probe;
+    /// the outcome is recorded like any half-open attempt, so success may
+    /// close the circuit and failure re-opens it. A HalfOpen circuit runs it
+    /// as one more probe, within `half_open_max_concurrent_operations`. A
+    /// Closed circuit has nothing to verify and fails with `StateConflict`
+    /// without running the operation.
+    pub fn try_probe<F, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        #[cfg(feature = "testing")]
+        let operation = {
+            let fault_operation = format!("circuit_breaker.{}", self.name);
+            move || crate::common::testing::faults::inject(&fault_operation).and_then(|_| operation())
+        };
+        #[cfg(feature = "chaos")]
+        let operation = move || chaos::inject(self.chaos_decision()).and_then(|_| operation());
+        self.check_accepting()?;
+        let start_time = self.now();
+        self.begin_probe()?;
+        self.execute_half_open(operation, start_time, self.config.load().operation_timeout)
+    }
+    
+    /// Force a single async trial call now, without waiting out the reset timeout
+    #[cfg(feature = "tokio")]
+    pub async fn try_probe_async<F, Fut, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        #[cfg(feature = "testing")]
+        let operation = {
+            let fault_operation = format!("circuit_breaker.{}", self.name);
+            move || async move {
+                crate::common::testing::faults::inject_async(&fault_operation).await?;
+                operation().await
             }
-            Err(e) => {
-                if self.should_count_as_failure(e) {
-                    self.record_failure(e, duration);
-                    
-                    // Check if we need to open the circuit
-                    if self.should_open_circuit() {
-                        self.transition_to_open("Failure threshold reached");
-                    }
-                } else {
-                    // Error not counted as failure for circuit breaking
-                    self.record_success(duration);
-                }
+        };
+        #[cfg(feature = "chaos")]
+        let operation = move || async move {
+            chaos::inject_async(self.chaos_decision()).await?;
+            operation().await
+        };
+        self.check_accepting()?;
+        let start_time = self.now();
+        self.begin_probe()?;
+        self.execute_half_open_async(operation, start_time, self.config.load().operation_timeout).await
+    }
+    
+    /// Execute an operation, trying `fallbacks` in order when it fails or the circuit rejects it
+    ///
+    /// Every fallback receives the primary operation's error and the first
+    /// one to succeed wins. Fallbacks run outside the breaker and leave its
+    /// state alone; observers see each as `FallbackSuccess` or
+    /// `FallbackFailure` with its index. When all of them fail, the primary
+    /// error is returned with a context counting the failed fallbacks.
+    pub fn execute_with_fallback<F, Ret>(&self, operation: F, fallbacks: &[Fallback<Ret>]) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        let error = match self.execute(operation) {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        for (index, fallback) in fallbacks.iter().enumerate() {
+            let start_time = self.now();
+            if let Some(value) = self.record_fallback(index, start_time, fallback(&error)) {
+                return Ok(value);
             }
         }
+        Err(self.fallbacks_exhausted(error, fallbacks.len()))
+    }
+    
+    /// Execute an async operation, trying `fallbacks` in order when it fails or the circuit rejects it
+    #[cfg(feature = "tokio")]
+    pub async fn execute_with_fallback_async<F, Fut, Ret>(&self, operation: F, fallbacks: &[AsyncFallback<Ret>]) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let error = match self.execute_async(operation).await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+        for (index, fallback) in fallbacks.iter().enumerate() {
+            let start_time = self.now();
+            if let Some(value) = self.record_fallback(index, start_time, fallback(&error).await) {
+                return Ok(value);
+            }
+        }
+        Err(self.fallbacks_exhausted(error, fallbacks.len()))
+    }
+    
+    // Private helper methods
+    
+    // Execute operation in Closed state
+    fn execute_closed<F, Ret>(&self, operation: F, start_time: Instant, timeout: Option<Duration>) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        let (result, timed_out) = match timeout {
+            Some(timeout) => self.execute_with_timeout(operation, timeout),
+            None => (operation(), false),
+        };
+        
+        self.record_call(false, start_time, &result, timeout.filter(|_| timed_out));
         
         result
     }
     
     // Execute operation in HalfOpen state
-    fn execute_half_open<F, Ret>(&self, operation: F, start_time: Instant) -> Result<Ret>
+    fn execute_half_open<F, Ret>(&self, operation: F, start_time: Instant, timeout: Option<Duration>) -> Result<Ret>
     where
         F: FnOnce() -> Result<Ret>,
     {
-        // Check if we can proceed with the operation
-        {
-            let mut inner = self.inner.write().unwrap();
-            if inner.half_open_concurrency_count >= self.config.half_open_max_concurrent_operations {
-                // Too many concurrent operations in half-open state
-                self.record_rejected();
-                return Err(super::CircuitBreakerOpenSnafu {
-                    name: self.name.clone(),
-                    retry_after: Some(Duration::from_millis(100)),
-                }.build());
-            }
-            
-            // Increment concurrency count
-            inner.half_open_concurrency_count += 1;
-        }
+        self.admit_probe()?;
         
         // Execute the operation
-        let result = if let Some(timeout) = self.config.operation_timeout {
-            self.execute_with_timeout(operation, timeout)
-        } else {
-            operation()
+        let (result, timed_out) = match timeout {
+            Some(timeout) => self.execute_with_timeout(operation, timeout),
+            None => (operation(), false),
         };
         
-        let duration = start_time.elapsed();
+        self.release_probe();
         
-        // Decrement concurrency count
-        {
-            let mut inner = self.inner.write().unwrap();
-            inner.half_open_concurrency_count = inner.half_open_concurrency_count
-                .saturating_sub(1);
-        }
+        self.record_call(true, start_time, &result, timeout.filter(|_| timed_out));
         
-        match &result {
-            Ok(_) => {
+        result
+    }
+    
+    // Span of one `execute` call; the outcome and duration are recorded as
+    // the call completes, and transitions it causes become events inside it
+    #[cfg(feature = "tracing-integration")]
+    fn call_span(&self, state: CircuitState) -> tracing::Span {
+        tracing::info_span!(
+            "circuit_breaker.call",
+            breaker = %self.name,
+            state = %state,
+            outcome = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        )
+    }
+    
+    // Chaos faults for the call about to run, drawn when it actually runs
+    #[cfg(feature = "chaos")]
+    fn chaos_decision(&self) -> (Option<Duration>, Option<AklypseError>) {
+        let config = self.config.load();
+        let Some(chaos) = &config.chaos else {
+            return (None, None);
+        };
+        let timeout = config.operation_timeout.unwrap_or(chaos.latency);
+        let mut rng = self.chaos_rng.lock().unwrap_or_else(|p| p.into_inner());
+        chaos.decide(&mut rng, &self.name, timeout)
+    }
+    
+    // Take one of the HalfOpen slots, rejecting the call when all are busy
+    fn admit_probe(&self) -> Result<()> {
+        let admitted = self.half_open_concurrency_count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                (count < self.config.load().half_open_max_concurrent_operations).then_some(count + 1)
+            })
+            .is_ok();
+        if !admitted {
+            // Too many concurrent operations in half-open state
+            self.record_rejected();
+            return Err(super::CircuitBreakerOpenSnafu {
+                name: self.name.clone(),
+                retry_after: Some(Duration::from_millis(100)),
+            }.build());
+        }
+        Ok(())
+    }
+    
+    fn release_probe(&self) {
+        let _ = self.half_open_concurrency_count
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| count.checked_sub(1));
+    }
+    
+    // Record the outcome of an admitted call and move the state machine;
+    // `probing` calls were admitted in HalfOpen
+    fn record_outcome(&self, probing: bool, duration: Duration, error: Option<&AklypseError>) {
+        match error {
+            None => {
                 self.record_success(duration);
                 
                 // Check if we can close the circuit
-                let close_circuit = {
-                    let inner = self.inner.read().unwrap();
-                    inner.consecutive_successes >= self.config.success_threshold_to_close
-                };
-                
-                if close_circuit {
+                if probing && self.consecutive_successes.load(Ordering::SeqCst) >= self.config.load().success_threshold_to_close {
                     self.transition_to_closed("Success threshold reached");
                 }
             }
-            Err(e) => {
-                if self.should_count_as_failure(e) {
-                    self.record_failure(e, duration);
-                    
+            Some(e) if self.should_count_as_failure(e) => {
+                self.record_failure(e, duration);
+                
+                if probing {
                     // Any failure in half-open should open the circuit again
                     self.transition_to_open("Failure in half-open state");
-                } else {
-                    // Error not counted as failure for circuit breaking
-                    self.record_success(duration);
+                } else if self.should_open_circuit() {
+                    self.transition_to_open("Failure threshold reached");
                 }
             }
+            Some(_) => {
+                // Error not counted as failure for circuit breaking
+                self.record_success(duration);
+            }
+        }
+    }
+    
+    // Record a call admitted at `start_time`; one cut by the breaker's
+    // timeout, `timed_out`, counts once, as a timeout
+    fn record_call<Ret>(&self, probing: bool, start_time: Instant, result: &Result<Ret>, timed_out: Option<Duration>) {
+        match timed_out {
+            Some(timeout) => self.record_timeout_outcome(probing, timeout),
+            None => self.record_outcome(probing, self.now().saturating_duration_since(start_time), result.as_ref().err()),
+        }
+    }
+    
+    // Like `record_outcome` for a call that timed out, recorded by `record_timeout` alone
+    fn record_timeout_outcome(&self, probing: bool, timeout: Duration) {
+        self.record_timeout(timeout);
+        if probing {
+            self.transition_to_open("Timeout in half-open state");
+        } else if self.should_open_circuit() {
+            self.transition_to_open("Failure threshold reached");
         }
-        
-        result
     }
     
     // Async versions
     
     #[cfg(feature = "tokio")]
-    async fn execute_closed_async<F, Fut, Ret>(&self, operation: F, start_time: Instant) -> Result<Ret>
+    async fn execute_closed_async<F, Fut, Ret>(&self, operation: F, start_time: Instant, timeout: Option<Duration>) -> Result<Ret>
     where
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<Ret>>,
     {
-        let result = if let Some(timeout) = self.config.operation_timeout {
-            self.execute_with_timeout_async(operation, timeout).await
-        } else {
-            operation().await
+        let (result, timed_out) = match timeout {
+            Some(timeout) => self.execute_with_timeout_async(operation, timeout).await,
+            None => (operation().await, false),
         };
         
-        let duration = start_time.elapsed();
-        
-        match &result {
-            Ok(_) => {
-                self.record_success(duration);
-            }
-            Err(e) => {
-                if self.should_count_as_failure(e) {
-                    self.record_failure(e, duration);
-                    
-                    // Check if we need to open the circuit
-                    if self.should_open_circuit() {
-                        self.transition_to_open("Failure threshold reached");
-                    }
-                } else {
-                    // Error not counted as failure for circuit breaking
-                    self.record_success(duration);
-                }
-            }
-        }
+        self.record_call(false, start_time, &result, timeout.filter(|_| timed_out));
         
         result
     }
     
     #[cfg(feature = "tokio")]
-    async fn execute_half_open_async<F, Fut, Ret>(&self, operation: F, start_time: Instant) -> Result<Ret>
+    async fn execute_half_open_async<F, Fut, Ret>(&self, operation: F, start_time: Instant, timeout: Option<Duration>) -> Result<Ret>
     where
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<Ret>>,
     {
-        // Check if we can proceed with the operation
-        {
-            let mut inner = self.inner.write().unwrap();
-            if inner.half_open_concurrency_count >= self.config.half_open_max_concurrent_operations {
-                // Too many concurrent operations in half-open state
-                self.record_rejected();
-                return Err(super::CircuitBreakerOpenSnafu {
-                    name: self.name.clone(),
-                    retry_after: Some(Duration::from_millis(100)),
-                }.build());
-            }
-            
-            // Increment concurrency count
-            inner.half_open_concurrency_count += 1;
-        }
+        self.admit_probe()?;
         
         // Execute the operation
-        let result = if let Some(timeout) = self.config.operation_timeout {
-            self.execute_with_timeout_async(operation, timeout).await
-        } else {
-            operation().await
+        let (result, timed_out) = match timeout {
+            Some(timeout) => self.execute_with_timeout_async(operation, timeout).await,
+            None => (operation().await, false),
         };
         
-        let duration = start_time.elapsed();
+        self.release_probe();
         
-        // Decrement concurrency count
-        {
-            let mut inner = self.inner.write().unwrap();
-            inner.half_open_concurrency_count = inner.half_open_concurrency_count
-                .saturating_sub(1);
-        }
-        
-        match &result {
-            Ok(_) => {
-                self.record_success(duration);
-                
-                // Check if we can close the circuit
-                let close_circuit = {
-                    let inner = self.inner.read().unwrap();
-                    inner.consecutive_successes >= self.config.success_threshold_to_close
-                };
-                
-                if close_circuit {
-                    self.transition_to_closed("Success threshold reached");
-                }
-            }
-            Err(e) => {
-                if self.should_count_as_failure(e) {
-                    self.record_failure(e, duration);
-                    
-                    // Any failure in half-open should open the circuit again
-                    self.transition_to_open("Failure in half-open state");
-                } else {
-                    // Error not counted as failure for circuit breaking
-                    self.record_success(duration);
-                }
-            }
-        }
+        self.record_call(true, start_time, &result, timeout.filter(|_| timed_out));
         
         result
     }
     
     // Timeout helpers
     
-    fn execute_with_timeout<F, Ret>(&self, operation: F, timeout: Duration) -> Result<Ret>
+    // Run `operation`, failing it with `Timeout` past `timeout`; the flag
+    // tells whether the breaker's timeout cut it, so it is recorded once
+    fn execute_with_timeout<F, Ret>(&self, operation: F, timeout: Duration) -> (Result<Ret>, bool)
     where
         F: FnOnce() -> Result<Ret>,
     {
-        // Simple timeout implementation for non-async code
-        // In a real implementation, you'd likely want to use a more sophisticated approach
-        
-        #[cfg(not(feature = "std-thread"))]
-        {
-            // Fallback implementation without threads
-            let start = Instant::now();
-            let result = operation();
-            if start.elapsed() > timeout {
-                self.record_timeout();
-                Err(super::TimeoutSnafu {
-                    operation: format!("Operation in circuit breaker '{}'", self.name),
-                    duration: timeout,
-                }.build())
-            } else {
-                result
+        // The operation may borrow from the caller's stack, so it cannot be
+        // abandoned mid-run: the deadline is checked once it returns.
+        // `execute_preemptive` returns at the deadline instead.
+        let start = self.now();
+        let result = operation();
+        if self.now().saturating_duration_since(start) > timeout {
+            (Err(self.timeout_error(timeout)), true)
+        } else {
+            (result, false)
+        }
+    }
+    
+    // Run `operation` on another thread and stop waiting for it at `timeout`;
+    // an abandoned operation runs to completion and its result is dropped.
+    // The flag tells whether the call timed out, which the caller records.
+    fn execute_detached<F, Ret>(&self, operation: F, timeout: Duration) -> (Result<Ret>, bool)
+    where
+        F: FnOnce() -> Result<Ret> + Send + 'static,
+        Ret: Send + 'static,
+    {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        let run = move || {
+            let _ = sender.send(operation());
+        };
+        // Reuse tokio's blocking pool when called from a runtime
+        #[cfg(feature = "tokio")]
+        let spawned = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                drop(handle.spawn_blocking(run));
+                Ok(())
             }
+            Err(_) => std::thread::Builder::new().name(format!("circuit-breaker-{}", self.name)).spawn(run).map(drop),
+        };
+        #[cfg(not(feature = "tokio"))]
+        let spawned = std::thread::Builder::new().name(format!("circuit-breaker-{}", self.name)).spawn(run).map(drop);
+        if let Err(error) = spawned {
+            let error = super::InternalSnafu {
+                message: format!("Could not spawn the operation of circuit breaker '{}': {}", self.name, error),
+                source: None,
+            }.build();
+            return (Err(error), false);
         }
         
-        #[cfg(feature = "std-thread")]
-        {
-            use std::thread;
-            use std::sync::mpsc;
-            
-            let (tx, rx) = mpsc::channel();
-            
-            let handle = thread::spawn(move || {
-                let result = operation();
-                let _ = tx.send(result);
-            });
-            
-            match rx.recv_timeout(timeout) {
-                Ok(result) => {
-                    // Operation completed within timeout
-                    let _ = handle.join();
-                    result
-                }
-                Err(_) => {
-                    // Operation timed out
-                    self.record_timeout();
-                    Err(super::TimeoutSnafu {
-                        operation: format!("Operation in circuit breaker '{}'", self.name),
-                        duration: timeout,
-                    }.build())
-                }
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => (result, false),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => (Err(self.timeout_error(timeout)), true),
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                let error = super::InternalSnafu {
+                    message: format!("Operation in circuit breaker '{}' panicked", self.name),
+                    source: None,
+                }.build();
+                (Err(error), false)
             }
         }
     }
     
+    // Timeout of one call: the configured one, shrunk to the time left before
+    // the active deadline, which is returned when it is the tighter bound.
+    // Fails without running anything once that deadline has passed.
+    fn call_timeout(&self, options: &CallOptions) -> Result<(Option<Duration>, Option<Deadline>)> {
+        let timeout = options.timeout.or(self.config.load().operation_timeout);
+        let Some(deadline) = deadline::current() else {
+            return Ok((timeout, None));
+        };
+        let remaining = deadline.remaining();
+        if remaining.is_zero() {
+            return Err(self.deadline_exceeded(deadline.timeout_error(), deadline));
+        }
+        match timeout {
+            Some(timeout) if timeout <= remaining => Ok((Some(timeout), None)),
+            _ => Ok((Some(remaining), Some(deadline))),
+        }
+    }
+    
+    // Record the deadline that bounded the call on the timeout it caused
+    fn bounded_by<Ret>(&self, result: Result<Ret>, deadline: Option<Deadline>) -> Result<Ret> {
+        match (result, deadline) {
+            (Err(error @ AklypseError::Timeout { .. }), Some(deadline)) => Err(self.deadline_exceeded(error, deadline)),
+            (result, _) => result,
+        }
+    }
+    
+    fn deadline_exceeded(&self, error: AklypseError, deadline: Deadline) -> AklypseError {
+        let message = format!("Deadline of '{}' cut short circuit breaker '{}'", deadline.operation(), self.name);
+        error.add_context(ErrorContext::new(message).with_deadline(deadline))
+    }
+    
+    // Run `operation`, turning a panic into an `Internal` error and keeping
+    // its payload in `slot` until the call is recorded
+    fn catch_panic<F, Ret>(&self, operation: F, slot: &PanicSlot) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        std::panic::catch_unwind(AssertUnwindSafe(operation)).unwrap_or_else(|payload| Err(self.caught_panic(payload, slot)))
+    }
+    
+    #[cfg(feature = "tokio")]
+    async fn catch_panic_async<F, Fut, Ret>(&self, operation: F, slot: &PanicSlot) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let caught = match std::panic::catch_unwind(AssertUnwindSafe(operation)) {
+            Ok(future) => CatchUnwind(Box::pin(future)).await,
+            Err(payload) => Err(payload),
+        };
+        caught.unwrap_or_else(|payload| Err(self.caught_panic(payload, slot)))
+    }
+    
+    fn caught_panic(&self, payload: Box<dyn Any + Send>, slot: &PanicSlot) -> AklypseError {
+        let error: AklypseError = super::InternalSnafu { message: format!("panic: {}", panic_message(payload.as_ref())), source: None }.build();
+        *slot.lock().unwrap_or_else(|p| p.into_inner()) = Some(payload);
+        error.add_context(ErrorContext::new(format!("Operation in circuit breaker '{}' panicked", self.name)))
+    }
+    
+    // Resume a caught panic once its call was recorded, if `propagate_panics` is set
+    fn resume_panic(&self, slot: PanicSlot) {
+        let payload = slot.into_inner().unwrap_or_else(|p| p.into_inner());
+        if let Some(payload) = payload.filter(|_| self.config.load().propagate_panics) {
+            std::panic::resume_unwind(payload);
+        }
+    }
+    
+    fn timeout_error(&self, timeout: Duration) -> AklypseError {
+        super::TimeoutSnafu {
+            operation: format!("Operation in circuit breaker '{}'", self.name),
+            duration: timeout,
+        }.build()
+    }
+    
     #[cfg(feature = "tokio")]
-    async fn execute_with_timeout_async<F, Fut, Ret>(&self, operation: F, timeout: Duration) -> Result<Ret>
+    async fn execute_with_timeout_async<F, Fut, Ret>(&self, operation: F, timeout: Duration) -> (Result<Ret>, bool)
     where
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<Ret>>,
     {
         match time::timeout(timeout, operation()).await {
-            Ok(result) => result,
-            Err(_) => {
-                self.record_timeout();
-                Err(super::TimeoutSnafu {
-                    operation: format!("Operation in circuit breaker '{}'", self.name),
-                    duration: timeout,
-                }.build())
-            }
+            Ok(result) => (result, false),
+            Err(_) => (Err(self.timeout_error(timeout)), true),
+        }
+    }
+    
+    // Time left of the reset timeout, or None if the circuit never opened
+    fn open_remaining(&self) -> Option<Duration> {
+        let inner = self.inner.read().unwrap();
+        inner.opened_at.map(|opened_at| inner.open_duration.saturating_sub(self.now().saturating_duration_since(opened_at)))
+    }
+    
+    // Wait out the reset timeout of the opening at `opened_at` on the
+    // breaker's timer, then tell the observers registered by then that a
+    // probe may go through. A clock not following real time cannot be waited on.
+    fn schedule_cooldown(&self, opened_at: Instant, open_duration: Duration) {
+        if self.observers.load().is_empty() || !self.config.load().clock.is_real_time() {
+            return;
+        }
+        #[cfg(feature = "tokio")]
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let this = self.this.clone();
+            let task = handle.spawn(async move {
+                let mut delay = Some(open_duration);
+                while let Some(wait) = delay {
+                    time::sleep(wait).await;
+                    delay = this.upgrade().and_then(|breaker| breaker.cooldown_step(opened_at));
+                }
+            });
+            self.cooldown.replace_task(task.abort_handle());
+            return;
+        }
+        self.cooldown.wait_on_thread(self.this.clone(), &self.name, opened_at, Instant::now() + open_duration);
+    }
+    
+    // Time still to wait for the opening at `opened_at`, or None once
+    // observers were notified or the circuit moved on
+    fn cooldown_step(&self, opened_at: Instant) -> Option<Duration> {
+        if self.state() != CircuitState::Open || self.inner.read().unwrap().opened_at != Some(opened_at) {
+            return None;
+        }
+        let remaining = self.open_remaining().unwrap_or_default();
+        if !remaining.is_zero() {
+            return Some(remaining);
+        }
+        self.for_each_observer(|observer| observer.on_half_open_eligible(&self.name));
+        None
+    }
+    
+    // Current time of the configured clock
+    fn now(&self) -> Instant {
+        self.config.load().clock.now()
+    }
+
+    // Reset timeout for the circuit opening now: grown per consecutive
+    // re-open, capped, then jittered when configured
+    fn next_open_duration(&self, inner: &mut InnerState) -> Duration {
+        let config = self.config.load();
+        let base = Backoff::Exponential {
+            initial: config.reset_timeout,
+            multiplier: config.reset_timeout_multiplier,
+            max: config.max_reset_timeout.max(config.reset_timeout),
+        }
+        .delay(inner.consecutive_reopens.saturating_add(1));
+        match &config.reset_jitter {
+            Some(jitter) => jitter.apply(base, inner.open_duration, &mut inner.jitter_rng),
+            None => base,
         }
     }
     
@@ -685,10 +2133,21 @@ impl CircuitBreaker {
     
     fn transition_to_open(&self, reason: &str) {
         let mut inner = self.inner.write().unwrap();
-        let prev_state = inner.state;
-        inner.state = CircuitState::Open;
-        inner.opened_at = Some(Instant::now());
-        inner.consecutive_successes = 0;
+        let prev_state = self.state();
+        // Pinned states are left only through the manual controls
+        if prev_state.is_forced() {
+            return;
+        }
+        self.state.store(CircuitState::Open.to_u8(), Ordering::SeqCst);
+        let opened_at = self.now();
+        inner.opened_at = Some(opened_at);
+        inner.consecutive_reopens = match prev_state {
+            CircuitState::HalfOpen => inner.consecutive_reopens.saturating_add(1),
+            _ => 0,
+        };
+        inner.open_duration = self.next_open_duration(&mut inner);
+        let open_duration = inner.open_duration;
+        self.consecutive_successes.store(0, Ordering::SeqCst);
         
         let event = CircuitTransitionEvent {
             from_state: prev_state,
@@ -696,25 +2155,27 @@ impl CircuitBreaker {
             timestamp: SystemTime::now(),
             reason: reason.to_string(),
         };
-        
-        // Update metrics
-        inner.metrics.state = CircuitState::Open;
-        inner.metrics.last_transition_timestamp = Some(SystemTime::now());
+        inner.last_transition_timestamp = Some(event.timestamp);
         
         // Drop the lock before calling observers
         drop(inner);
         
         info!("Circuit breaker '{}' transitioning to Open: {}", self.name, reason);
         self.notify_state_change(&event);
+        self.schedule_cooldown(opened_at, open_duration);
     }
     
     fn transition_to_half_open(&self, reason: &str) {
         let mut inner = self.inner.write().unwrap();
-        let prev_state = inner.state;
-        inner.state = CircuitState::HalfOpen;
-        inner.half_open_entered_at = Some(Instant::now());
-        inner.consecutive_successes = 0;
-        inner.half_open_concurrency_count = 0;
+        let prev_state = self.state();
+        // Pinned states are left only through the manual controls
+        if prev_state.is_forced() {
+            return;
+        }
+        self.state.store(CircuitState::HalfOpen.to_u8(), Ordering::SeqCst);
+        inner.half_open_entered_at = Some(self.now());
+        self.consecutive_successes.store(0, Ordering::SeqCst);
+        self.half_open_concurrency_count.store(0, Ordering::SeqCst);
         
         let event = CircuitTransitionEvent {
             from_state: prev_state,
@@ -722,10 +2183,7 @@ impl CircuitBreaker {
             timestamp: SystemTime::now(),
             reason: reason.to_string(),
         };
-        
-        // Update metrics
-        inner.metrics.state = CircuitState::HalfOpen;
-        inner.metrics.last_transition_timestamp = Some(SystemTime::now());
+        inner.last_transition_timestamp = Some(event.timestamp);
         
         // Drop the lock before calling observers
         drop(inner);
@@ -736,11 +2194,16 @@ impl CircuitBreaker {
     
     fn transition_to_closed(&self, reason: &str) {
         let mut inner = self.inner.write().unwrap();
-        let prev_state = inner.state;
-        inner.state = CircuitState::Closed;
+        let prev_state = self.state();
+        // Pinned states are left only through the manual controls
+        if prev_state.is_forced() {
+            return;
+        }
+        self.state.store(CircuitState::Closed.to_u8(), Ordering::SeqCst);
         inner.opened_at = None;
         inner.half_open_entered_at = None;
-        inner.consecutive_failures = 0;
+        inner.consecutive_reopens = 0;
+        self.consecutive_failures.store(0, Ordering::SeqCst);
         
         let event = CircuitTransitionEvent {
             from_state: prev_state,
@@ -748,10 +2211,7 @@ impl CircuitBreaker {
             timestamp: SystemTime::now(),
             reason: reason.to_string(),
         };
-        
-        // Update metrics
-        inner.metrics.state = CircuitState::Closed;
-        inner.metrics.last_transition_timestamp = Some(SystemTime::now());
+        inner.last_transition_timestamp = Some(event.timestamp);
         
         // Drop the lock before calling observers
         drop(inner);
@@ -760,42 +2220,24 @@ impl CircuitBreaker {
         self.notify_state_change(&event);
     }
     
-    // Result recording helpers
+    // Result recording helpers. They take no lock unless the event history is
+    // kept (and then only try to), with notification skipped entirely when
+    // nobody observes. They are not free of contention: the window cursor and
+    // counts, the consecutive counters and the latency histogram are atomics
+    // every thread writes; only the request counters are striped.
     
     fn record_success(&self, duration: Duration) {
-        let mut inner = self.inner.write().unwrap();
-        inner.consecutive_successes += 1;
-        inner.consecutive_failures = 0;
-        
-        // Update sliding window
-        if inner.results_window.len() >= self.config.sliding_window_size {
-            inner.results_window.pop_front();
+        self.consecutive_successes.fetch_add(1, Ordering::SeqCst);
+        // Only write when there is something to clear, keeping the line shared on the hot path
+        if self.consecutive_failures.load(Ordering::SeqCst) != 0 {
+            self.consecutive_failures.store(0, Ordering::SeqCst);
         }
-        inner.results_window.push_back(true);
+        self.window.load().push(0.0, self.is_slow(duration));
         
-        // Check if the call was slow
-        let was_slow = if let Some(threshold) = self.config.slow_call_duration_threshold {
-            duration >= threshold
-        } else {
-            false
-        };
-        
-        // Update slow call window
-        if inner.slow_call_window.len() >= self.config.sliding_window_size {
-            inner.slow_call_window.pop_front();
+        if self.config.load().track_metrics {
+            self.counters.record(|stripe| &stripe.successful_requests, false);
+            self.latencies.record(duration);
         }
-        inner.slow_call_window.push_back(was_slow);
-        
-        // Update metrics
-        inner.metrics.total_requests += 1;
-        inner.metrics.successful_requests += 1;
-        inner.metrics.consecutive_successes = inner.consecutive_successes as u32;
-        inner.metrics.consecutive_failures = 0;
-        
-        // Calculate rates
-        self.update_rates(&mut inner);
-        
-        drop(inner);
         
         self.notify_operation_result(
             CircuitOperationType::Success,
@@ -805,54 +2247,26 @@ impl CircuitBreaker {
     }
     
     fn record_failure(&self, error: &AklypseError, duration: Duration) {
-        let mut inner = self.inner.write().unwrap();
-        inner.consecutive_failures += 1;
-        inner.consecutive_successes = 0;
+        self.consecutive_failures.fetch_add(1, Ordering::SeqCst);
+        self.consecutive_successes.store(0, Ordering::SeqCst);
+        self.window.load().push(self.failure_weight(error.category()), self.is_slow(duration));
         
-        // Update sliding window
-        if inner.results_window.len() >= self.config.sliding_window_size {
-            inner.results_window.pop_front();
+        if self.config.load().track_metrics {
+            self.counters.record(|stripe| &stripe.failed_requests, true);
+            self.latencies.record(duration);
         }
-        inner.results_window.push_back(false);
-        
-        // Check if the call was slow (although it failed)
-        let was_slow = if let Some(threshold) = self.config.slow_call_duration_threshold {
-            duration >= threshold
-        } else {
-            false
-        };
-        
-        // Update slow call window
-        if inner.slow_call_window.len() >= self.config.sliding_window_size {
-            inner.slow_call_window.pop_front();
-        }
-        inner.slow_call_window.push_back(was_slow);
-        
-        // Update metrics
-        inner.metrics.total_requests += 1;
-        inner.metrics.failed_requests += 1;
-        inner.metrics.consecutive_failures = inner.consecutive_failures as u32;
-        inner.metrics.consecutive_successes = 0;
-        inner.metrics.last_error_timestamp = Some(SystemTime::now());
-        
-        // Calculate rates
-        self.update_rates(&mut inner);
-        
-        let error_clone = error.clone(); // This requires Clone for AklypseError
-        drop(inner);
         
         self.notify_operation_result(
             CircuitOperationType::Failure,
             duration,
-            Some(&error_clone)
+            Some(error)
         );
     }
     
     fn record_rejected(&self) {
-        let mut inner = self.inner.write().unwrap();
-        inner.metrics.total_requests += 1;
-        inner.metrics.rejected_requests += 1;
-        drop(inner);
+        if self.config.load().track_metrics {
+            self.counters.record(|stripe| &stripe.rejected_requests, false);
+        }
         
         // Zero duration since operation was rejected
         self.notify_operation_result(
@@ -862,66 +2276,148 @@ impl CircuitBreaker {
         );
     }
     
-    fn record_timeout(&self) {
-        let mut inner = self.inner.write().unwrap();
-        inner.consecutive_failures += 1;
-        inner.consecutive_successes = 0;
+    fn record_timeout(&self, duration: Duration) {
+        self.consecutive_failures.fetch_add(1, Ordering::SeqCst);
+        self.consecutive_successes.store(0, Ordering::SeqCst);
+        self.window.load().push(self.failure_weight(ErrorCategory::Timeout), self.is_slow(duration));
         
-        // Update sliding window
-        if inner.results_window.len() >= self.config.sliding_window_size {
-            inner.results_window.pop_front();
+        if self.config.load().track_metrics {
+            self.counters.record(|stripe| &stripe.timeout_requests, true);
         }
-        inner.results_window.push_back(false);
         
-        // Update metrics
-        inner.metrics.total_requests += 1;
-        inner.metrics.timeout_requests += 1;
-        inner.metrics.consecutive_failures = inner.consecutive_failures as u32;
-        inner.metrics.consecutive_successes = 0;
-        inner.metrics.last_error_timestamp = Some(SystemTime::now());
-        
-        // Calculate rates
-        self.update_rates(&mut inner);
-        
-        drop(inner);
+        if !self.has_observers() && self.config.load().event_history_size == 0 {
+            return;
+        }
         
-        let timeout_error = super::TimeoutSnafu {
-            operation: format!("Operation in circuit breaker '{}'", self.name),
-            duration: self.config.operation_timeout.unwrap_or_default(),
-        }.build();
+        let timeout_error = self.timeout_error(duration);
         
         self.notify_operation_result(
             CircuitOperationType::Timeout,
-            self.config.operation_timeout.unwrap_or_default(),
+            duration,
             Some(&timeout_error)
         );
     }
     
+    fn force(&self, state: CircuitState, reason: &str) {
+        let mut inner = self.inner.write().unwrap();
+        let prev_state = self.state();
+        self.state.store(state.to_u8(), Ordering::SeqCst);
+        inner.opened_at = None;
+        inner.half_open_entered_at = None;
+        inner.consecutive_reopens = 0;
+        self.half_open_concurrency_count.store(0, Ordering::SeqCst);
+        
+        let event = CircuitTransitionEvent {
+            from_state: prev_state,
+            to_state: state,
+            timestamp: SystemTime::now(),
+            reason: reason.to_string(),
+        };
+        inner.last_transition_timestamp = Some(event.timestamp);
+        
+        // Drop the lock before calling observers
+        drop(inner);
+        
+        info!("Circuit breaker '{}' {}", self.name, reason.to_lowercase());
+        self.notify_state_change(&event);
+    }
+    
+    fn reject_forced_open(&self) -> AklypseError {
+        self.record_rejected();
+        self.forced_open_error()
+    }
+    
+    // Rejection of a call that is not counted, left out of the metrics
+    fn uncounted_rejection(&self, state: CircuitState) -> AklypseError {
+        match state {
+            CircuitState::ForcedOpen => self.forced_open_error(),
+            _ => super::CircuitBreakerOpenSnafu {
+                name: self.name.clone(),
+                retry_after: self.open_remaining(),
+            }.build(),
+        }
+    }
+    
+    // Rejection of a maintenance window, which even calls bypassing the state
+    // cannot get through
+    fn forced_open_error(&self) -> AklypseError {
+        super::CircuitBreakerOpenSnafu {
+            name: self.name.clone(),
+            retry_after: None,
+        }.build()
+    }
+    
+    // Move an Open circuit to HalfOpen for a manual probe
+    fn begin_probe(&self) -> Result<()> {
+        let state = self.state();
+        let refusal = match state {
+            CircuitState::Closed | CircuitState::ForcedClosed => Some("is closed; there is nothing to probe"),
+            CircuitState::ForcedOpen => Some("is forced open; reset it to probe"),
+            CircuitState::Open | CircuitState::HalfOpen => None,
+        };
+        if let Some(refusal) = refusal {
+            return super::StateConflictSnafu {
+                message: format!("Circuit breaker '{}' {}", self.name, refusal),
+            }
+            .fail();
+        }
+        self.notify_operation_attempt(state);
+        if state == CircuitState::Open {
+            self.transition_to_half_open("Manual probe");
+        }
+        Ok(())
+    }
+    
+    fn record_fallback<Ret>(&self, index: usize, start_time: Instant, result: Result<Ret>) -> Option<Ret> {
+        let duration = self.now().saturating_duration_since(start_time);
+        match result {
+            Ok(value) => {
+                self.notify_operation_result(CircuitOperationType::FallbackSuccess(index), duration, None);
+                Some(value)
+            }
+            Err(error) => {
+                self.notify_operation_result(CircuitOperationType::FallbackFailure(index), duration, Some(&error));
+                None
+            }
+        }
+    }
+    
+    fn fallbacks_exhausted(&self, error: AklypseError, failed: usize) -> AklypseError {
+        if failed == 0 {
+            return error;
+        }
+        error.add_context(
+            ErrorContext::new(format!("All {} fallbacks of circuit breaker '{}' failed", failed, self.name))
+                .with_metadata(FALLBACKS_FAILED_METADATA_KEY, failed.to_string()),
+        )
+    }
+    
     // Helper methods
     
+    fn is_slow(&self, duration: Duration) -> bool {
+        self.config.load().slow_call_duration_threshold.is_some_and(|threshold| duration >= threshold)
+    }
+    
     fn should_open_circuit(&self) -> bool {
-        let inner = self.inner.read().unwrap();
+        let config = self.config.load();
+        let window = self.window.load();
         
         // Open if consecutive failures exceed threshold
-        if inner.consecutive_failures >= self.config.failure_threshold {
+        if self.consecutive_failures.load(Ordering::SeqCst) >= config.failure_threshold {
             return true;
         }
         
         // Check failure rate if we have enough samples
-        if inner.results_window.len() >= self.config.minimum_request_threshold_for_rate {
-            let failure_count = inner.results_window.iter().filter(|&&success| !success).count();
-            let failure_rate = failure_count as f64 / inner.results_window.len() as f64;
+        if window.len() >= config.minimum_request_threshold_for_rate {
+            let failure_rate = window.rate(window.failure_count()).unwrap_or(0.0);
             
-            if failure_rate >= self.config.failure_rate_threshold {
+            if failure_rate >= config.failure_rate_threshold {
                 return true;
             }
         }
         
         // Check slow call rate if configured
-        if let (Some(threshold), true) = (self.config.slow_call_rate_threshold, !inner.slow_call_window.is_empty()) {
-            let slow_count = inner.slow_call_window.iter().filter(|&&slow| slow).count();
-            let slow_rate = slow_count as f64 / inner.slow_call_window.len() as f64;
-            
+        if let (Some(threshold), Some(slow_rate)) = (config.slow_call_rate_threshold, window.rate(window.slow_count())) {
             if slow_rate >= threshold {
                 return true;
             }
@@ -932,60 +2428,161 @@ impl CircuitBreaker {
     
     fn should_count_as_failure(&self, error: &AklypseError) -> bool {
         // If there's a custom predicate, use that
-        if let Some(predicate) = &self.config.error_predicate {
-            return predicate(error);
+        if let Some(predicate) = &self.config.load().error_predicate {
+            if !predicate(error) {
+                return false;
+            }
         }
         
-        // By default, all errors count as failures
-        true
+        // Otherwise errors count as failures unless their category weighs nothing
+        self.failure_weight(error.category()) > 0.0
     }
     
-    fn update_rates(&self, inner: &mut InnerState) {
-        if inner.results_window.is_empty() {
-            inner.metrics.failure_rate_in_window = None;
-        } else {
-            let failure_count = inner.results_window.iter().filter(|&&success| !success).count();
-            let failure_rate = failure_count as f64 / inner.results_window.len() as f64;
-            inner.metrics.failure_rate_in_window = Some(failure_rate);
+    fn failure_weight(&self, category: ErrorCategory) -> f64 {
+        self.config.load().failure_weights.get(&category).copied().unwrap_or(1.0).max(0.0)
+    }
+    
+    // Observer notification methods
+    
+    fn register_observer(&self, observer: ObserverRef) -> ObserverId {
+        let id = ObserverId(self.next_observer_id.fetch_add(1, Ordering::Relaxed));
+        let entry = ObserverEntry { id, observer };
+        self.observers.rcu(|observers| {
+            let mut observers = Vec::clone(observers);
+            observers.push(entry.clone());
+            observers
+        });
+        id
+    }
+    
+    fn has_observers(&self) -> bool {
+        !self.observers.load().is_empty()
+    }
+    
+    // Call `notify` with every live observer, pruning dropped weak ones afterwards
+    fn for_each_observer(&self, mut notify: impl FnMut(&dyn CircuitBreakerObserver)) {
+        let mut dropped = false;
+        for entry in self.observers.load().iter() {
+            match &entry.observer {
+                ObserverRef::Strong(observer) => notify(observer.as_ref()),
+                ObserverRef::Weak(observer) => match observer.upgrade() {
+                    Some(observer) => notify(observer.as_ref()),
+                    None => dropped = true,
+                },
+            }
         }
-        
-        if inner.slow_call_window.is_empty() {
-            inner.metrics.slow_call_rate_in_window = None;
-        } else {
-            let slow_count = inner.slow_call_window.iter().filter(|&&slow| slow).count();
-            let slow_rate = slow_count as f64 / inner.slow_call_window.len() as f64;
-            inner.metrics.slow_call_rate_in_window = Some(slow_rate);
+        if dropped {
+            self.observers.rcu(|observers| {
+                observers.iter().filter(|entry| entry.is_alive()).cloned().collect::<Vec<_>>()
+            });
         }
     }
     
-    // Observer notification methods
+    // Append to the history; a busy history is skipped unless `wait` is set
+    fn remember(&self, entry: impl FnOnce() -> CircuitHistoryEntry, wait: bool) {
+        let capacity = self.config.load().event_history_size;
+        if capacity == 0 {
+            return;
+        }
+        let mut history = match self.history.try_lock() {
+            Ok(history) => history,
+            Err(std::sync::TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(std::sync::TryLockError::WouldBlock) if wait => self.history.lock().unwrap_or_else(|p| p.into_inner()),
+            Err(std::sync::TryLockError::WouldBlock) => return,
+        };
+        if history.len() >= capacity {
+            history.pop_front();
+        }
+        history.push_back(entry());
+    }
     
     fn notify_state_change(&self, event: &CircuitTransitionEvent) {
-        let observers = self.observers.lock().unwrap();
-        for observer in &*observers {
-            observer.on_state_change(&self.name, event);
-        }
+        #[cfg(feature = "tracing-integration")]
+        tracing::info!(
+            breaker = %self.name,
+            from = %event.from_state,
+            to = %event.to_state,
+            reason = %event.reason,
+            "circuit_breaker.transition"
+        );
+        self.remember(|| CircuitHistoryEntry::Transition(event.clone()), true);
+        self.for_each_observer(|observer| observer.on_state_change(&self.name, event));
     }
     
     fn notify_operation_attempt(&self, state: CircuitState) {
-        let observers = self.observers.lock().unwrap();
-        for observer in &*observers {
-            observer.on_operation_attempt(&self.name, state);
-        }
+        self.for_each_observer(|observer| observer.on_operation_attempt(&self.name, state));
     }
     
     fn notify_operation_result(&self, op_type: CircuitOperationType, duration: Duration, error: Option<&AklypseError>) {
-        let observers = self.observers.lock().unwrap();
-        for observer in &*observers {
-            observer.on_operation_result(&self.name, op_type, duration, error);
+        // Lands on the enclosing `circuit_breaker.call` span; a no-op in any other span
+        #[cfg(feature = "tracing-integration")]
+        tracing::Span::current()
+            .record("outcome", tracing::field::debug(op_type))
+            .record("duration_ms", duration.as_millis() as u64);
+        self.remember(
+            || CircuitHistoryEntry::Outcome {
+                op_type,
+                duration,
+                timestamp: SystemTime::now(),
+                error: error.cloned().map(Box::new),
+            },
+            false,
+        );
+        self.for_each_observer(|observer| observer.on_operation_result(&self.name, op_type, duration, error));
+    }
+    
+    fn notify_reset(&self) {
+        self.for_each_observer(|observer| observer.on_reset(&self.name));
+    }
+}
+
+// Panic payload caught from an operation, kept until the call is recorded
+type PanicSlot = Mutex<Option<Box<dyn Any + Send>>>;
+
+// Message of a panic payload, empty unless it is a string
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+// Future resolving to the panic payload instead of unwinding when polling panics
+#[cfg(feature = "tokio")]
+struct CatchUnwind<Fut>(std::pin::Pin<Box<Fut>>);
+
+#[cfg(feature = "tokio")]
+impl<Fut: std::future::Future> std::future::Future for CatchUnwind<Fut> {
+    type Output = std::result::Result<Fut::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<Self::Output> {
+        match std::panic::catch_unwind(AssertUnwindSafe(|| self.0.as_mut().poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => std::task::Poll::Ready(Err(payload)),
         }
     }
-      fn notify_reset(&self) {
-        let observers = self.observers.lock().unwrap();
-        for observer in &*observers {
-            observer.on_reset(&self.name);
+}
+
+// Poll all `futures` on the current task until each has completed, keeping their order
+#[cfg(feature = "tokio")]
+async fn join_all<Fut: std::future::Future>(futures: Vec<Fut>) -> Vec<Fut::Output> {
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<Fut::Output>> = futures.iter().map(|_| None).collect();
+    std::future::poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    std::task::Poll::Ready(value) => *output = Some(value),
+                    std::task::Poll::Pending => pending = true,
+                }
+            }
         }
-    }
+        if pending { std::task::Poll::Pending } else { std::task::Poll::Ready(()) }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
 }
 
 #[cfg(test)]
@@ -994,6 +2591,23 @@ mod tests {
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::thread;
 
+    #[test]
+    fn test_transition_event_describe() {
+        let event = CircuitTransitionEvent {
+            from_state: CircuitState::Closed,
+            to_state: CircuitState::Open,
+            timestamp: std::time::UNIX_EPOCH + Duration::from_secs(1_709_210_096),
+            reason: "failure threshold reached".to_string(),
+        };
+
+        assert_eq!(
+            event.to_string(),
+            "2024-02-29T12:34:56.000Z Closed -> Open: failure threshold reached"
+        );
+        let config = ErrorReportConfig::default().with_timestamp_format(crate::common::error::TimestampFormat::UnixSeconds);
+        assert!(event.describe(&config).starts_with("1709210096 Closed -> Open"));
+    }
+
     // Mock observer for testing
     struct TestObserver {
         state_changes: AtomicUsize,
@@ -1113,15 +2727,755 @@ mod tests {
         
         assert!(result.is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    
     #[test]
-    fn test_circuit_breaker_initial_state() {
-        let cb = CircuitBreaker::new("test", CircuitBreakerConfig::default());
+    fn test_reset_timeout_jitter() {
+        use crate::common::utils::jitter::EqualJitter;
+
+        let retry_after = |seed| {
+            let config = CircuitBreakerConfig {
+                reset_timeout: Duration::from_secs(60),
+                reset_jitter: Some(Arc::new(EqualJitter)),
+                reset_jitter_seed: Some(seed),
+                ..Default::default()
+            };
+            let cb = CircuitBreaker::new("jittered", config);
+            cb.trip();
+            match cb.execute(|| Ok(())) {
+                Err(AklypseError::CircuitBreakerOpen { retry_after, .. }) => retry_after.unwrap(),
+                other => panic!("expected rejection, got {:?}", other.err()),
+            }
+        };
+
+        let first = retry_after(5);
+        assert!(first >= Duration::from_secs(29) && first <= Duration::from_secs(60));
+        assert!(first.abs_diff(retry_after(5)) < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_call_options_override_timeout_and_counting() {
+        let config = CircuitBreakerConfig { failure_threshold: 1, operation_timeout: None, ..Default::default() };
+        let cb = CircuitBreaker::new("options", config);
+        let slow = || {
+            thread::sleep(Duration::from_millis(5));
+            Ok(())
+        };
+
+        let uncounted = CallOptions::new().with_timeout(Duration::from_millis(1)).with_record_outcome(false);
+        assert!(matches!(cb.execute_with_options(slow, &uncounted), Err(AklypseError::Timeout { .. })));
+        assert_eq!((cb.state(), cb.metrics().total_requests), (CircuitState::Closed, 0));
+
+        let tight = CallOptions::new().with_timeout(Duration::from_millis(1));
+        assert!(cb.execute_with_options(slow, &tight).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert_eq!(cb.metrics().timeout_requests, 1);
+
+        assert!(matches!(cb.execute(|| Ok(())), Err(AklypseError::CircuitBreakerOpen { .. })));
+        assert_eq!(cb.execute_with_options(|| Ok(8), &CallOptions::health_probe()).unwrap(), 8);
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert_eq!((cb.metrics().successful_requests, cb.metrics().rejected_requests), (0, 1));
+
+        // Not recording the outcome does not get a call past an Open circuit
+        let ran = AtomicBool::new(false);
+        let unrecorded = cb.execute_with_options(
+            || {
+                ran.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+            &CallOptions::new().with_record_outcome(false),
+        );
+        assert!(matches!(unrecorded, Err(AklypseError::CircuitBreakerOpen { retry_after: Some(_), .. })));
+        assert!(!ran.load(Ordering::SeqCst));
+        assert_eq!(cb.metrics().rejected_requests, 1);
+    }
+
+    #[test]
+    fn test_a_timeout_is_counted_once() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, operation_timeout: Some(Duration::from_millis(1)), ..Default::default() };
+        let cb = CircuitBreaker::new("timeouts", config);
+        let slow = || {
+            thread::sleep(Duration::from_millis(5));
+            Ok(())
+        };
+
+        assert!(matches!(cb.execute(slow), Err(AklypseError::Timeout { .. })));
+        let metrics = cb.metrics();
+        assert_eq!((metrics.total_requests, metrics.timeout_requests, metrics.failed_requests), (1, 1, 0));
+        assert_eq!((metrics.consecutive_failures, metrics.state), (1, CircuitState::Closed));
+
+        assert!(cb.execute(slow).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_an_async_timeout_is_counted_once() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, operation_timeout: Some(Duration::from_millis(1)), ..Default::default() };
+        let cb = CircuitBreaker::new("async-timeouts", config);
+
+        let result = cb
+            .execute_async(|| async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(())
+            })
+            .await;
+        assert!(matches!(result, Err(AklypseError::Timeout { .. })));
+        let metrics = cb.metrics();
+        assert_eq!((metrics.total_requests, metrics.consecutive_failures, metrics.state), (1, 1, CircuitState::Closed));
+    }
+
+    #[test]
+    fn test_forced_states_pin_the_circuit() {
+        let config = CircuitBreakerConfig { failure_threshold: 1, reset_timeout: Duration::ZERO, ..Default::default() };
+        let cb = CircuitBreaker::new("maintenance", config);
+
+        cb.force_closed();
+        for _ in 0..3 {
+            assert!(cb.execute(|| Err::<(), _>(internal_error())).is_err());
+        }
+        let metrics = cb.metrics();
+        assert_eq!((metrics.state, metrics.failed_requests), (CircuitState::ForcedClosed, 3));
+        assert!(cb.try_probe(|| Ok(())).is_err());
+
+        cb.force_open();
+        thread::sleep(Duration::from_millis(2));
+        match cb.execute(|| Ok(())) {
+            Err(AklypseError::CircuitBreakerOpen { retry_after, .. }) => assert_eq!(retry_after, None),
+            other => panic!("expected CircuitBreakerOpen, got {:?}", other),
+        }
+        assert!(matches!(cb.try_probe(|| Ok(())), Err(AklypseError::StateConflict { .. })));
+        // Not even a call bypassing the state gets through a maintenance window
+        let bypass = CallOptions::new().with_bypass_open(true);
+        assert!(matches!(cb.execute_with_options(|| Ok(()), &bypass), Err(AklypseError::CircuitBreakerOpen { .. })));
+        assert!(cb.execute_with_options(|| Ok(()), &CallOptions::health_probe()).is_err());
+        assert_eq!(cb.state(), CircuitState::ForcedOpen);
+        assert!(cb.state().is_forced());
+
+        cb.reset();
+        assert_eq!(cb.execute(|| Ok(1)).unwrap(), 1);
+        assert_eq!(cb.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_call_permits_record_outcomes() {
+        let config = CircuitBreakerConfig { failure_threshold: 1, success_threshold_to_close: 1, reset_timeout: Duration::ZERO, ..Default::default() };
+        let cb = CircuitBreaker::new("stream", config);
+        cb.acquire().unwrap().success();
+        cb.acquire().unwrap().failure(&internal_error());
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        // The reset timeout elapsed: one probe is admitted, and dropping it frees the slot
+        let probe = cb.acquire().unwrap();
+        assert_eq!(cb.state(), CircuitState::HalfOpen);
+        assert!(matches!(cb.acquire().unwrap_err(), AklypseError::CircuitBreakerOpen { .. }));
+        drop(probe);
+        cb.acquire().unwrap().success();
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        let metrics = cb.metrics();
+        assert_eq!((metrics.successful_requests, metrics.failed_requests, metrics.rejected_requests), (2, 1, 1));
+    }
+
+    #[test]
+    fn test_execute_preemptive_returns_at_deadline() {
+        let config = CircuitBreakerConfig { operation_timeout: Some(Duration::from_millis(20)), ..Default::default() };
+        let cb = CircuitBreaker::new("preemptive", config);
+        let started = std::time::Instant::now();
+        let error = cb.execute_preemptive(|| {
+            thread::sleep(Duration::from_secs(2));
+            Ok(())
+        }).unwrap_err();
+        assert!(matches!(error, AklypseError::Timeout { .. }));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        // The timeout counts once toward the threshold and the window
+        let metrics = cb.metrics();
+        assert_eq!((metrics.timeout_requests, metrics.failed_requests, metrics.total_requests), (1, 0, 1));
+        assert_eq!(metrics.consecutive_failures, 1);
+        assert_eq!(metrics.failure_rate_in_window, Some(1.0));
+
+        assert_eq!(cb.execute_preemptive(|| Ok(7)).unwrap(), 7);
+        assert_eq!(cb.metrics().failure_rate_in_window, Some(0.5));
+        // Printing the panic may take longer than the 20ms budget, so give it room
+        let cb = CircuitBreaker::new("preemptive-panic", CircuitBreakerConfig::default());
+        let panicked = cb.execute_preemptive(|| -> Result<()> { panic!("worker panicked") }).unwrap_err();
+        assert!(matches!(panicked, AklypseError::Internal { .. }));
+    }
+
+    #[test]
+    fn test_caller_deadline_shrinks_operation_timeout() {
+        let cb = CircuitBreaker::new("nested", CircuitBreakerConfig { operation_timeout: Some(Duration::from_secs(5)), ..Default::default() });
+        let started = std::time::Instant::now();
+        let error = deadline::with_deadline("checkout", Duration::from_millis(30), || {
+            cb.execute_preemptive(|| {
+                thread::sleep(Duration::from_secs(2));
+                Ok(())
+            })
+        }).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        assert_eq!(error.get_attachment::<Deadline>().map(Deadline::operation), Some("checkout"));
+
+        // Past the deadline nothing runs and nothing is recorded
+        let runs = AtomicUsize::new(0);
+        let recorded = cb.metrics().total_requests;
+        let error = deadline::with_deadline("checkout", Duration::ZERO, || cb.execute(|| Ok(runs.fetch_add(1, Ordering::SeqCst)))).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert_eq!(cb.metrics().total_requests, recorded);
+    }
+
+    #[test]
+    fn test_batch_failures_follow_policy() {
+        let config = CircuitBreakerConfig { failure_threshold: 1, operation_timeout: None, ..Default::default() };
+        let cb = CircuitBreaker::new("batch", config.clone());
+        let values = cb.execute_batch(vec![|| Ok(1), || Ok(2), || Ok(3)]).unwrap();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let batch = |fail: usize| -> Vec<Box<dyn FnOnce() -> Result<usize>>> {
+            (0..3)
+                .map(|i| -> Box<dyn FnOnce() -> Result<usize>> {
+                    if i < fail { Box::new(move || super::super::StateConflictSnafu { message: format!("op {}", i) }.fail()) } else { Box::new(move || Ok(i)) }
+                })
+                .collect()
+        };
+        let cb = CircuitBreaker::new("majority", CircuitBreakerConfig { batch_failure_policy: BatchFailurePolicy::MajorityFailure, ..config.clone() });
+        let error = cb.execute_batch(batch(1)).unwrap_err();
+        assert_eq!(error.get_rich_context().unwrap().metadata.get(BATCH_METADATA_KEY).map(String::as_str), Some("1/3"));
+        match &error {
+            AklypseError::WithRichContext { source, .. } => {
+                assert!(matches!(source.as_ref(), AklypseError::MultipleErrors { errors, .. } if errors.len() == 1));
+            }
+            other => panic!("expected aggregated errors, got {:?}", other),
+        }
+        assert_eq!(cb.state(), CircuitState::Closed);
+        assert!(cb.execute_batch(batch(2)).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let cb = CircuitBreaker::new("any", config);
+        assert!(cb.execute_batch(batch(1)).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+        assert!(matches!(cb.execute_batch(batch(0)).unwrap_err(), AklypseError::CircuitBreakerOpen { .. }));
+    }
+
+    #[test]
+    fn test_cooldown_is_announced_when_reset_timeout_elapses() {
+        #[derive(Default)]
+        struct CooldownObserver(Mutex<Vec<Instant>>);
+
+        impl CircuitBreakerObserver for CooldownObserver {
+            fn on_state_change(&self, _name: &str, _event: &CircuitTransitionEvent) {}
+            fn on_operation_attempt(&self, _name: &str, _state: CircuitState) {}
+            fn on_operation_result(&self, _name: &str, _op_type: CircuitOperationType, _duration: Duration, _error: Option<&AklypseError>) {}
+            fn on_reset(&self, _name: &str) {}
+            fn on_half_open_eligible(&self, _name: &str) {
+                self.0.lock().unwrap().push(Instant::now());
+            }
+        }
+
+        let config = CircuitBreakerConfig { reset_timeout: Duration::from_millis(40), ..Default::default() };
+        let cb = CircuitBreaker::new("cooldown", config);
+        let observer = Arc::new(CooldownObserver::default());
+        cb.add_observer(observer.clone());
+        assert_eq!(cb.time_until_half_open(), None);
+
+        let opened = Instant::now();
+        cb.trip();
+        let remaining = cb.time_until_half_open().unwrap();
+        assert!(remaining > Duration::from_millis(20) && remaining <= Duration::from_millis(40));
+        for _ in 0..100 {
+            if !observer.0.lock().unwrap().is_empty() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        let notified = observer.0.lock().unwrap().clone();
+        assert_eq!(notified.len(), 1);
+        assert!(notified[0] - opened >= Duration::from_millis(40));
+        assert_eq!(cb.time_until_half_open(), Some(Duration::ZERO));
+
+        // A circuit closed before its reset timeout announces nothing
+        cb.trip();
+        cb.reset();
+        thread::sleep(Duration::from_millis(60));
+        assert_eq!(observer.0.lock().unwrap().len(), 1);
+
+        // Re-opening hands the new wait to the same timer
+        cb.trip();
+        cb.reset();
+        cb.trip();
+        thread::sleep(Duration::from_millis(80));
+        assert_eq!(observer.0.lock().unwrap().len(), 2);
+        assert!(cb.cooldown.shared.slot.lock().unwrap().thread);
+    }
+
+    #[test]
+    fn test_panics_are_recorded_as_failures() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, operation_timeout: None, ..Default::default() };
+        let cb = CircuitBreaker::new("panicky", config.clone());
+        let error = cb.execute(|| -> Result<()> { panic!("index out of bounds") }).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Internal);
+        match &error {
+            AklypseError::WithRichContext { source, .. } => {
+                assert!(matches!(source.as_ref(), AklypseError::Internal { message, .. } if message == "panic: index out of bounds"));
+            }
+            other => panic!("expected a caught panic, got {:?}", other),
+        }
+        assert_eq!(cb.metrics().failed_requests, 1);
+        assert_eq!(cb.execute(|| Ok(5)).unwrap(), 5);
+
+        let cb = CircuitBreaker::new("propagating", CircuitBreakerConfig { propagate_panics: true, ..config });
+        for _ in 0..2 {
+            let unwound = std::panic::catch_unwind(AssertUnwindSafe(|| cb.execute(|| -> Result<()> { panic!("boom") })));
+            assert_eq!(unwound.unwrap_err().downcast_ref::<&str>(), Some(&"boom"));
+        }
+        assert_eq!(cb.metrics().failed_requests, 2);
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[cfg(feature = "tracing-integration")]
+    #[test]
+    fn test_calls_are_traced_with_transitions_inside() {
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry::LookupSpan;
+
+        #[derive(Clone, Default)]
+        struct Trace(Arc<Mutex<Vec<String>>>);
+
+        struct Fields<'a>(&'a mut Vec<String>);
+
+        impl tracing::field::Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn fmt::Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        impl<S: tracing::Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for Trace {
+            fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, _id: &tracing::span::Id, _ctx: Context<'_, S>) {
+                let mut trace = self.0.lock().unwrap();
+                trace.push(format!("span={}", attrs.metadata().name()));
+                attrs.record(&mut Fields(&mut trace));
+            }
+
+            fn on_record(&self, _id: &tracing::span::Id, values: &tracing::span::Record<'_>, _ctx: Context<'_, S>) {
+                values.record(&mut Fields(&mut self.0.lock().unwrap()));
+            }
+
+            fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+                let parent = ctx.event_span(event).map(|span| span.name()).unwrap_or("none");
+                self.0.lock().unwrap().push(format!("event in {}", parent));
+            }
+        }
+
+        let trace = Trace::default();
+        let subscriber = tracing_subscriber::registry().with(trace.clone());
+        let cb = CircuitBreaker::new("traced", CircuitBreakerConfig { failure_threshold: 1, ..Default::default() });
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = cb.execute(|| Err::<(), _>(internal_error()));
+        });
+
+        let trace = trace.0.lock().unwrap();
+        for expected in ["span=circuit_breaker.call", "breaker=traced", "state=Closed", "outcome=Failure"] {
+            assert!(trace.iter().any(|line| line == expected), "missing {} in {:?}", expected, trace);
+        }
+        assert!(trace.iter().any(|line| line == "event in circuit_breaker.call"));
+    }
+
+    #[test]
+    fn test_try_probe_bypasses_reset_timeout() {
+        let config = CircuitBreakerConfig { success_threshold_to_close: 1, operation_timeout: None, ..Default::default() };
+        let cb = CircuitBreaker::new("admin", config);
+        let observer = Arc::new(TestObserver::new());
+        cb.add_observer(observer.clone());
+
+        assert!(matches!(cb.try_probe(|| Ok(())), Err(AklypseError::StateConflict { .. })));
+        cb.trip();
+        assert!(cb.try_probe(|| Err::<(), _>(internal_error())).is_err());
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        assert_eq!(cb.try_probe(|| Ok(3)).unwrap(), 3);
+        assert_eq!(cb.state(), CircuitState::Closed);
+        // trip, then per probe: Open -> HalfOpen and back to Open, Open -> HalfOpen -> Closed
+        assert_eq!(observer.state_changes.load(Ordering::SeqCst), 5);
+        assert_eq!(observer.operation_results.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_reset_timeout_grows_while_probes_fail() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold_to_close: 1,
+            reset_timeout: Duration::from_millis(20),
+            reset_timeout_multiplier: 2.0,
+            max_reset_timeout: Duration::from_millis(50),
+            operation_timeout: None,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("backoff", config);
+        let retry_after = |cb: &CircuitBreaker| match cb.execute(|| Ok(())) {
+            Err(AklypseError::CircuitBreakerOpen { retry_after, .. }) => retry_after.unwrap(),
+            other => panic!("expected rejection, got {:?}", other.err()),
+        };
+        let fail = |cb: &CircuitBreaker| cb.execute(|| Err::<(), _>(internal_error())).unwrap_err();
+
+        fail(&cb);
+        let mut expected = Vec::new();
+        for _ in 0..3 {
+            let open_for = retry_after(&cb);
+            expected.push(open_for);
+            thread::sleep(open_for + Duration::from_millis(5));
+            fail(&cb); // the HalfOpen probe fails and re-opens the circuit
+        }
+        let ms = |d: Duration| d.as_millis().div_ceil(10) * 10;
+        assert_eq!(expected.into_iter().map(ms).collect::<Vec<_>>(), vec![20, 40, 50]);
+
+        // A successful probe closes the circuit and forgets the growth
+        thread::sleep(retry_after(&cb) + Duration::from_millis(5));
+        assert!(cb.execute(|| Ok(())).is_ok());
+        fail(&cb);
+        assert!(retry_after(&cb) <= Duration::from_millis(20));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn test_mock_clock_steps_over_reset_timeout() {
+        let clock = Arc::new(crate::common::utils::clock::MockClock::new());
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold_to_close: 1,
+            reset_timeout: Duration::from_secs(60),
+            operation_timeout: None,
+            clock: clock.clone(),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("clocked", config);
+        cb.execute(|| Err::<(), _>(internal_error())).unwrap_err();
+
+        clock.advance(Duration::from_secs(45));
+        match cb.execute(|| Ok(())) {
+            Err(AklypseError::CircuitBreakerOpen { retry_after, .. }) => assert_eq!(retry_after, Some(Duration::from_secs(15))),
+            other => panic!("expected rejection, got {:?}", other),
+        }
+
+        clock.advance(Duration::from_secs(15));
+        assert!(cb.execute(|| Ok(())).is_ok());
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        // No real-time timer waits on a mock clock
+        cb.add_observer(Arc::new(TestObserver::new()));
+        cb.trip();
+        assert!(!cb.cooldown.shared.slot.lock().unwrap().thread);
+    }
+
+    fn internal_error() -> AklypseError {
+        super::super::InternalSnafu { message: "Test error".to_string(), source: None }.build()
+    }
+
+    #[test]
+    fn test_metrics_track_counters_and_window() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 10,
+            minimum_request_threshold_for_rate: 100,
+            sliding_window_size: 4,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("windowed", config);
+        for fail in [false, false, true, false, true, true] {
+            let _ = cb.execute(|| if fail { Err(internal_error()) } else { Ok(()) });
+        }
+
+        let metrics = cb.metrics();
+        assert_eq!((metrics.total_requests, metrics.successful_requests, metrics.failed_requests), (6, 3, 3));
+        assert_eq!(metrics.consecutive_failures, 2);
+        assert_eq!(metrics.failure_rate_in_window, Some(0.75));
+        assert!(metrics.last_error_timestamp.is_some());
         assert_eq!(cb.state(), CircuitState::Closed);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_update_config_resizes_window_and_history() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 10,
+            minimum_request_threshold_for_rate: 100,
+            sliding_window_size: 4,
+            event_history_size: 8,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("tunable", config);
+        for fail in [true, true, false, true] {
+            let _ = cb.execute(|| if fail { Err(internal_error()) } else { Ok(()) });
+        }
+        assert_eq!(cb.metrics().failure_rate_in_window, Some(0.75));
+
+        cb.update_config(CircuitBreakerConfig { failure_threshold: 2, sliding_window_size: 2, event_history_size: 2, ..cb.config().as_ref().clone() });
+        assert_eq!(cb.config().sliding_window_size, 2);
+        assert_eq!(cb.metrics().failure_rate_in_window, Some(0.5));
+        assert_eq!(cb.history().len(), 2);
+
+        // The lowered threshold applies to the next failure
+        let _ = cb.execute(|| Err::<(), _>(internal_error()));
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_failure_weights_scale_failure_rate() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 10,
+            minimum_request_threshold_for_rate: 4,
+            failure_rate_threshold: 0.5,
+            failure_weights: HashMap::from([(ErrorCategory::Timeout, 2.0), (ErrorCategory::Validation, 0.0)]),
+            operation_timeout: None,
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("weighted", config);
+        let invalid = || super::super::ValidationSnafu { field: "id", message: "empty" }.fail::<()>();
+        for _ in 0..3 {
+            cb.execute(invalid).unwrap_err();
+        }
+        let metrics = cb.metrics();
+        assert_eq!((metrics.failed_requests, metrics.failure_rate_in_window), (0, Some(0.0)));
+
+        // One timeout among four calls weighs half the window
+        cb.execute(|| super::super::TimeoutSnafu { operation: "query", duration: Duration::from_secs(1) }.fail::<()>()).unwrap_err();
+        assert_eq!(cb.metrics().failure_rate_in_window, Some(0.5));
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_untracked_metrics_still_trip() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, track_metrics: false, ..Default::default() };
+        let cb = CircuitBreaker::new("untracked", config);
+        for _ in 0..2 {
+            let _ = cb.execute(|| Err::<(), _>(internal_error()));
+        }
+
+        assert_eq!(cb.state(), CircuitState::Open);
+        let metrics = cb.metrics();
+        assert_eq!(metrics.total_requests, 0);
+        assert_eq!(metrics.failure_rate_in_window, Some(1.0));
+        assert!(metrics.last_transition_timestamp.is_some());
+    }
+
+    #[test]
+    fn test_concurrent_recording_is_consistent() {
+        let cb = CircuitBreaker::new("concurrent", CircuitBreakerConfig { operation_timeout: None, ..Default::default() });
+        let observer = Arc::new(TestObserver::new());
+        cb.add_observer(observer.clone());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let cb = cb.clone();
+                thread::spawn(move || (0..500).for_each(|_| cb.execute(|| Ok(())).unwrap()))
+            })
+            .collect();
+        handles.into_iter().for_each(|handle| handle.join().unwrap());
+
+        let metrics = cb.metrics();
+        assert_eq!((metrics.total_requests, metrics.successful_requests), (4000, 4000));
+        assert_eq!(metrics.failure_rate_in_window, Some(0.0));
+        assert_eq!(observer.operation_results.load(Ordering::SeqCst), 4000);
+    }
+
+    #[test]
+    fn test_request_counters_are_striped() {
+        // Consecutive threads get consecutive stripes, wrapping around
+        let next = AtomicUsize::new(COUNTER_STRIPES - 1);
+        let assigned: Vec<usize> = (0..=COUNTER_STRIPES).map(|_| next_stripe(&next)).collect();
+        assert_eq!(assigned[..3], [COUNTER_STRIPES - 1, 0, 1]);
+        assert_eq!(assigned[COUNTER_STRIPES], COUNTER_STRIPES - 1);
+        assert_eq!(stripe_index(), stripe_index());
+
+        let counters = MetricCounters::default();
+        for stripe in 0..COUNTER_STRIPES {
+            counters.record_in(stripe, |stripe| &stripe.successful_requests, false);
+        }
+        counters.record_in(3, |stripe| &stripe.failed_requests, true);
+        assert!(counters.stripes.iter().all(|stripe| stripe.total_requests.load(Ordering::Relaxed) >= 1));
+        assert_eq!(counters.stripes[3].total_requests.load(Ordering::Relaxed), 2);
+        assert_eq!((counters.sum(|stripe| &stripe.total_requests), counters.sum(|stripe| &stripe.failed_requests)), (9, 1));
+        assert!(counters.last_error_timestamp().is_some());
+    }
+
+    // Observer recording the operation types it is told about
+    #[derive(Default)]
+    struct OutcomeObserver(std::sync::Mutex<Vec<CircuitOperationType>>);
+
+    impl CircuitBreakerObserver for OutcomeObserver {
+        fn on_state_change(&self, _name: &str, _event: &CircuitTransitionEvent) {}
+        fn on_operation_attempt(&self, _name: &str, _state: CircuitState) {}
+        fn on_operation_result(&self, _name: &str, op_type: CircuitOperationType, _duration: Duration, _error: Option<&AklypseError>) {
+            self.0.lock().unwrap().push(op_type);
+        }
+        fn on_reset(&self, _name: &str) {}
+    }
+
+    #[test]
+    fn test_fallbacks_tried_in_order_when_open() {
+        let cb = CircuitBreaker::new("pricing", CircuitBreakerConfig::default());
+        let observer = Arc::new(OutcomeObserver::default());
+        cb.add_observer(observer.clone());
+        cb.trip();
+
+        let fallbacks: Vec<Fallback<u32>> = vec![
+            Arc::new(|_| Err(internal_error())),
+            Arc::new(|error| {
+                assert!(matches!(error, AklypseError::CircuitBreakerOpen { .. }));
+                Ok(99)
+            }),
+            Arc::new(|_| panic!("later fallbacks must not run")),
+        ];
+        assert_eq!(cb.execute_with_fallback(|| Ok(1), &fallbacks).unwrap(), 99);
+        assert_eq!(
+            *observer.0.lock().unwrap(),
+            vec![
+                CircuitOperationType::Rejected,
+                CircuitOperationType::FallbackFailure(0),
+                CircuitOperationType::FallbackSuccess(1),
+            ]
+        );
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_failed_fallbacks_return_primary_error() {
+        let cb = CircuitBreaker::new("pricing", CircuitBreakerConfig::default());
+        let fallbacks: Vec<Fallback<u32>> = vec![Arc::new(|_| Err(internal_error())), Arc::new(|_| Err(internal_error()))];
+
+        let error = cb
+            .execute_with_fallback(|| super::super::StateConflictSnafu { message: "stale quote".to_string() }.fail(), &fallbacks)
+            .unwrap_err();
+        assert!(matches!(error.get_rich_context().map(|context| context.metadata.get(FALLBACKS_FAILED_METADATA_KEY).cloned()), Some(Some(ref failed)) if failed == "2"));
+        assert_eq!(error.category(), crate::common::error::ErrorCategory::StateConflict);
+        assert_eq!(cb.execute_with_fallback(|| Ok(5), &fallbacks).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_latency_histogram_buckets_round_trip() {
+        for micros in [0, 7, 15, 16, 31, 32, 1_000, 123_456, 5_000_000] {
+            let value = LatencyHistogram::value(LatencyHistogram::bucket(micros)).as_micros() as f64;
+            assert!((value - micros as f64).abs() <= micros as f64 / 32.0 + 0.5, "{} -> {}", micros, value);
+        }
+        assert_eq!(LatencyHistogram::bucket(u64::MAX), LATENCY_BUCKETS - 1);
+    }
+
+    #[test]
+    fn test_latency_snapshot_percentiles() {
+        let cb = CircuitBreaker::new("latency", CircuitBreakerConfig::default());
+        assert_eq!(cb.latency_snapshot(), LatencySnapshot::default());
+        for millis in 1..=100u64 {
+            cb.record_success(Duration::from_millis(millis));
+        }
+
+        let snapshot = cb.latency_snapshot();
+        assert_eq!(snapshot.count, 100);
+        let close = |actual: Option<Duration>, expected_millis: u64| {
+            let actual = actual.unwrap().as_secs_f64() * 1000.0;
+            assert!((actual - expected_millis as f64).abs() <= expected_millis as f64 * 0.04, "{} vs {}", actual, expected_millis);
+        };
+        close(snapshot.p50, 50);
+        close(snapshot.p95, 95);
+        close(snapshot.p99, 99);
+        close(snapshot.max, 100);
+        close(snapshot.mean, 50);
+        assert_eq!(cb.metrics().latency_p95, snapshot.p95);
+    }
+
+    #[test]
+    fn test_history_explains_why_circuit_opened() {
+        let config = CircuitBreakerConfig { failure_threshold: 2, event_history_size: 3, ..Default::default() };
+        let cb = CircuitBreaker::new("inventory", config);
+        cb.execute(|| Ok(())).unwrap();
+        for _ in 0..2 {
+            let _ = cb.execute(|| Err::<(), _>(internal_error()));
+        }
+
+        let history = cb.history();
+        assert_eq!(history.len(), 3);
+        assert!(history[..2].iter().all(|entry| matches!(
+            entry,
+            CircuitHistoryEntry::Outcome { op_type: CircuitOperationType::Failure, error: Some(error), .. }
+                if matches!(**error, AklypseError::Internal { .. })
+        )));
+        match &history[2] {
+            CircuitHistoryEntry::Transition(event) => {
+                assert_eq!((event.from_state, event.to_state), (CircuitState::Closed, CircuitState::Open));
+                assert_eq!(event.reason, "Failure threshold reached");
+            }
+            other => panic!("expected a transition, got {:?}", other),
+        }
+
+        let quiet = CircuitBreaker::new("quiet", CircuitBreakerConfig { event_history_size: 0, ..Default::default() });
+        quiet.trip();
+        assert!(quiet.history().is_empty());
+    }
+
+    #[test]
+    fn test_removed_and_dropped_observers_stop_receiving() {
+        let cb = CircuitBreaker::new("dashboard", CircuitBreakerConfig::default());
+        let kept = Arc::new(OutcomeObserver::default());
+        let removed = Arc::new(OutcomeObserver::default());
+        let weak = Arc::new(OutcomeObserver::default());
+        cb.add_observer(kept.clone());
+        let removed_id = cb.add_observer(removed.clone());
+        let weak_dyn: Arc<dyn CircuitBreakerObserver> = weak.clone();
+        cb.add_weak_observer(Arc::downgrade(&weak_dyn));
+        drop(weak_dyn);
+
+        cb.execute(|| Ok(())).unwrap();
+        assert!(cb.remove_observer(removed_id));
+        assert!(!cb.remove_observer(removed_id));
+        cb.execute(|| Ok(())).unwrap();
+        assert_eq!((kept.0.lock().unwrap().len(), removed.0.lock().unwrap().len(), weak.0.lock().unwrap().len()), (2, 1, 2));
+
+        assert_eq!(Arc::strong_count(&weak), 1);
+        drop(weak);
+        assert_eq!(cb.observer_count(), 1);
+        cb.execute(|| Ok(())).unwrap();
+        assert_eq!(cb.observers.load().len(), 1);
+        assert_eq!(kept.0.lock().unwrap().len(), 3);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_execute_with_fallback_async() {
+        let cb = CircuitBreaker::new("pricing", CircuitBreakerConfig::default());
+        let fallbacks: Vec<AsyncFallback<&str>> = vec![Arc::new(|_| Box::pin(async { Ok("cached") }))];
+        let value = cb.execute_with_fallback_async(|| async { Err(internal_error()) }, &fallbacks).await;
+        assert_eq!(value.unwrap(), "cached");
+        assert_eq!(cb.metrics().failed_requests, 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_panics_are_recorded_as_failures() {
+        fn end_of_stream() -> Result<()> {
+            panic!("stream ended early")
+        }
+
+        let cb = CircuitBreaker::new("panicky", CircuitBreakerConfig { failure_threshold: 1, ..Default::default() });
+        let error = cb
+            .execute_async(|| async {
+                tokio::task::yield_now().await;
+                end_of_stream()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Internal);
+        assert_eq!(cb.state(), CircuitState::Open);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_execute_batch_async_runs_concurrently() {
+        let cb = CircuitBreaker::new("fan-out", CircuitBreakerConfig::default());
+        let started = std::time::Instant::now();
+        let operations: Vec<_> = (0..4u64)
+            .map(|i| move || async move {
+                tokio::time::sleep(Duration::from_millis(50 - i * 10)).await;
+                Ok(i)
+            })
+            .collect();
+        assert_eq!(cb.execute_batch_async(operations).await.unwrap(), vec![0, 1, 2, 3]);
+        assert!(started.elapsed() < Duration::from_millis(120));
+        assert_eq!(cb.metrics().total_requests, 1);
+    }
+}
@@ -0,0 +1,313 @@
+/* src/common/error/context.rs */
+#![warn(missing_docs)]
+//! **Brief:** Scoped error context propagated through tasks and threads.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [Context Propagation]
+//!  - [Task-Local Scopes]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A scope is entered once at a request boundary with the correlation id,
+//! component, tags and metadata of that request. Every context attached
+//! inside the scope (`AklypseError::add_context`, `ResultExt::context_msg`,
+//! `ResultExt::context_rich`) inherits whichever of those fields it does not
+//! set itself. Errors built directly from a snafu selector capture the scope
+//! as a `ScopeCapture`, returned by `AklypseError::get_rich_context` until
+//! `AklypseError::with_scope_context` turns it into a context layer.
+//!
+//! `scope` stores the context in a tokio task-local (feature `tokio`), and
+//! `scope_sync` in a thread-local for synchronous code. Scopes nest: an inner
+//! scope inherits the fields it leaves unset from the outer one. Task-locals
+//! are not inherited by spawned tasks; wrap the spawned future in `propagate`
+//! to carry the current scope over.
+//!
+//! With the `tracing` feature, `ErrorContext::from_current_span` records the
+//! name, target and ids of the current span under the `SPAN_*_KEY` metadata
+//! keys, and every scope records the span it was entered in. `tracing` has
+//! no notion of a trace id; install a resolver with `set_trace_id_resolver`
+//! (e.g. reading the OpenTelemetry context) to record one as well.
+
+use super::types::ErrorContext;
+use std::cell::RefCell;
+use std::sync::Arc;
+#[cfg(feature = "tokio")]
+use std::future::Future;
+#[cfg(feature = "tracing")]
+use std::sync::OnceLock;
+
+/// Metadata key holding the name of the span an error context was captured in
+#[cfg(feature = "tracing")]
+pub const SPAN_NAME_KEY: &str = "span.name";
+/// Metadata key holding the target of the span an error context was captured in
+#[cfg(feature = "tracing")]
+pub const SPAN_TARGET_KEY: &str = "span.target";
+/// Metadata key holding the subscriber-assigned id of the span
+#[cfg(feature = "tracing")]
+pub const SPAN_ID_KEY: &str = "span.id";
+/// Metadata key holding the distributed trace id returned by the trace id resolver
+#[cfg(feature = "tracing")]
+pub const TRACE_ID_KEY: &str = "trace.id";
+
+/// Function returning the distributed trace id of the current span, if any
+#[cfg(feature = "tracing")]
+pub type TraceIdResolver = fn() -> Option<String>;
+
+#[cfg(feature = "tracing")]
+static TRACE_ID_RESOLVER: OnceLock<TraceIdResolver> = OnceLock::new();
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    static TASK_SCOPE: ErrorContext;
+}
+
+thread_local! {
+    static THREAD_SCOPES: RefCell<Vec<ErrorContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `future` with `context` as the error context scope of the current task
+#[cfg(feature = "tokio")]
+pub async fn scope<F: Future>(context: ErrorContext, future: F) -> F::Output {
+    TASK_SCOPE.scope(nested(context), future).await
+}
+
+/// Carry the scope active at the call site into `future`, e.g. before `tokio::spawn`
+#[cfg(feature = "tokio")]
+pub fn propagate<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let captured = current();
+    async move {
+        match captured {
+            Some(context) => TASK_SCOPE.scope(context, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Scope active when an `AklypseError` was built, filled in by its snafu selector
+#[derive(Debug, Clone, Default)]
+pub struct ScopeCapture(Option<Arc<ErrorContext>>);
+
+impl ScopeCapture {
+    /// Context of the captured scope, `None` when the error was built outside of any
+    pub fn context(&self) -> Option<&ErrorContext> {
+        self.0.as_deref()
+    }
+
+    /// Take the captured context, leaving nothing captured
+    pub fn take(&mut self) -> Option<ErrorContext> {
+        self.0.take().map(Arc::unwrap_or_clone)
+    }
+}
+
+impl snafu::GenerateImplicitData for ScopeCapture {
+    fn generate() -> Self {
+        Self(current().map(Arc::new))
+    }
+}
+
+/// Run `f` with `context` as the error context scope of the current thread
+pub fn scope_sync<R>(context: ErrorContext, f: impl FnOnce() -> R) -> R {
+    let context = nested(context);
+    THREAD_SCOPES.with(|scopes| scopes.borrow_mut().push(context));
+    // Pops the scope even when `f` unwinds
+    let _guard = ThreadScopeGuard;
+    f()
+}
+
+/// Context of the innermost active scope, if any
+pub fn current() -> Option<ErrorContext> {
+    let thread_scope = THREAD_SCOPES.with(|scopes| scopes.borrow().last().cloned());
+    #[cfg(feature = "tokio")]
+    let thread_scope = thread_scope.or_else(|| TASK_SCOPE.try_with(ErrorContext::clone).ok());
+    thread_scope
+}
+
+/// Whether a scope is active on the current task or thread
+pub fn is_active() -> bool {
+    current().is_some()
+}
+
+/// Fill the correlation id, component, tags and metadata `context` leaves unset from the active scope
+pub fn apply_current(context: &mut ErrorContext) {
+    if let Some(scope) = current() {
+        inherit(context, &scope);
+    }
+}
+
+/// Install the resolver used to record trace ids; only the first call takes effect
+///
+/// Returns false when a resolver was already installed.
+#[cfg(feature = "tracing")]
+pub fn set_trace_id_resolver(resolver: TraceIdResolver) -> bool {
+    TRACE_ID_RESOLVER.set(resolver).is_ok()
+}
+
+#[cfg(feature = "tracing")]
+impl ErrorContext {
+    /// Create a context describing the current `tracing` span
+    ///
+    /// The message is the span name (empty outside of any span).
+    pub fn from_current_span() -> Self {
+        let name = tracing::Span::current().metadata().map(|metadata| metadata.name()).unwrap_or_default();
+        ErrorContext::new(name).with_current_span()
+    }
+
+    /// Record the current span's name, target and ids into the metadata
+    ///
+    /// Keys already present are kept, and nothing is recorded outside of a span.
+    pub fn with_current_span(mut self) -> Self {
+        let span = tracing::Span::current();
+        let Some(metadata) = span.metadata() else {
+            return self;
+        };
+
+        let mut fields = vec![
+            (SPAN_NAME_KEY, metadata.name().to_string()),
+            (SPAN_TARGET_KEY, metadata.target().to_string()),
+        ];
+        if let Some(id) = span.id() {
+            fields.push((SPAN_ID_KEY, format!("{:016x}", id.into_u64())));
+        }
+        if let Some(trace_id) = TRACE_ID_RESOLVER.get().and_then(|resolve| resolve()) {
+            fields.push((TRACE_ID_KEY, trace_id));
+        }
+
+        for (key, value) in fields {
+            self.metadata_mut().entry(key.to_string()).or_insert(value);
+        }
+        self
+    }
+}
+
+struct ThreadScopeGuard;
+
+impl Drop for ThreadScopeGuard {
+    fn drop(&mut self) {
+        THREAD_SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+// Merge a newly entered scope with the one enclosing it
+fn nested(context: ErrorContext) -> ErrorContext {
+    #[cfg(feature = "tracing")]
+    let context = context.with_current_span();
+    let mut context = context;
+    apply_current(&mut context);
+    context
+}
+
+fn inherit(context: &mut ErrorContext, scope: &ErrorContext) {
+    if context.correlation_id.is_none() {
+        context.correlation_id = scope.correlation_id.clone();
+    }
+    if context.component.is_none() {
+        context.component = scope.component.clone();
+    }
+    for tag in scope.tags.iter() {
+        if !context.tags.contains(tag) {
+            context.tags_mut().push(tag.clone());
+        }
+    }
+    for (key, value) in scope.metadata.iter() {
+        context.metadata_mut().entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{AklypseError, InternalSnafu, ResultExt};
+
+    fn request_scope() -> ErrorContext {
+        ErrorContext::new("handling request")
+            .with_correlation_id("req-42")
+            .with_component("api")
+            .with_metadata("tenant", "acme")
+    }
+
+    fn failing() -> Result<(), AklypseError> {
+        Err(InternalSnafu { message: "boom".to_string(), source: None }.build())
+    }
+
+    #[test]
+    fn test_sync_scope_attaches_to_added_context() {
+        let err = scope_sync(request_scope(), || failing().context_msg("loading user").unwrap_err());
+
+        let context = err.get_rich_context().unwrap();
+        assert_eq!(&*context.message, "loading user");
+        assert_eq!(context.correlation_id.as_deref(), Some("req-42"));
+        assert_eq!(context.component.as_deref(), Some("api"));
+        assert_eq!(context.metadata.get("tenant").map(String::as_str), Some("acme"));
+        assert!(!is_active());
+    }
+
+    #[test]
+    fn test_nested_scopes_inherit_and_override() {
+        let inner = ErrorContext::new("db").with_component("storage");
+        let context = scope_sync(request_scope(), || scope_sync(inner, || current().unwrap()));
+
+        assert_eq!(context.component.as_deref(), Some("storage"));
+        assert_eq!(context.correlation_id.as_deref(), Some("req-42"));
+    }
+
+    #[test]
+    fn test_scope_is_popped_on_panic() {
+        let result = std::panic::catch_unwind(|| scope_sync(request_scope(), || panic!("boom")));
+        assert!(result.is_err());
+        assert!(current().is_none());
+    }
+
+    #[test]
+    fn test_errors_capture_the_scope_they_are_built_in() {
+        let bare = scope_sync(request_scope(), || failing().unwrap_err());
+        assert_eq!(bare.get_rich_context().unwrap().correlation_id.as_deref(), Some("req-42"));
+        assert!(!matches!(bare, AklypseError::WithRichContext { .. }));
+        // Clones keep the original's scope, wherever they are made
+        let cloned = scope_sync(ErrorContext::new("other").with_correlation_id("req-7"), || bare.clone());
+        assert_eq!(cloned.get_rich_context().unwrap().correlation_id.as_deref(), Some("req-42"));
+
+        // Attached later, outside of the scope
+        let layered = bare.with_scope_context();
+        assert!(matches!(&layered, AklypseError::WithRichContext { context, .. } if context.correlation_id.as_deref() == Some("req-42")));
+
+        let outside = failing().unwrap_err().with_scope_context();
+        assert!(outside.get_rich_context().is_none());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_task_scope_and_propagation() {
+        let err = scope(request_scope(), async {
+            let spawned = tokio::spawn(propagate(async { failing().context_msg("in task").unwrap_err() }));
+            spawned.await.unwrap()
+        })
+        .await;
+
+        assert_eq!(err.get_rich_context().unwrap().correlation_id.as_deref(), Some("req-42"));
+        assert!(current().is_none());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_context_from_current_span() {
+        tracing::subscriber::with_default(tracing_subscriber::registry(), || {
+            assert!(ErrorContext::from_current_span().metadata.is_empty());
+
+            let span = tracing::info_span!(target: "billing", "charge_card");
+            let _entered = span.enter();
+            let context = ErrorContext::from_current_span();
+
+            assert_eq!(&*context.message, "charge_card");
+            assert_eq!(context.metadata.get(SPAN_TARGET_KEY).map(String::as_str), Some("billing"));
+            assert!(context.metadata.contains_key(SPAN_ID_KEY));
+
+            let err = scope_sync(request_scope(), || failing().context_msg("charging").unwrap_err());
+            let metadata = &err.get_rich_context().unwrap().metadata;
+            assert_eq!(metadata.get(SPAN_NAME_KEY).map(String::as_str), Some("charge_card"));
+        });
+    }
+}
@@ -0,0 +1,416 @@
+/* src/common/error/decrust/applier.rs */
+#![warn(missing_docs)]
+//! **Brief:** Application of Decrust fixes to the files of a project.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Fix Application]
+//!  - [Atomic File Updates]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `FixApplier` carries out the `FixDetails` Decrust suggests that edit a
+//! file: `TextReplace`, `AddImport` and `AddCargoDependency` (which edits the
+//! `Cargo.toml` of the project root). Relative paths are resolved against the
+//! root, as cargo reports them; absolute paths, and paths whose `..`
+//! components lead out of the root, are rejected so that spans pointing into
+//! dependencies or the toolchain are never rewritten. The check is lexical:
+//! symbolic links inside the root are followed as they are.
+//!
+//! A change is written to a temporary file next to the target and renamed
+//! over it, so the target is never left half written; the previous contents
+//! are first saved to a backup file unless backups are turned off. In dry-run
+//! mode nothing is written and the returned `AppliedFix` only reports what
+//! would have changed. A `TextReplace` whose `original_text_snippet` no
+//! longer matches the file fails with `StateConflict` rather than editing
+//! code that moved since the diagnostic.
+
+use super::super::types::{Autocorrection, FixDetails};
+use super::super::{Result, StateConflictSnafu, ValidationSnafu};
+use crate::common::utils::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Outcome of applying one fix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFix {
+    /// File the fix edits
+    pub file_path: PathBuf,
+    /// Whether the fix changes the file; false when it was already applied
+    pub changed: bool,
+    /// Whether the file was left untouched because of dry-run mode
+    pub dry_run: bool,
+    /// Where the previous contents were saved, if a backup was written
+    pub backup_path: Option<PathBuf>,
+    /// Contents before the fix
+    pub original: String,
+    /// Contents after the fix
+    pub updated: String,
+}
+
+/// Applies file-editing `FixDetails` below a project root.
+#[derive(Debug, Clone)]
+pub struct FixApplier {
+    root: PathBuf,
+    dry_run: bool,
+    backup_extension: Option<String>,
+}
+
+impl FixApplier {
+    /// Applier for the project at `root`, writing `.orig` backups
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into(), dry_run: false, backup_extension: Some("orig".to_string()) }
+    }
+
+    /// Only report the changes, without writing anything
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the extension appended to backup file names, or turn backups off with `None`
+    pub fn with_backup_extension(mut self, extension: Option<&str>) -> Self {
+        self.backup_extension = extension.map(str::to_string);
+        self
+    }
+
+    /// Apply the details of `correction`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Validation` error if the correction has no details.
+    pub fn apply_autocorrection(&self, correction: &Autocorrection) -> Result<AppliedFix> {
+        match &correction.details {
+            Some(details) => self.apply(details),
+            None => ValidationSnafu { field: "details", message: format!("'{}' has no fix details", correction.description) }.fail(),
+        }
+    }
+
+    /// Apply `details` to the file it edits
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Validation` error for fixes that do not edit a file, whose
+    /// file lies outside the root, or with positions outside of the file, a `StateConflict` error if the replaced text
+    /// differs from `original_text_snippet`, and an `Io` error if the file
+    /// cannot be read or written.
+    pub fn apply(&self, details: &FixDetails) -> Result<AppliedFix> {
+        let (path, original, updated) = match details {
+            FixDetails::TextReplace { file_path, .. } => {
+                let path = self.resolve(Path::new(file_path))?;
+                let original = fs::read_to_string(&path)?;
+                let updated = replaced(&original, details)?;
+                (path, original, updated)
+            }
+            FixDetails::AddImport { file_path, import } => {
+                let path = self.resolve(Path::new(file_path))?;
+                let original = fs::read_to_string(&path)?;
+                let updated = with_import(&original, import);
+                (path, original, updated)
+            }
+            FixDetails::AddCargoDependency { dependency, version, features, is_dev_dependency } => {
+                let path = self.root.join("Cargo.toml");
+                let original = fs::read_to_string(&path)?;
+                let section = if *is_dev_dependency { "[dev-dependencies]" } else { "[dependencies]" };
+                let updated = with_dependency(&original, section, dependency, &dependency_value(version, features));
+                (path, original, updated)
+            }
+            FixDetails::ExecuteCommand { .. } | FixDetails::SuggestCodeChange { .. } => {
+                return ValidationSnafu { field: "details", message: format!("{:?} does not edit a file", details) }.fail();
+            }
+        };
+
+        let changed = original != updated;
+        let backup_path = if changed && !self.dry_run { self.write(&path, &original, &updated)? } else { None };
+        Ok(AppliedFix { file_path: path, changed, dry_run: self.dry_run, backup_path, original, updated })
+    }
+
+    // `file_path` below the root, refusing absolute paths and `..` leading out of it
+    fn resolve(&self, file_path: &Path) -> Result<PathBuf> {
+        let outside = || {
+            ValidationSnafu {
+                field: "file_path",
+                message: format!("'{}' is outside the project root '{}'", file_path.display(), self.root.display()),
+            }
+            .fail()
+        };
+        let mut relative = PathBuf::new();
+        for component in file_path.components() {
+            match component {
+                Component::Normal(part) => relative.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !relative.pop() {
+                        return outside();
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return outside(),
+            }
+        }
+        Ok(self.root.join(relative))
+    }
+
+    // Back up `original`, then replace the file through a renamed temporary file
+    fn write(&self, path: &Path, original: &str, updated: &str) -> Result<Option<PathBuf>> {
+        let backup = self.backup_extension.as_deref().map(|extension| sibling(path, "", &format!(".{}", extension)));
+        if let Some(backup) = &backup {
+            fs::write(backup, original)?;
+        }
+        let temporary = sibling(path, ".", ".decrust.tmp");
+        fs::write(&temporary, updated)?;
+        if let Err(error) = fs::rename(&temporary, path) {
+            let _ = fs::remove_file(&temporary);
+            return Err(error);
+        }
+        Ok(backup)
+    }
+}
+
+/// `contents` with the replacement of a `FixDetails::TextReplace` made
+///
+/// # Errors
+///
+/// Returns a `Validation` error for other fixes or positions outside of
+/// `contents`, and a `StateConflict` error if the replaced text differs from
+/// `original_text_snippet`.
+pub fn replaced(contents: &str, details: &FixDetails) -> Result<String> {
+    let FixDetails::TextReplace {
+        file_path,
+        line_start,
+        column_start,
+        line_end,
+        column_end,
+        original_text_snippet,
+        replacement_text,
+    } = details
+    else {
+        return ValidationSnafu { field: "details", message: format!("{:?} is not a text replacement", details) }.fail();
+    };
+
+    let (start, end) = match (offset(contents, *line_start, *column_start), offset(contents, *line_end, *column_end)) {
+        (Some(start), Some(end)) if start <= end => (start, end),
+        _ => {
+            return ValidationSnafu {
+                field: "position",
+                message: format!("{}:{}-{}:{} is outside of {}", line_start, column_start, line_end, column_end, file_path.display()),
+            }
+            .fail()
+        }
+    };
+    if let Some(expected) = original_text_snippet.as_deref().filter(|expected| *expected != &contents[start..end]) {
+        return StateConflictSnafu {
+            message: format!(
+                "{} changed at {}:{}: expected {:?}, found {:?}",
+                file_path.display(),
+                line_start,
+                column_start,
+                expected,
+                &contents[start..end]
+            ),
+        }
+        .fail();
+    }
+    Ok(format!("{}{}{}", &contents[..start], replacement_text, &contents[end..]))
+}
+
+// `path` with its file name wrapped in `prefix` and `suffix`
+fn sibling(path: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!("{}{}{}", prefix, name, suffix))
+}
+
+// Byte offset of a 1-based line and character column; the column may point just past the line
+fn offset(text: &str, line: usize, column: usize) -> Option<usize> {
+    if line == 0 || column == 0 {
+        return None;
+    }
+    let mut start = 0;
+    for _ in 1..line {
+        start += text[start..].find('\n')? + 1;
+    }
+    let line_text = &text[start..text[start..].find('\n').map_or(text.len(), |end| start + end)];
+    line_text
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain(std::iter::once(line_text.len()))
+        .nth(column - 1)
+        .map(|index| start + index)
+}
+
+// `text` with `use <import>;` after its last top-level import, unless it has it already
+fn with_import(text: &str, import: &str) -> String {
+    let import = import.trim().trim_start_matches("use ").trim_end_matches(';');
+    let statement = format!("use {};", import);
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.iter().any(|line| line.trim() == statement) {
+        return text.to_string();
+    }
+
+    // After the last `use` declaration, or else after the leading comments and inner attributes
+    let at = match lines.iter().rposition(|line| line.starts_with("use ") || line.starts_with("pub use ")) {
+        Some(last) => lines[last..].iter().position(|line| line.trim_end().ends_with(';')).map_or(lines.len(), |end| last + end + 1),
+        None => lines.iter().take_while(|line| line.starts_with("//") || line.starts_with("#![") || line.trim().is_empty()).count(),
+    };
+    let mut updated: Vec<&str> = lines[..at].to_vec();
+    updated.push(&statement);
+    updated.extend_from_slice(&lines[at..]);
+    let mut updated = updated.join("\n");
+    if text.ends_with('\n') || text.is_empty() {
+        updated.push('\n');
+    }
+    updated
+}
+
+fn dependency_value(version: &str, features: &[String]) -> String {
+    if features.is_empty() {
+        format!("\"{}\"", version)
+    } else {
+        let features: Vec<String> = features.iter().map(|feature| format!("\"{}\"", feature)).collect();
+        format!("{{ version = \"{}\", features = [{}] }}", version, features.join(", "))
+    }
+}
+
+// `manifest` with `name = value` at the end of `section`, unless the section names it already
+fn with_dependency(manifest: &str, section: &str, name: &str, value: &str) -> String {
+    let entry = format!("{} = {}", name, value);
+    let lines: Vec<&str> = manifest.lines().collect();
+    let Some(header) = lines.iter().position(|line| line.trim() == section) else {
+        let separator = if manifest.is_empty() || manifest.ends_with("\n\n") { "" } else if manifest.ends_with('\n') { "\n" } else { "\n\n" };
+        return format!("{}{}{}\n{}\n", manifest, separator, section, entry);
+    };
+
+    let body_end = lines[header + 1..].iter().position(|line| line.trim_start().starts_with('[')).map_or(lines.len(), |end| header + 1 + end);
+    let declared = lines[header + 1..body_end].iter().any(|line| {
+        line.split_once('=').is_some_and(|(key, _)| key.trim().trim_matches('"') == name)
+    });
+    if declared {
+        return manifest.to_string();
+    }
+
+    let at = header + 1 + lines[header + 1..body_end].iter().rposition(|line| !line.trim().is_empty()).map_or(0, |last| last + 1);
+    let mut updated: Vec<&str> = lines[..at].to_vec();
+    updated.push(&entry);
+    updated.extend_from_slice(&lines[at..]);
+    let mut updated = updated.join("\n");
+    if manifest.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::AklypseError;
+    use crate::common::utils::tempres::TempDirGuard;
+
+    const SOURCE: &str = "//! Prices\n\nuse std::fmt;\n\nfn total() -> u32 {\n    let x = 1u8;\n    x\n}\n";
+
+    fn replace(line: usize, original: Option<&str>) -> FixDetails {
+        FixDetails::TextReplace {
+            file_path: PathBuf::from("src/lib.rs"),
+            line_start: line,
+            column_start: 13,
+            line_end: line,
+            column_end: 16,
+            original_text_snippet: original.map(str::to_string),
+            replacement_text: "1u32".to_string(),
+        }
+    }
+
+    fn project() -> TempDirGuard {
+        let dir = TempDirGuard::new("aklypse-decrust-").unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), SOURCE).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\n\n[features]\ndefault = []\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_text_replace_writes_backup_and_honours_dry_run() {
+        let dir = project();
+        let path = dir.path().join("src/lib.rs");
+
+        let preview = FixApplier::new(dir.path()).with_dry_run(true).apply(&replace(6, Some("1u8"))).unwrap();
+        assert!(preview.changed && preview.dry_run);
+        assert!(preview.updated.contains("let x = 1u32;"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), SOURCE);
+
+        let applied = FixApplier::new(dir.path()).apply(&replace(6, Some("1u8"))).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), applied.updated);
+        assert_eq!(applied.backup_path, Some(dir.path().join("src/lib.rs.orig")));
+        assert_eq!(fs::read_to_string(dir.path().join("src/lib.rs.orig")).unwrap(), SOURCE);
+        assert!(!dir.path().join("src/.lib.rs.decrust.tmp").exists());
+
+        // The snippet no longer matches what is in the file
+        let error = FixApplier::new(dir.path()).apply(&replace(6, Some("1u8"))).unwrap_err();
+        assert!(matches!(error, AklypseError::StateConflict { .. }));
+        assert!(matches!(FixApplier::new(dir.path()).apply(&replace(40, None)).unwrap_err(), AklypseError::Validation { .. }));
+    }
+
+    #[test]
+    fn test_paths_outside_the_root_are_rejected() {
+        let dir = project();
+        let applier = FixApplier::new(dir.path().join("src"));
+        let at = |file_path: &Path| match replace(6, Some("1u8")) {
+            FixDetails::TextReplace { line_start, column_start, line_end, column_end, original_text_snippet, replacement_text, .. } => {
+                FixDetails::TextReplace { file_path: file_path.to_path_buf(), line_start, column_start, line_end, column_end, original_text_snippet, replacement_text }
+            }
+            _ => unreachable!(),
+        };
+
+        let absolute = dir.path().join("src/lib.rs");
+        let escaping = Path::new("nested/../../Cargo.toml");
+        for file_path in [absolute.as_path(), escaping] {
+            let error = applier.apply(&at(file_path)).unwrap_err();
+            assert!(matches!(&error, AklypseError::Validation { field, .. } if field == "file_path"), "{:?}", error);
+        }
+        let import = FixDetails::AddImport { file_path: "../src/lib.rs".to_string(), import: "std::io".to_string() };
+        assert!(applier.apply(&import).is_err());
+        assert_eq!(fs::read_to_string(&absolute).unwrap(), SOURCE);
+
+        // `..` staying below the root is fine
+        assert!(applier.with_dry_run(true).apply(&at(Path::new("./sub/../lib.rs"))).unwrap().changed);
+    }
+
+    #[test]
+    fn test_add_import_is_idempotent() {
+        let dir = project();
+        let applier = FixApplier::new(dir.path()).with_backup_extension(None);
+        let import = FixDetails::AddImport { file_path: "src/lib.rs".to_string(), import: "std::collections::HashMap".to_string() };
+
+        let applied = applier.apply(&import).unwrap();
+        assert!(applied.changed);
+        assert_eq!(applied.backup_path, None);
+        assert!(applied.updated.starts_with("//! Prices\n\nuse std::fmt;\nuse std::collections::HashMap;\n\nfn total()"));
+        assert!(!applier.apply(&import).unwrap().changed);
+
+        assert_eq!(with_import("fn main() {}\n", "use std::io;"), "use std::io;\nfn main() {}\n");
+        assert_eq!(with_import("//! Docs\n#![deny(warnings)]\nfn main() {}", "std::io"), "//! Docs\n#![deny(warnings)]\nuse std::io;\nfn main() {}");
+    }
+
+    #[test]
+    fn test_add_cargo_dependency() {
+        let dir = project();
+        let applier = FixApplier::new(dir.path());
+        let dependency = |name: &str, dev| FixDetails::AddCargoDependency {
+            dependency: name.to_string(),
+            version: "1".to_string(),
+            features: if name == "tokio" { vec!["full".to_string()] } else { Vec::new() },
+            is_dev_dependency: dev,
+        };
+
+        applier.apply(&dependency("tokio", false)).unwrap();
+        applier.apply(&dependency("proptest", true)).unwrap();
+        assert!(!applier.apply(&dependency("serde", false)).unwrap().changed);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"app\"\n\n[dependencies]\nserde = \"1\"\ntokio = { version = \"1\", features = [\"full\"] }\n\n[features]\ndefault = []\n\n[dev-dependencies]\nproptest = \"1\"\n"
+        );
+
+        let command = FixDetails::ExecuteCommand { command: "cargo".to_string(), args: Vec::new(), working_directory: None };
+        assert!(matches!(applier.apply(&command).unwrap_err(), AklypseError::Validation { .. }));
+    }
+}
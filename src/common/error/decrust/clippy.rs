@@ -0,0 +1,200 @@
+/* src/common/error/decrust/clippy.rs */
+#![warn(missing_docs)]
+//! **Brief:** Clippy lint findings as Decrust autocorrections.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Lint Integration]
+//!  - [Fix Providers]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `cargo clippy --message-format=json` reports lints in the same format as
+//! compiler errors, with the lint name as code (`clippy::needless_return`).
+//! `parse_clippy_json` reads the lint findings of such output and
+//! `ClippyProvider` answers them: a replacement clippy marked
+//! machine-applicable becomes a `FixDetails::TextReplace`, anything else a
+//! `FixDetails::SuggestCodeChange` carrying the lint's help. `lint_fix_type`
+//! gives the `FixType` of each replacement, so a caller can auto-apply plain
+//! rewrites while leaving refactorings and Cargo.toml changes to a person.
+//! A help-only suggestion is never a `TextReplacement`: lints that would be
+//! one are marked `ManualInterventionRequired` when clippy has no rewrite.
+//!
+//! The provider is registered by `Decrust::new`, and the generic compiler and
+//! diagnostic providers leave clippy lints to it.
+
+use super::parser::{parse_cargo_json, CompilerDiagnostic, MachineApplicableSuggestion};
+use super::providers::FixProvider;
+use super::super::types::{Autocorrection, FixDetails, FixType};
+use super::super::{AklypseError, Result};
+use super::AutocorrectableError;
+use std::path::PathBuf;
+
+/// Prefix of the codes of clippy lints
+pub const CLIPPY_LINT_PREFIX: &str = "clippy::";
+
+// Prefixes of lints replacing hand-written code by a std method
+const ALTERNATIVE_METHOD_PREFIXES: &[&str] = &["manual_", "map_", "iter_", "option_map_", "unnecessary_"];
+
+/// Kind of change a lint asks for when clippy supplies the rewrite
+pub fn lint_fix_type(code: &str) -> FixType {
+    let lint = code.strip_prefix(CLIPPY_LINT_PREFIX).unwrap_or(code);
+    match lint {
+        "cargo_common_metadata" | "multiple_crate_versions" | "wildcard_dependencies" | "negative_feature_names"
+        | "redundant_feature_names" => FixType::UpdateCargoToml,
+        "too_many_arguments" | "too_many_lines" | "cognitive_complexity" | "type_complexity" | "large_enum_variant"
+        | "result_large_err" | "module_inception" => FixType::Refactor,
+        "missing_docs_in_private_items" | "missing_errors_doc" | "missing_panics_doc" | "missing_safety_doc"
+        | "unwrap_used" | "expect_used" | "panic" | "todo" | "unimplemented" => FixType::ManualInterventionRequired,
+        "or_fun_call" | "expect_fun_call" | "unwrap_or_default" => FixType::SuggestAlternativeMethod,
+        _ if ALTERNATIVE_METHOD_PREFIXES.iter().any(|prefix| lint.starts_with(prefix)) => FixType::SuggestAlternativeMethod,
+        _ => FixType::TextReplacement,
+    }
+}
+
+/// Whether `error` carries the diagnostic of a clippy lint
+pub fn is_clippy_lint(error: &AklypseError) -> bool {
+    error
+        .get_diagnostic_info()
+        .and_then(|diag_info| diag_info.diagnostic_code.as_deref())
+        .is_some_and(|code| code.starts_with(CLIPPY_LINT_PREFIX))
+}
+
+/// Parse `cargo clippy --message-format=json` output, keeping the clippy lints
+///
+/// # Errors
+///
+/// Returns a `Parse` error if a line of `output` is not valid JSON.
+pub fn parse_clippy_json(output: &str) -> Result<Vec<CompilerDiagnostic>> {
+    Ok(parse_cargo_json(output)?
+        .into_iter()
+        .filter(|diagnostic| diagnostic.result.diagnostic_code.as_deref().is_some_and(|code| code.starts_with(CLIPPY_LINT_PREFIX)))
+        .collect())
+}
+
+/// Turns clippy lint findings into autocorrections
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClippyProvider;
+
+impl FixProvider for ClippyProvider {
+    fn name(&self) -> &str {
+        "clippy"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        is_clippy_lint(error)
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        let Some(diag_info) = error.get_diagnostic_info() else {
+            return Vec::new();
+        };
+        let code = diag_info.diagnostic_code.clone().unwrap_or_default();
+        let fix_type = lint_fix_type(&code);
+
+        let mut corrections: Vec<Autocorrection> = error
+            .get_attachment::<Vec<MachineApplicableSuggestion>>()
+            .map(|suggestions| suggestions.iter().filter_map(MachineApplicableSuggestion::to_autocorrection).collect())
+            .unwrap_or_default();
+        for correction in &mut corrections {
+            correction.fix_type = fix_type.clone();
+        }
+
+        // Lints without a safe rewrite point at the code together with their help
+        if corrections.is_empty() {
+            let location = diag_info.primary_location.as_ref();
+            let help = diag_info.suggested_fixes.join("\n");
+            let message = diag_info.original_message.clone().unwrap_or_default();
+            corrections.push(Autocorrection {
+                description: format!("{}: {}", code, if help.is_empty() { &message } else { &help }),
+                // Only the help text is known, there is nothing to replace
+                fix_type: if fix_type == FixType::TextReplacement { FixType::ManualInterventionRequired } else { fix_type },
+                confidence: 0.6,
+                details: location.map(|location| FixDetails::SuggestCodeChange {
+                    file_path: PathBuf::from(&location.file),
+                    line_hint: location.line as usize,
+                    suggested_code_snippet: help,
+                    explanation: message,
+                }),
+                diff_suggestion: None,
+                commands_to_apply: vec![],
+                targets_error_code: None,
+            });
+        }
+
+        for correction in &mut corrections {
+            correction.targets_error_code = Some(code.clone());
+        }
+        corrections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::Decrust;
+
+    // A lint clippy can rewrite on its own, one it cannot, and a rustc warning
+    const CLIPPY_OUTPUT: &str = concat!(
+        r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","code":{"code":"clippy::needless_return","explanation":null},"level":"warning","#,
+        r#""spans":[{"file_name":"src/lib.rs","line_start":2,"line_end":2,"column_start":5,"column_end":14,"is_primary":true,"expansion":null}],"#,
+        r#""children":[{"message":"remove `return`","level":"help","spans":[{"file_name":"src/lib.rs","line_start":2,"line_end":2,"#,
+        r#""column_start":5,"column_end":13,"suggested_replacement":"x","suggestion_applicability":"MachineApplicable","#,
+        r#""text":[{"text":"    return x;","highlight_start":5,"highlight_end":13}]}]}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"this function has too many arguments (9/7)","code":{"code":"clippy::too_many_arguments","explanation":null},"level":"warning","#,
+        r#""spans":[{"file_name":"src/lib.rs","line_start":5,"line_end":5,"column_start":1,"column_end":60,"is_primary":true,"expansion":null}],"#,
+        r#""children":[{"message":"for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#too_many_arguments","level":"help","spans":[]}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"unused variable: `y`","code":{"code":"unused_variables","explanation":null},"level":"warning","#,
+        r#""spans":[{"file_name":"src/lib.rs","line_start":9,"line_end":9,"column_start":9,"column_end":10,"is_primary":true,"expansion":null}],"children":[]}}"#,
+    );
+
+    #[test]
+    fn test_lint_fix_types() {
+        assert_eq!(lint_fix_type("clippy::needless_return"), FixType::TextReplacement);
+        assert_eq!(lint_fix_type("clippy::manual_map"), FixType::SuggestAlternativeMethod);
+        assert_eq!(lint_fix_type("clippy::too_many_arguments"), FixType::Refactor);
+        assert_eq!(lint_fix_type("clippy::wildcard_dependencies"), FixType::UpdateCargoToml);
+        assert_eq!(lint_fix_type("clippy::unwrap_used"), FixType::ManualInterventionRequired);
+    }
+
+    #[test]
+    fn test_lint_findings_become_autocorrections() {
+        let lints = parse_clippy_json(CLIPPY_OUTPUT).unwrap();
+        assert_eq!(lints.len(), 2);
+
+        let decrust = Decrust::new();
+        let error = lints[0].clone().into_error();
+        let suggestions = decrust.suggest_autocorrections(&error, Some("fn f(x: u8) -> u8 {\n    return x;\n}\n"));
+        // The help line is not offered again as a guessed replacement
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].fix_type, FixType::TextReplacement);
+        assert_eq!(suggestions[0].confidence, 0.95);
+        assert_eq!(suggestions[0].targets_error_code.as_deref(), Some("clippy::needless_return"));
+        assert!(suggestions[0].diff_suggestion.as_deref().unwrap().contains("-    return x;\n+    x;\n"));
+
+        let fixes = decrust.suggest_lint_fixes(CLIPPY_OUTPUT).unwrap();
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[1].fix_type, FixType::Refactor);
+        assert!(matches!(fixes[1].details, Some(FixDetails::SuggestCodeChange { line_hint: 5, .. })));
+    }
+
+    #[test]
+    fn test_help_only_rewrites_are_not_applicable() {
+        let output = concat!(
+            r#"{"reason":"compiler-message","message":{"message":"the loop variable `i` is only used to index `v`","code":{"code":"clippy::needless_range_loop","explanation":null},"level":"warning","#,
+            r#""spans":[{"file_name":"src/lib.rs","line_start":3,"line_end":3,"column_start":14,"column_end":26,"is_primary":true,"expansion":null}],"#,
+            r#""children":[{"message":"consider using an iterator: `for <item> in &v`","level":"help","spans":[]}]}}"#,
+        );
+        assert_eq!(lint_fix_type("clippy::needless_range_loop"), FixType::TextReplacement);
+
+        let fixes = Decrust::new().suggest_lint_fixes(output).unwrap();
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].fix_type, FixType::ManualInterventionRequired);
+        assert!(matches!(fixes[0].details, Some(FixDetails::SuggestCodeChange { line_hint: 3, .. })));
+    }
+}
@@ -0,0 +1,281 @@
+/* src/common/error/decrust/diff.rs */
+#![warn(missing_docs)]
+//! **Brief:** Unified diffs previewing Decrust text replacements.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Diff Rendering]
+//!  - [Terminal Output]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `unified_diff` compares two versions of a file line by line and renders
+//! the differences in the unified format read by `patch` and `git apply`,
+//! with `context` unchanged lines around each hunk. `text_replace_diff` does
+//! so for a `FixDetails::TextReplace` against the current contents of its
+//! file; this is what `Decrust::suggest_autocorrection` stores in
+//! `Autocorrection::diff_suggestion`.
+//!
+//! `colorize` adds ANSI colors for terminals: removed lines red, added lines
+//! green, hunk headers cyan.
+
+use super::super::theme::{paint, Color};
+use super::super::types::FixDetails;
+use super::super::Result;
+use super::applier::replaced;
+use std::path::Path;
+
+/// Unchanged lines shown around each hunk, as `diff -u` does
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+// One line of the comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Unified diff turning `original` into `updated`, empty if they are equal
+pub fn unified_diff(path: &Path, original: &str, updated: &str, context: usize) -> String {
+    let old: Vec<&str> = original.split_inclusive('\n').collect();
+    let new: Vec<&str> = updated.split_inclusive('\n').collect();
+    let lines = compare(&old, &new);
+    let changes: Vec<usize> = lines.iter().enumerate().filter(|(_, line)| !matches!(line, Line::Same(_))).map(|(index, _)| index).collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Line numbers reached before each entry of `lines`
+    let mut positions = Vec::with_capacity(lines.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            Line::Same(_) => (old_line, new_line) = (old_line + 1, new_line + 1),
+            Line::Removed(_) => old_line += 1,
+            Line::Added(_) => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    let mut diff = format!("--- a/{}\n+++ b/{}\n", path.display(), path.display());
+    let mut next = 0;
+    while next < changes.len() {
+        // Changes closer than twice the context share a hunk
+        let mut last = next;
+        while last + 1 < changes.len() && changes[last + 1] - changes[last] <= 2 * context + 1 {
+            last += 1;
+        }
+        let start = changes[next].saturating_sub(context);
+        let end = (changes[last] + context + 1).min(lines.len());
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        ));
+        for line in &lines[start..end] {
+            let (marker, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+            diff.push(marker);
+            diff.push_str(text);
+            if !text.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+        next = last + 1;
+    }
+    diff
+}
+
+/// Unified diff of applying a `FixDetails::TextReplace` to `contents`
+///
+/// # Errors
+///
+/// Fails like `applier::replaced` when the replacement does not fit `contents`.
+pub fn text_replace_diff(details: &FixDetails, contents: &str) -> Result<String> {
+    let updated = replaced(contents, details)?;
+    let path = match details {
+        FixDetails::TextReplace { file_path, .. } => file_path.as_path(),
+        _ => Path::new(""),
+    };
+    Ok(unified_diff(path, contents, &updated, DEFAULT_CONTEXT_LINES))
+}
+
+/// `diff` with ANSI colors for display in a terminal
+pub fn colorize(diff: &str) -> String {
+    diff.split_inclusive('\n')
+        .map(|line| {
+            let (text, newline) = line.strip_suffix('\n').map_or((line, ""), |text| (text, "\n"));
+            let painted = if text.starts_with("+++") || text.starts_with("---") {
+                paint(text, Color::Default, true)
+            } else if text.starts_with("@@") {
+                paint(text, Color::Cyan, false)
+            } else if text.starts_with('+') {
+                paint(text, Color::Green, false)
+            } else if text.starts_with('-') {
+                paint(text, Color::Red, false)
+            } else if text.starts_with('\\') {
+                paint(text, Color::BrightBlack, false)
+            } else {
+                text.to_string()
+            };
+            painted + newline
+        })
+        .collect()
+}
+
+// `start,count` of a hunk, 1-based; an empty range names the line before it
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start),
+        1 => (start + 1).to_string(),
+        _ => format!("{},{}", start + 1, count),
+    }
+}
+
+// Line-level comparison through the shortest edit script of the differing middle
+fn compare<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (old_middle, new_middle) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    let mut lines: Vec<Line<'a>> = old[..prefix].iter().map(|line| Line::Same(line)).collect();
+    lines.extend(edit_script(old_middle, new_middle));
+    lines.extend(old[old.len() - suffix..].iter().map(|line| Line::Same(line)));
+    lines
+}
+
+// Myers' greedy search for the fewest removals and additions turning `old`
+// into `new`: O((n + m) * d) time and space for d differing lines, instead
+// of a table as large as both inputs multiplied
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = old.len() + new.len();
+    if max == 0 {
+        return Vec::new();
+    }
+    let offset = max as isize;
+    // furthest[offset + k]: furthest x reached on diagonal k = x - y; one snapshot per edit distance
+    let mut furthest = vec![0isize; 2 * max + 2];
+    let mut trace = Vec::new();
+    let moves_down = |furthest: &[isize], d: isize, k: isize| {
+        k == -d || (k != d && furthest[(offset + k - 1) as usize] < furthest[(offset + k + 1) as usize])
+    };
+    'search: for d in 0..=max as isize {
+        trace.push(furthest.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if moves_down(&furthest, d, k) { furthest[(offset + k + 1) as usize] } else { furthest[(offset + k - 1) as usize] + 1 };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                (x, y) = (x + 1, y + 1);
+            }
+            furthest[(offset + k) as usize] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk the snapshots back from the end, collecting the script in reverse
+    let mut script = Vec::with_capacity(max);
+    let (mut x, mut y) = (n, m);
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        let (d, k) = (d as isize, x - y);
+        let previous_k = if moves_down(furthest, d, k) { k + 1 } else { k - 1 };
+        let previous_x = furthest[(offset + previous_k) as usize];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            script.push(Line::Same(old[x as usize - 1]));
+            (x, y) = (x - 1, y - 1);
+        }
+        if d > 0 {
+            script.push(if x == previous_x { Line::Added(new[y as usize - 1]) } else { Line::Removed(old[x as usize - 1]) });
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    script.reverse();
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let original: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let updated = original.replace("line 2\n", "line two\n").replace("line 15\n", "").replace("line 20\n", "line 20");
+
+        let diff = unified_diff(Path::new("src/lib.rs"), &original, &updated, 2);
+        assert_eq!(
+            diff,
+            concat!(
+                "--- a/src/lib.rs\n+++ b/src/lib.rs\n",
+                "@@ -1,4 +1,4 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n",
+                "@@ -13,8 +13,7 @@\n line 13\n line 14\n-line 15\n line 16\n line 17\n line 18\n line 19\n",
+                "-line 20\n+line 20\n\\ No newline at end of file\n",
+            )
+        );
+        assert_eq!(unified_diff(Path::new("same.rs"), &original, &original, 3), "");
+        assert_eq!(unified_diff(Path::new("new.rs"), "", "fn main() {}\n", 3), "--- a/new.rs\n+++ b/new.rs\n@@ -0,0 +1 @@\n+fn main() {}\n");
+    }
+
+    #[test]
+    fn test_large_inputs_compare_without_a_full_table() {
+        let old: Vec<String> = (0..20_000).map(|n| format!("line {}\n", n)).collect();
+        let mut new = old.clone();
+        new[5_000] = "changed\n".to_string();
+        new.remove(12_000);
+        new.insert(15_000, "inserted\n".to_string());
+        let (old, new): (Vec<&str>, Vec<&str>) = (old.iter().map(String::as_str).collect(), new.iter().map(String::as_str).collect());
+
+        let lines = compare(&old, &new);
+        fn kept<'a>(line: &Line<'a>, removed: bool) -> Option<&'a str> {
+            match *line {
+                Line::Same(text) => Some(text),
+                Line::Removed(text) if removed => Some(text),
+                Line::Added(text) if !removed => Some(text),
+                _ => None,
+            }
+        }
+        assert_eq!(lines.iter().filter_map(|line| kept(line, true)).collect::<Vec<_>>(), old);
+        assert_eq!(lines.iter().filter_map(|line| kept(line, false)).collect::<Vec<_>>(), new);
+        assert_eq!(lines.iter().filter(|line| !matches!(line, Line::Same(_))).count(), 4);
+    }
+
+    #[test]
+    fn test_text_replace_diff_and_colors() {
+        let details = FixDetails::TextReplace {
+            file_path: PathBuf::from("src/main.rs"),
+            line_start: 2,
+            column_start: 13,
+            line_end: 2,
+            column_end: 16,
+            original_text_snippet: Some("1u8".to_string()),
+            replacement_text: "1u32".to_string(),
+        };
+        let contents = "fn main() {\n    let x = 1u8;\n}\n";
+        let diff = text_replace_diff(&details, contents).unwrap();
+        assert_eq!(
+            diff,
+            "--- a/src/main.rs\n+++ b/src/main.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    let x = 1u8;\n+    let x = 1u32;\n }\n"
+        );
+        assert!(text_replace_diff(&details, "fn main() {}\n").is_err());
+
+        let colored = colorize(&diff);
+        assert!(colored.contains("\x1b[31m-    let x = 1u8;\x1b[0m\n"));
+        assert!(colored.contains("\x1b[32m+    let x = 1u32;\x1b[0m\n"));
+        assert!(colored.contains("\x1b[36m@@ -1,3 +1,3 @@\x1b[0m\n"));
+        assert!(colored.ends_with(" }\n"));
+    }
+}
@@ -0,0 +1,248 @@
+/* src/common/error/decrust/parser.rs */
+#![warn(missing_docs)]
+//! **Brief:** Ingestion of cargo's JSON build output for the Decrust engine.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Compiler Output Parsing]
+//!  - [Machine-Applicable Suggestions]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `parse_cargo_json` reads the output of `cargo build --message-format=json`
+//! (or rustc's `--error-format=json`) into one `CompilerDiagnostic` per
+//! top-level diagnostic. Besides the `DiagnosticResult` produced by
+//! `DiagnosticResult::parse_from_json`, it keeps the rustc level and the
+//! suggestions rustc marked `MachineApplicable`, with their exact spans.
+//!
+//! `CompilerDiagnostic::into_error` turns a diagnostic into an `AklypseError`
+//! carrying both, so `Decrust::suggest_autocorrection` answers it with a
+//! precise `FixDetails::TextReplace` instead of a guess from the primary
+//! location. Spans follow rustc: lines and columns are 1-based and the end
+//! column is excluded.
+
+use super::super::diagnostics::{diagnostic_from_json, json_diagnostics};
+use super::super::report::JsonValue;
+use super::super::types::{Autocorrection, DiagnosticResult, ErrorContext, ErrorSeverity, FixDetails, FixType};
+use super::super::{AklypseError, Result, ValidationSnafu};
+use std::path::PathBuf;
+
+/// A suggestion rustc considers safe to apply without review
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineApplicableSuggestion {
+    /// The `help` message offering the suggestion
+    pub message: String,
+    /// One `FixDetails::TextReplace` per span the suggestion rewrites
+    pub replacements: Vec<FixDetails>,
+}
+
+impl MachineApplicableSuggestion {
+    /// Autocorrection applying the suggestion, if it rewrites a single span
+    ///
+    /// An `Autocorrection` carries one `FixDetails`, so suggestions spanning
+    /// several places are left to callers reading `replacements` themselves.
+    pub fn to_autocorrection(&self) -> Option<Autocorrection> {
+        match self.replacements.as_slice() {
+            [replacement] => Some(
+                Autocorrection::new(format!("Apply compiler suggestion: {}", self.message), FixType::TextReplacement, 0.95)
+                    .with_details(replacement.clone()),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// A diagnostic read from compiler JSON output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompilerDiagnostic {
+    /// rustc level, such as "error" or "warning"
+    pub level: String,
+    /// Location, code, message and help lines of the diagnostic
+    pub result: DiagnosticResult,
+    /// Suggestions rustc marked `MachineApplicable`
+    pub suggestions: Vec<MachineApplicableSuggestion>,
+}
+
+impl CompilerDiagnostic {
+    /// Whether rustc reported the diagnostic as an error
+    pub fn is_error(&self) -> bool {
+        self.level.starts_with("error")
+    }
+
+    /// `Validation` error of the diagnostic's location, carrying the diagnostic for Decrust
+    ///
+    /// The `DiagnosticResult` becomes the context's diagnostic info and the
+    /// suggestions a `Vec<MachineApplicableSuggestion>` attachment.
+    pub fn into_error(self) -> AklypseError {
+        let field = self
+            .result
+            .primary_location
+            .as_ref()
+            .map(|location| format!("{}:{}:{}", location.file, location.line, location.column))
+            .unwrap_or_default();
+        let message = self.result.original_message.as_deref().and_then(|message| message.lines().next()).unwrap_or_default();
+        let error: AklypseError = ValidationSnafu { field, message: message.to_string() }.build();
+        let severity = if self.is_error() { ErrorSeverity::Error } else { ErrorSeverity::Warning };
+        error.add_context(
+            ErrorContext::new(format!("rustc {}", self.level))
+                .with_severity(severity)
+                .with_diagnostic_info(self.result)
+                .with_attachment(self.suggestions),
+        )
+    }
+}
+
+/// Parse compiler JSON output, one message per line
+///
+/// Fails on lines that are not valid JSON; cargo messages other than
+/// `compiler-message` and trailing summaries are skipped.
+pub fn parse_cargo_json(output: &str) -> Result<Vec<CompilerDiagnostic>> {
+    Ok(json_diagnostics(output)?
+        .iter()
+        .filter_map(|diagnostic| {
+            Some(CompilerDiagnostic {
+                level: diagnostic.get("level").and_then(JsonValue::as_str).unwrap_or("error").to_string(),
+                result: diagnostic_from_json(diagnostic)?,
+                suggestions: machine_applicable(diagnostic),
+            })
+        })
+        .collect())
+}
+
+fn machine_applicable(diagnostic: &JsonValue) -> Vec<MachineApplicableSuggestion> {
+    let mut suggestions = Vec::new();
+    for child in diagnostic.get("children").map(JsonValue::items).unwrap_or_default() {
+        let replacements: Vec<FixDetails> = child
+            .get("spans")
+            .map(JsonValue::items)
+            .unwrap_or_default()
+            .iter()
+            .filter(|span| span.get("suggestion_applicability").and_then(JsonValue::as_str) == Some("MachineApplicable"))
+            .filter_map(replacement)
+            .collect();
+        if !replacements.is_empty() {
+            let message = child.get("message").and_then(JsonValue::as_str).unwrap_or_default();
+            suggestions.push(MachineApplicableSuggestion { message: message.to_string(), replacements });
+        }
+    }
+    suggestions
+}
+
+fn replacement(span: &JsonValue) -> Option<FixDetails> {
+    let position = |key: &str| span.get(key).and_then(JsonValue::as_u64).map(|value| value as usize);
+    Some(FixDetails::TextReplace {
+        file_path: PathBuf::from(span.get("file_name")?.as_str()?),
+        line_start: position("line_start")?,
+        column_start: position("column_start")?,
+        line_end: position("line_end")?,
+        column_end: position("column_end")?,
+        original_text_snippet: highlighted(span),
+        replacement_text: span.get("suggested_replacement")?.as_str()?.to_string(),
+    })
+}
+
+// Text the span covers, from the highlighted part of each source line it quotes
+fn highlighted(span: &JsonValue) -> Option<String> {
+    let lines = span.get("text").map(JsonValue::items).unwrap_or_default();
+    if lines.is_empty() {
+        return None;
+    }
+    let parts: Option<Vec<String>> = lines
+        .iter()
+        .map(|line| {
+            let text = line.get("text")?.as_str()?;
+            let start = line.get("highlight_start")?.as_u64()? as usize;
+            let end = line.get("highlight_end")?.as_u64()? as usize;
+            Some(text.chars().skip(start.saturating_sub(1)).take(end.saturating_sub(start)).collect())
+        })
+        .collect();
+    parts.map(|parts| parts.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::Decrust;
+
+    // An unused import rustc can remove on its own, and a type error whose fix it is unsure of
+    const CARGO_OUTPUT: &str = concat!(
+        r#"{"reason":"compiler-artifact","target":{"name":"app"}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"unused import: `std::fmt`","code":{"code":"unused_imports","explanation":null},"level":"warning","#,
+        r#""spans":[{"file_name":"src/lib.rs","line_start":1,"line_end":1,"column_start":5,"column_end":13,"is_primary":true,"expansion":null,"#,
+        r#""text":[{"text":"use std::fmt;","highlight_start":5,"highlight_end":13}]}],"#,
+        r#""children":[{"message":"remove the unused import","level":"help","spans":[{"file_name":"src/lib.rs","line_start":1,"line_end":2,"#,
+        r#""column_start":1,"column_end":1,"suggested_replacement":"","suggestion_applicability":"MachineApplicable","#,
+        r#""text":[{"text":"use std::fmt;","highlight_start":1,"highlight_end":14},{"text":"","highlight_start":1,"highlight_end":1}]}]}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","#,
+        r#""spans":[{"file_name":"src/lib.rs","line_start":4,"line_end":4,"column_start":18,"column_end":21,"is_primary":true,"expansion":null}],"#,
+        r#""children":[{"message":"change the type","level":"help","spans":[{"file_name":"src/lib.rs","line_start":4,"line_end":4,"#,
+        r#""column_start":18,"column_end":21,"suggested_replacement":"1u32","suggestion_applicability":"MaybeIncorrect"}]}]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}"#,
+        "\n",
+        r#"{"reason":"build-finished","success":false}"#,
+    );
+
+    #[test]
+    fn test_parse_keeps_machine_applicable_spans() {
+        let diagnostics = parse_cargo_json(CARGO_OUTPUT).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+
+        let warning = &diagnostics[0];
+        assert!(!warning.is_error());
+        assert_eq!(warning.result.diagnostic_code.as_deref(), Some("unused_imports"));
+        assert_eq!(
+            warning.suggestions,
+            vec![MachineApplicableSuggestion {
+                message: "remove the unused import".to_string(),
+                replacements: vec![FixDetails::TextReplace {
+                    file_path: PathBuf::from("src/lib.rs"),
+                    line_start: 1,
+                    column_start: 1,
+                    line_end: 2,
+                    column_end: 1,
+                    original_text_snippet: Some("use std::fmt;\n".to_string()),
+                    replacement_text: String::new(),
+                }],
+            }]
+        );
+
+        let error = &diagnostics[1];
+        assert!(error.is_error());
+        assert!(error.suggestions.is_empty());
+        assert_eq!(error.result.suggested_fixes, vec!["change the type: `1u32`"]);
+
+        assert!(parse_cargo_json("{\"reason\":").is_err());
+    }
+
+    #[test]
+    fn test_suggest_autocorrection_from_compiler_output() {
+        let decrust = Decrust::new();
+        let mut diagnostics = parse_cargo_json(CARGO_OUTPUT).unwrap().into_iter();
+
+        let error = diagnostics.next().unwrap().into_error();
+        assert!(matches!(error.category(), crate::common::error::ErrorCategory::Validation));
+        let correction = decrust.suggest_autocorrection(&error, Some("use std::fmt;\n\nfn main() {}\n")).unwrap();
+        assert_eq!(correction.fix_type, FixType::TextReplacement);
+        assert_eq!(correction.diff_suggestion.as_deref(), Some("--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,2 @@\n-use std::fmt;\n \n fn main() {}\n"));
+        assert_eq!(correction.targets_error_code.as_deref(), Some("unused_imports"));
+        assert!(matches!(
+            correction.details,
+            Some(FixDetails::TextReplace { line_end: 2, column_end: 1, ref replacement_text, .. }) if replacement_text.is_empty()
+        ));
+
+        // Without a machine-applicable suggestion the help lines are used
+        let error = diagnostics.next().unwrap().into_error();
+        let correction = decrust.suggest_autocorrection(&error, None).unwrap();
+        assert!(correction.confidence < 0.95);
+        assert_eq!(correction.targets_error_code.as_deref(), Some("E0308"));
+
+        let corrections = decrust.suggest_from_cargo_output(CARGO_OUTPUT).unwrap();
+        assert_eq!(corrections.len(), 2);
+    }
+}
@@ -0,0 +1,745 @@
+/* src/common/error/decrust/providers.rs */
+#![warn(missing_docs)]
+//! **Brief:** Fix providers consulted by the Decrust engine.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Fix Providers]
+//!  - [Extension Points]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `FixProvider` knows how to fix one kind of error. `Decrust` asks each of
+//! its providers whether it supports an error and ranks all suggestions
+//! offered by confidence, earlier providers first among equals. Downstream
+//! crates add domain-specific providers with `Decrust::with_provider`; these
+//! are consulted before the built-in ones below, which `Decrust::new`
+//! registers in this order:
+//!
+//! - `CompilerSuggestionProvider`: replacements rustc marked machine-applicable
+//! - `clippy::ClippyProvider`: clippy lint findings, which the two generic
+//!   diagnostic providers leave to it
+//! - `DiagnosticToolProvider`: help lines of any embedded `DiagnosticResult`
+//! - `NotFoundProvider`, `IoProvider`, `ConfigurationProvider`,
+//!   `NetworkProvider` and `ExternalServiceProvider`: one per error category
+//! - `validation::ValidationProvider`: validation errors, corrected from the
+//!   field schemas given to `Decrust::with_validation_schema`
+//! - `ChecksumProvider` (feature `integrity`): failed checksum verifications
+//!
+//! The network and external service providers also read the failure text for
+//! transport problems: unresolved host names, failed TLS handshakes, proxies
+//! in the way, and transient failures better handled by a `Retrier` or a
+//! `CircuitBreaker` than by a person.
+
+use super::clippy::is_clippy_lint;
+use super::parser::MachineApplicableSuggestion;
+use super::super::types::{Autocorrection, ErrorCategory, FixDetails, FixType};
+use super::super::AklypseError;
+use super::AutocorrectableError;
+use crate::common::utils::{net, process, retry};
+use std::path::PathBuf;
+
+/// Source of autocorrections for the errors it supports.
+pub trait FixProvider: Send + Sync {
+    /// Name identifying the provider, as used by `Decrust::without_provider`
+    fn name(&self) -> &str;
+
+    /// Whether the provider has something to say about `error`
+    fn supports(&self, error: &AklypseError) -> bool;
+
+    /// Suggestions for a supported `error`, best first
+    ///
+    /// `source_code_context` holds the contents of the file the error
+    /// occurred in, when the caller knows them.
+    fn suggest(&self, error: &AklypseError, source_code_context: Option<&str>) -> Vec<Autocorrection>;
+}
+
+/// Applies the replacements rustc marked machine-applicable
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompilerSuggestionProvider;
+
+impl FixProvider for CompilerSuggestionProvider {
+    fn name(&self) -> &str {
+        "compiler_suggestion"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.get_attachment::<Vec<MachineApplicableSuggestion>>().is_some_and(|suggestions| !suggestions.is_empty())
+            && !is_clippy_lint(error)
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        let code = error.get_diagnostic_info().and_then(|diag_info| diag_info.diagnostic_code.clone());
+        error
+            .get_attachment::<Vec<MachineApplicableSuggestion>>()
+            .map(|suggestions| suggestions.iter().filter_map(MachineApplicableSuggestion::to_autocorrection).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut correction| {
+                correction.targets_error_code = code.clone();
+                correction
+            })
+            .collect()
+    }
+}
+
+/// Applies the help lines of a `DiagnosticResult` embedded in the error
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DiagnosticToolProvider;
+
+impl FixProvider for DiagnosticToolProvider {
+    fn name(&self) -> &str {
+        "diagnostic_tool"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.get_diagnostic_info().is_some_and(|diag_info| !diag_info.suggested_fixes.is_empty()) && !is_clippy_lint(error)
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        diagnostic_tool(error).into_iter().collect()
+    }
+}
+
+/// Creates missing files and directories
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NotFoundProvider;
+
+impl FixProvider for NotFoundProvider {
+    fn name(&self) -> &str {
+        "not_found"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::NotFound
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        not_found(error).into_iter().collect()
+    }
+}
+
+/// Diagnoses missing paths and permission problems of I/O errors
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IoProvider;
+
+impl FixProvider for IoProvider {
+    fn name(&self) -> &str {
+        "io"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::Io
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        io(error).into_iter().collect()
+    }
+}
+
+/// Points at the configuration key or file to correct
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigurationProvider;
+
+impl FixProvider for ConfigurationProvider {
+    fn name(&self) -> &str {
+        "configuration"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::Configuration
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        configuration(error).into_iter().collect()
+    }
+}
+
+/// Diagnoses the endpoint of network errors
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetworkProvider;
+
+impl FixProvider for NetworkProvider {
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::Network
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        let mut corrections: Vec<Autocorrection> = network(error).into_iter().collect();
+        // The URL diagnosis may already have suggested the same lookup
+        let diagnosed: Vec<String> = corrections.iter().flat_map(|correction| correction.commands_to_apply.clone()).collect();
+        corrections.extend(
+            transport_hints(error, ErrorCategory::Network, &proxy_variables())
+                .into_iter()
+                .filter(|hint| !hint.commands_to_apply.iter().any(|command| diagnosed.contains(command))),
+        );
+        corrections
+    }
+}
+
+/// Suggests fixes for failing external services and the transport to them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExternalServiceProvider;
+
+impl FixProvider for ExternalServiceProvider {
+    fn name(&self) -> &str {
+        "external_service"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::ExternalService
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        let mut corrections = external_service(error);
+        corrections.extend(transport_hints(error, ErrorCategory::ExternalService, &proxy_variables()));
+        corrections
+    }
+}
+
+/// Re-downloads files that failed checksum verification
+#[cfg(feature = "integrity")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChecksumProvider;
+
+#[cfg(feature = "integrity")]
+impl FixProvider for ChecksumProvider {
+    fn name(&self) -> &str {
+        "checksum"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::Validation
+    }
+
+    fn suggest(&self, error: &AklypseError, _source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        checksum(error).into_iter().collect()
+    }
+}
+
+fn diagnostic_tool(error: &AklypseError) -> Option<Autocorrection> {
+    let diag_info = error.get_diagnostic_info()?;
+    tracing::debug!("Decrust: Found tool-suggested fixes in DiagnosticResult.");
+    let primary_fix_text = diag_info.suggested_fixes.join("\n");
+    let file_path_from_diag = diag_info
+        .primary_location
+        .as_ref()
+        .map(|loc| PathBuf::from(&loc.file));
+
+    let details = file_path_from_diag.map(|fp| FixDetails::TextReplace {
+        file_path: fp,
+        line_start: diag_info.primary_location.as_ref().map_or(0, |loc| loc.line as usize),
+        column_start: diag_info.primary_location.as_ref().map_or(0, |loc| loc.column as usize),
+        line_end: diag_info.primary_location.as_ref().map_or(0, |loc| loc.line as usize),
+        column_end: diag_info.primary_location.as_ref().map_or(0, |loc| {
+            loc.column as usize + primary_fix_text.chars().filter(|&c| c != '\n').count().max(1)
+        }),
+        original_text_snippet: diag_info.original_message.clone(),
+        replacement_text: primary_fix_text,
+    });
+
+    Some(Autocorrection {
+        description: "Apply fix suggested by diagnostic tool.".to_string(),
+        fix_type: FixType::TextReplacement,
+        confidence: 0.85, // High confidence for tool-provided suggestions
+        details,
+        diff_suggestion: None,
+        commands_to_apply: vec![],
+        targets_error_code: diag_info.diagnostic_code.clone(),
+    })
+}
+
+fn not_found(error: &AklypseError) -> Option<Autocorrection> {
+    let (resource_type, identifier) = if let AklypseError::NotFound { resource_type, identifier, .. } = error {
+        (resource_type.clone(), identifier.clone())
+    } else {
+        // Should not happen if category matches variant, but good for robustness
+        tracing::warn!("Decrust: NotFound category with unexpected error variant: {:?}", error);
+        ("unknown resource".to_string(), "unknown identifier".to_string())
+    };
+
+    let mut commands = vec![];
+    let mut suggestion_details = None;
+    if resource_type == "file" || resource_type == "path" {
+        let path_buf = PathBuf::from(&identifier);
+        if let Some(parent) = path_buf.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() { // Check if parent needs creation
+                commands.push(format!("mkdir -p \"{}\"", parent.display()));
+            }
+        }
+        commands.push(format!("touch \"{}\"", identifier));
+        suggestion_details = Some(FixDetails::ExecuteCommand {
+            command: commands.first().cloned().unwrap_or_default(), // Simplified, could be multiple
+            args: commands.iter().skip(1).cloned().collect(),
+            working_directory: None,
+        });
+    }
+    Some(Autocorrection {
+        description: format!(
+            "Resource type '{}' with identifier '{}' not found. Consider creating it if it's a file/directory, or verify the path/name.",
+            resource_type, identifier
+        ),
+        fix_type: if commands.is_empty() { FixType::ManualInterventionRequired } else { FixType::ExecuteCommand },
+        confidence: 0.7,
+        details: suggestion_details,
+        diff_suggestion: None,
+        commands_to_apply: commands,
+        targets_error_code: Some(format!("{:?}", ErrorCategory::NotFound)),
+    })
+}
+
+fn io(error: &AklypseError) -> Option<Autocorrection> {
+    let (source_msg, path_opt, operation_opt, io_kind_opt) = if let AklypseError::Io { source, path, operation, .. } = error {
+        (source.to_string(), path.clone(), Some(operation.clone()), Some(source.kind()))
+    } else {
+        (String::from("Unknown I/O error"), None, None, None)
+    };
+    let path_str = path_opt.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| "<unknown_path>".to_string());
+    let op_str = operation_opt.unwrap_or_else(|| "<unknown_op>".to_string());
+
+    let mut details = None;
+    let mut commands = vec![];
+    let fix_type = match io_kind_opt {
+        Some(std::io::ErrorKind::NotFound) => {
+            if let Some(p) = &path_opt {
+                details = Some(FixDetails::SuggestCodeChange {
+                    file_path: p.clone(),
+                    line_hint: 0, // Placeholder, context would improve this
+                    suggested_code_snippet: format!("// Ensure path '{}' exists before operation '{}'\n// Or handle the NotFound error gracefully.", p.display(), op_str),
+                    explanation: "The file or directory specified in the operation was not found at the given path.".to_string(),
+                });
+                if p.is_dir() || p.extension().is_none() { // Heuristic for directory
+                    commands.push(format!("mkdir -p \"{}\"", p.display()));
+                } else { // Likely a file
+                     if let Some(parent) = p.parent() {
+                         if !parent.as_os_str().is_empty() && !parent.exists() {
+                             commands.push(format!("mkdir -p \"{}\"", parent.display()));
+                         }
+                     }
+                     commands.push(format!("touch \"{}\"", p.display()));
+                }
+            }
+            FixType::ExecuteCommand // With commands, or ManualInterventionRequired if no commands
+        }
+        Some(std::io::ErrorKind::PermissionDenied) => {
+            details = Some(FixDetails::SuggestCodeChange{
+                file_path: path_opt.clone().unwrap_or_else(|| PathBuf::from("unknown_file_causing_permission_error")),
+                line_hint: 0,
+                suggested_code_snippet: format!("// Check permissions for path '{}' for operation '{}'", path_str, op_str),
+                explanation: "The application does not have the necessary permissions to perform the I/O operation.".to_string()
+            });
+            FixType::ConfigurationChange // e.g., chmod, chown
+        }
+        _ => FixType::Information,
+    };
+
+    Some(Autocorrection {
+        description: format!("I/O error during '{}' on path '{}': {}. Verify path, permissions, or disk space.", op_str, path_str, source_msg),
+        fix_type,
+        confidence: 0.65,
+        details,
+        diff_suggestion: None,
+        commands_to_apply: commands,
+        targets_error_code: Some(format!("{:?}", ErrorCategory::Io)),
+    })
+}
+
+fn configuration(error: &AklypseError) -> Option<Autocorrection> {
+    // Loader details ride on the contexts wrapping the Config error
+    let (config_error, metadata) = unwrap_contexts(error);
+    let (message, path_opt) = if let AklypseError::Config { message, path, .. } = config_error {
+        (message.clone(), path.clone())
+    } else {
+        ("Unknown configuration error".to_string(), None)
+    };
+    let (key_path, expected_type) = config_key_details(&metadata);
+    let line_hint = metadata
+        .get(crate::common::utils::parse::LINE_METADATA_KEY)
+        .and_then(|line| line.parse().ok())
+        .unwrap_or(1); // Suggest reviewing start of file
+    let suggested_code_snippet = match (key_path, expected_type) {
+        (Some(key_path), Some(expected_type)) => {
+            format!("# Set '{}' to a value of type {}\n# Error: {}", key_path, expected_type, message)
+        }
+        (Some(key_path), None) => format!("# Add or correct the key '{}'\n# Error: {}", key_path, message),
+        _ => format!("# Review this configuration file for error related to: {}\n# Ensure all values are correctly formatted and all required fields are present.", message),
+    };
+    let target_file = path_opt.clone().unwrap_or_else(|| PathBuf::from("config.toml")); // Default assumption
+    Some(Autocorrection {
+        description: format!("Configuration issue for path '{}': {}. Please review the configuration file structure and values.",
+            path_opt.as_ref().map(|p| p.display().to_string()).unwrap_or_else(||"<unknown_config>".to_string()), message),
+        fix_type: FixType::ConfigurationChange,
+        confidence: if key_path.is_some() { 0.8 } else { 0.7 },
+        details: Some(FixDetails::SuggestCodeChange {
+            file_path: target_file,
+            line_hint,
+            suggested_code_snippet,
+            explanation: "Configuration files require specific syntax, valid values, and all mandatory fields to be present.".to_string()
+        }),
+        diff_suggestion: None,
+        commands_to_apply: vec![],
+        targets_error_code: Some(format!("{:?}", ErrorCategory::Configuration)),
+    })
+}
+
+fn network(error: &AklypseError) -> Option<Autocorrection> {
+    let (network_error, metadata) = unwrap_contexts(error);
+    let (url, kind) = if let AklypseError::Network { url, kind, .. } = network_error {
+        (url.clone(), kind.clone())
+    } else {
+        (None, "unknown".to_string())
+    };
+    // Diagnose the URL itself when the error did not already name a problem
+    let diagnosed = url.as_deref().and_then(|url| net::validate_endpoint(url, &net::EndpointOptions::new()).err());
+    let problem = metadata
+        .get(net::PROBLEM_METADATA_KEY)
+        .copied()
+        .or_else(|| diagnosed.as_ref()?.get_rich_context()?.metadata.get(net::PROBLEM_METADATA_KEY).map(String::as_str))
+        .and_then(net::UrlProblem::from_name);
+    // Suggestions end up in reports and copied commands, so credentials are left out
+    let redacted = url.as_deref().map(net::redact);
+    let url_str = redacted.clone().unwrap_or_else(|| "<unknown_url>".to_string());
+    let host = url.as_deref().and_then(|url| net::parse_url(url).ok()).map(|parsed| parsed.host);
+
+    let (description, fix_type, commands) = match problem {
+        Some(net::UrlProblem::Unresolvable) => (
+            format!("Host of '{}' does not resolve. Check the host name and DNS configuration.", url_str),
+            FixType::ExecuteCommand,
+            host.map(|host| vec![format!("nslookup {}", host)]).unwrap_or_default(),
+        ),
+        Some(net::UrlProblem::MissingScheme) => (
+            format!("URL '{}' has no scheme. Prefix it with e.g. 'https://'.", url_str),
+            FixType::ConfigurationChange,
+            vec![],
+        ),
+        Some(problem) => (
+            format!("URL '{}' is malformed ({}). Correct the endpoint configuration.", url_str, problem),
+            FixType::ConfigurationChange,
+            vec![],
+        ),
+        None => (
+            format!("Network error of kind '{}' for '{}'. Verify the service is reachable.", kind, url_str),
+            if url.is_some() { FixType::ExecuteCommand } else { FixType::ManualInterventionRequired },
+            redacted.iter().map(|url| format!("curl -sv \"{}\"", url)).collect(),
+        ),
+    };
+    let details = commands.first().map(|command| FixDetails::ExecuteCommand {
+        command: command.clone(),
+        args: vec![],
+        working_directory: None,
+    });
+    Some(Autocorrection {
+        description,
+        fix_type,
+        confidence: if problem.is_some() { 0.75 } else { 0.5 },
+        details,
+        diff_suggestion: None,
+        commands_to_apply: commands,
+        targets_error_code: Some(format!("{:?}", ErrorCategory::Network)),
+    })
+}
+
+// Answers of the service itself: rejected credentials, rate limits, and a health check
+pub(super) fn external_service(error: &AklypseError) -> Vec<Autocorrection> {
+    let (inner, metadata) = unwrap_contexts(error);
+    let service = if let AklypseError::ExternalService { service_name, .. } = inner { service_name.as_str() } else { "<unknown_service>" };
+    let text = failure_text(inner, &metadata);
+    let target = Some(format!("{:?}", ErrorCategory::ExternalService));
+    let mut corrections = Vec::new();
+
+    if mentions(&text, AUTH_MARKERS) {
+        corrections.push(Autocorrection {
+            description: format!(
+                "Service '{}' rejected the request's credentials. Check that the API key or token is set, unexpired and allowed to perform the call.",
+                service
+            ),
+            fix_type: FixType::ConfigurationChange,
+            confidence: 0.65,
+            details: Some(call_site_change(
+                error,
+                format!("// Load the credentials of '{}' from configuration or the environment instead of hard-coding them", service),
+                "The service answered 401/403: the credentials are missing, expired or lack a permission.",
+            )),
+            diff_suggestion: None,
+            commands_to_apply: vec![],
+            targets_error_code: target.clone(),
+        });
+    }
+    if mentions(&text, RATE_LIMIT_MARKERS) {
+        corrections.push(Autocorrection {
+            description: format!(
+                "Service '{}' is rate limiting the client. Honor its Retry-After header and throttle calls with a `RateLimiter`.",
+                service
+            ),
+            fix_type: FixType::ConfigurationChange,
+            confidence: 0.6,
+            details: Some(call_site_change(
+                error,
+                concat!(
+                    "RateLimiterConfig { strategy: RateLimitStrategy::FixedWindow { limit: 10, window: Duration::from_secs(1) }, ",
+                    "max_wait: Some(Duration::from_secs(5)), ..Default::default() }"
+                )
+                .to_string(),
+                "The service answered 429: calls must be spread out rather than retried immediately.",
+            )),
+            diff_suggestion: None,
+            commands_to_apply: vec![],
+            targets_error_code: target.clone(),
+        });
+    }
+    // Processes run through `utils::process` carry their command line
+    let command = metadata.get(process::COMMAND_METADATA_KEY).map(|command| command.to_string());
+    corrections.push(Autocorrection {
+        description: match &command {
+            Some(command) => format!("Command `{}` failed. Run it by hand to see its full output.", command),
+            None => format!("Service '{}' failed: check its health and status page before changing the client.", service),
+        },
+        fix_type: if command.is_some() { FixType::ExecuteCommand } else { FixType::Information },
+        confidence: 0.3,
+        details: command.as_ref().map(|command| FixDetails::ExecuteCommand {
+            command: command.clone(),
+            args: vec![],
+            working_directory: None,
+        }),
+        diff_suggestion: None,
+        commands_to_apply: command.into_iter().collect(),
+        targets_error_code: target,
+    });
+    corrections
+}
+
+// Failure text markers, matched as whole tokens against the lowercased kind, message and sources
+const DNS_MARKERS: &[&str] = &["dns", "failed to lookup", "name or service not known", "no such host", "nodename nor servname", "name resolution"];
+const TLS_MARKERS: &[&str] = &["certificate", "tls", "ssl", "x509", "handshake", "unknownissuer"];
+const PROXY_MARKERS: &[&str] = &["proxy", "407"];
+const TRANSIENT_MARKERS: &[&str] = &["timed out", "timeout", "refused", "reset", "unavailable", "502", "503", "504"];
+const AUTH_MARKERS: &[&str] = &["401", "403", "unauthorized", "forbidden", "credential", "api key", "access token"];
+const RATE_LIMIT_MARKERS: &[&str] = &["429", "rate limit", "too many requests", "quota"];
+
+/// Environment variables read by HTTP clients to pick a proxy
+pub const PROXY_ENV_VARS: &[&str] = &["HTTPS_PROXY", "HTTP_PROXY", "ALL_PROXY", "NO_PROXY"];
+
+// DNS, TLS, proxy and transient failure hints for errors reaching a remote endpoint,
+// given the names of the proxy variables set in the environment
+pub(super) fn transport_hints(error: &AklypseError, category: ErrorCategory, proxy_variables: &[String]) -> Vec<Autocorrection> {
+    let (inner, metadata) = unwrap_contexts(error);
+    let text = failure_text(inner, &metadata);
+    let url = match inner {
+        AklypseError::Network { url, .. } => url.as_deref(),
+        _ => None,
+    }
+    .or_else(|| metadata.get(net::URL_METADATA_KEY).copied());
+    let parsed = url.and_then(|url| net::parse_url(url).ok());
+    let host = parsed.as_ref().map(|parsed| parsed.host.clone());
+    let target = Some(format!("{:?}", category));
+    let mut hints = Vec::new();
+
+    if mentions(&text, DNS_MARKERS) {
+        let commands: Vec<String> = host.iter().map(|host| format!("nslookup {}", host)).collect();
+        hints.push(Autocorrection {
+            description: format!(
+                "Host '{}' could not be resolved. Check its spelling, /etc/hosts and the resolvers in /etc/resolv.conf.",
+                host.as_deref().unwrap_or("<unknown_host>")
+            ),
+            fix_type: if commands.is_empty() { FixType::ManualInterventionRequired } else { FixType::ExecuteCommand },
+            confidence: 0.7,
+            details: execute(&commands),
+            diff_suggestion: None,
+            commands_to_apply: commands,
+            targets_error_code: target.clone(),
+        });
+    }
+    if mentions(&text, TLS_MARKERS) {
+        let commands: Vec<String> = parsed
+            .iter()
+            .map(|parsed| {
+                let port = parsed.effective_port().unwrap_or(443);
+                format!("openssl s_client -connect {}:{} -servername {} </dev/null", parsed.host, port, parsed.host)
+            })
+            .collect();
+        hints.push(Autocorrection {
+            description: format!(
+                "TLS handshake with '{}' failed. Check the certificate's chain, host names and expiry and the local clock; point SSL_CERT_FILE at a CA bundle when the issuer is private.",
+                host.as_deref().unwrap_or("<unknown_host>")
+            ),
+            fix_type: if commands.is_empty() { FixType::ManualInterventionRequired } else { FixType::ExecuteCommand },
+            confidence: 0.7,
+            details: execute(&commands),
+            diff_suggestion: None,
+            commands_to_apply: commands,
+            targets_error_code: target.clone(),
+        });
+    }
+    // A proxy named by the failure is likely at fault; one merely configured might be
+    let variables = proxy_variables;
+    let named = mentions(&text, PROXY_MARKERS);
+    if named || !variables.is_empty() {
+        let commands = vec!["env | grep -i _proxy".to_string()];
+        hints.push(Autocorrection {
+            description: format!(
+                "Requests may go through a proxy{}. Check the proxy address and credentials, or add '{}' to NO_PROXY.",
+                if variables.is_empty() { String::new() } else { format!(" ({} set)", variables.join(", ")) },
+                host.as_deref().unwrap_or("<unknown_host>")
+            ),
+            fix_type: FixType::ConfigurationChange,
+            confidence: if named { 0.65 } else { 0.35 },
+            details: execute(&commands),
+            diff_suggestion: None,
+            commands_to_apply: commands,
+            targets_error_code: target.clone(),
+        });
+    }
+    if mentions(&text, TRANSIENT_MARKERS) {
+        // Errors returned by a `Retrier` record how many attempts it made
+        let (description, snippet) = match metadata.get(retry::ATTEMPT_METADATA_KEY) {
+            Some(attempts) => (
+                format!(
+                    "The call still failed after {} attempts. Guard it with a `CircuitBreaker` so callers fail fast while the endpoint recovers.",
+                    attempts
+                ),
+                "CircuitBreakerConfig { failure_threshold: 5, reset_timeout: Duration::from_secs(30), operation_timeout: Some(Duration::from_secs(10)), ..Default::default() }",
+            ),
+            None => (
+                "The failure looks transient. Retry the call with backoff through a `Retrier`, and guard the endpoint with a `CircuitBreaker`.".to_string(),
+                "RetryPolicy::new().with_max_attempts(3).with_backoff(Backoff::exponential(Duration::from_millis(100), Duration::from_secs(5)))",
+            ),
+        };
+        hints.push(Autocorrection {
+            description,
+            fix_type: FixType::ConfigurationChange,
+            confidence: 0.45,
+            details: Some(call_site_change(error, snippet.to_string(), "Timeouts, refused connections and 5xx answers usually pass on their own.")),
+            diff_suggestion: None,
+            commands_to_apply: vec![],
+            targets_error_code: target,
+        });
+    }
+    hints
+}
+
+// Lowercased kind, message and source chain of `error`, with a process's stderr
+fn failure_text(error: &AklypseError, metadata: &std::collections::HashMap<&str, &str>) -> String {
+    let (head, source): (String, Option<&(dyn std::error::Error + 'static)>) = match error {
+        AklypseError::Network { kind, source, .. } => (kind.clone(), Some(source.as_ref() as &(dyn std::error::Error + 'static))),
+        AklypseError::ExternalService { message, source, .. } => {
+            (message.clone(), source.as_deref().map(|source| source as &(dyn std::error::Error + 'static)))
+        }
+        _ => (error.to_string(), None),
+    };
+    let mut text = head;
+    for cause in std::iter::successors(source, |cause| AklypseError::source_of(*cause)) {
+        text.push('\n');
+        text.push_str(&cause.to_string());
+    }
+    if let Some(stderr) = metadata.get(process::STDERR_TAIL_METADATA_KEY) {
+        text.push('\n');
+        text.push_str(stderr);
+    }
+    text.to_lowercase()
+}
+
+// Whether a marker occurs in `text` as a token: words may not continue a longer word
+// (`ssl` in `openssl`), status codes must stand alone (`403` in neither `4031` nor `/403/`)
+fn mentions(text: &str, markers: &[&str]) -> bool {
+    markers.iter().any(|marker| {
+        let code = marker.bytes().all(|byte| byte.is_ascii_digit());
+        text.match_indices(marker).any(|(start, _)| {
+            let before = text[..start].chars().next_back();
+            let after = text[start + marker.len()..].chars().next();
+            let joins = |c: char| c.is_alphanumeric() || c == '_' || (code && c == '/');
+            let starts_token = !before.is_some_and(|c| joins(c) || (code && c == '.'));
+            let ends_token = !code || !after.is_some_and(joins);
+            starts_token && ends_token
+        })
+    })
+}
+
+// Names of the proxy variables set; their values may embed credentials and are left out
+fn proxy_variables() -> Vec<String> {
+    PROXY_ENV_VARS
+        .iter()
+        .flat_map(|name| [name.to_string(), name.to_lowercase()])
+        .filter(|name| std::env::var_os(name).is_some_and(|value| !value.is_empty()))
+        .collect()
+}
+
+fn execute(commands: &[String]) -> Option<FixDetails> {
+    commands.first().map(|command| FixDetails::ExecuteCommand { command: command.clone(), args: vec![], working_directory: None })
+}
+
+// Code change at the place the error was raised, as recorded by its context
+pub(super) fn call_site_change(error: &AklypseError, snippet: String, explanation: &str) -> FixDetails {
+    let location = error.get_rich_context().and_then(|context| context.source_location.as_ref());
+    FixDetails::SuggestCodeChange {
+        file_path: location.map_or_else(|| PathBuf::from("unknown_call_site"), |location| PathBuf::from(&location.file)),
+        line_hint: location.map_or(0, |location| location.line as usize),
+        suggested_code_snippet: snippet,
+        explanation: explanation.to_string(),
+    }
+}
+
+#[cfg(feature = "integrity")]
+fn checksum(error: &AklypseError) -> Option<Autocorrection> {
+    use crate::common::utils::integrity;
+
+    // Only checksum mismatches carry a fix; other validation errors need the caller's input
+    let (_, metadata) = unwrap_contexts(error);
+    let path = *metadata.get(integrity::PATH_METADATA_KEY)?;
+    let mut commands = vec![format!("rm -f \"{}\"", path)];
+    if let Some(url) = metadata.get(integrity::SOURCE_URL_METADATA_KEY) {
+        commands.push(format!("curl -fL -o \"{}\" \"{}\"", path, net::redact(url)));
+    }
+    Some(Autocorrection {
+        description: format!(
+            "File '{}' failed checksum verification (expected {}, got {}). Delete it and download it again.",
+            path,
+            metadata.get(integrity::EXPECTED_METADATA_KEY).copied().unwrap_or("<unknown>"),
+            metadata.get(integrity::ACTUAL_METADATA_KEY).copied().unwrap_or("<unknown>"),
+        ),
+        fix_type: FixType::ExecuteCommand,
+        confidence: 0.8,
+        details: Some(FixDetails::ExecuteCommand {
+            command: commands[commands.len() - 1].clone(),
+            args: vec![],
+            working_directory: None,
+        }),
+        diff_suggestion: None,
+        commands_to_apply: commands,
+        targets_error_code: Some(format!("{:?}", ErrorCategory::Validation)),
+    })
+}
+
+/// Innermost error below any rich contexts, with their merged metadata
+///
+/// Outer contexts win when several set the same key.
+pub(super) fn unwrap_contexts(error: &AklypseError) -> (&AklypseError, std::collections::HashMap<&str, &str>) {
+    let mut inner = error;
+    let mut metadata = std::collections::HashMap::new();
+    while let AklypseError::WithRichContext { context, source, .. } = inner {
+        for (key, value) in context.metadata.iter() {
+            metadata.entry(key.as_str()).or_insert(value.as_str());
+        }
+        inner = source;
+    }
+    (inner, metadata)
+}
+
+/// Key path and expected type recorded by `utils::config::ConfigLoader`
+#[cfg(feature = "config")]
+fn config_key_details<'a>(metadata: &std::collections::HashMap<&str, &'a str>) -> (Option<&'a str>, Option<&'a str>) {
+    use crate::common::utils::config::{EXPECTED_TYPE_METADATA_KEY, KEY_PATH_METADATA_KEY};
+    (metadata.get(KEY_PATH_METADATA_KEY).copied(), metadata.get(EXPECTED_TYPE_METADATA_KEY).copied())
+}
+
+/// Without the config loader no error carries key details
+#[cfg(not(feature = "config"))]
+fn config_key_details<'a>(_metadata: &std::collections::HashMap<&str, &'a str>) -> (Option<&'a str>, Option<&'a str>) {
+    (None, None)
+}
@@ -0,0 +1,482 @@
+/* src/common/error/decrust/validation.rs */
+#![warn(missing_docs)]
+//! **Brief:** Schema-aware Decrust suggestions for validation errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Autocorrection System]
+//!  - [Validation Fixes]
+//!  - [Field Schemas]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ValidationProvider` answers the `AklypseError::Validation` errors raised
+//! by application code; compiler diagnostics, also reported as validation
+//! errors, are left to the providers reading them. A `FieldSchema` registered
+//! with `Decrust::with_validation_schema` describes what a field accepts, and
+//! the provider suggests a corrected value from it: the closest allowed value,
+//! a number brought into range, a string cut to length, or the schema's
+//! example. It also offers the `common::validation::rules` checking the schema
+//! where the field is set.
+//!
+//! The rejected value is read from `VALUE_METADATA_KEY` in the error's context,
+//! or from a `range` rule message ending in "(was …)". When it appears in the
+//! source code context, the corrected value comes as a `FixDetails::TextReplace`
+//! of the literal. Without a schema, the bounds of a `range` message and a few
+//! well-known field names (email, uuid, url, …) still lead to a suggestion.
+
+use super::providers::{call_site_change, unwrap_contexts, FixProvider};
+use super::super::types::{Autocorrection, ErrorCategory, FixDetails, FixType};
+use super::super::AklypseError;
+use super::AutocorrectableError;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Metadata key holding the value a validation rejected
+pub const VALUE_METADATA_KEY: &str = "validation.value";
+
+// Patterns of values usually named alike, matched against the field's last path segment
+const WELL_KNOWN_PATTERNS: &[(&str, &str)] = &[
+    ("email", r"^[^@\s]+@[^@\s]+\.[^@\s]+$"),
+    ("uuid", r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"),
+    ("phone", r"^\+?[0-9 ()-]{7,20}$"),
+    ("zip", r"^\d{5}(-\d{4})?$"),
+    ("version", r"^\d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?$"),
+    ("slug", r"^[a-z0-9]+(-[a-z0-9]+)*$"),
+];
+
+// Field names holding endpoints, checked with `utils::net` rather than a pattern
+const URL_FIELDS: &[&str] = &["url", "uri", "endpoint"];
+
+/// What a field accepts, as far as Decrust needs to know to correct it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldSchema {
+    /// Dotted path of the field, as reported by `Validation { field, .. }`
+    pub field: String,
+    /// What the field holds, quoted in suggestions
+    pub description: Option<String>,
+    /// Only values accepted, when the field is an enumeration
+    pub allowed_values: Vec<String>,
+    /// Inclusive bounds of a numeric field
+    pub range: Option<(f64, f64)>,
+    /// Inclusive bounds of the length of a string field, in characters
+    pub length: Option<(usize, usize)>,
+    /// Regular expression values must match
+    pub pattern: Option<String>,
+    /// A valid value, offered when no closer correction is known
+    pub example: Option<String>,
+}
+
+impl FieldSchema {
+    /// Schema of `field` accepting anything
+    pub fn new(field: impl Into<String>) -> Self {
+        Self { field: field.into(), ..Self::default() }
+    }
+
+    /// Describe what the field holds
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Accept only `values`
+    pub fn with_allowed_values(mut self, values: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.allowed_values = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Accept numbers within `min..=max`
+    ///
+    /// Inverted bounds are swapped; a range with a bound that is not finite is ignored.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = ordered_range(min, max);
+        self
+    }
+
+    /// Accept strings of `min..=max` characters
+    pub fn with_length(mut self, min: usize, max: usize) -> Self {
+        self.length = Some((min, max));
+        self
+    }
+
+    /// Accept strings matching the regular expression `pattern`
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Give a valid value to offer as a replacement
+    pub fn with_example(mut self, example: impl Into<String>) -> Self {
+        self.example = Some(example.into());
+        self
+    }
+
+    /// Closest value accepted by the schema, with the confidence of the guess and why it was chosen
+    ///
+    /// `None` when the schema knows nothing wrong with `value` and has no example.
+    pub fn corrected_value(&self, value: &str) -> Option<(String, f64, String)> {
+        if !self.allowed_values.is_empty() && !self.allowed_values.iter().any(|allowed| allowed == value) {
+            let lowered = value.to_lowercase();
+            let (distance, closest) = self
+                .allowed_values
+                .iter()
+                .map(|allowed| (edit_distance(&lowered, &allowed.to_lowercase()), allowed))
+                .min_by_key(|(distance, _)| *distance)?;
+            return Some(if distance <= (value.chars().count() / 3).max(2) {
+                (closest.clone(), 0.8, "closest allowed value".to_string())
+            } else {
+                (closest.clone(), 0.5, format!("one of {}", self.allowed_values.join(", ")))
+            });
+        }
+        // `range` is public, so its bounds may not have gone through `with_range`
+        if let Some((min, max)) = self.range.and_then(|(min, max)| ordered_range(min, max)) {
+            if let Ok(number) = value.trim().parse::<f64>() {
+                let nearest = if number < min { min } else { max };
+                if number < min || number > max {
+                    return Some((nearest.to_string(), 0.75, format!("nearest value between {} and {}", min, max)));
+                }
+            }
+        }
+        if let Some((_, max)) = self.length {
+            if value.chars().count() > max {
+                return Some((value.chars().take(max).collect(), 0.6, format!("cut to {} characters", max)));
+            }
+        }
+        let reason = match &self.pattern {
+            Some(pattern) => format!("example matching {}", pattern),
+            None => "example of a valid value".to_string(),
+        };
+        self.example.clone().filter(|example| example != value).map(|example| (example, 0.55, reason))
+    }
+
+    // Calls of `common::validation::rules` checking the schema, one per line
+    fn rules_snippet(&self) -> String {
+        let mut rules = Vec::new();
+        if let Some((min, max)) = self.length {
+            rules.push(format!("length({}, {})", min, max));
+        }
+        if let Some((min, max)) = self.range {
+            rules.push(format!("range({}, {})", min, max));
+        }
+        if let Some(pattern) = &self.pattern {
+            rules.push(format!("pattern(r\"{}\")", pattern));
+        }
+        if !self.allowed_values.is_empty() {
+            let values: Vec<String> = self.allowed_values.iter().map(|value| format!("{:?}", value)).collect();
+            rules.push(format!(
+                "custom(|value: &str| if [{}].contains(&value) {{ Ok(()) }} else {{ Err({:?}.to_string()) }})",
+                values.join(", "),
+                format!("must be one of {}", self.allowed_values.join(", "))
+            ));
+        }
+        rules.iter().map(|rule| format!("errors.check({:?}, &value, &{});", self.field, rule)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Corrects the values rejected by validations, using the registered field schemas
+#[derive(Debug, Clone, Default)]
+pub struct ValidationProvider {
+    schemas: BTreeMap<String, FieldSchema>,
+}
+
+impl ValidationProvider {
+    /// Provider without any schema
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `schema`, replacing an earlier one of the same field
+    pub fn with_schema(mut self, schema: FieldSchema) -> Self {
+        self.schemas.insert(schema.field.clone(), schema);
+        self
+    }
+
+    /// Schema of `field`
+    ///
+    /// Falls back to the path without collection indices (`items.sku` for
+    /// `items[2].sku`), then to the last path segment (`sku`).
+    pub fn schema(&self, field: &str) -> Option<&FieldSchema> {
+        let unindexed = without_indices(field);
+        let last = unindexed.rsplit('.').next().unwrap_or_default();
+        self.schemas.get(field).or_else(|| self.schemas.get(&unindexed)).or_else(|| self.schemas.get(last))
+    }
+}
+
+impl FixProvider for ValidationProvider {
+    fn name(&self) -> &str {
+        "validation"
+    }
+
+    fn supports(&self, error: &AklypseError) -> bool {
+        error.category() == ErrorCategory::Validation && error.get_diagnostic_info().is_none()
+    }
+
+    fn suggest(&self, error: &AklypseError, source_code_context: Option<&str>) -> Vec<Autocorrection> {
+        let (inner, metadata) = unwrap_contexts(error);
+        let AklypseError::Validation { field, message, .. } = inner else {
+            return Vec::new();
+        };
+        let registered = self.schema(field);
+        let stated = stated_range(message);
+        let mut schema = registered.cloned().unwrap_or_else(|| FieldSchema::new(field.clone()));
+        if schema.range.is_none() {
+            schema.range = stated.as_ref().map(|(min, max, _)| (*min, *max));
+        }
+        let rejected = metadata.get(VALUE_METADATA_KEY).map(|value| value.to_string()).or(stated.map(|(_, _, value)| value));
+        let target = Some(format!("{:?}", ErrorCategory::Validation));
+        let mut corrections = Vec::new();
+
+        if let Some((rejected, (value, confidence, reason))) =
+            rejected.as_ref().and_then(|rejected| Some((rejected, schema.corrected_value(rejected)?)))
+        {
+            let details = literal_replacement(error, rejected, &value, source_code_context).unwrap_or_else(|| {
+                call_site_change(
+                    error,
+                    format!("{} = {};", field.rsplit('.').next().unwrap_or(field), code_literal(&value)),
+                    &format!("'{}' {}.", field, message),
+                )
+            });
+            corrections.push(Autocorrection {
+                description: format!("Set '{}' to {} instead of {} ({}).", field, code_literal(&value), code_literal(rejected), reason),
+                fix_type: if matches!(details, FixDetails::TextReplace { .. }) { FixType::TextReplacement } else { FixType::ConfigurationChange },
+                confidence,
+                details: Some(details),
+                diff_suggestion: None,
+                commands_to_apply: vec![],
+                targets_error_code: target.clone(),
+            });
+        }
+
+        let rules = registered.map(FieldSchema::rules_snippet).filter(|rules| !rules.is_empty());
+        if let (Some(schema), Some(rules)) = (registered, rules) {
+            corrections.push(Autocorrection {
+                description: format!(
+                    "Check '{}'{} against its schema where it is set, so invalid values are reported there.",
+                    field,
+                    schema.description.as_ref().map(|description| format!(" ({})", description)).unwrap_or_default()
+                ),
+                fix_type: FixType::Refactor,
+                confidence: 0.45,
+                details: Some(call_site_change(error, rules, "Rules of common::validation::rules matching the registered schema.")),
+                diff_suggestion: None,
+                commands_to_apply: vec![],
+                targets_error_code: target.clone(),
+            });
+        } else if registered.is_none() {
+            if let Some((snippet, kind)) = well_known_check(field) {
+                corrections.push(Autocorrection {
+                    description: format!("'{}' looks like {}: validate it with a rule rejecting malformed values.", field, kind),
+                    fix_type: FixType::Refactor,
+                    confidence: 0.5,
+                    details: Some(call_site_change(error, snippet, &format!("Common check for {} values.", kind))),
+                    diff_suggestion: None,
+                    commands_to_apply: vec![],
+                    targets_error_code: target.clone(),
+                });
+            }
+        }
+
+        if corrections.is_empty() {
+            corrections.push(Autocorrection {
+                description: format!(
+                    "Check the value of '{}': {}. Describe the field with a `FieldSchema` (Decrust::with_validation_schema) for concrete corrections.",
+                    field, message
+                ),
+                fix_type: FixType::ManualInterventionRequired,
+                confidence: 0.3,
+                details: Some(call_site_change(error, format!("// Correct the value of '{}' before it is validated", field), message)),
+                diff_suggestion: None,
+                commands_to_apply: vec![],
+                targets_error_code: target,
+            });
+        }
+        corrections
+    }
+}
+
+// Bounds and value of a `rules::range` message: "must be between 18 and 130 (was 12)"
+fn stated_range(message: &str) -> Option<(f64, f64, String)> {
+    let rest = message.strip_prefix("must be between ")?;
+    let (min, rest) = rest.split_once(" and ")?;
+    let (max, rest) = rest.split_once(" (was ")?;
+    let value = rest.strip_suffix(')')?;
+    let (min, max) = ordered_range(min.parse().ok()?, max.parse().ok()?)?;
+    Some((min, max, value.to_string()))
+}
+
+// Bounds in ascending order, `None` unless both are finite
+fn ordered_range(min: f64, max: f64) -> Option<(f64, f64)> {
+    (min.is_finite() && max.is_finite()).then_some(if min <= max { (min, max) } else { (max, min) })
+}
+
+// `items[2].sku` as `items.sku`
+fn without_indices(field: &str) -> String {
+    let mut path = String::with_capacity(field.len());
+    let mut depth = 0;
+    for c in field.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ if depth == 0 => path.push(c),
+            _ => {}
+        }
+    }
+    path
+}
+
+// Rule checking values of a field named like a well-known kind, with that kind
+fn well_known_check(field: &str) -> Option<(String, String)> {
+    let name = without_indices(field).rsplit('.').next().unwrap_or_default().to_lowercase();
+    if URL_FIELDS.iter().any(|kind| name.contains(kind)) {
+        return Some((
+            "net::validate_endpoint(&value, &net::EndpointOptions::new())?;".to_string(),
+            "an endpoint URL".to_string(),
+        ));
+    }
+    WELL_KNOWN_PATTERNS.iter().find(|(kind, _)| name.contains(kind)).map(|(kind, pattern)| {
+        (format!("errors.check({:?}, &value, &pattern(r\"{}\"));", field, pattern), format!("a {}", kind))
+    })
+}
+
+// Numbers as they are, anything else quoted
+fn code_literal(value: &str) -> String {
+    if value.parse::<f64>().is_ok() { value.to_string() } else { format!("{:?}", value) }
+}
+
+// Replacement of the `rejected` literal closest to the error's call site
+fn literal_replacement(error: &AklypseError, rejected: &str, value: &str, source_code_context: Option<&str>) -> Option<FixDetails> {
+    let source = source_code_context?;
+    let location = error.get_rich_context()?.source_location.as_ref()?;
+    if rejected.is_empty() || rejected.contains('\n') {
+        return None;
+    }
+    // Quoted string literals first, so `12` does not match inside `120`
+    let quoted = format!("{:?}", rejected);
+    let (needle, replacement) =
+        if source.contains(&quoted) { (quoted, format!("{:?}", value)) } else { (rejected.to_string(), value.to_string()) };
+    let (line, column) = source
+        .lines()
+        .enumerate()
+        .flat_map(|(index, text)| text.match_indices(needle.as_str()).map(move |(offset, _)| (index + 1, text[..offset].chars().count() + 1)))
+        .filter(|(line, column)| is_standalone(source, *line, *column, &needle))
+        .min_by_key(|(line, _)| line.abs_diff(location.line as usize))?;
+    Some(FixDetails::TextReplace {
+        file_path: PathBuf::from(&location.file),
+        line_start: line,
+        column_start: column,
+        line_end: line,
+        column_end: column + needle.chars().count(),
+        original_text_snippet: Some(needle),
+        replacement_text: replacement,
+    })
+}
+
+// Whether the match of `needle` at `line`/`column` is not part of a longer word or number
+fn is_standalone(source: &str, line: usize, column: usize, needle: &str) -> bool {
+    let Some(text) = source.lines().nth(line - 1) else {
+        return false;
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_' || *c == '.');
+    let end = column - 1 + needle.chars().count();
+    !word(column.checked_sub(2).and_then(|index| chars.get(index))) && !word(chars.get(end))
+}
+
+// Levenshtein distance, in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + usize::from(ca != *cb)).min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{Decrust, ErrorContext, ValidationSnafu};
+
+    fn rejected(field: &str, message: &str, value: &str) -> AklypseError {
+        let error: AklypseError = ValidationSnafu { field, message }.build();
+        error.add_context(ErrorContext::new("validating settings").with_metadata(VALUE_METADATA_KEY, value))
+    }
+
+    #[test]
+    fn test_corrected_values_follow_the_schema() {
+        let level = FieldSchema::new("log.level").with_allowed_values(["trace", "debug", "info", "warn", "error"]);
+        assert_eq!(level.corrected_value("wran"), Some(("warn".to_string(), 0.8, "closest allowed value".to_string())));
+        assert_eq!(level.corrected_value("info"), None);
+
+        let port = FieldSchema::new("port").with_range(1.0, 65535.0).with_example("8080");
+        assert_eq!(port.corrected_value("70000").unwrap().0, "65535");
+        assert_eq!(port.corrected_value("http").unwrap().0, "8080");
+
+        // Inverted and non-finite bounds never reach a comparison
+        assert_eq!(FieldSchema::new("n").with_range(10.0, 1.0).corrected_value("50").unwrap().0, "10");
+        assert_eq!(FieldSchema::new("n").with_range(f64::NAN, 1.0).range, None);
+        let unchecked = FieldSchema { range: Some((f64::NAN, 1.0)), ..FieldSchema::new("n") };
+        assert_eq!(unchecked.corrected_value("5"), None);
+        assert_eq!(stated_range("must be between 10 and 1 (was 5)"), Some((1.0, 10.0, "5".to_string())));
+        assert_eq!(stated_range("must be between NaN and 1 (was 5)"), None);
+
+        let name = FieldSchema::new("name").with_length(1, 4);
+        assert_eq!(name.corrected_value("abcdef").unwrap().0, "abcd");
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(without_indices("rooms[1].guests"), "rooms.guests");
+    }
+
+    #[test]
+    fn test_schema_suggestions_from_the_engine() {
+        let decrust = Decrust::new()
+            .with_validation_schema(FieldSchema::new("log.level").with_allowed_values(["debug", "info", "warn"]).with_description("tracing level"));
+
+        let error = rejected("log.level", "unknown level", "wran");
+        let line = error.get_rich_context().unwrap().source_location.as_ref().unwrap().line as usize;
+        let source = format!("{}let level = \"wran\";\n", "\n".repeat(line - 1));
+        let suggestions = decrust.suggest_autocorrections(&error, Some(&source));
+        assert_eq!(suggestions[0].description, "Set 'log.level' to \"warn\" instead of \"wran\" (closest allowed value).");
+        assert_eq!(suggestions[0].fix_type, FixType::TextReplacement);
+        assert!(matches!(
+            &suggestions[0].details,
+            Some(FixDetails::TextReplace { line_start, column_start: 13, column_end: 19, replacement_text, .. })
+                if *line_start == line && replacement_text == "\"warn\""
+        ));
+        assert!(suggestions[0].diff_suggestion.as_deref().unwrap().contains("+let level = \"warn\";\n"));
+        assert!(matches!(
+            &suggestions[1].details,
+            Some(FixDetails::SuggestCodeChange { suggested_code_snippet, .. }) if suggested_code_snippet.starts_with("errors.check(\"log.level\", &value, &custom(")
+        ));
+
+        // Schemas only reach the built-in provider
+        assert!(Decrust::new().without_provider("validation").with_validation_schema(FieldSchema::new("x")).suggest_autocorrections(&error, None).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_without_a_schema() {
+        let decrust = Decrust::new();
+
+        // The bounds and value of a range rule are read from its message
+        let error: AklypseError = ValidationSnafu { field: "user.age", message: "must be between 18 and 130 (was 12)" }.build();
+        let suggestions = decrust.suggest_autocorrections(&error, None);
+        assert_eq!(suggestions[0].description, "Set 'user.age' to 18 instead of 12 (nearest value between 18 and 130).");
+        assert_eq!(suggestions[0].fix_type, FixType::ConfigurationChange);
+
+        let error: AklypseError = ValidationSnafu { field: "contacts[0].email", message: "missing @" }.build();
+        let suggestions = decrust.suggest_autocorrections(&error, None);
+        assert_eq!(suggestions.len(), 1);
+        assert!(matches!(
+            &suggestions[0].details,
+            Some(FixDetails::SuggestCodeChange { suggested_code_snippet, .. }) if suggested_code_snippet.contains(WELL_KNOWN_PATTERNS[0].1)
+        ));
+
+        let error: AklypseError = ValidationSnafu { field: "color", message: "unsupported" }.build();
+        assert_eq!(decrust.suggest_autocorrection(&error, None).unwrap().fix_type, FixType::ManualInterventionRequired);
+    }
+}
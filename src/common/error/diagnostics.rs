@@ -0,0 +1,488 @@
+/* src/common/error/diagnostics.rs */
+#![warn(missing_docs)]
+//! **Brief:** Parsers turning rustc and cargo diagnostics into `DiagnosticResult` values.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Diagnostics]
+//!  - [Compiler Output Parsing]
+//!  - [Structured Diagnostics]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Both parsers accept the output of a whole build and return one
+//! `DiagnosticResult` per top-level diagnostic; trailing summaries such as
+//! "aborting due to 2 previous errors" are skipped. The headline becomes
+//! `original_message` (followed by any `note` lines), the primary span the
+//! `primary_location`, every `help` line a suggested fix, and macro backtraces
+//! the `expansion_trace`.
+//!
+//! `DiagnosticResult::from_panic` covers the runtime counterpart: the panic
+//! message, where it happened, and the stack frames with the panic machinery
+//! filtered out.
+
+use super::report::{BacktraceFrame, JsonValue};
+use super::types::{DiagnosticResult, ErrorLocation, MacroExpansion};
+use super::{ParseSnafu, Result};
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic::PanicHookInfo;
+
+// Symbols of the unwinding and panic machinery, never interesting to the reader
+const PANIC_FRAME_PREFIXES: &[&str] = &[
+    "std::",
+    "core::",
+    "alloc::",
+    "<std::",
+    "<core::",
+    "<alloc::",
+    "__rust",
+    "rust_begin_unwind",
+    "rust_panic",
+];
+
+impl DiagnosticResult {
+    /// Parse human-readable rustc/clippy output (`--error-format=human`)
+    pub fn parse_rustc_text(text: &str) -> Vec<DiagnosticResult> {
+        let mut results = Vec::new();
+        let mut current: Option<TextDiagnostic> = None;
+
+        for line in text.lines() {
+            if let Some((code, message)) = parse_headline(line) {
+                results.extend(current.take().map(TextDiagnostic::finish));
+                if !is_summary_message(message) {
+                    current = Some(TextDiagnostic::new(code, message));
+                }
+                continue;
+            }
+
+            if let Some(diagnostic) = current.as_mut() {
+                diagnostic.push_line(line);
+            }
+        }
+
+        results.extend(current.map(TextDiagnostic::finish));
+        results
+    }
+
+    /// Parse JSON diagnostics, one per line
+    ///
+    /// Accepts both raw rustc diagnostics (`--error-format=json`) and cargo
+    /// messages (`--message-format=json`); cargo messages other than
+    /// `compiler-message` are skipped. Fails on lines that are not valid JSON.
+    pub fn parse_from_json(text: &str) -> Result<Vec<DiagnosticResult>> {
+        Ok(json_diagnostics(text)?.iter().filter_map(diagnostic_from_json).collect())
+    }
+}
+
+// Raw rustc diagnostics of JSON output, one per line, with other cargo messages dropped
+pub(crate) fn json_diagnostics(text: &str) -> Result<Vec<JsonValue>> {
+    let mut diagnostics = Vec::new();
+
+    for (index, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let value = JsonValue::parse(line).map_err(|message| {
+            ParseSnafu {
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+                    as Box<dyn std::error::Error + Send + Sync>,
+                kind: "json".to_string(),
+                context_info: format!("diagnostic on line {}", index + 1),
+            }
+            .build()
+        })?;
+
+        match value.get("reason").and_then(JsonValue::as_str) {
+            Some("compiler-message") => diagnostics.extend(value.get("message").cloned()),
+            Some(_) => {}
+            None => diagnostics.push(value),
+        }
+    }
+
+    Ok(diagnostics)
+}
+
+impl DiagnosticResult {
+    /// Build a diagnostic from inside a panic hook
+    pub fn from_panic(info: &PanicHookInfo<'_>, backtrace: Backtrace) -> DiagnosticResult {
+        let location = info
+            .location()
+            .map(|location| ErrorLocation::new(location.file(), location.line(), location.column(), ""));
+        Self::from_panic_payload(info.payload(), location, &backtrace)
+    }
+
+    /// Build a diagnostic from a payload caught with `std::panic::catch_unwind`
+    ///
+    /// The panic location is not part of a caught payload; pass it when known
+    /// (e.g. recorded by a panic hook).
+    pub fn from_panic_payload(
+        payload: &(dyn Any + Send),
+        location: Option<ErrorLocation>,
+        backtrace: &Backtrace,
+    ) -> DiagnosticResult {
+        let stack_frames = match backtrace.status() {
+            BacktraceStatus::Captured => panic_frames(&backtrace.to_string()),
+            _ => Vec::new(),
+        };
+
+        DiagnosticResult {
+            primary_location: location,
+            expansion_trace: Vec::new(),
+            suggested_fixes: Vec::new(),
+            original_message: Some(panic_message(payload)),
+            diagnostic_code: None,
+            stack_frames,
+        }
+    }
+}
+
+/// Message of a panic payload (`panic!` produces `&str` or `String` payloads)
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+// Frames of a rendered backtrace, minus the panic machinery
+fn panic_frames(rendered: &str) -> Vec<ErrorLocation> {
+    BacktraceFrame::parse_all(rendered)
+        .into_iter()
+        .filter(|frame| !PANIC_FRAME_PREFIXES.iter().any(|prefix| frame.symbol.starts_with(prefix)))
+        .map(|frame| {
+            let location = frame.location.as_deref().and_then(parse_location);
+            let mut location = location.unwrap_or_else(|| ErrorLocation::new("", 0, 0, ""));
+            location.function_context = frame.symbol;
+            location
+        })
+        .collect()
+}
+
+// "error[E0425]: message" / "warning: message" -> (code, message)
+fn parse_headline(line: &str) -> Option<(Option<&str>, &str)> {
+    let rest = ["error", "warning"].iter().find_map(|level| line.strip_prefix(level))?;
+    let (code, rest) = match rest.strip_prefix('[') {
+        Some(bracketed) => {
+            let (code, rest) = bracketed.split_once(']')?;
+            (Some(code), rest)
+        }
+        None => (None, rest),
+    };
+    rest.strip_prefix(": ").map(|message| (code, message.trim()))
+}
+
+fn is_summary_message(message: &str) -> bool {
+    message.starts_with("aborting due to")
+        || message.starts_with("could not compile")
+        || (message.ends_with("emitted") && message.contains("warning"))
+}
+
+// "file:line:column", tolerating colons inside the path (Windows drive letters)
+fn parse_location(text: &str) -> Option<ErrorLocation> {
+    let mut parts = text.trim().rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    let file = parts.next()?;
+    Some(ErrorLocation::new(file, line, column, ""))
+}
+
+// "this error originates in the macro `vec` (in Nightly builds, ...)" -> "vec"
+fn originating_macro(note: &str) -> Option<&str> {
+    let rest = note.split_once("originates in the macro `")?.1;
+    rest.split_once('`').map(|(name, _)| name)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Main,
+    Note,
+    Help,
+}
+
+struct TextDiagnostic {
+    code: Option<String>,
+    message: String,
+    primary_location: Option<ErrorLocation>,
+    notes: Vec<String>,
+    fixes: Vec<String>,
+    macros: Vec<String>,
+    section: Section,
+    // Index into `fixes` of a help line still waiting for its suggested code
+    pending_help: Option<usize>,
+}
+
+impl TextDiagnostic {
+    fn new(code: Option<&str>, message: &str) -> Self {
+        Self {
+            code: code.map(str::to_string),
+            message: message.to_string(),
+            primary_location: None,
+            notes: Vec::new(),
+            fixes: Vec::new(),
+            macros: Vec::new(),
+            section: Section::Main,
+            pending_help: None,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let trimmed = line.trim_start();
+
+        if let Some(location) = trimmed.strip_prefix("--> ") {
+            // Only the main section's first span is primary; later spans belong to notes/help
+            if self.section == Section::Main && self.primary_location.is_none() {
+                self.primary_location = parse_location(location);
+            }
+        } else if let Some(annotation) = trimmed.strip_prefix("= ") {
+            self.push_sub_diagnostic(annotation);
+        } else if trimmed.starts_with("note: ") || trimmed.starts_with("help: ") {
+            self.push_sub_diagnostic(trimmed);
+        } else if let Some(index) = self.pending_help {
+            // A suggestion renders as "12 |     fixed code"
+            if let Some((number, code)) = trimmed.split_once('|') {
+                if !number.trim().is_empty() && number.trim().bytes().all(|b| b.is_ascii_digit()) {
+                    self.fixes[index] = format!("{}: `{}`", self.fixes[index], code.trim());
+                    self.pending_help = None;
+                }
+            }
+        }
+    }
+
+    fn push_sub_diagnostic(&mut self, text: &str) {
+        self.pending_help = None;
+        if let Some(note) = text.strip_prefix("note: ") {
+            self.section = Section::Note;
+            match originating_macro(note) {
+                Some(name) => self.macros.push(name.to_string()),
+                None => self.notes.push(note.trim().to_string()),
+            }
+        } else if let Some(help) = text.strip_prefix("help: ") {
+            self.section = Section::Help;
+            self.fixes.push(help.trim().to_string());
+            self.pending_help = Some(self.fixes.len() - 1);
+        }
+    }
+
+    fn finish(self) -> DiagnosticResult {
+        let expansion_site = self
+            .primary_location
+            .clone()
+            .unwrap_or_else(|| ErrorLocation::new("", 0, 0, ""));
+        let mut original_message = self.message;
+        for note in &self.notes {
+            original_message.push_str("\nnote: ");
+            original_message.push_str(note);
+        }
+
+        DiagnosticResult {
+            primary_location: self.primary_location,
+            expansion_trace: self
+                .macros
+                .into_iter()
+                .map(|macro_name| MacroExpansion {
+                    macro_name,
+                    expansion_site: expansion_site.clone(),
+                    generated_code_snippet: String::new(),
+                })
+                .collect(),
+            suggested_fixes: self.fixes,
+            original_message: Some(original_message),
+            diagnostic_code: self.code,
+            stack_frames: Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn diagnostic_from_json(diagnostic: &JsonValue) -> Option<DiagnosticResult> {
+    let message = diagnostic.get("message")?.as_str()?;
+    if is_summary_message(message) {
+        return None;
+    }
+
+    let spans = diagnostic.get("spans").map(JsonValue::items).unwrap_or_default();
+    let primary = spans
+        .iter()
+        .find(|span| matches!(span.get("is_primary"), Some(JsonValue::Bool(true))))
+        .or_else(|| spans.first());
+
+    let mut original_message = message.to_string();
+    let mut suggested_fixes = Vec::new();
+    for child in diagnostic.get("children").map(JsonValue::items).unwrap_or_default() {
+        let Some(child_message) = child.get("message").and_then(JsonValue::as_str) else {
+            continue;
+        };
+        match child.get("level").and_then(JsonValue::as_str) {
+            Some("help") => {
+                let replacement = child
+                    .get("spans")
+                    .map(JsonValue::items)
+                    .unwrap_or_default()
+                    .iter()
+                    .find_map(|span| span.get("suggested_replacement").and_then(JsonValue::as_str));
+                suggested_fixes.push(match replacement {
+                    Some(replacement) => format!("{}: `{}`", child_message, replacement.trim()),
+                    None => child_message.to_string(),
+                });
+            }
+            Some("note") => {
+                original_message.push_str("\nnote: ");
+                original_message.push_str(child_message);
+            }
+            _ => {}
+        }
+    }
+
+    // Walk the macro backtrace from the innermost expansion outwards
+    let mut expansion_trace = Vec::new();
+    let mut expansion = primary.and_then(|span| span.get("expansion"));
+    while let Some(current) = expansion.filter(|value| matches!(value, JsonValue::Object(_))) {
+        let site = current.get("span");
+        if let (Some(name), Some(location)) =
+            (current.get("macro_decl_name").and_then(JsonValue::as_str), site.and_then(span_location))
+        {
+            expansion_trace.push(MacroExpansion {
+                macro_name: name.trim_end_matches('!').to_string(),
+                expansion_site: location,
+                generated_code_snippet: site.map(span_text).unwrap_or_default(),
+            });
+        }
+        expansion = site.and_then(|span| span.get("expansion"));
+    }
+
+    Some(DiagnosticResult {
+        primary_location: primary.and_then(span_location),
+        expansion_trace,
+        suggested_fixes,
+        original_message: Some(original_message),
+        diagnostic_code: diagnostic
+            .get("code")
+            .and_then(|code| code.get("code"))
+            .and_then(JsonValue::as_str)
+            .map(str::to_string),
+        stack_frames: Vec::new(),
+    })
+}
+
+pub(crate) fn span_location(span: &JsonValue) -> Option<ErrorLocation> {
+    Some(ErrorLocation::new(
+        span.get("file_name")?.as_str()?,
+        span.get("line_start")?.as_u64()? as u32,
+        span.get("column_start")?.as_u64()? as u32,
+        "",
+    ))
+}
+
+fn span_text(span: &JsonValue) -> String {
+    span.get("text")
+        .map(JsonValue::items)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|line| line.get("text").and_then(JsonValue::as_str))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUSTC_TEXT: &str = r#"error[E0425]: cannot find value `usr` in this scope
+  --> src/main.rs:4:20
+   |
+4  |     println!("{}", usr);
+   |                    ^^^ help: a local variable with a similar name exists: `user`
+   |
+note: the variable was declared here
+  --> src/main.rs:2:9
+   |
+2  |     let user = "x";
+   |         ^^^^
+help: consider renaming the binding
+   |
+2  |     let usr = "x";
+   |         ~~~
+   = note: this error originates in the macro `$crate::format_args_nl` which comes from the expansion of the macro `println` (in Nightly builds, run with -Z macro-backtrace for more info)
+
+warning: unused variable: `x`
+ --> C:\work\app\src\lib.rs:10:9
+  |
+  = note: `#[warn(unused_variables)]` on by default
+  = help: if this is intentional, prefix it with an underscore: `_x`
+
+error: aborting due to 1 previous error; 1 warning emitted
+"#;
+
+    #[test]
+    fn test_parse_rustc_text() {
+        let results = DiagnosticResult::parse_rustc_text(RUSTC_TEXT);
+        assert_eq!(results.len(), 2);
+
+        let error = &results[0];
+        assert_eq!(error.diagnostic_code.as_deref(), Some("E0425"));
+        assert_eq!(
+            error.original_message.as_deref(),
+            Some("cannot find value `usr` in this scope\nnote: the variable was declared here")
+        );
+        let location = error.primary_location.as_ref().unwrap();
+        assert_eq!((location.file.as_str(), location.line, location.column), ("src/main.rs", 4, 20));
+        assert_eq!(error.suggested_fixes, vec!["consider renaming the binding: `let usr = \"x\";`"]);
+        assert_eq!(error.expansion_trace[0].macro_name, "$crate::format_args_nl");
+
+        let warning = &results[1];
+        assert_eq!(warning.diagnostic_code, None);
+        assert_eq!(warning.primary_location.as_ref().unwrap().file, "C:\\work\\app\\src\\lib.rs");
+        assert_eq!(warning.suggested_fixes, vec!["if this is intentional, prefix it with an underscore: `_x`"]);
+    }
+
+    #[test]
+    fn test_diagnostic_from_panic_payload() {
+        let payload = std::panic::catch_unwind(|| panic!("index {} out of range", 7)).unwrap_err();
+        let location = ErrorLocation::new("src/lib.rs", 12, 5, "");
+        let result = DiagnosticResult::from_panic_payload(payload.as_ref(), Some(location.clone()), &Backtrace::disabled());
+
+        assert_eq!(result.original_message.as_deref(), Some("index 7 out of range"));
+        assert_eq!(result.primary_location, Some(location));
+        assert!(result.stack_frames.is_empty());
+        assert_eq!(panic_message(&42_u32), "Box<dyn Any>");
+    }
+
+    #[test]
+    fn test_panic_frames_skip_machinery() {
+        let rendered = "   0: std::panicking::begin_panic\n             at /rustc/abc/library/std/src/panicking.rs:626:12\n   1: app::parse\n             at ./src/parse.rs:8:9\n   2: core::ops::function::FnOnce::call_once\n   3: app::main\n";
+        let frames = panic_frames(rendered);
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].function_context.as_str(), frames[0].file.as_str(), frames[0].line), ("app::parse", "./src/parse.rs", 8));
+        assert_eq!((frames[1].function_context.as_str(), frames[1].line), ("app::main", 0));
+    }
+
+    #[test]
+    fn test_parse_cargo_json() {
+        let json = concat!(
+            r#"{"reason":"compiler-artifact","target":{"name":"app"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","#,
+            r#""spans":[{"file_name":"src/a.rs","line_start":3,"column_start":5,"is_primary":false,"expansion":null},"#,
+            r#"{"file_name":"src/b.rs","line_start":8,"column_start":13,"is_primary":true,"expansion":{"macro_decl_name":"vec!","#,
+            r#""span":{"file_name":"src/b.rs","line_start":7,"column_start":1,"text":[{"text":"vec![1u8]"}],"expansion":null}}}],"#,
+            r#""children":[{"message":"expected `u32`","level":"note","spans":[]},"#,
+            r#"{"message":"change the type","level":"help","spans":[{"suggested_replacement":"1u32"}]}]}}"#,
+            "\n",
+            r#"{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}"#,
+        );
+
+        let results = DiagnosticResult::parse_from_json(json).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let result = &results[0];
+        assert_eq!(result.diagnostic_code.as_deref(), Some("E0308"));
+        assert_eq!(result.original_message.as_deref(), Some("mismatched types\nnote: expected `u32`"));
+        assert_eq!(result.primary_location.as_ref().unwrap().file, "src/b.rs");
+        assert_eq!(result.suggested_fixes, vec!["change the type: `1u32`"]);
+        assert_eq!(result.expansion_trace[0].macro_name, "vec");
+        assert_eq!(result.expansion_trace[0].generated_code_snippet, "vec![1u8]");
+
+        assert!(DiagnosticResult::parse_from_json("{not json").is_err());
+    }
+}
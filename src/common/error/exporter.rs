@@ -0,0 +1,258 @@
+/* src/common/error/exporter.rs */
+#![warn(missing_docs)]
+//! **Brief:** Prometheus exporter for circuit breaker, retrier and bulkhead metrics.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Metrics Export]
+//!  - [Fault Tolerance]
+//!  - [Monitoring]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `ResilienceMetricsExporter` holds the circuit breakers, retriers and
+//! bulkheads of an application and `encode` renders their current metrics
+//! in the Prometheus text exposition format, ready to be served from a
+//! `/metrics` endpoint. Nothing is recorded between scrapes: every encode
+//! reads the components' own counters.
+//!
+//! Series are prefixed `aklypse_` and labelled with the component name
+//! (`breaker`, `retrier`, `bulkhead`). The breaker state is a gauge set to 1
+//! for the current `state` label and 0 for the others, and breaker latencies
+//! are a summary with the 0.5, 0.95 and 0.99 quantiles.
+
+use super::{Bulkhead, CircuitBreaker, CircuitState, Retrier};
+use std::fmt::{self, Write};
+use std::sync::{Arc, RwLock};
+
+const CIRCUIT_STATES: [CircuitState; 5] = [
+    CircuitState::Closed,
+    CircuitState::Open,
+    CircuitState::HalfOpen,
+    CircuitState::ForcedOpen,
+    CircuitState::ForcedClosed,
+];
+
+// One metric family: its series share the HELP and TYPE lines
+struct Family {
+    name: &'static str,
+    kind: &'static str,
+    help: &'static str,
+    series: Vec<(&'static str, String, String)>, // (name suffix, rendered labels, value)
+}
+
+impl Family {
+    fn new(name: &'static str, kind: &'static str, help: &'static str) -> Self {
+        Self { name, kind, help, series: Vec::new() }
+    }
+
+    fn push(&mut self, labels: &[(&str, &str)], value: impl fmt::Display) {
+        self.push_suffixed("", labels, value);
+    }
+
+    // Series of the family named with `suffix` appended, like a summary's `_sum`
+    fn push_suffixed(&mut self, suffix: &'static str, labels: &[(&str, &str)], value: impl fmt::Display) {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.series.push((suffix, labels, value.to_string()));
+    }
+
+    // Families without series are left out entirely
+    fn encode(&self, out: &mut String) {
+        if self.series.is_empty() {
+            return;
+        }
+        let _ = writeln!(out, "# HELP aklypse_{} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE aklypse_{} {}", self.name, self.kind);
+        for (suffix, labels, value) in &self.series {
+            let _ = writeln!(out, "aklypse_{}{}{{{}}} {}", self.name, suffix, labels, value);
+        }
+    }
+}
+
+fn state_label(state: CircuitState) -> &'static str {
+    match state {
+        CircuitState::Closed => "closed",
+        CircuitState::Open => "open",
+        CircuitState::HalfOpen => "half_open",
+        CircuitState::ForcedOpen => "forced_open",
+        CircuitState::ForcedClosed => "forced_closed",
+    }
+}
+
+/// Components whose metrics are exported in the Prometheus text format
+#[derive(Default)]
+pub struct ResilienceMetricsExporter {
+    circuit_breakers: RwLock<Vec<Arc<CircuitBreaker>>>,
+    retriers: RwLock<Vec<Arc<Retrier>>>,
+    bulkheads: RwLock<Vec<Arc<Bulkhead>>>,
+}
+
+impl ResilienceMetricsExporter {
+    /// Creates an empty exporter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include `breaker` in the export
+    pub fn register_circuit_breaker(&self, breaker: Arc<CircuitBreaker>) {
+        self.circuit_breakers.write().unwrap_or_else(|p| p.into_inner()).push(breaker);
+    }
+
+    /// Include `retrier` in the export
+    pub fn register_retrier(&self, retrier: Arc<Retrier>) {
+        self.retriers.write().unwrap_or_else(|p| p.into_inner()).push(retrier);
+    }
+
+    /// Include `bulkhead` in the export
+    pub fn register_bulkhead(&self, bulkhead: Arc<Bulkhead>) {
+        self.bulkheads.write().unwrap_or_else(|p| p.into_inner()).push(bulkhead);
+    }
+
+    /// Render the current metrics of every component in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut families = self.circuit_breaker_families();
+        families.extend(self.retrier_families());
+        families.extend(self.bulkhead_families());
+        let mut out = String::new();
+        for family in &families {
+            family.encode(&mut out);
+        }
+        out
+    }
+
+    fn circuit_breaker_families(&self) -> Vec<Family> {
+        let mut state = Family::new("circuit_breaker_state", "gauge", "Whether the circuit is in the labelled state");
+        let mut calls = Family::new("circuit_breaker_calls_total", "counter", "Calls by outcome");
+        let mut consecutive = Family::new("circuit_breaker_consecutive_failures", "gauge", "Failures since the last success");
+        let mut failure_rate = Family::new("circuit_breaker_failure_rate", "gauge", "Weighted failure rate of the sliding window");
+        let mut latency = Family::new("circuit_breaker_call_duration_seconds", "summary", "Duration of completed calls");
+        for breaker in self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            let name = breaker.name();
+            let metrics = breaker.metrics();
+            for candidate in CIRCUIT_STATES {
+                state.push(&[("breaker", name), ("state", state_label(candidate))], u8::from(metrics.state == candidate));
+            }
+            for (outcome, count) in [
+                ("success", metrics.successful_requests),
+                ("failure", metrics.failed_requests),
+                ("rejected", metrics.rejected_requests),
+                ("timeout", metrics.timeout_requests),
+            ] {
+                calls.push(&[("breaker", name), ("outcome", outcome)], count);
+            }
+            consecutive.push(&[("breaker", name)], metrics.consecutive_failures);
+            if let Some(rate) = metrics.failure_rate_in_window {
+                failure_rate.push(&[("breaker", name)], rate);
+            }
+            let snapshot = breaker.latency_snapshot();
+            for (quantile, value) in [("0.5", snapshot.p50), ("0.95", snapshot.p95), ("0.99", snapshot.p99)] {
+                if let Some(value) = value {
+                    latency.push(&[("breaker", name), ("quantile", quantile)], value.as_secs_f64());
+                }
+            }
+            let sum = snapshot.mean.map_or(0.0, |mean| mean.as_secs_f64() * snapshot.count as f64);
+            latency.push_suffixed("_sum", &[("breaker", name)], sum);
+            latency.push_suffixed("_count", &[("breaker", name)], snapshot.count);
+        }
+        vec![state, calls, consecutive, failure_rate, latency]
+    }
+
+    fn retrier_families(&self) -> Vec<Family> {
+        let mut executions = Family::new("retrier_executions_total", "counter", "Calls to execute");
+        let mut attempts = Family::new("retrier_attempts_total", "counter", "Attempts made, first ones included");
+        let mut retries = Family::new("retrier_retries_total", "counter", "Attempts beyond the first of each call");
+        let mut exhausted = Family::new("retrier_exhausted_total", "counter", "Calls failed because the policy gave up");
+        for retrier in self.retriers.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            let labels = [("retrier", retrier.name())];
+            let metrics = retrier.metrics();
+            executions.push(&labels, metrics.executions);
+            attempts.push(&labels, metrics.attempts);
+            retries.push(&labels, metrics.retries());
+            exhausted.push(&labels, metrics.exhausted);
+        }
+        vec![executions, attempts, retries, exhausted]
+    }
+
+    fn bulkhead_families(&self) -> Vec<Family> {
+        let mut in_flight = Family::new("bulkhead_in_flight", "gauge", "Slots currently held");
+        let mut max_concurrent = Family::new("bulkhead_max_concurrent", "gauge", "Configured number of slots");
+        let mut queued = Family::new("bulkhead_queued", "gauge", "Callers waiting for a slot");
+        let mut admitted = Family::new("bulkhead_admitted_total", "counter", "Calls that got a slot");
+        let mut rejected = Family::new("bulkhead_rejected_total", "counter", "Calls turned away, by reason");
+        let mut wait = Family::new("bulkhead_wait_seconds_total", "counter", "Time admitted calls spent waiting");
+        for bulkhead in self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            let name = bulkhead.name();
+            let labels = [("bulkhead", name)];
+            let metrics = bulkhead.metrics();
+            in_flight.push(&labels, metrics.in_flight);
+            max_concurrent.push(&labels, metrics.max_concurrent);
+            queued.push(&labels, metrics.queued);
+            admitted.push(&labels, metrics.admitted_requests);
+            rejected.push(&[("bulkhead", name), ("reason", "queue_full")], metrics.rejected_requests);
+            rejected.push(&[("bulkhead", name), ("reason", "wait_timeout")], metrics.wait_timeouts);
+            wait.push(&labels, metrics.total_wait.as_secs_f64());
+        }
+        vec![in_flight, max_concurrent, queued, admitted, rejected, wait]
+    }
+}
+
+impl fmt::Debug for ResilienceMetricsExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResilienceMetricsExporter")
+            .field("circuit_breakers", &self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).len())
+            .field("retriers", &self.retriers.read().unwrap_or_else(|p| p.into_inner()).len())
+            .field("bulkheads", &self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{BulkheadConfig, CircuitBreakerConfig, InternalSnafu, RetryPolicy};
+    use crate::common::utils::retry::Backoff;
+    use std::time::Duration;
+
+    #[test]
+    fn test_encode_renders_every_component() {
+        let exporter = ResilienceMetricsExporter::new();
+        let breaker = CircuitBreaker::new("pay\"ments", CircuitBreakerConfig { failure_threshold: 1, ..Default::default() });
+        let retrier = Retrier::new("inventory", RetryPolicy::new().with_backoff(Backoff::Fixed(Duration::ZERO)).with_max_attempts(3));
+        let bulkhead = Bulkhead::new("exports", BulkheadConfig { max_concurrent: 2, ..Default::default() });
+        exporter.register_circuit_breaker(breaker.clone());
+        exporter.register_retrier(retrier.clone());
+        exporter.register_bulkhead(bulkhead.clone());
+
+        breaker.execute(|| Ok(())).unwrap();
+        let _ = breaker.execute(|| InternalSnafu { message: "down", source: None }.fail::<()>());
+        let _ = retrier.execute(|| InternalSnafu { message: "down", source: None }.fail::<()>());
+        let _permit = bulkhead.try_acquire().unwrap();
+
+        let text = exporter.encode();
+        for line in [
+            "# TYPE aklypse_circuit_breaker_state gauge",
+            "aklypse_circuit_breaker_state{breaker=\"pay\\\"ments\",state=\"open\"} 1",
+            "aklypse_circuit_breaker_state{breaker=\"pay\\\"ments\",state=\"closed\"} 0",
+            "aklypse_circuit_breaker_calls_total{breaker=\"pay\\\"ments\",outcome=\"failure\"} 1",
+            "aklypse_circuit_breaker_call_duration_seconds_count{breaker=\"pay\\\"ments\"} 2",
+            "aklypse_retrier_attempts_total{retrier=\"inventory\"} 1",
+            "aklypse_retrier_exhausted_total{retrier=\"inventory\"} 1",
+            "aklypse_bulkhead_in_flight{bulkhead=\"exports\"} 1",
+            "aklypse_bulkhead_rejected_total{bulkhead=\"exports\",reason=\"wait_timeout\"} 0",
+        ] {
+            assert!(text.lines().any(|candidate| candidate == line), "missing {} in\n{}", line, text);
+        }
+        assert_eq!(text.matches("# HELP aklypse_circuit_breaker_state ").count(), 1);
+    }
+
+    #[test]
+    fn test_empty_exporter_renders_nothing() {
+        assert_eq!(ResilienceMetricsExporter::new().encode(), "");
+    }
+}
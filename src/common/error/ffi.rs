@@ -0,0 +1,313 @@
+/* src/common/error/ffi.rs */
+#![warn(missing_docs)]
+//! **Brief:** C ABI for exchanging AklypseErrors with C and C++ code.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Foreign Function Interface]
+//!  - [Error Interchange]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! With feature `ffi`, errors cross the C boundary as opaque
+//! `AklypseErrorHandle` pointers. C creates them with
+//! `aklypse_error_from_errno` or `aklypse_error_new`, Rust hands them out with
+//! `AklypseErrorHandle::into_raw`, and whoever ends up holding one releases it
+//! with `aklypse_error_free`.
+//!
+//! Category and severity are exchanged as the stable integer codes of
+//! `category_code` and `severity_code`. Text getters follow `snprintf`: they
+//! write at most `capacity - 1` bytes plus a NUL into the caller's buffer and
+//! return the full length, so a call with a null buffer sizes it. Truncated
+//! output may end inside a UTF-8 sequence.
+
+use super::report::ErrorReport;
+use super::reporter::ErrorReportConfig;
+use super::types::{ErrorCategory, ErrorSeverity};
+use super::{
+    AklypseError, ConcurrencySnafu, ConfigSnafu, ExternalServiceSnafu, InternalSnafu, IoSnafu, StateConflictSnafu,
+    ValidationSnafu,
+};
+use std::ffi::{c_char, c_int, CStr};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// Categories in code order; a category's code is its index
+const CATEGORIES: [ErrorCategory; 17] = [
+    ErrorCategory::Io,
+    ErrorCategory::Parsing,
+    ErrorCategory::Network,
+    ErrorCategory::Configuration,
+    ErrorCategory::Validation,
+    ErrorCategory::Internal,
+    ErrorCategory::CircuitBreaker,
+    ErrorCategory::Timeout,
+    ErrorCategory::ResourceExhaustion,
+    ErrorCategory::NotFound,
+    ErrorCategory::Concurrency,
+    ErrorCategory::ExternalService,
+    ErrorCategory::Authentication,
+    ErrorCategory::Authorization,
+    ErrorCategory::StateConflict,
+    ErrorCategory::Multiple,
+    ErrorCategory::Unspecified,
+];
+
+// Severities in code order; a severity's code is its index
+const SEVERITIES: [ErrorSeverity; 6] = [
+    ErrorSeverity::Debug,
+    ErrorSeverity::Info,
+    ErrorSeverity::Warning,
+    ErrorSeverity::Error,
+    ErrorSeverity::Critical,
+    ErrorSeverity::Fatal,
+];
+
+/// Stable integer code of a category, as returned by `aklypse_error_category`
+pub fn category_code(category: ErrorCategory) -> c_int {
+    CATEGORIES.iter().position(|known| *known == category).map_or(-1, |index| index as c_int)
+}
+
+/// Category with the integer code `code`
+pub fn category_from_code(code: c_int) -> Option<ErrorCategory> {
+    usize::try_from(code).ok().and_then(|index| CATEGORIES.get(index)).copied()
+}
+
+/// Stable integer code of a severity, as returned by `aklypse_error_severity`
+pub fn severity_code(severity: ErrorSeverity) -> c_int {
+    SEVERITIES.iter().position(|known| *known == severity).map_or(-1, |index| index as c_int)
+}
+
+/// Error owned by foreign code
+#[derive(Debug)]
+pub struct AklypseErrorHandle {
+    error: AklypseError,
+}
+
+impl AklypseErrorHandle {
+    /// Hand `error` to foreign code, which must release it with `aklypse_error_free`
+    pub fn into_raw(error: AklypseError) -> *mut AklypseErrorHandle {
+        Box::into_raw(Box::new(AklypseErrorHandle { error }))
+    }
+
+    /// Take back an error created by `into_raw` or the C constructors
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a live handle from this module, not used afterwards.
+    pub unsafe fn from_raw(handle: *mut AklypseErrorHandle) -> AklypseError {
+        Box::from_raw(handle).error
+    }
+
+    /// The wrapped error
+    pub fn error(&self) -> &AklypseError {
+        &self.error
+    }
+}
+
+// Human-readable message: the outermost context's, else the variant's own text
+fn message(error: &AklypseError) -> String {
+    if let Some(context) = error.get_rich_context() {
+        return context.message.to_string();
+    }
+    match error {
+        AklypseError::Io { source, .. } => source.to_string(),
+        AklypseError::Parse { source, .. } | AklypseError::Network { source, .. } => source.to_string(),
+        AklypseError::Config { message, .. }
+        | AklypseError::Internal { message, .. }
+        | AklypseError::Concurrency { message, .. }
+        | AklypseError::ExternalService { message, .. }
+        | AklypseError::StateConflict { message, .. }
+        | AklypseError::Whatever { message, .. } => message.clone(),
+        AklypseError::Validation { field, message, .. } => format!("{}: {}", field, message),
+        other => other.to_string(),
+    }
+}
+
+// `text` copied `snprintf`-style into `buffer`, returning its full length
+unsafe fn copy_out(text: &str, buffer: *mut c_char, capacity: usize) -> usize {
+    if !buffer.is_null() && capacity > 0 {
+        let len = text.len().min(capacity - 1);
+        std::ptr::copy_nonoverlapping(text.as_ptr(), buffer.cast::<u8>(), len);
+        *buffer.add(len) = 0;
+    }
+    text.len()
+}
+
+// Borrowed C string as UTF-8, replacing invalid sequences; empty for null
+unsafe fn c_str(text: *const c_char) -> String {
+    if text.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(text).to_string_lossy().into_owned()
+    }
+}
+
+/// Io error for an `errno` value, with the failed `operation` and optional `path`
+///
+/// # Safety
+///
+/// `operation` and `path` must each be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_from_errno(
+    errno: c_int,
+    operation: *const c_char,
+    path: *const c_char,
+) -> *mut AklypseErrorHandle {
+    let path = (!path.is_null()).then(|| PathBuf::from(c_str(path)));
+    let error = IoSnafu {
+        source: Arc::new(std::io::Error::from_raw_os_error(errno)),
+        path,
+        operation: c_str(operation),
+    }
+    .build();
+    AklypseErrorHandle::into_raw(error)
+}
+
+/// Error of the category with code `category` carrying `message`
+///
+/// Configuration, validation, state conflict, concurrency and external
+/// service codes produce errors of that category; any other code produces an
+/// internal error.
+///
+/// # Safety
+///
+/// `message` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_new(category: c_int, message: *const c_char) -> *mut AklypseErrorHandle {
+    let message = c_str(message);
+    let error = match category_from_code(category) {
+        Some(ErrorCategory::Configuration) => ConfigSnafu { message, path: None::<PathBuf>, source: None }.build(),
+        Some(ErrorCategory::Validation) => ValidationSnafu { field: "ffi", message }.build(),
+        Some(ErrorCategory::StateConflict) => StateConflictSnafu { message }.build(),
+        Some(ErrorCategory::Concurrency) => ConcurrencySnafu { message, source: None }.build(),
+        Some(ErrorCategory::ExternalService) => ExternalServiceSnafu { service_name: "ffi", message, source: None }.build(),
+        _ => InternalSnafu { message, source: None }.build(),
+    };
+    AklypseErrorHandle::into_raw(error)
+}
+
+/// Release a handle; null is ignored
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, not used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_free(handle: *mut AklypseErrorHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Category code of the error (see `category_code`), -1 for null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_category(handle: *const AklypseErrorHandle) -> c_int {
+    handle.as_ref().map_or(-1, |handle| category_code(handle.error.category()))
+}
+
+/// Severity code of the error (see `severity_code`), -1 for null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_severity(handle: *const AklypseErrorHandle) -> c_int {
+    handle.as_ref().map_or(-1, |handle| severity_code(handle.error.severity()))
+}
+
+/// Copy the error code (e.g. `AKL-IO`) into `buffer`; returns its length, 0 for null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle; `buffer` must be null or valid for `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_code(handle: *const AklypseErrorHandle, buffer: *mut c_char, capacity: usize) -> usize {
+    let Some(handle) = handle.as_ref() else {
+        return 0;
+    };
+    let code = ErrorReport::from_error(&handle.error, &ErrorReportConfig::default()).code().unwrap_or_default();
+    copy_out(&code, buffer, capacity)
+}
+
+/// Copy the error message into `buffer`; returns its length, 0 for null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle; `buffer` must be null or valid for `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_message(handle: *const AklypseErrorHandle, buffer: *mut c_char, capacity: usize) -> usize {
+    let Some(handle) = handle.as_ref() else {
+        return 0;
+    };
+    copy_out(&message(&handle.error), buffer, capacity)
+}
+
+/// Copy the error's JSON report into `buffer`; returns its length, 0 for null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle; `buffer` must be null or valid for `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aklypse_error_to_json(handle: *const AklypseErrorHandle, buffer: *mut c_char, capacity: usize) -> usize {
+    let Some(handle) = handle.as_ref() else {
+        return 0;
+    };
+    let json = ErrorReport::from_error(&handle.error, &ErrorReportConfig::default()).to_json(false);
+    copy_out(&json, buffer, capacity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    // Read a text getter the way C would: size, allocate, fill
+    unsafe fn read(getter: unsafe extern "C" fn(*const AklypseErrorHandle, *mut c_char, usize) -> usize, handle: *const AklypseErrorHandle) -> String {
+        let len = getter(handle, std::ptr::null_mut(), 0);
+        let mut buffer = vec![0 as c_char; len + 1];
+        assert_eq!(getter(handle, buffer.as_mut_ptr(), buffer.len()), len);
+        CStr::from_ptr(buffer.as_ptr()).to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_errno_round_trip() {
+        let operation = CString::new("open").unwrap();
+        let path = CString::new("/etc/aklypse.toml").unwrap();
+        unsafe {
+            let handle = aklypse_error_from_errno(2, operation.as_ptr(), path.as_ptr());
+            assert_eq!(category_from_code(aklypse_error_category(handle)), Some(ErrorCategory::Io));
+            assert_eq!(aklypse_error_severity(handle), severity_code((*handle).error().severity()));
+            assert_eq!(read(aklypse_error_code, handle), "AKL-IO");
+            assert_eq!(read(aklypse_error_message, handle), std::io::Error::from_raw_os_error(2).to_string());
+            assert!(read(aklypse_error_to_json, handle).starts_with('{'));
+
+            let error = AklypseErrorHandle::from_raw(handle);
+            assert!(matches!(error, AklypseError::Io { path: Some(path), .. } if path.as_path() == std::path::Path::new("/etc/aklypse.toml")));
+        }
+    }
+
+    #[test]
+    fn test_new_truncation_and_null_handles() {
+        let message = CString::new("quota exceeded").unwrap();
+        unsafe {
+            let handle = aklypse_error_new(category_code(ErrorCategory::Validation), message.as_ptr());
+            assert_eq!(category_from_code(aklypse_error_category(handle)), Some(ErrorCategory::Validation));
+
+            let mut small = [0x7f as c_char; 6];
+            assert_eq!(aklypse_error_code(handle, small.as_mut_ptr(), small.len()), "AKL-VALIDATION".len());
+            assert_eq!(CStr::from_ptr(small.as_ptr()).to_str().unwrap(), "AKL-V");
+            aklypse_error_free(handle);
+
+            aklypse_error_free(std::ptr::null_mut());
+            assert_eq!(aklypse_error_category(std::ptr::null()), -1);
+            assert_eq!(aklypse_error_message(std::ptr::null(), std::ptr::null_mut(), 0), 0);
+        }
+        assert_eq!(category_from_code(-3), None);
+    }
+}
@@ -0,0 +1,262 @@
+/* src/common/error/health.rs */
+#![warn(missing_docs)]
+//! **Brief:** Health report aggregating breakers, bulkheads and rate limiters.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Health Checks]
+//!  - [Fault Tolerance]
+//!  - [Monitoring]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `HealthRegistry` holds the circuit breakers, bulkheads and rate
+//! limiters of an application and turns their current state into a
+//! `HealthReport`: one `HealthStatus` per component with its details, and
+//! the worst of them as the overall status. With the `serde` feature the
+//! report serializes as-is, ready to be returned by an HTTP health endpoint
+//! with `HealthStatus::http_status` as the response code.
+//!
+//! A component is judged as follows:
+//!  - a breaker is unhealthy while open, degraded while half-open
+//!  - a bulkhead is degraded while every slot is taken
+//!  - a rate limiter is degraded while it has no permit left
+
+use super::{Bulkhead, CircuitBreaker, CircuitState, RateLimiter};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+/// Health of a component or of the whole application, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum HealthStatus {
+    /// Working normally
+    Healthy,
+    /// Working, with reduced capacity
+    Degraded,
+    /// Failing calls
+    Unhealthy,
+}
+
+impl HealthStatus {
+    /// Response code for a health endpoint: 200 unless unhealthy, then 503
+    pub fn http_status(self) -> u16 {
+        match self {
+            HealthStatus::Healthy | HealthStatus::Degraded => 200,
+            HealthStatus::Unhealthy => 503,
+        }
+    }
+}
+
+impl fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HealthStatus::Healthy => f.write_str("healthy"),
+            HealthStatus::Degraded => f.write_str("degraded"),
+            HealthStatus::Unhealthy => f.write_str("unhealthy"),
+        }
+    }
+}
+
+/// Kind of a component of a `HealthReport`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComponentKind {
+    /// A `CircuitBreaker`
+    CircuitBreaker,
+    /// A `Bulkhead`
+    Bulkhead,
+    /// A `RateLimiter`
+    RateLimiter,
+}
+
+/// Health of one component
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentHealth {
+    /// Name the component was created with
+    pub name: String,
+    /// What the component is
+    pub kind: ComponentKind,
+    /// Health of the component
+    pub status: HealthStatus,
+    /// Current state and counters of the component, rendered as text
+    pub details: BTreeMap<String, String>,
+}
+
+/// Health of every registered component at one point in time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthReport {
+    /// Worst status of the components; healthy without any
+    pub status: HealthStatus,
+    /// When the report was taken
+    pub timestamp: SystemTime,
+    /// Components in registration order, breakers first
+    pub components: Vec<ComponentHealth>,
+}
+
+impl HealthReport {
+    /// Components that are not healthy
+    pub fn failing(&self) -> impl Iterator<Item = &ComponentHealth> {
+        self.components.iter().filter(|component| component.status != HealthStatus::Healthy)
+    }
+}
+
+/// Components whose health makes up a `HealthReport`
+#[derive(Default)]
+pub struct HealthRegistry {
+    circuit_breakers: RwLock<Vec<Arc<CircuitBreaker>>>,
+    bulkheads: RwLock<Vec<Arc<Bulkhead>>>,
+    rate_limiters: RwLock<Vec<Arc<RateLimiter>>>,
+}
+
+impl HealthRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include `breaker` in the reports
+    pub fn register_circuit_breaker(&self, breaker: Arc<CircuitBreaker>) {
+        self.circuit_breakers.write().unwrap_or_else(|p| p.into_inner()).push(breaker);
+    }
+
+    /// Include `bulkhead` in the reports
+    pub fn register_bulkhead(&self, bulkhead: Arc<Bulkhead>) {
+        self.bulkheads.write().unwrap_or_else(|p| p.into_inner()).push(bulkhead);
+    }
+
+    /// Include `limiter` in the reports
+    pub fn register_rate_limiter(&self, limiter: Arc<RateLimiter>) {
+        self.rate_limiters.write().unwrap_or_else(|p| p.into_inner()).push(limiter);
+    }
+
+    /// Take a report of the current health of every component
+    pub fn report(&self) -> HealthReport {
+        let mut components = Vec::new();
+        for breaker in self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            components.push(circuit_breaker_health(breaker));
+        }
+        for bulkhead in self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            components.push(bulkhead_health(bulkhead));
+        }
+        for limiter in self.rate_limiters.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            components.push(rate_limiter_health(limiter));
+        }
+        HealthReport {
+            status: components.iter().map(|component| component.status).max().unwrap_or(HealthStatus::Healthy),
+            timestamp: SystemTime::now(),
+            components,
+        }
+    }
+}
+
+impl fmt::Debug for HealthRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthRegistry").field("report", &self.report()).finish()
+    }
+}
+
+fn details<const N: usize>(entries: [(&str, String); N]) -> BTreeMap<String, String> {
+    entries.into_iter().map(|(key, value)| (key.to_string(), value)).collect()
+}
+
+fn circuit_breaker_health(breaker: &CircuitBreaker) -> ComponentHealth {
+    let metrics = breaker.metrics();
+    let status = match metrics.state {
+        CircuitState::Closed | CircuitState::ForcedClosed => HealthStatus::Healthy,
+        CircuitState::HalfOpen => HealthStatus::Degraded,
+        CircuitState::Open | CircuitState::ForcedOpen => HealthStatus::Unhealthy,
+    };
+    let mut details = details([
+        ("state", metrics.state.to_string()),
+        ("total_requests", metrics.total_requests.to_string()),
+        ("failed_requests", metrics.failed_requests.to_string()),
+        ("rejected_requests", metrics.rejected_requests.to_string()),
+        ("consecutive_failures", metrics.consecutive_failures.to_string()),
+    ]);
+    if let Some(rate) = metrics.failure_rate_in_window {
+        details.insert("failure_rate".to_string(), format!("{:.3}", rate));
+    }
+    ComponentHealth { name: breaker.name().to_string(), kind: ComponentKind::CircuitBreaker, status, details }
+}
+
+fn bulkhead_health(bulkhead: &Bulkhead) -> ComponentHealth {
+    let metrics = bulkhead.metrics();
+    let status = if metrics.in_flight >= metrics.max_concurrent { HealthStatus::Degraded } else { HealthStatus::Healthy };
+    let details = details([
+        ("in_flight", metrics.in_flight.to_string()),
+        ("max_concurrent", metrics.max_concurrent.to_string()),
+        ("queued", metrics.queued.to_string()),
+        ("rejected_requests", (metrics.rejected_requests + metrics.wait_timeouts).to_string()),
+    ]);
+    ComponentHealth { name: bulkhead.name().to_string(), kind: ComponentKind::Bulkhead, status, details }
+}
+
+fn rate_limiter_health(limiter: &RateLimiter) -> ComponentHealth {
+    let metrics = limiter.metrics();
+    let status = if metrics.available_permits == 0 { HealthStatus::Degraded } else { HealthStatus::Healthy };
+    let details = details([
+        ("available_permits", metrics.available_permits.to_string()),
+        ("permitted_requests", metrics.permitted_requests.to_string()),
+        ("rejected_requests", metrics.rejected_requests.to_string()),
+    ]);
+    ComponentHealth { name: limiter.name().to_string(), kind: ComponentKind::RateLimiter, status, details }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{BulkheadConfig, CircuitBreakerConfig, RateLimitStrategy, RateLimiterConfig};
+    use std::time::Duration;
+
+    fn registry() -> (HealthRegistry, Arc<CircuitBreaker>, Arc<Bulkhead>, Arc<RateLimiter>) {
+        let registry = HealthRegistry::new();
+        let breaker = CircuitBreaker::new("payments", CircuitBreakerConfig::default());
+        let bulkhead = Bulkhead::new("exports", BulkheadConfig { max_concurrent: 1, ..Default::default() });
+        let strategy = RateLimitStrategy::FixedWindow { limit: 1, window: Duration::from_secs(60) };
+        let limiter = RateLimiter::new("search", RateLimiterConfig { strategy, ..Default::default() });
+        registry.register_circuit_breaker(breaker.clone());
+        registry.register_bulkhead(bulkhead.clone());
+        registry.register_rate_limiter(limiter.clone());
+        (registry, breaker, bulkhead, limiter)
+    }
+
+    #[test]
+    fn test_report_takes_worst_component_status() {
+        let (registry, breaker, bulkhead, limiter) = registry();
+        let report = registry.report();
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.components.len(), 3);
+        assert_eq!(report.failing().count(), 0);
+
+        let _permit = bulkhead.try_acquire().unwrap();
+        limiter.try_acquire().unwrap();
+        let report = registry.report();
+        assert_eq!((report.status, report.status.http_status()), (HealthStatus::Degraded, 200));
+        let failing: Vec<_> = report.failing().map(|component| (component.kind, component.name.as_str())).collect();
+        assert_eq!(failing, [(ComponentKind::Bulkhead, "exports"), (ComponentKind::RateLimiter, "search")]);
+
+        breaker.trip();
+        let report = registry.report();
+        assert_eq!((report.status, report.status.http_status()), (HealthStatus::Unhealthy, 503));
+        assert_eq!(report.components[0].details["state"], "Open");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_serializes_for_endpoints() {
+        let (registry, breaker, ..) = registry();
+        breaker.trip();
+        let json = serde_json::to_value(registry.report()).unwrap();
+        assert_eq!(json["status"], "unhealthy");
+        assert_eq!(json["components"][0]["kind"], "CircuitBreaker");
+        assert_eq!(json["components"][0]["details"]["state"], "Open");
+    }
+}
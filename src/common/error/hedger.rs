@@ -0,0 +1,251 @@
+/* src/common/error/hedger.rs */
+#![warn(missing_docs)]
+//! **Brief:** Hedged execution racing a delayed second attempt against a slow first one.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Hedged Requests]
+//!  - [Fault Tolerance]
+//!  - [Tail Latency]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `Hedger` cuts the tail latency of calls to flaky services. It starts an
+//! async operation and, if it has not completed after `hedge_delay`, starts
+//! a second attempt alongside it. The first attempt to succeed wins; the
+//! other is cancelled by dropping its future. When the first attempt fails
+//! before the delay, the hedge starts straight away (`hedge_on_failure`).
+//! The call fails only when every attempt it started has failed, with the
+//! first error.
+//!
+//! With a `CircuitBreaker` attached, the hedged call runs through it as a
+//! single operation: the breaker's state admits or rejects the whole call,
+//! and a call whose attempts all failed is recorded as one failure.
+
+use super::{AklypseError, CircuitBreaker, ErrorContext, Result};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Metadata key holding the name of the hedger whose attempts all failed
+pub const HEDGER_METADATA_KEY: &str = "hedger";
+
+/// Configuration for the Hedger.
+#[derive(Debug, Clone)]
+pub struct HedgerConfig {
+    /// How long the first attempt runs alone before the hedge starts.
+    pub hedge_delay: Duration,
+    /// Start the hedge as soon as the first attempt fails, instead of failing the call.
+    pub hedge_on_failure: bool,
+}
+
+impl Default for HedgerConfig {
+    fn default() -> Self {
+        Self {
+            hedge_delay: Duration::from_millis(100),
+            hedge_on_failure: true,
+        }
+    }
+}
+
+/// Metrics collected by the hedger
+#[derive(Debug, Clone, Default)]
+pub struct HedgerMetrics {
+    /// Calls executed
+    pub total_requests: u64,
+    /// Calls that started a hedge
+    pub hedged_requests: u64,
+    /// Calls won by the hedge
+    pub hedge_wins: u64,
+    /// Calls whose attempts all failed
+    pub failed_requests: u64,
+}
+
+/// Races a delayed second attempt against a slow first one.
+pub struct Hedger {
+    name: String,
+    config: HedgerConfig,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    metrics: Mutex<HedgerMetrics>,
+}
+
+impl Hedger {
+    /// Creates a new Hedger instance
+    pub fn new(name: impl Into<String>, config: HedgerConfig) -> Self {
+        Self {
+            name: name.into(),
+            config,
+            circuit_breaker: None,
+            metrics: Mutex::new(HedgerMetrics::default()),
+        }
+    }
+
+    /// Run hedged calls through `circuit_breaker`
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Name the hedger was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the current metrics of the hedger
+    pub fn metrics(&self) -> HedgerMetrics {
+        self.lock().clone()
+    }
+
+    /// Execute an async operation, hedging it with a second attempt if it is slow
+    ///
+    /// `operation` is called once per attempt, so at most twice.
+    pub async fn execute<F, Fut, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Ret>>,
+    {
+        match &self.circuit_breaker {
+            Some(circuit_breaker) => circuit_breaker.execute_async(|| self.race(&operation)).await,
+            None => self.race(&operation).await,
+        }
+    }
+
+    async fn race<F, Fut, Ret>(&self, operation: &F) -> Result<Ret>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<Ret>>,
+    {
+        self.lock().total_requests += 1;
+        let primary = operation();
+        tokio::pin!(primary);
+        let mut first_error = tokio::select! {
+            result = &mut primary => match result {
+                Ok(value) => return Ok(value),
+                Err(error) if !self.config.hedge_on_failure => return Err(self.failed(error, 1)),
+                Err(error) => Some(error),
+            },
+            _ = tokio::time::sleep(self.config.hedge_delay) => None,
+        };
+
+        self.lock().hedged_requests += 1;
+        let hedge = operation();
+        tokio::pin!(hedge);
+        let mut primary_done = first_error.is_some();
+        let mut hedge_done = false;
+        while !(primary_done && hedge_done) {
+            tokio::select! {
+                result = &mut primary, if !primary_done => match result {
+                    Ok(value) => return Ok(value),
+                    Err(error) => {
+                        primary_done = true;
+                        first_error.get_or_insert(error);
+                    }
+                },
+                result = &mut hedge, if !hedge_done => match result {
+                    Ok(value) => {
+                        self.lock().hedge_wins += 1;
+                        return Ok(value);
+                    }
+                    Err(error) => {
+                        hedge_done = true;
+                        first_error.get_or_insert(error);
+                    }
+                },
+            }
+        }
+        let error = first_error.expect("both attempts failed");
+        Err(self.failed(error, 2))
+    }
+
+    fn failed(&self, error: AklypseError, attempts: u32) -> AklypseError {
+        self.lock().failed_requests += 1;
+        error.add_context(
+            ErrorContext::new(format!("All {} attempts of hedger '{}' failed", attempts, self.name))
+                .with_metadata(HEDGER_METADATA_KEY, self.name.clone()),
+        )
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HedgerMetrics> {
+        self.metrics.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+impl std::fmt::Debug for Hedger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hedger")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("circuit_breaker", &self.circuit_breaker.as_ref().map(|breaker| breaker.name().to_string()))
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreakerConfig, CircuitState, InternalSnafu};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn hedger(hedge_delay: Duration) -> Hedger {
+        Hedger::new("lookup", HedgerConfig { hedge_delay, ..Default::default() })
+    }
+
+    #[tokio::test]
+    async fn test_fast_call_is_not_hedged() {
+        let hedger = hedger(Duration::from_millis(50));
+        let calls = AtomicUsize::new(0);
+        let value = hedger.execute(|| async { Ok(calls.fetch_add(1, Ordering::SeqCst)) }).await.unwrap();
+        assert_eq!((value, calls.load(Ordering::SeqCst)), (0, 1));
+        assert_eq!(hedger.metrics().hedged_requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedge_wins_and_slow_attempt_is_cancelled() {
+        let hedger = hedger(Duration::from_millis(10));
+        let calls = AtomicUsize::new(0);
+        let finished = AtomicUsize::new(0);
+        let value = hedger
+            .execute(|| async {
+                let attempt = calls.fetch_add(1, Ordering::SeqCst);
+                if attempt == 0 {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+                finished.fetch_add(1, Ordering::SeqCst);
+                Ok(attempt)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(value, 1);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        let metrics = hedger.metrics();
+        assert_eq!((metrics.total_requests, metrics.hedged_requests, metrics.hedge_wins), (1, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_failures_feed_the_circuit_breaker() {
+        let config = CircuitBreakerConfig { failure_threshold: 1, operation_timeout: None, ..Default::default() };
+        let breaker = CircuitBreaker::new("lookup", config);
+        let hedger = hedger(Duration::from_secs(5)).with_circuit_breaker(breaker.clone());
+        let calls = AtomicUsize::new(0);
+
+        let error = hedger
+            .execute(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(InternalSnafu { message: "backend down", source: None }.build())
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(HEDGER_METADATA_KEY).map(String::as_str), Some("lookup"));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(hedger.metrics().failed_requests, 1);
+
+        let rejected = hedger.execute(|| async { Ok(()) }).await.unwrap_err();
+        assert!(matches!(rejected, AklypseError::CircuitBreakerOpen { .. }));
+    }
+}
@@ -0,0 +1,375 @@
+/* src/common/error/labels.rs */
+#![warn(missing_docs)]
+//! **Brief:** Localizable message catalog for the fixed labels in error reports.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Localization]
+//!  - [Message Catalogs]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Human-readable report formats (Plain, Markdown, HTML) look their fixed
+//! strings up here using the locale selected on `ErrorReportConfig`. English
+//! is always available and is used whenever a translation is missing, so
+//! log-bound reports keep rendering exactly as before. Structured formats
+//! such as JSON keep their English field names.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Locale used when none is selected or a translation is missing
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Fixed strings appearing in rendered error reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportLabel {
+    /// Header of every report ("Error")
+    Error,
+    /// Report reference code ("Report ID")
+    ReportId,
+    /// Severity line ("Severity")
+    Severity,
+    /// Cause chain entries ("Caused by")
+    CausedBy,
+    /// Marker for a truncated cause chain ("... (more causes hidden)")
+    MoreCausesHidden,
+    /// Extracted structured fields ("Fields")
+    Fields,
+    /// Rich context section ("Context")
+    Context,
+    /// Component name ("Component")
+    Component,
+    /// Correlation id ("Correlation ID")
+    CorrelationId,
+    /// Context timestamp ("Timestamp")
+    Timestamp,
+    /// Source location ("Location")
+    Location,
+    /// Context tags ("Tags")
+    Tags,
+    /// Recovery suggestion ("Recovery suggestion")
+    RecoverySuggestion,
+    /// Typed recovery steps ("Recovery actions")
+    RecoveryActions,
+    /// Diagnostics section ("Diagnostics")
+    Diagnostics,
+    /// Diagnostic code ("Code")
+    Code,
+    /// Diagnostic location ("At")
+    At,
+    /// Tool-suggested fix ("Suggested")
+    Suggested,
+    /// Backtrace section ("Backtrace")
+    Backtrace,
+    /// Decrust autocorrection section ("Suggested fixes")
+    SuggestedFixes,
+    /// Autocorrection confidence ("confidence")
+    Confidence,
+    /// Pagination marker of streamed reports ("Page")
+    Page,
+    /// Summary of capped entries sharing one category; `{count}` and `{category}` are substituted
+    MoreOfCategory,
+    /// Summary of capped entries across categories; `{count}` and `{breakdown}` are substituted
+    MoreOmitted,
+    /// Header of an end-of-run summary ("Error summary")
+    ErrorSummary,
+    /// Total number of errors ("Total")
+    Total,
+    /// Counts per category ("By category")
+    ByCategory,
+    /// Counts per severity ("By severity")
+    BySeverity,
+    /// Counts per error code ("By code")
+    ByCode,
+    /// Most frequent distinct errors ("Most frequent")
+    MostFrequent,
+    /// Earliest occurrence of an error ("first seen")
+    FirstSeen,
+    /// Latest occurrence of an error ("last seen")
+    LastSeen,
+}
+
+impl ReportLabel {
+    /// Every label, in a stable order
+    pub const ALL: [ReportLabel; 32] = [
+        ReportLabel::Error,
+        ReportLabel::ReportId,
+        ReportLabel::Severity,
+        ReportLabel::CausedBy,
+        ReportLabel::MoreCausesHidden,
+        ReportLabel::Fields,
+        ReportLabel::Context,
+        ReportLabel::Component,
+        ReportLabel::CorrelationId,
+        ReportLabel::Timestamp,
+        ReportLabel::Location,
+        ReportLabel::Tags,
+        ReportLabel::RecoverySuggestion,
+        ReportLabel::RecoveryActions,
+        ReportLabel::Diagnostics,
+        ReportLabel::Code,
+        ReportLabel::At,
+        ReportLabel::Suggested,
+        ReportLabel::Backtrace,
+        ReportLabel::SuggestedFixes,
+        ReportLabel::Confidence,
+        ReportLabel::Page,
+        ReportLabel::MoreOfCategory,
+        ReportLabel::MoreOmitted,
+        ReportLabel::ErrorSummary,
+        ReportLabel::Total,
+        ReportLabel::ByCategory,
+        ReportLabel::BySeverity,
+        ReportLabel::ByCode,
+        ReportLabel::MostFrequent,
+        ReportLabel::FirstSeen,
+        ReportLabel::LastSeen,
+    ];
+
+    /// English text of the label
+    pub fn english(self) -> &'static str {
+        match self {
+            ReportLabel::Error => "Error",
+            ReportLabel::ReportId => "Report ID",
+            ReportLabel::Severity => "Severity",
+            ReportLabel::CausedBy => "Caused by",
+            ReportLabel::MoreCausesHidden => "... (more causes hidden)",
+            ReportLabel::Fields => "Fields",
+            ReportLabel::Context => "Context",
+            ReportLabel::Component => "Component",
+            ReportLabel::CorrelationId => "Correlation ID",
+            ReportLabel::Timestamp => "Timestamp",
+            ReportLabel::Location => "Location",
+            ReportLabel::Tags => "Tags",
+            ReportLabel::RecoverySuggestion => "Recovery suggestion",
+            ReportLabel::RecoveryActions => "Recovery actions",
+            ReportLabel::Diagnostics => "Diagnostics",
+            ReportLabel::Code => "Code",
+            ReportLabel::At => "At",
+            ReportLabel::Suggested => "Suggested",
+            ReportLabel::Backtrace => "Backtrace",
+            ReportLabel::SuggestedFixes => "Suggested fixes",
+            ReportLabel::Confidence => "confidence",
+            ReportLabel::Page => "Page",
+            ReportLabel::MoreOfCategory => "... and {count} more of category {category}",
+            ReportLabel::MoreOmitted => "... and {count} more ({breakdown})",
+            ReportLabel::ErrorSummary => "Error summary",
+            ReportLabel::Total => "Total",
+            ReportLabel::ByCategory => "By category",
+            ReportLabel::BySeverity => "By severity",
+            ReportLabel::ByCode => "By code",
+            ReportLabel::MostFrequent => "Most frequent",
+            ReportLabel::FirstSeen => "first seen",
+            ReportLabel::LastSeen => "last seen",
+        }
+    }
+}
+
+/// Message catalog mapping locales to translated report labels
+#[derive(Debug, Clone, Default)]
+pub struct LabelCatalog {
+    translations: HashMap<String, HashMap<ReportLabel, String>>,
+}
+
+impl LabelCatalog {
+    /// Create an empty catalog (every lookup falls back to English)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Catalog with the translations shipped with the crate
+    pub fn builtin() -> &'static LabelCatalog {
+        static BUILTIN: OnceLock<LabelCatalog> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            use ReportLabel::*;
+            LabelCatalog::new()
+                .with_locale("es", [
+                    (Error, "Error"),
+                    (ReportId, "ID del informe"),
+                    (Severity, "Gravedad"),
+                    (CausedBy, "Causado por"),
+                    (MoreCausesHidden, "... (más causas ocultas)"),
+                    (Fields, "Campos"),
+                    (Context, "Contexto"),
+                    (Component, "Componente"),
+                    (CorrelationId, "ID de correlación"),
+                    (Timestamp, "Marca de tiempo"),
+                    (Location, "Ubicación"),
+                    (Tags, "Etiquetas"),
+                    (RecoverySuggestion, "Sugerencia de recuperación"),
+                    (RecoveryActions, "Acciones de recuperación"),
+                    (Diagnostics, "Diagnóstico"),
+                    (Code, "Código"),
+                    (At, "En"),
+                    (Suggested, "Sugerido"),
+                    (Backtrace, "Traza"),
+                    (SuggestedFixes, "Correcciones sugeridas"),
+                    (Confidence, "confianza"),
+                    (Page, "Página"),
+                    (MoreOfCategory, "... y {count} más de la categoría {category}"),
+                    (MoreOmitted, "... y {count} más ({breakdown})"),
+                    (ErrorSummary, "Resumen de errores"),
+                    (Total, "Total"),
+                    (ByCategory, "Por categoría"),
+                    (BySeverity, "Por gravedad"),
+                    (ByCode, "Por código"),
+                    (MostFrequent, "Más frecuentes"),
+                    (FirstSeen, "visto por primera vez"),
+                    (LastSeen, "visto por última vez"),
+                ])
+                .with_locale("de", [
+                    (Error, "Fehler"),
+                    (ReportId, "Berichts-ID"),
+                    (Severity, "Schweregrad"),
+                    (CausedBy, "Verursacht durch"),
+                    (MoreCausesHidden, "... (weitere Ursachen ausgeblendet)"),
+                    (Fields, "Felder"),
+                    (Context, "Kontext"),
+                    (Component, "Komponente"),
+                    (CorrelationId, "Korrelations-ID"),
+                    (Timestamp, "Zeitstempel"),
+                    (Location, "Ort"),
+                    (Tags, "Tags"),
+                    (RecoverySuggestion, "Lösungsvorschlag"),
+                    (RecoveryActions, "Wiederherstellungsaktionen"),
+                    (Diagnostics, "Diagnose"),
+                    (Code, "Code"),
+                    (At, "Bei"),
+                    (Suggested, "Vorgeschlagen"),
+                    (Backtrace, "Backtrace"),
+                    (SuggestedFixes, "Vorgeschlagene Korrekturen"),
+                    (Confidence, "Konfidenz"),
+                    (Page, "Seite"),
+                    (MoreOfCategory, "... und {count} weitere der Kategorie {category}"),
+                    (MoreOmitted, "... und {count} weitere ({breakdown})"),
+                    (ErrorSummary, "Fehlerübersicht"),
+                    (Total, "Gesamt"),
+                    (ByCategory, "Nach Kategorie"),
+                    (BySeverity, "Nach Schweregrad"),
+                    (ByCode, "Nach Code"),
+                    (MostFrequent, "Am häufigsten"),
+                    (FirstSeen, "zuerst gesehen"),
+                    (LastSeen, "zuletzt gesehen"),
+                ])
+                .with_locale("fr", [
+                    (Error, "Erreur"),
+                    (ReportId, "ID du rapport"),
+                    (Severity, "Gravité"),
+                    (CausedBy, "Causé par"),
+                    (MoreCausesHidden, "... (autres causes masquées)"),
+                    (Fields, "Champs"),
+                    (Context, "Contexte"),
+                    (Component, "Composant"),
+                    (CorrelationId, "ID de corrélation"),
+                    (Timestamp, "Horodatage"),
+                    (Location, "Emplacement"),
+                    (Tags, "Étiquettes"),
+                    (RecoverySuggestion, "Suggestion de résolution"),
+                    (RecoveryActions, "Actions de récupération"),
+                    (Diagnostics, "Diagnostics"),
+                    (Code, "Code"),
+                    (At, "À"),
+                    (Suggested, "Suggéré"),
+                    (Backtrace, "Trace d'appels"),
+                    (SuggestedFixes, "Corrections suggérées"),
+                    (Confidence, "confiance"),
+                    (Page, "Page"),
+                    (MoreOfCategory, "... et {count} de plus de la catégorie {category}"),
+                    (MoreOmitted, "... et {count} de plus ({breakdown})"),
+                    (ErrorSummary, "Résumé des erreurs"),
+                    (Total, "Total"),
+                    (ByCategory, "Par catégorie"),
+                    (BySeverity, "Par gravité"),
+                    (ByCode, "Par code"),
+                    (MostFrequent, "Les plus fréquentes"),
+                    (FirstSeen, "vu pour la première fois"),
+                    (LastSeen, "vu pour la dernière fois"),
+                ])
+        })
+    }
+
+    /// Add or replace a single translation
+    pub fn with_translation(mut self, locale: impl Into<String>, label: ReportLabel, text: impl Into<String>) -> Self {
+        self.translations
+            .entry(normalize_locale(&locale.into()))
+            .or_default()
+            .insert(label, text.into());
+        self
+    }
+
+    /// Add or replace several translations for one locale
+    pub fn with_locale<I, T>(mut self, locale: impl Into<String>, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (ReportLabel, T)>,
+        T: Into<String>,
+    {
+        let table = self.translations.entry(normalize_locale(&locale.into())).or_default();
+        for (label, text) in entries {
+            table.insert(label, text.into());
+        }
+        self
+    }
+
+    /// Locales that have at least one translation
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.translations.keys().map(String::as_str)
+    }
+
+    /// Look up a translation, falling back from `pt-BR` to `pt`, without the English fallback
+    pub fn get(&self, locale: &str, label: ReportLabel) -> Option<&str> {
+        let locale = normalize_locale(locale);
+        let language = locale.split('-').next().unwrap_or_default();
+
+        [locale.as_str(), language]
+            .iter()
+            .find_map(|candidate| self.translations.get(*candidate).and_then(|table| table.get(&label)))
+            .map(String::as_str)
+    }
+
+    /// Look up a label, falling back from `pt-BR` to `pt` and finally to English
+    pub fn lookup(&self, locale: &str, label: ReportLabel) -> &str {
+        self.get(locale, label).unwrap_or_else(|| label.english())
+    }
+}
+
+// Lowercase and use '-' as separator, so "pt_BR" and "pt-br" match the same table
+pub(crate) fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_fallback() {
+        let catalog = LabelCatalog::new();
+        assert_eq!(catalog.lookup("ja", ReportLabel::CausedBy), "Caused by");
+        assert_eq!(catalog.lookup(DEFAULT_LOCALE, ReportLabel::Error), "Error");
+    }
+
+    #[test]
+    fn test_region_falls_back_to_language() {
+        let catalog = LabelCatalog::new()
+            .with_translation("pt", ReportLabel::Error, "Erro")
+            .with_translation("pt_BR", ReportLabel::CausedBy, "Causado por");
+
+        assert_eq!(catalog.lookup("pt-BR", ReportLabel::CausedBy), "Causado por");
+        assert_eq!(catalog.lookup("pt-BR", ReportLabel::Error), "Erro");
+    }
+
+    #[test]
+    fn test_builtin_catalog_is_complete() {
+        let catalog = LabelCatalog::builtin();
+        for locale in ["es", "de", "fr"] {
+            for label in ReportLabel::ALL {
+                assert!(!catalog.lookup(locale, label).is_empty());
+            }
+        }
+        assert_eq!(catalog.lookup("de-AT", ReportLabel::Error), "Fehler");
+    }
+}
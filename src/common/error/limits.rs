@@ -0,0 +1,356 @@
+/* src/common/error/limits.rs */
+#![warn(missing_docs)]
+//! **Brief:** Size limits for error contexts with explicit overflow accounting.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [Size Limits]
+//!  - [Overflow Accounting]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Contexts travel through every sink, so a single oversized value can take a
+//! log pipeline down. The installed `ContextPolicy` caps the number of
+//! metadata entries, the length of each metadata value and the number of
+//! tags. `ErrorContext::with_metadata` and `ErrorContext::add_tag` apply it on
+//! insert, and `AklypseError::add_context` applies it to the whole context.
+//! Nothing is dropped silently: the context records what was cut under
+//! `OVERFLOW_METADATA_KEY`.
+//!
+//! Scrubbers registered with `ContextPolicy::register_scrubber` run on the
+//! same insert path, so sensitive values (passwords, authorization headers)
+//! are masked as soon as they enter a context, before any sink can see them.
+
+use super::types::ErrorContext;
+use std::fmt;
+use std::sync::RwLock;
+
+/// Metadata key recording what a `ContextPolicy` dropped or truncated
+pub const OVERFLOW_METADATA_KEY: &str = "overflow";
+
+/// Suffix appended to metadata values cut to the length limit
+pub const TRUNCATION_MARKER: &str = "...[truncated]";
+
+/// Replacement written by the `redact` scrubber
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Function masking a sensitive metadata value in place
+pub type Scrubber = fn(&mut String);
+
+static INSTALLED_POLICY: RwLock<ContextPolicy> = RwLock::new(ContextPolicy::DEFAULT);
+
+static SCRUBBERS: RwLock<Vec<(String, Scrubber)>> = RwLock::new(Vec::new());
+
+/// Limits on the size of an `ErrorContext`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextPolicy {
+    /// Maximum number of metadata entries, not counting the overflow marker
+    pub max_metadata_entries: usize,
+    /// Maximum length of a metadata value in bytes, before the truncation marker
+    pub max_value_len: usize,
+    /// Maximum number of tags
+    pub max_tags: usize,
+}
+
+impl ContextPolicy {
+    /// 64 metadata entries of at most 4 KiB each, and 32 tags
+    pub const DEFAULT: ContextPolicy = ContextPolicy {
+        max_metadata_entries: 64,
+        max_value_len: 4096,
+        max_tags: 32,
+    };
+
+    /// Set the maximum number of metadata entries
+    pub fn with_max_metadata_entries(mut self, max: usize) -> Self {
+        self.max_metadata_entries = max;
+        self
+    }
+
+    /// Set the maximum length of a metadata value in bytes
+    pub fn with_max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = max;
+        self
+    }
+
+    /// Set the maximum number of tags
+    pub fn with_max_tags(mut self, max: usize) -> Self {
+        self.max_tags = max;
+        self
+    }
+
+    /// Make this the policy applied to every context, replacing the previous one
+    pub fn install(self) {
+        *INSTALLED_POLICY.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = self;
+    }
+
+    /// The installed policy (`ContextPolicy::DEFAULT` unless another was installed)
+    pub fn current() -> ContextPolicy {
+        *INSTALLED_POLICY.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Run `scrubber` on every metadata value inserted under a key matching `key_pattern`
+    ///
+    /// Patterns match case-insensitively and may use `*` as a wildcard
+    /// (`"authorization"`, `"*password*"`). Every matching scrubber runs, in
+    /// registration order.
+    pub fn register_scrubber(key_pattern: impl Into<String>, scrubber: Scrubber) {
+        let pattern = key_pattern.into().to_ascii_lowercase();
+        SCRUBBERS.write().unwrap_or_else(|poisoned| poisoned.into_inner()).push((pattern, scrubber));
+    }
+
+    /// Remove every registered scrubber
+    pub fn clear_scrubbers() {
+        SCRUBBERS.write().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+    }
+}
+
+/// Scrubber replacing the whole value with `REDACTED`
+pub fn redact(value: &mut String) {
+    value.clear();
+    value.push_str(REDACTED);
+}
+
+// Apply the scrubbers registered for `key`
+fn scrub(key: &str, value: &mut String) {
+    let key = key.to_ascii_lowercase();
+    for (pattern, scrubber) in SCRUBBERS.read().unwrap_or_else(|poisoned| poisoned.into_inner()).iter() {
+        if matches_pattern(pattern.as_bytes(), key.as_bytes()) {
+            scrubber(value);
+        }
+    }
+}
+
+// Glob match where `*` stands for any run of bytes, in O(pattern * key) at
+// worst: on a mismatch only the latest `*` is retried, one byte further
+fn matches_pattern(pattern: &[u8], key: &[u8]) -> bool {
+    let (mut p, mut k) = (0, 0);
+    // Pattern position after the latest `*`, and the key position it resumes from
+    let mut retry = None;
+    while k < key.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                p += 1;
+                retry = Some((p, k));
+            }
+            Some(&byte) if byte == key[k] => (p, k) = (p + 1, k + 1),
+            _ => match retry {
+                Some((after_star, resume)) => {
+                    (p, k) = (after_star, resume + 1);
+                    retry = Some((after_star, resume + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&byte| byte == b'*')
+}
+
+impl Default for ContextPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// What a `ContextPolicy` cut from one context, as recorded under `OVERFLOW_METADATA_KEY`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ContextOverflow {
+    /// Metadata entries dropped because the entry limit was reached
+    pub dropped_metadata: usize,
+    /// Metadata values cut to the length limit
+    pub truncated_values: usize,
+    /// Tags dropped because the tag limit was reached
+    pub dropped_tags: usize,
+}
+
+impl ContextOverflow {
+    /// Overflow recorded in a context, if anything was cut
+    pub fn of(context: &ErrorContext) -> Option<ContextOverflow> {
+        context.metadata.get(OVERFLOW_METADATA_KEY).map(|marker| Self::parse(marker))
+    }
+
+    fn parse(marker: &str) -> ContextOverflow {
+        let mut overflow = ContextOverflow::default();
+        for (key, value) in marker.split_whitespace().filter_map(|pair| pair.split_once('=')) {
+            let count = value.parse().unwrap_or(0);
+            match key {
+                "dropped_metadata" => overflow.dropped_metadata = count,
+                "truncated_values" => overflow.truncated_values = count,
+                "dropped_tags" => overflow.dropped_tags = count,
+                _ => {}
+            }
+        }
+        overflow
+    }
+
+    pub(crate) fn record(context: &mut ErrorContext, update: impl FnOnce(&mut ContextOverflow)) {
+        let mut overflow = Self::of(context).unwrap_or_default();
+        update(&mut overflow);
+        context.metadata_mut().insert(OVERFLOW_METADATA_KEY.to_string(), overflow.to_string());
+    }
+}
+
+impl fmt::Display for ContextOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dropped_metadata={} truncated_values={} dropped_tags={}",
+            self.dropped_metadata, self.truncated_values, self.dropped_tags
+        )
+    }
+}
+
+impl ErrorContext {
+    /// Insert a metadata entry within the limits of `policy`, after the registered scrubbers
+    pub fn insert_metadata_limited(&mut self, key: String, mut value: String, policy: &ContextPolicy) {
+        let entries = self.metadata.len() - usize::from(self.metadata.contains_key(OVERFLOW_METADATA_KEY));
+        if !self.metadata.contains_key(&key) && entries >= policy.max_metadata_entries {
+            ContextOverflow::record(self, |overflow| overflow.dropped_metadata += 1);
+            return;
+        }
+
+        scrub(&key, &mut value);
+        let value = match truncate_value(value, policy.max_value_len) {
+            Ok(value) => value,
+            Err(truncated) => {
+                ContextOverflow::record(self, |overflow| overflow.truncated_values += 1);
+                truncated
+            }
+        };
+        self.metadata_mut().insert(key, value);
+    }
+
+    /// Add a tag within the limits of `policy`, ignoring duplicates
+    pub fn push_tag_limited(&mut self, tag: String, policy: &ContextPolicy) {
+        if self.tags.contains(&tag) {
+            return;
+        }
+        if self.tags.len() >= policy.max_tags {
+            ContextOverflow::record(self, |overflow| overflow.dropped_tags += 1);
+            return;
+        }
+        self.tags_mut().push(tag);
+    }
+
+    /// Cut a context assembled field by field down to the limits of `policy`
+    ///
+    /// Metadata entries are kept in key order so the result is deterministic.
+    pub fn enforce_limits(&mut self, policy: &ContextPolicy) {
+        if self.tags.len() > policy.max_tags {
+            let dropped = self.tags.len() - policy.max_tags;
+            self.tags_mut().truncate(policy.max_tags);
+            ContextOverflow::record(self, |overflow| overflow.dropped_tags += dropped);
+        }
+
+        let metadata = self.metadata_mut();
+        let marker = metadata.remove(OVERFLOW_METADATA_KEY);
+        let mut entries: Vec<_> = metadata.drain().collect();
+        entries.sort();
+        if let Some(marker) = marker {
+            metadata.insert(OVERFLOW_METADATA_KEY.to_string(), marker);
+        }
+        for (key, value) in entries {
+            self.insert_metadata_limited(key, value, policy);
+        }
+    }
+}
+
+// Ok(value) when it fits, Err(truncated value) otherwise
+fn truncate_value(mut value: String, max_len: usize) -> Result<String, String> {
+    if value.len() <= max_len {
+        return Ok(value);
+    }
+    let mut cut = max_len;
+    while !value.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    value.truncate(cut);
+    value.push_str(TRUNCATION_MARKER);
+    Err(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_limits_record_overflow() {
+        let policy = ContextPolicy::DEFAULT.with_max_metadata_entries(2).with_max_value_len(4).with_max_tags(1);
+        let mut context = ErrorContext::new("oversized");
+
+        context.insert_metadata_limited("a".to_string(), "1".to_string(), &policy);
+        context.insert_metadata_limited("b".to_string(), "héllo".to_string(), &policy);
+        context.insert_metadata_limited("c".to_string(), "3".to_string(), &policy);
+        context.insert_metadata_limited("a".to_string(), "replaced".to_string(), &policy);
+        context.push_tag_limited("x".to_string(), &policy);
+        context.push_tag_limited("y".to_string(), &policy);
+
+        assert_eq!(context.metadata.get("b").map(String::as_str), Some("hél...[truncated]"));
+        assert!(!context.metadata.contains_key("c"));
+        assert_eq!(*context.tags, vec!["x"]);
+        assert_eq!(
+            ContextOverflow::of(&context),
+            Some(ContextOverflow { dropped_metadata: 1, truncated_values: 2, dropped_tags: 1 })
+        );
+    }
+
+    #[test]
+    fn test_enforce_limits_on_assembled_context() {
+        let policy = ContextPolicy::DEFAULT.with_max_metadata_entries(1).with_max_tags(2);
+        let mut context = ErrorContext::new("assembled");
+        *context.tags_mut() = vec!["a".into(), "b".into(), "c".into()];
+        context.metadata_mut().insert("z".into(), "1".into());
+        context.metadata_mut().insert("m".into(), "2".into());
+
+        context.enforce_limits(&policy);
+
+        assert_eq!(context.tags.len(), 2);
+        assert_eq!(context.metadata.get("m").map(String::as_str), Some("2"));
+        assert_eq!(context.metadata.get(OVERFLOW_METADATA_KEY).map(String::as_str), Some("dropped_metadata=1 truncated_values=0 dropped_tags=1"));
+    }
+
+    #[test]
+    fn test_scrubbers_mask_on_insert() {
+        // Keys unique to this test, so the global registry does not affect others
+        ContextPolicy::register_scrubber("*scrubtest_password*", redact);
+        ContextPolicy::register_scrubber("scrubtest_card", |value| {
+            let keep = value.len().saturating_sub(4);
+            value.replace_range(..keep, &"*".repeat(keep));
+        });
+
+        let context = ErrorContext::new("login failed")
+            .with_metadata("DB_SCRUBTEST_PASSWORD", "hunter2")
+            .with_metadata("scrubtest_card", "4111111111111111")
+            .with_metadata("scrubtest_user", "alice");
+
+        assert_eq!(context.metadata.get("DB_SCRUBTEST_PASSWORD").map(String::as_str), Some(REDACTED));
+        assert_eq!(context.metadata.get("scrubtest_card").map(String::as_str), Some("************1111"));
+        assert_eq!(context.metadata.get("scrubtest_user").map(String::as_str), Some("alice"));
+        assert!(matches_pattern(b"auth*", b"authorization"));
+        assert!(!matches_pattern(b"auth", b"authorization"));
+    }
+
+    #[test]
+    fn test_key_patterns_match_without_backtracking_blowup() {
+        assert!(matches_pattern(b"*", b""));
+        assert!(matches_pattern(b"a*b*c", b"axxbyybc"));
+        assert!(matches_pattern(b"*token", b"x_token_token"));
+        assert!(!matches_pattern(b"a*b", b"axxbc"));
+        assert!(!matches_pattern(b"*a", b""));
+
+        // Exponential for a recursive matcher trying every split
+        let key = [b'a'; 64];
+        assert!(!matches_pattern(&b"*a".repeat(20).into_iter().chain(*b"*b").collect::<Vec<_>>(), &key));
+        assert!(matches_pattern(&b"*a".repeat(20), &key));
+    }
+
+    #[test]
+    fn test_builder_applies_installed_policy() {
+        let context = (0..40).fold(ErrorContext::new("many tags"), |context, i| context.add_tag(format!("t{}", i)));
+
+        assert_eq!(context.tags.len(), ContextPolicy::current().max_tags);
+        assert!(ContextOverflow::of(&context).is_some_and(|overflow| overflow.dropped_tags == 8));
+    }
+}
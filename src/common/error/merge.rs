@@ -0,0 +1,271 @@
+/* src/common/error/merge.rs */
+#![warn(missing_docs)]
+//! **Brief:** Merging error contexts and computing the delta between two of them.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [Context Merging]
+//!  - [Context Diffing]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ErrorContext::merge` folds one context into another, e.g. the context of
+//! each retry attempt into the context of the whole operation. Tags, recovery
+//! actions and overflow counts are unioned; the `MergePolicy` decides what
+//! happens when both contexts set the same metadata key or scalar field.
+//! The result stays within the installed `ContextPolicy`.
+//!
+//! `ErrorContext::diff` reports what changed between two contexts, so tests
+//! can assert that a given layer added exactly the context it should.
+
+use super::limits::{ContextOverflow, ContextPolicy, OVERFLOW_METADATA_KEY};
+use super::types::ErrorContext;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Conflict rule of `ErrorContext::merge`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergePolicy {
+    /// Keep the value of the context being merged into
+    #[default]
+    KeepExisting,
+    /// Take the value of the context being merged in
+    Overwrite,
+    /// Keep both metadata values, joined with `"; "` unless already present; scalar fields keep the existing value
+    Combine,
+}
+
+/// Metadata entry whose value differs between two contexts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataChange {
+    /// Metadata key
+    pub key: String,
+    /// Value in the original context
+    pub before: String,
+    /// Value in the updated context
+    pub after: String,
+}
+
+/// Structured delta between two contexts, as returned by `ErrorContext::diff`
+///
+/// Collections are sorted by key or tag so equal deltas compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContextDiff {
+    /// Names of the scalar fields that differ (`message`, `severity`, `component`, ...)
+    pub changed_fields: Vec<&'static str>,
+    /// Metadata entries only present in the updated context
+    pub added_metadata: Vec<(String, String)>,
+    /// Metadata keys only present in the original context
+    pub removed_metadata: Vec<String>,
+    /// Metadata entries present in both with different values
+    pub changed_metadata: Vec<MetadataChange>,
+    /// Tags only present in the updated context
+    pub added_tags: Vec<String>,
+    /// Tags only present in the original context
+    pub removed_tags: Vec<String>,
+}
+
+impl ContextDiff {
+    /// Whether the two contexts carry the same information
+    pub fn is_empty(&self) -> bool {
+        self.changed_fields.is_empty()
+            && self.added_metadata.is_empty()
+            && self.removed_metadata.is_empty()
+            && self.changed_metadata.is_empty()
+            && self.added_tags.is_empty()
+            && self.removed_tags.is_empty()
+    }
+}
+
+impl ErrorContext {
+    /// Fold `other` into this context, resolving conflicts with `policy`
+    ///
+    /// The severity becomes the higher of the two, and unset fields are
+    /// always filled from `other` whatever the policy.
+    pub fn merge(mut self, other: ErrorContext, policy: MergePolicy) -> Self {
+        let overwrite = policy == MergePolicy::Overwrite;
+        let other_overflow = ContextOverflow::of(&other);
+        merge_field(&mut self.public_message, other.public_message, overwrite);
+        merge_field(&mut self.message_key, other.message_key, overwrite);
+        merge_field(&mut self.source_location, other.source_location, overwrite);
+        merge_field(&mut self.recovery_suggestion, other.recovery_suggestion, overwrite);
+        merge_field(&mut self.timestamp, other.timestamp, overwrite);
+        merge_field(&mut self.correlation_id, other.correlation_id, overwrite);
+        merge_field(&mut self.component, other.component, overwrite);
+        merge_field(&mut self.diagnostic_info, other.diagnostic_info, overwrite);
+        if overwrite {
+            self.message = other.message;
+        }
+        self.severity = self.severity.max(other.severity);
+        self.attachments.extend_from(&other.attachments, overwrite);
+
+        for action in other.recovery_actions.iter().cloned() {
+            if !self.recovery_actions.contains(&action) {
+                self.recovery_actions_mut().push(action);
+            }
+        }
+
+        let limits = ContextPolicy::current();
+        for tag in other.tags.iter().cloned() {
+            self.push_tag_limited(tag, &limits);
+        }
+
+        let mut entries: Vec<_> = Arc::unwrap_or_clone(other.metadata).into_iter().collect();
+        entries.sort();
+        for (key, value) in entries {
+            if key == OVERFLOW_METADATA_KEY {
+                continue;
+            }
+            let value = match (self.metadata.get(&key), policy) {
+                (None, _) | (Some(_), MergePolicy::Overwrite) => value,
+                (Some(_), MergePolicy::KeepExisting) => continue,
+                (Some(existing), MergePolicy::Combine) if existing.split("; ").any(|part| part == value) => continue,
+                (Some(existing), MergePolicy::Combine) => format!("{}; {}", existing, value),
+            };
+            self.insert_metadata_limited(key, value, &limits);
+        }
+        if let Some(overflow) = other_overflow {
+            ContextOverflow::record(&mut self, |total| {
+                total.dropped_metadata += overflow.dropped_metadata;
+                total.truncated_values += overflow.truncated_values;
+                total.dropped_tags += overflow.dropped_tags;
+            });
+        }
+        self
+    }
+
+    /// What changed from this context to `updated`
+    pub fn diff(&self, updated: &ErrorContext) -> ContextDiff {
+        let mut diff = ContextDiff::default();
+
+        let fields = [
+            ("message", self.message != updated.message),
+            ("public_message", self.public_message != updated.public_message),
+            ("message_key", self.message_key != updated.message_key),
+            ("severity", self.severity != updated.severity),
+            ("source_location", self.source_location != updated.source_location),
+            ("recovery_suggestion", self.recovery_suggestion != updated.recovery_suggestion),
+            ("recovery_actions", self.recovery_actions != updated.recovery_actions),
+            ("timestamp", self.timestamp != updated.timestamp),
+            ("correlation_id", self.correlation_id != updated.correlation_id),
+            ("component", self.component != updated.component),
+            ("diagnostic_info", self.diagnostic_info != updated.diagnostic_info),
+        ];
+        diff.changed_fields = fields.iter().filter(|(_, changed)| *changed).map(|(name, _)| *name).collect();
+
+        let keys: BTreeSet<_> = self.metadata.keys().chain(updated.metadata.keys()).collect();
+        for key in keys {
+            match (self.metadata.get(key), updated.metadata.get(key)) {
+                (None, Some(after)) => diff.added_metadata.push((key.clone(), after.clone())),
+                (Some(_), None) => diff.removed_metadata.push(key.clone()),
+                (Some(before), Some(after)) if before != after => diff.changed_metadata.push(MetadataChange {
+                    key: key.clone(),
+                    before: before.clone(),
+                    after: after.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        let before: BTreeSet<_> = self.tags.iter().collect();
+        let after: BTreeSet<_> = updated.tags.iter().collect();
+        diff.added_tags = after.difference(&before).map(|tag| tag.to_string()).collect();
+        diff.removed_tags = before.difference(&after).map(|tag| tag.to_string()).collect();
+
+        diff
+    }
+}
+
+fn merge_field<T>(existing: &mut Option<T>, incoming: Option<T>, overwrite: bool) {
+    if incoming.is_some() && (overwrite || existing.is_none()) {
+        *existing = incoming;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::types::{ErrorSeverity, RecoveryAction};
+    use std::time::Duration;
+
+    fn operation() -> ErrorContext {
+        ErrorContext::new("syncing account")
+            .with_component("sync")
+            .with_metadata("account", "42")
+            .with_metadata("region", "eu-west-1")
+            .add_tag("sync")
+    }
+
+    fn attempt() -> ErrorContext {
+        ErrorContext::new("attempt 2")
+            .with_severity(ErrorSeverity::Critical)
+            .with_component("http")
+            .with_correlation_id("req-7")
+            .with_metadata("region", "eu-central-1")
+            .with_metadata("attempt", "2")
+            .add_tag("retry")
+            .with_recovery_action(RecoveryAction::RetryAfter(Duration::from_secs(1)))
+    }
+
+    #[test]
+    fn test_merge_policies() {
+        let kept = operation().merge(attempt(), MergePolicy::KeepExisting);
+        assert_eq!(&*kept.message, "syncing account");
+        assert_eq!(kept.component.as_deref(), Some("sync"));
+        assert_eq!(kept.correlation_id.as_deref(), Some("req-7"));
+        assert_eq!(kept.severity, ErrorSeverity::Critical);
+        assert_eq!(kept.metadata.get("region").map(String::as_str), Some("eu-west-1"));
+        assert_eq!(kept.metadata.get("attempt").map(String::as_str), Some("2"));
+        assert_eq!(*kept.tags, vec!["sync", "retry"]);
+        assert_eq!(kept.recovery_actions.len(), 1);
+
+        let overwritten = operation().merge(attempt(), MergePolicy::Overwrite);
+        assert_eq!(&*overwritten.message, "attempt 2");
+        assert_eq!(overwritten.component.as_deref(), Some("http"));
+        assert_eq!(overwritten.metadata.get("region").map(String::as_str), Some("eu-central-1"));
+
+        let combined = operation().merge(attempt(), MergePolicy::Combine).merge(attempt(), MergePolicy::Combine);
+        assert_eq!(combined.metadata.get("region").map(String::as_str), Some("eu-west-1; eu-central-1"));
+        assert_eq!(combined.recovery_actions.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_sums_overflow() {
+        let limits = ContextPolicy::DEFAULT.with_max_tags(0);
+        let mut first = ErrorContext::new("first");
+        first.push_tag_limited("a".to_string(), &limits);
+        let mut second = ErrorContext::new("second");
+        second.push_tag_limited("b".to_string(), &limits);
+
+        let merged = first.merge(second, MergePolicy::KeepExisting);
+        assert_eq!(ContextOverflow::of(&merged).map(|overflow| overflow.dropped_tags), Some(2));
+    }
+
+    #[test]
+    fn test_diff_reports_added_context() {
+        let before = operation();
+        let mut after = before.clone().merge(attempt(), MergePolicy::Overwrite);
+        after.timestamp = before.timestamp;
+
+        let diff = before.diff(&after);
+        assert_eq!(
+            diff.changed_fields,
+            vec!["message", "severity", "recovery_actions", "correlation_id", "component"]
+        );
+        assert_eq!(diff.added_metadata, vec![("attempt".to_string(), "2".to_string())]);
+        assert_eq!(
+            diff.changed_metadata,
+            vec![MetadataChange {
+                key: "region".to_string(),
+                before: "eu-west-1".to_string(),
+                after: "eu-central-1".to_string(),
+            }]
+        );
+        assert_eq!(diff.added_tags, vec!["retry"]);
+        assert!(diff.removed_metadata.is_empty() && diff.removed_tags.is_empty());
+        assert!(before.diff(&before).is_empty());
+    }
+}
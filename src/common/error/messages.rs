@@ -0,0 +1,146 @@
+/* src/common/error/messages.rs */
+#![warn(missing_docs)]
+//! **Brief:** Message catalogs resolving localized user-facing error messages by key.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Context]
+//!  - [User-Facing Messages]
+//!  - [Localization]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! An `ErrorContext` carries two audiences' text: `message` is the English
+//! developer message that ends up in logs, while `public_message` and
+//! `message_key` describe what an end user may see. Frontends resolve the key
+//! through a `MessageCatalog` in the user's locale, substituting the key's
+//! `{name}` arguments, and fall back to the public message when the catalog
+//! has no translation. The developer message is never shown to end users.
+
+use super::labels::normalize_locale;
+use super::types::{ErrorContext, MessageKey};
+use super::AklypseError;
+use std::collections::HashMap;
+
+/// Source of translated message templates
+pub trait MessageCatalog: Send + Sync {
+    /// Template of `key` in `locale`, with `{name}` placeholders for the arguments
+    fn template(&self, locale: &str, key: &str) -> Option<String>;
+
+    /// Translated text of `key` in `locale` with its arguments substituted
+    fn render(&self, locale: &str, key: &MessageKey) -> Option<String> {
+        self.template(locale, &key.key).map(|template| substitute(&template, &key.args))
+    }
+}
+
+/// `MessageCatalog` backed by in-memory tables, falling back from `pt-BR` to `pt`
+#[derive(Debug, Clone, Default)]
+pub struct StaticMessageCatalog {
+    templates: HashMap<String, HashMap<String, String>>,
+}
+
+impl StaticMessageCatalog {
+    /// Create an empty catalog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the template of `key` in `locale`
+    pub fn with_message(mut self, locale: &str, key: impl Into<String>, template: impl Into<String>) -> Self {
+        self.templates
+            .entry(normalize_locale(locale))
+            .or_default()
+            .insert(key.into(), template.into());
+        self
+    }
+}
+
+impl MessageCatalog for StaticMessageCatalog {
+    fn template(&self, locale: &str, key: &str) -> Option<String> {
+        let locale = normalize_locale(locale);
+        let language = locale.split('-').next().unwrap_or_default();
+
+        [locale.as_str(), language]
+            .iter()
+            .find_map(|candidate| self.templates.get(*candidate).and_then(|table| table.get(key)))
+            .cloned()
+    }
+}
+
+impl ErrorContext {
+    /// Text to show an end user in `locale`: the translated message key, else the public message
+    pub fn user_message(&self, catalog: &dyn MessageCatalog, locale: &str) -> Option<String> {
+        self.message_key
+            .as_ref()
+            .and_then(|key| catalog.render(locale, key))
+            .or_else(|| self.public_message.as_deref().map(str::to_string))
+    }
+}
+
+impl AklypseError {
+    /// User-facing text of the outermost rich context that has one
+    pub fn user_message(&self, catalog: &dyn MessageCatalog, locale: &str) -> Option<String> {
+        let mut current = self;
+        loop {
+            match current {
+                AklypseError::WithRichContext { context, source, .. } => {
+                    if let Some(message) = context.user_message(catalog, locale) {
+                        return Some(message);
+                    }
+                    current = source.as_ref();
+                }
+                _ => return None,
+            }
+        }
+    }
+}
+
+// Replace every `{name}` with its argument, leaving unknown placeholders as they are
+fn substitute(template: &str, args: &[(String, String)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::InternalSnafu;
+
+    fn catalog() -> StaticMessageCatalog {
+        StaticMessageCatalog::new()
+            .with_message("en", "payment.declined", "Your card ending in {last4} was declined.")
+            .with_message("de", "payment.declined", "Ihre Karte mit Endziffern {last4} wurde abgelehnt.")
+    }
+
+    #[test]
+    fn test_user_message_resolves_key_then_public_message() {
+        let context = ErrorContext::new("gateway returned 402 for charge ch_123")
+            .with_public_message("Your payment could not be processed.")
+            .with_message_key(MessageKey::new("payment.declined").with_arg("last4", 4242));
+
+        assert_eq!(
+            context.user_message(&catalog(), "de-AT").as_deref(),
+            Some("Ihre Karte mit Endziffern 4242 wurde abgelehnt.")
+        );
+        assert_eq!(
+            context.user_message(&catalog(), "ja").as_deref(),
+            Some("Your payment could not be processed.")
+        );
+        assert_eq!(ErrorContext::new("internal").user_message(&catalog(), "en"), None);
+    }
+
+    #[test]
+    fn test_error_user_message_skips_developer_contexts() {
+        let error = InternalSnafu { message: "null pointer in ledger".to_string(), source: None }
+            .build()
+            .add_context(ErrorContext::new("charging card").with_message_key(MessageKey::new("payment.declined")))
+            .add_context(ErrorContext::new("handling checkout"));
+
+        assert_eq!(
+            error.user_message(&catalog(), "en").as_deref(),
+            Some("Your card ending in {last4} was declined.")
+        );
+    }
+}
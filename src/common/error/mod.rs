@@ -12,27 +12,121 @@
 // **Author:** Lord Xyn
 // **License:** MIT
 
+pub mod attachments;
+#[cfg(feature = "tokio")]
+pub mod async_observer;
+pub mod breaker_group;
+pub mod bulkhead;
+#[cfg(feature = "tokio")]
+pub mod bus;
+pub mod channel;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod circuitbreaker;
+pub mod context;
 pub mod decrust;
+pub mod diagnostics;
+#[cfg(feature = "metrics")]
+pub mod exporter;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "tokio")]
+pub mod hedger;
+pub mod health;
+pub mod labels;
+pub mod limits;
+pub mod merge;
+pub mod messages;
+pub mod outlier;
+pub mod panic_hook;
+pub mod pipeline;
+#[cfg(feature = "tokio")]
+pub mod protected_task;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+pub mod ratelimiter;
+pub mod registry;
+pub mod report;
 pub mod reporter;
+pub mod retrier;
+pub mod schema;
+pub mod severity;
+pub mod stream;
+pub mod summary;
+pub mod theme;
+#[cfg(feature = "tower")]
+pub mod tower_layer;
+pub mod tracing_sink;
 pub mod types;
 
-use snafu::{self, prelude::*, Backtrace, ErrorCompat, Snafu};
+use snafu::Snafu;
 use std::sync::Arc;
 use std::path::PathBuf;
 use std::time::Duration;
-use std::fmt;
 
 // Re-export key types from submodules
 pub use self::types::{
     ErrorContext, ErrorSource, ErrorSeverity, ErrorCategory, DiagnosticResult,
-    Autocorrection, FixType, FixDetails,
+    Autocorrection, FixType, FixDetails, RecoveryAction, MessageKey, ErrorReportFormat,
 };
-pub use self::reporter::{ErrorReporter, ErrorReportConfig, ErrorReportFormat};
+#[cfg(feature = "tokio")]
+pub use self::async_observer::{
+    AsyncCircuitBreakerObserver, AsyncObserverDispatcher, AsyncObserverStats, CircuitEvent, ObserverFuture,
+    DEFAULT_ASYNC_OBSERVER_CAPACITY,
+};
+pub use self::attachments::{debug_attachment_fields, Attachments};
+pub use self::breaker_group::{BreakerGroup, GroupWarning, Propagation};
+pub use self::bulkhead::{
+    Bulkhead, BulkheadConfig, BulkheadMetrics, BulkheadObserver, BulkheadPermit, BulkheadRejection,
+};
+#[cfg(feature = "tokio")]
+pub use self::bus::{BusEvent, BusFilter, BusSubscription, ErrorBus, DEFAULT_BUS_CAPACITY};
+#[cfg(feature = "chaos")]
+pub use self::chaos::{ChaosConfig, CHAOS_METADATA_KEY};
+pub use self::channel::{ChannelSink, ChannelSinkStats, OverflowPolicy};
+#[cfg(feature = "metrics")]
+pub use self::exporter::ResilienceMetricsExporter;
+pub use self::health::{ComponentHealth, ComponentKind, HealthRegistry, HealthReport, HealthStatus};
+#[cfg(feature = "tokio")]
+pub use self::hedger::{Hedger, HedgerConfig, HedgerMetrics, HEDGER_METADATA_KEY};
+pub use self::labels::{LabelCatalog, ReportLabel};
+pub use self::limits::{ContextOverflow, ContextPolicy, Scrubber};
+pub use self::merge::{ContextDiff, MergePolicy, MetadataChange};
+pub use self::messages::{MessageCatalog, StaticMessageCatalog};
+pub use self::ratelimiter::{
+    RateLimitStrategy, RateLimiter, RateLimiterConfig, RateLimiterMetrics, RateLimiterObserver,
+};
+pub use self::registry::{ResilienceRegistry, SHUTDOWN_PENDING_METADATA_KEY};
+pub use self::report::{
+    ErrorReport, BacktraceFrame, FieldExtractor, ReportId, ReportStore, io_error_fields, REPORT_ID_METADATA_KEY,
+};
+pub use self::reporter::{
+    ErrorReporter, ErrorReportConfig, ErrorReporterBuilder, ReportSink,
+    SinkRegistration, SinkFailurePolicy, WriterSink, FileSink, ReportStyle, WrapMode,
+    TimestampFormat, TimestampZone, Compression,
+};
+pub use self::panic_hook::{install_panic_hook, panic_error};
+pub use self::outlier::{OutlierDetection, OUTLIER_KEY_METADATA_KEY};
+#[cfg(feature = "tokio")]
+pub use self::protected_task::{ProtectedTask, PROTECTED_TASK_METADATA_KEY};
+pub use self::pipeline::{Fallback, PipelineMetrics, ResiliencePipeline};
+pub use self::retrier::{Retrier, RetrierMetrics, RetryPolicy, RETRIER_METADATA_KEY, TRANSIENT_CATEGORIES};
+pub use self::schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_KEY};
+pub use self::severity::{LevelMapping, SeverityPolicy};
+pub use self::stream::{StreamOptions, StreamSummary};
+pub use self::summary::{ErrorSummary, FingerprintCount};
+pub use self::theme::{Color, GlyphSet, Theme};
+pub use self::tracing_sink::{TracingSink, TRACING_TARGET};
+#[cfg(feature = "tower")]
+pub use self::tower_layer::{AklypseErrorLayer, AklypseErrorService};
 pub use self::circuitbreaker::{
-    CircuitBreaker, CircuitBreakerConfig, CircuitState, CircuitBreakerObserver
+    BatchFailurePolicy, CallOptions, CallPermit, CircuitBreaker, CircuitBreakerConfig, CircuitHistoryEntry, CircuitState, ErrorPredicate,
+    CircuitBreakerObserver, LatencySnapshot, ObserverId, BATCH_METADATA_KEY, FALLBACKS_FAILED_METADATA_KEY,
 };
+#[cfg(feature = "tokio")]
+pub use self::circuitbreaker::AsyncFallback;
 pub use self::decrust::{Decrust, AutocorrectableError};
+pub use self::decrust::applier::{AppliedFix, FixApplier};
 
 /// A Result type specialized for AklypseError
 pub type Result<T, E = AklypseError> = std::result::Result<T, E>;
@@ -43,130 +137,265 @@ pub type Result<T, E = AklypseError> = std::result::Result<T, E>;
 pub enum AklypseError {
     /// I/O related errors
     Io {
+        /// The underlying I/O error
+        #[snafu(source(false))]
         source: Arc<std::io::Error>,
+        /// Path being operated on
         path: Option<PathBuf>,
+        /// What was being done, e.g. "read"
         operation: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Parsing errors (JSON, YAML, etc.)
     Parse {
+        /// The underlying parser error
+        #[snafu(source(false))]
         source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        /// Format being parsed, e.g. "json"
         kind: String,
+        /// What was being parsed
         context_info: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Network related errors
     Network {
+        /// The underlying transport error
+        #[snafu(source(false))]
         source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        /// Address being contacted
         url: Option<String>,
+        /// Kind of failure, e.g. "connect"
         kind: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Configuration related errors
     Config {
+        /// What is wrong with the configuration
         message: String,
+        /// File the configuration came from
         path: Option<PathBuf>,
+        /// Error that made it invalid
+        #[snafu(source(false))]
         source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Validation errors
     Validation {
+        /// Field that failed validation
         field: String,
+        /// Why it failed
         message: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Internal errors
     Internal {
+        /// What went wrong
         message: String,
+        /// Error that caused it
+        #[snafu(source(false))]
         source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Circuit breaker is open
     CircuitBreakerOpen {
+        /// Name of the breaker
         name: String,
+        /// How long until the breaker lets a probe through
         retry_after: Option<Duration>,
+        /// Stack at the point the error was created
+        backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
+    },
+    
+    /// Rate limit exceeded
+    RateLimited {
+        /// Name of the limiter
+        name: String,
+        /// How long until a permit is available
+        retry_after: Duration,
+        /// Stack at the point the error was created
+        backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
+    },
+    
+    /// Component is shutting down
+    ShuttingDown {
+        /// Component that is shutting down
+        component: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Operation timed out
     Timeout {
+        /// Operation that timed out
         operation: String,
+        /// Time it was allowed
         duration: Duration,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Resource exhaustion
     ResourceExhausted {
+        /// Resource that ran out
         resource: String,
+        /// Its limit
         limit: String,
+        /// Usage when the limit was hit
         current: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Resource not found
     NotFound {
+        /// Kind of resource looked up
         resource_type: String,
+        /// Identifier it was looked up by
         identifier: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// State conflict
     StateConflict {
+        /// Why the operation conflicts with the current state
         message: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Concurrency related errors
     Concurrency {
+        /// What went wrong
         message: String,
+        /// Error that caused it
+        #[snafu(source(false))]
         source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// External service errors
     ExternalService {
+        /// Service that failed
         service_name: String,
+        /// What it reported
         message: String,
+        /// Error returned by its client
+        #[snafu(source(false))]
         source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Missing value errors
     MissingValue {
+        /// The value that was missing
         item_description: String,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Multiple errors
     MultipleErrors {
+        /// The collected errors
         errors: Vec<AklypseError>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
     
     /// Error with rich context
     WithRichContext {
+        /// Context added to the error
         context: types::ErrorContext,
+        /// The error the context was added to
+        #[snafu(source(false))]
         source: Box<AklypseError>,
+        /// Stack at the point the error was created
         backtrace: snafu::Backtrace,
     },
     
     /// General purpose error wrapper
     Whatever {
+        /// What went wrong
         message: String,
+        /// Error that caused it
+        #[snafu(source(false))]
         source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+        /// Stack at the point the error was created, when captured
         backtrace: Option<snafu::Backtrace>,
+        /// Context of the `context::scope` the error was created in
+        #[snafu(implicit)]
+        scope: context::ScopeCapture,
     },
 }
 
 impl Clone for AklypseError {
     fn clone(&self) -> Self {
-        match self {
+        let mut clone = match self {
             Self::Io { source, path, operation, .. } => {
                 // Properly preserve the original error kind and message when cloning
                 let source_clone = Arc::new(std::io::Error::new(
@@ -193,10 +422,7 @@ impl Clone for AklypseError {
             Self::Network { source, url, kind, .. } => {
                 let source_message = format!("{}", source);
                 NetworkSnafu {
-                    source: Box::new(std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        source_message,
-                    )),
+                    source: Box::new(std::io::Error::other(source_message)),
                     url: url.clone(),
                     kind: kind.clone(),
                 }.build()
@@ -204,7 +430,7 @@ impl Clone for AklypseError {
             Self::Config { message, path, source, .. } => {
                 let cloned_source = source.as_ref().map(|s| {
                     let msg = format!("{}", s);
-                    Box::new(std::io::Error::new(std::io::ErrorKind::Other, msg)) as Box<dyn std::error::Error + Send + Sync>
+                    Box::new(std::io::Error::other(msg)) as Box<dyn std::error::Error + Send + Sync>
                 });
                 ConfigSnafu {
                     message: message.clone(),
@@ -221,7 +447,7 @@ impl Clone for AklypseError {
             Self::Internal { message, source, .. } => {
                 let cloned_source = source.as_ref().map(|s| {
                     let msg = format!("{}", s);
-                    Box::new(std::io::Error::new(std::io::ErrorKind::Other, msg)) as Box<dyn std::error::Error + Send + Sync>
+                    Box::new(std::io::Error::other(msg)) as Box<dyn std::error::Error + Send + Sync>
                 });
                 InternalSnafu {
                     message: message.clone(),
@@ -234,6 +460,17 @@ impl Clone for AklypseError {
                     retry_after: *retry_after,
                 }.build()
             },
+            Self::RateLimited { name, retry_after, .. } => {
+                RateLimitedSnafu {
+                    name: name.clone(),
+                    retry_after: *retry_after,
+                }.build()
+            },
+            Self::ShuttingDown { component, .. } => {
+                ShuttingDownSnafu {
+                    component: component.clone(),
+                }.build()
+            },
             Self::Timeout { operation, duration, .. } => {
                 TimeoutSnafu {
                     operation: operation.clone(),
@@ -261,7 +498,7 @@ impl Clone for AklypseError {
             Self::Concurrency { message, source, .. } => {
                 let cloned_source = source.as_ref().map(|s| {
                     let msg = format!("{}", s);
-                    Box::new(std::io::Error::new(std::io::ErrorKind::Other, msg)) as Box<dyn std::error::Error + Send + Sync>
+                    Box::new(std::io::Error::other(msg)) as Box<dyn std::error::Error + Send + Sync>
                 });
                 ConcurrencySnafu {
                     message: message.clone(),
@@ -271,7 +508,7 @@ impl Clone for AklypseError {
             Self::ExternalService { service_name, message, source, .. } => {
                 let cloned_source = source.as_ref().map(|s| {
                     let msg = format!("{}", s);
-                    Box::new(std::io::Error::new(std::io::ErrorKind::Other, msg)) as Box<dyn std::error::Error + Send + Sync>
+                    Box::new(std::io::Error::other(msg)) as Box<dyn std::error::Error + Send + Sync>
                 });
                 ExternalServiceSnafu {
                     service_name: service_name.clone(),
@@ -292,27 +529,42 @@ impl Clone for AklypseError {
             Self::WithRichContext { context, source, .. } => {
                 WithRichContextSnafu {
                     context: context.clone(),
-                    source: Box::new(source.clone()),
+                    source: source.clone(),
                 }.build()
             },
-            Self::Whatever { message, source, backtrace, .. } => {
+            Self::Whatever { message, source, .. } => {
                 let cloned_source = source.as_ref().map(|s| {
                     let msg = format!("{}", s);
-                    Box::new(std::io::Error::new(std::io::ErrorKind::Other, msg)) as Box<dyn std::error::Error + Send + Sync>
+                    Box::new(std::io::Error::other(msg)) as Box<dyn std::error::Error + Send + Sync>
                 });
                 WhateverSnafu {
                     message: message.clone(),
                     source: cloned_source,
-                    backtrace: backtrace.clone(),
                 }.build()
             },
+        };
+        // Keep the scope of the original rather than the one the clone is made in
+        if let (Some(scope), Some(captured)) = (clone.scope_capture_mut(), self.scope_capture()) {
+            *scope = captured.clone();
         }
+        clone
     }
 }
 
 impl AklypseError {
     /// Add rich context to an error
-    pub fn add_context(self, context: types::ErrorContext) -> Self {
+    ///
+    /// Fields the context leaves unset are inherited from the active
+    /// `context::scope`, if any, the result is cut to the installed
+    /// `ContextPolicy`, and a missing source location is set to the caller's
+    /// location.
+    #[track_caller]
+    pub fn add_context(self, mut context: types::ErrorContext) -> Self {
+        context::apply_current(&mut context);
+        context.enforce_limits(&limits::ContextPolicy::current());
+        if context.source_location.is_none() {
+            context.source_location = Some(types::ErrorSource::caller());
+        }
         WithRichContextSnafu {
             context,
             source: Box::new(self),
@@ -320,11 +572,77 @@ impl AklypseError {
     }
     
     /// Add a simple message context to an error
+    #[track_caller]
     pub fn add_context_msg(self, message: impl Into<String>) -> Self {
         let context = types::ErrorContext::new(message);
         self.add_context(context)
     }
     
+    /// Turn the scope captured when the error was built into a rich context layer
+    ///
+    /// Every error records the `context::scope` active when it is built, and
+    /// `get_rich_context` already falls back to it; this makes it a
+    /// `WithRichContext` layer for code matching on the variants. Errors
+    /// built outside of a scope, or already carrying rich context, are
+    /// returned unchanged.
+    #[track_caller]
+    pub fn with_scope_context(mut self) -> Self {
+        let captured = self.scope_capture_mut().and_then(|scope| scope.take());
+        match captured {
+            Some(scope) => self.add_context(scope),
+            None => self,
+        }
+    }
+
+    // Scope captured by the variant; `WithRichContext` inherits it into its context instead
+    fn scope_capture(&self) -> Option<&context::ScopeCapture> {
+        match self {
+            Self::Io { scope, .. }
+            | Self::Parse { scope, .. }
+            | Self::Network { scope, .. }
+            | Self::Config { scope, .. }
+            | Self::Validation { scope, .. }
+            | Self::Internal { scope, .. }
+            | Self::CircuitBreakerOpen { scope, .. }
+            | Self::RateLimited { scope, .. }
+            | Self::ShuttingDown { scope, .. }
+            | Self::Timeout { scope, .. }
+            | Self::ResourceExhausted { scope, .. }
+            | Self::NotFound { scope, .. }
+            | Self::StateConflict { scope, .. }
+            | Self::Concurrency { scope, .. }
+            | Self::ExternalService { scope, .. }
+            | Self::MissingValue { scope, .. }
+            | Self::MultipleErrors { scope, .. }
+            | Self::Whatever { scope, .. } => Some(scope),
+            Self::WithRichContext { .. } => None,
+        }
+    }
+
+    fn scope_capture_mut(&mut self) -> Option<&mut context::ScopeCapture> {
+        match self {
+            Self::Io { scope, .. }
+            | Self::Parse { scope, .. }
+            | Self::Network { scope, .. }
+            | Self::Config { scope, .. }
+            | Self::Validation { scope, .. }
+            | Self::Internal { scope, .. }
+            | Self::CircuitBreakerOpen { scope, .. }
+            | Self::RateLimited { scope, .. }
+            | Self::ShuttingDown { scope, .. }
+            | Self::Timeout { scope, .. }
+            | Self::ResourceExhausted { scope, .. }
+            | Self::NotFound { scope, .. }
+            | Self::StateConflict { scope, .. }
+            | Self::Concurrency { scope, .. }
+            | Self::ExternalService { scope, .. }
+            | Self::MissingValue { scope, .. }
+            | Self::MultipleErrors { scope, .. }
+            | Self::Whatever { scope, .. } => Some(scope),
+            Self::WithRichContext { .. } => None,
+        }
+    }
+
     /// Get the error category
     pub fn category(&self) -> types::ErrorCategory {
         match self {
@@ -335,6 +653,8 @@ impl AklypseError {
             AklypseError::Validation { .. } => types::ErrorCategory::Validation,
             AklypseError::Internal { .. } => types::ErrorCategory::Internal,
             AklypseError::CircuitBreakerOpen { .. } => types::ErrorCategory::CircuitBreaker,
+            AklypseError::RateLimited { .. } => types::ErrorCategory::ResourceExhaustion,
+            AklypseError::ShuttingDown { .. } => types::ErrorCategory::StateConflict,
             AklypseError::Timeout { .. } => types::ErrorCategory::Timeout,
             AklypseError::ResourceExhausted { .. } => types::ErrorCategory::ResourceExhaustion,
             AklypseError::NotFound { .. } => types::ErrorCategory::NotFound,
@@ -349,26 +669,82 @@ impl AklypseError {
     }
     
     /// Get the error severity
+    ///
+    /// The rich context's severity, or `ErrorSeverity::Error` without one, as
+    /// remapped by the installed `SeverityPolicy`.
     pub fn severity(&self) -> types::ErrorSeverity {
-        if let AklypseError::WithRichContext { context, .. } = self {
-            context.severity
-        } else {
-            // Default severity for errors without explicit context
-            types::ErrorSeverity::Error
-        }
+        let explicit = self.get_rich_context().map(|context| context.severity);
+        severity::resolve_installed(self.category(), explicit)
     }
     
     /// Get the rich context if available
+    ///
+    /// Errors without a `WithRichContext` layer that were built inside a
+    /// `context::scope` return the scope's context.
     pub fn get_rich_context(&self) -> Option<&types::ErrorContext> {
         match self {
             AklypseError::WithRichContext { context, .. } => Some(context),
+            _ => self.scope_capture().and_then(context::ScopeCapture::context),
+        }
+    }
+
+    /// The error this one wraps, if any
+    ///
+    /// `source` fields are plain data to the selectors, so `Error::source`
+    /// does not see them; chain walkers go through this instead.
+    pub fn cause(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AklypseError::Io { source, .. } => Some(source.as_ref()),
+            AklypseError::Parse { source, .. } | AklypseError::Network { source, .. } => Some(source.as_ref()),
+            AklypseError::WithRichContext { source, .. } => Some(source.as_ref()),
+            AklypseError::Config { source, .. }
+            | AklypseError::Internal { source, .. }
+            | AklypseError::Concurrency { source, .. }
+            | AklypseError::ExternalService { source, .. }
+            | AklypseError::Whatever { source, .. } => {
+                source.as_deref().map(|source| source as &(dyn std::error::Error + 'static))
+            }
             _ => None,
         }
     }
+
+    /// Next link of an error chain, seeing through `AklypseError` sources
+    pub fn source_of<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a (dyn std::error::Error + 'static)> {
+        match error.downcast_ref::<AklypseError>() {
+            Some(aklypse_error) => aklypse_error.cause(),
+            None => error.source(),
+        }
+    }
+
+    /// Link an emitted report back into the error's context metadata
+    ///
+    /// The id is stored under `REPORT_ID_METADATA_KEY`, so a reference code shown
+    /// to a user can be traced to the full report. Errors without rich context
+    /// are wrapped in one first.
+    #[track_caller]
+    pub fn with_report_id(self, report_id: &report::ReportId) -> Self {
+        match self {
+            AklypseError::WithRichContext { mut context, source, .. } => {
+                context.metadata_mut().insert(report::REPORT_ID_METADATA_KEY.to_string(), report_id.to_string());
+                WithRichContextSnafu { context, source }.build()
+            }
+            other => other.add_context(
+                types::ErrorContext::new("Error report emitted")
+                    .with_metadata(report::REPORT_ID_METADATA_KEY, report_id.to_string()),
+            ),
+        }
+    }
+
+    /// Get the report id previously linked with `with_report_id`, if any
+    pub fn report_id(&self) -> Option<report::ReportId> {
+        self.get_rich_context()
+            .and_then(|context| context.metadata.get(report::REPORT_ID_METADATA_KEY))
+            .and_then(|id| report::ReportId::parse(id))
+    }
 }
 
 /// Extension trait for Result to add context to an error
-pub trait ResultExt<T, E_Orig> {
+pub trait ResultExt<T, EOrig> {
     /// Add a simple message context to an error
     fn context_msg(self, message: impl Into<String>) -> Result<T, AklypseError>;
     
@@ -380,6 +756,7 @@ impl<T, E> ResultExt<T, E> for std::result::Result<T, E>
 where
     E: Into<AklypseError>,
 {
+    #[track_caller]
     fn context_msg(self, message: impl Into<String>) -> Result<T, AklypseError> {
         match self {
             Ok(value) => Ok(value),
@@ -390,6 +767,7 @@ where
         }
     }
     
+    #[track_caller]
     fn context_rich(self, context: types::ErrorContext) -> Result<T, AklypseError> {
         match self {
             Ok(value) => Ok(value),
@@ -454,7 +832,7 @@ mod tests {
         let err_with_context = err.add_context_msg("Additional context");
         
         if let AklypseError::WithRichContext { context, source, .. } = &err_with_context {
-            assert_eq!(context.message, "Additional context");
+            assert_eq!(&*context.message, "Additional context");
             if let AklypseError::Internal { message, .. } = &**source {
                 assert_eq!(message, "Test error");
             } else {
@@ -492,6 +870,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_context_records_caller_location() {
+        let result: Result<(), AklypseError> = Err(InternalSnafu { message: "boom".to_string(), source: None }.build());
+        let (err, line) = (result.context_msg("loading"), line!());
+
+        let location = err.unwrap_err().get_rich_context().unwrap().source_location.clone().unwrap();
+        assert_eq!(location.file, file!());
+        assert_eq!(location.line, line);
+
+        let explicit = ErrorSource::new("lib.rs", 7, "app");
+        let err = InternalSnafu { message: "boom".to_string(), source: None }
+            .build()
+            .add_context(ErrorContext::new("ctx").with_source_location(explicit.clone()));
+        assert_eq!(err.get_rich_context().unwrap().source_location, Some(explicit));
+    }
+
     #[test]
     fn test_option_ext() {
         // Test with Some value
@@ -512,6 +906,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_report_id_linking() {
+        let report_id = report::ReportId::generate();
+        let err = NotFoundSnafu {
+            resource_type: "user".to_string(),
+            identifier: "42".to_string(),
+        }.build();
+
+        let linked = err.with_report_id(&report_id);
+        assert_eq!(linked.report_id(), Some(report_id.clone()));
+        assert_eq!(linked.category(), ErrorCategory::NotFound);
+
+        // Linking into an existing rich context keeps that context
+        let with_context = InternalSnafu {
+            message: "boom".to_string(),
+            source: None,
+        }.build().add_context_msg("Processing order");
+        let linked = with_context.with_report_id(&report_id);
+        assert_eq!(linked.get_rich_context().map(|c| &*c.message), Some("Processing order"));
+        assert_eq!(linked.report_id(), Some(report_id));
+    }
+
     #[test]
     fn test_multiple_errors() {
         // Create multiple errors
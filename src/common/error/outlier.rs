@@ -0,0 +1,210 @@
+/* src/common/error/outlier.rs */
+#![warn(missing_docs)]
+//! **Brief:** Per-key outlier detection ejecting failing endpoints behind one circuit breaker.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Circuit Breaker Pattern]
+//!  - [Outlier Detection]
+//!  - [Load Balancing]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A breaker in front of several endpoints of one dependency, such as the
+//! hosts of a service, should not open for all of them because one host is
+//! down. `CircuitBreaker::execute_keyed` tracks the outcomes of each key
+//! separately: after `consecutive_failures` counted failures in a row the key
+//! is ejected, and its calls are rejected with `CircuitBreakerOpen` while the
+//! other keys keep going through the breaker as usual.
+//!
+//! An ejection lasts `base_ejection_time` times the number of times the key
+//! was ejected, capped at `max_ejection_time`. At most `max_ejection_percent`
+//! of the known keys are ejected at once, but always at least one, so a
+//! failure of every endpoint still reaches the breaker's own thresholds.
+//! Keys are remembered until `CircuitBreaker::forget_key`, so they should
+//! come from a bounded set.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the key whose calls were rejected while ejected
+pub const OUTLIER_KEY_METADATA_KEY: &str = "outlier.key";
+
+/// When `CircuitBreaker::execute_keyed` ejects a key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierDetection {
+    /// Counted failures in a row that eject a key
+    pub consecutive_failures: usize,
+    /// Ejection of a key ejected for the first time; grows with each ejection
+    pub base_ejection_time: Duration,
+    /// Upper bound of the grown ejection time
+    pub max_ejection_time: Duration,
+    /// Share (0.0 to 1.0) of the known keys that may be ejected at once
+    pub max_ejection_percent: f64,
+}
+
+impl Default for OutlierDetection {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: 5,
+            base_ejection_time: Duration::from_secs(30),
+            max_ejection_time: Duration::from_secs(300),
+            max_ejection_percent: 0.5,
+        }
+    }
+}
+
+impl OutlierDetection {
+    /// Eject keys after five failures in a row, for 30s growing up to 5min, at most half of them
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the counted failures in a row that eject a key (at least 1)
+    pub fn with_consecutive_failures(mut self, failures: usize) -> Self {
+        self.consecutive_failures = failures.max(1);
+        self
+    }
+
+    /// Set the first ejection time and its upper bound
+    pub fn with_ejection_time(mut self, base: Duration, max: Duration) -> Self {
+        self.base_ejection_time = base;
+        self.max_ejection_time = max.max(base);
+        self
+    }
+
+    /// Set the share of the known keys that may be ejected at once
+    pub fn with_max_ejection_percent(mut self, percent: f64) -> Self {
+        self.max_ejection_percent = percent.clamp(0.0, 1.0);
+        self
+    }
+}
+
+// Outcomes of one key
+#[derive(Debug, Default)]
+struct KeyStats {
+    consecutive_failures: usize,
+    ejections: u32,
+    ejected_until: Option<Instant>,
+}
+
+impl KeyStats {
+    fn is_ejected(&self, now: Instant) -> bool {
+        self.ejected_until.is_some_and(|until| now < until)
+    }
+}
+
+// Per-key outcomes of a breaker's keyed calls
+#[derive(Debug, Default)]
+pub(crate) struct OutlierTracker {
+    keys: HashMap<String, KeyStats>,
+}
+
+impl OutlierTracker {
+    // Time left of the ejection of `key`, if it is ejected
+    pub(crate) fn ejection_remaining(&self, key: &str, now: Instant) -> Option<Duration> {
+        let stats = self.keys.get(key)?;
+        stats.ejected_until.filter(|_| stats.is_ejected(now)).map(|until| until - now)
+    }
+
+    // Record an outcome of `key`; returns the ejection time if it ejected the key
+    pub(crate) fn record(&mut self, key: &str, failed: bool, now: Instant, config: &OutlierDetection) -> Option<Duration> {
+        let known = self.keys.len() + usize::from(!self.keys.contains_key(key));
+        let ejected = self.keys.values().filter(|stats| stats.is_ejected(now)).count();
+        let stats = self.keys.entry(key.to_string()).or_default();
+        if !failed {
+            stats.consecutive_failures = 0;
+            return None;
+        }
+        stats.consecutive_failures += 1;
+        let allowed = ((known as f64 * config.max_ejection_percent) as usize).max(1);
+        if stats.consecutive_failures < config.consecutive_failures || stats.is_ejected(now) || ejected >= allowed {
+            return None;
+        }
+        stats.consecutive_failures = 0;
+        stats.ejections = stats.ejections.saturating_add(1);
+        let ejection = config.base_ejection_time.saturating_mul(stats.ejections).min(config.max_ejection_time);
+        stats.ejected_until = Some(now + ejection);
+        Some(ejection)
+    }
+
+    // Keys ejected at `now`
+    pub(crate) fn ejected(&self, now: Instant) -> Vec<String> {
+        let mut keys: Vec<String> = self.keys.iter().filter(|(_, stats)| stats.is_ejected(now)).map(|(key, _)| key.clone()).collect();
+        keys.sort();
+        keys
+    }
+
+    pub(crate) fn forget(&mut self, key: &str) -> bool {
+        self.keys.remove(key).is_some()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.keys.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{AklypseError, CircuitBreaker, CircuitBreakerConfig, CircuitState, StateConflictSnafu};
+
+    fn failing() -> crate::common::error::Result<&'static str> {
+        StateConflictSnafu { message: "connection refused".to_string() }.fail()
+    }
+
+    #[test]
+    fn test_failing_key_is_ejected_alone() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 100,
+            operation_timeout: None,
+            outlier_detection: OutlierDetection::new().with_consecutive_failures(2),
+            ..Default::default()
+        };
+        let cb = CircuitBreaker::new("hosts", config);
+        assert_eq!(cb.execute_keyed("10.0.0.1", || Ok("a")).unwrap(), "a");
+        for _ in 0..2 {
+            assert!(cb.execute_keyed("10.0.0.2", failing).is_err());
+        }
+
+        match cb.execute_keyed("10.0.0.2", || Ok("b")).unwrap_err() {
+            AklypseError::WithRichContext { context, source, .. } => {
+                assert_eq!(context.metadata.get(OUTLIER_KEY_METADATA_KEY).map(String::as_str), Some("10.0.0.2"));
+                assert!(matches!(*source, AklypseError::CircuitBreakerOpen { retry_after: Some(_), .. }));
+            }
+            other => panic!("expected an ejected key, got {:?}", other),
+        }
+        assert_eq!(cb.ejected_keys(), vec!["10.0.0.2".to_string()]);
+        assert_eq!(cb.execute_keyed("10.0.0.1", || Ok("a")).unwrap(), "a");
+        assert_eq!(cb.state(), CircuitState::Closed);
+
+        assert!(cb.forget_key("10.0.0.2"));
+        assert_eq!(cb.execute_keyed("10.0.0.2", || Ok("b")).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_ejections_are_capped_and_grow() {
+        let config = OutlierDetection::new()
+            .with_consecutive_failures(1)
+            .with_ejection_time(Duration::from_secs(10), Duration::from_secs(25))
+            .with_max_ejection_percent(0.5);
+        let mut tracker = OutlierTracker::default();
+        let now = Instant::now();
+        for key in ["a", "b", "c", "d"] {
+            tracker.record(key, false, now, &config);
+        }
+        assert_eq!(tracker.record("a", true, now, &config), Some(Duration::from_secs(10)));
+        assert_eq!(tracker.record("b", true, now, &config), Some(Duration::from_secs(10)));
+        // Half of the four keys are out already
+        assert_eq!(tracker.record("c", true, now, &config), None);
+        assert_eq!(tracker.ejected(now), vec!["a".to_string(), "b".to_string()]);
+
+        let later = now + Duration::from_secs(11);
+        assert_eq!(tracker.ejection_remaining("a", later), None);
+        assert_eq!(tracker.record("a", true, later, &config), Some(Duration::from_secs(20)));
+        let much_later = later + Duration::from_secs(21);
+        assert_eq!(tracker.record("a", true, much_later, &config), Some(Duration::from_secs(25)));
+    }
+}
@@ -0,0 +1,198 @@
+/* src/common/error/panic_hook.rs */
+#![warn(missing_docs)]
+//! **Brief:** Panic hook turning panics into reported Internal errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Panic Handling]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `install_panic_hook` routes panics through the error framework: each panic
+//! becomes a `Critical` `AklypseError::Internal` whose context carries a
+//! `DiagnosticResult` built from the payload, the panic location and the
+//! backtrace with the panic machinery filtered out. The error is delivered to
+//! the reporter's sinks, which are flushed before the previously installed
+//! hook runs and the thread unwinds or the process aborts.
+//!
+//! A reporter without sinks, or one whose sinks fail, falls back to rendering
+//! the report with the given `ErrorReportConfig`: as a `tracing` event at the
+//! level the installed `LevelMapping` assigns to the panic's severity when a
+//! subscriber is active, else to stderr. A panic raised
+//! while reporting a panic on the same thread skips reporting and goes
+//! straight to the previous hook.
+
+use super::reporter::{ErrorReportConfig, ErrorReporter};
+use super::severity::event_at;
+use super::tracing_sink::TRACING_TARGET;
+use super::types::{DiagnosticResult, ErrorContext, ErrorSeverity};
+use super::{AklypseError, InternalSnafu};
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::panic::PanicHookInfo;
+use std::sync::Arc;
+
+/// Metadata key holding the name of the thread that panicked
+pub const THREAD_METADATA_KEY: &str = "panic.thread";
+
+thread_local! {
+    // Set while this thread is reporting a panic
+    static REPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Report every panic through `reporter` before the previous hook runs
+///
+/// `config` renders the report to stderr when the reporter has no sinks or
+/// delivering to them fails.
+pub fn install_panic_hook(reporter: Arc<ErrorReporter>, config: ErrorReportConfig) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if !REPORTING.with(|reporting| reporting.replace(true)) {
+            report_panic(&reporter, &config, info);
+            REPORTING.with(|reporting| reporting.set(false));
+        }
+        previous(info);
+    }));
+}
+
+/// The `AklypseError::Internal` a panic is reported as
+pub fn panic_error(info: &PanicHookInfo<'_>) -> AklypseError {
+    let diagnostic = DiagnosticResult::from_panic(info, Backtrace::force_capture());
+    let message = diagnostic.original_message.clone().unwrap_or_default();
+    let thread = std::thread::current().name().unwrap_or("<unnamed>").to_string();
+    let context_message = match info.location() {
+        Some(location) => format!("Thread '{}' panicked at {}", thread, location),
+        None => format!("Thread '{}' panicked", thread),
+    };
+
+    let error: AklypseError = InternalSnafu { message: format!("panic: {}", message), source: None }.build();
+    error.add_context(
+        ErrorContext::new(context_message)
+            .with_severity(ErrorSeverity::Critical)
+            .with_metadata(THREAD_METADATA_KEY, thread)
+            .with_diagnostic_info(diagnostic),
+    )
+}
+
+fn report_panic(reporter: &ErrorReporter, config: &ErrorReportConfig, info: &PanicHookInfo<'_>) {
+    let error = panic_error(info);
+    let delivered = reporter.sink_names().next().is_some()
+        && reporter.report_to_sinks(&error).and_then(|_| reporter.flush_sinks()).is_ok();
+    if delivered {
+        return;
+    }
+    let subscribed = tracing::dispatcher::get_default(|dispatch| !dispatch.is::<tracing::subscriber::NoSubscriber>());
+    if subscribed {
+        let mut rendered = Vec::new();
+        let _ = reporter.report(&error, config, &mut rendered);
+        event_at!(TRACING_TARGET, error.severity().as_tracing_level(), "{}", String::from_utf8_lossy(&rendered).trim_end());
+    } else {
+        let _ = reporter.report(&error, config, &mut std::io::stderr().lock());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ChannelSink, ErrorReport, SinkRegistration};
+    use std::sync::{Mutex, MutexGuard};
+    use tracing::Level;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    // Serializes the tests installing the process-wide hook
+    static HOOK: Mutex<()> = Mutex::new(());
+
+    type Hook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+    // Holds `HOOK` and puts back the hook found on entry once dropped, so no
+    // test leaves its reporter answering the panics of later ones
+    struct HookGuard {
+        previous: Option<Hook>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl HookGuard {
+        fn acquire() -> Self {
+            let lock = HOOK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            Self { previous: Some(std::panic::take_hook()), _lock: lock }
+        }
+    }
+
+    impl Drop for HookGuard {
+        fn drop(&mut self) {
+            if let Some(previous) = self.previous.take() {
+                std::panic::set_hook(previous);
+            }
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for Events {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let metadata = event.metadata();
+            self.0.lock().unwrap().push((*metadata.level(), metadata.target().to_string()));
+        }
+    }
+
+    fn thread_of(report: &ErrorReport) -> Option<&str> {
+        report.context.as_ref()?.metadata.get(THREAD_METADATA_KEY).map(String::as_str)
+    }
+
+    #[test]
+    fn test_panics_are_reported_through_sinks() {
+        let _hook = HookGuard::acquire();
+        let (sink, receiver) = ChannelSink::bounded(64);
+        let reporter = ErrorReporter::builder().add_sink(SinkRegistration::new("channel", sink)).build();
+        install_panic_hook(Arc::new(reporter), ErrorReportConfig::default());
+
+        let result = std::thread::Builder::new()
+            .name("panic-hook-test".to_string())
+            .spawn(|| panic!("ledger out of balance"))
+            .unwrap()
+            .join();
+        assert!(result.is_err());
+
+        // The hook is process-wide, so skip reports of panics in other tests
+        let report = receiver
+            .try_iter()
+            .find(|report| thread_of(report) == Some("panic-hook-test"))
+            .expect("panic was reported");
+        let diagnostics = report.diagnostics.expect("panic diagnostics");
+        assert_eq!(diagnostics.original_message.as_deref(), Some("ledger out of balance"));
+        assert!(diagnostics.primary_location.unwrap().file.ends_with("panic_hook.rs"));
+        assert!(diagnostics.stack_frames.iter().all(|frame| !frame.function_context.starts_with("std::")));
+
+        // A panic on a thread already reporting one is left to the previous hook
+        let result = std::thread::Builder::new()
+            .name("panic-hook-nested".to_string())
+            .spawn(|| {
+                REPORTING.with(|reporting| reporting.set(true));
+                panic!("raised while reporting")
+            })
+            .unwrap()
+            .join();
+        assert!(result.is_err());
+        assert!(receiver.try_iter().all(|report| thread_of(&report) != Some("panic-hook-nested")));
+    }
+
+    #[test]
+    fn test_fallback_event_uses_mapped_level() {
+        let _hook = HookGuard::acquire();
+        install_panic_hook(Arc::new(ErrorReporter::builder().build()), ErrorReportConfig::default());
+
+        let events = Events::default();
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(events.clone()), || {
+            assert!(std::panic::catch_unwind(|| panic!("vault sealed")).is_err());
+        });
+
+        let events = events.0.lock().unwrap();
+        let expected = (ErrorSeverity::Critical.as_tracing_level(), TRACING_TARGET.to_string());
+        assert_eq!(events[..], [expected]);
+    }
+}
@@ -0,0 +1,412 @@
+/* src/common/error/pipeline.rs */
+#![warn(missing_docs)]
+//! **Brief:** Resilience pipeline composing timeout, retry, circuit breaker, concurrency limit and fallback.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Fault Tolerance]
+//!  - [Resilience Pipeline]
+//!  - [Unified Metrics]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `ResiliencePipeline` wraps one operation in every configured stage, in a
+//! fixed order where each stage wraps the one before it:
+//!
+//! 1. timeout — each attempt fails with `AklypseError::Timeout` past the limit
+//! 2. retry — timed attempts are re-run per `RetryOptions`
+//! 3. circuit breaker — the whole retried call counts as one breaker call
+//! 4. bulkhead — calls beyond its slots and queue are rejected with
+//!    `AklypseError::ResourceExhausted` before anything runs
+//! 5. fallback — any error left is handed to the fallback
+//!
+//! Stages left unconfigured are skipped. Every call updates one
+//! `PipelineMetrics`, which also carries the breaker's and bulkhead's metrics.
+
+use super::bulkhead::{Bulkhead, BulkheadConfig, BulkheadMetrics};
+use super::circuitbreaker::{CircuitBreaker, CircuitMetrics};
+use super::{AklypseError, Result, TimeoutSnafu};
+use crate::common::utils::retry::{retry, RetryOptions};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Recovery run on the error a pipeline could not avoid
+pub type Fallback<T> = Arc<dyn Fn(&AklypseError) -> Result<T> + Send + Sync>;
+
+/// Counters of a `ResiliencePipeline`
+#[derive(Debug, Clone, Default)]
+pub struct PipelineMetrics {
+    /// Calls made through the pipeline
+    pub calls: u64,
+    /// Calls whose operation eventually succeeded
+    pub successes: u64,
+    /// Calls that ended in an error before the fallback stage
+    pub failures: u64,
+    /// Attempts that exceeded the timeout
+    pub timeouts: u64,
+    /// Attempts made beyond the first one of each call
+    pub retries: u64,
+    /// Calls rejected by the open circuit breaker
+    pub breaker_rejections: u64,
+    /// Calls rejected by the bulkhead
+    pub concurrency_rejections: u64,
+    /// Failures handed to the fallback
+    pub fallbacks: u64,
+    /// Calls currently running
+    pub in_flight: usize,
+    /// Metrics of the circuit breaker stage, if configured
+    pub circuit: Option<CircuitMetrics>,
+    /// Metrics of the bulkhead stage, if configured
+    pub bulkhead: Option<BulkheadMetrics>,
+}
+
+/// One callable composing the resilience primitives in a defined order
+pub struct ResiliencePipeline<T> {
+    name: String,
+    timeout: Option<Duration>,
+    retry: Option<RetryOptions>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    bulkhead: Option<Arc<Bulkhead>>,
+    fallback: Option<Fallback<T>>,
+    metrics: Mutex<PipelineMetrics>,
+}
+
+impl<T> ResiliencePipeline<T> {
+    /// Pipeline with no stages, which runs operations as they are
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            timeout: None,
+            retry: None,
+            circuit_breaker: None,
+            bulkhead: None,
+            fallback: None,
+            metrics: Mutex::new(PipelineMetrics::default()),
+        }
+    }
+
+    /// Fail each attempt that runs longer than `timeout`
+    ///
+    /// Synchronous attempts cannot be interrupted, so they are failed once
+    /// they return; asynchronous attempts are cancelled at the limit.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry failed attempts per `options`
+    pub fn with_retry(mut self, options: RetryOptions) -> Self {
+        self.retry = Some(options);
+        self
+    }
+
+    /// Guard the retried call with `circuit_breaker`
+    pub fn with_circuit_breaker(mut self, circuit_breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Reject calls while `max_concurrent` calls are already running
+    ///
+    /// Shorthand for a bulkhead named after the pipeline, without a queue.
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        let config = BulkheadConfig { max_concurrent, ..BulkheadConfig::default() };
+        self.bulkhead = Some(Bulkhead::new(self.name.clone(), config));
+        self
+    }
+
+    /// Run calls in a slot of `bulkhead`, which may be shared with other callers
+    pub fn with_bulkhead(mut self, bulkhead: Arc<Bulkhead>) -> Self {
+        self.bulkhead = Some(bulkhead);
+        self
+    }
+
+    /// Recover from errors with `fallback`
+    pub fn with_fallback(mut self, fallback: impl Fn(&AklypseError) -> Result<T> + Send + Sync + 'static) -> Self {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    /// Name of the pipeline, used in the errors it raises
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Snapshot of the pipeline's metrics
+    pub fn metrics(&self) -> PipelineMetrics {
+        let mut metrics = self.metrics.lock().unwrap_or_else(|p| p.into_inner()).clone();
+        metrics.circuit = self.circuit_breaker.as_ref().map(|breaker| breaker.metrics());
+        metrics.bulkhead = self.bulkhead.as_ref().map(|bulkhead| bulkhead.metrics());
+        metrics.in_flight = metrics.bulkhead.as_ref().map_or(0, |bulkhead| bulkhead.in_flight);
+        metrics
+    }
+
+    /// Run `operation` through every configured stage
+    pub fn execute<F>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Result<T>,
+    {
+        self.record(|metrics| metrics.calls += 1);
+        let permit = self.bulkhead.as_ref().map(|bulkhead| bulkhead.acquire()).transpose();
+        let result = match self.admitted(permit) {
+            Ok(_permit) => self.run_breaker(|| self.run_retry(|| self.run_timeout(&mut operation))),
+            Err(error) => Err(error),
+        };
+        self.finish(result)
+    }
+
+    /// Run the async `operation` through every configured stage
+    #[cfg(feature = "tokio")]
+    pub async fn execute_async<F, Fut>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.record(|metrics| metrics.calls += 1);
+        let permit = match &self.bulkhead {
+            Some(bulkhead) => bulkhead.acquire_async().await.map(Some),
+            None => Ok(None),
+        };
+        let result = match self.admitted(permit) {
+            Ok(_permit) => match &self.circuit_breaker {
+                Some(breaker) => {
+                    let mut ran = false;
+                    let result = breaker
+                        .execute_async(|| {
+                            ran = true;
+                            self.run_retry_async(&mut operation)
+                        })
+                        .await;
+                    if !ran {
+                        self.record(|metrics| metrics.breaker_rejections += 1);
+                    }
+                    result
+                }
+                None => self.run_retry_async(&mut operation).await,
+            },
+            Err(error) => Err(error),
+        };
+        self.finish(result)
+    }
+
+    // Stage 4: count the bulkhead's rejection; the slot is released on drop
+    fn admitted<P>(&self, permit: Result<P>) -> Result<P> {
+        if permit.is_err() {
+            self.record(|metrics| metrics.concurrency_rejections += 1);
+        }
+        permit
+    }
+
+    // Stage 3: count the retried call as one breaker call
+    fn run_breaker(&self, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+        let Some(breaker) = &self.circuit_breaker else {
+            return operation();
+        };
+        let mut ran = false;
+        let result = breaker.execute(|| {
+            ran = true;
+            operation()
+        });
+        if !ran {
+            self.record(|metrics| metrics.breaker_rejections += 1);
+        }
+        result
+    }
+
+    // Stage 2: re-run timed attempts
+    fn run_retry(&self, mut attempt: impl FnMut() -> Result<T>) -> Result<T> {
+        match &self.retry {
+            Some(options) => retry(options, |number| {
+                if number > 1 {
+                    self.record(|metrics| metrics.retries += 1);
+                }
+                attempt()
+            }),
+            None => attempt(),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn run_retry_async<F, Fut>(&self, operation: &mut F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        match &self.retry {
+            Some(options) => {
+                crate::common::utils::retry::retry_async(options, |number| {
+                    if number > 1 {
+                        self.record(|metrics| metrics.retries += 1);
+                    }
+                    self.run_timeout_async(operation())
+                })
+                .await
+            }
+            None => self.run_timeout_async(operation()).await,
+        }
+    }
+
+    // Stage 1: fail attempts that outlive the timeout
+    fn run_timeout(&self, operation: &mut impl FnMut() -> Result<T>) -> Result<T> {
+        let started = Instant::now();
+        let result = operation();
+        match self.timeout {
+            Some(timeout) if started.elapsed() > timeout => Err(self.timed_out(timeout)),
+            _ => result,
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    async fn run_timeout_async(&self, attempt: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        match self.timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, attempt).await {
+                Ok(result) => result,
+                Err(_) => Err(self.timed_out(timeout)),
+            },
+            None => attempt.await,
+        }
+    }
+
+    fn timed_out(&self, timeout: Duration) -> AklypseError {
+        self.record(|metrics| metrics.timeouts += 1);
+        TimeoutSnafu {
+            operation: format!("Operation in pipeline '{}'", self.name),
+            duration: timeout,
+        }
+        .build()
+    }
+
+    // Stage 5: count the outcome and hand errors to the fallback
+    fn finish(&self, result: Result<T>) -> Result<T> {
+        match result {
+            Ok(value) => {
+                self.record(|metrics| metrics.successes += 1);
+                Ok(value)
+            }
+            Err(error) => {
+                self.record(|metrics| metrics.failures += 1);
+                match &self.fallback {
+                    Some(fallback) => {
+                        self.record(|metrics| metrics.fallbacks += 1);
+                        fallback(&error)
+                    }
+                    None => Err(error),
+                }
+            }
+        }
+    }
+
+    fn record(&self, update: impl FnOnce(&mut PipelineMetrics)) {
+        update(&mut self.metrics.lock().unwrap_or_else(|p| p.into_inner()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreakerConfig, ErrorCategory, InternalSnafu};
+    use crate::common::utils::retry::Backoff;
+
+    fn failure() -> AklypseError {
+        InternalSnafu { message: "upstream unavailable".to_string(), source: None }.build()
+    }
+
+    #[test]
+    fn test_retry_then_fallback_metrics() {
+        let pipeline = ResiliencePipeline::new("quotes")
+            .with_retry(RetryOptions::new(Backoff::Fixed(Duration::ZERO)).with_max_attempts(3))
+            .with_fallback(|_| Ok(0));
+
+        let mut calls = 0;
+        assert_eq!(
+            pipeline
+                .execute(|| {
+                    calls += 1;
+                    if calls < 2 { Err(failure()) } else { Ok(7) }
+                })
+                .unwrap(),
+            7
+        );
+        assert_eq!(pipeline.execute(|| Err(failure())).unwrap(), 0);
+
+        let metrics = pipeline.metrics();
+        assert_eq!((metrics.calls, metrics.successes, metrics.failures), (2, 1, 1));
+        assert_eq!((metrics.retries, metrics.fallbacks), (3, 1));
+        assert!(metrics.circuit.is_none());
+    }
+
+    #[test]
+    fn test_timeout_and_breaker_stages() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            operation_timeout: None,
+            ..Default::default()
+        };
+        let pipeline = ResiliencePipeline::new("ledger")
+            .with_timeout(Duration::from_millis(1))
+            .with_circuit_breaker(CircuitBreaker::new("ledger", config));
+
+        let error = pipeline
+            .execute(|| {
+                std::thread::sleep(Duration::from_millis(5));
+                Ok(())
+            })
+            .unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+
+        let mut ran = false;
+        let error = pipeline
+            .execute(|| {
+                ran = true;
+                Ok(())
+            })
+            .unwrap_err();
+        assert!(!ran);
+        assert_eq!(error.category(), ErrorCategory::CircuitBreaker);
+
+        let metrics = pipeline.metrics();
+        assert_eq!((metrics.timeouts, metrics.breaker_rejections, metrics.failures), (1, 1, 2));
+        assert_eq!(metrics.circuit.map(|circuit| circuit.rejected_requests), Some(1));
+    }
+
+    #[test]
+    fn test_concurrency_limit_rejects_before_running() {
+        let pipeline = ResiliencePipeline::new("exports").with_max_concurrent(1);
+
+        let nested = pipeline
+            .execute(|| {
+                let mut ran = false;
+                let inner = pipeline.execute(|| {
+                    ran = true;
+                    Ok(())
+                });
+                assert!(!ran);
+                inner
+            })
+            .unwrap_err();
+        assert_eq!(nested.category(), ErrorCategory::ResourceExhaustion);
+        assert_eq!(pipeline.metrics().concurrency_rejections, 1);
+        assert_eq!(pipeline.metrics().in_flight, 0);
+        assert!(pipeline.execute(|| Ok(())).is_ok());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_execute_async_cancels_at_timeout() {
+        let pipeline = ResiliencePipeline::new("async")
+            .with_timeout(Duration::from_millis(5))
+            .with_retry(RetryOptions::new(Backoff::Fixed(Duration::ZERO)).with_max_attempts(2));
+
+        let error = pipeline
+            .execute_async(|| async {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(())
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        assert_eq!((pipeline.metrics().timeouts, pipeline.metrics().retries), (2, 1));
+    }
+}
@@ -0,0 +1,203 @@
+/* src/common/error/protected_task.rs */
+#![warn(missing_docs)]
+//! **Brief:** Spawned tokio tasks whose outcome feeds a circuit breaker.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Circuit Breaker Pattern]
+//!  - [Task Supervision]
+//!  - [Panic Handling]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ProtectedTask::spawn` runs a future on its own tokio task as one call
+//! through a `CircuitBreaker`. The breaker admits or rejects the task when
+//! it starts; once admitted, the task's result is recorded like any other
+//! call, and a panic is turned into an `Internal` error that counts as a
+//! failure instead of tearing down the awaiting task.
+//!
+//! Cancellation is not held against the dependency: aborting the task, or
+//! the runtime shutting down, records nothing and only frees the breaker's
+//! HalfOpen slot. The handle is itself a future resolving to the task's
+//! result.
+
+use super::circuitbreaker::panic_message;
+use super::{AklypseError, CircuitBreaker, ErrorContext, InternalSnafu, Result};
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::task::{AbortHandle, JoinError, JoinHandle};
+
+/// Metadata key holding the name of the breaker whose task panicked or was cancelled
+pub const PROTECTED_TASK_METADATA_KEY: &str = "protected_task";
+
+/// Handle of a task spawned through a circuit breaker.
+pub struct ProtectedTask<T> {
+    breaker: String,
+    handle: JoinHandle<Result<T>>,
+}
+
+// Aborts the inner task when the outer one is dropped mid-flight
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl<T: Send + 'static> ProtectedTask<T> {
+    /// Spawn `future` as a call through `breaker`
+    ///
+    /// # Panics
+    ///
+    /// Outside of a tokio runtime.
+    pub fn spawn<Fut>(breaker: Arc<CircuitBreaker>, future: Fut) -> Self
+    where
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let name = breaker.name().to_string();
+        let handle = tokio::spawn(async move {
+            let permit = breaker.acquire()?;
+            let inner = tokio::spawn(future);
+            let _abort = AbortOnDrop(inner.abort_handle());
+            match inner.await {
+                Ok(result) => {
+                    match &result {
+                        Ok(_) => permit.success(),
+                        Err(error) => permit.failure(error),
+                    }
+                    result
+                }
+                Err(error) if error.is_panic() => {
+                    let error = panicked(breaker.name(), error.into_panic());
+                    permit.failure(&error);
+                    Err(error)
+                }
+                Err(_) => Err(cancelled(breaker.name())),
+            }
+        });
+        Self { breaker: name, handle }
+    }
+}
+
+impl<T> ProtectedTask<T> {
+    /// Cancel the task; awaiting it then fails without touching the breaker
+    pub fn abort(&self) {
+        self.handle.abort();
+    }
+
+    /// Whether the task has completed, failed or been cancelled
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+impl<T> Future for ProtectedTask<T> {
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle).poll(cx).map(|joined| joined.unwrap_or_else(|error| Err(self.join_failed(error))))
+    }
+}
+
+impl<T> ProtectedTask<T> {
+    fn join_failed(&self, error: JoinError) -> AklypseError {
+        if error.is_panic() {
+            panicked(&self.breaker, error.into_panic())
+        } else {
+            cancelled(&self.breaker)
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ProtectedTask<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProtectedTask")
+            .field("breaker", &self.breaker)
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+fn panicked(breaker: &str, payload: Box<dyn Any + Send>) -> AklypseError {
+    let error: AklypseError = InternalSnafu { message: format!("panic: {}", panic_message(payload.as_ref())), source: None }.build();
+    error.add_context(
+        ErrorContext::new(format!("Task protected by circuit breaker '{}' panicked", breaker))
+            .with_metadata(PROTECTED_TASK_METADATA_KEY, breaker.to_string()),
+    )
+}
+
+fn cancelled(breaker: &str) -> AklypseError {
+    let error: AklypseError =
+        InternalSnafu { message: format!("Task protected by circuit breaker '{}' was cancelled", breaker), source: None }.build();
+    error.add_context(ErrorContext::new("Task cancelled").with_metadata(PROTECTED_TASK_METADATA_KEY, breaker.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreakerConfig, CircuitState};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    fn breaker() -> Arc<CircuitBreaker> {
+        CircuitBreaker::new("worker", CircuitBreakerConfig { failure_threshold: 1, operation_timeout: None, ..Default::default() })
+    }
+
+    #[tokio::test]
+    async fn test_panic_becomes_failure_and_trips() {
+        let cb = breaker();
+        assert_eq!(ProtectedTask::spawn(cb.clone(), async { Ok(3) }).await.unwrap(), 3);
+
+        let error = ProtectedTask::<()>::spawn(cb.clone(), async { panic!("worker exploded") }).await.unwrap_err();
+        match &error {
+            AklypseError::WithRichContext { source, .. } => {
+                assert!(matches!(source.as_ref(), AklypseError::Internal { message, .. } if message == "panic: worker exploded"));
+            }
+            other => panic!("expected a panic error, got {:?}", other),
+        }
+        assert_eq!(cb.state(), CircuitState::Open);
+
+        let rejected = ProtectedTask::spawn(cb.clone(), async { Ok(()) }).await.unwrap_err();
+        assert!(matches!(rejected, AklypseError::CircuitBreakerOpen { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_abort_cancels_work_without_counting_failure() {
+        // Flag set when the spawned future is dropped
+        struct Dropped(Arc<AtomicBool>);
+
+        impl Drop for Dropped {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let cb = breaker();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = Dropped(dropped.clone());
+        let task = ProtectedTask::spawn(cb.clone(), async move {
+            let _guard = guard;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        task.abort();
+        assert!(task.await.is_err());
+
+        for _ in 0..100 {
+            if dropped.load(Ordering::SeqCst) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(dropped.load(Ordering::SeqCst));
+        assert_eq!(cb.metrics().failed_requests, 0);
+        assert_eq!(cb.state(), CircuitState::Closed);
+    }
+}
@@ -0,0 +1,613 @@
+/* src/common/error/proto.rs */
+#![warn(missing_docs)]
+//! **Brief:** Protobuf encoding of error reports (feature `protobuf`).
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Binary Encoding]
+//!  - [Protobuf Schema]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! The message types below are hand-written `prost` messages mirroring
+//! `PROTO_SCHEMA`, so no build script or `protoc` is needed. Field numbers are
+//! part of the wire contract: never renumber or reuse them, only append.
+//! Enumerations (category, severity, fix type) travel as their variant names,
+//! exactly as in the JSON representation.
+
+use super::report::{BacktraceFrame, ErrorReport, ReportId};
+use super::schema::REPORT_SCHEMA_VERSION;
+use super::types::{
+    Autocorrection, DiagnosticResult, ErrorCategory, ErrorContext, ErrorLocation, ErrorSeverity, ErrorSource, FixType,
+    MessageKey, RecoveryAction,
+};
+use super::{ParseSnafu, Result};
+use prost::Message;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Protobuf schema of the messages in this module, for consumers in other languages
+pub const PROTO_SCHEMA: &str = r#"syntax = "proto3";
+
+package aklypse.error.v1;
+
+message ErrorReport {
+  string report_id = 1;
+  string message = 2;
+  optional string category = 3;
+  string severity = 4;
+  repeated string chain = 5;
+  bool chain_truncated = 6;
+  ErrorContext context = 7;
+  repeated Field fields = 8;
+  Diagnostics diagnostics = 9;
+  repeated Autocorrection autocorrections = 10;
+  repeated BacktraceFrame backtrace = 11;
+  uint32 schema_version = 12;
+}
+
+message Field {
+  string key = 1;
+  string value = 2;
+}
+
+message ErrorContext {
+  string message = 1;
+  string severity = 2;
+  optional string component = 3;
+  optional string correlation_id = 4;
+  optional string recovery_suggestion = 5;
+  optional uint64 timestamp_ms = 6;
+  repeated string tags = 7;
+  map<string, string> metadata = 8;
+  SourceLocation source_location = 9;
+  repeated RecoveryAction recovery_actions = 10;
+  optional string public_message = 11;
+  MessageKey message_key = 12;
+}
+
+message MessageKey {
+  string key = 1;
+  repeated Field args = 2;
+}
+
+message RecoveryAction {
+  string kind = 1;
+  string value = 2;
+}
+
+message SourceLocation {
+  string file = 1;
+  uint32 line = 2;
+  string module_path = 3;
+}
+
+message Diagnostics {
+  optional string code = 1;
+  optional string message = 2;
+  Location location = 3;
+  repeated string suggested_fixes = 4;
+}
+
+message Location {
+  string file = 1;
+  uint32 line = 2;
+  uint32 column = 3;
+  string function_context = 4;
+}
+
+message Autocorrection {
+  string description = 1;
+  string fix_type = 2;
+  double confidence = 3;
+  optional string diff = 4;
+  repeated string commands = 5;
+  optional string targets_error_code = 6;
+}
+
+message BacktraceFrame {
+  string symbol = 1;
+  optional string location = 2;
+}
+"#;
+
+/// Wire form of an `ErrorReport`
+#[derive(Clone, PartialEq, Message)]
+pub struct ErrorReportProto {
+    /// Report id
+    #[prost(string, tag = "1")]
+    pub report_id: String,
+    /// Error message
+    #[prost(string, tag = "2")]
+    pub message: String,
+    /// Category variant name
+    #[prost(string, optional, tag = "3")]
+    pub category: Option<String>,
+    /// Severity variant name
+    #[prost(string, tag = "4")]
+    pub severity: String,
+    /// Cause chain messages
+    #[prost(string, repeated, tag = "5")]
+    pub chain: Vec<String>,
+    /// Whether the chain was truncated
+    #[prost(bool, tag = "6")]
+    pub chain_truncated: bool,
+    /// Rich context
+    #[prost(message, optional, tag = "7")]
+    pub context: Option<ErrorContextProto>,
+    /// Extracted fields
+    #[prost(message, repeated, tag = "8")]
+    pub fields: Vec<FieldProto>,
+    /// Embedded diagnostics
+    #[prost(message, optional, tag = "9")]
+    pub diagnostics: Option<DiagnosticsProto>,
+    /// Proposed autocorrections
+    #[prost(message, repeated, tag = "10")]
+    pub autocorrections: Vec<AutocorrectionProto>,
+    /// Backtrace frames
+    #[prost(message, repeated, tag = "11")]
+    pub backtrace: Vec<BacktraceFrameProto>,
+    /// Report schema version the message was written with (0 before versioning)
+    #[prost(uint32, tag = "12")]
+    pub schema_version: u32,
+}
+
+/// Wire form of an extracted field
+#[derive(Clone, PartialEq, Message)]
+pub struct FieldProto {
+    /// Field name
+    #[prost(string, tag = "1")]
+    pub key: String,
+    /// Field value
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+/// Wire form of an `ErrorContext`
+#[derive(Clone, PartialEq, Message)]
+pub struct ErrorContextProto {
+    /// Context message
+    #[prost(string, tag = "1")]
+    pub message: String,
+    /// Severity variant name
+    #[prost(string, tag = "2")]
+    pub severity: String,
+    /// Component name
+    #[prost(string, optional, tag = "3")]
+    pub component: Option<String>,
+    /// Correlation id
+    #[prost(string, optional, tag = "4")]
+    pub correlation_id: Option<String>,
+    /// Recovery suggestion
+    #[prost(string, optional, tag = "5")]
+    pub recovery_suggestion: Option<String>,
+    /// Milliseconds since the Unix epoch
+    #[prost(uint64, optional, tag = "6")]
+    pub timestamp_ms: Option<u64>,
+    /// Tags
+    #[prost(string, repeated, tag = "7")]
+    pub tags: Vec<String>,
+    /// Metadata
+    #[prost(map = "string, string", tag = "8")]
+    pub metadata: HashMap<String, String>,
+    /// Source location
+    #[prost(message, optional, tag = "9")]
+    pub source_location: Option<SourceLocationProto>,
+    /// Typed recovery steps
+    #[prost(message, repeated, tag = "10")]
+    pub recovery_actions: Vec<RecoveryActionProto>,
+    /// User-facing message
+    #[prost(string, optional, tag = "11")]
+    pub public_message: Option<String>,
+    /// Key of the translated user-facing message
+    #[prost(message, optional, tag = "12")]
+    pub message_key: Option<MessageKeyProto>,
+}
+
+/// Wire form of a `MessageKey`
+#[derive(Clone, PartialEq, Message)]
+pub struct MessageKeyProto {
+    /// Message key
+    #[prost(string, tag = "1")]
+    pub key: String,
+    /// Named arguments, in order
+    #[prost(message, repeated, tag = "2")]
+    pub args: Vec<FieldProto>,
+}
+
+/// Wire form of a `RecoveryAction`
+#[derive(Clone, PartialEq, Message)]
+pub struct RecoveryActionProto {
+    /// Action kind (`RecoveryAction::kind`)
+    #[prost(string, tag = "1")]
+    pub kind: String,
+    /// Action value (`RecoveryAction::value`)
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+/// Wire form of an `ErrorSource`
+#[derive(Clone, PartialEq, Message)]
+pub struct SourceLocationProto {
+    /// Source file
+    #[prost(string, tag = "1")]
+    pub file: String,
+    /// Line number
+    #[prost(uint32, tag = "2")]
+    pub line: u32,
+    /// Module path
+    #[prost(string, tag = "3")]
+    pub module_path: String,
+}
+
+/// Wire form of a `DiagnosticResult` (the macro expansion trace is not transmitted)
+#[derive(Clone, PartialEq, Message)]
+pub struct DiagnosticsProto {
+    /// Diagnostic code
+    #[prost(string, optional, tag = "1")]
+    pub code: Option<String>,
+    /// Original tool message
+    #[prost(string, optional, tag = "2")]
+    pub message: Option<String>,
+    /// Primary location
+    #[prost(message, optional, tag = "3")]
+    pub location: Option<LocationProto>,
+    /// Tool-suggested fixes
+    #[prost(string, repeated, tag = "4")]
+    pub suggested_fixes: Vec<String>,
+}
+
+/// Wire form of an `ErrorLocation`
+#[derive(Clone, PartialEq, Message)]
+pub struct LocationProto {
+    /// Source file
+    #[prost(string, tag = "1")]
+    pub file: String,
+    /// Line number
+    #[prost(uint32, tag = "2")]
+    pub line: u32,
+    /// Column number
+    #[prost(uint32, tag = "3")]
+    pub column: u32,
+    /// Enclosing function
+    #[prost(string, tag = "4")]
+    pub function_context: String,
+}
+
+/// Wire form of an `Autocorrection` (fix details are not transmitted)
+#[derive(Clone, PartialEq, Message)]
+pub struct AutocorrectionProto {
+    /// Fix description
+    #[prost(string, tag = "1")]
+    pub description: String,
+    /// Fix type variant name
+    #[prost(string, tag = "2")]
+    pub fix_type: String,
+    /// Confidence between 0 and 1
+    #[prost(double, tag = "3")]
+    pub confidence: f64,
+    /// Unified diff
+    #[prost(string, optional, tag = "4")]
+    pub diff: Option<String>,
+    /// Commands applying the fix
+    #[prost(string, repeated, tag = "5")]
+    pub commands: Vec<String>,
+    /// Error code the fix targets
+    #[prost(string, optional, tag = "6")]
+    pub targets_error_code: Option<String>,
+}
+
+/// Wire form of a `BacktraceFrame`
+#[derive(Clone, PartialEq, Message)]
+pub struct BacktraceFrameProto {
+    /// Symbol name
+    #[prost(string, tag = "1")]
+    pub symbol: String,
+    /// Source location
+    #[prost(string, optional, tag = "2")]
+    pub location: Option<String>,
+}
+
+impl From<&ErrorReport> for ErrorReportProto {
+    fn from(report: &ErrorReport) -> Self {
+        Self {
+            report_id: report.report_id.to_string(),
+            message: report.message.clone(),
+            category: report.category.map(|category| format!("{:?}", category)),
+            severity: format!("{:?}", report.severity),
+            chain: report.chain.clone(),
+            chain_truncated: report.chain_truncated,
+            context: report.context.as_ref().map(context_to_proto),
+            fields: report
+                .fields
+                .iter()
+                .map(|(key, value)| FieldProto { key: key.clone(), value: value.clone() })
+                .collect(),
+            diagnostics: report.diagnostics.as_ref().map(|diagnostics| DiagnosticsProto {
+                code: diagnostics.diagnostic_code.clone(),
+                message: diagnostics.original_message.clone(),
+                location: diagnostics.primary_location.as_ref().map(|location| LocationProto {
+                    file: location.file.clone(),
+                    line: location.line,
+                    column: location.column,
+                    function_context: location.function_context.clone(),
+                }),
+                suggested_fixes: diagnostics.suggested_fixes.clone(),
+            }),
+            autocorrections: report
+                .autocorrections
+                .iter()
+                .map(|autocorrection| AutocorrectionProto {
+                    description: autocorrection.description.clone(),
+                    fix_type: format!("{:?}", autocorrection.fix_type),
+                    confidence: autocorrection.confidence,
+                    diff: autocorrection.diff_suggestion.clone(),
+                    commands: autocorrection.commands_to_apply.clone(),
+                    targets_error_code: autocorrection.targets_error_code.clone(),
+                })
+                .collect(),
+            backtrace: report
+                .backtrace_frames
+                .iter()
+                .map(|frame| BacktraceFrameProto { symbol: frame.symbol.clone(), location: frame.location.clone() })
+                .collect(),
+            schema_version: REPORT_SCHEMA_VERSION,
+        }
+    }
+}
+
+impl ErrorReportProto {
+    /// Convert back into an `ErrorReport`
+    ///
+    /// Unknown enumeration names (e.g. from a newer producer) degrade to
+    /// `ErrorCategory::Unspecified`, `ErrorSeverity::Error` and
+    /// `FixType::Information` instead of failing.
+    pub fn into_report(self) -> Result<ErrorReport> {
+        let report_id = ReportId::parse(&self.report_id).ok_or_else(|| {
+            ParseSnafu {
+                source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid report id"))
+                    as Box<dyn std::error::Error + Send + Sync>,
+                kind: "protobuf".to_string(),
+                context_info: format!("report_id '{}'", self.report_id),
+            }
+            .build()
+        })?;
+
+        let mut report = ErrorReport::new(self.message).with_report_id(report_id);
+        report.category = self.category.as_deref().map(parse_category);
+        report.severity = parse_severity(&self.severity);
+        report.chain = self.chain;
+        report.chain_truncated = self.chain_truncated;
+        report.context = self.context.map(context_from_proto);
+        report.fields = self.fields.into_iter().map(|field| (field.key, field.value)).collect();
+        report.diagnostics = self.diagnostics.map(|diagnostics| DiagnosticResult {
+            primary_location: diagnostics.location.map(|location| {
+                ErrorLocation::new(location.file, location.line, location.column, location.function_context)
+            }),
+            expansion_trace: Vec::new(),
+            suggested_fixes: diagnostics.suggested_fixes,
+            original_message: diagnostics.message,
+            diagnostic_code: diagnostics.code,
+            stack_frames: Vec::new(),
+        });
+        report.autocorrections = self
+            .autocorrections
+            .into_iter()
+            .map(|autocorrection| {
+                let mut converted =
+                    Autocorrection::new(autocorrection.description, parse_fix_type(&autocorrection.fix_type), autocorrection.confidence);
+                converted.diff_suggestion = autocorrection.diff;
+                converted.commands_to_apply = autocorrection.commands;
+                converted.targets_error_code = autocorrection.targets_error_code;
+                converted
+            })
+            .collect();
+        report.backtrace_frames = self
+            .backtrace
+            .into_iter()
+            .map(|frame| BacktraceFrame { symbol: frame.symbol, location: frame.location })
+            .collect();
+
+        Ok(report)
+    }
+}
+
+impl ErrorReport {
+    /// Encode the report as a protobuf `aklypse.error.v1.ErrorReport` message
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        ErrorReportProto::from(self).encode_to_vec()
+    }
+
+    /// Decode a report from its protobuf encoding
+    pub fn from_protobuf(bytes: &[u8]) -> Result<ErrorReport> {
+        let proto = ErrorReportProto::decode(bytes).map_err(|err| {
+            ParseSnafu {
+                source: Box::new(err) as Box<dyn std::error::Error + Send + Sync>,
+                kind: "protobuf".to_string(),
+                context_info: "decoding ErrorReport".to_string(),
+            }
+            .build()
+        })?;
+        proto.into_report()
+    }
+}
+
+/// Convert a protobuf-encoded report into the JSON representation of `ErrorReport::to_json`
+pub fn protobuf_to_json(bytes: &[u8], pretty: bool) -> Result<String> {
+    ErrorReport::from_protobuf(bytes).map(|report| report.to_json(pretty))
+}
+
+fn context_to_proto(context: &ErrorContext) -> ErrorContextProto {
+    ErrorContextProto {
+        message: context.message.to_string(),
+        severity: format!("{:?}", context.severity),
+        component: context.component.as_deref().map(str::to_string),
+        correlation_id: context.correlation_id.as_deref().map(str::to_string),
+        recovery_suggestion: context.recovery_suggestion.as_deref().map(str::to_string),
+        timestamp_ms: context
+            .timestamp
+            .and_then(|timestamp| timestamp.duration_since(UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_millis() as u64),
+        tags: context.tags.to_vec(),
+        metadata: (*context.metadata).clone(),
+        source_location: context.source_location.as_ref().map(|location| SourceLocationProto {
+            file: location.file.clone(),
+            line: location.line,
+            module_path: location.module_path.clone(),
+        }),
+        recovery_actions: context
+            .recovery_actions
+            .iter()
+            .map(|action| RecoveryActionProto { kind: action.kind().to_string(), value: action.value() })
+            .collect(),
+        public_message: context.public_message.as_deref().map(str::to_string),
+        message_key: context.message_key.as_ref().map(|key| MessageKeyProto {
+            key: key.key.clone(),
+            args: key
+                .args
+                .iter()
+                .map(|(key, value)| FieldProto { key: key.clone(), value: value.clone() })
+                .collect(),
+        }),
+    }
+}
+
+fn context_from_proto(proto: ErrorContextProto) -> ErrorContext {
+    let mut context = ErrorContext::new(proto.message).with_severity(parse_severity(&proto.severity));
+    context.component = proto.component.map(Arc::from);
+    context.correlation_id = proto.correlation_id.map(Arc::from);
+    context.recovery_suggestion = proto.recovery_suggestion.map(Arc::from);
+    context.public_message = proto.public_message.map(Arc::from);
+    context.message_key = proto.message_key.map(|key| MessageKey {
+        key: key.key,
+        args: key.args.into_iter().map(|arg| (arg.key, arg.value)).collect(),
+    });
+    context.timestamp = proto.timestamp_ms.map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+    context.tags = Arc::new(proto.tags);
+    context.metadata = Arc::new(proto.metadata);
+    context.source_location = proto
+        .source_location
+        .map(|location| ErrorSource::new(location.file, location.line, location.module_path));
+    // Actions of kinds unknown to this build are skipped
+    context.recovery_actions = Arc::new(
+        proto
+            .recovery_actions
+            .into_iter()
+            .filter_map(|action| RecoveryAction::from_parts(&action.kind, action.value))
+            .collect(),
+    );
+    context
+}
+
+pub(crate) fn parse_category(name: &str) -> ErrorCategory {
+    match name {
+        "Io" => ErrorCategory::Io,
+        "Parsing" => ErrorCategory::Parsing,
+        "Network" => ErrorCategory::Network,
+        "Configuration" => ErrorCategory::Configuration,
+        "Validation" => ErrorCategory::Validation,
+        "Internal" => ErrorCategory::Internal,
+        "CircuitBreaker" => ErrorCategory::CircuitBreaker,
+        "Timeout" => ErrorCategory::Timeout,
+        "ResourceExhaustion" => ErrorCategory::ResourceExhaustion,
+        "NotFound" => ErrorCategory::NotFound,
+        "Concurrency" => ErrorCategory::Concurrency,
+        "ExternalService" => ErrorCategory::ExternalService,
+        "Authentication" => ErrorCategory::Authentication,
+        "Authorization" => ErrorCategory::Authorization,
+        "StateConflict" => ErrorCategory::StateConflict,
+        "Multiple" => ErrorCategory::Multiple,
+        _ => ErrorCategory::Unspecified,
+    }
+}
+
+pub(crate) fn parse_severity(name: &str) -> ErrorSeverity {
+    match name {
+        "Debug" => ErrorSeverity::Debug,
+        "Info" => ErrorSeverity::Info,
+        "Warning" => ErrorSeverity::Warning,
+        "Critical" => ErrorSeverity::Critical,
+        "Fatal" => ErrorSeverity::Fatal,
+        _ => ErrorSeverity::Error,
+    }
+}
+
+pub(crate) fn parse_fix_type(name: &str) -> FixType {
+    match name {
+        "TextReplacement" => FixType::TextReplacement,
+        "AstModification" => FixType::AstModification,
+        "AddImport" => FixType::AddImport,
+        "AddDependency" => FixType::AddDependency,
+        "ConfigurationChange" => FixType::ConfigurationChange,
+        "ExecuteCommand" => FixType::ExecuteCommand,
+        "Refactor" => FixType::Refactor,
+        "ManualInterventionRequired" => FixType::ManualInterventionRequired,
+        "UpdateCargoToml" => FixType::UpdateCargoToml,
+        "RunCargoCommand" => FixType::RunCargoCommand,
+        "SuggestAlternativeMethod" => FixType::SuggestAlternativeMethod,
+        _ => FixType::Information,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> ErrorReport {
+        let mut context = ErrorContext::new("loading config")
+            .with_component("loader")
+            .with_metadata("path", "app.toml")
+            .with_recovery_action(RecoveryAction::RetryAfter(Duration::from_millis(1500)))
+            .with_recovery_action(RecoveryAction::CheckConfigKey("loader.path".to_string()))
+            .with_public_message("Settings could not be loaded.")
+            .with_message_key(MessageKey::new("config.missing").with_arg("path", "app.toml"));
+        context.timestamp = Some(UNIX_EPOCH + Duration::from_millis(1_709_210_096_789));
+
+        let mut report = ErrorReport::new("file missing").with_report_id(ReportId::from_parts(1_709_210_096_789, 42));
+        report.category = Some(ErrorCategory::NotFound);
+        report.severity = ErrorSeverity::Warning;
+        report.chain = vec!["os error 2".to_string()];
+        report.context = Some(context);
+        report.fields = vec![("io.kind".to_string(), "NotFound".to_string())];
+        report.autocorrections = vec![Autocorrection::new("Create the file", FixType::ExecuteCommand, 0.7)];
+        report.backtrace_frames = vec![BacktraceFrame::new("app::main").with_location("src/main.rs:3:5")];
+        report
+    }
+
+    #[test]
+    fn test_protobuf_round_trip_matches_json() {
+        let report = sample_report();
+        let bytes = report.to_protobuf();
+        let decoded = ErrorReport::from_protobuf(&bytes).unwrap();
+
+        assert_eq!(decoded.to_json(false), report.to_json(false));
+        assert_eq!(protobuf_to_json(&bytes, true).unwrap(), report.to_json(true));
+    }
+
+    #[test]
+    fn test_protobuf_records_schema_version() {
+        let proto = ErrorReportProto::decode(sample_report().to_protobuf().as_slice()).unwrap();
+        assert_eq!(proto.schema_version, REPORT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_invalid_protobuf_is_a_parse_error() {
+        let err = ErrorReport::from_protobuf(&[0xff, 0xff, 0xff]).unwrap_err();
+        assert_eq!(err.category(), ErrorCategory::Parsing);
+    }
+
+    #[test]
+    fn test_unknown_enum_names_degrade() {
+        let mut proto = ErrorReportProto::from(&sample_report());
+        proto.category = Some("FromTheFuture".to_string());
+        proto.severity = "Catastrophic".to_string();
+
+        let report = proto.into_report().unwrap();
+        assert_eq!(report.category, Some(ErrorCategory::Unspecified));
+        assert_eq!(report.severity, ErrorSeverity::Error);
+    }
+}
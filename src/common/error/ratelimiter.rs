@@ -0,0 +1,449 @@
+/* src/common/error/ratelimiter.rs */
+#![warn(missing_docs)]
+//! **Brief:** Rate limiter with token bucket and fixed window strategies.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Rate Limiting]
+//!  - [Fault Tolerance]
+//!  - [Throughput Limits]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `RateLimiter` caps how many calls start per unit of time, shared by
+//! threads and tokio tasks. A `TokenBucket` allows bursts of up to
+//! `capacity` calls and refills one token every `refill_interval`; a
+//! `FixedWindow` allows `limit` calls per `window`, counted from the first
+//! call. A call over the limit fails with `AklypseError::RateLimited`,
+//! whose `retry_after` says when a permit is next available, unless the
+//! caller may wait that long (`max_wait`). With a `jitter`, the jittered
+//! `retry_after` is added to it, so callers turned away together come back
+//! spread out, and never before a permit can be free.
+//!
+//! Like `CircuitBreaker`, it reports to `RateLimiterObserver`s and keeps a
+//! `RateLimiterMetrics` snapshot of its counters. The limiter never counts
+//! how long calls run; pair it with a `Bulkhead` for that.
+
+use super::{AklypseError, RateLimitedSnafu, Result};
+use crate::common::utils::jitter::{Jitter, SeededRng};
+use arc_swap::ArcSwap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How a rate limiter hands out permits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitStrategy {
+    /// Bucket of `capacity` tokens, starting full; one token is added every
+    /// `refill_interval` and each call takes one.
+    TokenBucket {
+        /// Most tokens the bucket holds, i.e. the largest burst.
+        capacity: u32,
+        /// Time to add one token; a zero interval never limits.
+        refill_interval: Duration,
+    },
+    /// `limit` calls per consecutive `window`.
+    FixedWindow {
+        /// Calls allowed in each window.
+        limit: u32,
+        /// Length of a window.
+        window: Duration,
+    },
+}
+
+/// Configuration for the RateLimiter.
+#[derive(Debug, Clone)]
+pub struct RateLimiterConfig {
+    /// How permits are handed out.
+    pub strategy: RateLimitStrategy,
+    /// Longest `acquire` waits for a permit before being rejected; never waits when `None`.
+    pub max_wait: Option<Duration>,
+    /// Optional jitter lengthening each `retry_after` by a random share of it.
+    pub jitter: Option<Arc<dyn Jitter>>,
+    /// Seed of the jitter's random source; drawn from entropy when `None`.
+    pub jitter_seed: Option<u64>,
+}
+
+impl Default for RateLimiterConfig {
+    fn default() -> Self {
+        Self {
+            strategy: RateLimitStrategy::TokenBucket { capacity: 100, refill_interval: Duration::from_millis(10) },
+            max_wait: None,
+            jitter: None,
+            jitter_seed: None,
+        }
+    }
+}
+
+/// Metrics collected by the rate limiter
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiterMetrics {
+    /// Calls that asked for a permit
+    pub total_requests: u64,
+    /// Calls that got a permit
+    pub permitted_requests: u64,
+    /// Calls rejected with `RateLimited`
+    pub rejected_requests: u64,
+    /// Permits left right now
+    pub available_permits: u32,
+    /// Time permitted calls spent waiting, summed
+    pub total_wait: Duration,
+}
+
+/// Observer trait for rate limiter events.
+pub trait RateLimiterObserver: Send + Sync {
+    /// Called when a call gets a permit, after waiting `waited`.
+    fn on_permitted(&self, name: &str, waited: Duration, available: u32);
+    /// Called when a call is turned away; a permit is free after `retry_after`.
+    fn on_rejected(&self, name: &str, retry_after: Duration);
+}
+
+// Permits left under the strategy, refreshed before every decision
+#[derive(Debug)]
+struct Allowance {
+    tokens: f64,
+    last_refill: Instant,
+    window_start: Instant,
+    window_count: u32,
+}
+
+struct LimiterState {
+    allowance: Allowance,
+    metrics: RateLimiterMetrics,
+    jitter_rng: SeededRng,
+    // Last jitter added, the `previous` delay of the jitter strategy
+    last_jitter: Duration,
+}
+
+/// Limits how many calls start per unit of time.
+pub struct RateLimiter {
+    name: String,
+    config: RateLimiterConfig,
+    state: Mutex<LimiterState>,
+    observers: ArcSwap<Vec<Arc<dyn RateLimiterObserver>>>,
+}
+
+impl RateLimiter {
+    /// Creates a new RateLimiter instance
+    pub fn new(name: impl Into<String>, config: RateLimiterConfig) -> Arc<Self> {
+        let now = Instant::now();
+        let tokens = match config.strategy {
+            RateLimitStrategy::TokenBucket { capacity, .. } => f64::from(capacity),
+            RateLimitStrategy::FixedWindow { .. } => 0.0,
+        };
+        let allowance = Allowance { tokens, last_refill: now, window_start: now, window_count: 0 };
+        let state = LimiterState {
+            allowance,
+            metrics: RateLimiterMetrics::default(),
+            jitter_rng: SeededRng::from_seed(config.jitter_seed),
+            last_jitter: Duration::ZERO,
+        };
+        Arc::new(Self {
+            name: name.into(),
+            config,
+            state: Mutex::new(state),
+            observers: ArcSwap::from_pointee(Vec::new()),
+        })
+    }
+
+    /// Name the rate limiter was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Add an observer to the rate limiter
+    pub fn add_observer(&self, observer: Arc<dyn RateLimiterObserver>) {
+        self.observers.rcu(|observers| {
+            let mut observers = Vec::clone(observers);
+            observers.push(observer.clone());
+            observers
+        });
+    }
+
+    /// Get the current metrics of the rate limiter
+    pub fn metrics(&self) -> RateLimiterMetrics {
+        let mut state = self.lock();
+        self.refresh(&mut state.allowance, Instant::now());
+        let available = self.available(&state.allowance);
+        RateLimiterMetrics { available_permits: available, ..state.metrics.clone() }
+    }
+
+    /// Take a permit if one is available right now, without waiting
+    pub fn try_acquire(&self) -> Result<()> {
+        self.try_take(Duration::ZERO).map_err(|retry_after| self.reject(retry_after))
+    }
+
+    /// Take a permit, sleeping the thread for up to `max_wait` until one is available
+    pub fn acquire(&self) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let retry_after = match self.try_take(started.elapsed()) {
+                Ok(()) => return Ok(()),
+                Err(retry_after) => retry_after,
+            };
+            if !self.may_wait(started, retry_after) {
+                return Err(self.reject(retry_after));
+            }
+            thread::sleep(retry_after);
+        }
+    }
+
+    /// Take a permit, suspending the task for up to `max_wait` until one is available
+    #[cfg(feature = "tokio")]
+    pub async fn acquire_async(&self) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            let retry_after = match self.try_take(started.elapsed()) {
+                Ok(()) => return Ok(()),
+                Err(retry_after) => retry_after,
+            };
+            if !self.may_wait(started, retry_after) {
+                return Err(self.reject(retry_after));
+            }
+            tokio::time::sleep(retry_after).await;
+        }
+    }
+
+    /// Execute an operation once the rate limiter permits it
+    pub fn execute<F, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Result<Ret>,
+    {
+        self.acquire()?;
+        operation()
+    }
+
+    /// Execute an async operation once the rate limiter permits it
+    #[cfg(feature = "tokio")]
+    pub async fn execute_async<F, Fut, Ret>(&self, operation: F) -> Result<Ret>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        self.acquire_async().await?;
+        operation().await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    // Take a permit for a caller that has waited `waited`, or say how long until one is free
+    fn try_take(&self, waited: Duration) -> std::result::Result<(), Duration> {
+        let now = Instant::now();
+        let mut state = self.lock();
+        self.refresh(&mut state.allowance, now);
+        let allowance = &mut state.allowance;
+        let taken = match self.config.strategy {
+            RateLimitStrategy::TokenBucket { refill_interval, .. } => {
+                if allowance.tokens >= 1.0 || refill_interval.is_zero() {
+                    allowance.tokens = (allowance.tokens - 1.0).max(0.0);
+                    Ok(())
+                } else {
+                    Err(refill_interval.mul_f64(1.0 - allowance.tokens))
+                }
+            }
+            RateLimitStrategy::FixedWindow { limit, window } => {
+                if allowance.window_count < limit {
+                    allowance.window_count += 1;
+                    Ok(())
+                } else {
+                    Err((allowance.window_start + window).saturating_duration_since(now))
+                }
+            }
+        };
+        // A rejection is counted by `reject`, once the caller stops waiting
+        if let (Err(retry_after), Some(jitter)) = (taken, &self.config.jitter) {
+            let state = &mut *state;
+            state.last_jitter = jitter.apply(retry_after, state.last_jitter, &mut state.jitter_rng);
+            return Err(retry_after + state.last_jitter);
+        }
+        taken?;
+
+        let available = self.available(&state.allowance);
+        let metrics = &mut state.metrics;
+        metrics.total_requests += 1;
+        metrics.permitted_requests += 1;
+        metrics.total_wait += waited;
+        drop(state);
+        for observer in self.observers.load().iter() {
+            observer.on_permitted(&self.name, waited, available);
+        }
+        Ok(())
+    }
+
+    // Add the tokens refilled, or start the window containing `now`
+    fn refresh(&self, allowance: &mut Allowance, now: Instant) {
+        match self.config.strategy {
+            RateLimitStrategy::TokenBucket { capacity, refill_interval } => {
+                if !refill_interval.is_zero() {
+                    let refilled = now.saturating_duration_since(allowance.last_refill).as_secs_f64() / refill_interval.as_secs_f64();
+                    allowance.tokens = (allowance.tokens + refilled).min(f64::from(capacity));
+                }
+                allowance.last_refill = now;
+            }
+            RateLimitStrategy::FixedWindow { window, .. } => {
+                let elapsed = now.saturating_duration_since(allowance.window_start);
+                if elapsed >= window {
+                    let skipped = if window.is_zero() { 1 } else { (elapsed.as_nanos() / window.as_nanos()) as u32 };
+                    allowance.window_start += window * skipped;
+                    allowance.window_count = 0;
+                }
+            }
+        }
+    }
+
+    fn available(&self, allowance: &Allowance) -> u32 {
+        match self.config.strategy {
+            RateLimitStrategy::TokenBucket { .. } => allowance.tokens as u32,
+            RateLimitStrategy::FixedWindow { limit, .. } => limit.saturating_sub(allowance.window_count),
+        }
+    }
+
+    fn may_wait(&self, started: Instant, retry_after: Duration) -> bool {
+        self.config.max_wait.is_some_and(|max_wait| started.elapsed() + retry_after <= max_wait)
+    }
+
+    fn reject(&self, retry_after: Duration) -> AklypseError {
+        let mut state = self.lock();
+        state.metrics.total_requests += 1;
+        state.metrics.rejected_requests += 1;
+        drop(state);
+        for observer in self.observers.load().iter() {
+            observer.on_rejected(&self.name, retry_after);
+        }
+        RateLimitedSnafu { name: self.name.clone(), retry_after }.build()
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("name", &self.name)
+            .field("config", &self.config)
+            .field("metrics", &self.metrics())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingObserver {
+        permitted: AtomicUsize,
+        rejected: AtomicUsize,
+    }
+
+    impl RateLimiterObserver for CountingObserver {
+        fn on_permitted(&self, _name: &str, _waited: Duration, _available: u32) {
+            self.permitted.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_rejected(&self, _name: &str, _retry_after: Duration) {
+            self.rejected.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn limiter(strategy: RateLimitStrategy) -> Arc<RateLimiter> {
+        RateLimiter::new("search", RateLimiterConfig { strategy, ..Default::default() })
+    }
+
+    #[test]
+    fn test_token_bucket_allows_burst_then_rejects() {
+        let limiter = limiter(RateLimitStrategy::TokenBucket { capacity: 3, refill_interval: Duration::from_secs(60) });
+        let observer = Arc::new(CountingObserver::default());
+        limiter.add_observer(observer.clone());
+
+        for _ in 0..3 {
+            limiter.try_acquire().unwrap();
+        }
+        let error = limiter.execute(|| Ok(())).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::ResourceExhaustion);
+        match error {
+            AklypseError::RateLimited { name, retry_after, .. } => {
+                assert_eq!(name, "search");
+                assert!(retry_after > Duration::from_secs(59) && retry_after <= Duration::from_secs(60));
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+
+        let metrics = limiter.metrics();
+        assert_eq!((metrics.total_requests, metrics.permitted_requests, metrics.rejected_requests), (4, 3, 1));
+        assert_eq!(metrics.available_permits, 0);
+        assert_eq!((observer.permitted.load(Ordering::SeqCst), observer.rejected.load(Ordering::SeqCst)), (3, 1));
+    }
+
+    #[test]
+    fn test_fixed_window_resets_after_window() {
+        let limiter = limiter(RateLimitStrategy::FixedWindow { limit: 2, window: Duration::from_millis(30) });
+        limiter.try_acquire().unwrap();
+        limiter.try_acquire().unwrap();
+        let retry_after = match limiter.try_acquire() {
+            Err(AklypseError::RateLimited { retry_after, .. }) => retry_after,
+            other => panic!("expected RateLimited, got {:?}", other),
+        };
+        assert!(retry_after <= Duration::from_millis(30));
+
+        thread::sleep(retry_after);
+        assert!(limiter.try_acquire().is_ok());
+        assert_eq!(limiter.metrics().available_permits, 1);
+    }
+
+    #[test]
+    fn test_acquire_waits_within_max_wait() {
+        let config = RateLimiterConfig {
+            strategy: RateLimitStrategy::TokenBucket { capacity: 1, refill_interval: Duration::from_millis(20) },
+            max_wait: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let limiter = RateLimiter::new("waiting", config);
+        limiter.acquire().unwrap();
+
+        let started = Instant::now();
+        assert_eq!(limiter.execute(|| Ok(5)).unwrap(), 5);
+        assert!(started.elapsed() >= Duration::from_millis(15));
+        let metrics = limiter.metrics();
+        assert_eq!((metrics.permitted_requests, metrics.rejected_requests), (2, 0));
+        assert!(metrics.total_wait > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_retry_after_is_jittered() {
+        use crate::common::utils::jitter::EqualJitter;
+
+        let window = Duration::from_secs(60);
+        let retry_afters = |seed: u64| -> Vec<Duration> {
+            let config = RateLimiterConfig {
+                strategy: RateLimitStrategy::FixedWindow { limit: 1, window },
+                jitter: Some(Arc::new(EqualJitter)),
+                jitter_seed: Some(seed),
+                ..Default::default()
+            };
+            let limiter = RateLimiter::new("jittered", config);
+            limiter.try_acquire().unwrap();
+            (0..3)
+                .map(|_| match limiter.try_acquire() {
+                    Err(AklypseError::RateLimited { retry_after, .. }) => retry_after,
+                    other => panic!("expected RateLimited, got {:?}", other),
+                })
+                .collect()
+        };
+
+        let first = retry_afters(11);
+        for retry_after in &first {
+            // The window's remainder plus between half and all of it again
+            assert!(*retry_after >= window + window / 2 - Duration::from_secs(1) && *retry_after <= window * 2);
+        }
+        assert!(first.windows(2).any(|pair| pair[0].abs_diff(pair[1]) > Duration::from_millis(100)));
+        for (retry_after, same_seed) in first.iter().zip(retry_afters(11)) {
+            assert!(retry_after.abs_diff(same_seed) < Duration::from_millis(100));
+        }
+    }
+}
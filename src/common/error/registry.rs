@@ -0,0 +1,266 @@
+/* src/common/error/registry.rs */
+#![warn(missing_docs)]
+//! **Brief:** Registry of resilience primitives draining them on graceful shutdown.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Graceful Shutdown]
+//!  - [Circuit Breaker Pattern]
+//!  - [Bulkhead Pattern]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `ResilienceRegistry` holds the circuit breakers and bulkheads of an
+//! application so they can be stopped together. `shutdown` first makes each
+//! of them refuse new work with `AklypseError::ShuttingDown`, then waits
+//! until nothing is left in flight: no HalfOpen probe running, no bulkhead
+//! permit held or queued, and no event waiting in a breaker's async observer
+//! queue. If that takes longer than the budget it gives up with
+//! `AklypseError::Timeout`, naming what was still busy under
+//! `SHUTDOWN_PENDING_METADATA_KEY`.
+//!
+//! The registry fits in a `utils::shutdown::ShutdownCoordinator` as one of
+//! its hooks, registered before the hooks of the subsystems it protects.
+
+use super::{Bulkhead, CircuitBreaker, ErrorContext, Result};
+use crate::common::utils::deadline::Deadline;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Metadata key listing the components still busy when a shutdown gave up
+pub const SHUTDOWN_PENDING_METADATA_KEY: &str = "shutdown.pending";
+
+// Interval between two checks of whether everything drained
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Circuit breakers and bulkheads shut down together
+#[derive(Default)]
+pub struct ResilienceRegistry {
+    circuit_breakers: RwLock<Vec<Arc<CircuitBreaker>>>,
+    bulkheads: RwLock<Vec<Arc<Bulkhead>>>,
+    shut_down: AtomicBool,
+}
+
+impl ResilienceRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shut `breaker` down with the registry; it is shut down at once if the registry already is
+    pub fn register_circuit_breaker(&self, breaker: Arc<CircuitBreaker>) {
+        if self.is_shut_down() {
+            breaker.shut_down();
+        }
+        self.circuit_breakers.write().unwrap_or_else(|p| p.into_inner()).push(breaker);
+    }
+
+    /// Shut `bulkhead` down with the registry; it is shut down at once if the registry already is
+    pub fn register_bulkhead(&self, bulkhead: Arc<Bulkhead>) {
+        if self.is_shut_down() {
+            bulkhead.shut_down();
+        }
+        self.bulkheads.write().unwrap_or_else(|p| p.into_inner()).push(bulkhead);
+    }
+
+    /// Whether `shutdown` was called
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::SeqCst)
+    }
+
+    /// Refuse new work everywhere, then block until in-flight work drained or `budget` ran out
+    pub fn shutdown(&self, budget: Duration) -> Result<()> {
+        let deadline = self.begin_shutdown(budget);
+        loop {
+            let pending = self.pending();
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if deadline.is_expired() {
+                return Err(overrun(&deadline, pending));
+            }
+            std::thread::sleep(deadline.remaining().min(DRAIN_POLL_INTERVAL));
+        }
+    }
+
+    /// `shutdown` for async callers, letting the runtime drive the drain meanwhile
+    #[cfg(feature = "tokio")]
+    pub async fn shutdown_async(&self, budget: Duration) -> Result<()> {
+        let deadline = self.begin_shutdown(budget);
+        loop {
+            let pending = self.pending();
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if deadline.is_expired() {
+                return Err(overrun(&deadline, pending));
+            }
+            tokio::time::sleep(deadline.remaining().min(DRAIN_POLL_INTERVAL)).await;
+        }
+    }
+
+    fn begin_shutdown(&self, budget: Duration) -> Deadline {
+        let deadline = Deadline::after("resilience registry shutdown", budget);
+        self.shut_down.store(true, Ordering::SeqCst);
+        for breaker in self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            breaker.shut_down();
+        }
+        for bulkhead in self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            bulkhead.shut_down();
+        }
+        deadline
+    }
+
+    // Components still busy, described for the timeout error
+    fn pending(&self) -> Vec<String> {
+        let mut pending = Vec::new();
+        for breaker in self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            let (probes, events) = (breaker.in_flight_probes(), breaker.pending_observer_events());
+            if probes > 0 || events > 0 {
+                pending.push(format!("circuit breaker '{}' (probes: {}, observer events: {})", breaker.name(), probes, events));
+            }
+        }
+        for bulkhead in self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).iter() {
+            let metrics = bulkhead.metrics();
+            if metrics.in_flight > 0 || metrics.queued > 0 {
+                pending.push(format!("bulkhead '{}' (in flight: {}, queued: {})", bulkhead.name(), metrics.in_flight, metrics.queued));
+            }
+        }
+        pending
+    }
+}
+
+impl fmt::Debug for ResilienceRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResilienceRegistry")
+            .field("circuit_breakers", &self.circuit_breakers.read().unwrap_or_else(|p| p.into_inner()).len())
+            .field("bulkheads", &self.bulkheads.read().unwrap_or_else(|p| p.into_inner()).len())
+            .field("shut_down", &self.is_shut_down())
+            .finish()
+    }
+}
+
+fn overrun(deadline: &Deadline, pending: Vec<String>) -> super::AklypseError {
+    deadline.timeout_error().add_context(
+        ErrorContext::new(format!("{} components did not drain in time", pending.len()))
+            .with_metadata(SHUTDOWN_PENDING_METADATA_KEY, pending.join(", ")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{AklypseError, BulkheadConfig, CircuitBreakerConfig, ErrorCategory};
+    use std::thread;
+
+    fn registry() -> (ResilienceRegistry, Arc<CircuitBreaker>, Arc<Bulkhead>) {
+        let registry = ResilienceRegistry::new();
+        let breaker = CircuitBreaker::new("payments", CircuitBreakerConfig::default());
+        let bulkhead = Bulkhead::new("workers", BulkheadConfig { max_concurrent: 1, max_queued: 1, ..Default::default() });
+        registry.register_circuit_breaker(breaker.clone());
+        registry.register_bulkhead(bulkhead.clone());
+        (registry, breaker, bulkhead)
+    }
+
+    #[test]
+    fn test_shutdown_rejects_new_work() {
+        let (registry, breaker, bulkhead) = registry();
+        assert_eq!(breaker.execute(|| Ok(1)).unwrap(), 1);
+        registry.shutdown(Duration::from_millis(100)).unwrap();
+
+        let error = breaker.execute(|| Ok(1)).unwrap_err();
+        assert!(matches!(&error, AklypseError::ShuttingDown { component, .. } if component == "circuit breaker 'payments'"));
+        assert_eq!(error.category(), ErrorCategory::StateConflict);
+        assert!(matches!(bulkhead.execute(|| Ok(())).unwrap_err(), AklypseError::ShuttingDown { .. }));
+        assert!(breaker.acquire().is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_shutdown_rejects_async_probes() {
+        use crate::common::error::CircuitState;
+
+        let (registry, breaker, _bulkhead) = registry();
+        breaker.trip();
+        registry.shutdown(Duration::from_millis(100)).unwrap();
+
+        let ran = AtomicBool::new(false);
+        let probe = breaker.try_probe_async(|| async {
+            ran.store(true, Ordering::SeqCst);
+            Ok(())
+        });
+        assert!(matches!(probe.await.unwrap_err(), AklypseError::ShuttingDown { .. }));
+        assert!(!ran.load(Ordering::SeqCst));
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_shutdown_waits_for_held_permits() {
+        let (registry, _breaker, bulkhead) = registry();
+        let holder = {
+            let bulkhead = bulkhead.clone();
+            thread::spawn(move || {
+                bulkhead
+                    .execute(|| {
+                        thread::sleep(Duration::from_millis(40));
+                        Ok(())
+                    })
+                    .unwrap()
+            })
+        };
+        while bulkhead.metrics().in_flight == 0 {
+            thread::yield_now();
+        }
+
+        // A queued caller is turned away rather than waited for
+        let queued = {
+            let bulkhead = bulkhead.clone();
+            thread::spawn(move || bulkhead.acquire().map(drop))
+        };
+        while bulkhead.metrics().queued == 0 {
+            thread::yield_now();
+        }
+
+        let error = registry.shutdown(Duration::from_millis(5)).unwrap_err();
+        let pending = error.get_rich_context().unwrap().metadata.get(SHUTDOWN_PENDING_METADATA_KEY).cloned().unwrap();
+        assert!(pending.contains("bulkhead 'workers' (in flight: 1"));
+        assert!(matches!(queued.join().unwrap().unwrap_err(), AklypseError::ShuttingDown { .. }));
+
+        registry.shutdown(Duration::from_secs(1)).unwrap();
+        assert_eq!(bulkhead.metrics().in_flight, 0);
+        holder.join().unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_shutdown_flushes_async_observers() {
+        use crate::common::error::{AsyncCircuitBreakerObserver, CircuitEvent, ObserverFuture};
+        use std::sync::atomic::AtomicUsize;
+
+        #[derive(Default)]
+        struct SlowObserver(AtomicUsize);
+
+        impl AsyncCircuitBreakerObserver for SlowObserver {
+            fn on_event<'a>(&'a self, _name: &'a str, _event: CircuitEvent) -> ObserverFuture<'a> {
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(2)).await;
+                    self.0.fetch_add(1, Ordering::SeqCst);
+                })
+            }
+        }
+
+        let (registry, breaker, _bulkhead) = registry();
+        let observer = Arc::new(SlowObserver::default());
+        breaker.add_async_observer(observer.clone());
+        for _ in 0..5 {
+            breaker.execute(|| Ok(())).unwrap();
+        }
+        registry.shutdown_async(Duration::from_secs(1)).await.unwrap();
+        assert_eq!(observer.0.load(Ordering::SeqCst), 10);
+        assert_eq!(breaker.pending_observer_events(), 0);
+    }
+}
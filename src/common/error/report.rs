@@ -0,0 +1,1241 @@
+/* src/common/error/report.rs */
+#![warn(missing_docs)]
+//! **Brief:** Structured error report representation shared by all report formats.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Report Extraction]
+//!  - [Structured Output]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! An `ErrorReport` holds everything the reporter extracts from an error
+//! (message, cause chain, rich context, backtrace frames, diagnostics and
+//! autocorrections). Format renderers operate on this value, and callers can
+//! inspect it directly when they need the data rather than a rendered string.
+
+use super::decrust::Decrust;
+use super::reporter::ErrorReportConfig;
+use super::schema::{REPORT_SCHEMA_VERSION, SCHEMA_VERSION_KEY};
+use super::types::{Autocorrection, DiagnosticResult, ErrorCategory, ErrorContext, ErrorSeverity, RecoveryAction};
+use super::AklypseError;
+use snafu::ErrorCompat;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Metadata key under which a report id is linked into an error's context
+pub const REPORT_ID_METADATA_KEY: &str = "report_id";
+
+// Crockford base32 alphabet used by the ULID text encoding
+const ULID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const ULID_LEN: usize = 26;
+
+/// Unique identifier of one emitted error report (a ULID)
+///
+/// Report ids sort by creation time, are safe to show to end users as a
+/// "reference code", and can be looked up in a `ReportStore` to retrieve the
+/// full internal report.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ReportId(String);
+
+impl ReportId {
+    /// Generate a new id from the current time and 80 random bits
+    pub fn generate() -> Self {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        Self::from_parts(millis, random_bits())
+    }
+
+    /// Build an id from a millisecond timestamp and random bits (only the low 80 bits are used)
+    pub fn from_parts(timestamp_ms: u64, random: u128) -> Self {
+        let value = ((timestamp_ms as u128 & 0xFFFF_FFFF_FFFF) << 80) | (random & ((1u128 << 80) - 1));
+        let encoded = (0..ULID_LEN)
+            .map(|i| ULID_ALPHABET[((value >> (125 - 5 * i)) & 0x1F) as usize] as char)
+            .collect();
+        Self(encoded)
+    }
+
+    /// Parse an id from its 26 character text form
+    pub fn parse(text: &str) -> Option<Self> {
+        let normalized = text.trim().to_ascii_uppercase();
+        let valid = normalized.len() == ULID_LEN
+            && normalized.bytes().all(|b| ULID_ALPHABET.contains(&b))
+            // The first character only carries three bits
+            && normalized.as_bytes()[0] <= b'7';
+        valid.then_some(Self(normalized))
+    }
+
+    /// Millisecond Unix timestamp encoded in the id
+    pub fn timestamp_ms(&self) -> u64 {
+        let value = self.0.bytes().fold(0u128, |acc, b| {
+            let digit = ULID_ALPHABET.iter().position(|&a| a == b).unwrap_or_default() as u128;
+            (acc << 5) | digit
+        });
+        (value >> 80) as u64
+    }
+
+    /// Text form of the id
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ReportId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// 128 bits of per-process randomness without pulling in an RNG dependency
+fn random_bits() -> u128 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let state = RandomState::new();
+
+    let mut high = state.build_hasher();
+    high.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    let high = high.finish();
+
+    let mut low = state.build_hasher();
+    low.write_u64(high);
+    ((high as u128) << 64) | low.finish() as u128
+}
+
+/// Bounded in-memory index of emitted reports, keyed by `ReportId`
+///
+/// When attached to an `ErrorReporter`, every report fanned out to the sinks
+/// is indexed here so a reference code shown to a user can be traced back to
+/// the full report. The oldest reports are evicted once `capacity` is reached.
+#[derive(Debug)]
+pub struct ReportStore {
+    capacity: usize,
+    inner: Mutex<ReportStoreInner>,
+}
+
+#[derive(Debug, Default)]
+struct ReportStoreInner {
+    reports: HashMap<ReportId, ErrorReport>,
+    order: VecDeque<ReportId>,
+}
+
+impl ReportStore {
+    /// Create a store keeping at most `capacity` reports
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            inner: Mutex::new(ReportStoreInner::default()),
+        }
+    }
+
+    /// Index a report under its id, evicting the oldest report when full
+    pub fn insert(&self, report: ErrorReport) {
+        let mut inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let id = report.report_id.clone();
+
+        if inner.reports.insert(id.clone(), report).is_none() {
+            inner.order.push_back(id);
+        }
+
+        while inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.reports.remove(&oldest);
+            }
+        }
+    }
+
+    /// Look up a report by id
+    pub fn get(&self, id: &ReportId) -> Option<ErrorReport> {
+        let inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        inner.reports.get(id).cloned()
+    }
+
+    /// Number of reports currently indexed
+    pub fn len(&self) -> usize {
+        let inner = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        inner.reports.len()
+    }
+
+    /// Whether the store holds no reports
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A single frame captured from an error backtrace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    /// Symbol name of the frame
+    pub symbol: String,
+    /// Source location (`file:line:column`) if the backtrace resolved one
+    pub location: Option<String>,
+}
+
+impl BacktraceFrame {
+    /// Create a frame without a resolved location
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            location: None,
+        }
+    }
+
+    /// Attach a resolved source location to the frame
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Parse the textual rendering of a backtrace into frames
+    ///
+    /// Understands the standard library layout of numbered symbol lines
+    /// (`  3: my_crate::func`) each optionally followed by an `at file:line`
+    /// line. Disabled or unsupported backtraces yield no frames.
+    pub fn parse_all(rendered: &str) -> Vec<BacktraceFrame> {
+        let mut frames: Vec<BacktraceFrame> = Vec::new();
+
+        for line in rendered.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(location) = line.strip_prefix("at ") {
+                if let Some(frame) = frames.last_mut() {
+                    frame.location = Some(location.to_string());
+                }
+                continue;
+            }
+
+            match line.split_once(": ") {
+                Some((index, symbol)) if index.chars().all(|c| c.is_ascii_digit()) => {
+                    frames.push(BacktraceFrame::new(symbol));
+                }
+                _ => {}
+            }
+        }
+
+        frames
+    }
+}
+
+/// Pulls structured `(key, value)` fields out of errors it recognizes
+///
+/// Extractors are run against the reported error and every error in its
+/// cause chain, and typically downcast to a known third-party type (a
+/// database error code, an HTTP status) returning nothing for anything else.
+pub type FieldExtractor = fn(&(dyn std::error::Error + 'static)) -> Vec<(String, String)>;
+
+/// Extractor reporting the kind and OS error code of `std::io::Error`s
+pub fn io_error_fields(error: &(dyn std::error::Error + 'static)) -> Vec<(String, String)> {
+    let Some(io_error) = error.downcast_ref::<std::io::Error>() else {
+        return Vec::new();
+    };
+
+    let mut fields = vec![("io.kind".to_string(), format!("{:?}", io_error.kind()))];
+    if let Some(code) = io_error.raw_os_error() {
+        fields.push(("io.os_error".to_string(), code.to_string()));
+    }
+    fields
+}
+
+/// Structured, format-independent contents of an error report
+///
+/// Produced by `ErrorReporter::build_report`. Which parts are populated is
+/// decided by the `ErrorReportConfig` used to build the report, so renderers
+/// simply render whatever is present.
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    /// Unique id of this report
+    pub report_id: ReportId,
+    /// Display message of the reported error
+    pub message: String,
+    /// Category of the error, when the error is an `AklypseError`
+    pub category: Option<ErrorCategory>,
+    /// Severity of the error (`ErrorSeverity::Error` for foreign error types)
+    pub severity: ErrorSeverity,
+    /// Messages of the cause chain, outermost cause first
+    pub chain: Vec<String>,
+    /// Whether the cause chain was cut short by `max_chain_depth`
+    pub chain_truncated: bool,
+    /// Fields produced by the configured `FieldExtractor`s, in chain order
+    pub fields: Vec<(String, String)>,
+    /// Rich context attached to the error, if any
+    pub context: Option<ErrorContext>,
+    /// Frames of the captured backtrace
+    pub backtrace_frames: Vec<BacktraceFrame>,
+    /// Diagnostic information embedded in the error
+    pub diagnostics: Option<DiagnosticResult>,
+    /// Proposed autocorrections for the error
+    pub autocorrections: Vec<Autocorrection>,
+}
+
+impl ErrorReport {
+    /// Create an empty report carrying only a message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            report_id: ReportId::generate(),
+            message: message.into(),
+            category: None,
+            severity: ErrorSeverity::Error,
+            chain: Vec::new(),
+            chain_truncated: false,
+            fields: Vec::new(),
+            context: None,
+            backtrace_frames: Vec::new(),
+            diagnostics: None,
+            autocorrections: Vec::new(),
+        }
+    }
+
+    /// Extract a report from any error, honouring the `include_*` switches of `config`
+    ///
+    /// `AklypseError`s (found by downcasting) additionally contribute their
+    /// category, severity, rich context, diagnostics and backtrace.
+    pub fn from_error<E>(error: &E, config: &ErrorReportConfig) -> Self
+    where
+        E: std::error::Error + 'static,
+    {
+        let mut report = Self::new(error.to_string());
+
+        if config.include_source_chain {
+            let mut source = AklypseError::source_of(error);
+            while let Some(err) = source {
+                if let Some(max_depth) = config.max_chain_depth {
+                    if report.chain.len() >= max_depth {
+                        report.chain_truncated = true;
+                        break;
+                    }
+                }
+                report.chain.push(err.to_string());
+                source = AklypseError::source_of(err);
+            }
+        }
+
+        let error_ref: &(dyn std::error::Error + 'static) = error;
+        if !config.field_extractors.is_empty() {
+            let mut current = Some(error_ref);
+            let mut depth = 0;
+            while let Some(err) = current {
+                for extractor in &config.field_extractors {
+                    report.fields.extend(extractor(err));
+                }
+                depth += 1;
+                if config.max_chain_depth.is_some_and(|max_depth| depth > max_depth) {
+                    break;
+                }
+                current = AklypseError::source_of(err);
+            }
+        }
+
+        if let Some(aklypse_error) = error_ref.downcast_ref::<AklypseError>() {
+            report.category = Some(aklypse_error.category());
+            if config.include_severity {
+                report.severity = aklypse_error.severity();
+            }
+
+            if let Some(context) = aklypse_error.get_rich_context() {
+                if config.include_diagnostics {
+                    report.diagnostics = context.diagnostic_info.as_deref().cloned();
+                }
+                if config.include_rich_context {
+                    let mut context = context.clone();
+                    if !config.include_source_location {
+                        context.source_location = None;
+                    }
+                    report.context = Some(context);
+                }
+            }
+
+            if config.include_autocorrections {
+                report.autocorrections = Decrust::new().suggest_autocorrections(aklypse_error, None);
+            }
+
+            if config.include_backtrace {
+                if let Some(backtrace) = ErrorCompat::backtrace(aklypse_error) {
+                    report.backtrace_frames = BacktraceFrame::parse_all(&backtrace.to_string());
+                }
+            }
+        }
+
+        if config.deterministic {
+            report.make_deterministic();
+        }
+
+        report
+    }
+
+    /// Replace the generated id, e.g. to share one id across several renderings
+    pub fn with_report_id(mut self, report_id: ReportId) -> Self {
+        self.report_id = report_id;
+        self
+    }
+
+    /// Attach proposed autocorrections to the report
+    pub fn with_autocorrections(mut self, autocorrections: Vec<Autocorrection>) -> Self {
+        self.autocorrections = autocorrections;
+        self
+    }
+
+    /// Replace volatile details with stable placeholders for snapshot tests
+    ///
+    /// The report id becomes the all-zero id, context timestamps are dropped,
+    /// absolute paths keep only their file name (`<abs>/main.rs`), and
+    /// backtrace addresses and symbol hashes are masked.
+    pub fn make_deterministic(&mut self) {
+        self.report_id = ReportId::from_parts(0, 0);
+        self.message = mask_absolute_paths(&self.message);
+        for cause in &mut self.chain {
+            *cause = mask_absolute_paths(cause);
+        }
+
+        if let Some(context) = &mut self.context {
+            context.timestamp = None;
+            context.message = mask_absolute_paths(&context.message).into();
+            for value in context.metadata_mut().values_mut() {
+                *value = mask_absolute_paths(value);
+            }
+            if let Some(location) = &mut context.source_location {
+                location.file = mask_absolute_paths(&location.file);
+            }
+        }
+
+        if let Some(diagnostics) = &mut self.diagnostics {
+            if let Some(location) = &mut diagnostics.primary_location {
+                location.file = mask_absolute_paths(&location.file);
+            }
+            for expansion in &mut diagnostics.expansion_trace {
+                expansion.expansion_site.file = mask_absolute_paths(&expansion.expansion_site.file);
+            }
+        }
+
+        for frame in &mut self.backtrace_frames {
+            frame.symbol = mask_symbol_hash(&mask_addresses(&frame.symbol));
+            if let Some(location) = &frame.location {
+                frame.location = Some(mask_absolute_paths(location));
+            }
+        }
+    }
+
+    /// Short error code: the diagnostic code when present, otherwise derived from the category
+    pub fn code(&self) -> Option<String> {
+        self.diagnostics
+            .as_ref()
+            .and_then(|diagnostics| diagnostics.diagnostic_code.clone())
+            .or_else(|| self.category.map(|category| format!("AKL-{:?}", category).to_ascii_uppercase()))
+    }
+
+    /// Stable fingerprint grouping reports of the "same" error
+    ///
+    /// Hashes the category, message and cause chain with every run of digits
+    /// collapsed, so ids, counts and ports embedded in messages do not split
+    /// otherwise identical errors into separate groups. The value is stable
+    /// across processes and releases.
+    pub fn fingerprint(&self) -> String {
+        // 64-bit FNV-1a; std's hashers are randomly seeded per process
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |text: &str| {
+            let mut in_digits = false;
+            for byte in text.bytes() {
+                let byte = if byte.is_ascii_digit() {
+                    if in_digits {
+                        continue;
+                    }
+                    in_digits = true;
+                    b'#'
+                } else {
+                    in_digits = false;
+                    byte
+                };
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+            hash ^= 0xff;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        };
+
+        feed(&self.category.map(|category| format!("{:?}", category)).unwrap_or_default());
+        feed(&self.message);
+        for cause in &self.chain {
+            feed(cause);
+        }
+
+        format!("{:016x}", hash)
+    }
+
+    /// When the error was observed: the context timestamp, falling back to the report id's
+    pub fn timestamp(&self) -> SystemTime {
+        self.context
+            .as_ref()
+            .and_then(|context| context.timestamp)
+            .unwrap_or_else(|| UNIX_EPOCH + Duration::from_millis(self.report_id.timestamp_ms()))
+    }
+
+    /// Whether the report was extracted from an `AklypseError`
+    pub fn is_aklypse_error(&self) -> bool {
+        self.category.is_some()
+    }
+
+    /// Render the report as a JSON document
+    pub fn to_json(&self, pretty: bool) -> String {
+        self.to_json_value().render(pretty)
+    }
+
+    pub(crate) fn to_json_value(&self) -> JsonValue {
+        let mut fields = vec![
+            (SCHEMA_VERSION_KEY.to_string(), JsonValue::Number(REPORT_SCHEMA_VERSION.to_string())),
+            ("report_id".to_string(), JsonValue::string(self.report_id.as_str())),
+            ("error".to_string(), JsonValue::string(&self.message)),
+        ];
+
+        if let Some(category) = self.category {
+            fields.push(("category".to_string(), JsonValue::string(format!("{:?}", category))));
+        }
+        fields.push(("severity".to_string(), JsonValue::string(format!("{:?}", self.severity))));
+
+        if !self.chain.is_empty() {
+            fields.push((
+                "chain".to_string(),
+                JsonValue::Array(self.chain.iter().map(JsonValue::string).collect()),
+            ));
+            if self.chain_truncated {
+                fields.push(("chain_truncated".to_string(), JsonValue::Bool(true)));
+            }
+        }
+
+        if !self.fields.is_empty() {
+            fields.push((
+                "fields".to_string(),
+                JsonValue::Object(
+                    self.fields
+                        .iter()
+                        .map(|(key, value)| (key.clone(), JsonValue::string(value)))
+                        .collect(),
+                ),
+            ));
+        }
+
+        if let Some(context) = &self.context {
+            fields.push(("context".to_string(), context_to_json(context)));
+        }
+
+        if let Some(diagnostics) = &self.diagnostics {
+            fields.push(("diagnostics".to_string(), diagnostics_to_json(diagnostics)));
+        }
+
+        if !self.autocorrections.is_empty() {
+            fields.push((
+                "autocorrections".to_string(),
+                JsonValue::Array(self.autocorrections.iter().map(autocorrection_to_json).collect()),
+            ));
+        }
+
+        if !self.backtrace_frames.is_empty() {
+            fields.push((
+                "backtrace".to_string(),
+                JsonValue::Array(
+                    self.backtrace_frames
+                        .iter()
+                        .map(|frame| {
+                            JsonValue::Object(vec![
+                                ("symbol".to_string(), JsonValue::string(&frame.symbol)),
+                                ("location".to_string(), JsonValue::optional_string(frame.location.as_ref())),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ));
+        }
+
+        JsonValue::Object(fields)
+    }
+}
+
+fn context_to_json(context: &ErrorContext) -> JsonValue {
+    let mut fields = vec![
+        ("message".to_string(), JsonValue::string(&context.message)),
+        ("severity".to_string(), JsonValue::string(format!("{:?}", context.severity))),
+    ];
+
+    if let Some(public_message) = &context.public_message {
+        fields.push(("public_message".to_string(), JsonValue::string(public_message)));
+    }
+    if let Some(key) = &context.message_key {
+        fields.push((
+            "message_key".to_string(),
+            JsonValue::Object(vec![
+                ("key".to_string(), JsonValue::string(&key.key)),
+                (
+                    "args".to_string(),
+                    JsonValue::Object(key.args.iter().map(|(k, v)| (k.clone(), JsonValue::string(v))).collect()),
+                ),
+            ]),
+        ));
+    }
+
+    if let Some(component) = &context.component {
+        fields.push(("component".to_string(), JsonValue::string(component)));
+    }
+    if let Some(correlation_id) = &context.correlation_id {
+        fields.push(("correlation_id".to_string(), JsonValue::string(correlation_id)));
+    }
+    if let Some(timestamp) = context.timestamp {
+        fields.push(("timestamp".to_string(), JsonValue::string(format_rfc3339(timestamp))));
+    }
+    if let Some(suggestion) = &context.recovery_suggestion {
+        fields.push(("recovery_suggestion".to_string(), JsonValue::string(suggestion)));
+    }
+    if !context.recovery_actions.is_empty() {
+        fields.push((
+            "recovery_actions".to_string(),
+            JsonValue::Array(
+                context
+                    .recovery_actions
+                    .iter()
+                    .map(|action| {
+                        let value = match action {
+                            RecoveryAction::RetryAfter(_) => JsonValue::Number(action.value()),
+                            _ => JsonValue::string(action.value()),
+                        };
+                        JsonValue::Object(vec![
+                            ("kind".to_string(), JsonValue::string(action.kind())),
+                            ("value".to_string(), value),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ));
+    }
+    if let Some(location) = &context.source_location {
+        fields.push((
+            "source_location".to_string(),
+            JsonValue::Object(vec![
+                ("file".to_string(), JsonValue::string(&location.file)),
+                ("line".to_string(), JsonValue::Number(location.line.to_string())),
+                ("module_path".to_string(), JsonValue::string(&location.module_path)),
+            ]),
+        ));
+    }
+    if !context.tags.is_empty() {
+        fields.push((
+            "tags".to_string(),
+            JsonValue::Array(context.tags.iter().map(JsonValue::string).collect()),
+        ));
+    }
+    if !context.metadata.is_empty() {
+        // Sorted so identical contexts always render identically
+        let mut entries: Vec<_> = context.metadata.iter().collect();
+        entries.sort();
+        fields.push((
+            "metadata".to_string(),
+            JsonValue::Object(entries.into_iter().map(|(k, v)| (k.clone(), JsonValue::string(v))).collect()),
+        ));
+    }
+
+    JsonValue::Object(fields)
+}
+
+fn diagnostics_to_json(diagnostics: &DiagnosticResult) -> JsonValue {
+    let mut fields = Vec::new();
+
+    if let Some(code) = &diagnostics.diagnostic_code {
+        fields.push(("code".to_string(), JsonValue::string(code)));
+    }
+    if let Some(message) = &diagnostics.original_message {
+        fields.push(("message".to_string(), JsonValue::string(message)));
+    }
+    if let Some(location) = &diagnostics.primary_location {
+        fields.push((
+            "location".to_string(),
+            JsonValue::Object(vec![
+                ("file".to_string(), JsonValue::string(&location.file)),
+                ("line".to_string(), JsonValue::Number(location.line.to_string())),
+                ("column".to_string(), JsonValue::Number(location.column.to_string())),
+            ]),
+        ));
+    }
+    if !diagnostics.suggested_fixes.is_empty() {
+        fields.push((
+            "suggested_fixes".to_string(),
+            JsonValue::Array(diagnostics.suggested_fixes.iter().map(JsonValue::string).collect()),
+        ));
+    }
+
+    JsonValue::Object(fields)
+}
+
+fn autocorrection_to_json(autocorrection: &Autocorrection) -> JsonValue {
+    let mut fields = vec![
+        ("description".to_string(), JsonValue::string(&autocorrection.description)),
+        ("fix_type".to_string(), JsonValue::string(format!("{:?}", autocorrection.fix_type))),
+        ("confidence".to_string(), JsonValue::Number(format!("{:.2}", autocorrection.confidence))),
+    ];
+
+    if let Some(diff) = &autocorrection.diff_suggestion {
+        fields.push(("diff".to_string(), JsonValue::string(diff)));
+    }
+    if !autocorrection.commands_to_apply.is_empty() {
+        fields.push((
+            "commands".to_string(),
+            JsonValue::Array(autocorrection.commands_to_apply.iter().map(JsonValue::string).collect()),
+        ));
+    }
+    if let Some(code) = &autocorrection.targets_error_code {
+        fields.push(("targets_error_code".to_string(), JsonValue::string(code)));
+    }
+
+    JsonValue::Object(fields)
+}
+
+/// Minimal JSON document model used by the structured report formats
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonValue {
+    Null,
+    Bool(bool),
+    /// Pre-formatted numeric literal
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Object with insertion-ordered fields
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    pub(crate) fn string(value: impl AsRef<str>) -> Self {
+        JsonValue::String(value.as_ref().to_string())
+    }
+
+    pub(crate) fn optional_string(value: Option<impl AsRef<str>>) -> Self {
+        value.map_or(JsonValue::Null, JsonValue::string)
+    }
+
+    /// Field of an object, if this is an object holding `key`
+    pub(crate) fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(fields) => fields.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            JsonValue::Number(value) => value.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Items of an array; any other value yields no items
+    pub(crate) fn items(&self) -> &[JsonValue] {
+        match self {
+            JsonValue::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// Parse a JSON document; numbers keep their literal text
+    pub(crate) fn parse(text: &str) -> std::result::Result<JsonValue, String> {
+        let mut parser = JsonParser { text, pos: 0 };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.pos != text.len() {
+            return Err(format!("trailing characters at offset {}", parser.pos));
+        }
+        Ok(value)
+    }
+
+    /// Render the value, optionally pretty-printed with two-space indentation
+    pub(crate) fn render(&self, pretty: bool) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out, pretty, 0);
+        out
+    }
+
+    fn render_into(&self, out: &mut String, pretty: bool, depth: usize) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            JsonValue::Number(value) => out.push_str(value),
+            JsonValue::String(value) => escape_json_into(out, value),
+            JsonValue::Array(items) => {
+                if items.is_empty() {
+                    out.push_str("[]");
+                    return;
+                }
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    push_newline(out, pretty, depth + 1);
+                    item.render_into(out, pretty, depth + 1);
+                }
+                push_newline(out, pretty, depth);
+                out.push(']');
+            }
+            JsonValue::Object(fields) => {
+                if fields.is_empty() {
+                    out.push_str("{}");
+                    return;
+                }
+                out.push('{');
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    push_newline(out, pretty, depth + 1);
+                    escape_json_into(out, key);
+                    out.push_str(if pretty { ": " } else { ":" });
+                    value.render_into(out, pretty, depth + 1);
+                }
+                push_newline(out, pretty, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn push_newline(out: &mut String, pretty: bool, depth: usize) {
+    if pretty {
+        out.push('\n');
+        for _ in 0..depth {
+            out.push_str("  ");
+        }
+    }
+}
+
+// Recursive-descent parser behind `JsonValue::parse`
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> std::result::Result<(), String> {
+        self.skip_whitespace();
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at offset {}", byte as char, self.pos))
+        }
+    }
+
+    fn parse_value(&mut self) -> std::result::Result<JsonValue, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => {
+                for (literal, value) in [("null", JsonValue::Null), ("true", JsonValue::Bool(true)), ("false", JsonValue::Bool(false))] {
+                    if self.text[self.pos..].starts_with(literal) {
+                        self.pos += literal.len();
+                        return Ok(value);
+                    }
+                }
+                Err(format!("unexpected character at offset {}", self.pos))
+            }
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+
+    fn parse_object(&mut self) -> std::result::Result<JsonValue, String> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            fields.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(fields));
+                }
+                _ => return Err(format!("expected ',' or '}}' at offset {}", self.pos)),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> std::result::Result<JsonValue, String> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(format!("expected ',' or ']' at offset {}", self.pos)),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> std::result::Result<JsonValue, String> {
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let literal = &rest[..len];
+        if literal.parse::<f64>().is_err() {
+            return Err(format!("invalid number at offset {}", start));
+        }
+        self.pos += len;
+        Ok(JsonValue::Number(literal.to_string()))
+    }
+
+    fn parse_string(&mut self) -> std::result::Result<String, String> {
+        if self.peek() != Some(b'"') {
+            return Err(format!("expected string at offset {}", self.pos));
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let mut chars = rest.chars();
+            let c = chars.next().ok_or("unterminated string")?;
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escape = chars.next().ok_or("unterminated escape")?;
+                    self.pos += 1;
+                    match escape {
+                        '"' => out.push('"'),
+                        '\\' => out.push('\\'),
+                        '/' => out.push('/'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'u' => out.push(self.parse_unicode_escape()?),
+                        other => return Err(format!("invalid escape '\\{}'", other)),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    // Decode the hex digits after `\u`, joining surrogate pairs
+    fn parse_unicode_escape(&mut self) -> std::result::Result<char, String> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) && self.text[self.pos..].starts_with("\\u") {
+            self.pos += 2;
+            let low = self.parse_hex4()?;
+            0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+        } else {
+            high
+        };
+        Ok(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+
+    fn parse_hex4(&mut self) -> std::result::Result<u32, String> {
+        let digits = self.text.get(self.pos..self.pos + 4).ok_or("truncated \\u escape")?;
+        let value = u32::from_str_radix(digits, 16).map_err(|_| format!("invalid \\u escape at offset {}", self.pos))?;
+        self.pos += 4;
+        Ok(value)
+    }
+}
+
+// Replace absolute paths (`/home/ci/src/main.rs`, `C:\\src\\main.rs`) with `<abs>/main.rs`
+fn mask_absolute_paths(text: &str) -> String {
+    let is_boundary = |c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '(' | ')' | '[' | ']' | ',' | '=');
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let at_token_start = out.chars().last().is_none_or(is_boundary);
+        let bytes = rest.as_bytes();
+        let is_unix = bytes[0] == b'/';
+        let is_windows = bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'\\' | b'/');
+
+        if at_token_start && (is_unix || is_windows) {
+            let skip = if is_windows { 2 } else { 0 };
+            let end = rest[skip..]
+                .find(|c: char| is_boundary(c) || c == ':')
+                .map_or(rest.len(), |index| index + skip);
+            let path = &rest[..end];
+            let file_name = path.rsplit(['/', '\\']).next().unwrap_or_default();
+            // A lone "/" or "a/b" inside prose is not worth masking
+            if path.len() > 1 && path[skip + 1..].contains(['/', '\\']) {
+                out.push_str("<abs>/");
+                out.push_str(file_name);
+            } else {
+                out.push_str(path);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        let next = rest.chars().next().unwrap_or_default();
+        out.push(next);
+        rest = &rest[next.len_utf8()..];
+    }
+
+    out
+}
+
+// Replace hexadecimal addresses (`0x7ffd5a1c`) with `0x<addr>`
+fn mask_addresses(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find("0x") {
+        out.push_str(&rest[..index]);
+        let digits = rest[index + 2..].bytes().take_while(u8::is_ascii_hexdigit).count();
+        if digits > 0 {
+            out.push_str("0x<addr>");
+        } else {
+            out.push_str("0x");
+        }
+        rest = &rest[index + 2 + digits..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+// Drop the `::h0123456789abcdef` disambiguator rustc appends to legacy mangled symbols
+fn mask_symbol_hash(symbol: &str) -> String {
+    match symbol.rsplit_once("::h") {
+        Some((path, hash)) if hash.len() == 16 && hash.bytes().all(|b| b.is_ascii_hexdigit()) => path.to_string(),
+        _ => symbol.to_string(),
+    }
+}
+
+/// Format a point in time as an RFC 3339 UTC timestamp with millisecond precision
+pub fn format_rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil-from-days (H. Hinnant), valid for every date after 1970-01-01
+    let z = days as i64 + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+fn escape_json_into(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::types::ErrorContext;
+    use super::super::{InternalSnafu, WithRichContextSnafu};
+
+    #[test]
+    fn test_json_parse_round_trip() {
+        let text = r#"{"a": [1, -2.5e3, true, null], "b": "tab\tq\"\u00e9\ud83d\ude00", "c": {}}"#;
+        let value = JsonValue::parse(text).unwrap();
+
+        assert_eq!(value.render(false), "{\"a\":[1,-2.5e3,true,null],\"b\":\"tab\\tq\\\"é😀\",\"c\":{}}");
+        assert!(JsonValue::parse("{\"a\": 1} x").is_err());
+        assert!(JsonValue::parse("[1,]").is_err());
+    }
+
+    #[test]
+    fn test_backtrace_frame_parsing() {
+        let rendered = "   0: my_crate::load\n             at ./src/load.rs:10:5\n   1: main\n";
+        let frames = BacktraceFrame::parse_all(rendered);
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].symbol, "my_crate::load");
+        assert_eq!(frames[0].location, Some("./src/load.rs:10:5".to_string()));
+        assert_eq!(frames[1].location, None);
+        assert!(BacktraceFrame::parse_all("disabled backtrace").is_empty());
+    }
+
+    #[test]
+    fn test_report_extracts_rich_context() {
+        let error = WithRichContextSnafu {
+            context: ErrorContext::new("Loading settings")
+                .with_severity(ErrorSeverity::Critical)
+                .with_component("config"),
+            source: Box::new(InternalSnafu {
+                message: "boom".to_string(),
+                source: None,
+            }.build()),
+        }.build();
+
+        let report = ErrorReport::from_error(&error, &ErrorReportConfig::default());
+
+        assert!(report.is_aklypse_error());
+        assert_eq!(report.category, Some(ErrorCategory::Internal));
+        assert_eq!(report.severity, ErrorSeverity::Critical);
+        assert_eq!(report.context.as_ref().and_then(|c| c.component.as_deref()), Some("config"));
+        assert_eq!(report.chain.len(), 1);
+    }
+
+    #[test]
+    fn test_json_renders_recovery_actions() {
+        let mut report = ErrorReport::new("rate limited");
+        report.context = Some(
+            ErrorContext::new("calling billing")
+                .with_recovery_action(RecoveryAction::RetryAfter(Duration::from_millis(250)))
+                .with_recovery_action(RecoveryAction::ContactService("billing-oncall".to_string())),
+        );
+
+        let json = JsonValue::parse(&report.to_json(false)).unwrap();
+        let actions = json.get("context").and_then(|c| c.get("recovery_actions")).unwrap().items();
+        assert_eq!(actions[0].get("kind").and_then(JsonValue::as_str), Some("retry_after"));
+        assert_eq!(actions[0].get("value").and_then(JsonValue::as_u64), Some(250));
+        assert_eq!(actions[1].get("value").and_then(JsonValue::as_str), Some("billing-oncall"));
+    }
+
+    #[test]
+    fn test_json_rendering_escapes_strings() {
+        let id = ReportId::from_parts(0, 0);
+        let report = ErrorReport::new("bad \"quote\"\nline").with_report_id(id);
+        let json = report.to_json(false);
+
+        assert_eq!(
+            json,
+            "{\"schema_version\":4,\"report_id\":\"00000000000000000000000000\",\"error\":\"bad \\\"quote\\\"\\nline\",\"severity\":\"Error\"}"
+        );
+    }
+
+    #[test]
+    fn test_report_id_round_trip() {
+        let id = ReportId::from_parts(1_700_000_000_000, 42);
+        assert_eq!(id.as_str().len(), 26);
+        assert_eq!(id.timestamp_ms(), 1_700_000_000_000);
+        assert_eq!(ReportId::parse(&id.as_str().to_lowercase()), Some(id));
+        assert_eq!(ReportId::parse("not-a-ulid"), None);
+
+        // Generated ids are unique
+        assert_ne!(ReportId::generate(), ReportId::generate());
+    }
+
+    #[test]
+    fn test_report_store_evicts_oldest() {
+        let store = ReportStore::new(2);
+        let first = ErrorReport::new("first");
+        let first_id = first.report_id.clone();
+        store.insert(first);
+        store.insert(ErrorReport::new("second"));
+        store.insert(ErrorReport::new("third"));
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get(&first_id).is_none());
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(format_rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let time = UNIX_EPOCH + Duration::from_millis(1_709_210_096_789);
+        assert_eq!(format_rfc3339(time), "2024-02-29T12:34:56.789Z");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_numbers() {
+        let mut first = ErrorReport::new("connection to 10.0.0.1:5432 refused after 3 attempts");
+        first.category = Some(ErrorCategory::Network);
+        let mut second = ErrorReport::new("connection to 10.0.0.7:6543 refused after 12 attempts");
+        second.category = Some(ErrorCategory::Network);
+
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(first.fingerprint().len(), 16);
+        assert_ne!(first.fingerprint(), ErrorReport::new("connection refused").fingerprint());
+        assert_eq!(first.code().as_deref(), Some("AKL-NETWORK"));
+    }
+
+    #[test]
+    fn test_make_deterministic() {
+        let mut context = ErrorContext::new("reading /home/ci/work/app/config.toml")
+            .with_metadata("path", "C:\\Users\\ci\\app\\config.toml");
+        context.timestamp = Some(SystemTime::now());
+
+        let mut report = ErrorReport::new("failed to open /tmp/build-42/data.bin: not found");
+        report.chain.push("a/b relative path stays".to_string());
+        report.context = Some(context);
+        report.backtrace_frames = vec![
+            BacktraceFrame::new("app::main::h0123456789abcdef").with_location("/home/ci/work/app/src/main.rs:10:5"),
+            BacktraceFrame::new("0x7ffd5a1c - <unknown>"),
+        ];
+        report.make_deterministic();
+
+        assert_eq!(report.report_id.as_str(), "00000000000000000000000000");
+        assert_eq!(report.message, "failed to open <abs>/data.bin: not found");
+        assert_eq!(report.chain[0], "a/b relative path stays");
+        let context = report.context.as_ref().unwrap();
+        assert!(context.timestamp.is_none());
+        assert_eq!(&*context.message, "reading <abs>/config.toml");
+        assert_eq!(context.metadata["path"], "<abs>/config.toml");
+        assert_eq!(report.backtrace_frames[0].symbol, "app::main");
+        assert_eq!(report.backtrace_frames[0].location.as_deref(), Some("<abs>/main.rs:10:5"));
+        assert_eq!(report.backtrace_frames[1].symbol, "0x<addr> - <unknown>");
+    }
+}
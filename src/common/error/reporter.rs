@@ -12,22 +12,137 @@
 // **Author:** Lord Xyn
 // **License:** MIT
 
-use super::types::{ErrorReportFormat, ErrorSeverity};
+use super::labels::{LabelCatalog, ReportLabel, DEFAULT_LOCALE};
+use super::report::{format_rfc3339, ErrorReport, FieldExtractor, ReportId, ReportStore};
+use super::theme::{visible_width, Painter, Theme};
+use super::types::{ErrorContext, ErrorReportFormat, ErrorSeverity};
+use super::{AklypseError, IoSnafu, MultipleErrorsSnafu, Result};
+use std::fmt;
+use std::fs::{File, OpenOptions};
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use tracing::warn;
 
 /// Configuration for the error reporter
 #[derive(Debug, Clone)]
 pub struct ErrorReportConfig {
+    /// Render the error's own message
     pub include_message: bool,
+    /// Render the messages of the errors it wraps
     pub include_source_chain: bool,
+    /// Render the captured backtrace
     pub include_backtrace: bool,
+    /// Render the `ErrorContext` of `AklypseError`s
     pub include_rich_context: bool,
+    /// Render where the context was created
     pub include_source_location: bool,
+    /// Render the error's severity
     pub include_severity: bool,
+    /// Output format of the report
     pub format: ErrorReportFormat,
+    /// Maximum number of causes rendered; `None` renders the whole chain
     pub max_chain_depth: Option<usize>,
+    /// Indent JSON output
     pub pretty_print_json: bool,
+    /// Render the diagnostics attached to the context
     pub include_diagnostics: bool,
+    /// Run Decrust on `AklypseError`s and render its suggested fixes
+    pub include_autocorrections: bool,
+    /// Extractors pulling structured fields out of the error and its causes
+    pub field_extractors: Vec<FieldExtractor>,
+    /// Locale for the fixed labels of human-readable formats (`None` renders English)
+    pub locale: Option<String>,
+    /// Catalog consulted before the built-in translations
+    pub label_catalog: Option<Arc<LabelCatalog>>,
+    /// Replace ids, timestamps, absolute paths and addresses with stable
+    /// placeholders so output can be snapshot-tested
+    pub deterministic: bool,
+    /// How timestamps in context, summaries and events are written
+    pub timestamp_format: TimestampFormat,
+    /// Time zone of RFC 3339 timestamps
+    pub timestamp_zone: TimestampZone,
+    /// Layout of the rendered report
+    pub style: ReportStyle,
+    /// Colors and glyphs for Terminal output (defaults to `Theme::classic`) and,
+    /// when set, inline styles for HTML output
+    pub theme: Option<Theme>,
+    /// Line wrapping applied to the Plain and Terminal formats
+    pub wrap: WrapMode,
+}
+
+/// Overall layout of a rendered report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportStyle {
+    /// Multi-line report with one section per kind of information
+    #[default]
+    Full,
+    /// Single `key=value` line summarizing the error and its cause chain,
+    /// intended for log shippers. JSON output is emitted on one line.
+    Compact,
+}
+
+/// Line wrapping for human-readable text output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// Never wrap lines
+    #[default]
+    None,
+    /// Wrap lines to the given number of columns
+    Width(usize),
+    /// Wrap to the width of the attached terminal, or not at all when output is not a TTY
+    Terminal,
+}
+
+/// How timestamps are written in reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    /// RFC 3339 with millisecond precision (`2025-03-01T12:00:00.000Z`)
+    #[default]
+    Rfc3339,
+    /// Seconds since the Unix epoch
+    UnixSeconds,
+    /// Milliseconds since the Unix epoch
+    UnixMillis,
+    /// Age relative to now (`3m ago`) in Terminal output, RFC 3339 elsewhere
+    Relative,
+}
+
+/// Time zone used for RFC 3339 timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampZone {
+    /// Coordinated Universal Time
+    #[default]
+    Utc,
+    /// The local time zone of the host
+    #[cfg(feature = "chrono")]
+    Local,
+}
+
+impl WrapMode {
+    // Column used when a terminal is detected but reports no width
+    const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+    /// Effective wrap width, if wrapping applies
+    pub fn resolve(self) -> Option<usize> {
+        match self {
+            WrapMode::None => None,
+            WrapMode::Width(width) => Some(width.max(1)),
+            WrapMode::Terminal => {
+                use std::io::IsTerminal;
+                if !io::stdout().is_terminal() && !io::stderr().is_terminal() {
+                    return None;
+                }
+                let width = std::env::var("COLUMNS")
+                    .ok()
+                    .and_then(|columns| columns.trim().parse::<usize>().ok())
+                    .filter(|&columns| columns > 0)
+                    .unwrap_or(Self::DEFAULT_TERMINAL_WIDTH);
+                Some(width)
+            }
+        }
+    }
 }
 
 impl Default for ErrorReportConfig {
@@ -43,17 +158,649 @@ impl Default for ErrorReportConfig {
             max_chain_depth: None,
             pretty_print_json: true,
             include_diagnostics: true,
+            include_autocorrections: false,
+            deterministic: false,
+            field_extractors: Vec::new(),
+            locale: None,
+            label_catalog: None,
+            timestamp_format: TimestampFormat::Rfc3339,
+            timestamp_zone: TimestampZone::Utc,
+            style: ReportStyle::Full,
+            theme: None,
+            wrap: WrapMode::None,
+        }
+    }
+}
+
+impl ErrorReportConfig {
+    /// Render human-readable labels in the given locale
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Render reports as a single `key=value` line
+    pub fn compact(mut self) -> Self {
+        self.style = ReportStyle::Compact;
+        self
+    }
+
+    /// Register an extractor run against every error in the chain
+    pub fn with_field_extractor(mut self, extractor: FieldExtractor) -> Self {
+        self.field_extractors.push(extractor);
+        self
+    }
+
+    /// Produce stable output suitable for snapshot tests
+    pub fn deterministic(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Apply a theme to Terminal and HTML output
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Select how timestamps are written
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    /// Write RFC 3339 timestamps in the given time zone
+    pub fn with_timestamp_zone(mut self, zone: TimestampZone) -> Self {
+        self.timestamp_zone = zone;
+        self
+    }
+
+    /// Render a timestamp according to `timestamp_format` and `timestamp_zone`
+    pub fn format_timestamp(&self, time: SystemTime) -> String {
+        self.format_timestamp_at(time, SystemTime::now())
+    }
+
+    pub(crate) fn format_timestamp_at(&self, time: SystemTime, now: SystemTime) -> String {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        match self.timestamp_format {
+            TimestampFormat::UnixSeconds => since_epoch.as_secs().to_string(),
+            TimestampFormat::UnixMillis => since_epoch.as_millis().to_string(),
+            TimestampFormat::Relative if self.format == ErrorReportFormat::Terminal => format_relative(time, now),
+            TimestampFormat::Rfc3339 | TimestampFormat::Relative => match self.timestamp_zone {
+                TimestampZone::Utc => format_rfc3339(time),
+                #[cfg(feature = "chrono")]
+                TimestampZone::Local => chrono::DateTime::<chrono::Local>::from(time)
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            },
+        }
+    }
+
+    /// Wrap Plain and Terminal output according to the given mode
+    pub fn with_wrap(mut self, wrap: WrapMode) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Translated text of a report label for the configured locale
+    ///
+    /// Looks in `label_catalog` first, then in the built-in catalog, and
+    /// finally falls back to English.
+    pub fn label(&self, label: ReportLabel) -> &str {
+        let locale = self.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        self.label_catalog
+            .as_ref()
+            .and_then(|catalog| catalog.get(locale, label))
+            .or_else(|| LabelCatalog::builtin().get(locale, label))
+            .unwrap_or_else(|| label.english())
+    }
+}
+
+/// A destination that accepts fully rendered error reports
+///
+/// Sinks are registered on an `ErrorReporter` through `ErrorReporter::builder()`.
+/// Each sink receives the report already rendered in the format configured
+/// for that sink.
+pub trait ReportSink: Send + Sync {
+    /// Write one rendered report to the sink
+    fn write_report(&self, rendered: &str) -> io::Result<()>;
+
+    /// Deliver one report in both its structured and rendered form
+    ///
+    /// `ErrorReporter::report_to_sinks` calls this; the default forwards the
+    /// rendered text to `write_report`. Sinks consuming `ErrorReport` values
+    /// directly (such as `ChannelSink`) override it.
+    fn publish(&self, _report: &ErrorReport, rendered: &str) -> io::Result<()> {
+        self.write_report(rendered)
+    }
+
+    /// Flush any buffered output held by the sink
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Flush and finalize the sink at shutdown (e.g. write compression trailers)
+    ///
+    /// The sink may reject further reports once closed.
+    fn close(&self) -> io::Result<()> {
+        self.flush()
+    }
+}
+
+impl<S: ReportSink + ?Sized> ReportSink for Arc<S> {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        (**self).write_report(rendered)
+    }
+
+    fn publish(&self, report: &ErrorReport, rendered: &str) -> io::Result<()> {
+        (**self).publish(report, rendered)
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        (**self).flush()
+    }
+
+    fn close(&self) -> io::Result<()> {
+        (**self).close()
+    }
+}
+
+/// Sink that writes reports to any `Write` implementation
+pub struct WriterSink<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    /// Create a sink around the given writer
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Consume the sink and return the wrapped writer
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl WriterSink<io::Stderr> {
+    /// Create a sink writing to standard error
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl WriterSink<io::Stdout> {
+    /// Create a sink writing to standard output
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: Write + Send> ReportSink for WriterSink<W> {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.write_all(rendered.as_bytes())
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let mut writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.flush()
+    }
+}
+
+/// Compression applied by a `FileSink`
+///
+/// Compressed sinks append a new gzip member / zstd frame to an existing
+/// file; both formats decode concatenated members as one stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    /// Write reports uncompressed
+    #[default]
+    None,
+    /// gzip at the given level (0-9)
+    #[cfg(feature = "gzip")]
+    Gzip {
+        /// Compression level, 0 (fastest) to 9 (smallest)
+        level: u32,
+    },
+    /// zstd at the given level (1-22)
+    #[cfg(feature = "zstd")]
+    Zstd {
+        /// Compression level, 1 (fastest) to 22 (smallest)
+        level: i32,
+    },
+}
+
+enum FileWriter {
+    Plain(File),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<File>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, File>),
+}
+
+impl FileWriter {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            FileWriter::Plain(file) => file,
+            #[cfg(feature = "gzip")]
+            FileWriter::Gzip(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            FileWriter::Zstd(encoder) => encoder,
+        }
+    }
+
+    // Write the compression trailer and flush the file to disk
+    #[allow(clippy::infallible_destructuring_match)] // one arm without compression features
+    fn finish(self) -> io::Result<()> {
+        let file = match self {
+            FileWriter::Plain(file) => file,
+            #[cfg(feature = "gzip")]
+            FileWriter::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            FileWriter::Zstd(encoder) => encoder.finish()?,
+        };
+        file.sync_all()
+    }
+}
+
+/// Sink that appends reports to a file on disk, optionally compressed
+///
+/// Compressed output is only complete once the sink is closed, either
+/// explicitly through `ReportSink::close` / `ErrorReporter::close_sinks` or
+/// when the sink is dropped.
+pub struct FileSink {
+    path: PathBuf,
+    compression: Compression,
+    file: Mutex<Option<FileWriter>>,
+}
+
+impl FileSink {
+    /// Open (or create) the file at `path` in append mode
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_compressed(path, Compression::None)
+    }
+
+    /// Open (or create) the file at `path` in append mode, compressing reports
+    pub fn open_compressed(path: impl AsRef<Path>, compression: Compression) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let writer = match compression {
+            Compression::None => FileWriter::Plain(file),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip { level } => {
+                FileWriter::Gzip(flate2::write::GzEncoder::new(file, flate2::Compression::new(level.min(9))))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd { level } => FileWriter::Zstd(zstd::stream::write::Encoder::new(file, level)?),
+        };
+
+        Ok(Self {
+            path,
+            compression,
+            file: Mutex::new(Some(writer)),
+        })
+    }
+
+    /// Path of the file backing this sink
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Compression applied to written reports
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn closed_error() -> io::Error {
+        io::Error::new(io::ErrorKind::BrokenPipe, "file sink is closed")
+    }
+}
+
+impl ReportSink for FileSink {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match file.as_mut() {
+            Some(writer) => writer.writer().write_all(rendered.as_bytes()),
+            None => Err(Self::closed_error()),
+        }
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match file.as_mut() {
+            Some(writer) => writer.writer().flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn close(&self) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match file.take() {
+            Some(writer) => writer.finish(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FileSink {
+    fn drop(&mut self) {
+        if let Err(err) = self.close() {
+            warn!("Failed to finalize error report file '{}': {}", self.path.display(), err);
+        }
+    }
+}
+
+/// What to do when a sink fails to accept a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SinkFailurePolicy {
+    /// Log the failure through `tracing` and continue with the remaining sinks
+    #[default]
+    Log,
+    /// Silently drop the failure
+    Ignore,
+    /// Return the failure from `report_to_sinks` once every sink has been tried
+    Propagate,
+}
+
+/// Predicate deciding whether a sink should receive a given error
+pub type SinkFilter = Arc<dyn Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync>;
+
+/// A named sink together with its own format, configuration, filters and failure policy
+pub struct SinkRegistration {
+    name: String,
+    sink: Arc<dyn ReportSink>,
+    config: ErrorReportConfig,
+    filters: Vec<SinkFilter>,
+    failure_policy: SinkFailurePolicy,
+}
+
+impl SinkRegistration {
+    /// Register `sink` under `name` using the default report configuration
+    pub fn new(name: impl Into<String>, sink: impl ReportSink + 'static) -> Self {
+        Self::from_arc(name, Arc::new(sink))
+    }
+
+    /// Register an already shared sink under `name`
+    pub fn from_arc(name: impl Into<String>, sink: Arc<dyn ReportSink>) -> Self {
+        Self {
+            name: name.into(),
+            sink,
+            config: ErrorReportConfig::default(),
+            filters: Vec::new(),
+            failure_policy: SinkFailurePolicy::default(),
+        }
+    }
+
+    /// Use the given report configuration for this sink
+    pub fn with_config(mut self, config: ErrorReportConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Override only the output format of this sink
+    pub fn with_format(mut self, format: ErrorReportFormat) -> Self {
+        self.config.format = format;
+        self
+    }
+
+    /// Only deliver errors for which `filter` returns true
+    ///
+    /// Multiple filters can be added; all of them must accept the error.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&(dyn std::error::Error + 'static)) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Only deliver errors at or above the given severity
+    ///
+    /// Errors that are not `AklypseError`s are treated as `ErrorSeverity::Error`.
+    pub fn with_min_severity(self, min_severity: ErrorSeverity) -> Self {
+        self.with_filter(move |error| {
+            let severity = error
+                .downcast_ref::<AklypseError>()
+                .map(|e| e.severity())
+                .unwrap_or(ErrorSeverity::Error);
+            severity >= min_severity
+        })
+    }
+
+    /// Only deliver errors carrying `tag` in any of their rich contexts
+    pub fn with_tag(self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        self.with_filter(move |error| any_context(error, |context| context.has_tag(&tag)))
+    }
+
+    /// Only deliver errors carrying a tag in `namespace` (e.g. `team`) in any of their rich contexts
+    pub fn with_tag_ns(self, namespace: impl Into<String>) -> Self {
+        let namespace = namespace.into();
+        self.with_filter(move |error| any_context(error, |context| context.has_tag_ns(&namespace)))
+    }
+
+    /// Set how failures of this sink are handled
+    pub fn with_failure_policy(mut self, policy: SinkFailurePolicy) -> Self {
+        self.failure_policy = policy;
+        self
+    }
+
+    /// Name of the sink
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Report configuration used for this sink
+    pub fn config(&self) -> &ErrorReportConfig {
+        &self.config
+    }
+
+    fn accepts(&self, error: &(dyn std::error::Error + 'static)) -> bool {
+        self.filters.iter().all(|filter| filter(error))
+    }
+}
+
+// Whether any rich context layer of an `AklypseError` satisfies `predicate`
+fn any_context(error: &(dyn std::error::Error + 'static), predicate: impl Fn(&ErrorContext) -> bool) -> bool {
+    let mut current = error.downcast_ref::<AklypseError>();
+    while let Some(AklypseError::WithRichContext { context, source, .. }) = current {
+        if predicate(context) {
+            return true;
+        }
+        current = Some(&**source);
+    }
+    false
+}
+
+impl fmt::Debug for SinkRegistration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkRegistration")
+            .field("name", &self.name)
+            .field("format", &self.config.format)
+            .field("filters", &self.filters.len())
+            .field("failure_policy", &self.failure_policy)
+            .finish()
+    }
+}
+
+/// Builder composing several named sinks into one `ErrorReporter`
+#[derive(Debug, Default)]
+pub struct ErrorReporterBuilder {
+    sinks: Vec<SinkRegistration>,
+    report_store: Option<Arc<ReportStore>>,
+}
+
+impl ErrorReporterBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fully configured sink registration
+    pub fn add_sink(mut self, registration: SinkRegistration) -> Self {
+        self.sinks.push(registration);
+        self
+    }
+
+    /// Add a sink with the default configuration in the given format
+    pub fn sink(self, name: impl Into<String>, sink: impl ReportSink + 'static, format: ErrorReportFormat) -> Self {
+        self.add_sink(SinkRegistration::new(name, sink).with_format(format))
+    }
+
+    /// Index every fanned-out report in the given store
+    pub fn with_report_store(mut self, store: Arc<ReportStore>) -> Self {
+        self.report_store = Some(store);
+        self
+    }
+
+    /// Build the reporter
+    pub fn build(self) -> ErrorReporter {
+        ErrorReporter {
+            sinks: self.sinks,
+            report_store: self.report_store,
         }
     }
 }
 
 /// Utility for generating formatted error reports
+///
+/// A reporter created with `new()` renders reports on demand through `report`
+/// and `report_to_string`. A reporter created through `builder()` additionally
+/// fans a single `report_to_sinks` call out to every registered sink.
 #[derive(Debug, Default)]
-pub struct ErrorReporter;
+pub struct ErrorReporter {
+    sinks: Vec<SinkRegistration>,
+    report_store: Option<Arc<ReportStore>>,
+}
 
 impl ErrorReporter {
+    /// Create a reporter without sinks
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Start building a reporter with multiple named sinks
+    pub fn builder() -> ErrorReporterBuilder {
+        ErrorReporterBuilder::new()
+    }
+
+    /// Names of the registered sinks, in delivery order
+    pub fn sink_names(&self) -> impl Iterator<Item = &str> {
+        self.sinks.iter().map(|s| s.name.as_str())
+    }
+
+    /// Store indexing the reports emitted by this reporter, if one was configured
+    pub fn report_store(&self) -> Option<&Arc<ReportStore>> {
+        self.report_store.as_ref()
+    }
+
+    /// Render and deliver an error to every registered sink
+    ///
+    /// Each sink renders the error with its own configuration, but all sinks
+    /// share a single `ReportId`, which is returned so callers can show it as a
+    /// reference code or link it into the error with `AklypseError::with_report_id`.
+    /// A failing sink never prevents delivery to the sinks after it; failures
+    /// are handled according to each sink's `SinkFailurePolicy`, and those
+    /// marked `Propagate` are returned once every sink has been tried.
+    pub fn report_to_sinks<E>(&self, error: &E) -> Result<ReportId>
+    where
+        E: std::error::Error + 'static,
+    {
+        let report_id = ReportId::generate();
+        let mut failures = Vec::new();
+
+        if let Some(store) = &self.report_store {
+            store.insert(self.build_report(error, &ErrorReportConfig::default()).with_report_id(report_id.clone()));
+        }
+
+        for registration in &self.sinks {
+            if !registration.accepts(error) {
+                continue;
+            }
+
+            let report = self
+                .build_report(error, &registration.config)
+                .with_report_id(report_id.clone());
+            let mut rendered = Vec::new();
+            let result = self
+                .render(&report, &registration.config, &mut rendered)
+                .and_then(|_| registration.sink.publish(&report, &String::from_utf8_lossy(&rendered)));
+
+            if let Err(io_err) = result {
+                self.handle_sink_failure(registration, "write_report", io_err, &mut failures);
+            }
+        }
+
+        Self::collect_sink_failures(failures).map(|_| report_id)
+    }
+
+    /// Flush every registered sink, isolating failures in the same way as `report_to_sinks`
+    pub fn flush_sinks(&self) -> Result<()> {
+        let mut failures = Vec::new();
+
+        for registration in &self.sinks {
+            if let Err(io_err) = registration.sink.flush() {
+                self.handle_sink_failure(registration, "flush", io_err, &mut failures);
+            }
+        }
+
+        Self::collect_sink_failures(failures)
+    }
+
+    /// Flush and finalize every registered sink at shutdown
+    ///
+    /// Compressed file sinks write their trailers here; reports sent to a
+    /// closed sink afterwards are handled as sink failures.
+    pub fn close_sinks(&self) -> Result<()> {
+        let mut failures = Vec::new();
+
+        for registration in &self.sinks {
+            if let Err(io_err) = registration.sink.close() {
+                self.handle_sink_failure(registration, "close", io_err, &mut failures);
+            }
+        }
+
+        Self::collect_sink_failures(failures)
+    }
+
+    fn handle_sink_failure(
+        &self,
+        registration: &SinkRegistration,
+        operation: &str,
+        io_err: io::Error,
+        failures: &mut Vec<AklypseError>,
+    ) {
+        match registration.failure_policy {
+            SinkFailurePolicy::Log => {
+                warn!("Error report sink '{}' failed during {}: {}", registration.name, operation, io_err);
+            }
+            SinkFailurePolicy::Ignore => {}
+            SinkFailurePolicy::Propagate => {
+                failures.push(IoSnafu {
+                    source: Arc::new(io_err),
+                    path: None::<PathBuf>,
+                    operation: format!("report sink '{}' {}", registration.name, operation),
+                }.build());
+            }
+        }
+    }
+
+    fn collect_sink_failures(mut failures: Vec<AklypseError>) -> Result<()> {
+        match failures.len() {
+            0 => Ok(()),
+            1 => Err(failures.remove(0)),
+            _ => Err(MultipleErrorsSnafu { errors: failures }.build()),
+        }
+    }
+
+    /// Extract the structured contents of an error report without rendering it
+    pub fn build_report<E>(&self, error: &E, config: &ErrorReportConfig) -> ErrorReport
+    where
+        E: std::error::Error + 'static,
+    {
+        ErrorReport::from_error(error, config)
     }
 
     /// Report an error to a writer using the provided configuration
@@ -65,120 +812,772 @@ impl ErrorReporter {
     ) -> io::Result<()>
     where
         W: Write,
-        E: std::error::Error,
+        E: std::error::Error + 'static,
+    {
+        let report = self.build_report(error, config);
+        self.render(&report, config, writer)
+    }
+
+    /// Render an already built report to a writer in the configured format
+    pub fn render<W>(
+        &self,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
     {
+        // Precomputed reports are adjusted to the config: autocorrections are
+        // only shown when asked for, and deterministic output is always scrubbed
+        let adjusted;
+        let report = if config.deterministic || (!config.include_autocorrections && !report.autocorrections.is_empty()) {
+            let mut copy = report.clone();
+            if !config.include_autocorrections {
+                copy.autocorrections.clear();
+            }
+            if config.deterministic {
+                copy.make_deterministic();
+            }
+            adjusted = copy;
+            &adjusted
+        } else {
+            report
+        };
+
+        if config.style == ReportStyle::Compact {
+            return match config.format {
+                ErrorReportFormat::Json => writeln!(writer, "{}", report.to_json(false)),
+                _ => self.render_compact(report, writer),
+            };
+        }
+
         match config.format {
-            ErrorReportFormat::Plain => self.report_plain(error, config, writer),
-            ErrorReportFormat::Json => self.report_json(error, config, writer),
-            ErrorReportFormat::Markdown => self.report_markdown(error, config, writer),
-            ErrorReportFormat::Html => self.report_html(error, config, writer),
+            ErrorReportFormat::Plain | ErrorReportFormat::Terminal => {
+                let default_theme;
+                let theme = match (config.format, &config.theme) {
+                    (ErrorReportFormat::Plain, _) => None,
+                    (_, Some(theme)) => Some(theme),
+                    (_, None) => {
+                        default_theme = Theme::default();
+                        Some(&default_theme)
+                    }
+                };
+                let painter = Painter::new(theme, report.severity);
+
+                match config.wrap.resolve() {
+                    Some(width) => {
+                        let mut buffer = Vec::new();
+                        self.render_plain(report, config, &painter, &mut buffer)?;
+                        writer.write_all(wrap_text(&String::from_utf8_lossy(&buffer), width).as_bytes())
+                    }
+                    None => self.render_plain(report, config, &painter, writer),
+                }
+            }
+            ErrorReportFormat::Json => self.render_json(report, config, writer),
+            ErrorReportFormat::Markdown => self.render_markdown(report, config, writer),
+            ErrorReportFormat::Html => self.render_html(report, config, writer),
         }
     }
 
     /// Report an error as a string using the provided configuration
     pub fn report_to_string<E>(&self, error: &E, config: &ErrorReportConfig) -> String
     where
-        E: std::error::Error,
+        E: std::error::Error + 'static,
     {
         let mut buffer = Vec::new();
         let _ = self.report(error, config, &mut buffer);
         String::from_utf8_lossy(&buffer).to_string()
     }
 
-    fn report_plain<W, E>(
-        &self,
-        error: &E,
-        config: &ErrorReportConfig,
-        writer: &mut W,
-    ) -> io::Result<()>
-    where
-        W: Write,
-        E: std::error::Error,
-    {
-        // Implementation of plain text error reporting
-        // This would use the Display or Debug implementations for errors
-        // and format according to the config options
-        writeln!(writer, "Error: {}", error)?;
-        
-        // If error supports source(), we can get the cause chain
-        if config.include_source_chain {
-            let mut source = error.source();
-            let mut depth = 0;
-            
-            while let Some(err) = source {
-                if let Some(max_depth) = config.max_chain_depth {
-                    if depth >= max_depth {
-                        writeln!(writer, "... (more causes hidden)")?;
-                        break;
+    /// Write one CSV row per error, preceded by a header row
+    ///
+    /// Columns are `timestamp, code, category, severity, fingerprint, message,
+    /// correlation_id`, quoted per RFC 4180, which makes large batch-job failure
+    /// sets easy to sort and group in a spreadsheet.
+    pub fn report_batch_csv<'a, I, E, W>(&self, errors: I, config: &ErrorReportConfig, writer: &mut W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a E>,
+        E: std::error::Error + 'static,
+        W: Write,
+    {
+        self.report_batch_delimited(errors, config, ',', writer)
+    }
+
+    /// Same as [`report_batch_csv`](Self::report_batch_csv) with tab-separated columns
+    pub fn report_batch_tsv<'a, I, E, W>(&self, errors: I, config: &ErrorReportConfig, writer: &mut W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a E>,
+        E: std::error::Error + 'static,
+        W: Write,
+    {
+        self.report_batch_delimited(errors, config, '\t', writer)
+    }
+
+    fn report_batch_delimited<'a, I, E, W>(
+        &self,
+        errors: I,
+        config: &ErrorReportConfig,
+        delimiter: char,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'a E>,
+        E: std::error::Error + 'static,
+        W: Write,
+    {
+        let write_row = |writer: &mut W, cells: &[&str]| -> io::Result<()> {
+            let row = cells
+                .iter()
+                .map(|cell| delimited_cell(cell, delimiter))
+                .collect::<Vec<_>>()
+                .join(&delimiter.to_string());
+            // RFC 4180 line terminator
+            write!(writer, "{}\r\n", row)
+        };
+
+        write_row(
+            writer,
+            &["timestamp", "code", "category", "severity", "fingerprint", "message", "correlation_id"],
+        )?;
+
+        for error in errors {
+            let report = self.build_report(error, config);
+            let category = report.category.map(|category| format!("{:?}", category)).unwrap_or_default();
+            write_row(
+                writer,
+                &[
+                    &config.format_timestamp(report.timestamp()),
+                    &report.code().unwrap_or_default(),
+                    &category,
+                    &format!("{:?}", report.severity),
+                    &report.fingerprint(),
+                    &report.message,
+                    report
+                        .context
+                        .as_ref()
+                        .and_then(|context| context.correlation_id.as_deref())
+                        .unwrap_or_default(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn render_plain<W>(
+        &self,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        painter: &Painter<'_>,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let label = |label: ReportLabel| painter.label(config.label(label));
+
+        if config.include_message {
+            let header = format!("{}: {}", config.label(ReportLabel::Error), report.message);
+            writeln!(writer, "{}", painter.header(&header))?;
+        } else {
+            writeln!(writer, "{}", painter.header(config.label(ReportLabel::Error)))?;
+        }
+        writeln!(
+            writer,
+            "{}: {}",
+            label(ReportLabel::ReportId),
+            painter.muted(report.report_id.as_str())
+        )?;
+
+        if report.is_aklypse_error() && config.include_severity {
+            writeln!(writer, "{}: {:?}", label(ReportLabel::Severity), report.severity)?;
+        }
+
+        for cause in &report.chain {
+            writeln!(writer, "{}{}: {}", painter.cause_prefix(), label(ReportLabel::CausedBy), cause)?;
+        }
+        if report.chain_truncated {
+            writeln!(writer, "{}", label(ReportLabel::MoreCausesHidden))?;
+        }
+
+        if !report.fields.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::Fields))?;
+            for (key, value) in &report.fields {
+                writeln!(writer, "  {}: {}", key, value)?;
+            }
+        }
+
+        if let Some(context) = &report.context {
+            writeln!(writer, "{}: {}", label(ReportLabel::Context), context.message)?;
+            if let Some(component) = &context.component {
+                writeln!(writer, "  {}: {}", label(ReportLabel::Component), component)?;
+            }
+            if let Some(correlation_id) = &context.correlation_id {
+                writeln!(writer, "  {}: {}", label(ReportLabel::CorrelationId), correlation_id)?;
+            }
+            if let Some(timestamp) = context.timestamp {
+                writeln!(writer, "  {}: {}", label(ReportLabel::Timestamp), config.format_timestamp(timestamp))?;
+            }
+            if let Some(location) = &context.source_location {
+                write!(writer, "  {}: {}:{}", label(ReportLabel::Location), location.file, location.line)?;
+                if let Some(column) = location.column {
+                    write!(writer, ":{}", column)?;
+                }
+                if location.module_path.is_empty() {
+                    writeln!(writer)?;
+                } else {
+                    writeln!(writer, " ({})", location.module_path)?;
+                }
+            }
+            if !context.tags.is_empty() {
+                writeln!(writer, "  {}: {}", label(ReportLabel::Tags), context.tags.join(", "))?;
+            }
+            for (key, value) in sorted_metadata(context) {
+                writeln!(writer, "  {}: {}", key, value)?;
+            }
+            if let Some(suggestion) = &context.recovery_suggestion {
+                writeln!(writer, "{}: {}", label(ReportLabel::RecoverySuggestion), suggestion)?;
+            }
+            if !context.recovery_actions.is_empty() {
+                writeln!(writer, "{}:", label(ReportLabel::RecoveryActions))?;
+                for action in context.recovery_actions.iter() {
+                    writeln!(writer, "  - {}", action)?;
+                }
+            }
+        }
+
+        if let Some(diagnostics) = &report.diagnostics {
+            writeln!(writer, "{}:", label(ReportLabel::Diagnostics))?;
+            if let Some(code) = &diagnostics.diagnostic_code {
+                writeln!(writer, "  {}: {}", label(ReportLabel::Code), code)?;
+            }
+            if let Some(location) = &diagnostics.primary_location {
+                writeln!(
+                    writer,
+                    "  {}: {}:{}:{}",
+                    label(ReportLabel::At),
+                    location.file,
+                    location.line,
+                    location.column
+                )?;
+            }
+            for fix in &diagnostics.suggested_fixes {
+                writeln!(writer, "  {}: {}", label(ReportLabel::Suggested), fix)?;
+            }
+        }
+
+        if !report.autocorrections.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::SuggestedFixes))?;
+            for autocorrection in &report.autocorrections {
+                writeln!(
+                    writer,
+                    "  {} {} ({}: {:.0}%)",
+                    painter.suggestion_bullet(),
+                    autocorrection.description,
+                    label(ReportLabel::Confidence),
+                    autocorrection.confidence * 100.0
+                )?;
+                if let Some(diff) = &autocorrection.diff_suggestion {
+                    for line in diff.lines() {
+                        writeln!(writer, "      {}", line)?;
+                    }
+                }
+                for command in &autocorrection.commands_to_apply {
+                    writeln!(writer, "      $ {}", command)?;
+                }
+            }
+        }
+
+        if !report.backtrace_frames.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::Backtrace))?;
+            for (index, frame) in report.backtrace_frames.iter().enumerate() {
+                let line = match &frame.location {
+                    Some(location) => format!("{:>3}: {} at {}", index, frame.symbol, location),
+                    None => format!("{:>3}: {}", index, frame.symbol),
+                };
+                writeln!(writer, "  {}", painter.muted(&line))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_compact<W>(&self, report: &ErrorReport, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let mut fields: Vec<(&str, String)> = vec![
+            ("error", report.message.clone()),
+            ("report_id", report.report_id.to_string()),
+            ("severity", format!("{:?}", report.severity)),
+        ];
+
+        if let Some(category) = report.category {
+            fields.push(("category", format!("{:?}", category)));
+        }
+        if let Some(context) = &report.context {
+            fields.push(("context", context.message.to_string()));
+            if let Some(component) = &context.component {
+                fields.push(("component", component.to_string()));
+            }
+            if let Some(correlation_id) = &context.correlation_id {
+                fields.push(("correlation_id", correlation_id.to_string()));
+            }
+        }
+        for (key, value) in &report.fields {
+            fields.push((key.as_str(), value.clone()));
+        }
+        for cause in &report.chain {
+            fields.push(("cause", cause.clone()));
+        }
+        if report.chain_truncated {
+            fields.push(("causes_truncated", "true".to_string()));
+        }
+
+        let line = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, logfmt_value(value)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(writer, "{}", line)
+    }
+
+    fn render_json<W>(
+        &self,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "{}", report.to_json(config.pretty_print_json))
+    }
+
+    fn render_markdown<W>(
+        &self,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "## {}\n\n```", config.label(ReportLabel::Error))?;
+        writeln!(writer, "{}", report.message)?;
+        writeln!(writer, "```")?;
+        writeln!(writer, "\n**{}:** `{}`", config.label(ReportLabel::ReportId), report.report_id)?;
+
+        if report.is_aklypse_error() {
+            writeln!(writer, "\n**{}:** {:?}", config.label(ReportLabel::Severity), report.severity)?;
+        }
+
+        if !report.chain.is_empty() {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::CausedBy))?;
+            for cause in &report.chain {
+                writeln!(writer, "- {}", cause)?;
+            }
+            if report.chain_truncated {
+                writeln!(writer, "- {}", config.label(ReportLabel::MoreCausesHidden))?;
+            }
+        }
+
+        if !report.fields.is_empty() {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::Fields))?;
+            for (key, value) in &report.fields {
+                writeln!(writer, "- **{}:** {}", key, value)?;
+            }
+        }
+
+        if let Some(context) = &report.context {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::Context))?;
+            writeln!(writer, "{}", context.message)?;
+            if let Some(component) = &context.component {
+                writeln!(writer, "\n- **{}:** {}", config.label(ReportLabel::Component), component)?;
+            }
+            if let Some(correlation_id) = &context.correlation_id {
+                writeln!(writer, "- **{}:** {}", config.label(ReportLabel::CorrelationId), correlation_id)?;
+            }
+            if let Some(timestamp) = context.timestamp {
+                writeln!(
+                    writer,
+                    "- **{}:** {}",
+                    config.label(ReportLabel::Timestamp),
+                    config.format_timestamp(timestamp)
+                )?;
+            }
+            for (key, value) in sorted_metadata(context) {
+                writeln!(writer, "- **{}:** {}", key, value)?;
+            }
+            if let Some(suggestion) = &context.recovery_suggestion {
+                writeln!(writer, "\n> **{}:** {}", config.label(ReportLabel::RecoverySuggestion), suggestion)?;
+            }
+            if !context.recovery_actions.is_empty() {
+                writeln!(writer, "\n**{}:**\n", config.label(ReportLabel::RecoveryActions))?;
+                for action in context.recovery_actions.iter() {
+                    writeln!(writer, "- {}", action)?;
+                }
+            }
+        }
+
+        if let Some(diagnostics) = &report.diagnostics {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::Diagnostics))?;
+            if let Some(code) = &diagnostics.diagnostic_code {
+                writeln!(writer, "- **{}:** `{}`", config.label(ReportLabel::Code), code)?;
+            }
+            for fix in &diagnostics.suggested_fixes {
+                writeln!(writer, "- {}", fix)?;
+            }
+        }
+
+        if !report.autocorrections.is_empty() {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::SuggestedFixes))?;
+            for autocorrection in &report.autocorrections {
+                writeln!(
+                    writer,
+                    "- {} _({}: {:.0}%)_",
+                    autocorrection.description,
+                    config.label(ReportLabel::Confidence),
+                    autocorrection.confidence * 100.0
+                )?;
+                if let Some(diff) = &autocorrection.diff_suggestion {
+                    writeln!(writer, "\n  ```diff")?;
+                    for line in diff.lines() {
+                        writeln!(writer, "  {}", line)?;
+                    }
+                    writeln!(writer, "  ```")?;
+                }
+                if !autocorrection.commands_to_apply.is_empty() {
+                    writeln!(writer, "\n  ```sh")?;
+                    for command in &autocorrection.commands_to_apply {
+                        writeln!(writer, "  {}", command)?;
+                    }
+                    writeln!(writer, "  ```")?;
+                }
+            }
+        }
+
+        if !report.backtrace_frames.is_empty() {
+            writeln!(writer, "\n### {}\n\n```", config.label(ReportLabel::Backtrace))?;
+            for (index, frame) in report.backtrace_frames.iter().enumerate() {
+                writeln!(writer, "{:>3}: {}", index, frame.symbol)?;
+            }
+            writeln!(writer, "```")?;
+        }
+
+        Ok(())
+    }
+
+    fn render_html<W>(
+        &self,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        // Inline styles and glyphs only appear when a theme is configured
+        let theme = config.theme.as_ref();
+        let style = |color: Option<String>| {
+            color
+                .map(|color| format!(" style=\"color:{}\"", color))
+                .unwrap_or_default()
+        };
+        let accent = style(theme.and_then(|theme| theme.accent.css()));
+        let glyph = |glyph: &str| {
+            if glyph.is_empty() {
+                String::new()
+            } else {
+                format!("<span class=\"error-glyph\"{}>{}</span> ", accent, escape_html(glyph))
+            }
+        };
+
+        let header_style = theme
+            .map(|theme| format!(" style=\"{}\"", theme.header_css(report.severity)))
+            .unwrap_or_default();
+        let header_glyph = theme.map_or(String::new(), |theme| glyph(theme.glyphs.severity(report.severity)));
+        writeln!(
+            writer,
+            "<div class=\"error\" data-report-id=\"{}\"><pre{}>{}{}</pre>",
+            report.report_id,
+            header_style,
+            header_glyph,
+            escape_html(&report.message)
+        )?;
+
+        if !report.chain.is_empty() {
+            let cause_glyph = theme.map_or(String::new(), |theme| glyph(theme.glyphs.cause()));
+            writeln!(writer, "<ul class=\"error-chain\">")?;
+            for cause in &report.chain {
+                writeln!(writer, "<li>{}{}</li>", cause_glyph, escape_html(cause))?;
+            }
+            writeln!(writer, "</ul>")?;
+        }
+
+        if !report.fields.is_empty() {
+            writeln!(writer, "<dl class=\"error-fields\">")?;
+            for (key, value) in &report.fields {
+                writeln!(writer, "<dt{}>{}</dt><dd>{}</dd>", accent, escape_html(key), escape_html(value))?;
+            }
+            writeln!(writer, "</dl>")?;
+        }
+
+        if let Some(context) = &report.context {
+            writeln!(writer, "<dl class=\"error-context\">")?;
+            writeln!(
+                writer,
+                "<dt{}>{}</dt><dd>{}</dd>",
+                accent,
+                escape_html(config.label(ReportLabel::Context)),
+                escape_html(&context.message)
+            )?;
+            if let Some(timestamp) = context.timestamp {
+                writeln!(
+                    writer,
+                    "<dt{}>{}</dt><dd><time datetime=\"{}\">{}</time></dd>",
+                    accent,
+                    escape_html(config.label(ReportLabel::Timestamp)),
+                    format_rfc3339(timestamp),
+                    escape_html(&config.format_timestamp(timestamp))
+                )?;
+            }
+            for (key, value) in sorted_metadata(context) {
+                writeln!(writer, "<dt{}>{}</dt><dd>{}</dd>", accent, escape_html(key), escape_html(value))?;
+            }
+            if let Some(suggestion) = &context.recovery_suggestion {
+                writeln!(
+                    writer,
+                    "<dt{}>{}</dt><dd>{}</dd>",
+                    accent,
+                    escape_html(config.label(ReportLabel::RecoverySuggestion)),
+                    escape_html(suggestion)
+                )?;
+            }
+            if !context.recovery_actions.is_empty() {
+                write!(
+                    writer,
+                    "<dt{}>{}</dt><dd><ul>",
+                    accent,
+                    escape_html(config.label(ReportLabel::RecoveryActions))
+                )?;
+                for action in context.recovery_actions.iter() {
+                    write!(writer, "<li data-kind=\"{}\">{}</li>", action.kind(), escape_html(&action.to_string()))?;
+                }
+                writeln!(writer, "</ul></dd>")?;
+            }
+            writeln!(writer, "</dl>")?;
+        }
+
+        if !report.autocorrections.is_empty() {
+            let fix_glyph = theme.map_or(String::new(), |theme| glyph(theme.glyphs.suggestion()));
+            writeln!(
+                writer,
+                "<section class=\"error-fixes\"><h4{}>{}</h4><ul>",
+                accent,
+                escape_html(config.label(ReportLabel::SuggestedFixes))
+            )?;
+            for autocorrection in &report.autocorrections {
+                writeln!(
+                    writer,
+                    "<li data-confidence=\"{:.2}\">{}{}",
+                    autocorrection.confidence,
+                    fix_glyph,
+                    escape_html(&autocorrection.description)
+                )?;
+                if let Some(diff) = &autocorrection.diff_suggestion {
+                    writeln!(writer, "<pre class=\"error-fix-diff\">{}</pre>", escape_html(diff))?;
+                }
+                for command in &autocorrection.commands_to_apply {
+                    writeln!(writer, "<code class=\"error-fix-command\">{}</code>", escape_html(command))?;
+                }
+                writeln!(writer, "</li>")?;
+            }
+            writeln!(writer, "</ul></section>")?;
+        }
+
+        if !report.backtrace_frames.is_empty() {
+            let muted = style(theme.and_then(|theme| theme.muted.css()));
+            writeln!(writer, "<pre class=\"error-backtrace\"{}>", muted)?;
+            for (index, frame) in report.backtrace_frames.iter().enumerate() {
+                writeln!(writer, "{:>3}: {}", index, escape_html(&frame.symbol))?;
+            }
+            writeln!(writer, "</pre>")?;
+        }
+
+        writeln!(writer, "</div>")?;
+        Ok(())
+    }
+}
+
+// Metadata entries sorted by key so reports render identically across runs
+fn sorted_metadata(context: &ErrorContext) -> Vec<(&String, &String)> {
+    let mut entries: Vec<_> = context.metadata.iter().collect();
+    entries.sort();
+    entries
+}
+
+// "just now", "42s ago", "3m ago", "5h ago", "2d ago" (or "in 3m" for future times)
+fn format_relative(time: SystemTime, now: SystemTime) -> String {
+    let (elapsed, future) = match now.duration_since(time) {
+        Ok(elapsed) => (elapsed, false),
+        Err(error) => (error.duration(), true),
+    };
+
+    let secs = elapsed.as_secs();
+    let amount = match secs {
+        0..=4 => return "just now".to_string(),
+        5..=59 => format!("{}s", secs),
+        60..=3_599 => format!("{}m", secs / 60),
+        3_600..=86_399 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    };
+
+    if future {
+        format!("in {}", amount)
+    } else {
+        format!("{} ago", amount)
+    }
+}
+
+// Quote a CSV/TSV cell when it contains the delimiter, quotes or line breaks
+fn delimited_cell(value: &str, delimiter: char) -> String {
+    if value.contains([delimiter, '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Quote a logfmt value when it contains separators, quotes or control characters
+fn logfmt_value(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.chars().any(|c| c.is_whitespace() || c == '=' || c == '"' || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Word-wrap every line to `width` columns; continuation lines keep the
+// original indentation plus two spaces, and overlong words are split.
+// ANSI color sequences take up no columns.
+fn wrap_text(text: &str, width: usize) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        if visible_width(line) <= width {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        let indent_len = line.len() - line.trim_start().len();
+        let continuation = format!("{}  ", &line[..indent_len]);
+        let continuation_width = continuation.chars().count();
+        // Give up on indenting when the indent alone fills the line
+        let continuation = if continuation_width >= width { String::new() } else { continuation };
+
+        let mut current = line[..indent_len].to_string();
+        let mut current_len = indent_len;
+        let mut line_has_word = false;
+
+        for word in line[indent_len..].split(' ').filter(|w| !w.is_empty()) {
+            let mut word: Vec<char> = word.chars().collect();
+
+            loop {
+                let separator = usize::from(line_has_word);
+                let word_width = visible_width(&word.iter().collect::<String>());
+                if current_len + separator + word_width <= width {
+                    if line_has_word {
+                        current.push(' ');
                     }
+                    current.extend(word.iter());
+                    current_len += separator + word_width;
+                    line_has_word = true;
+                    break;
+                }
+
+                if line_has_word {
+                    out.push_str(&current);
+                    out.push('\n');
+                    current = continuation.clone();
+                    current_len = current.chars().count();
+                    line_has_word = false;
+                    continue;
+                }
+
+                // Word longer than the available space: hard split it
+                let available = width.saturating_sub(current_len).max(1);
+                let rest = word.split_off(visible_split_index(&word, available));
+                current.extend(word.iter());
+                out.push_str(&current);
+                out.push('\n');
+                current = continuation.clone();
+                current_len = current.chars().count();
+                word = rest;
+                if word.is_empty() {
+                    break;
                 }
-                
-                writeln!(writer, "Caused by: {}", err)?;
-                source = err.source();
-                depth += 1;
             }
         }
-        
-        // If the error has backtrace support (via ErrorCompat trait)
-        // we would include it here
-        
-        Ok(())
-    }
 
-    fn report_json<W, E>(
-        &self,
-        error: &E,
-        config: &ErrorReportConfig,
-        writer: &mut W,
-    ) -> io::Result<()>
-    where
-        W: Write,
-        E: std::error::Error,
-    {
-        // Implementation of JSON error reporting would go here
-        // This would serialize the error chain and related information to JSON
-        writeln!(writer, "{{\"error\": \"{}\"}}", error.to_string().replace("\"", "\\\""))?;
-        Ok(())
+        if line_has_word {
+            out.push_str(&current);
+            out.push('\n');
+        }
     }
 
-    fn report_markdown<W, E>(
-        &self,
-        error: &E,
-        config: &ErrorReportConfig,
-        writer: &mut W,
-    ) -> io::Result<()>
-    where
-        W: Write,
-        E: std::error::Error,
-    {
-        // Implementation of Markdown error reporting would go here
-        writeln!(writer, "## Error\n\n```")?;
-        writeln!(writer, "{}", error)?;
-        writeln!(writer, "```")?;
-        Ok(())
-    }    fn report_html<W, E>(
-        &self,
-        error: &E,
-        config: &ErrorReportConfig,
-        writer: &mut W,
-    ) -> io::Result<()>
-    where
-        W: Write,
-        E: std::error::Error,
-    {
-        // Implementation of HTML error reporting would go here
-        writeln!(
-            writer,
-            "<div class=\"error\"><pre>{}</pre></div>",
-            error.to_string().replace("<", "&lt;").replace(">", "&gt;")
-        )?;
-        Ok(())
+    out
+}
+
+// Index of the char after the first `columns` visible characters, never splitting an escape sequence
+fn visible_split_index(word: &[char], columns: usize) -> usize {
+    let mut seen = 0;
+    let mut in_escape = false;
+    for (index, &c) in word.iter().enumerate() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (true, c) if c.is_ascii_alphabetic() => in_escape = false,
+            (true, _) => {}
+            (false, _) => {
+                if seen == columns {
+                    return index;
+                }
+                seen += 1;
+            }
+        }
     }
+    word.len()
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::error::report::io_error_fields;
+    use crate::common::error::types::{Autocorrection, FixType, RecoveryAction};
     use std::error::Error;
     use std::fmt;
 
@@ -222,6 +1621,7 @@ mod tests {
             max_chain_depth: None,
             pretty_print_json: false,
             include_diagnostics: false,
+            ..Default::default()
         };
 
         // Generate report as string
@@ -257,6 +1657,7 @@ mod tests {
             max_chain_depth: None,
             pretty_print_json: false,
             include_diagnostics: false,
+            ..Default::default()
         };
 
         // Generate report as string
@@ -291,4 +1692,527 @@ mod tests {
         assert!(report.contains("\"error\""));
         assert!(report.contains("JSON test error"));
     }
+
+    // Sink capturing rendered reports for inspection
+    #[derive(Default)]
+    struct CaptureSink {
+        reports: Mutex<Vec<String>>,
+    }
+
+    impl ReportSink for CaptureSink {
+        fn write_report(&self, rendered: &str) -> io::Result<()> {
+            self.reports.lock().unwrap().push(rendered.to_string());
+            Ok(())
+        }
+    }
+
+    // Sink that always fails
+    struct FailingSink;
+
+    impl ReportSink for FailingSink {
+        fn write_report(&self, _rendered: &str) -> io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "sink closed"))
+        }
+    }
+
+    #[test]
+    fn test_build_report_exposes_chain() {
+        let error = TestError {
+            message: "Outer".to_string(),
+            source: Some(Box::new(TestError {
+                message: "Inner".to_string(),
+                source: None,
+            })),
+        };
+
+        let reporter = ErrorReporter::new();
+        let config = ErrorReportConfig {
+            max_chain_depth: Some(1),
+            ..Default::default()
+        };
+        let report = reporter.build_report(&error, &config);
+
+        assert_eq!(report.message, "Outer");
+        assert_eq!(report.chain, vec!["Inner".to_string()]);
+        assert!(!report.chain_truncated);
+        assert!(!report.is_aklypse_error());
+
+        // Rendering the prebuilt report shows its id
+        let mut rendered = Vec::new();
+        reporter.render(&report, &config, &mut rendered).unwrap();
+        let rendered = String::from_utf8(rendered).unwrap();
+        assert!(rendered.contains(&format!("Report ID: {}", report.report_id)));
+        assert!(rendered.contains("Caused by: Inner"));
+    }
+
+    #[test]
+    fn test_localized_labels() {
+        let error = TestError {
+            message: "Fallo".to_string(),
+            source: Some(Box::new(TestError {
+                message: "Disco lleno".to_string(),
+                source: None,
+            })),
+        };
+
+        let reporter = ErrorReporter::new();
+        let spanish = ErrorReportConfig::default().with_locale("es-MX");
+        let report = reporter.report_to_string(&error, &spanish);
+        assert!(report.starts_with("Error: Fallo"));
+        assert!(report.contains("Causado por: Disco lleno"));
+
+        // Custom catalogs take precedence, untranslated labels stay English
+        let custom = ErrorReportConfig {
+            label_catalog: Some(Arc::new(LabelCatalog::new().with_translation("xx", ReportLabel::Error, "Oops"))),
+            ..ErrorReportConfig::default().with_locale("xx")
+        };
+        let report = reporter.report_to_string(&error, &custom);
+        assert!(report.starts_with("Oops: Fallo"));
+        assert!(report.contains("Caused by: Disco lleno"));
+    }
+
+    #[test]
+    fn test_recovery_actions_rendered() {
+        let error = crate::common::error::InternalSnafu { message: "quota exceeded".to_string(), source: None }
+            .build()
+            .add_context(
+                ErrorContext::new("uploading")
+                    .with_recovery_action(RecoveryAction::RunCommand("app quota --reset".to_string())),
+            );
+
+        let reporter = ErrorReporter::new();
+        let plain = reporter.report_to_string(&error, &ErrorReportConfig::default());
+        assert!(plain.contains("Recovery actions:\n  - Run `app quota --reset`"));
+
+        let html = reporter.report_to_string(&error, &ErrorReportConfig { format: ErrorReportFormat::Html, ..Default::default() });
+        assert!(html.contains("<li data-kind=\"run_command\">Run `app quota --reset`</li>"));
+    }
+
+    #[test]
+    fn test_autocorrections_rendered_when_enabled() {
+        let error = crate::common::error::NotFoundSnafu {
+            resource_type: "file".to_string(),
+            identifier: "missing.txt".to_string(),
+        }
+        .build();
+
+        let reporter = ErrorReporter::new();
+        let disabled = reporter.report_to_string(&error, &ErrorReportConfig::default());
+        assert!(!disabled.contains("Suggested fixes"));
+
+        let config = ErrorReportConfig {
+            include_autocorrections: true,
+            ..Default::default()
+        };
+        let plain = reporter.report_to_string(&error, &config);
+        assert!(plain.contains("Suggested fixes:"));
+        assert!(plain.contains("(confidence: 70%)"));
+        assert!(plain.contains("$ touch \"missing.txt\""));
+
+        let markdown = reporter.report_to_string(
+            &error,
+            &ErrorReportConfig { format: ErrorReportFormat::Markdown, ..config.clone() },
+        );
+        assert!(markdown.contains("### Suggested fixes"));
+        assert!(markdown.contains("```sh"));
+
+        let json = reporter.report_to_string(&error, &ErrorReportConfig { format: ErrorReportFormat::Json, ..config });
+        assert!(json.contains("\"autocorrections\""));
+    }
+
+    #[test]
+    fn test_precomputed_autocorrections_respect_config() {
+        let error = TestError { message: "boom".to_string(), source: None };
+        let reporter = ErrorReporter::new();
+        let report = reporter
+            .build_report(&error, &ErrorReportConfig::default())
+            .with_autocorrections(vec![Autocorrection::new("Restart the worker", FixType::ManualInterventionRequired, 0.5)]);
+
+        let mut hidden = Vec::new();
+        reporter.render(&report, &ErrorReportConfig::default(), &mut hidden).unwrap();
+        assert!(!String::from_utf8_lossy(&hidden).contains("Restart the worker"));
+
+        let config = ErrorReportConfig { format: ErrorReportFormat::Html, include_autocorrections: true, ..Default::default() };
+        let mut shown = Vec::new();
+        reporter.render(&report, &config, &mut shown).unwrap();
+        let shown = String::from_utf8_lossy(&shown);
+        assert!(shown.contains("<li data-confidence=\"0.50\">Restart the worker"));
+    }
+
+    #[test]
+    fn test_report_batch_csv() {
+        let errors = vec![
+            TestError { message: "plain failure".to_string(), source: None },
+            TestError { message: "row 7, \"quoted\"\nsecond line".to_string(), source: None },
+        ];
+
+        let reporter = ErrorReporter::new();
+        let mut buffer = Vec::new();
+        reporter.report_batch_csv(&errors, &ErrorReportConfig::default(), &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(rows[0], "timestamp,code,category,severity,fingerprint,message,correlation_id");
+        assert!(rows[1].contains(",Error,"));
+        assert!(rows[1].ends_with("plain failure,"));
+        assert!(rows[2].ends_with("\"row 7, \"\"quoted\"\"\nsecond line\","));
+
+        let mut buffer = Vec::new();
+        reporter.report_batch_tsv(&errors[..1], &ErrorReportConfig::default(), &mut buffer).unwrap();
+        let tsv = String::from_utf8(buffer).unwrap();
+        assert_eq!(tsv.lines().next().unwrap().split('\t').count(), 7);
+        assert!(tsv.contains("\tplain failure\t"));
+    }
+
+    #[test]
+    fn test_deterministic_output_is_stable() {
+        let error = TestError {
+            message: "cannot read /var/lib/app/state.db".to_string(),
+            source: None,
+        };
+
+        let reporter = ErrorReporter::new();
+        let config = ErrorReportConfig::default().deterministic();
+        let first = reporter.report_to_string(&error, &config);
+        let second = reporter.report_to_string(&error, &config);
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            "Error: cannot read <abs>/state.db\nReport ID: 00000000000000000000000000\n"
+        );
+    }
+
+    #[test]
+    fn test_field_extractors_cover_foreign_errors() {
+        #[derive(Debug)]
+        struct HttpError {
+            status: u16,
+            source: io::Error,
+        }
+
+        impl fmt::Display for HttpError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "request failed with status {}", self.status)
+            }
+        }
+
+        impl Error for HttpError {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.source)
+            }
+        }
+
+        fn http_status(error: &(dyn Error + 'static)) -> Vec<(String, String)> {
+            error
+                .downcast_ref::<HttpError>()
+                .map(|error| vec![("http.status".to_string(), error.status.to_string())])
+                .unwrap_or_default()
+        }
+
+        let error = HttpError {
+            status: 503,
+            source: io::Error::new(io::ErrorKind::ConnectionReset, "reset by peer"),
+        };
+        let config = ErrorReportConfig::default()
+            .with_field_extractor(http_status)
+            .with_field_extractor(io_error_fields);
+
+        let reporter = ErrorReporter::new();
+        let report = reporter.build_report(&error, &config);
+        assert_eq!(
+            report.fields,
+            vec![
+                ("http.status".to_string(), "503".to_string()),
+                ("io.kind".to_string(), "ConnectionReset".to_string()),
+            ]
+        );
+
+        let plain = reporter.report_to_string(&error, &config);
+        assert!(plain.contains("Fields:\n  http.status: 503\n  io.kind: ConnectionReset\n"));
+
+        let json = reporter.report_to_string(&error, &ErrorReportConfig { format: ErrorReportFormat::Json, ..config });
+        assert!(json.contains("\"fields\": {"));
+        assert!(json.contains("\"http.status\": \"503\""));
+    }
+
+    #[test]
+    fn test_terminal_format_applies_theme() {
+        let error = TestError {
+            message: "Write failed".to_string(),
+            source: Some(Box::new(TestError {
+                message: "disk full".to_string(),
+                source: None,
+            })),
+        };
+
+        let reporter = ErrorReporter::new();
+        let config = ErrorReportConfig {
+            format: ErrorReportFormat::Terminal,
+            ..Default::default()
+        };
+        let report = reporter.report_to_string(&error, &config);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines[0], "\x1b[1;31m[x] Error: Write failed\x1b[0m");
+        assert_eq!(lines[2], "\x1b[36m->\x1b[0m \x1b[36mCaused by\x1b[0m: disk full");
+
+        // Colors do not count towards the wrap width
+        let wrapped = reporter.report_to_string(&error, &config.clone().with_wrap(WrapMode::Width(24)));
+        assert_eq!(wrapped.lines().next().unwrap(), lines[0]);
+
+        let html = reporter.report_to_string(
+            &error,
+            &ErrorReportConfig { format: ErrorReportFormat::Html, ..Default::default() }.with_theme(Theme::vivid()),
+        );
+        assert!(html.contains("<pre style=\"color:#e06c75;border-left:4px solid #e06c75;font-weight:bold;\">"));
+        assert!(html.contains("❌"));
+        assert!(html.contains("↳</span> disk full"));
+
+        // Without a theme HTML output stays unstyled
+        let html = reporter.report_to_string(&error, &ErrorReportConfig { format: ErrorReportFormat::Html, ..Default::default() });
+        assert!(!html.contains("style="));
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1_709_210_096_789);
+        let now = time + std::time::Duration::from_secs(185);
+
+        let config = ErrorReportConfig::default();
+        assert_eq!(config.format_timestamp_at(time, now), "2024-02-29T12:34:56.789Z");
+
+        let config = config.with_timestamp_format(TimestampFormat::UnixSeconds);
+        assert_eq!(config.format_timestamp_at(time, now), "1709210096");
+        let config = config.with_timestamp_format(TimestampFormat::UnixMillis);
+        assert_eq!(config.format_timestamp_at(time, now), "1709210096789");
+
+        // Relative ages are a Terminal nicety; other formats stay absolute
+        let config = config.with_timestamp_format(TimestampFormat::Relative);
+        assert_eq!(config.format_timestamp_at(time, now), "2024-02-29T12:34:56.789Z");
+        let config = ErrorReportConfig { format: ErrorReportFormat::Terminal, ..config };
+        assert_eq!(config.format_timestamp_at(time, now), "3m ago");
+        assert_eq!(config.format_timestamp_at(now, time), "in 3m");
+        assert_eq!(config.format_timestamp_at(time, time), "just now");
+    }
+
+    #[test]
+    fn test_file_sink_rejects_reports_after_close() {
+        let path = std::env::temp_dir().join(format!("aklypse-file-sink-{}.log", ReportId::generate()));
+        let sink = FileSink::open(&path).unwrap();
+
+        sink.write_report("first\n").unwrap();
+        sink.close().unwrap();
+        assert!(sink.write_report("second\n").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\n");
+
+        drop(sink);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_file_sink_round_trip() {
+        use std::io::Read;
+
+        let path = std::env::temp_dir().join(format!("aklypse-file-sink-{}.log.gz", ReportId::generate()));
+        let reporter = ErrorReporter::builder()
+            .sink(
+                "archive",
+                FileSink::open_compressed(&path, Compression::Gzip { level: 6 }).unwrap(),
+                ErrorReportFormat::Json,
+            )
+            .build();
+
+        let error = TestError { message: "compressed".to_string(), source: None };
+        reporter.report_to_sinks(&error).unwrap();
+        reporter.close_sinks().unwrap();
+
+        let mut decoded = String::new();
+        flate2::read::MultiGzDecoder::new(File::open(&path).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(decoded.contains("\"error\": \"compressed\""));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_file_sink_round_trip() {
+        let path = std::env::temp_dir().join(format!("aklypse-file-sink-{}.log.zst", ReportId::generate()));
+        let sink = FileSink::open_compressed(&path, Compression::Zstd { level: 3 }).unwrap();
+        sink.write_report("first\n").unwrap();
+        sink.close().unwrap();
+
+        // A second session appends another frame to the same file
+        let sink = FileSink::open_compressed(&path, Compression::Zstd { level: 3 }).unwrap();
+        sink.write_report("second\n").unwrap();
+        drop(sink);
+
+        let decoded = zstd::stream::decode_all(File::open(&path).unwrap()).unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), "first\nsecond\n");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_compact_style_is_single_line() {
+        let error = TestError {
+            message: "Write failed".to_string(),
+            source: Some(Box::new(TestError {
+                message: "disk full".to_string(),
+                source: None,
+            })),
+        };
+
+        let reporter = ErrorReporter::new();
+        let report = reporter.report_to_string(&error, &ErrorReportConfig::default().compact());
+
+        assert_eq!(report.lines().count(), 1);
+        assert!(report.starts_with("error=\"Write failed\" report_id="));
+        assert!(report.contains("severity=Error cause=\"disk full\""));
+
+        let json = ErrorReportConfig {
+            format: ErrorReportFormat::Json,
+            ..ErrorReportConfig::default().compact()
+        };
+        assert_eq!(reporter.report_to_string(&error, &json).lines().count(), 1);
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let wrapped = wrap_text("Caused by: the quick brown fox jumps\n  Tags: a, b\n", 16);
+        assert_eq!(
+            wrapped,
+            "Caused by: the\n  quick brown\n  fox jumps\n  Tags: a, b\n"
+        );
+
+        // Overlong words are split instead of overflowing
+        let wrapped = wrap_text("abcdefghij", 4);
+        assert_eq!(wrapped, "abcd\n  ef\n  gh\n  ij\n");
+    }
+
+    #[test]
+    fn test_plain_wrapping_respects_width() {
+        let error = TestError {
+            message: "a rather long error message that certainly exceeds the configured width".to_string(),
+            source: None,
+        };
+
+        let reporter = ErrorReporter::new();
+        let config = ErrorReportConfig::default().with_wrap(WrapMode::Width(30));
+        let report = reporter.report_to_string(&error, &config);
+
+        assert!(report.lines().all(|line| line.chars().count() <= 30));
+        assert!(report.lines().count() > 3);
+    }
+
+    #[test]
+    fn test_builder_fans_out_to_all_sinks() {
+        let plain = Arc::new(CaptureSink::default());
+        let json = Arc::new(CaptureSink::default());
+
+        let reporter = ErrorReporter::builder()
+            .sink("plain", plain.clone(), ErrorReportFormat::Plain)
+            .sink("json", json.clone(), ErrorReportFormat::Json)
+            .build();
+
+        let error = TestError {
+            message: "Fanout error".to_string(),
+            source: None,
+        };
+
+        let report_id = reporter.report_to_sinks(&error).unwrap();
+        assert_eq!(reporter.sink_names().collect::<Vec<_>>(), vec!["plain", "json"]);
+
+        let plain_reports = plain.reports.lock().unwrap();
+        let json_reports = json.reports.lock().unwrap();
+        assert_eq!(plain_reports.len(), 1);
+        assert_eq!(json_reports.len(), 1);
+        assert!(plain_reports[0].starts_with("Error: Fanout error"));
+        assert!(json_reports[0].starts_with("{"));
+
+        // Every sink carries the same report id
+        assert!(plain_reports[0].contains(report_id.as_str()));
+        assert!(json_reports[0].contains(report_id.as_str()));
+    }
+
+    #[test]
+    fn test_report_store_indexes_fanned_out_reports() {
+        let store = Arc::new(ReportStore::new(16));
+        let reporter = ErrorReporter::builder()
+            .with_report_store(store.clone())
+            .build();
+
+        let error = TestError {
+            message: "Indexed error".to_string(),
+            source: None,
+        };
+
+        let report_id = reporter.report_to_sinks(&error).unwrap();
+        let stored = store.get(&report_id).expect("report should be indexed");
+        assert_eq!(stored.message, "Indexed error");
+    }
+
+    #[test]
+    fn test_failing_sink_does_not_block_others() {
+        let capture = Arc::new(CaptureSink::default());
+
+        let reporter = ErrorReporter::builder()
+            .add_sink(
+                SinkRegistration::new("broken", FailingSink)
+                    .with_failure_policy(SinkFailurePolicy::Propagate),
+            )
+            .sink("capture", capture.clone(), ErrorReportFormat::Plain)
+            .build();
+
+        let error = TestError {
+            message: "Isolated error".to_string(),
+            source: None,
+        };
+
+        let result = reporter.report_to_sinks(&error);
+        assert!(matches!(result, Err(AklypseError::Io { .. })));
+        assert_eq!(capture.reports.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sink_tag_routing() {
+        let billing = Arc::new(CaptureSink::default());
+        let flaky = Arc::new(CaptureSink::default());
+        let reporter = ErrorReporter::builder()
+            .add_sink(SinkRegistration::from_arc("billing", billing.clone()).with_tag_ns("team"))
+            .add_sink(SinkRegistration::from_arc("flaky", flaky.clone()).with_tag("flaky"))
+            .build();
+
+        let error = crate::common::error::InternalSnafu { message: "charge failed".to_string(), source: None }
+            .build()
+            .add_context(ErrorContext::new("charging card").with_tag_ns("team", "billing"))
+            .add_context_msg("handling checkout");
+
+        reporter.report_to_sinks(&error).unwrap();
+        assert_eq!(billing.reports.lock().unwrap().len(), 1);
+        assert!(flaky.reports.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sink_filters() {
+        let capture = Arc::new(CaptureSink::default());
+
+        let reporter = ErrorReporter::builder()
+            .add_sink(
+                SinkRegistration::from_arc("critical-only", capture.clone())
+                    .with_min_severity(ErrorSeverity::Critical),
+            )
+            .build();
+
+        let error = TestError {
+            message: "Not critical".to_string(),
+            source: None,
+        };
+
+        assert!(reporter.report_to_sinks(&error).is_ok());
+        assert!(capture.reports.lock().unwrap().is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,464 @@
+/* src/common/error/retrier.rs */
+#![warn(missing_docs)]
+//! **Brief:** Retry policy executor with exponential backoff, jitter and category filtering.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Fault Tolerance]
+//!  - [Retry Policy]
+//!  - [Circuit Breaker Composition]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `Retrier` is a named, shareable executor built from a `RetryPolicy`,
+//! the counterpart of `CircuitBreaker` for transient failures. By default it
+//! retries only the categories that are usually transient (I/O, network,
+//! timeouts, external services, concurrency, exhausted resources and open
+//! circuits); `retry_on` and `with_retry_if` narrow or replace that choice.
+//!
+//! It composes with a breaker by wrapping its calls: when an attempt is
+//! rejected by an open circuit, the next delay is stretched to the breaker's
+//! `retry_after`, so the retry lands when the circuit probes again instead of
+//! being rejected straight away. A `RateLimited` rejection is waited out the
+//! same way.
+//!
+//! The error returned after the last attempt carries the same context as the
+//! `utils::retry` helpers: the attempt number and the total time spent.
+//!
+//! Retries respect the caller's deadline: the active `utils::deadline` scope,
+//! or the deadline recorded in the failed attempt's context. The retrier
+//! gives up instead of sleeping past it, and the final error records it.
+
+use super::{AklypseError, ErrorCategory, ErrorContext, Result};
+use crate::common::utils::deadline::{self, Deadline};
+use crate::common::utils::jitter::{Jitter, SeededRng};
+use crate::common::utils::retry::{Backoff, RetryPredicate, ATTEMPT_METADATA_KEY, ELAPSED_METADATA_KEY};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the name of the retrier that gave up
+pub const RETRIER_METADATA_KEY: &str = "retrier";
+
+/// Categories retried by `RetryPolicy::default`
+pub const TRANSIENT_CATEGORIES: [ErrorCategory; 7] = [
+    ErrorCategory::Io,
+    ErrorCategory::Network,
+    ErrorCategory::Timeout,
+    ErrorCategory::ExternalService,
+    ErrorCategory::Concurrency,
+    ErrorCategory::ResourceExhaustion,
+    ErrorCategory::CircuitBreaker,
+];
+
+/// How a `Retrier` retries
+#[derive(Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay between attempts
+    pub backoff: Backoff,
+    /// Randomization applied to every backoff delay
+    pub jitter: Option<Arc<dyn Jitter>>,
+    /// Seed of the jitter's random source; drawn from entropy when `None`
+    pub jitter_seed: Option<u64>,
+    /// Give up instead of sleeping past this much time since the first attempt
+    pub max_elapsed: Option<Duration>,
+    /// Categories worth retrying
+    pub retryable_categories: HashSet<ErrorCategory>,
+    /// Extra condition an error of a retryable category must meet
+    pub retry_if: Option<RetryPredicate>,
+}
+
+impl RetryPolicy {
+    /// Three attempts with exponential backoff from 100ms up to 10s, retrying `TRANSIENT_CATEGORIES`
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff::exponential(Duration::from_millis(100), Duration::from_secs(10)),
+            jitter: None,
+            jitter_seed: None,
+            max_elapsed: None,
+            retryable_categories: TRANSIENT_CATEGORIES.into_iter().collect(),
+            retry_if: None,
+        }
+    }
+
+    /// Set the total number of attempts (at least 1)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay between attempts
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Randomize every delay with `jitter`
+    pub fn with_jitter(mut self, jitter: impl Jitter + 'static) -> Self {
+        self.jitter = Some(Arc::new(jitter));
+        self
+    }
+
+    /// Seed the jitter's random source, making delays reproducible
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    /// Stop retrying once the next delay would end past `max_elapsed`
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Retry exactly the given categories
+    pub fn retry_on(mut self, categories: impl IntoIterator<Item = ErrorCategory>) -> Self {
+        self.retryable_categories = categories.into_iter().collect();
+        self
+    }
+
+    /// Only retry errors of a retryable category that also match `predicate`
+    pub fn with_retry_if(mut self, predicate: impl Fn(&AklypseError) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_if = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Whether `error` is worth another attempt
+    pub fn is_retryable(&self, error: &AklypseError) -> bool {
+        self.retryable_categories.contains(&error.category())
+            && self.retry_if.as_ref().is_none_or(|retry_if| retry_if(error))
+    }
+
+    // Delay before the attempt after `attempt`, or None to give up with `error`
+    fn next_delay(&self, attempt: u32, started: Instant, error: &AklypseError, state: &mut AttemptState) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error) {
+            return None;
+        }
+        let mut delay = self.backoff.delay(attempt);
+        if let Some(jitter) = &self.jitter {
+            delay = jitter.apply(delay, state.previous, &mut state.rng);
+            state.previous = delay;
+        }
+        if let Some(retry_after) = retry_after_hint(error) {
+            delay = delay.max(retry_after);
+        }
+        if deadline_of(error).is_some_and(|deadline| delay >= deadline.remaining()) {
+            return None;
+        }
+        match self.max_elapsed {
+            Some(max_elapsed) if started.elapsed() + delay > max_elapsed => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("jitter", &self.jitter)
+            .field("jitter_seed", &self.jitter_seed)
+            .field("max_elapsed", &self.max_elapsed)
+            .field("retryable_categories", &self.retryable_categories)
+            .field("retry_if", &self.retry_if.is_some())
+            .finish()
+    }
+}
+
+// Deadline the attempts run under: the active scope, else the one the error ran into
+fn deadline_of(error: &AklypseError) -> Option<Deadline> {
+    deadline::current().or_else(|| error.get_attachment::<Deadline>().cloned())
+}
+
+// When an open circuit or a rate limiter rejected the attempt, how long until it may pass
+fn retry_after_hint(error: &AklypseError) -> Option<Duration> {
+    match error {
+        AklypseError::CircuitBreakerOpen { retry_after, .. } => *retry_after,
+        AklypseError::RateLimited { retry_after, .. } => Some(*retry_after),
+        AklypseError::WithRichContext { source, .. } => retry_after_hint(source),
+        _ => None,
+    }
+}
+
+// Random source and last delay of one execution
+struct AttemptState {
+    rng: SeededRng,
+    previous: Duration,
+}
+
+/// Metrics collected by the retrier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetrierMetrics {
+    /// Calls to `execute`
+    pub executions: u64,
+    /// Attempts made, first ones included
+    pub attempts: u64,
+    /// Calls that eventually succeeded
+    pub successes: u64,
+    /// Calls failed because the policy gave up
+    pub exhausted: u64,
+}
+
+impl RetrierMetrics {
+    /// Attempts beyond the first of each call
+    pub fn retries(&self) -> u64 {
+        self.attempts.saturating_sub(self.executions)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RetrierCounters {
+    executions: AtomicU64,
+    attempts: AtomicU64,
+    successes: AtomicU64,
+    exhausted: AtomicU64,
+}
+
+/// Named executor retrying operations per a `RetryPolicy`
+#[derive(Debug)]
+pub struct Retrier {
+    name: String,
+    policy: RetryPolicy,
+    counters: RetrierCounters,
+}
+
+impl Retrier {
+    /// Creates a new Retrier instance
+    pub fn new(name: impl Into<String>, policy: RetryPolicy) -> Arc<Self> {
+        Arc::new(Self { name: name.into(), policy, counters: RetrierCounters::default() })
+    }
+
+    /// Name the retrier was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The policy the retrier follows
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Get the current metrics of the retrier
+    pub fn metrics(&self) -> RetrierMetrics {
+        RetrierMetrics {
+            executions: self.counters.executions.load(Ordering::Relaxed),
+            attempts: self.counters.attempts.load(Ordering::Relaxed),
+            successes: self.counters.successes.load(Ordering::Relaxed),
+            exhausted: self.counters.exhausted.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Run `operation` until it succeeds or the policy gives up, sleeping the thread between attempts
+    pub fn execute<F, Ret>(&self, mut operation: F) -> Result<Ret>
+    where
+        F: FnMut() -> Result<Ret>,
+    {
+        let started = Instant::now();
+        self.counters.executions.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.attempt_state();
+        let mut attempt = 1;
+        loop {
+            self.counters.attempts.fetch_add(1, Ordering::Relaxed);
+            match operation() {
+                Ok(value) => return Ok(self.succeeded(value)),
+                Err(error) => match self.policy.next_delay(attempt, started, &error, &mut state) {
+                    Some(delay) => std::thread::sleep(delay),
+                    None => return Err(self.exhausted(error, attempt, started)),
+                },
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Run the async `operation` until it succeeds or the policy gives up, sleeping the task between attempts
+    #[cfg(feature = "tokio")]
+    pub async fn execute_async<F, Fut, Ret>(&self, mut operation: F) -> Result<Ret>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<Ret>>,
+    {
+        let started = Instant::now();
+        self.counters.executions.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.attempt_state();
+        let mut attempt = 1;
+        loop {
+            self.counters.attempts.fetch_add(1, Ordering::Relaxed);
+            match operation().await {
+                Ok(value) => return Ok(self.succeeded(value)),
+                Err(error) => match self.policy.next_delay(attempt, started, &error, &mut state) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(self.exhausted(error, attempt, started)),
+                },
+            }
+            attempt += 1;
+        }
+    }
+
+    // Fresh random source and delay history for one execution
+    fn attempt_state(&self) -> AttemptState {
+        AttemptState { rng: SeededRng::from_seed(self.policy.jitter_seed), previous: Duration::ZERO }
+    }
+
+    fn succeeded<Ret>(&self, value: Ret) -> Ret {
+        self.counters.successes.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    fn exhausted(&self, error: AklypseError, attempt: u32, started: Instant) -> AklypseError {
+        self.counters.exhausted.fetch_add(1, Ordering::Relaxed);
+        let message = if attempt == 1 {
+            format!("Retrier '{}' did not retry the first failure", self.name)
+        } else {
+            format!("Retrier '{}' gave up after {} attempts", self.name, attempt)
+        };
+        let mut context = ErrorContext::new(message)
+            .with_metadata(RETRIER_METADATA_KEY, self.name.clone())
+            .with_metadata(ATTEMPT_METADATA_KEY, attempt.to_string())
+            .with_metadata(ELAPSED_METADATA_KEY, started.elapsed().as_millis().to_string());
+        if let Some(deadline) = deadline_of(&error) {
+            context = context.with_deadline(deadline);
+        }
+        error.add_context(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreaker, CircuitBreakerConfig, CircuitState, NotFoundSnafu, TimeoutSnafu};
+
+    fn timeout() -> AklypseError {
+        TimeoutSnafu { operation: "GET /inventory".to_string(), duration: Duration::from_millis(50) }.build()
+    }
+
+    fn attempts(error: &AklypseError) -> Option<String> {
+        error.get_rich_context().and_then(|context| context.metadata.get(ATTEMPT_METADATA_KEY).cloned())
+    }
+
+    #[test]
+    fn test_retries_transient_categories_only() {
+        let retrier = Retrier::new("inventory", RetryPolicy::new().with_backoff(Backoff::Fixed(Duration::ZERO)));
+
+        let mut calls = 0;
+        let value = retrier.execute(|| {
+            calls += 1;
+            if calls < 3 { Err(timeout()) } else { Ok(calls) }
+        });
+        assert_eq!(value.unwrap(), 3);
+
+        let mut calls = 0;
+        let error = retrier
+            .execute(|| {
+                calls += 1;
+                NotFoundSnafu { resource_type: "sku".to_string(), identifier: "A-17".to_string() }.fail::<()>()
+            })
+            .unwrap_err();
+        assert_eq!(calls, 1);
+        assert_eq!(attempts(&error).as_deref(), Some("1"));
+        assert_eq!(error.category(), ErrorCategory::NotFound);
+
+        let metrics = retrier.metrics();
+        assert_eq!((metrics.executions, metrics.attempts, metrics.successes, metrics.exhausted), (2, 4, 1, 1));
+        assert_eq!(metrics.retries(), 2);
+    }
+
+    #[test]
+    fn test_policy_filters_and_caps() {
+        let policy = RetryPolicy::new()
+            .with_backoff(Backoff::Fixed(Duration::ZERO))
+            .with_max_attempts(5)
+            .retry_on([ErrorCategory::Timeout])
+            .with_retry_if(|error| !matches!(error, AklypseError::Timeout { duration, .. } if *duration > Duration::from_secs(1)));
+        assert!(policy.is_retryable(&timeout()));
+        assert!(!policy.is_retryable(&TimeoutSnafu { operation: "slow".to_string(), duration: Duration::from_secs(5) }.build()));
+
+        let retrier = Retrier::new("capped", policy);
+        let error = retrier.execute(|| Err::<(), _>(timeout())).unwrap_err();
+        assert_eq!(attempts(&error).as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn test_composes_with_open_circuit_breaker() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            reset_timeout: Duration::from_millis(30),
+            operation_timeout: None,
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new("inventory", config);
+        let policy = RetryPolicy::new().with_backoff(Backoff::Fixed(Duration::from_millis(1))).with_max_attempts(3);
+        let retrier = Retrier::new("inventory", policy);
+
+        let mut calls = 0;
+        let value = retrier.execute(|| {
+            breaker.execute(|| {
+                calls += 1;
+                if calls == 1 { Err(timeout()) } else { Ok("in stock") }
+            })
+        });
+        // Attempt 1 trips the breaker, attempt 2 is rejected, and waiting out its
+        // retry_after lets attempt 3 probe the half-open circuit
+        assert_eq!(value.unwrap(), "in stock");
+        assert_eq!(calls, 2);
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    }
+
+    #[test]
+    fn test_gives_up_at_caller_deadline() {
+        let policy = RetryPolicy::new().with_backoff(Backoff::Fixed(Duration::from_millis(40))).with_max_attempts(10);
+        let retrier = Retrier::new("deadline", policy);
+        let mut calls = 0;
+        let error = deadline::with_deadline("render page", Duration::from_millis(100), || {
+            retrier.execute(|| {
+                calls += 1;
+                Err::<(), _>(timeout())
+            })
+        }).unwrap_err();
+        assert!(calls < 4);
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.deadline().map(Deadline::operation), Some("render page"));
+        assert_eq!(context.metadata.get(deadline::DEADLINE_METADATA_KEY).map(String::as_str), Some("render page"));
+
+        // An attempt that ran into an expired deadline is not retried outside the scope either
+        let error = retrier
+            .execute(|| {
+                let deadline = Deadline::after("render page", Duration::ZERO);
+                Err::<(), _>(timeout().add_context(ErrorContext::new("Cut short").with_deadline(deadline)))
+            })
+            .unwrap_err();
+        assert_eq!(attempts(&error).as_deref(), Some("1"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_execute_async() {
+        let retrier = Retrier::new("async", RetryPolicy::new().with_backoff(Backoff::Fixed(Duration::from_millis(1))));
+        let mut calls = 0;
+        let value = retrier
+            .execute_async(|| {
+                calls += 1;
+                let attempt = calls;
+                async move { if attempt == 1 { Err(timeout()) } else { Ok(attempt) } }
+            })
+            .await;
+        assert_eq!(value.unwrap(), 2);
+
+        // A call never polled never started
+        drop(retrier.execute_async(|| async { Ok(()) }));
+        assert_eq!((retrier.metrics().executions, retrier.metrics().attempts), (1, 2));
+    }
+}
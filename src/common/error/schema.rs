@@ -0,0 +1,166 @@
+/* src/common/error/schema.rs */
+#![warn(missing_docs)]
+//! **Brief:** Versioned schema of structured error reports and migrations between versions.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Schema Versioning]
+//!  - [Document Migration]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Every structured report (JSON documents and protobuf messages) carries the
+//! `schema_version` it was written with. Whenever the shape of a report
+//! changes, `REPORT_SCHEMA_VERSION` is bumped and a step is appended to
+//! `MIGRATIONS`, so stored documents can be upgraded one version at a time
+//! with `ErrorReport::migrate` instead of silently changing under consumers.
+//!
+//! Version history:
+//! - `1`: documents written before versioning existed (no `schema_version` key)
+//! - `2`: adds the leading `schema_version` key
+//! - `3`: adds the optional `context.recovery_actions` array
+//! - `4`: adds the optional `context.public_message` and `context.message_key`
+
+use super::report::{ErrorReport, JsonValue};
+use super::{ParseSnafu, Result, ValidationSnafu};
+
+/// Schema version of the reports produced by this build
+pub const REPORT_SCHEMA_VERSION: u32 = 4;
+
+/// Key under which structured outputs record their schema version
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+type Migration = fn(&mut Vec<(String, JsonValue)>);
+
+// `MIGRATIONS[n]` upgrades a document from version `n + 1` to `n + 2`; the
+// version key itself is restamped by `ErrorReport::migrate` after each step
+const MIGRATIONS: [Migration; (REPORT_SCHEMA_VERSION - 1) as usize] = [
+    // Version 2 only introduced the version key
+    |_| {},
+    // Versions 3 and 4 only added optional fields
+    |_| {},
+    |_| {},
+];
+
+impl ErrorReport {
+    /// Upgrade a JSON report written with schema `from_version` to `REPORT_SCHEMA_VERSION`
+    ///
+    /// Returns the migrated document as compact JSON. Fails when `from_version`
+    /// is unknown to this build, or when the document records a different
+    /// version than the caller claims.
+    pub fn migrate(from_version: u32, value: &str) -> Result<String> {
+        if from_version == 0 || from_version > REPORT_SCHEMA_VERSION {
+            return Err(ValidationSnafu {
+                field: SCHEMA_VERSION_KEY.to_string(),
+                message: format!(
+                    "cannot migrate from schema version {} (supported: 1..={})",
+                    from_version, REPORT_SCHEMA_VERSION
+                ),
+            }
+            .build());
+        }
+
+        let mut fields = parse_report_object(value)?;
+        let recorded = recorded_version(&fields)?.unwrap_or(1);
+        if recorded != from_version {
+            return Err(ValidationSnafu {
+                field: SCHEMA_VERSION_KEY.to_string(),
+                message: format!("document records schema version {}, not {}", recorded, from_version),
+            }
+            .build());
+        }
+
+        for (index, migration) in MIGRATIONS.iter().enumerate().skip(from_version as usize - 1) {
+            migration(&mut fields);
+            stamp_version(&mut fields, index as u32 + 2);
+        }
+
+        Ok(JsonValue::Object(fields).render(false))
+    }
+
+    /// Schema version recorded in a JSON report (`1` for unversioned documents)
+    pub fn schema_version_of(value: &str) -> Result<u32> {
+        let fields = parse_report_object(value)?;
+        Ok(recorded_version(&fields)?.unwrap_or(1))
+    }
+}
+
+fn parse_report_object(value: &str) -> Result<Vec<(String, JsonValue)>> {
+    let parse_error = |message: String| {
+        ParseSnafu {
+            source: Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, message))
+                as Box<dyn std::error::Error + Send + Sync>,
+            kind: "json".to_string(),
+            context_info: "migrating error report".to_string(),
+        }
+        .build()
+    };
+
+    match JsonValue::parse(value).map_err(parse_error)? {
+        JsonValue::Object(fields) => Ok(fields),
+        _ => Err(parse_error("report is not a JSON object".to_string())),
+    }
+}
+
+fn recorded_version(fields: &[(String, JsonValue)]) -> Result<Option<u32>> {
+    let invalid = || {
+        ValidationSnafu {
+            field: SCHEMA_VERSION_KEY.to_string(),
+            message: "schema version is not a positive integer".to_string(),
+        }
+        .build()
+    };
+
+    match fields.iter().find(|(key, _)| key == SCHEMA_VERSION_KEY) {
+        None => Ok(None),
+        Some((_, JsonValue::Number(number))) => number.parse().map(Some).map_err(|_| invalid()),
+        Some(_) => Err(invalid()),
+    }
+}
+
+// Set the version key, keeping it the first key of the document
+fn stamp_version(fields: &mut Vec<(String, JsonValue)>, version: u32) {
+    fields.retain(|(key, _)| key != SCHEMA_VERSION_KEY);
+    fields.insert(0, (SCHEMA_VERSION_KEY.to_string(), JsonValue::Number(version.to_string())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::report::ReportId;
+
+    #[test]
+    fn test_migrate_unversioned_document() {
+        let legacy = r#"{"report_id":"00000000000000000000000000","error":"disk \"full\"\n","severity":"Error","chain":["ENOSPC"]}"#;
+        let migrated = ErrorReport::migrate(1, legacy).unwrap();
+
+        assert_eq!(
+            migrated,
+            r#"{"schema_version":4,"report_id":"00000000000000000000000000","error":"disk \"full\"\n","severity":"Error","chain":["ENOSPC"]}"#
+        );
+        assert_eq!(ErrorReport::schema_version_of(&migrated).unwrap(), REPORT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_current_documents_migrate_unchanged() {
+        let report = ErrorReport::new("boom").with_report_id(ReportId::from_parts(0, 0));
+        let json = report.to_json(false);
+
+        assert_eq!(ErrorReport::schema_version_of(&json).unwrap(), REPORT_SCHEMA_VERSION);
+        assert_eq!(ErrorReport::migrate(REPORT_SCHEMA_VERSION, &report.to_json(true)).unwrap(), json);
+    }
+
+    #[test]
+    fn test_migrate_rejects_mismatched_or_unknown_versions() {
+        let current = ErrorReport::new("boom").to_json(false);
+
+        assert!(ErrorReport::migrate(1, &current).is_err());
+        assert!(ErrorReport::migrate(REPORT_SCHEMA_VERSION + 1, &current).is_err());
+        assert!(ErrorReport::migrate(0, "{}").is_err());
+        assert!(ErrorReport::migrate(1, "[1, 2]").is_err());
+        assert!(ErrorReport::migrate(1, "{\"error\": ").is_err());
+    }
+}
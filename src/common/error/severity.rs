@@ -0,0 +1,230 @@
+/* src/common/error/severity.rs */
+#![warn(missing_docs)]
+//! **Brief:** Application-wide policy remapping error severities per category.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Classification]
+//!  - [Severity Policy]
+//!  - [Deployment Configuration]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `AklypseError::severity()` consults the installed `SeverityPolicy`, so
+//! deployments can tune severities without touching the code creating the
+//! errors (e.g. treat every configuration error as `Critical` in production).
+//! A policy has two kinds of rules per category:
+//! - a default, replacing `ErrorSeverity::Error` for errors carrying no rich context
+//! - a minimum, raising the severity of every error of the category
+//!
+//! The installed `LevelMapping` decides once which `tracing` (and, with the
+//! `log` feature, `log`) level each severity is emitted at, so every
+//! integration agrees on what counts as a warning and what as an error:
+//! `TracingSink`, the panic hook's fallback and `BusFilter::with_min_level`
+//! all go through it.
+
+use super::types::{ErrorCategory, ErrorSeverity};
+use super::AklypseError;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tracing::Level;
+
+static INSTALLED_POLICY: RwLock<Option<SeverityPolicy>> = RwLock::new(None);
+
+static INSTALLED_LEVELS: RwLock<LevelMapping> = RwLock::new(LevelMapping::DEFAULT);
+
+// `tracing` needs the level of an event at compile time
+macro_rules! event_at {
+    ($target:expr, $level:expr, $($fields:tt)*) => {
+        match $level {
+            tracing::Level::TRACE => tracing::event!(target: $target, tracing::Level::TRACE, $($fields)*),
+            tracing::Level::DEBUG => tracing::event!(target: $target, tracing::Level::DEBUG, $($fields)*),
+            tracing::Level::INFO => tracing::event!(target: $target, tracing::Level::INFO, $($fields)*),
+            tracing::Level::WARN => tracing::event!(target: $target, tracing::Level::WARN, $($fields)*),
+            _ => tracing::event!(target: $target, tracing::Level::ERROR, $($fields)*),
+        }
+    };
+}
+pub(crate) use event_at;
+
+/// Per-category severity rules consulted by `AklypseError::severity()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeverityPolicy {
+    defaults: HashMap<ErrorCategory, ErrorSeverity>,
+    minimums: HashMap<ErrorCategory, ErrorSeverity>,
+}
+
+impl SeverityPolicy {
+    /// Create a policy without rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Severity of errors of `category` that carry no rich context
+    pub fn with_default(mut self, category: ErrorCategory, severity: ErrorSeverity) -> Self {
+        self.defaults.insert(category, severity);
+        self
+    }
+
+    /// Raise every error of `category` to at least `severity`
+    pub fn with_minimum(mut self, category: ErrorCategory, severity: ErrorSeverity) -> Self {
+        self.minimums.insert(category, severity);
+        self
+    }
+
+    /// Apply the rules to an error whose own severity is `explicit`, if it has one
+    pub fn resolve(&self, category: ErrorCategory, explicit: Option<ErrorSeverity>) -> ErrorSeverity {
+        let severity = explicit
+            .or_else(|| self.defaults.get(&category).copied())
+            .unwrap_or(ErrorSeverity::Error);
+        match self.minimums.get(&category) {
+            Some(&minimum) => severity.max(minimum),
+            None => severity,
+        }
+    }
+
+    /// Severity of `error` under this policy, whether or not the policy is installed
+    pub fn severity_of(&self, error: &AklypseError) -> ErrorSeverity {
+        self.resolve(error.category(), error.get_rich_context().map(|context| context.severity))
+    }
+
+    /// Make this the policy consulted by `AklypseError::severity()`, replacing any previous one
+    pub fn install(self) {
+        *INSTALLED_POLICY.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(self);
+    }
+
+    /// Remove the installed policy, restoring the built-in severities
+    pub fn uninstall() {
+        *INSTALLED_POLICY.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    /// Copy of the installed policy, if any
+    pub fn installed() -> Option<SeverityPolicy> {
+        INSTALLED_POLICY.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+}
+
+/// Table mapping each severity to the `tracing` level it is emitted at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelMapping {
+    // Indexed by `ErrorSeverity as usize`
+    levels: [Level; 6],
+}
+
+impl LevelMapping {
+    /// Debug and Info map to their namesakes, Warning to `WARN`, and Error and above to `ERROR`
+    pub const DEFAULT: LevelMapping = LevelMapping {
+        levels: [Level::DEBUG, Level::INFO, Level::WARN, Level::ERROR, Level::ERROR, Level::ERROR],
+    };
+
+    /// Emit `severity` at `level`
+    pub fn with_level(mut self, severity: ErrorSeverity, level: Level) -> Self {
+        self.levels[severity as usize] = level;
+        self
+    }
+
+    /// Level `severity` is emitted at
+    pub fn level(&self, severity: ErrorSeverity) -> Level {
+        self.levels[severity as usize]
+    }
+
+    /// Make this the mapping used by `ErrorSeverity::as_tracing_level`, replacing the previous one
+    pub fn install(self) {
+        *INSTALLED_LEVELS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = self;
+    }
+
+    /// The installed mapping (`LevelMapping::DEFAULT` unless another was installed)
+    pub fn current() -> LevelMapping {
+        *INSTALLED_LEVELS.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for LevelMapping {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ErrorSeverity {
+    /// `tracing` level of this severity under the installed `LevelMapping`
+    pub fn as_tracing_level(self) -> Level {
+        LevelMapping::current().level(self)
+    }
+
+    /// `log` level of this severity under the installed `LevelMapping`
+    #[cfg(feature = "log")]
+    pub fn as_log_level(self) -> log::Level {
+        match self.as_tracing_level() {
+            Level::TRACE => log::Level::Trace,
+            Level::DEBUG => log::Level::Debug,
+            Level::INFO => log::Level::Info,
+            Level::WARN => log::Level::Warn,
+            _ => log::Level::Error,
+        }
+    }
+}
+
+// Severity through the installed policy, or the built-in rules without one
+pub(crate) fn resolve_installed(category: ErrorCategory, explicit: Option<ErrorSeverity>) -> ErrorSeverity {
+    match INSTALLED_POLICY.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref() {
+        Some(policy) => policy.resolve(category, explicit),
+        None => explicit.unwrap_or(ErrorSeverity::Error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::types::ErrorContext;
+    use crate::common::error::ConfigSnafu;
+
+    fn config_error() -> AklypseError {
+        ConfigSnafu { message: "missing key".to_string(), path: None, source: None }.build()
+    }
+
+    #[test]
+    fn test_policy_defaults_and_minimums() {
+        let policy = SeverityPolicy::new()
+            .with_default(ErrorCategory::Configuration, ErrorSeverity::Warning)
+            .with_minimum(ErrorCategory::Timeout, ErrorSeverity::Critical);
+
+        assert_eq!(policy.severity_of(&config_error()), ErrorSeverity::Warning);
+        let with_context = config_error().add_context(ErrorContext::new("ctx").with_severity(ErrorSeverity::Info));
+        assert_eq!(policy.severity_of(&with_context), ErrorSeverity::Info);
+
+        assert_eq!(policy.resolve(ErrorCategory::Timeout, Some(ErrorSeverity::Info)), ErrorSeverity::Critical);
+        assert_eq!(policy.resolve(ErrorCategory::Timeout, Some(ErrorSeverity::Fatal)), ErrorSeverity::Fatal);
+        assert_eq!(policy.resolve(ErrorCategory::Io, None), ErrorSeverity::Error);
+    }
+
+    #[test]
+    fn test_installed_policy_is_consulted() {
+        // Authentication errors are not produced by any other test
+        let error = config_error();
+        let category = ErrorCategory::Authentication;
+        assert_eq!(resolve_installed(category, None), ErrorSeverity::Error);
+
+        SeverityPolicy::new().with_minimum(category, ErrorSeverity::Fatal).install();
+        assert_eq!(resolve_installed(category, None), ErrorSeverity::Fatal);
+        assert_eq!(error.severity(), ErrorSeverity::Error);
+        SeverityPolicy::uninstall();
+
+        assert!(SeverityPolicy::installed().is_none());
+        assert!(ErrorSeverity::Fatal > ErrorSeverity::Critical);
+    }
+
+    #[test]
+    fn test_level_mapping() {
+        let mapping = LevelMapping::DEFAULT.with_level(ErrorSeverity::Warning, Level::ERROR);
+        assert_eq!(mapping.level(ErrorSeverity::Warning), Level::ERROR);
+        assert_eq!(mapping.level(ErrorSeverity::Debug), Level::DEBUG);
+        assert_eq!(LevelMapping::DEFAULT.level(ErrorSeverity::Fatal), Level::ERROR);
+
+        // The default mapping is installed unless a test replaces it
+        assert_eq!(ErrorSeverity::Warning.as_tracing_level(), Level::WARN);
+        #[cfg(feature = "log")]
+        assert_eq!(ErrorSeverity::Info.as_log_level(), log::Level::Info);
+    }
+}
@@ -0,0 +1,416 @@
+/* src/common/error/stream.rs */
+#![warn(missing_docs)]
+//! **Brief:** Incremental rendering of very large error sets.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Streaming Output]
+//!  - [Batch Summaries]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Batch imports can fail with a `MultipleErrors` holding thousands of
+//! children. Rendering those through the regular formats would materialize
+//! one giant string; the streaming renderer instead writes one entry per
+//! child as it goes, emits pagination markers, and can stop after a cap,
+//! summarizing the rest by category. Memory use is bounded by the number of
+//! error categories, not the number of errors.
+
+use super::labels::ReportLabel;
+use super::report::{ErrorReport, JsonValue};
+use super::reporter::{escape_html, ErrorReportConfig, ErrorReporter};
+use super::types::{ErrorCategory, ErrorReportFormat};
+use super::AklypseError;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Options controlling the streaming renderer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamOptions {
+    /// Emit a pagination marker (and flush the writer) every `page_size` entries
+    pub page_size: Option<usize>,
+    /// Render at most this many entries and summarize the rest by category
+    pub max_items: Option<usize>,
+}
+
+impl StreamOptions {
+    /// Render every entry without pagination
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a pagination marker every `page_size` entries
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
+    /// Stop after `max_items` entries and summarize the remainder
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+}
+
+/// Outcome of a streaming render
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StreamSummary {
+    /// Entries written in full
+    pub rendered: usize,
+    /// Entries skipped because of `max_items`
+    pub omitted: usize,
+    /// Skipped entries per category, most frequent first
+    pub omitted_by_category: Vec<(ErrorCategory, usize)>,
+}
+
+impl ErrorReporter {
+    /// Stream an error, expanding a `MultipleErrors` into one entry per child
+    ///
+    /// Errors that are not (possibly context-wrapped) `MultipleErrors` are
+    /// rendered as a single entry.
+    pub fn report_multiple<W>(
+        &self,
+        error: &AklypseError,
+        config: &ErrorReportConfig,
+        options: StreamOptions,
+        writer: &mut W,
+    ) -> io::Result<StreamSummary>
+    where
+        W: Write,
+    {
+        let mut current = error;
+        while let AklypseError::WithRichContext { source, .. } = current {
+            current = source;
+        }
+
+        match current {
+            AklypseError::MultipleErrors { errors, .. } => {
+                self.write_stream_header(error, errors.len(), config, writer)?;
+                self.report_stream(errors, config, options, writer)
+            }
+            _ => self.report_stream(std::iter::once(error), config, options, writer),
+        }
+    }
+
+    /// Render errors one at a time as they are pulled from `errors`
+    ///
+    /// Plain, Markdown and HTML output list one line per error; JSON output
+    /// is newline-delimited, one compact report per line, with pagination
+    /// markers and the summary emitted as their own JSON objects.
+    pub fn report_stream<'a, I, E, W>(
+        &self,
+        errors: I,
+        config: &ErrorReportConfig,
+        options: StreamOptions,
+        writer: &mut W,
+    ) -> io::Result<StreamSummary>
+    where
+        I: IntoIterator<Item = &'a E>,
+        E: std::error::Error + 'static,
+        W: Write,
+    {
+        let mut summary = StreamSummary::default();
+        let mut omitted: HashMap<ErrorCategory, usize> = HashMap::new();
+
+        if config.format == ErrorReportFormat::Html {
+            writeln!(writer, "<ol class=\"error-stream\">")?;
+        }
+
+        for error in errors {
+            if options.max_items.is_some_and(|max| summary.rendered >= max) {
+                let category = error_category(error);
+                *omitted.entry(category).or_default() += 1;
+                summary.omitted += 1;
+                continue;
+            }
+
+            if let Some(page_size) = options.page_size {
+                if summary.rendered > 0 && summary.rendered % page_size == 0 {
+                    writer.flush()?;
+                    self.write_page_marker(summary.rendered / page_size + 1, summary.rendered + 1, config, writer)?;
+                }
+            }
+
+            let report = self.build_report(error, config);
+            summary.rendered += 1;
+            self.write_stream_entry(summary.rendered, &report, config, writer)?;
+        }
+
+        let mut by_category: Vec<_> = omitted.into_iter().collect();
+        by_category.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| format!("{:?}", a.0).cmp(&format!("{:?}", b.0))));
+        summary.omitted_by_category = by_category;
+
+        if summary.omitted > 0 {
+            self.write_omitted_summary(&summary, config, writer)?;
+        }
+        if config.format == ErrorReportFormat::Html {
+            writeln!(writer, "</ol>")?;
+        }
+
+        writer.flush()?;
+        Ok(summary)
+    }
+
+    fn write_stream_header<W>(
+        &self,
+        error: &AklypseError,
+        count: usize,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let message = error.to_string();
+        match config.format {
+            ErrorReportFormat::Plain | ErrorReportFormat::Terminal => writeln!(
+                writer,
+                "{}: {} ({})",
+                config.label(ReportLabel::Error),
+                message,
+                group_thousands(count)
+            ),
+            ErrorReportFormat::Markdown => writeln!(
+                writer,
+                "## {}: {} ({})\n",
+                config.label(ReportLabel::Error),
+                message,
+                group_thousands(count)
+            ),
+            ErrorReportFormat::Html => writeln!(
+                writer,
+                "<p class=\"error\" data-count=\"{}\">{}</p>",
+                count,
+                escape_html(&message)
+            ),
+            ErrorReportFormat::Json => writeln!(
+                writer,
+                "{}",
+                JsonValue::Object(vec![
+                    ("error".to_string(), JsonValue::string(message)),
+                    ("count".to_string(), JsonValue::Number(count.to_string())),
+                ])
+                .render(false)
+            ),
+        }
+    }
+
+    fn write_stream_entry<W>(
+        &self,
+        index: usize,
+        report: &ErrorReport,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let category = report.category.unwrap_or(ErrorCategory::Unspecified);
+        match config.format {
+            ErrorReportFormat::Plain | ErrorReportFormat::Terminal => {
+                writeln!(writer, "  {}. [{:?}] {}", index, category, report.message)
+            }
+            ErrorReportFormat::Markdown => writeln!(writer, "{}. **{:?}** {}", index, category, report.message),
+            ErrorReportFormat::Html => writeln!(
+                writer,
+                "<li data-category=\"{:?}\">{}</li>",
+                category,
+                escape_html(&report.message)
+            ),
+            ErrorReportFormat::Json => writeln!(writer, "{}", report.to_json(false)),
+        }
+    }
+
+    fn write_page_marker<W>(
+        &self,
+        page: usize,
+        first_index: usize,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let label = config.label(ReportLabel::Page);
+        match config.format {
+            ErrorReportFormat::Plain | ErrorReportFormat::Terminal => writeln!(writer, "-- {} {} --", label, page),
+            ErrorReportFormat::Markdown => writeln!(writer, "\n#### {} {}\n", label, page),
+            ErrorReportFormat::Html => writeln!(
+                writer,
+                "<li class=\"error-page\" data-page=\"{}\" value=\"{}\">{} {}</li>",
+                page,
+                first_index,
+                escape_html(label),
+                page
+            ),
+            ErrorReportFormat::Json => writeln!(
+                writer,
+                "{}",
+                JsonValue::Object(vec![("page".to_string(), JsonValue::Number(page.to_string()))]).render(false)
+            ),
+        }
+    }
+
+    fn write_omitted_summary<W>(
+        &self,
+        summary: &StreamSummary,
+        config: &ErrorReportConfig,
+        writer: &mut W,
+    ) -> io::Result<()>
+    where
+        W: Write,
+    {
+        if config.format == ErrorReportFormat::Json {
+            let by_category = summary
+                .omitted_by_category
+                .iter()
+                .map(|(category, count)| (format!("{:?}", category), JsonValue::Number(count.to_string())))
+                .collect();
+            let value = JsonValue::Object(vec![
+                ("omitted".to_string(), JsonValue::Number(summary.omitted.to_string())),
+                ("omitted_by_category".to_string(), JsonValue::Object(by_category)),
+            ]);
+            return writeln!(writer, "{}", value.render(false));
+        }
+
+        let text = match summary.omitted_by_category.as_slice() {
+            [(category, _)] => config
+                .label(ReportLabel::MoreOfCategory)
+                .replace("{count}", &group_thousands(summary.omitted))
+                .replace("{category}", &format!("{:?}", category)),
+            breakdown => {
+                let breakdown = breakdown
+                    .iter()
+                    .map(|(category, count)| format!("{:?}: {}", category, group_thousands(*count)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                config
+                    .label(ReportLabel::MoreOmitted)
+                    .replace("{count}", &group_thousands(summary.omitted))
+                    .replace("{breakdown}", &breakdown)
+            }
+        };
+
+        match config.format {
+            ErrorReportFormat::Html => writeln!(writer, "<li class=\"error-omitted\">{}</li>", escape_html(&text)),
+            ErrorReportFormat::Markdown => writeln!(writer, "\n_{}_", text),
+            _ => writeln!(writer, "  {}", text),
+        }
+    }
+}
+
+// Category of an arbitrary error, without building a full report
+fn error_category<E>(error: &E) -> ErrorCategory
+where
+    E: std::error::Error + 'static,
+{
+    let error_ref: &(dyn std::error::Error + 'static) = error;
+    error_ref
+        .downcast_ref::<AklypseError>()
+        .map_or(ErrorCategory::Unspecified, AklypseError::category)
+}
+
+// 4213 -> "4,213"
+fn group_thousands(value: usize) -> String {
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{MultipleErrorsSnafu, NotFoundSnafu, ValidationSnafu};
+
+    fn validation_errors(count: usize) -> Vec<AklypseError> {
+        (0..count)
+            .map(|index| {
+                ValidationSnafu {
+                    field: format!("row_{}", index),
+                    message: "must not be empty".to_string(),
+                }
+                .build()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_group_thousands() {
+        assert_eq!(group_thousands(7), "7");
+        assert_eq!(group_thousands(4_213), "4,213");
+        assert_eq!(group_thousands(1_000_000), "1,000,000");
+    }
+
+    #[test]
+    fn test_stream_caps_and_summarizes() {
+        let errors = validation_errors(4_223);
+        let reporter = ErrorReporter::new();
+        let mut buffer = Vec::new();
+
+        let summary = reporter
+            .report_stream(&errors, &ErrorReportConfig::default(), StreamOptions::new().with_max_items(10), &mut buffer)
+            .unwrap();
+
+        assert_eq!(summary.rendered, 10);
+        assert_eq!(summary.omitted, 4_213);
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(output.lines().count(), 11);
+        assert!(output.ends_with("... and 4,213 more of category Validation\n"));
+    }
+
+    #[test]
+    fn test_stream_paginates_multiple_errors() {
+        let mut errors = validation_errors(5);
+        errors.push(
+            NotFoundSnafu {
+                resource_type: "file".to_string(),
+                identifier: "a.csv".to_string(),
+            }
+            .build(),
+        );
+        let error = MultipleErrorsSnafu { errors }.build();
+
+        let reporter = ErrorReporter::new();
+        let mut buffer = Vec::new();
+        let options = StreamOptions::new().with_page_size(2).with_max_items(4);
+        reporter
+            .report_multiple(&error, &ErrorReportConfig::default(), options, &mut buffer)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].ends_with("(6)"));
+        assert!(lines[1].starts_with("  1. [Validation]"));
+        assert_eq!(lines[3], "-- Page 2 --");
+        assert_eq!(lines[6], "  ... and 2 more (NotFound: 1, Validation: 1)");
+    }
+
+    #[test]
+    fn test_stream_json_lines() {
+        let errors = validation_errors(3);
+        let config = ErrorReportConfig {
+            format: ErrorReportFormat::Json,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        ErrorReporter::new()
+            .report_stream(&errors, &config, StreamOptions::new().with_max_items(2), &mut buffer)
+            .unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("{\"schema_version\":4,\"report_id\":"));
+        assert_eq!(lines[2], "{\"omitted\":1,\"omitted_by_category\":{\"Validation\":1}}");
+    }
+}
@@ -0,0 +1,463 @@
+/* src/common/error/summary.rs */
+#![warn(missing_docs)]
+//! **Brief:** End-of-run digests of many errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Aggregation]
+//!  - [Run Summaries]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! CLI tools and batch jobs often collect every error of a run and want to
+//! print one digest at exit: how many errors of each category, severity and
+//! code occurred, and which distinct errors (grouped by `ErrorReport::fingerprint`)
+//! were the most frequent. `ErrorSummary` computes that digest and renders it
+//! in every report format.
+
+use super::labels::ReportLabel;
+use super::report::{format_rfc3339, ErrorReport, JsonValue};
+use super::reporter::{escape_html, ErrorReportConfig, ReportStyle};
+use super::theme::{Painter, Theme};
+use super::types::{ErrorCategory, ErrorReportFormat, ErrorSeverity};
+use super::AklypseError;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::time::SystemTime;
+
+/// Number of fingerprints kept by `ErrorSummary::from_errors`
+pub const DEFAULT_TOP_FINGERPRINTS: usize = 10;
+
+/// One group of identical errors in a summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FingerprintCount {
+    /// Fingerprint shared by the errors of the group
+    pub fingerprint: String,
+    /// Number of errors in the group
+    pub count: usize,
+    /// Message of the first error seen in the group
+    pub sample_message: String,
+    /// Category of the group, if known
+    pub category: Option<ErrorCategory>,
+    /// Earliest timestamp of an error in the group
+    pub first_seen: SystemTime,
+    /// Latest timestamp of an error in the group
+    pub last_seen: SystemTime,
+}
+
+/// Counts and most frequent errors of a run
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ErrorSummary {
+    /// Number of summarized errors
+    pub total: usize,
+    /// Errors per category, most frequent first
+    pub by_category: Vec<(ErrorCategory, usize)>,
+    /// Errors per severity, most severe first
+    pub by_severity: Vec<(ErrorSeverity, usize)>,
+    /// Errors per code, most frequent first
+    pub by_code: Vec<(String, usize)>,
+    /// Most frequent fingerprints, most frequent first
+    pub top_fingerprints: Vec<FingerprintCount>,
+}
+
+impl ErrorSummary {
+    /// Summarize a set of errors, keeping the `DEFAULT_TOP_FINGERPRINTS` most frequent groups
+    pub fn from_errors(errors: &[AklypseError]) -> Self {
+        let config = summary_config();
+        let reports: Vec<ErrorReport> = errors.iter().map(|error| ErrorReport::from_error(error, &config)).collect();
+        Self::from_reports(&reports, DEFAULT_TOP_FINGERPRINTS)
+    }
+
+    /// Summarize already built reports, keeping the `top` most frequent groups
+    pub fn from_reports<'a, I>(reports: I, top: usize) -> Self
+    where
+        I: IntoIterator<Item = &'a ErrorReport>,
+    {
+        let mut summary = Self::default();
+        let mut categories: HashMap<ErrorCategory, usize> = HashMap::new();
+        let mut severities: HashMap<ErrorSeverity, usize> = HashMap::new();
+        let mut codes: HashMap<String, usize> = HashMap::new();
+        let mut fingerprints: HashMap<String, FingerprintCount> = HashMap::new();
+        // First-seen order breaks ties between equally frequent fingerprints
+        let mut first_seen: Vec<String> = Vec::new();
+
+        for report in reports {
+            summary.total += 1;
+            *categories.entry(report.category.unwrap_or(ErrorCategory::Unspecified)).or_default() += 1;
+            *severities.entry(report.severity).or_default() += 1;
+            if let Some(code) = report.code() {
+                *codes.entry(code).or_default() += 1;
+            }
+
+            let fingerprint = report.fingerprint();
+            let timestamp = report.timestamp();
+            let group = fingerprints.entry(fingerprint.clone()).or_insert_with(|| {
+                first_seen.push(fingerprint.clone());
+                FingerprintCount {
+                    fingerprint,
+                    count: 0,
+                    sample_message: report.message.clone(),
+                    category: report.category,
+                    first_seen: timestamp,
+                    last_seen: timestamp,
+                }
+            });
+            group.count += 1;
+            group.first_seen = group.first_seen.min(timestamp);
+            group.last_seen = group.last_seen.max(timestamp);
+        }
+
+        summary.by_category = sorted_by_count(categories, |category| format!("{:?}", category));
+        summary.by_code = sorted_by_count(codes, Clone::clone);
+
+        let mut by_severity: Vec<_> = severities.into_iter().collect();
+        by_severity.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+        summary.by_severity = by_severity;
+
+        let mut top_fingerprints: Vec<FingerprintCount> = first_seen
+            .iter()
+            .filter_map(|fingerprint| fingerprints.remove(fingerprint))
+            .collect();
+        // Stable sort keeps first-seen order among equal counts
+        top_fingerprints.sort_by_key(|entry| std::cmp::Reverse(entry.count));
+        top_fingerprints.truncate(top);
+        summary.top_fingerprints = top_fingerprints;
+
+        summary
+    }
+
+    /// Whether no errors were summarized
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Render the summary in the configured format
+    pub fn render<W>(&self, config: &ErrorReportConfig, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        if config.style == ReportStyle::Compact {
+            return match config.format {
+                ErrorReportFormat::Json => writeln!(writer, "{}", self.to_json_value().render(false)),
+                _ => self.render_compact(writer),
+            };
+        }
+
+        match config.format {
+            ErrorReportFormat::Plain => self.render_text(config, &Painter::new(None, self.worst_severity()), writer),
+            ErrorReportFormat::Terminal => {
+                let default_theme = Theme::default();
+                let theme = config.theme.as_ref().unwrap_or(&default_theme);
+                self.render_text(config, &Painter::new(Some(theme), self.worst_severity()), writer)
+            }
+            ErrorReportFormat::Json => writeln!(writer, "{}", self.to_json_value().render(config.pretty_print_json)),
+            ErrorReportFormat::Markdown => self.render_markdown(config, writer),
+            ErrorReportFormat::Html => self.render_html(config, writer),
+        }
+    }
+
+    /// Render the summary into a string
+    pub fn render_to_string(&self, config: &ErrorReportConfig) -> String {
+        let mut buffer = Vec::new();
+        let _ = self.render(config, &mut buffer);
+        String::from_utf8_lossy(&buffer).to_string()
+    }
+
+    fn worst_severity(&self) -> ErrorSeverity {
+        self.by_severity.first().map_or(ErrorSeverity::Info, |(severity, _)| *severity)
+    }
+
+    fn render_text<W>(&self, config: &ErrorReportConfig, painter: &Painter<'_>, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let label = |label: ReportLabel| painter.label(config.label(label));
+
+        let header = format!("{}: {}", config.label(ReportLabel::ErrorSummary), self.total);
+        writeln!(writer, "{}", painter.header(&header))?;
+
+        if !self.by_category.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::ByCategory))?;
+            for (category, count) in &self.by_category {
+                writeln!(writer, "  {:?}: {}", category, count)?;
+            }
+        }
+        if !self.by_severity.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::BySeverity))?;
+            for (severity, count) in &self.by_severity {
+                writeln!(writer, "  {:?}: {}", severity, count)?;
+            }
+        }
+        if !self.by_code.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::ByCode))?;
+            for (code, count) in &self.by_code {
+                writeln!(writer, "  {}: {}", code, count)?;
+            }
+        }
+        if !self.top_fingerprints.is_empty() {
+            writeln!(writer, "{}:", label(ReportLabel::MostFrequent))?;
+            for group in &self.top_fingerprints {
+                writeln!(
+                    writer,
+                    "  {:>5}x {} {} {}",
+                    group.count,
+                    painter.muted(&format!("[{}]", group.fingerprint)),
+                    group.sample_message,
+                    painter.muted(&seen_range(group, config))
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_compact<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        let mut fields = vec![format!("total={}", self.total)];
+        fields.extend(self.by_category.iter().map(|(category, count)| format!("category.{:?}={}", category, count)));
+        fields.extend(self.by_severity.iter().map(|(severity, count)| format!("severity.{:?}={}", severity, count)));
+        writeln!(writer, "{}", fields.join(" "))
+    }
+
+    fn render_markdown<W>(&self, config: &ErrorReportConfig, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "## {}\n", config.label(ReportLabel::ErrorSummary))?;
+        writeln!(writer, "**{}:** {}", config.label(ReportLabel::Total), self.total)?;
+
+        let mut table = |title: ReportLabel, rows: Vec<(String, usize)>| -> io::Result<()> {
+            if rows.is_empty() {
+                return Ok(());
+            }
+            writeln!(writer, "\n### {}\n", config.label(title))?;
+            writeln!(writer, "| | |\n|---|---:|")?;
+            for (name, count) in rows {
+                writeln!(writer, "| {} | {} |", name, count)?;
+            }
+            Ok(())
+        };
+        table(ReportLabel::ByCategory, debug_rows(&self.by_category))?;
+        table(ReportLabel::BySeverity, debug_rows(&self.by_severity))?;
+        table(ReportLabel::ByCode, self.by_code.clone())?;
+
+        if !self.top_fingerprints.is_empty() {
+            writeln!(writer, "\n### {}\n", config.label(ReportLabel::MostFrequent))?;
+            for group in &self.top_fingerprints {
+                writeln!(
+                    writer,
+                    "- **{}x** `{}` {} _{}_",
+                    group.count,
+                    group.fingerprint,
+                    group.sample_message,
+                    seen_range(group, config)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn render_html<W>(&self, config: &ErrorReportConfig, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        writeln!(writer, "<div class=\"error-summary\" data-total=\"{}\">", self.total)?;
+        writeln!(
+            writer,
+            "<h3>{}: {}</h3>",
+            escape_html(config.label(ReportLabel::ErrorSummary)),
+            self.total
+        )?;
+
+        let mut table = |title: ReportLabel, rows: Vec<(String, usize)>| -> io::Result<()> {
+            if rows.is_empty() {
+                return Ok(());
+            }
+            writeln!(writer, "<table><caption>{}</caption>", escape_html(config.label(title)))?;
+            for (name, count) in rows {
+                writeln!(writer, "<tr><th>{}</th><td>{}</td></tr>", escape_html(&name), count)?;
+            }
+            writeln!(writer, "</table>")
+        };
+        table(ReportLabel::ByCategory, debug_rows(&self.by_category))?;
+        table(ReportLabel::BySeverity, debug_rows(&self.by_severity))?;
+        table(ReportLabel::ByCode, self.by_code.clone())?;
+
+        if !self.top_fingerprints.is_empty() {
+            writeln!(writer, "<ol class=\"error-summary-top\">")?;
+            for group in &self.top_fingerprints {
+                writeln!(
+                    writer,
+                    "<li data-fingerprint=\"{}\" data-count=\"{}\" data-first-seen=\"{}\" data-last-seen=\"{}\">{} <small>{}</small></li>",
+                    group.fingerprint,
+                    group.count,
+                    format_rfc3339(group.first_seen),
+                    format_rfc3339(group.last_seen),
+                    escape_html(&group.sample_message),
+                    escape_html(&seen_range(group, config))
+                )?;
+            }
+            writeln!(writer, "</ol>")?;
+        }
+
+        writeln!(writer, "</div>")
+    }
+
+    fn to_json_value(&self) -> JsonValue {
+        let counts = |rows: Vec<(String, usize)>| {
+            JsonValue::Object(
+                rows.into_iter()
+                    .map(|(name, count)| (name, JsonValue::Number(count.to_string())))
+                    .collect(),
+            )
+        };
+
+        JsonValue::Object(vec![
+            ("total".to_string(), JsonValue::Number(self.total.to_string())),
+            ("by_category".to_string(), counts(debug_rows(&self.by_category))),
+            ("by_severity".to_string(), counts(debug_rows(&self.by_severity))),
+            ("by_code".to_string(), counts(self.by_code.clone())),
+            (
+                "top_fingerprints".to_string(),
+                JsonValue::Array(
+                    self.top_fingerprints
+                        .iter()
+                        .map(|group| {
+                            JsonValue::Object(vec![
+                                ("fingerprint".to_string(), JsonValue::string(&group.fingerprint)),
+                                ("count".to_string(), JsonValue::Number(group.count.to_string())),
+                                ("message".to_string(), JsonValue::string(&group.sample_message)),
+                                (
+                                    "category".to_string(),
+                                    JsonValue::optional_string(group.category.map(|category| format!("{:?}", category))),
+                                ),
+                                ("first_seen".to_string(), JsonValue::string(format_rfc3339(group.first_seen))),
+                                ("last_seen".to_string(), JsonValue::string(format_rfc3339(group.last_seen))),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+// "(first seen …, last seen …)" using the configured timestamp format
+fn seen_range(group: &FingerprintCount, config: &ErrorReportConfig) -> String {
+    format!(
+        "({} {}, {} {})",
+        config.label(ReportLabel::FirstSeen),
+        config.format_timestamp(group.first_seen),
+        config.label(ReportLabel::LastSeen),
+        config.format_timestamp(group.last_seen)
+    )
+}
+
+// Only what the summary needs: context for timestamps, but no backtraces or autocorrections
+fn summary_config() -> ErrorReportConfig {
+    ErrorReportConfig {
+        include_backtrace: false,
+        include_source_location: false,
+        include_autocorrections: false,
+        ..Default::default()
+    }
+}
+
+// Entries sorted by count (descending), then by name for a stable order
+fn sorted_by_count<K, F>(counts: HashMap<K, usize>, name: F) -> Vec<(K, usize)>
+where
+    K: Eq + Hash,
+    F: Fn(&K) -> String,
+{
+    let mut entries: Vec<_> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| name(&a.0).cmp(&name(&b.0))));
+    entries
+}
+
+fn debug_rows<K: std::fmt::Debug>(rows: &[(K, usize)]) -> Vec<(String, usize)> {
+    rows.iter().map(|(key, count)| (format!("{:?}", key), *count)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{NotFoundSnafu, ValidationSnafu};
+
+    fn sample_errors() -> Vec<AklypseError> {
+        let mut errors: Vec<AklypseError> = (0..3)
+            .map(|row| {
+                ValidationSnafu {
+                    field: format!("row {}", row),
+                    message: "empty".to_string(),
+                }
+                .build()
+            })
+            .collect();
+        errors.push(
+            NotFoundSnafu {
+                resource_type: "file".to_string(),
+                identifier: "input.csv".to_string(),
+            }
+            .build(),
+        );
+        errors
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let summary = ErrorSummary::from_errors(&sample_errors());
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(
+            summary.by_category,
+            vec![(ErrorCategory::Validation, 3), (ErrorCategory::NotFound, 1)]
+        );
+        assert_eq!(summary.by_severity, vec![(ErrorSeverity::Error, 4)]);
+        assert_eq!(summary.by_code[0], ("AKL-VALIDATION".to_string(), 3));
+        assert_eq!(summary.top_fingerprints.len(), 2);
+        assert_eq!(summary.top_fingerprints[0].count, 3);
+        assert_eq!(summary.top_fingerprints[0].category, Some(ErrorCategory::Validation));
+    }
+
+    #[test]
+    fn test_summary_renders_every_format() {
+        let summary = ErrorSummary::from_errors(&sample_errors());
+
+        let plain = summary.render_to_string(&ErrorReportConfig::default());
+        assert!(plain.starts_with("Error summary: 4\nBy category:\n  Validation: 3\n  NotFound: 1\n"));
+
+        for format in [
+            ErrorReportFormat::Json,
+            ErrorReportFormat::Markdown,
+            ErrorReportFormat::Html,
+            ErrorReportFormat::Terminal,
+        ] {
+            let config = ErrorReportConfig { format, ..Default::default() };
+            assert!(summary.render_to_string(&config).contains("Validation"));
+        }
+
+        let json = summary.render_to_string(&ErrorReportConfig {
+            format: ErrorReportFormat::Json,
+            pretty_print_json: false,
+            ..Default::default()
+        });
+        assert!(json.starts_with("{\"total\":4,\"by_category\":{\"Validation\":3,\"NotFound\":1}"));
+
+        let compact = summary.render_to_string(&ErrorReportConfig::default().compact());
+        assert_eq!(compact, "total=4 category.Validation=3 category.NotFound=1 severity.Error=4\n");
+    }
+
+    #[test]
+    fn test_empty_summary() {
+        let summary = ErrorSummary::from_errors(&[]);
+        assert!(summary.is_empty());
+        assert_eq!(summary.render_to_string(&ErrorReportConfig::default()), "Error summary: 0\n");
+    }
+}
@@ -0,0 +1,407 @@
+/* src/common/error/theme.rs */
+#![warn(missing_docs)]
+//! **Brief:** Color themes and severity glyphs for Terminal and HTML reports.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Themes]
+//!  - [Severity Glyphs]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `Theme` decides the colors of the report header (per severity), of
+//! section labels (accent) and of secondary details (muted), plus the glyphs
+//! prefixing the header, cause chain and suggestions. The Terminal format
+//! always renders with a theme (`Theme::classic` unless configured); HTML
+//! output only gains inline styles when a theme is set explicitly.
+
+use super::types::ErrorSeverity;
+
+/// A color usable both as an ANSI escape and as a CSS value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Leave the terminal's / page's color unchanged
+    Default,
+    /// ANSI black
+    Black,
+    /// ANSI red
+    Red,
+    /// ANSI green
+    Green,
+    /// ANSI yellow
+    Yellow,
+    /// ANSI blue
+    Blue,
+    /// ANSI magenta
+    Magenta,
+    /// ANSI cyan
+    Cyan,
+    /// ANSI white
+    White,
+    /// ANSI bright black (gray)
+    BrightBlack,
+    /// ANSI bright red
+    BrightRed,
+    /// ANSI bright yellow
+    BrightYellow,
+    /// ANSI bright cyan
+    BrightCyan,
+    /// 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// ANSI SGR parameters selecting this color as foreground, `None` for `Default`
+    pub fn ansi_foreground(self) -> Option<String> {
+        let code = match self {
+            Color::Default => return None,
+            Color::Black => "30",
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Blue => "34",
+            Color::Magenta => "35",
+            Color::Cyan => "36",
+            Color::White => "37",
+            Color::BrightBlack => "90",
+            Color::BrightRed => "91",
+            Color::BrightYellow => "93",
+            Color::BrightCyan => "96",
+            Color::Rgb(r, g, b) => return Some(format!("38;2;{};{};{}", r, g, b)),
+        };
+        Some(code.to_string())
+    }
+
+    /// CSS color value, `None` for `Default`
+    pub fn css(self) -> Option<String> {
+        let (r, g, b) = match self {
+            Color::Default => return None,
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 49, 49),
+            Color::Green => (13, 188, 121),
+            Color::Yellow => (229, 229, 16),
+            Color::Blue => (36, 114, 200),
+            Color::Magenta => (188, 63, 188),
+            Color::Cyan => (17, 168, 205),
+            Color::White => (229, 229, 229),
+            Color::BrightBlack => (102, 102, 102),
+            Color::BrightRed => (241, 76, 76),
+            Color::BrightYellow => (245, 245, 67),
+            Color::BrightCyan => (41, 184, 219),
+            Color::Rgb(r, g, b) => (r, g, b),
+        };
+        Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+}
+
+/// Glyphs prefixing report sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphSet {
+    /// No glyphs
+    None,
+    /// Plain ASCII markers that survive any terminal or log pipeline
+    #[default]
+    Ascii,
+    /// Emoji markers
+    Emoji,
+}
+
+impl GlyphSet {
+    /// Marker for a report of the given severity
+    pub fn severity(self, severity: ErrorSeverity) -> &'static str {
+        match (self, severity) {
+            (GlyphSet::None, _) => "",
+            (GlyphSet::Ascii, ErrorSeverity::Debug) => "[.]",
+            (GlyphSet::Ascii, ErrorSeverity::Info) => "[i]",
+            (GlyphSet::Ascii, ErrorSeverity::Warning) => "[!]",
+            (GlyphSet::Ascii, ErrorSeverity::Error) => "[x]",
+            (GlyphSet::Ascii, ErrorSeverity::Critical) => "[X]",
+            (GlyphSet::Ascii, ErrorSeverity::Fatal) => "[#]",
+            (GlyphSet::Emoji, ErrorSeverity::Debug) => "🐛",
+            (GlyphSet::Emoji, ErrorSeverity::Info) => "ℹ️",
+            (GlyphSet::Emoji, ErrorSeverity::Warning) => "⚠️",
+            (GlyphSet::Emoji, ErrorSeverity::Error) => "❌",
+            (GlyphSet::Emoji, ErrorSeverity::Critical) => "🔥",
+            (GlyphSet::Emoji, ErrorSeverity::Fatal) => "💀",
+        }
+    }
+
+    /// Marker for an entry of the cause chain
+    pub fn cause(self) -> &'static str {
+        match self {
+            GlyphSet::None => "",
+            GlyphSet::Ascii => "->",
+            GlyphSet::Emoji => "↳",
+        }
+    }
+
+    /// Marker for a suggested fix
+    pub fn suggestion(self) -> &'static str {
+        match self {
+            GlyphSet::None => "-",
+            GlyphSet::Ascii => "*",
+            GlyphSet::Emoji => "💡",
+        }
+    }
+}
+
+/// Colors and glyphs applied to Terminal and HTML reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Name of the theme
+    pub name: String,
+    /// Header color for `ErrorSeverity::Debug`
+    pub debug: Color,
+    /// Header color for `ErrorSeverity::Info`
+    pub info: Color,
+    /// Header color for `ErrorSeverity::Warning`
+    pub warning: Color,
+    /// Header color for `ErrorSeverity::Error`
+    pub error: Color,
+    /// Header color for `ErrorSeverity::Critical`
+    pub critical: Color,
+    /// Header color for `ErrorSeverity::Fatal`
+    pub fatal: Color,
+    /// Color of section labels and glyphs
+    pub accent: Color,
+    /// Color of secondary details (ids, locations, backtraces)
+    pub muted: Color,
+    /// Glyphs prefixing the header, cause chain and suggestions
+    pub glyphs: GlyphSet,
+    /// Render the header in bold
+    pub bold_header: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Theme {
+    /// ANSI palette with ASCII glyphs, safe for every terminal
+    pub fn classic() -> Self {
+        Self {
+            name: "classic".to_string(),
+            debug: Color::BrightBlack,
+            info: Color::Blue,
+            warning: Color::Yellow,
+            error: Color::Red,
+            critical: Color::BrightRed,
+            fatal: Color::Magenta,
+            accent: Color::Cyan,
+            muted: Color::BrightBlack,
+            glyphs: GlyphSet::Ascii,
+            bold_header: true,
+        }
+    }
+
+    /// Softer 24-bit palette with emoji glyphs
+    pub fn vivid() -> Self {
+        Self {
+            name: "vivid".to_string(),
+            debug: Color::Rgb(150, 150, 170),
+            info: Color::Rgb(97, 175, 239),
+            warning: Color::Rgb(229, 192, 123),
+            error: Color::Rgb(224, 108, 117),
+            critical: Color::Rgb(255, 85, 85),
+            fatal: Color::Rgb(255, 0, 170),
+            accent: Color::Rgb(198, 120, 221),
+            muted: Color::Rgb(92, 99, 112),
+            glyphs: GlyphSet::Emoji,
+            bold_header: true,
+        }
+    }
+
+    /// Start a user-defined theme from the classic palette
+    pub fn custom(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::classic()
+        }
+    }
+
+    /// Header color for a severity
+    pub fn severity_color(&self, severity: ErrorSeverity) -> Color {
+        match severity {
+            ErrorSeverity::Debug => self.debug,
+            ErrorSeverity::Info => self.info,
+            ErrorSeverity::Warning => self.warning,
+            ErrorSeverity::Error => self.error,
+            ErrorSeverity::Critical => self.critical,
+            ErrorSeverity::Fatal => self.fatal,
+        }
+    }
+
+    /// Set the header color of a severity
+    pub fn with_severity_color(mut self, severity: ErrorSeverity, color: Color) -> Self {
+        match severity {
+            ErrorSeverity::Debug => self.debug = color,
+            ErrorSeverity::Info => self.info = color,
+            ErrorSeverity::Warning => self.warning = color,
+            ErrorSeverity::Error => self.error = color,
+            ErrorSeverity::Critical => self.critical = color,
+            ErrorSeverity::Fatal => self.fatal = color,
+        }
+        self
+    }
+
+    /// Set the accent color
+    pub fn with_accent(mut self, color: Color) -> Self {
+        self.accent = color;
+        self
+    }
+
+    /// Set the muted color
+    pub fn with_muted(mut self, color: Color) -> Self {
+        self.muted = color;
+        self
+    }
+
+    /// Set the glyph set
+    pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Inline CSS for the HTML report header of a severity
+    pub fn header_css(&self, severity: ErrorSeverity) -> String {
+        let mut css = self
+            .severity_color(severity)
+            .css()
+            .map(|color| format!("color:{};border-left:4px solid {};", color, color))
+            .unwrap_or_default();
+        if self.bold_header {
+            css.push_str("font-weight:bold;");
+        }
+        css
+    }
+}
+
+/// Applies an optional theme while rendering text reports
+///
+/// Without a theme every method returns its input unchanged, which keeps the
+/// Plain format byte-for-byte identical to unthemed output.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Painter<'a> {
+    theme: Option<&'a Theme>,
+    severity: ErrorSeverity,
+}
+
+impl<'a> Painter<'a> {
+    pub(crate) fn new(theme: Option<&'a Theme>, severity: ErrorSeverity) -> Self {
+        Self { theme, severity }
+    }
+
+    /// Report header, prefixed with the severity glyph
+    pub(crate) fn header(&self, text: &str) -> String {
+        match self.theme {
+            None => text.to_string(),
+            Some(theme) => {
+                let glyph = theme.glyphs.severity(self.severity);
+                let text = if glyph.is_empty() { text.to_string() } else { format!("{} {}", glyph, text) };
+                paint(&text, theme.severity_color(self.severity), theme.bold_header)
+            }
+        }
+    }
+
+    /// Section label
+    pub(crate) fn label(&self, text: &str) -> String {
+        match self.theme {
+            None => text.to_string(),
+            Some(theme) => paint(text, theme.accent, false),
+        }
+    }
+
+    /// Secondary detail
+    pub(crate) fn muted(&self, text: &str) -> String {
+        match self.theme {
+            None => text.to_string(),
+            Some(theme) => paint(text, theme.muted, false),
+        }
+    }
+
+    /// Prefix of a cause chain entry (empty without a theme)
+    pub(crate) fn cause_prefix(&self) -> String {
+        match self.theme.map(|theme| (theme, theme.glyphs.cause())) {
+            Some((theme, glyph)) if !glyph.is_empty() => format!("{} ", paint(glyph, theme.accent, false)),
+            _ => String::new(),
+        }
+    }
+
+    /// Bullet of a suggested fix
+    pub(crate) fn suggestion_bullet(&self) -> String {
+        match self.theme {
+            None => "-".to_string(),
+            Some(theme) => paint(theme.glyphs.suggestion(), theme.accent, false),
+        }
+    }
+}
+
+// Wrap text in ANSI SGR sequences
+pub(crate) fn paint(text: &str, color: Color, bold: bool) -> String {
+    let mut params: Vec<String> = Vec::new();
+    if bold {
+        params.push("1".to_string());
+    }
+    if let Some(color) = color.ansi_foreground() {
+        params.push(color);
+    }
+    if params.is_empty() {
+        return text.to_string();
+    }
+    format!("\x1b[{}m{}\x1b[0m", params.join(";"), text)
+}
+
+/// Number of columns `text` occupies on screen, ignoring ANSI escape sequences
+pub(crate) fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in text.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (true, c) if c.is_ascii_alphabetic() => in_escape = false,
+            (true, _) => {}
+            (false, _) => width += 1,
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_painter_without_theme_is_identity() {
+        let painter = Painter::new(None, ErrorSeverity::Error);
+        assert_eq!(painter.header("Error: boom"), "Error: boom");
+        assert_eq!(painter.label("Context"), "Context");
+        assert_eq!(painter.cause_prefix(), "");
+    }
+
+    #[test]
+    fn test_classic_theme_header() {
+        let theme = Theme::classic();
+        let painter = Painter::new(Some(&theme), ErrorSeverity::Warning);
+        let header = painter.header("Error: boom");
+
+        assert_eq!(header, "\x1b[1;33m[!] Error: boom\x1b[0m");
+        assert_eq!(visible_width(&header), "[!] Error: boom".len());
+    }
+
+    #[test]
+    fn test_custom_theme() {
+        let theme = Theme::custom("ops")
+            .with_severity_color(ErrorSeverity::Critical, Color::Rgb(255, 0, 128))
+            .with_glyphs(GlyphSet::Emoji);
+
+        assert_eq!(theme.severity_color(ErrorSeverity::Critical).css().unwrap(), "#ff0080");
+        assert!(theme.header_css(ErrorSeverity::Critical).starts_with("color:#ff0080;"));
+        assert_eq!(theme.glyphs.severity(ErrorSeverity::Critical), "🔥");
+        assert_eq!(Theme::vivid().glyphs, GlyphSet::Emoji);
+    }
+}
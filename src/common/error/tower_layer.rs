@@ -0,0 +1,283 @@
+/* src/common/error/tower_layer.rs */
+#![warn(missing_docs)]
+//! **Brief:** Tower layer turning inbound service errors into reported AklypseErrors and responses.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Service Integration]
+//!  - [Error Reporting]
+//!  - [HTTP Responses]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `AklypseErrorLayer` wraps an HTTP `tower::Service` so its errors never
+//! reach the server: each one becomes an `AklypseError` (kept as is when the
+//! service already fails with one, `Internal` otherwise) with the request's
+//! method, route and correlation header as rich context. The error is handed
+//! to the reporter's sinks on tokio's blocking pool, so slow sinks do not
+//! delay the response, and then mapped to a response, by default a plain
+//! text one whose status follows the error category (`status_for`).
+//!
+//! The wrapped service is infallible; errors from `poll_ready` are answered
+//! on the next call.
+
+use super::reporter::ErrorReporter;
+use super::types::{ErrorCategory, ErrorContext};
+use super::{AklypseError, InternalSnafu};
+use http::{HeaderName, Request, Response, StatusCode};
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Header read for the correlation id unless configured otherwise
+pub const DEFAULT_CORRELATION_HEADER: &str = "x-request-id";
+
+/// Metadata key holding the request method
+pub const METHOD_METADATA_KEY: &str = "http.method";
+
+/// Metadata key holding the request path
+pub const ROUTE_METADATA_KEY: &str = "http.route";
+
+/// Metadata key holding the value of the correlation header
+pub const CORRELATION_METADATA_KEY: &str = "http.correlation_id";
+
+/// Builds the response sent in place of a failed request
+pub type ResponseMapper<B> = Arc<dyn Fn(&AklypseError) -> Response<B> + Send + Sync>;
+
+/// HTTP status matching an error's category
+pub fn status_for(error: &AklypseError) -> StatusCode {
+    match error.category() {
+        ErrorCategory::Validation | ErrorCategory::Parsing => StatusCode::BAD_REQUEST,
+        ErrorCategory::Authentication => StatusCode::UNAUTHORIZED,
+        ErrorCategory::Authorization => StatusCode::FORBIDDEN,
+        ErrorCategory::NotFound => StatusCode::NOT_FOUND,
+        ErrorCategory::StateConflict | ErrorCategory::Concurrency => StatusCode::CONFLICT,
+        ErrorCategory::ResourceExhaustion => StatusCode::TOO_MANY_REQUESTS,
+        ErrorCategory::CircuitBreaker => StatusCode::SERVICE_UNAVAILABLE,
+        ErrorCategory::ExternalService | ErrorCategory::Network => StatusCode::BAD_GATEWAY,
+        ErrorCategory::Timeout => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+// Plain text response naming the status, never leaking error details
+fn default_response<B: From<String>>(error: &AklypseError) -> Response<B> {
+    let status = status_for(error);
+    let mut response = Response::new(B::from(status.canonical_reason().unwrap_or("Error").to_string()));
+    *response.status_mut() = status;
+    response
+}
+
+/// Layer adding `AklypseErrorService` to a tower stack
+pub struct AklypseErrorLayer<B> {
+    reporter: Arc<ErrorReporter>,
+    correlation_header: HeaderName,
+    mapper: ResponseMapper<B>,
+}
+
+impl<B: From<String> + 'static> AklypseErrorLayer<B> {
+    /// Report errors through `reporter` and answer with plain text responses
+    pub fn new(reporter: Arc<ErrorReporter>) -> Self {
+        Self {
+            reporter,
+            correlation_header: HeaderName::from_static(DEFAULT_CORRELATION_HEADER),
+            mapper: Arc::new(default_response::<B>),
+        }
+    }
+}
+
+impl<B> AklypseErrorLayer<B> {
+    /// Read the correlation id from `header` instead of `x-request-id`
+    pub fn with_correlation_header(mut self, header: HeaderName) -> Self {
+        self.correlation_header = header;
+        self
+    }
+
+    /// Build error responses with `mapper`
+    pub fn with_response_mapper<F>(mut self, mapper: F) -> Self
+    where
+        F: Fn(&AklypseError) -> Response<B> + Send + Sync + 'static,
+    {
+        self.mapper = Arc::new(mapper);
+        self
+    }
+}
+
+impl<B> Clone for AklypseErrorLayer<B> {
+    fn clone(&self) -> Self {
+        Self {
+            reporter: self.reporter.clone(),
+            correlation_header: self.correlation_header.clone(),
+            mapper: self.mapper.clone(),
+        }
+    }
+}
+
+impl<B> fmt::Debug for AklypseErrorLayer<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AklypseErrorLayer").field("correlation_header", &self.correlation_header).finish()
+    }
+}
+
+impl<S, B> Layer<S> for AklypseErrorLayer<B> {
+    type Service = AklypseErrorService<S, B>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AklypseErrorService { inner, layer: self.clone(), pending: None }
+    }
+}
+
+/// Service produced by `AklypseErrorLayer`
+pub struct AklypseErrorService<S, B> {
+    inner: S,
+    layer: AklypseErrorLayer<B>,
+    pending: Option<AklypseError>,
+}
+
+impl<S: Clone, B> Clone for AklypseErrorService<S, B> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), layer: self.layer.clone(), pending: None }
+    }
+}
+
+impl<S: fmt::Debug, B> fmt::Debug for AklypseErrorService<S, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AklypseErrorService").field("inner", &self.inner).field("layer", &self.layer).finish()
+    }
+}
+
+impl<S, ReqBody, B> Service<Request<ReqBody>> for AklypseErrorService<S, B>
+where
+    S: Service<Request<ReqBody>, Response = Response<B>>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response<B>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<B>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        if self.pending.is_none() {
+            match self.inner.poll_ready(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(error)) => self.pending = Some(into_aklypse(error.into())),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let context = request_context(&request, &self.layer.correlation_header);
+        let layer = self.layer.clone();
+        if let Some(error) = self.pending.take() {
+            return Box::pin(std::future::ready(Ok(layer.handle(error.add_context(context)))));
+        }
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            match future.await {
+                Ok(response) => Ok(response),
+                Err(error) => Ok(layer.handle(into_aklypse(error.into()).add_context(context))),
+            }
+        })
+    }
+}
+
+impl<B> AklypseErrorLayer<B> {
+    // Report in the background and answer with the mapped response
+    fn handle(&self, error: AklypseError) -> Response<B> {
+        let response = (self.mapper)(&error);
+        let reporter = self.reporter.clone();
+        let report = move || {
+            let _ = reporter.report_to_sinks(&error);
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(report)),
+            Err(_) => report(),
+        }
+        response
+    }
+}
+
+// Keep errors that already are AklypseErrors, wrap anything else as Internal
+fn into_aklypse(error: Box<dyn std::error::Error + Send + Sync>) -> AklypseError {
+    match error.downcast::<AklypseError>() {
+        Ok(error) => *error,
+        Err(error) => InternalSnafu { message: error.to_string(), source: Some(error) }.build(),
+    }
+}
+
+fn request_context<B>(request: &Request<B>, correlation_header: &HeaderName) -> ErrorContext {
+    let method = request.method().as_str();
+    let route = request.uri().path();
+    let mut context = ErrorContext::new(format!("Handling {} {}", method, route))
+        .with_metadata(METHOD_METADATA_KEY, method)
+        .with_metadata(ROUTE_METADATA_KEY, route);
+    if let Some(value) = request.headers().get(correlation_header).and_then(|value| value.to_str().ok()) {
+        context = context.with_metadata(CORRELATION_METADATA_KEY, value);
+    }
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ChannelSink, NotFoundSnafu, SinkRegistration};
+    use std::time::Duration;
+    use tower::{service_fn, ServiceExt};
+
+    fn request(path: &str) -> Request<()> {
+        Request::builder().method("DELETE").uri(path).header("x-request-id", "req-7").body(()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_errors_become_reported_responses() {
+        let (sink, receiver) = ChannelSink::bounded(4);
+        let reporter = ErrorReporter::builder().add_sink(SinkRegistration::new("channel", sink)).build();
+        let layer = AklypseErrorLayer::<String>::new(Arc::new(reporter));
+
+        let service = layer.layer(service_fn(|request: Request<()>| async move {
+            match request.uri().path() {
+                "/users/42" => Err(NotFoundSnafu { resource_type: "user", identifier: "42" }.build()),
+                _ => Ok(Response::new("deleted".to_string())),
+            }
+        }));
+
+        let response = service.clone().oneshot(request("/users/1")).await.unwrap();
+        assert_eq!(response.into_body(), "deleted");
+
+        let response = service.oneshot(request("/users/42")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.into_body(), "Not Found");
+
+        let receiver = Arc::new(std::sync::Mutex::new(receiver));
+        let report = tokio::task::spawn_blocking(move || receiver.lock().unwrap().recv_timeout(Duration::from_secs(5)))
+            .await
+            .unwrap()
+            .expect("error reported");
+        let metadata = &report.context.unwrap().metadata;
+        assert_eq!(metadata.get(METHOD_METADATA_KEY).map(String::as_str), Some("DELETE"));
+        assert_eq!(metadata.get(ROUTE_METADATA_KEY).map(String::as_str), Some("/users/42"));
+        assert_eq!(metadata.get(CORRELATION_METADATA_KEY).map(String::as_str), Some("req-7"));
+    }
+
+    #[tokio::test]
+    async fn test_foreign_errors_are_internal_and_mapper_is_used() {
+        let layer = AklypseErrorLayer::<String>::new(Arc::new(ErrorReporter::new()))
+            .with_response_mapper(|error| Response::new(format!("{:?}", error.category())));
+        let service = layer.layer(service_fn(|_: Request<()>| async {
+            Err::<Response<String>, _>(std::io::Error::other("disk on fire"))
+        }));
+
+        let response = service.oneshot(request("/")).await.unwrap();
+        assert_eq!(response.into_body(), "Internal");
+    }
+}
@@ -0,0 +1,100 @@
+/* src/common/error/tracing_sink.rs */
+#![warn(missing_docs)]
+//! **Brief:** Report sink emitting error reports as `tracing` events.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Error Handling Framework]
+//!  - [Error Reporting]
+//!  - [Report Sinks]
+//!  - [Tracing Integration]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `TracingSink` forwards every fanned-out report to the active `tracing`
+//! subscriber under `TRACING_TARGET`, at the level the installed
+//! `LevelMapping` assigns to the report's severity.
+
+use super::report::ErrorReport;
+use super::reporter::ReportSink;
+use super::severity::event_at;
+use super::types::ErrorSeverity;
+use std::io;
+
+/// Target of the events emitted by `TracingSink`
+pub const TRACING_TARGET: &str = "aklypse::error";
+
+/// Sink emitting reports as `tracing` events
+///
+/// Structured reports carry their id, category and severity as event
+/// fields. Rendered text written without a report is emitted at the level of
+/// `ErrorSeverity::Error`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+impl TracingSink {
+    /// Create a sink
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ReportSink for TracingSink {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        event_at!(TRACING_TARGET, ErrorSeverity::Error.as_tracing_level(), "{}", rendered);
+        Ok(())
+    }
+
+    fn publish(&self, report: &ErrorReport, _rendered: &str) -> io::Result<()> {
+        event_at!(
+            TRACING_TARGET,
+            report.severity.as_tracing_level(),
+            report_id = %report.report_id,
+            category = ?report.category,
+            severity = ?report.severity,
+            "{}",
+            report.message
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::Level;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    #[derive(Clone, Default)]
+    struct Levels(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for Levels {
+        fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+            let metadata = event.metadata();
+            self.0.lock().unwrap().push((*metadata.level(), metadata.target().to_string()));
+        }
+    }
+
+    #[test]
+    fn test_events_use_mapped_levels() {
+        let levels = Levels::default();
+        let subscriber = tracing_subscriber::registry().with(levels.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let mut report = ErrorReport::new("disk almost full");
+            report.severity = ErrorSeverity::Warning;
+            TracingSink::new().publish(&report, "").unwrap();
+            report.severity = ErrorSeverity::Fatal;
+            TracingSink::new().publish(&report, "").unwrap();
+            TracingSink::new().write_report("rendered").unwrap();
+        });
+
+        let recorded = levels.0.lock().unwrap();
+        let levels: Vec<_> = recorded.iter().map(|(level, _)| *level).collect();
+        assert_eq!(levels, vec![Level::WARN, Level::ERROR, Level::ERROR]);
+        assert!(recorded.iter().all(|(_, target)| target == TRACING_TARGET));
+    }
+}
@@ -12,118 +12,202 @@
 // **Author:** Lord Xyn
 // **License:** MIT
 
+use super::attachments::Attachments;
+use super::limits::ContextPolicy;
+use crate::common::utils::deadline::{Deadline, DEADLINE_BUDGET_METADATA_KEY, DEADLINE_METADATA_KEY};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::fmt;
+use std::time::{Duration, SystemTime};
 use std::sync::Arc;
 
 // Note: Depending on feature flags you might use chrono::DateTime<Utc> instead of SystemTime
 type TimestampType = SystemTime;
 
 /// Severity level for errors
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorSeverity {
+    /// Diagnostic detail, not a problem
     Debug,
+    /// Worth knowing, no action needed
     Info,
+    /// Unexpected but recovered from
     Warning,
+    /// The operation failed
     Error,
+    /// A component cannot work
     Critical,
+    /// The process cannot continue
+    Fatal,
 }
 
 /// Categorization of errors
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorCategory {
+    /// File system and other I/O
     Io,
+    /// Malformed input
     Parsing,
+    /// Connections and transport
     Network,
+    /// Missing or invalid settings
     Configuration,
+    /// Values rejected by validation rules
     Validation,
+    /// Bugs and broken invariants
     Internal,
+    /// Calls rejected by an open circuit
     CircuitBreaker,
+    /// Operations that ran out of time
     Timeout,
+    /// Limits, quotas and rate limits
     ResourceExhaustion,
+    /// Missing resources
     NotFound,
+    /// Locks, channels and task coordination
     Concurrency,
+    /// Failures reported by a dependency
     ExternalService,
+    /// Unknown or unverified callers
     Authentication,
+    /// Callers lacking permission
     Authorization,
+    /// Operations invalid in the current state
     StateConflict,
+    /// Several errors collected together
     Multiple,
+    /// Errors without a category
     Unspecified,
 }
 
 /// Output formats for error reports
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorReportFormat {
+    /// Plain text
     Plain,
+    /// JSON document
     Json,
+    /// Markdown
     Markdown,
+    /// HTML fragment
     Html,
+    /// Text with ANSI colors
+    Terminal,
 }
 
 /// Nature of a proposed autocorrection fix
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FixType {
+    /// Replace text in a file
     TextReplacement,
+    /// Change the syntax tree of a file
     AstModification,
+    /// Add a `use` declaration
     AddImport,
+    /// Add a dependency to the manifest
     AddDependency,
+    /// Change a configuration value
     ConfigurationChange,
+    /// Run a command
     ExecuteCommand,
+    /// Restructure code
     Refactor,
+    /// Needs a person to act
     ManualInterventionRequired,
+    /// Explanation only, nothing to apply
     Information,
+    /// Edit `Cargo.toml`
     UpdateCargoToml,
+    /// Run a cargo command
     RunCargoCommand,
+    /// Call a different method
     SuggestAlternativeMethod,
 }
 
 /// Detailed information for specific fix types
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
 pub enum FixDetails {
+    /// Replace a span of text in a file
     TextReplace {
+        /// File to edit
         file_path: PathBuf,
+        /// First line of the span, 1-based
         line_start: usize,
+        /// Column the span starts at, 1-based
         column_start: usize,
+        /// Last line of the span
         line_end: usize,
+        /// Column the span ends at, exclusive
         column_end: usize,
+        /// Text expected in the span, to check before replacing
         original_text_snippet: Option<String>,
+        /// Text written in place of the span
         replacement_text: String,
     },
+    /// Add a `use` declaration to a file
     AddImport {
+        /// File to add the declaration to
         file_path: String,
+        /// Path to import
         import: String,
     },
+    /// Add a dependency to the manifest
     AddCargoDependency {
+        /// Crate name
         dependency: String,
+        /// Version requirement
         version: String,
+        /// Features to enable
         features: Vec<String>,
+        /// Add it under `[dev-dependencies]`
         is_dev_dependency: bool,
     },
+    /// Run a command
     ExecuteCommand {
+        /// Program to run
         command: String,
+        /// Arguments passed to it
         args: Vec<String>,
+        /// Directory to run it in; the current one when `None`
         working_directory: Option<PathBuf>,
     },
+    /// Propose code for a person to review
     SuggestCodeChange {
+        /// File the change belongs in
         file_path: PathBuf,
+        /// Line near which to apply it
         line_hint: usize,
+        /// Code to write
         suggested_code_snippet: String,
+        /// Why the change fixes the error
         explanation: String,
     },
 }
 
 /// Describes the source location of an error
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorSource {
+    /// Source file
     pub file: String,
+    /// Line in the file
     pub line: u32,
+    /// Module path, empty when unknown
     pub module_path: String,
+    /// Column in the line
     pub column: Option<u32>,
+    /// Enclosing function
     pub function: Option<String>,
 }
 
 impl ErrorSource {
+    /// Location at `line` of `file` in `module_path`
     pub fn new(file: impl Into<String>, line: u32, module_path: impl Into<String>) -> Self {
         Self {
             file: file.into(),
@@ -134,11 +218,22 @@ impl ErrorSource {
         }
     }
 
+    /// Capture the location of the caller, through any chain of `#[track_caller]` functions
+    ///
+    /// `std::panic::Location` carries no module path, so it is left empty.
+    #[track_caller]
+    pub fn caller() -> Self {
+        let location = std::panic::Location::caller();
+        Self::new(location.file(), location.line(), String::new()).with_column(location.column())
+    }
+
+    /// Set the column
     pub fn with_column(mut self, column: u32) -> Self {
         self.column = Some(column);
         self
     }
 
+    /// Set the enclosing function
     pub fn with_function(mut self, function: impl Into<String>) -> Self {
         self.function = Some(function.into());
         self
@@ -147,15 +242,22 @@ impl ErrorSource {
 
 /// Specific location for diagnostic purposes
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorLocation {
+    /// Source file
     pub file: String,
+    /// Line in the file
     pub line: u32,
+    /// Column in the line
     pub column: u32,
+    /// Enclosing function or symbol
     pub function_context: String,
+    /// Snafu variant raised at the location
     pub snafu_variant: Option<String>,
 }
 
 impl ErrorLocation {
+    /// Location at `line` and `column` of `file`, inside `function_context`
     pub fn new(
         file: impl Into<String>,
         line: u32,
@@ -171,6 +273,7 @@ impl ErrorLocation {
         }
     }
 
+    /// Set the Snafu variant raised at the location
     pub fn with_snafu_variant(mut self, variant: impl Into<String>) -> Self {
         self.snafu_variant = Some(variant.into());
         self
@@ -179,107 +282,424 @@ impl ErrorLocation {
 
 /// A step in a macro expansion trace
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MacroExpansion {
+    /// Name of the expanded macro
     pub macro_name: String,
+    /// Where it was invoked
     pub expansion_site: ErrorLocation,
+    /// Code it generated
     pub generated_code_snippet: String,
 }
 
 /// Holds detailed diagnostic information
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DiagnosticResult {
+    /// Where the error was raised
     pub primary_location: Option<ErrorLocation>,
+    /// Macro expansions leading to the location, outermost first
+    #[cfg_attr(feature = "serde", serde(default))]
     pub expansion_trace: Vec<MacroExpansion>,
+    /// Fixes in prose
+    #[cfg_attr(feature = "serde", serde(default))]
     pub suggested_fixes: Vec<String>,
+    /// Message of the underlying diagnostic
     pub original_message: Option<String>,
+    /// Code of the underlying diagnostic, e.g. a compiler error code
     pub diagnostic_code: Option<String>,
+    /// Call stack leading to the diagnostic, innermost first (`function_context` holds the symbol)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stack_frames: Vec<ErrorLocation>,
+}
+
+/// Machine-actionable recovery step, complementing the prose `recovery_suggestion`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecoveryAction {
+    /// Retry the operation once the duration has elapsed
+    RetryAfter(Duration),
+    /// Check the named configuration key
+    CheckConfigKey(String),
+    /// Contact the named service or team
+    ContactService(String),
+    /// Run the shell command
+    RunCommand(String),
+    /// Read the documentation at the URL
+    SeeDocs(String),
+}
+
+impl RecoveryAction {
+    /// Stable snake_case name of the action, as used in structured reports
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RecoveryAction::RetryAfter(_) => "retry_after",
+            RecoveryAction::CheckConfigKey(_) => "check_config_key",
+            RecoveryAction::ContactService(_) => "contact_service",
+            RecoveryAction::RunCommand(_) => "run_command",
+            RecoveryAction::SeeDocs(_) => "see_docs",
+        }
+    }
+
+    /// Textual value of the action (milliseconds for `RetryAfter`)
+    pub fn value(&self) -> String {
+        match self {
+            RecoveryAction::RetryAfter(delay) => delay.as_millis().to_string(),
+            RecoveryAction::CheckConfigKey(value)
+            | RecoveryAction::ContactService(value)
+            | RecoveryAction::RunCommand(value)
+            | RecoveryAction::SeeDocs(value) => value.clone(),
+        }
+    }
+
+    /// Rebuild an action from its `kind()` and `value()`
+    pub fn from_parts(kind: &str, value: impl Into<String>) -> Option<Self> {
+        let value = value.into();
+        match kind {
+            "retry_after" => value.parse().ok().map(|millis| RecoveryAction::RetryAfter(Duration::from_millis(millis))),
+            "check_config_key" => Some(RecoveryAction::CheckConfigKey(value)),
+            "contact_service" => Some(RecoveryAction::ContactService(value)),
+            "run_command" => Some(RecoveryAction::RunCommand(value)),
+            "see_docs" => Some(RecoveryAction::SeeDocs(value)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RecoveryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoveryAction::RetryAfter(delay) => write!(f, "Retry after {:?}", delay),
+            RecoveryAction::CheckConfigKey(key) => write!(f, "Check configuration key `{}`", key),
+            RecoveryAction::ContactService(service) => write!(f, "Contact {}", service),
+            RecoveryAction::RunCommand(command) => write!(f, "Run `{}`", command),
+            RecoveryAction::SeeDocs(url) => write!(f, "See {}", url),
+        }
+    }
+}
+
+/// Key of a translatable user-facing message, with its named arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageKey {
+    /// Catalog key of the message
+    pub key: String,
+    /// Arguments substituted for `{name}` placeholders, in insertion order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub args: Vec<(String, String)>,
+}
+
+impl MessageKey {
+    /// Key without arguments
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into(), args: Vec::new() }
+    }
+
+    /// Add or replace a named argument
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        let name = name.into();
+        let value = value.to_string();
+        match self.args.iter_mut().find(|(existing, _)| *existing == name) {
+            Some(arg) => arg.1 = value,
+            None => self.args.push((name, value)),
+        }
+        self
+    }
+
+    /// Value of a named argument
+    pub fn arg(&self, name: &str) -> Option<&str> {
+        self.args.iter().find(|(existing, _)| existing == name).map(|(_, value)| value.as_str())
+    }
 }
 
 /// Additional structured context for an error
+///
+/// The strings, collections and diagnostic info sit behind `Arc`s, so
+/// cloning a context (and every error carrying one) only bumps reference
+/// counts for them. Strings are replaced whole; metadata goes in through `with_metadata` or
+/// `insert_metadata_limited`, which run the registered scrubbers, and tags and recovery actions
+/// are mutated through `tags_mut` and `recovery_actions_mut`, which copy the shared data first
+/// when needed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorContext {
-    pub message: String,
+    /// Developer-facing description of what was being done
+    #[cfg_attr(feature = "serde", serde(with = "shared_str"))]
+    pub message: Arc<str>,
+    /// Message safe to show to end users; `message` stays the developer-facing text
+    #[cfg_attr(feature = "serde", serde(default, with = "shared_str_option"))]
+    pub public_message: Option<Arc<str>>,
+    /// Key under which frontends look up the translated user-facing message
+    pub message_key: Option<MessageKey>,
+    /// Where the context was created
     pub source_location: Option<ErrorSource>,
-    pub recovery_suggestion: Option<String>,
-    pub metadata: HashMap<String, String>,
+    /// What to do about the error, in prose
+    #[cfg_attr(feature = "serde", serde(default, with = "shared_str_option"))]
+    pub recovery_suggestion: Option<Arc<str>>,
+    /// Typed recovery steps, in the order they should be attempted
+    #[cfg_attr(feature = "serde", serde(default, with = "shared"))]
+    pub recovery_actions: Arc<Vec<RecoveryAction>>,
+    /// Free-form key/value pairs
+    #[cfg_attr(feature = "serde", serde(default, with = "shared"))]
+    pub metadata: Arc<HashMap<String, String>>,
+    /// How serious the error is
     pub severity: ErrorSeverity,
+    /// When the context was created
     pub timestamp: Option<TimestampType>,
-    pub correlation_id: Option<String>,
-    pub component: Option<String>,
-    pub tags: Vec<String>,
-    pub diagnostic_info: Option<DiagnosticResult>,
+    /// Id linking the error to a request or trace
+    #[cfg_attr(feature = "serde", serde(default, with = "shared_str_option"))]
+    pub correlation_id: Option<Arc<str>>,
+    /// Component the error arose in
+    #[cfg_attr(feature = "serde", serde(default, with = "shared_str_option"))]
+    pub component: Option<Arc<str>>,
+    /// Labels for filtering and routing, deduplicated
+    #[cfg_attr(feature = "serde", serde(default, with = "shared"))]
+    pub tags: Arc<Vec<String>>,
+    /// Locations, stack frames and fixes gathered for the error
+    #[cfg_attr(feature = "serde", serde(with = "shared_option"))]
+    pub diagnostic_info: Option<Arc<DiagnosticResult>>,
+    /// Typed payloads, never serialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub attachments: Attachments,
 }
 
 impl ErrorContext {
+    /// Context with `message`, `Error` severity and the current time
     pub fn new(message: impl Into<String>) -> Self {
         Self {
-            message: message.into(),
+            message: Arc::from(message.into()),
+            public_message: None,
+            message_key: None,
             source_location: None,
             recovery_suggestion: None,
-            metadata: HashMap::new(),
+            recovery_actions: Arc::default(),
+            metadata: Arc::default(),
             severity: ErrorSeverity::Error,
             timestamp: Some(SystemTime::now()),
             correlation_id: None,
             component: None,
-            tags: Vec::new(),
+            tags: Arc::default(),
             diagnostic_info: None,
+            attachments: Attachments::new(),
         }
     }
 
+    /// Set the severity
     pub fn with_severity(mut self, severity: ErrorSeverity) -> Self {
         self.severity = severity;
         self
     }
 
+    /// Set where the context was created
     pub fn with_source_location(mut self, source_location: ErrorSource) -> Self {
         self.source_location = Some(source_location);
         self
     }
 
+    /// Set the recovery suggestion
     pub fn with_recovery_suggestion(mut self, suggestion: impl Into<String>) -> Self {
-        self.recovery_suggestion = Some(suggestion.into());
+        self.recovery_suggestion = Some(Arc::from(suggestion.into()));
+        self
+    }
+
+    /// Set the user-facing message shown when no translation is available
+    pub fn with_public_message(mut self, message: impl Into<String>) -> Self {
+        self.public_message = Some(Arc::from(message.into()));
+        self
+    }
+
+    /// Set the key of the translated user-facing message
+    pub fn with_message_key(mut self, key: MessageKey) -> Self {
+        self.message_key = Some(key);
+        self
+    }
+
+    /// Append a typed recovery step
+    pub fn with_recovery_action(mut self, action: RecoveryAction) -> Self {
+        self.recovery_actions_mut().push(action);
         self
     }
 
+    /// Add a metadata entry within the limits of the installed `ContextPolicy`
     pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.metadata.insert(key.into(), value.into());
+        self.insert_metadata_limited(key.into(), value.into(), &ContextPolicy::current());
         self
     }
 
+    /// Set the correlation id
     pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
-        self.correlation_id = Some(id.into());
+        self.correlation_id = Some(Arc::from(id.into()));
         self
     }
 
+    /// Set the component
     pub fn with_component(mut self, component: impl Into<String>) -> Self {
-        self.component = Some(component.into());
+        self.component = Some(Arc::from(component.into()));
         self
     }
 
+    /// Add a tag unless it is already present, within the limits of the installed `ContextPolicy`
+    ///
+    /// Tags of the form `namespace:value` can be queried by namespace with
+    /// `has_tag_ns` and `tags_in_ns`.
     pub fn add_tag(mut self, tag: impl Into<String>) -> Self {
-        self.tags.push(tag.into());
+        self.push_tag_limited(tag.into(), &ContextPolicy::current());
         self
     }
 
+    /// Add the namespaced tag `namespace:value`
+    pub fn with_tag_ns(self, namespace: &str, value: impl AsRef<str>) -> Self {
+        self.add_tag(format!("{}:{}", namespace, value.as_ref()))
+    }
+
+    /// Whether the exact tag is present
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Whether any tag lives in `namespace`
+    pub fn has_tag_ns(&self, namespace: &str) -> bool {
+        self.tags_in_ns(namespace).next().is_some()
+    }
+
+    /// Values of the tags in `namespace`, e.g. `["billing"]` for the tag `team:billing`
+    pub fn tags_in_ns<'a>(&'a self, namespace: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.tags
+            .iter()
+            .filter_map(move |tag| match split_tag(tag) {
+                (Some(ns), value) if ns == namespace => Some(value),
+                _ => None,
+            })
+    }
+
+    /// Set the diagnostic info
     pub fn with_diagnostic_info(mut self, diagnostic: DiagnosticResult) -> Self {
-        self.diagnostic_info = Some(diagnostic);
+        self.diagnostic_info = Some(Arc::new(diagnostic));
         self
     }
+
+    /// Record the deadline the error ran into, as an attachment and as metadata
+    pub fn with_deadline(self, deadline: Deadline) -> Self {
+        self.with_metadata(DEADLINE_METADATA_KEY, deadline.operation())
+            .with_metadata(DEADLINE_BUDGET_METADATA_KEY, deadline.budget().as_millis().to_string())
+            .with_attachment(deadline)
+    }
+
+    /// Deadline recorded with `with_deadline`
+    pub fn deadline(&self) -> Option<&Deadline> {
+        self.attachments.get::<Deadline>()
+    }
+
+    // Metadata for mutation, unshared from any clone first. Bypasses the
+    // scrubbers, so only for values that were scrubbed already or that the
+    // framework generates itself.
+    pub(crate) fn metadata_mut(&mut self) -> &mut HashMap<String, String> {
+        Arc::make_mut(&mut self.metadata)
+    }
+
+    /// Tags for mutation, unshared from any clone first
+    pub fn tags_mut(&mut self) -> &mut Vec<String> {
+        Arc::make_mut(&mut self.tags)
+    }
+
+    /// Recovery actions for mutation, unshared from any clone first
+    pub fn recovery_actions_mut(&mut self) -> &mut Vec<RecoveryAction> {
+        Arc::make_mut(&mut self.recovery_actions)
+    }
+}
+
+/// Split a tag into its namespace and value (`"team:billing"` -> `(Some("team"), "billing")`)
+///
+/// Tags without a `:` or with an empty namespace have no namespace.
+pub fn split_tag(tag: &str) -> (Option<&str>, &str) {
+    match tag.split_once(':') {
+        Some((namespace, value)) if !namespace.is_empty() => (Some(namespace), value),
+        _ => (None, tag),
+    }
+}
+
+// Serde through the shared value, so the `rc` feature of serde is not required
+#[cfg(feature = "serde")]
+mod shared {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<T: Serialize, S: Serializer>(value: &Arc<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        T::serialize(value, serializer)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<T>, D::Error> {
+        T::deserialize(deserializer).map(Arc::new)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod shared_str {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(value: &Arc<str>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_ref().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<str>, D::Error> {
+        String::deserialize(deserializer).map(Arc::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod shared_str_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<S: Serializer>(value: &Option<Arc<str>>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_deref().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Arc<str>>, D::Error> {
+        Option::<String>::deserialize(deserializer).map(|value| value.map(Arc::from))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod shared_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+
+    pub fn serialize<T: Serialize, S: Serializer>(value: &Option<Arc<T>>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.as_deref().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Arc<T>>, D::Error> {
+        Option::<T>::deserialize(deserializer).map(|value| value.map(Arc::new))
+    }
 }
 
 /// A proposed autocorrection for an error
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Autocorrection {
+    /// What the fix does
     pub description: String,
+    /// Nature of the fix
     pub fix_type: FixType,
+    /// How likely the fix is right, from 0.0 to 1.0
     pub confidence: f64,
+    /// Structured data for applying the fix
     pub details: Option<FixDetails>,
+    /// Unified diff of the change
     pub diff_suggestion: Option<String>,
+    /// Commands that apply the fix
+    #[cfg_attr(feature = "serde", serde(default))]
     pub commands_to_apply: Vec<String>,
+    /// Diagnostic code the fix addresses
     pub targets_error_code: Option<String>,
 }
 
 impl Autocorrection {
+    /// Fix without details, diff or commands
     pub fn new(description: impl Into<String>, fix_type: FixType, confidence: f64) -> Self {
         Self {
             description: description.into(),
@@ -292,21 +712,25 @@ impl Autocorrection {
         }
     }
 
+    /// Set the structured details
     pub fn with_details(mut self, details: FixDetails) -> Self {
         self.details = Some(details);
         self
     }
 
+    /// Set the diff
     pub fn with_diff_suggestion(mut self, diff: impl Into<String>) -> Self {
         self.diff_suggestion = Some(diff.into());
         self
     }
 
+    /// Append a command that applies the fix
     pub fn add_command(mut self, command: impl Into<String>) -> Self {
         self.commands_to_apply.push(command.into());
         self
     }
 
+    /// Set the diagnostic code the fix addresses
     pub fn with_target_error_code(mut self, code: impl Into<String>) -> Self {
         self.targets_error_code = Some(code.into());
         self
@@ -336,16 +760,56 @@ mod tests {
             .with_component("auth_service")
             .add_tag("security");
 
-        assert_eq!(context.message, "Test error");
+        assert_eq!(&*context.message, "Test error");
         assert_eq!(context.severity, ErrorSeverity::Warning);
-        assert_eq!(context.recovery_suggestion, Some("Try again".to_string()));
+        assert_eq!(context.recovery_suggestion.as_deref(), Some("Try again"));
         assert_eq!(context.metadata.get("request_id"), Some(&"123456".to_string()));
-        assert_eq!(context.correlation_id, Some("corr-789".to_string()));
-        assert_eq!(context.component, Some("auth_service".to_string()));
+        assert_eq!(context.correlation_id.as_deref(), Some("corr-789"));
+        assert_eq!(context.component.as_deref(), Some("auth_service"));
         assert_eq!(context.tags.len(), 1);
         assert_eq!(context.tags[0], "security");
     }
 
+    #[test]
+    fn test_clone_shares_until_mutated() {
+        let original = ErrorContext::new("shared")
+            .with_metadata("key", "value")
+            .add_tag("tag")
+            .with_diagnostic_info(DiagnosticResult {
+                primary_location: None,
+                expansion_trace: Vec::new(),
+                suggested_fixes: Vec::new(),
+                original_message: None,
+                diagnostic_code: None,
+                stack_frames: Vec::new(),
+            });
+        let mut clone = original.clone();
+        assert!(Arc::ptr_eq(&original.metadata, &clone.metadata));
+        assert!(Arc::ptr_eq(&original.tags, &clone.tags));
+        assert!(Arc::ptr_eq(original.diagnostic_info.as_ref().unwrap(), clone.diagnostic_info.as_ref().unwrap()));
+
+        clone.metadata_mut().insert("key".to_string(), "changed".to_string());
+        clone.tags_mut().push("other".to_string());
+        assert_eq!(original.metadata.get("key").map(String::as_str), Some("value"));
+        assert_eq!(original.tags.len(), 1);
+        assert!(!Arc::ptr_eq(&original.metadata, &clone.metadata));
+    }
+
+    #[test]
+    fn test_recovery_actions() {
+        let context = ErrorContext::new("upstream unavailable")
+            .with_recovery_action(RecoveryAction::RetryAfter(Duration::from_secs(5)))
+            .with_recovery_action(RecoveryAction::SeeDocs("https://docs.example.com/outages".to_string()));
+
+        assert_eq!(context.recovery_actions.len(), 2);
+        assert_eq!(context.recovery_actions[0].to_string(), "Retry after 5s");
+        for action in context.recovery_actions.iter() {
+            assert_eq!(RecoveryAction::from_parts(action.kind(), action.value()).as_ref(), Some(action));
+        }
+        assert_eq!(RecoveryAction::from_parts("retry_after", "soon"), None);
+        assert_eq!(RecoveryAction::from_parts("reboot", "now"), None);
+    }
+
     #[test]
     fn test_error_source() {
         let source = ErrorSource::new("src/main.rs", 42, "main")
@@ -418,4 +882,70 @@ mod tests {
         assert_eq!(autocorrection.commands_to_apply[0], "cargo check");
         assert_eq!(autocorrection.targets_error_code, Some("E0001".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_namespaced_tags() {
+        let context = ErrorContext::new("Charge failed")
+            .with_tag_ns("team", "billing")
+            .with_tag_ns("feature", "checkout")
+            .with_tag_ns("feature", "refunds")
+            .add_tag("team:billing")
+            .add_tag("flaky")
+            .add_tag(":odd");
+
+        assert_eq!(context.tags.len(), 5);
+        assert!(context.has_tag("flaky"));
+        assert!(context.has_tag_ns("team"));
+        assert!(!context.has_tag_ns("flaky"));
+        assert_eq!(context.tags_in_ns("feature").collect::<Vec<_>>(), vec!["checkout", "refunds"]);
+        assert_eq!(split_tag(":odd"), (None, ":odd"));
+        assert_eq!(split_tag("url:http://x"), (Some("url"), "http://x"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let context = ErrorContext::new("Loading settings")
+            .with_severity(ErrorSeverity::Critical)
+            .with_source_location(ErrorSource::new("src/config.rs", 42, "app::config").with_column(7))
+            .with_diagnostic_info(DiagnosticResult {
+                primary_location: Some(ErrorLocation::new("src/config.rs", 42, 7, "load")),
+                expansion_trace: Vec::new(),
+                suggested_fixes: vec!["add the key".to_string()],
+                original_message: None,
+                diagnostic_code: Some("E0425".to_string()),
+                stack_frames: Vec::new(),
+            })
+            .add_tag("startup");
+
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["severity"], "Critical");
+        assert_eq!(json["source_location"]["module_path"], "app::config");
+        assert_eq!(json["diagnostic_info"]["primary_location"]["function_context"], "load");
+
+        let decoded: ErrorContext = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.severity, context.severity);
+        assert_eq!(decoded.source_location, context.source_location);
+        assert_eq!(decoded.diagnostic_info, context.diagnostic_info);
+        assert_eq!(decoded.timestamp, context.timestamp);
+
+        // Collections may be omitted by older producers
+        let minimal: ErrorContext = serde_json::from_str(
+            r#"{"message":"m","source_location":null,"recovery_suggestion":null,"severity":"Fatal","timestamp":null,"correlation_id":null,"component":null,"diagnostic_info":null}"#,
+        )
+        .unwrap();
+        assert!(minimal.tags.is_empty() && minimal.metadata.is_empty());
+        assert_eq!(serde_json::to_string(&ErrorCategory::NotFound).unwrap(), "\"NotFound\"");
+
+        let details = FixDetails::AddImport { file_path: "src/lib.rs".to_string(), import: "use std::fmt;".to_string() };
+        assert_eq!(serde_json::to_value(&details).unwrap()["kind"], "AddImport");
+    }
+
+    #[test]
+    fn test_context_records_deadline() {
+        let context = ErrorContext::new("Query cut short").with_deadline(Deadline::after("load dashboard", Duration::from_millis(250)));
+        assert_eq!(context.deadline().map(Deadline::operation), Some("load dashboard"));
+        assert_eq!(context.metadata.get(DEADLINE_BUDGET_METADATA_KEY).map(String::as_str), Some("250"));
+        assert!(ErrorContext::new("No deadline").deadline().is_none());
+    }
+}
@@ -3,10 +3,13 @@
 pub mod error;
 pub mod types;
 pub mod data_types;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod utils;
+pub mod validation;
 
 // Re-export commonly used items
 pub use error::*;
-pub use types::*;
 pub use data_types::*;
-pub use utils::*;
\ No newline at end of file
+pub use utils::*;
+pub use validation::*;
\ No newline at end of file
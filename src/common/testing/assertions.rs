@@ -0,0 +1,232 @@
+/* src/common/testing/assertions.rs */
+#![warn(missing_docs)]
+//! **Brief:** Fluent assertions over AklypseErrors for downstream test suites.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Testing Support]
+//!  - [Error Assertions]
+//!  - [Structured Matching]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ErrorAssert` checks an error's structure instead of its `Display`
+//! output: category, severity, report code, the messages anywhere in its
+//! chain, and the metadata and tags of its rich context layers. Every check
+//! returns the assertion so they chain, and a failing check panics at the
+//! caller's line with the full error in the message.
+
+use crate::common::error::{
+    AklypseError, ErrorCategory, ErrorContext, ErrorReport, ErrorReportConfig, ErrorSeverity,
+};
+
+/// Chainable assertions over one error
+#[derive(Debug)]
+pub struct ErrorAssert<'a> {
+    error: &'a AklypseError,
+}
+
+impl<'a> ErrorAssert<'a> {
+    /// Assert on `error`
+    pub fn new(error: &'a AklypseError) -> Self {
+        Self { error }
+    }
+
+    /// Assert on the error of `result`, panicking if it is `Ok`
+    #[track_caller]
+    pub fn from_result<T: std::fmt::Debug>(result: &'a Result<T, AklypseError>) -> Self {
+        match result {
+            Ok(value) => panic!("expected an error, got Ok({:?})", value),
+            Err(error) => Self::new(error),
+        }
+    }
+
+    /// The error under test
+    pub fn error(&self) -> &'a AklypseError {
+        self.error
+    }
+
+    #[track_caller]
+    fn check(self, holds: bool, expectation: std::fmt::Arguments<'_>) -> Self {
+        assert!(holds, "expected error {}, got {:#?}", expectation, self.error);
+        self
+    }
+
+    /// Category, looking through rich context layers
+    #[track_caller]
+    pub fn has_category(self, category: ErrorCategory) -> Self {
+        let actual = self.error.category();
+        self.check(actual == category, format_args!("of category {:?} (is {:?})", category, actual))
+    }
+
+    /// Effective severity
+    #[track_caller]
+    pub fn has_severity(self, severity: ErrorSeverity) -> Self {
+        let actual = self.error.severity();
+        self.check(actual == severity, format_args!("of severity {:?} (is {:?})", severity, actual))
+    }
+
+    /// Code shown in reports (e.g. `AKL-VALIDATION`, or the diagnostic code)
+    #[track_caller]
+    pub fn has_code(self, code: &str) -> Self {
+        let actual = ErrorReport::from_error(self.error, &ErrorReportConfig::default()).code();
+        self.check(actual.as_deref() == Some(code), format_args!("with code {} (is {:?})", code, actual))
+    }
+
+    /// Some message in the chain (see `chain_messages`) contains `needle`
+    #[track_caller]
+    pub fn chain_contains(self, needle: &str) -> Self {
+        let messages = chain_messages(self.error);
+        let found = messages.iter().any(|message| message.contains(needle));
+        self.check(found, format_args!("whose chain contains {:?} (chain is {:?})", needle, messages))
+    }
+
+    /// Some rich context layer has metadata `key` set to `value`
+    #[track_caller]
+    pub fn has_metadata(self, key: &str, value: &str) -> Self {
+        let found = contexts(self.error).any(|context| context.metadata.get(key).is_some_and(|actual| actual == value));
+        self.check(found, format_args!("with metadata {}={:?}", key, value))
+    }
+
+    /// Some rich context layer has metadata `key`, whatever its value
+    #[track_caller]
+    pub fn has_metadata_key(self, key: &str) -> Self {
+        let found = contexts(self.error).any(|context| context.metadata.contains_key(key));
+        self.check(found, format_args!("with metadata key {}", key))
+    }
+
+    /// Some rich context layer carries `tag`
+    #[track_caller]
+    pub fn has_tag(self, tag: &str) -> Self {
+        let found = contexts(self.error).any(|context| context.has_tag(tag));
+        self.check(found, format_args!("tagged {}", tag))
+    }
+
+    /// The innermost error (below every rich context layer) satisfies `predicate`
+    ///
+    /// Pairs with `matches!` to check the variant and its fields.
+    #[track_caller]
+    pub fn root_matches(self, predicate: impl FnOnce(&AklypseError) -> bool) -> Self {
+        let root = root(self.error);
+        self.check(predicate(root), format_args!("whose root matches the predicate (root is {:?})", root))
+    }
+}
+
+// Rich context layers, outermost first
+fn contexts(error: &AklypseError) -> impl Iterator<Item = &ErrorContext> {
+    let mut current = Some(error);
+    std::iter::from_fn(move || match current? {
+        AklypseError::WithRichContext { context, source, .. } => {
+            current = Some(source);
+            Some(context)
+        }
+        _ => {
+            current = None;
+            None
+        }
+    })
+}
+
+fn root(error: &AklypseError) -> &AklypseError {
+    match error {
+        AklypseError::WithRichContext { source, .. } => root(source),
+        other => other,
+    }
+}
+
+/// Every human-readable message of an error, outermost first
+///
+/// Covers rich context messages, the descriptive fields of each variant,
+/// the errors inside `MultipleErrors`, and the `Display` of foreign sources
+/// with their own `source()` chains.
+pub fn chain_messages(error: &AklypseError) -> Vec<String> {
+    let mut messages = Vec::new();
+    collect_messages(error, &mut messages);
+    messages
+}
+
+fn collect_messages(error: &AklypseError, messages: &mut Vec<String>) {
+    let mut foreign: Option<&(dyn std::error::Error + 'static)> = None;
+    match error {
+        AklypseError::WithRichContext { context, source, .. } => {
+            messages.push(context.message.to_string());
+            return collect_messages(source, messages);
+        }
+        AklypseError::MultipleErrors { errors, .. } => {
+            return errors.iter().for_each(|error| collect_messages(error, messages));
+        }
+        AklypseError::Io { source, operation, .. } => {
+            messages.push(operation.clone());
+            foreign = Some(&**source);
+        }
+        AklypseError::Parse { source, context_info, .. } => {
+            messages.push(context_info.clone());
+            foreign = Some(&**source);
+        }
+        AklypseError::Network { source, url, .. } => {
+            messages.extend(url.clone());
+            foreign = Some(&**source);
+        }
+        AklypseError::Config { message, source, .. }
+        | AklypseError::Internal { message, source, .. }
+        | AklypseError::Concurrency { message, source, .. }
+        | AklypseError::ExternalService { message, source, .. }
+        | AklypseError::Whatever { message, source, .. } => {
+            messages.push(message.clone());
+            foreign = source.as_deref().map(|source| source as &(dyn std::error::Error + 'static));
+        }
+        AklypseError::Validation { field, message, .. } => messages.push(format!("{}: {}", field, message)),
+        AklypseError::CircuitBreakerOpen { name, .. } | AklypseError::RateLimited { name, .. } => messages.push(name.clone()),
+        AklypseError::Timeout { operation, .. } => messages.push(operation.clone()),
+        AklypseError::ResourceExhausted { resource, .. } => messages.push(resource.clone()),
+        AklypseError::NotFound { resource_type, identifier, .. } => messages.push(format!("{} {}", resource_type, identifier)),
+        AklypseError::StateConflict { message, .. } => messages.push(message.clone()),
+        AklypseError::ShuttingDown { component, .. } => messages.push(component.clone()),
+        AklypseError::MissingValue { item_description, .. } => messages.push(item_description.clone()),
+    }
+    while let Some(source) = foreign {
+        messages.push(source.to_string());
+        foreign = AklypseError::source_of(source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ConfigSnafu, ValidationSnafu};
+
+    fn config_error() -> AklypseError {
+        let source = std::io::Error::other("invalid digit found in string");
+        let error: AklypseError = ConfigSnafu {
+            message: "port is not a number".to_string(),
+            path: None::<std::path::PathBuf>,
+            source: Some(Box::new(source) as Box<dyn std::error::Error + Send + Sync>),
+        }
+        .build();
+        error.add_context(
+            ErrorContext::new("Loading settings").with_metadata("config.key_path", "server.port").add_tag("team:platform"),
+        )
+    }
+
+    #[test]
+    fn test_assertions_pass_on_matching_error() {
+        let result: Result<(), AklypseError> = Err(config_error());
+        ErrorAssert::from_result(&result)
+            .has_category(ErrorCategory::Configuration)
+            .has_code("AKL-CONFIGURATION")
+            .has_metadata("config.key_path", "server.port")
+            .has_metadata_key("config.key_path")
+            .has_tag("team:platform")
+            .chain_contains("Loading settings")
+            .chain_contains("invalid digit")
+            .root_matches(|root| matches!(root, AklypseError::Config { path: None, .. }));
+    }
+
+    #[test]
+    #[should_panic(expected = "whose chain contains \"timeout\"")]
+    fn test_failing_assertion_names_expectation() {
+        let error: AklypseError = ValidationSnafu { field: "email", message: "missing @".to_string() }.build();
+        ErrorAssert::new(&error).has_category(ErrorCategory::Validation).chain_contains("timeout");
+    }
+}
@@ -0,0 +1,228 @@
+/* src/common/testing/capture.rs */
+#![warn(missing_docs)]
+//! **Brief:** Capturing reporter and circuit breaker observer for tests.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Testing Support]
+//!  - [Report Capture]
+//!  - [Circuit Breaker Observation]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `CapturingReporter` is an `ErrorReporter` whose only sink keeps every
+//! report in memory, for code under test that takes an `Arc<ErrorReporter>`.
+//! `CapturingObserver` records every callback a circuit breaker makes. Both
+//! are cheap to clone and share their recordings between clones.
+
+use crate::common::error::circuitbreaker::{CircuitOperationType, CircuitTransitionEvent};
+use crate::common::error::{
+    AklypseError, CircuitBreakerObserver, CircuitState, ErrorReport, ErrorReporter, ReportSink, SinkRegistration,
+};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Sink appending every structured report to a shared list
+struct CaptureSink {
+    reports: Arc<Mutex<Vec<ErrorReport>>>,
+}
+
+impl ReportSink for CaptureSink {
+    fn write_report(&self, rendered: &str) -> io::Result<()> {
+        self.reports.lock().unwrap_or_else(|p| p.into_inner()).push(ErrorReport::new(rendered));
+        Ok(())
+    }
+
+    fn publish(&self, report: &ErrorReport, _rendered: &str) -> io::Result<()> {
+        self.reports.lock().unwrap_or_else(|p| p.into_inner()).push(report.clone());
+        Ok(())
+    }
+}
+
+/// Reporter recording every report delivered through `report_to_sinks`
+#[derive(Debug, Clone)]
+pub struct CapturingReporter {
+    reporter: Arc<ErrorReporter>,
+    reports: Arc<Mutex<Vec<ErrorReport>>>,
+}
+
+impl CapturingReporter {
+    /// Reporter with a single capturing sink named `capture`
+    pub fn new() -> Self {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = CaptureSink { reports: reports.clone() };
+        let reporter = ErrorReporter::builder().add_sink(SinkRegistration::new("capture", sink)).build();
+        Self { reporter: Arc::new(reporter), reports }
+    }
+
+    /// The reporter to hand to the code under test
+    pub fn reporter(&self) -> Arc<ErrorReporter> {
+        self.reporter.clone()
+    }
+
+    /// Copies of the reports captured so far, oldest first
+    pub fn reports(&self) -> Vec<ErrorReport> {
+        self.reports.lock().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+
+    /// Remove and return the reports captured so far
+    pub fn take(&self) -> Vec<ErrorReport> {
+        std::mem::take(&mut *self.reports.lock().unwrap_or_else(|p| p.into_inner()))
+    }
+
+    /// Number of reports captured so far
+    pub fn len(&self) -> usize {
+        self.reports.lock().unwrap_or_else(|p| p.into_inner()).len()
+    }
+
+    /// Whether nothing was reported
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for CapturingReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One callback received by `CapturingObserver`
+#[derive(Debug, Clone)]
+pub enum ObservedEvent {
+    /// `on_state_change`
+    StateChange {
+        /// Circuit breaker name
+        name: String,
+        /// The transition
+        event: CircuitTransitionEvent,
+    },
+    /// `on_operation_attempt`
+    Attempt {
+        /// Circuit breaker name
+        name: String,
+        /// State when the operation was attempted
+        state: CircuitState,
+    },
+    /// `on_operation_result`
+    Result {
+        /// Circuit breaker name
+        name: String,
+        /// Outcome of the operation
+        op_type: CircuitOperationType,
+        /// How long it took
+        duration: Duration,
+        /// The error it failed with, if any
+        error: Option<Box<AklypseError>>,
+    },
+    /// `on_reset`
+    Reset {
+        /// Circuit breaker name
+        name: String,
+    },
+}
+
+/// Circuit breaker observer recording every callback
+#[derive(Debug, Clone, Default)]
+pub struct CapturingObserver {
+    events: Arc<Mutex<Vec<ObservedEvent>>>,
+}
+
+impl CapturingObserver {
+    /// Observer with no recorded events
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, event: ObservedEvent) {
+        self.events.lock().unwrap_or_else(|p| p.into_inner()).push(event);
+    }
+
+    /// Copies of the recorded events, oldest first
+    pub fn events(&self) -> Vec<ObservedEvent> {
+        self.events.lock().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+
+    /// State transitions as `(from, to)` pairs, oldest first
+    pub fn transitions(&self) -> Vec<(CircuitState, CircuitState)> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                ObservedEvent::StateChange { event, .. } => Some((event.from_state, event.to_state)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Number of operation results of `op_type`
+    pub fn result_count(&self, op_type: CircuitOperationType) -> usize {
+        self.events
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .iter()
+            .filter(|event| matches!(event, ObservedEvent::Result { op_type: recorded, .. } if *recorded == op_type))
+            .count()
+    }
+
+    /// Errors the observed operations failed with, oldest first
+    pub fn errors(&self) -> Vec<AklypseError> {
+        self.events()
+            .into_iter()
+            .filter_map(|event| match event {
+                ObservedEvent::Result { error, .. } => error.map(|error| *error),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl CircuitBreakerObserver for CapturingObserver {
+    fn on_state_change(&self, name: &str, event: &CircuitTransitionEvent) {
+        self.record(ObservedEvent::StateChange { name: name.to_string(), event: event.clone() });
+    }
+
+    fn on_operation_attempt(&self, name: &str, state: CircuitState) {
+        self.record(ObservedEvent::Attempt { name: name.to_string(), state });
+    }
+
+    fn on_operation_result(&self, name: &str, op_type: CircuitOperationType, duration: Duration, error: Option<&AklypseError>) {
+        self.record(ObservedEvent::Result { name: name.to_string(), op_type, duration, error: error.cloned().map(Box::new) });
+    }
+
+    fn on_reset(&self, name: &str) {
+        self.record(ObservedEvent::Reset { name: name.to_string() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreaker, CircuitBreakerConfig, ErrorCategory, StateConflictSnafu};
+
+    #[test]
+    fn test_capturing_reporter_records_reports() {
+        let capture = CapturingReporter::new();
+        let error: AklypseError = StateConflictSnafu { message: "order already shipped".to_string() }.build();
+        capture.reporter().report_to_sinks(&error).unwrap();
+
+        assert_eq!(capture.len(), 1);
+        let reports = capture.take();
+        assert_eq!(reports[0].category, Some(ErrorCategory::StateConflict));
+        assert!(capture.is_empty());
+    }
+
+    #[test]
+    fn test_capturing_observer_records_breaker_activity() {
+        let observer = CapturingObserver::new();
+        let config = CircuitBreakerConfig { failure_threshold: 1, ..CircuitBreakerConfig::default() };
+        let breaker = CircuitBreaker::new("inventory", config);
+        breaker.add_observer(Arc::new(observer.clone()));
+
+        let _ = breaker.execute(|| StateConflictSnafu { message: "stale".to_string() }.fail::<()>());
+        assert_eq!(observer.result_count(CircuitOperationType::Failure), 1);
+        assert_eq!(observer.transitions(), vec![(CircuitState::Closed, CircuitState::Open)]);
+        assert!(matches!(observer.errors().as_slice(), [AklypseError::StateConflict { .. }]));
+    }
+}
@@ -0,0 +1,315 @@
+/* src/common/testing/faults.rs */
+#![warn(missing_docs)]
+//! **Brief:** Fault injection plans consulted by the fs, process, pool and circuit breaker helpers.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Testing Support]
+//!  - [Fault Injection]
+//!  - [Failure-Path Testing]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A `FaultPlan` maps operation names to faults: a fixed sequence of
+//! outcomes, a probability of failing, and an added latency. Once installed,
+//! the crate's own helpers consult it before doing real work and fail with
+//! the configured `AklypseError` instead:
+//!
+//! | Operation name                | Consulted by                                  |
+//! |-------------------------------|-----------------------------------------------|
+//! | `fs.<function>`               | `utils::fs` (`fs.read`, `fs.write`, ...)       |
+//! | `process.<program>`           | `utils::process::run` and `run_async`         |
+//! | `pool.<name>`                 | `ResourcePool::acquire` and `acquire_async`   |
+//! | `circuit_breaker.<name>`      | `CircuitBreaker::execute` and `execute_async` |
+//!
+//! A rule's name may end in `*` to match a prefix (`fs.*`). Injected errors
+//! carry a context with `OPERATION_METADATA_KEY` so tests can tell them from
+//! real failures. Probabilities draw from a `SeededRng`, so a plan replays
+//! the same failures for the same seed.
+//!
+//! `install` makes the plan process-wide until its guard drops; only one
+//! process-wide plan can be installed at a time, later installs wait for the
+//! earlier guard. `install_local` affects only the calling thread, which
+//! keeps parallel tests from seeing each other's faults.
+
+use crate::common::error::{AklypseError, ErrorContext, Result};
+use crate::common::utils::jitter::{JitterRng, SeededRng};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+use std::time::Duration;
+
+/// Metadata key holding the operation a fault was injected into
+pub const OPERATION_METADATA_KEY: &str = "fault.operation";
+
+/// One outcome of a scripted sequence
+#[derive(Debug, Clone)]
+pub enum Fault {
+    /// Let the operation run
+    Pass,
+    /// Fail with the error
+    Fail(Box<AklypseError>),
+    /// Wait, then let the operation run
+    Delay(Duration),
+}
+
+#[derive(Debug, Clone, Default)]
+struct FaultRule {
+    sequence: Vec<Fault>,
+    probability: Option<(f64, AklypseError)>,
+    latency: Option<Duration>,
+}
+
+/// Faults to inject, keyed by operation name
+#[derive(Debug, Clone)]
+pub struct FaultPlan {
+    rules: Vec<(String, FaultRule)>,
+    seed: u64,
+}
+
+impl FaultPlan {
+    /// Plan injecting nothing, seeded with 0
+    pub fn new() -> Self {
+        Self { rules: Vec::new(), seed: 0 }
+    }
+
+    /// Seed of the generator deciding probabilistic failures
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn rule(&mut self, operation: &str) -> &mut FaultRule {
+        let index = match self.rules.iter().position(|(name, _)| name == operation) {
+            Some(index) => index,
+            None => {
+                self.rules.push((operation.to_string(), FaultRule::default()));
+                self.rules.len() - 1
+            }
+        };
+        &mut self.rules[index].1
+    }
+
+    /// Run through `faults` on successive calls, then let calls pass
+    pub fn with_sequence(mut self, operation: &str, faults: impl IntoIterator<Item = Fault>) -> Self {
+        self.rule(operation).sequence.extend(faults);
+        self
+    }
+
+    /// Fail the next `times` calls with `error`
+    pub fn with_failures(self, operation: &str, times: usize, error: AklypseError) -> Self {
+        self.with_sequence(operation, std::iter::repeat_n(Fault::Fail(Box::new(error)), times))
+    }
+
+    /// Fail each call (once any sequence is used up) with `probability`
+    pub fn with_probability(mut self, operation: &str, probability: f64, error: AklypseError) -> Self {
+        self.rule(operation).probability = Some((probability.clamp(0.0, 1.0), error));
+        self
+    }
+
+    /// Delay every call by `latency`
+    pub fn with_latency(mut self, operation: &str, latency: Duration) -> Self {
+        self.rule(operation).latency = Some(latency);
+        self
+    }
+
+    /// Install process-wide until the guard drops
+    pub fn install(self) -> FaultPlanGuard {
+        static INSTALL: Mutex<()> = Mutex::new(());
+        let exclusive = INSTALL.lock().unwrap_or_else(|p| p.into_inner());
+        let state = Arc::new(PlanState::new(self));
+        *GLOBAL.write().unwrap_or_else(|p| p.into_inner()) = Some(state.clone());
+        FaultPlanGuard { state, scope: Scope::Global { _exclusive: exclusive } }
+    }
+
+    /// Install for the calling thread only until the guard drops
+    pub fn install_local(self) -> FaultPlanGuard {
+        let state = Arc::new(PlanState::new(self));
+        let previous = LOCAL.with(|local| local.borrow_mut().replace(state.clone()));
+        FaultPlanGuard { state, scope: Scope::Local(previous) }
+    }
+}
+
+impl Default for FaultPlan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+struct PlanState {
+    rules: Vec<(String, Mutex<std::vec::IntoIter<Fault>>, FaultRule)>,
+    rng: Mutex<SeededRng>,
+    hits: Mutex<HashMap<String, usize>>,
+}
+
+impl PlanState {
+    fn new(plan: FaultPlan) -> Self {
+        let rules = plan
+            .rules
+            .into_iter()
+            .map(|(name, mut rule)| {
+                let sequence = std::mem::take(&mut rule.sequence).into_iter();
+                (name, Mutex::new(sequence), rule)
+            })
+            .collect();
+        Self { rules, rng: Mutex::new(SeededRng::new(plan.seed)), hits: Mutex::new(HashMap::new()) }
+    }
+
+    // What to do for one call of `operation`: a delay and/or an error
+    fn decide(&self, operation: &str) -> Option<(Option<Duration>, Option<AklypseError>)> {
+        let (_, sequence, rule) = self.rules.iter().find(|(name, _, _)| matches(name, operation))?;
+        *self.hits.lock().unwrap_or_else(|p| p.into_inner()).entry(operation.to_string()).or_default() += 1;
+
+        let mut delay = rule.latency;
+        let scripted = sequence.lock().unwrap_or_else(|p| p.into_inner()).next();
+        let error = match scripted {
+            Some(Fault::Fail(error)) => Some(*error),
+            Some(Fault::Delay(extra)) => {
+                delay = Some(delay.unwrap_or_default() + extra);
+                None
+            }
+            Some(Fault::Pass) => None,
+            None => rule.probability.as_ref().and_then(|(probability, error)| {
+                let roll = self.rng.lock().unwrap_or_else(|p| p.into_inner()).next_f64();
+                (roll < *probability).then(|| error.clone())
+            }),
+        };
+        let error = error.map(|error| {
+            error.add_context(
+                ErrorContext::new(format!("Injected fault for '{}'", operation)).with_metadata(OPERATION_METADATA_KEY, operation),
+            )
+        });
+        Some((delay, error))
+    }
+}
+
+fn matches(pattern: &str, operation: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => operation.starts_with(prefix),
+        None => pattern == operation,
+    }
+}
+
+static GLOBAL: RwLock<Option<Arc<PlanState>>> = RwLock::new(None);
+
+thread_local! {
+    static LOCAL: RefCell<Option<Arc<PlanState>>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug)]
+enum Scope {
+    Global { _exclusive: MutexGuard<'static, ()> },
+    Local(Option<Arc<PlanState>>),
+}
+
+/// Keeps a plan installed; uninstalls it on drop
+#[derive(Debug)]
+pub struct FaultPlanGuard {
+    state: Arc<PlanState>,
+    scope: Scope,
+}
+
+impl FaultPlanGuard {
+    /// How many times `operation` consulted the plan and matched a rule
+    pub fn hits(&self, operation: &str) -> usize {
+        self.state.hits.lock().unwrap_or_else(|p| p.into_inner()).get(operation).copied().unwrap_or(0)
+    }
+}
+
+impl Drop for FaultPlanGuard {
+    fn drop(&mut self) {
+        match &mut self.scope {
+            Scope::Global { .. } => *GLOBAL.write().unwrap_or_else(|p| p.into_inner()) = None,
+            Scope::Local(previous) => LOCAL.with(|local| *local.borrow_mut() = previous.take()),
+        }
+    }
+}
+
+// The plan in effect for this thread: its local plan, else the global one
+fn active() -> Option<Arc<PlanState>> {
+    LOCAL
+        .with(|local| local.borrow().clone())
+        .or_else(|| GLOBAL.read().unwrap_or_else(|p| p.into_inner()).clone())
+}
+
+/// Apply the installed plan to one call of `operation`, sleeping for any latency
+pub(crate) fn inject(operation: &str) -> Result<()> {
+    let Some((delay, error)) = active().and_then(|plan| plan.decide(operation)) else {
+        return Ok(());
+    };
+    if let Some(delay) = delay {
+        std::thread::sleep(delay);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+/// `inject` for async callers, waiting on the tokio timer
+#[cfg(feature = "tokio")]
+pub(crate) async fn inject_async(operation: &str) -> Result<()> {
+    let Some((delay, error)) = active().and_then(|plan| plan.decide(operation)) else {
+        return Ok(());
+    };
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+    error.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{CircuitBreaker, CircuitBreakerConfig, CircuitState, StateConflictSnafu, TimeoutSnafu};
+    use crate::common::utils::fs;
+    use crate::common::utils::tempres::TempFileGuard;
+
+    fn timeout() -> AklypseError {
+        TimeoutSnafu { operation: "injected".to_string(), duration: Duration::from_secs(1) }.build()
+    }
+
+    #[test]
+    fn test_sequences_and_latency_apply_to_fs() {
+        let file = TempFileGuard::new("aklypse-faults-").unwrap();
+        let guard = FaultPlan::new()
+            .with_sequence("fs.write", [Fault::Pass, Fault::Fail(Box::new(timeout()))])
+            .with_latency("fs.read*", Duration::from_millis(20))
+            .install_local();
+
+        fs::write(file.path(), "ok").unwrap();
+        let error = fs::write(file.path(), "lost").unwrap_err();
+        assert!(matches!(error.get_rich_context(), Some(context) if context.metadata.get(OPERATION_METADATA_KEY).map(String::as_str) == Some("fs.write")));
+        fs::write(file.path(), "ok again").unwrap();
+
+        let started = std::time::Instant::now();
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), "ok again");
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(guard.hits("fs.write"), 3);
+
+        // Other threads do not see a local plan
+        let path = file.path().to_path_buf();
+        std::thread::spawn(move || fs::read(path).unwrap()).join().unwrap();
+        assert_eq!(guard.hits("fs.read"), 0);
+        drop(guard);
+        assert!(active().is_none());
+    }
+
+    #[test]
+    fn test_failures_trip_circuit_breaker_and_probability_is_seeded() {
+        let _guard = FaultPlan::new().with_failures("circuit_breaker.faulty-ledger", 3, timeout()).install_local();
+        let config = CircuitBreakerConfig { failure_threshold: 3, ..CircuitBreakerConfig::default() };
+        let breaker = CircuitBreaker::new("faulty-ledger", config);
+        for _ in 0..3 {
+            assert!(breaker.execute(|| Ok(())).is_err());
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        let outcomes = |seed| {
+            let conflict: AklypseError = StateConflictSnafu { message: "flaky".to_string() }.build();
+            let _guard = FaultPlan::new().with_seed(seed).with_probability("process.*", 0.5, conflict).install_local();
+            (0..32).map(|_| inject("process.git").is_err()).collect::<Vec<_>>()
+        };
+        assert_eq!(outcomes(7), outcomes(7));
+        assert!(outcomes(7).iter().any(|failed| *failed) && outcomes(7).iter().any(|failed| !*failed));
+    }
+}
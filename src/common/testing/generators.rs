@@ -0,0 +1,320 @@
+/* src/common/testing/generators.rs */
+#![warn(missing_docs)]
+//! **Brief:** `Arbitrary` implementations (arbitrary and proptest) for the error types.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Testing Support]
+//!  - [Property Testing]
+//!  - [Fuzzing]
+//!  - [Error Generators]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `AklypseError`, `ErrorContext` and `Autocorrection` implement both
+//! `arbitrary::Arbitrary` (for cargo-fuzz targets) and
+//! `proptest::arbitrary::Arbitrary` (for `any::<T>()` in property tests).
+//!
+//! Values are drawn from realistic vocabularies (file paths, services,
+//! operations, metadata keys) rather than random strings, so generated errors
+//! exercise the same code paths real ones do. Errors nest: rich context
+//! layers and `MultipleErrors` recurse up to `MAX_DEPTH` levels. The proptest
+//! strategies are built on the arbitrary ones by feeding them random bytes,
+//! so both crates generate the same shapes and proptest shrinks by shrinking
+//! those bytes.
+
+use crate::common::error::{
+    AklypseError, Autocorrection, CircuitBreakerOpenSnafu, ConcurrencySnafu, ConfigSnafu, ErrorContext, ErrorSeverity,
+    ErrorSource, ExternalServiceSnafu, FixDetails, FixType, InternalSnafu, IoSnafu, MissingValueSnafu,
+    MultipleErrorsSnafu, NetworkSnafu, NotFoundSnafu, ParseSnafu, RateLimitedSnafu, RecoveryAction,
+    ResourceExhaustedSnafu, ShuttingDownSnafu, StateConflictSnafu, TimeoutSnafu, ValidationSnafu,
+};
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use proptest::prelude::{any, BoxedStrategy, Strategy};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Deepest nesting of rich contexts and `MultipleErrors` in generated errors
+pub const MAX_DEPTH: usize = 3;
+
+// Latest generated timestamp, 2100-01-01
+const LATEST_TIMESTAMP_SECS: u64 = 4_102_444_800;
+
+// Bytes handed to the arbitrary generators by the proptest strategies
+const PROPTEST_ENTROPY: std::ops::Range<usize> = 16..1024;
+
+const PATHS: &[&str] = &["config/app.toml", "/var/lib/aklypse/state.db", "data/prices.csv", "C:\\aklypse\\keys.json", "ünïcode/файл.txt"];
+const SERVICES: &[&str] = &["payments-api", "postgres", "redis", "exchange-feed", "auth"];
+const OPERATIONS: &[&str] = &["read", "write", "connect", "fetch quote", "commit transaction", "rename to 'backup.db'"];
+const FIELDS: &[&str] = &["email", "amount", "config.server.port", "order.items[3].qty", "api_key"];
+const MESSAGES: &[&str] = &[
+    "unexpected end of input",
+    "connection reset by peer",
+    "value must be positive",
+    "lock poisoned",
+    "quota exceeded for tenant \"acme\"",
+    "multi\nline\tmessage",
+    "",
+];
+const METADATA_KEYS: &[&str] = &["request_id", "user_id", "attempt", "net.url", "config.key_path", "process.exit_code"];
+const TAGS: &[&str] = &["team:billing", "team:platform", "retryable", "pii", "env:prod"];
+const PARSE_KINDS: &[&str] = &["json", "yaml", "toml", "csv", "protobuf"];
+const NETWORK_KINDS: &[&str] = &["dns", "connect", "tls", "http"];
+const URLS: &[&str] = &["https://api.example.com/v1/orders", "http://localhost:8080/health", "postgres://db:5432/app"];
+const IO_KINDS: &[std::io::ErrorKind] = &[
+    std::io::ErrorKind::NotFound,
+    std::io::ErrorKind::PermissionDenied,
+    std::io::ErrorKind::ConnectionRefused,
+    std::io::ErrorKind::TimedOut,
+    std::io::ErrorKind::InvalidData,
+];
+const SEVERITIES: &[ErrorSeverity] = &[
+    ErrorSeverity::Debug,
+    ErrorSeverity::Info,
+    ErrorSeverity::Warning,
+    ErrorSeverity::Error,
+    ErrorSeverity::Critical,
+    ErrorSeverity::Fatal,
+];
+const FIX_TYPES: &[FixType] = &[
+    FixType::TextReplacement,
+    FixType::AddImport,
+    FixType::AddDependency,
+    FixType::ConfigurationChange,
+    FixType::ExecuteCommand,
+    FixType::ManualInterventionRequired,
+    FixType::Information,
+    FixType::RunCargoCommand,
+];
+
+fn pick(u: &mut Unstructured<'_>, words: &[&str]) -> Result<String> {
+    u.choose(words).map(|word| word.to_string())
+}
+
+fn optional<T>(u: &mut Unstructured<'_>, generate: impl FnOnce(&mut Unstructured<'_>) -> Result<T>) -> Result<Option<T>> {
+    if u.arbitrary()? {
+        generate(u).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn io_source(u: &mut Unstructured<'_>) -> Result<std::io::Error> {
+    Ok(std::io::Error::new(*u.choose(IO_KINDS)?, pick(u, MESSAGES)?))
+}
+
+fn boxed_source(u: &mut Unstructured<'_>) -> Result<Box<dyn std::error::Error + Send + Sync>> {
+    Ok(Box::new(io_source(u)?))
+}
+
+fn optional_source(u: &mut Unstructured<'_>) -> Result<Option<Box<dyn std::error::Error + Send + Sync>>> {
+    optional(u, boxed_source)
+}
+
+fn duration(u: &mut Unstructured<'_>) -> Result<Duration> {
+    Ok(Duration::from_millis(u.int_in_range(0..=120_000)?))
+}
+
+// Error nested at most `depth` more levels
+fn error_at_depth(u: &mut Unstructured<'_>, depth: usize) -> Result<AklypseError> {
+    let variants = if depth == 0 { 16 } else { 18 };
+    let error = match u.choose_index(variants)? {
+        0 => IoSnafu {
+            source: Arc::new(io_source(u)?),
+            path: optional(u, |u| pick(u, PATHS).map(PathBuf::from))?,
+            operation: pick(u, OPERATIONS)?,
+        }
+        .build(),
+        1 => ParseSnafu { source: boxed_source(u)?, kind: pick(u, PARSE_KINDS)?, context_info: pick(u, MESSAGES)? }.build(),
+        2 => NetworkSnafu { source: boxed_source(u)?, url: optional(u, |u| pick(u, URLS))?, kind: pick(u, NETWORK_KINDS)? }.build(),
+        3 => ConfigSnafu {
+            message: pick(u, MESSAGES)?,
+            path: optional(u, |u| pick(u, PATHS).map(PathBuf::from))?,
+            source: optional_source(u)?,
+        }
+        .build(),
+        4 => ValidationSnafu { field: pick(u, FIELDS)?, message: pick(u, MESSAGES)? }.build(),
+        5 => InternalSnafu { message: pick(u, MESSAGES)?, source: optional_source(u)? }.build(),
+        6 => CircuitBreakerOpenSnafu { name: pick(u, SERVICES)?, retry_after: optional(u, duration)? }.build(),
+        7 => TimeoutSnafu { operation: pick(u, OPERATIONS)?, duration: duration(u)? }.build(),
+        8 => {
+            let limit: u32 = u.int_in_range(1..=10_000)?;
+            let current = limit.saturating_add(u.int_in_range(0..=100)?);
+            ResourceExhaustedSnafu { resource: pick(u, SERVICES)?, limit: limit.to_string(), current: current.to_string() }.build()
+        }
+        9 => NotFoundSnafu { resource_type: pick(u, &["user", "order", "file", "key"])?, identifier: u.int_in_range(0..=99_999u32)?.to_string() }.build(),
+        10 => StateConflictSnafu { message: pick(u, MESSAGES)? }.build(),
+        11 => ConcurrencySnafu { message: pick(u, MESSAGES)?, source: optional_source(u)? }.build(),
+        12 => ExternalServiceSnafu { service_name: pick(u, SERVICES)?, message: pick(u, MESSAGES)?, source: optional_source(u)? }.build(),
+        13 => MissingValueSnafu { item_description: pick(u, FIELDS)? }.build(),
+        14 => RateLimitedSnafu { name: pick(u, SERVICES)?, retry_after: duration(u)? }.build(),
+        15 => ShuttingDownSnafu { component: pick(u, SERVICES)? }.build(),
+        16 => {
+            let count = u.int_in_range(1..=3)?;
+            let errors = (0..count).map(|_| error_at_depth(u, depth - 1)).collect::<Result<Vec<_>>>()?;
+            MultipleErrorsSnafu { errors }.build()
+        }
+        _ => error_at_depth(u, depth - 1)?.add_context(ErrorContext::arbitrary(u)?),
+    };
+    Ok(error)
+}
+
+impl<'a> Arbitrary<'a> for AklypseError {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        error_at_depth(u, MAX_DEPTH)
+    }
+}
+
+impl<'a> Arbitrary<'a> for ErrorContext {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut context = ErrorContext::new(pick(u, MESSAGES)?).with_severity(*u.choose(SEVERITIES)?);
+        context.timestamp = optional(u, |u| Ok(UNIX_EPOCH + Duration::from_secs(u.int_in_range(0..=LATEST_TIMESTAMP_SECS)?)))?;
+        if let Some(message) = optional(u, |u| pick(u, MESSAGES))? {
+            context = context.with_public_message(message);
+        }
+        if let Some(component) = optional(u, |u| pick(u, SERVICES))? {
+            context = context.with_component(component);
+        }
+        if let Some(id) = optional(u, |u| Ok(format!("corr-{:08x}", u.arbitrary::<u32>()?)))? {
+            context = context.with_correlation_id(id);
+        }
+        if u.arbitrary()? {
+            let location = ErrorSource::new(pick(u, PATHS)?, u.int_in_range(1..=5_000)?, "aklypse::testing");
+            context = context.with_source_location(location);
+        }
+        for _ in 0..u.int_in_range(0..=4)? {
+            let key = pick(u, METADATA_KEYS)?;
+            context = context.with_metadata(key, pick(u, MESSAGES)?);
+        }
+        for _ in 0..u.int_in_range(0..=2)? {
+            context = context.add_tag(pick(u, TAGS)?);
+        }
+        for _ in 0..u.int_in_range(0..=2)? {
+            let action = match u.choose_index(5)? {
+                0 => RecoveryAction::RetryAfter(duration(u)?),
+                1 => RecoveryAction::CheckConfigKey(pick(u, FIELDS)?),
+                2 => RecoveryAction::ContactService(pick(u, SERVICES)?),
+                3 => RecoveryAction::RunCommand(format!("systemctl restart {}", pick(u, SERVICES)?)),
+                _ => RecoveryAction::SeeDocs(pick(u, URLS)?),
+            };
+            context = context.with_recovery_action(action);
+        }
+        Ok(context)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Autocorrection {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let confidence = f64::from(u.int_in_range(0..=100u8)?) / 100.0;
+        let mut fix = Autocorrection::new(pick(u, MESSAGES)?, u.choose(FIX_TYPES)?.clone(), confidence);
+        let details = match u.choose_index(4)? {
+            0 => None,
+            1 => {
+                let line_start = u.int_in_range(1..=500)?;
+                Some(FixDetails::TextReplace {
+                    file_path: PathBuf::from(pick(u, PATHS)?),
+                    line_start,
+                    column_start: u.int_in_range(1..=120)?,
+                    line_end: line_start + u.int_in_range(0..=5)?,
+                    column_end: u.int_in_range(1..=120)?,
+                    original_text_snippet: optional(u, |u| pick(u, MESSAGES))?,
+                    replacement_text: pick(u, MESSAGES)?,
+                })
+            }
+            2 => Some(FixDetails::ExecuteCommand {
+                command: pick(u, &["cargo", "curl", "rm"])?,
+                args: (0..u.int_in_range(0..=3)?).map(|_| pick(u, PATHS)).collect::<Result<_>>()?,
+                working_directory: optional(u, |u| pick(u, PATHS).map(PathBuf::from))?,
+            }),
+            _ => Some(FixDetails::SuggestCodeChange {
+                file_path: PathBuf::from(pick(u, PATHS)?),
+                line_hint: u.int_in_range(1..=500)?,
+                suggested_code_snippet: pick(u, MESSAGES)?,
+                explanation: pick(u, MESSAGES)?,
+            }),
+        };
+        if let Some(details) = details {
+            fix = fix.with_details(details);
+        }
+        for _ in 0..u.int_in_range(0..=2)? {
+            fix = fix.add_command(format!("cargo {}", pick(u, &["check", "update", "fix --allow-dirty"])?));
+        }
+        if let Some(code) = optional(u, |u| Ok(format!("E{:04}", u.int_in_range(1..=799u16)?)))? {
+            fix = fix.with_target_error_code(code);
+        }
+        Ok(fix)
+    }
+}
+
+// Proptest strategy running the arbitrary generator over random bytes
+fn from_entropy<T>() -> BoxedStrategy<T>
+where
+    T: for<'a> Arbitrary<'a> + std::fmt::Debug + 'static,
+{
+    proptest::collection::vec(any::<u8>(), PROPTEST_ENTROPY)
+        .prop_map(|bytes| T::arbitrary(&mut Unstructured::new(&bytes)).expect("generators never reject input"))
+        .boxed()
+}
+
+macro_rules! impl_proptest_arbitrary {
+    ($($ty:ty),*) => {
+        $(
+            impl proptest::arbitrary::Arbitrary for $ty {
+                type Parameters = ();
+                type Strategy = BoxedStrategy<$ty>;
+
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    from_entropy::<$ty>()
+                }
+            }
+        )*
+    };
+}
+
+impl_proptest_arbitrary!(AklypseError, ErrorContext, Autocorrection);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ErrorReportConfig, ErrorReporter};
+    use proptest::prelude::*;
+
+    fn depth(error: &AklypseError) -> usize {
+        match error {
+            AklypseError::WithRichContext { source, .. } => 1 + depth(source),
+            AklypseError::MultipleErrors { errors, .. } => 1 + errors.iter().map(depth).max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_is_deterministic_and_total() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(2048).collect();
+        let first = AklypseError::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        let second = AklypseError::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert_eq!(format!("{:?}", first.category()), format!("{:?}", second.category()));
+
+        // Exhausted input still yields values
+        assert!(AklypseError::arbitrary(&mut Unstructured::new(&[])).is_ok());
+        assert!(Autocorrection::arbitrary(&mut Unstructured::new(&[])).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn test_generated_errors_are_bounded_and_render(error in any::<AklypseError>()) {
+            prop_assert!(depth(&error) <= MAX_DEPTH);
+            let rendered = ErrorReporter::new().report_to_string(&error, &ErrorReportConfig::default());
+            prop_assert!(!rendered.is_empty());
+        }
+
+        #[test]
+        fn test_generated_contexts_are_well_formed(context in any::<ErrorContext>(), fix in any::<Autocorrection>()) {
+            let latest = UNIX_EPOCH + Duration::from_secs(LATEST_TIMESTAMP_SECS);
+            prop_assert!(context.timestamp.is_none_or(|time| time <= latest));
+            prop_assert!(context.tags.iter().all(|tag| TAGS.contains(&tag.as_str())));
+            prop_assert!((0.0..=1.0).contains(&fix.confidence));
+        }
+    }
+}
@@ -0,0 +1,23 @@
+/* src/common/testing/mod.rs */
+#![warn(missing_docs)]
+//! **Brief:** Test support for crates built on aklypse (feature `testing`).
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Testing]
+//!  - [Error Assertions]
+//!  - [Event Capture]
+//!  - [Fault Injection]
+//!  - [Error Generators]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+pub mod assertions;
+pub mod capture;
+pub mod faults;
+pub mod generators;
+
+pub use self::assertions::{chain_messages, ErrorAssert};
+pub use self::capture::{CapturingObserver, CapturingReporter, ObservedEvent};
+pub use self::faults::{Fault, FaultPlan, FaultPlanGuard};
@@ -0,0 +1,103 @@
+/* src/common/utils/clock.rs */
+#![warn(missing_docs)]
+//! **Brief:** Clock abstraction so time-dependent logic can be tested without sleeping.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Time]
+//!  - [Clocks]
+//!  - [Deterministic Testing]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Components that measure elapsed time read it from a `Clock` instead of
+//! calling `Instant::now()` directly. `SystemClock` is the real monotonic
+//! clock and the default everywhere. With the `test-util` feature,
+//! `MockClock` only moves when told to, so a test can step over a reset
+//! timeout or a time window instantly and deterministically.
+//!
+//! Only measurements go through the clock: actual waiting (sleeps, tokio
+//! timeouts) still takes real time, and timers that would have to wait on a
+//! clock reporting `is_real_time() == false` are not scheduled at all.
+
+use std::fmt;
+use std::time::Instant;
+#[cfg(feature = "test-util")]
+use std::{sync::Mutex, time::Duration};
+
+/// Source of the current monotonic time
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// The current instant
+    fn now(&self) -> Instant;
+
+    /// Whether the clock follows real time, so that waiting on the system
+    /// clock until one of its instants is meaningful
+    fn is_real_time(&self) -> bool {
+        true
+    }
+}
+
+/// The real monotonic clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock standing still until advanced
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<Instant>,
+}
+
+#[cfg(feature = "test-util")]
+impl MockClock {
+    /// A clock frozen at the current instant
+    pub fn new() -> Self {
+        Self { now: Mutex::new(Instant::now()) }
+    }
+
+    /// Move the clock forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap_or_else(|p| p.into_inner()) += duration;
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    fn is_real_time(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_advanced() {
+        let clock = MockClock::new();
+        let start = clock.now();
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(clock.now(), start);
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(clock.now() - start, Duration::from_secs(30));
+    }
+}
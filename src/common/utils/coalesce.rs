@@ -0,0 +1,309 @@
+/* src/common/utils/coalesce.rs */
+#![warn(missing_docs)]
+//! **Brief:** Single-flight call coalescing and debouncing for failure-prone operations.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Call Coalescing]
+//!  - [Single Flight]
+//!  - [Debounce]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! When a dependency recovers, every caller that was waiting on it retries at
+//! once. `SingleFlight` lets only the first caller for a key run the
+//! operation; callers arriving while it runs wait and receive a clone of its
+//! result. Errors are shared the same way, which stays cheap because cloning
+//! an `AklypseError` shares its context. `Debounce` lets at most one call
+//! through per window and skips the rest.
+
+use crate::common::error::{AklypseError, InternalSnafu, Result};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+// Result slot of one in-flight call, filled by its leader
+struct Flight<T> {
+    result: Mutex<Option<Result<T>>>,
+    done: Condvar,
+}
+
+/// Coalesces concurrent calls with the same key into one execution
+pub struct SingleFlight<K, T> {
+    flights: Mutex<HashMap<K, Arc<Flight<T>>>>,
+}
+
+impl<K: Eq + Hash + Clone, T: Clone> SingleFlight<K, T> {
+    /// Create a group with no call in flight
+    pub fn new() -> Self {
+        Self { flights: Mutex::new(HashMap::new()) }
+    }
+
+    /// Run `operation` for `key`, or wait for the call already running for it
+    ///
+    /// Returns the result and whether it was shared from another caller's
+    /// execution. Calls made after the execution finished run again.
+    pub fn execute(&self, key: K, operation: impl FnOnce() -> Result<T>) -> (Result<T>, bool) {
+        let (flight, leader) = {
+            let mut flights = self.flights.lock().unwrap_or_else(|p| p.into_inner());
+            match flights.get(&key) {
+                Some(flight) => (flight.clone(), false),
+                None => {
+                    let flight = Arc::new(Flight { result: Mutex::new(None), done: Condvar::new() });
+                    flights.insert(key.clone(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leader {
+            let mut result = flight.result.lock().unwrap_or_else(|p| p.into_inner());
+            while result.is_none() {
+                result = flight.done.wait(result).unwrap_or_else(|p| p.into_inner());
+            }
+            return (result.clone().unwrap_or_else(|| Err(abandoned())), true);
+        }
+
+        // Completes the flight even if `operation` panics, so waiters never hang
+        let landing = Landing { group: self, key: Some(key), flight: &flight };
+        let result = operation();
+        landing.land(result.clone());
+        (result, false)
+    }
+
+    /// Number of keys with a call in flight
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap_or_else(|p| p.into_inner()).len()
+    }
+}
+
+impl<K: Eq + Hash + Clone, T: Clone> Default for SingleFlight<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Landing<'a, K: Eq + Hash, T> {
+    group: &'a SingleFlight<K, T>,
+    key: Option<K>,
+    flight: &'a Flight<T>,
+}
+
+impl<K: Eq + Hash, T> Landing<'_, K, T> {
+    fn land(mut self, result: Result<T>) {
+        self.finish(result);
+    }
+
+    fn finish(&mut self, result: Result<T>) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        self.group.flights.lock().unwrap_or_else(|p| p.into_inner()).remove(&key);
+        *self.flight.result.lock().unwrap_or_else(|p| p.into_inner()) = Some(result);
+        self.flight.done.notify_all();
+    }
+}
+
+impl<K: Eq + Hash, T> Drop for Landing<'_, K, T> {
+    fn drop(&mut self) {
+        self.finish(Err(abandoned()));
+    }
+}
+
+/// Coalesces concurrent async calls with the same key into one execution
+#[cfg(feature = "tokio")]
+pub struct AsyncSingleFlight<K, T> {
+    flights: Mutex<HashMap<K, tokio::sync::watch::Receiver<Option<Result<T>>>>>,
+}
+
+#[cfg(feature = "tokio")]
+impl<K: Eq + Hash + Clone, T: Clone> AsyncSingleFlight<K, T> {
+    /// Create a group with no call in flight
+    pub fn new() -> Self {
+        Self { flights: Mutex::new(HashMap::new()) }
+    }
+
+    /// Run `operation` for `key`, or wait for the call already running for it
+    ///
+    /// Returns the result and whether it was shared. Waiters fail with
+    /// `AklypseError::Internal` if the running call is cancelled.
+    pub async fn execute<F, Fut>(&self, key: K, operation: F) -> (Result<T>, bool)
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let sender = {
+            let mut flights = self.flights.lock().unwrap_or_else(|p| p.into_inner());
+            match flights.get(&key) {
+                Some(receiver) => Err(receiver.clone()),
+                None => {
+                    let (sender, receiver) = tokio::sync::watch::channel(None);
+                    flights.insert(key.clone(), receiver);
+                    Ok(sender)
+                }
+            }
+        };
+
+        match sender {
+            Err(mut receiver) => {
+                let result = match receiver.wait_for(Option::is_some).await {
+                    Ok(result) => result.clone().unwrap_or_else(|| Err(abandoned())),
+                    Err(_) => Err(abandoned()),
+                };
+                (result, true)
+            }
+            Ok(sender) => {
+                // Removes the key even if this future is dropped mid-call
+                let cleanup = AsyncCleanup { flights: &self.flights, key: Some(key) };
+                let result = operation().await;
+                drop(cleanup);
+                let _ = sender.send(Some(result.clone()));
+                (result, false)
+            }
+        }
+    }
+
+    /// Number of keys with a call in flight
+    pub fn in_flight(&self) -> usize {
+        self.flights.lock().unwrap_or_else(|p| p.into_inner()).len()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<K: Eq + Hash + Clone, T: Clone> Default for AsyncSingleFlight<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tokio")]
+struct AsyncCleanup<'a, K: Eq + Hash, T> {
+    flights: &'a Mutex<HashMap<K, tokio::sync::watch::Receiver<Option<Result<T>>>>>,
+    key: Option<K>,
+}
+
+#[cfg(feature = "tokio")]
+impl<K: Eq + Hash, T> Drop for AsyncCleanup<'_, K, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.flights.lock().unwrap_or_else(|p| p.into_inner()).remove(&key);
+        }
+    }
+}
+
+fn abandoned() -> AklypseError {
+    InternalSnafu { message: "Coalesced call ended without a result".to_string(), source: None }.build()
+}
+
+/// Lets at most one call through per window, skipping the others
+#[derive(Debug)]
+pub struct Debounce {
+    window: Duration,
+    last_run: Mutex<Option<Instant>>,
+}
+
+impl Debounce {
+    /// Debounce allowing one call every `window`
+    pub fn new(window: Duration) -> Self {
+        Self { window, last_run: Mutex::new(None) }
+    }
+
+    /// Whether a call made now would run
+    pub fn is_ready(&self) -> bool {
+        self.last_run
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .is_none_or(|last_run| last_run.elapsed() >= self.window)
+    }
+
+    /// Run `operation` unless another call ran less than `window` ago
+    pub fn call<R>(&self, operation: impl FnOnce() -> R) -> Option<R> {
+        {
+            let mut last_run = self.last_run.lock().unwrap_or_else(|p| p.into_inner());
+            if last_run.is_some_and(|last_run| last_run.elapsed() < self.window) {
+                return None;
+            }
+            *last_run = Some(Instant::now());
+        }
+        Some(operation())
+    }
+
+    /// Let the next call through regardless of the window
+    pub fn reset(&self) {
+        *self.last_run.lock().unwrap_or_else(|p| p.into_inner()) = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_single_flight_shares_one_execution() {
+        let group = Arc::new(SingleFlight::<&str, u32>::new());
+        let executions = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(4));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let (group, executions, barrier) = (group.clone(), executions.clone(), barrier.clone());
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    group.execute("rates", || {
+                        executions.fetch_add(1, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(50));
+                        Err(InternalSnafu { message: "upstream down".to_string(), source: None }.build())
+                    })
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert_eq!(results.iter().filter(|(_, shared)| *shared).count(), 3);
+        assert!(results.iter().all(|(result, _)| result.as_ref().unwrap_err().category() == ErrorCategory::Internal));
+        assert_eq!(group.in_flight(), 0);
+        let (result, shared) = group.execute("rates", || Ok(5));
+        assert_eq!((result.unwrap(), shared), (5, false));
+    }
+
+    #[test]
+    fn test_debounce() {
+        let debounce = Debounce::new(Duration::from_secs(60));
+        assert_eq!(debounce.call(|| 1), Some(1));
+        assert_eq!(debounce.call(|| 2), None);
+        assert!(!debounce.is_ready());
+        debounce.reset();
+        assert_eq!(debounce.call(|| 3), Some(3));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_single_flight() {
+        let group = Arc::new(AsyncSingleFlight::<u8, String>::new());
+        let leader = {
+            let group = group.clone();
+            tokio::spawn(async move {
+                group
+                    .execute(1, || async {
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok("fresh".to_string())
+                    })
+                    .await
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let (follower, shared) = group.execute(1, || async { Ok("second".to_string()) }).await;
+
+        assert!(shared);
+        assert_eq!(follower.unwrap(), "fresh");
+        assert_eq!(leader.await.unwrap().0.unwrap(), "fresh");
+        assert_eq!(group.in_flight(), 0);
+    }
+}
@@ -0,0 +1,433 @@
+/* src/common/utils/config.rs */
+#![warn(missing_docs)]
+//! **Brief:** Layered configuration loading reporting failures as rich Config errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Configuration Loading]
+//!  - [Layer Merging]
+//!  - [Environment Overrides]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ConfigLoader` merges defaults, configuration files and environment
+//! variables, in the order they were added, into one document and
+//! deserializes it into the user's type. Tables merge key by key; any other
+//! value replaces what earlier layers set. Files are read as TOML, YAML or
+//! JSON by extension (`.toml` needs feature `toml`, `.yaml`/`.yml` feature
+//! `yaml`, `.json` feature `json`); other files fail with a `Config` error.
+//! Environment variables whose name or value is not UTF-8 are skipped.
+//!
+//! Every failure is an `AklypseError::Config` whose `path` is the file that
+//! supplied the offending value. Its context records the key path under
+//! `KEY_PATH_METADATA_KEY`, the expected type under
+//! `EXPECTED_TYPE_METADATA_KEY`, the layer under `SOURCE_METADATA_KEY` and,
+//! for syntax errors, the line under `parse::LINE_METADATA_KEY`; Decrust
+//! reads these to point its configuration fix at the right place.
+
+use crate::common::error::{AklypseError, ConfigSnafu, ErrorContext, Result};
+use crate::common::utils::{fs, parse};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// Metadata key holding the dotted path of the offending key, e.g. `server.port`
+pub const KEY_PATH_METADATA_KEY: &str = "config.key_path";
+
+/// Metadata key holding the type the offending value should have had
+pub const EXPECTED_TYPE_METADATA_KEY: &str = "config.expected_type";
+
+/// Metadata key naming the layer that supplied the offending value
+pub const SOURCE_METADATA_KEY: &str = "config.source";
+
+// Separator between key segments in environment variable names
+const ENV_SEPARATOR: &str = "__";
+
+// One configuration layer, applied in insertion order
+enum Layer {
+    Defaults(Value),
+    File { path: PathBuf, required: bool },
+    Env { prefix: String, vars: Option<Vec<(String, String)>> },
+}
+
+// Where a merged value came from
+#[derive(Debug, Clone)]
+enum Origin {
+    Defaults,
+    File(PathBuf),
+    Env(String),
+}
+
+impl Origin {
+    fn path(&self) -> Option<PathBuf> {
+        match self {
+            Origin::File(path) => Some(path.clone()),
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Origin::Defaults => "defaults".to_string(),
+            Origin::File(path) => format!("file:{}", path.display()),
+            Origin::Env(name) => format!("env:{}", name),
+        }
+    }
+}
+
+/// Builder merging configuration layers into a `T`
+pub struct ConfigLoader<T> {
+    layers: Vec<Layer>,
+    defaults_error: Option<AklypseError>,
+    _target: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> ConfigLoader<T> {
+    /// Loader with no layers
+    pub fn new() -> Self {
+        Self { layers: Vec::new(), defaults_error: None, _target: PhantomData }
+    }
+
+    /// Start from the values of `defaults`
+    pub fn with_defaults(mut self, defaults: &T) -> Self
+    where
+        T: Serialize,
+    {
+        match serde_json::to_value(defaults) {
+            Ok(value) => self.layers.push(Layer::Defaults(value)),
+            Err(source) => {
+                self.defaults_error = Some(
+                    ConfigSnafu {
+                        message: "Default configuration could not be serialized".to_string(),
+                        path: None::<PathBuf>,
+                        source: Some(Box::new(source) as Box<dyn std::error::Error + Send + Sync>),
+                    }
+                    .build(),
+                )
+            }
+        }
+        self
+    }
+
+    /// Merge the file at `path`, failing the load if it cannot be read
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::File { path: path.into(), required: true });
+        self
+    }
+
+    /// Merge the file at `path` if it exists
+    pub fn with_optional_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.layers.push(Layer::File { path: path.into(), required: false });
+        self
+    }
+
+    /// Merge process environment variables starting with `prefix`
+    ///
+    /// `APP__SERVER__PORT=8080` with prefix `APP` sets `server.port`. Values
+    /// that parse as JSON numbers, booleans or `null` keep that type; anything
+    /// else is a string (quote it, e.g. `"8080"`, to force a string).
+    pub fn with_env(mut self, prefix: impl Into<String>) -> Self {
+        self.layers.push(Layer::Env { prefix: prefix.into(), vars: None });
+        self
+    }
+
+    /// Like `with_env`, reading `vars` instead of the process environment
+    pub fn with_env_vars(
+        mut self,
+        prefix: impl Into<String>,
+        vars: impl IntoIterator<Item = (impl Into<String>, impl Into<String>)>,
+    ) -> Self {
+        let vars = vars.into_iter().map(|(name, value)| (name.into(), value.into())).collect();
+        self.layers.push(Layer::Env { prefix: prefix.into(), vars: Some(vars) });
+        self
+    }
+
+    /// Merge all layers and deserialize the result
+    #[track_caller]
+    pub fn load(self) -> Result<T> {
+        if let Some(error) = self.defaults_error {
+            return Err(error);
+        }
+
+        let mut merged = Value::Object(Map::new());
+        let mut origins = HashMap::new();
+        for layer in self.layers {
+            match layer {
+                Layer::Defaults(value) => merge(&mut merged, value, "", &Origin::Defaults, &mut origins),
+                Layer::File { path, required } => {
+                    if !required && !path.exists() {
+                        continue;
+                    }
+                    let value = read_file(&path)?;
+                    merge(&mut merged, value, "", &Origin::File(path), &mut origins);
+                }
+                Layer::Env { prefix, vars } => {
+                    // `std::env::vars` panics on variables that are not UTF-8; they are skipped
+                    let vars = vars.unwrap_or_else(|| {
+                        std::env::vars_os()
+                            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+                            .collect()
+                    });
+                    for (name, raw) in vars {
+                        let Some(keys) = env_keys(&prefix, &name) else {
+                            continue;
+                        };
+                        let mut value = env_value(&raw);
+                        for key in keys.iter().rev() {
+                            value = Value::Object(Map::from_iter([(key.clone(), value)]));
+                        }
+                        merge(&mut merged, value, "", &Origin::Env(name), &mut origins);
+                    }
+                }
+            }
+        }
+
+        serde_path_to_error::deserialize(merged).map_err(|error| {
+            let message = error.inner().to_string();
+            let mut key_path = error.path().to_string();
+            if key_path == "." {
+                key_path.clear();
+            }
+            // serde reports a missing field at its parent
+            if let Some(field) = message.strip_prefix("missing field `").and_then(|rest| rest.strip_suffix('`')) {
+                key_path = join(&key_path, field);
+            }
+            let expected = message.rsplit_once(", expected ").map(|(_, expected)| expected.to_string());
+            let origin = origin_of(&origins, &key_path);
+
+            let error = ConfigSnafu {
+                message: format!("Invalid configuration at '{}': {}", key_path, message),
+                path: origin.as_ref().and_then(Origin::path),
+                source: Some(Box::new(error) as Box<dyn std::error::Error + Send + Sync>),
+            }
+            .build();
+            let mut context = ErrorContext::new(format!("Configuration key '{}' is invalid", key_path))
+                .with_metadata(KEY_PATH_METADATA_KEY, key_path);
+            if let Some(expected) = expected {
+                context = context.with_metadata(EXPECTED_TYPE_METADATA_KEY, expected);
+            }
+            if let Some(origin) = origin {
+                context = context.with_metadata(SOURCE_METADATA_KEY, origin.describe());
+            }
+            error.add_context(context)
+        })
+    }
+}
+
+impl<T: DeserializeOwned> Default for ConfigLoader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[track_caller]
+fn read_file(path: &Path) -> Result<Value> {
+    let text = fs::read_to_string(path).map_err(|error| {
+        ConfigSnafu {
+            message: format!("Configuration file '{}' could not be read", path.display()),
+            path: Some(path.to_path_buf()),
+            source: Some(Box::new(error) as Box<dyn std::error::Error + Send + Sync>),
+        }
+        .build()
+    })?;
+
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+    let parsed: Result<Value> = match extension.to_ascii_lowercase().as_str() {
+        #[cfg(feature = "json")]
+        "json" => parse::from_json_str(&text),
+        #[cfg(feature = "toml")]
+        "toml" => parse::from_toml_str(&text),
+        #[cfg(feature = "yaml")]
+        "yaml" | "yml" => parse::from_yaml_str(&text),
+        _ => {
+            return ConfigSnafu {
+                message: format!("Unsupported configuration file format '.{}'", extension),
+                path: Some(path.to_path_buf()),
+                source: None,
+            }
+            .fail()
+        }
+    };
+
+    parsed.map_err(|error| {
+        let line = error.get_rich_context().and_then(|context| context.metadata.get(parse::LINE_METADATA_KEY).cloned());
+        let mut context = ErrorContext::new(format!("Configuration file '{}' is malformed", path.display()))
+            .with_metadata(SOURCE_METADATA_KEY, Origin::File(path.to_path_buf()).describe());
+        if let Some(line) = line {
+            context = context.with_metadata(parse::LINE_METADATA_KEY, line);
+        }
+        ConfigSnafu {
+            message: format!("Configuration file '{}' is not valid {}", path.display(), extension.to_ascii_uppercase()),
+            path: Some(path.to_path_buf()),
+            source: Some(Box::new(error) as Box<dyn std::error::Error + Send + Sync>),
+        }
+        .build()
+        .add_context(context)
+    })
+}
+
+// Merge `overlay` into `base`, recording the origin of each replaced key path
+fn merge(base: &mut Value, overlay: Value, key_path: &str, origin: &Origin, origins: &mut HashMap<String, Origin>) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                let child_path = join(key_path, &key);
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value, &child_path, origin, origins),
+                    None => {
+                        origins.retain(|path, _| !is_within(path, &child_path));
+                        origins.insert(child_path, origin.clone());
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => {
+            origins.retain(|path, _| !is_within(path, key_path));
+            origins.insert(key_path.to_string(), origin.clone());
+            *base = overlay;
+        }
+    }
+}
+
+// Origin of the closest recorded ancestor of `key_path`
+fn origin_of(origins: &HashMap<String, Origin>, key_path: &str) -> Option<Origin> {
+    let mut current = key_path;
+    loop {
+        if let Some(origin) = origins.get(current) {
+            return Some(origin.clone());
+        }
+        current = &current[..current.rfind(['.', '['])?];
+    }
+}
+
+fn is_within(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path == ancestor
+        || path.strip_prefix(ancestor).is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+}
+
+fn join(parent: &str, key: &str) -> String {
+    if parent.is_empty() { key.to_string() } else { format!("{}.{}", parent, key) }
+}
+
+fn env_keys(prefix: &str, name: &str) -> Option<Vec<String>> {
+    let rest = name.strip_prefix(prefix)?.strip_prefix(ENV_SEPARATOR)?;
+    let keys: Vec<String> = rest.split(ENV_SEPARATOR).map(str::to_ascii_lowercase).collect();
+    (!keys.iter().any(String::is_empty)).then_some(keys)
+}
+
+fn env_value(raw: &str) -> Value {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::Null | Value::String(_))) => value,
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::decrust::Decrust;
+    use crate::common::error::types::FixDetails;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct AppConfig {
+        name: String,
+        debug: bool,
+        server: Server,
+    }
+
+    fn defaults() -> AppConfig {
+        AppConfig { name: "api".to_string(), debug: false, server: Server { host: "0.0.0.0".to_string(), port: 80 } }
+    }
+
+    fn scratch_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("aklypse-config-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn metadata(error: &AklypseError, key: &str) -> Option<String> {
+        error.get_rich_context().and_then(|context| context.metadata.get(key).cloned())
+    }
+
+    #[test]
+    fn test_layers_merge_in_order() {
+        let file = scratch_file("merge.json", r#"{ "server": { "port": 8080 } }"#);
+        let config = ConfigLoader::new()
+            .with_defaults(&defaults())
+            .with_file(&file)
+            .with_optional_file("does-not-exist.json")
+            .with_env_vars("APP", [("APP__DEBUG", "true"), ("APP__SERVER__HOST", "example.com"), ("OTHER__NAME", "x")])
+            .load()
+            .unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(
+            config,
+            AppConfig {
+                name: "api".to_string(),
+                debug: true,
+                server: Server { host: "example.com".to_string(), port: 8080 },
+            }
+        );
+    }
+
+    #[test]
+    fn test_type_error_names_file_key_and_type() {
+        let file = scratch_file("type.json", r#"{ "server": { "port": "eighty" } }"#);
+        let error = ConfigLoader::<AppConfig>::new().with_defaults(&defaults()).with_file(&file).load().unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(metadata(&error, KEY_PATH_METADATA_KEY).as_deref(), Some("server.port"));
+        assert_eq!(metadata(&error, EXPECTED_TYPE_METADATA_KEY).as_deref(), Some("u16"));
+        match Decrust::new().suggest_autocorrection(&error, None).and_then(|fix| fix.details) {
+            Some(FixDetails::SuggestCodeChange { file_path, suggested_code_snippet, .. }) => {
+                assert_eq!(file_path, file);
+                assert!(suggested_code_snippet.starts_with("# Set 'server.port' to a value of type u16"));
+            }
+            other => panic!("expected a code change, got {:?}", other),
+        }
+        match error {
+            AklypseError::WithRichContext { source, .. } => match *source {
+                AklypseError::Config { path, .. } => assert_eq!(path, Some(file)),
+                other => panic!("expected Config, got {:?}", other),
+            },
+            other => panic!("expected context, got {:?}", other),
+        }
+
+        let error = ConfigLoader::<AppConfig>::new()
+            .with_env_vars("APP", [("APP__NAME", "api"), ("APP__DEBUG", "yes")])
+            .load()
+            .unwrap_err();
+        assert_eq!(metadata(&error, SOURCE_METADATA_KEY).as_deref(), Some("env:APP__DEBUG"));
+        let missing = ConfigLoader::<AppConfig>::new().with_env_vars("APP", [("APP__NAME", "api")]).load().unwrap_err();
+        assert_eq!(metadata(&missing, KEY_PATH_METADATA_KEY).as_deref(), Some("debug"));
+    }
+
+    #[test]
+    fn test_syntax_error_records_line() {
+        let file = scratch_file("syntax.json", "{\n  \"name\": \"api\",\n  \"debug\": ,\n}");
+        let error = ConfigLoader::<AppConfig>::new().with_file(&file).load().unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+
+        assert_eq!(error.category(), crate::common::error::ErrorCategory::Configuration);
+        assert_eq!(metadata(&error, parse::LINE_METADATA_KEY).as_deref(), Some("3"));
+        match Decrust::new().suggest_autocorrection(&error, None).and_then(|fix| fix.details) {
+            Some(FixDetails::SuggestCodeChange { line_hint, .. }) => assert_eq!(line_hint, 3),
+            other => panic!("expected a code change, got {:?}", other),
+        }
+    }
+}
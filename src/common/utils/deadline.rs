@@ -0,0 +1,232 @@
+/* src/common/utils/deadline.rs */
+#![warn(missing_docs)]
+//! **Brief:** Ambient deadline scopes with checkpoints failing past expiry.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Deadlines]
+//!  - [Scoped Propagation]
+//!  - [Timeout Checkpoints]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! A deadline is entered once for a whole operation with `with_deadline`
+//! (thread-local) or `with_deadline_async` (tokio task-local, feature
+//! `tokio`). Code anywhere below asks how much time is left with `remaining`
+//! and calls `checkpoint` between steps, which fails with
+//! `AklypseError::Timeout` naming the operation once the deadline passed.
+//!
+//! Scopes nest, but an inner scope never extends the outer one: it keeps
+//! whichever deadline expires first. Task-locals are not inherited by spawned
+//! tasks; re-enter the scope with `with_deadline_at` inside the spawned task.
+//!
+//! `CircuitBreaker` and `Retrier` consult the active deadline: a breaker
+//! shrinks its operation timeout to the time left, and a retrier gives up
+//! rather than sleep past it. The errors they return carry the deadline in
+//! their `ErrorContext` (`ErrorContext::deadline`), so an outer retrier
+//! knows not to retry a call that ran out of the caller's time.
+
+use crate::common::error::{AklypseError, Result, TimeoutSnafu};
+use std::cell::RefCell;
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the operation of the deadline an error ran into
+pub const DEADLINE_METADATA_KEY: &str = "deadline";
+
+/// Metadata key holding that deadline's budget in milliseconds
+pub const DEADLINE_BUDGET_METADATA_KEY: &str = "deadline.budget_ms";
+
+/// Point in time by which a named operation must finish
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deadline {
+    operation: String,
+    budget: Duration,
+    expires_at: Instant,
+}
+
+impl Deadline {
+    /// Deadline `budget` from now
+    pub fn after(operation: impl Into<String>, budget: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            operation: operation.into(),
+            budget,
+            expires_at: now.checked_add(budget).unwrap_or(now + Duration::from_secs(u32::MAX as u64)),
+        }
+    }
+
+    /// Deadline at `expires_at`
+    pub fn at(operation: impl Into<String>, expires_at: Instant) -> Self {
+        Self {
+            operation: operation.into(),
+            budget: expires_at.saturating_duration_since(Instant::now()),
+            expires_at,
+        }
+    }
+
+    /// Name of the operation the deadline applies to
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// Time the operation was given when the deadline was set
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Instant the deadline expires at
+    pub fn expires_at(&self) -> Instant {
+        self.expires_at
+    }
+
+    /// Time left before expiry, zero once expired
+    pub fn remaining(&self) -> Duration {
+        self.expires_at.saturating_duration_since(Instant::now())
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    /// Fail with `AklypseError::Timeout` if the deadline has passed
+    #[track_caller]
+    pub fn check(&self) -> Result<()> {
+        if self.is_expired() {
+            return Err(self.timeout_error());
+        }
+        Ok(())
+    }
+
+    /// Timeout error naming the operation and its budget
+    #[track_caller]
+    pub fn timeout_error(&self) -> AklypseError {
+        TimeoutSnafu { operation: self.operation.clone(), duration: self.budget }.build()
+    }
+
+    // The earlier of `self` and the enclosing deadline
+    fn nested(self) -> Self {
+        match current() {
+            Some(outer) if outer.expires_at < self.expires_at => outer,
+            _ => self,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    static TASK_DEADLINE: Deadline;
+}
+
+thread_local! {
+    static THREAD_DEADLINES: RefCell<Vec<Deadline>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` with `operation` due within `budget` on the current thread
+pub fn with_deadline<R>(operation: impl Into<String>, budget: Duration, f: impl FnOnce() -> R) -> R {
+    with_deadline_at(Deadline::after(operation, budget), f)
+}
+
+/// Run `f` under `deadline` on the current thread
+pub fn with_deadline_at<R>(deadline: Deadline, f: impl FnOnce() -> R) -> R {
+    let deadline = deadline.nested();
+    THREAD_DEADLINES.with(|deadlines| deadlines.borrow_mut().push(deadline));
+    // Pops the deadline even when `f` unwinds
+    let _guard = ThreadDeadlineGuard;
+    f()
+}
+
+/// Run `future` with `operation` due within `budget` on the current task
+#[cfg(feature = "tokio")]
+pub async fn with_deadline_async<F: Future>(operation: impl Into<String>, budget: Duration, future: F) -> F::Output {
+    TASK_DEADLINE.scope(Deadline::after(operation, budget).nested(), future).await
+}
+
+/// Innermost active deadline, if any
+pub fn current() -> Option<Deadline> {
+    let thread_deadline = THREAD_DEADLINES.with(|deadlines| deadlines.borrow().last().cloned());
+    #[cfg(feature = "tokio")]
+    let thread_deadline = thread_deadline.or_else(|| TASK_DEADLINE.try_with(Deadline::clone).ok());
+    thread_deadline
+}
+
+/// Time left before the active deadline, or `None` outside of any deadline scope
+pub fn remaining() -> Option<Duration> {
+    current().map(|deadline| deadline.remaining())
+}
+
+/// Fail with `AklypseError::Timeout` if the active deadline has passed
+///
+/// Always succeeds outside of any deadline scope.
+#[track_caller]
+pub fn checkpoint() -> Result<()> {
+    match current() {
+        Some(deadline) => deadline.check(),
+        None => Ok(()),
+    }
+}
+
+struct ThreadDeadlineGuard;
+
+impl Drop for ThreadDeadlineGuard {
+    fn drop(&mut self) {
+        THREAD_DEADLINES.with(|deadlines| deadlines.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_fails_after_expiry() {
+        assert!(checkpoint().is_ok());
+        assert_eq!(remaining(), None);
+
+        with_deadline("import catalog", Duration::ZERO, || {
+            match checkpoint() {
+                Err(AklypseError::Timeout { operation, duration, .. }) => {
+                    assert_eq!(operation, "import catalog");
+                    assert_eq!(duration, Duration::ZERO);
+                }
+                other => panic!("expected a timeout, got {:?}", other),
+            }
+        });
+        with_deadline("import catalog", Duration::from_secs(60), || {
+            assert!(checkpoint().is_ok());
+            assert!(remaining().unwrap() > Duration::from_secs(59));
+        });
+        assert!(current().is_none());
+    }
+
+    #[test]
+    fn test_inner_scope_cannot_extend_outer() {
+        with_deadline("request", Duration::from_secs(1), || {
+            with_deadline("query", Duration::from_secs(60), || {
+                assert_eq!(current().unwrap().operation(), "request");
+                assert!(remaining().unwrap() <= Duration::from_secs(1));
+            });
+            with_deadline("cache lookup", Duration::from_millis(10), || {
+                assert_eq!(current().unwrap().operation(), "cache lookup");
+            });
+        });
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_task_deadline() {
+        use crate::common::error::ErrorCategory;
+
+        let result = with_deadline_async("sync orders", Duration::from_millis(1), async {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            checkpoint()
+        })
+        .await;
+        assert_eq!(result.unwrap_err().category(), ErrorCategory::Timeout);
+        assert!(remaining().is_none());
+    }
+}
@@ -0,0 +1,204 @@
+/* src/common/utils/fs.rs */
+#![warn(missing_docs)]
+//! **Brief:** Filesystem wrappers returning Io errors with path and operation filled in.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Filesystem]
+//!  - [Io Error Enrichment]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Each function mirrors its `std::fs` counterpart but fails with
+//! `AklypseError::Io` whose `path` and `operation` are always populated, which
+//! is what Decrust keys its I/O suggestions on. Failed writes additionally
+//! record the free space of the target filesystem under
+//! `AVAILABLE_BYTES_METADATA_KEY` where it can be queried (Unix with the
+//! `libc` feature).
+//!
+//! With feature `testing`, each function first consults the installed
+//! `FaultPlan` under `fs.<function name>`.
+
+use crate::common::error::{AklypseError, ErrorContext, IoSnafu, Result};
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Metadata key holding the bytes available on the filesystem a write failed on
+pub const AVAILABLE_BYTES_METADATA_KEY: &str = "fs.available_bytes";
+
+// Io error for `operation` on `path`, shared with the other utilities touching files
+#[track_caller]
+pub(crate) fn io_error(source: io::Error, path: &Path, operation: &str) -> AklypseError {
+    IoSnafu { source: Arc::new(source), path: Some(path.to_path_buf()), operation }.build()
+}
+
+/// Read the whole file at `path`
+#[track_caller]
+pub fn read(path: impl AsRef<Path>) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.read")?;
+    fs::read(path).map_err(|source| io_error(source, path, "read"))
+}
+
+/// Read the whole file at `path` as UTF-8 text
+#[track_caller]
+pub fn read_to_string(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.read_to_string")?;
+    fs::read_to_string(path).map_err(|source| io_error(source, path, "read"))
+}
+
+/// Write `contents` to `path`, replacing any existing file
+#[track_caller]
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<()> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.write")?;
+    fs::write(path, contents).map_err(|source| {
+        let error = io_error(source, path, "write");
+        match available_space(path) {
+            Some(bytes) => error.add_context(
+                ErrorContext::new(format!("Write failed with {} bytes available", bytes))
+                    .with_metadata(AVAILABLE_BYTES_METADATA_KEY, bytes.to_string()),
+            ),
+            None => error,
+        }
+    })
+}
+
+/// Create `path` and all of its missing parents
+#[track_caller]
+pub fn create_dir_all(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.create_dir_all")?;
+    fs::create_dir_all(path).map_err(|source| io_error(source, path, "create directory"))
+}
+
+/// Rename `from` to `to`; the error's path is `from`
+#[track_caller]
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.rename")?;
+    fs::rename(from, to).map_err(|source| io_error(source, from, &format!("rename to '{}'", to.display())))
+}
+
+/// Remove the file at `path`
+#[track_caller]
+pub fn remove_file(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.remove_file")?;
+    fs::remove_file(path).map_err(|source| io_error(source, path, "remove file"))
+}
+
+/// Remove the empty directory at `path`
+#[track_caller]
+pub fn remove_dir(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.remove_dir")?;
+    fs::remove_dir(path).map_err(|source| io_error(source, path, "remove directory"))
+}
+
+/// Entries of the directory at `path`
+///
+/// Errors on individual entries are plain `io::Error`s, as with `std::fs`.
+#[track_caller]
+pub fn read_dir(path: impl AsRef<Path>) -> Result<fs::ReadDir> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.read_dir")?;
+    fs::read_dir(path).map_err(|source| io_error(source, path, "read directory"))
+}
+
+/// Metadata of the file or directory at `path`
+#[track_caller]
+pub fn metadata(path: impl AsRef<Path>) -> Result<Metadata> {
+    let path = path.as_ref();
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject("fs.metadata")?;
+    fs::metadata(path).map_err(|source| io_error(source, path, "read metadata"))
+}
+
+/// Bytes available to unprivileged users on the filesystem holding `path`
+///
+/// Queries the closest existing ancestor when `path` does not exist yet.
+/// Always `None` where the query is unsupported.
+pub fn available_space(path: &Path) -> Option<u64> {
+    #[cfg(all(unix, feature = "libc"))]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let existing = path.ancestors().find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())?;
+        let c_path = std::ffi::CString::new(existing.as_os_str().as_bytes()).ok()?;
+        let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+        // SAFETY: `c_path` is NUL-terminated and `stats` is only read after statvfs succeeded
+        let stats = unsafe {
+            if libc::statvfs(c_path.as_ptr(), stats.as_mut_ptr()) != 0 {
+                return None;
+            }
+            stats.assume_init()
+        };
+        #[allow(clippy::unnecessary_cast)]
+        Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+    }
+    #[cfg(not(all(unix, feature = "libc")))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aklypse-fs-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = scratch_dir("round-trip");
+        create_dir_all(dir.join("nested")).unwrap();
+        write(dir.join("nested/a.txt"), "hello").unwrap();
+        rename(dir.join("nested/a.txt"), dir.join("b.txt")).unwrap();
+        assert_eq!(read_to_string(dir.join("b.txt")).unwrap(), "hello");
+        assert_eq!(metadata(dir.join("b.txt")).unwrap().len(), 5);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors_carry_path_and_operation() {
+        let missing = scratch_dir("missing").join("settings.toml");
+
+        match read(&missing) {
+            Err(AklypseError::Io { path, operation, source, .. }) => {
+                assert_eq!(path.as_deref(), Some(missing.as_path()));
+                assert_eq!(operation, "read");
+                assert_eq!(source.kind(), io::ErrorKind::NotFound);
+            }
+            other => panic!("expected Io, got {:?}", other),
+        }
+        match rename(&missing, "elsewhere.toml") {
+            Err(AklypseError::Io { operation, .. }) => assert_eq!(operation, "rename to 'elsewhere.toml'"),
+            other => panic!("expected Io, got {:?}", other),
+        }
+
+        let error = write(missing.join("child"), "x").unwrap_err();
+        assert_eq!(error.category(), crate::common::error::ErrorCategory::Io);
+        #[cfg(all(unix, feature = "libc"))]
+        assert!(error.get_rich_context().unwrap().metadata.contains_key(AVAILABLE_BYTES_METADATA_KEY));
+    }
+}
@@ -0,0 +1,266 @@
+/* src/common/utils/idempotency.rs */
+#![warn(missing_docs)]
+//! **Brief:** Idempotency keys and guards preventing retried side effects from running twice.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Retry]
+//!  - [Idempotency Keys]
+//!  - [Duplicate Suppression]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! An operation with side effects (charging a card, sending an email) must not
+//! run twice when a caller retries a request whose first attempt succeeded.
+//! Each logical request carries an `IdempotencyKey`; an `IdempotencyGuard`
+//! claims the key before the operation runs, marks it completed on success
+//! and releases it on failure so the operation may be tried again.
+//!
+//! `guarded` and `retry::retry_idempotent` reject a duplicate with
+//! `AklypseError::StateConflict`, whose context records the key under
+//! `IDEMPOTENCY_KEY_METADATA_KEY` and the reason under
+//! `DUPLICATE_METADATA_KEY`.
+
+use crate::common::error::{AklypseError, ErrorContext, ReportId, Result, StateConflictSnafu};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the idempotency key of a suppressed duplicate
+pub const IDEMPOTENCY_KEY_METADATA_KEY: &str = "idempotency.key";
+
+/// Metadata key holding why a duplicate was suppressed (`in_progress` or `completed`)
+pub const DUPLICATE_METADATA_KEY: &str = "idempotency.duplicate";
+
+/// Key identifying one logical execution of a non-idempotent operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Key from a caller-provided value, such as an `Idempotency-Key` header
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    /// Fresh unique key (a ULID)
+    pub fn generate() -> Self {
+        Self(ReportId::generate().to_string())
+    }
+
+    /// Key derived from the parts identifying a request, stable across processes
+    ///
+    /// `derive("payments", &["order-17", "4200"])` yields the same key for
+    /// every attempt to charge order 17 the same amount.
+    pub fn derive(namespace: &str, parts: &[&str]) -> Self {
+        // 64-bit FNV-1a over length-prefixed parts, so ("ab", "c") != ("a", "bc")
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for part in std::iter::once(&namespace).chain(parts) {
+            for byte in (part.len() as u64).to_le_bytes().iter().chain(part.as_bytes()) {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        Self(format!("{}:{:016x}", namespace, hash))
+    }
+
+    /// Text form of the key
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Outcome of claiming an idempotency key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotencyStatus {
+    /// The key was free and is now claimed by the caller
+    Claimed,
+    /// Another execution holding the key has not finished
+    InProgress,
+    /// An execution with the key already succeeded
+    Completed,
+}
+
+/// Record of which idempotency keys are running or done
+pub trait IdempotencyGuard: Send + Sync {
+    /// Claim `key` for execution; only `Claimed` allows running the operation
+    fn try_claim(&self, key: &IdempotencyKey) -> IdempotencyStatus;
+
+    /// Mark the execution holding `key` as succeeded
+    fn complete(&self, key: &IdempotencyKey);
+
+    /// Drop the claim on `key` after a failure, so a later execution may run
+    fn release(&self, key: &IdempotencyKey);
+}
+
+/// In-memory `IdempotencyGuard` forgetting keys `ttl` after they were last touched
+///
+/// Claims also expire after `ttl`, so a key held by a crashed execution does
+/// not stay blocked forever.
+#[derive(Debug)]
+pub struct InMemoryIdempotencyStore {
+    ttl: Duration,
+    entries: Mutex<HashMap<IdempotencyKey, (IdempotencyStatus, Instant)>>,
+}
+
+impl InMemoryIdempotencyStore {
+    /// Store remembering keys for `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self { ttl, entries: Mutex::new(HashMap::new()) }
+    }
+
+    /// Number of keys remembered, including expired ones not yet purged
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no key is remembered
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Forget every expired key
+    pub fn purge_expired(&self) {
+        let ttl = self.ttl;
+        self.lock().retain(|_, (_, touched)| touched.elapsed() < ttl);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<IdempotencyKey, (IdempotencyStatus, Instant)>> {
+        self.entries.lock().unwrap_or_else(|p| p.into_inner())
+    }
+}
+
+impl Default for InMemoryIdempotencyStore {
+    /// Keys are remembered for 24 hours
+    fn default() -> Self {
+        Self::new(Duration::from_secs(24 * 60 * 60))
+    }
+}
+
+impl IdempotencyGuard for InMemoryIdempotencyStore {
+    fn try_claim(&self, key: &IdempotencyKey) -> IdempotencyStatus {
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some((status, touched)) if touched.elapsed() < self.ttl => *status,
+            _ => {
+                entries.insert(key.clone(), (IdempotencyStatus::InProgress, Instant::now()));
+                IdempotencyStatus::Claimed
+            }
+        }
+    }
+
+    fn complete(&self, key: &IdempotencyKey) {
+        self.lock().insert(key.clone(), (IdempotencyStatus::Completed, Instant::now()));
+    }
+
+    fn release(&self, key: &IdempotencyKey) {
+        self.lock().remove(key);
+    }
+}
+
+/// Run `operation` once per `key`, rejecting duplicates
+///
+/// The key is completed when `operation` succeeds and released when it fails.
+#[track_caller]
+pub fn guarded<T>(guard: &dyn IdempotencyGuard, key: &IdempotencyKey, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+    claim(guard, key)?;
+    settle(guard, key, operation())
+}
+
+// Claim `key`, or fail with the duplicate-suppression error
+#[track_caller]
+pub(crate) fn claim(guard: &dyn IdempotencyGuard, key: &IdempotencyKey) -> Result<()> {
+    let reason = match guard.try_claim(key) {
+        IdempotencyStatus::Claimed => return Ok(()),
+        IdempotencyStatus::InProgress => "in_progress",
+        IdempotencyStatus::Completed => "completed",
+    };
+    let error: AklypseError = StateConflictSnafu {
+        message: format!("Operation with idempotency key '{}' is {}", key, reason.replace('_', " ")),
+    }
+    .build();
+    Err(error.add_context(
+        ErrorContext::new("Duplicate execution suppressed")
+            .with_metadata(IDEMPOTENCY_KEY_METADATA_KEY, key.to_string())
+            .with_metadata(DUPLICATE_METADATA_KEY, reason),
+    ))
+}
+
+// Complete or release `key` according to `result`
+pub(crate) fn settle<T>(guard: &dyn IdempotencyGuard, key: &IdempotencyKey, result: Result<T>) -> Result<T> {
+    match &result {
+        Ok(_) => guard.complete(key),
+        Err(_) => guard.release(key),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ErrorCategory, InternalSnafu};
+
+    #[test]
+    fn test_keys() {
+        let derived = IdempotencyKey::derive("payments", &["order-17", "4200"]);
+        assert_eq!(derived, IdempotencyKey::derive("payments", &["order-17", "4200"]));
+        assert_ne!(derived, IdempotencyKey::derive("payments", &["order-1", "74200"]));
+        assert!(derived.as_str().starts_with("payments:"));
+
+        let generated = IdempotencyKey::generate();
+        assert_eq!(generated.as_str().len(), 26);
+        assert_ne!(generated, IdempotencyKey::generate());
+    }
+
+    #[test]
+    fn test_guarded_suppresses_duplicates() {
+        let store = InMemoryIdempotencyStore::default();
+        let key = IdempotencyKey::new("charge-17");
+        let mut charges = 0;
+
+        let failed = guarded(&store, &key, || -> Result<()> {
+            Err(InternalSnafu { message: "gateway reset".to_string(), source: None }.build())
+        });
+        assert!(failed.is_err());
+        assert!(store.is_empty());
+
+        guarded(&store, &key, || {
+            charges += 1;
+            Ok(())
+        })
+        .unwrap();
+        let duplicate = guarded(&store, &key, || {
+            charges += 1;
+            Ok(())
+        })
+        .unwrap_err();
+
+        assert_eq!(charges, 1);
+        assert_eq!(duplicate.category(), ErrorCategory::StateConflict);
+        let context = duplicate.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(IDEMPOTENCY_KEY_METADATA_KEY).map(String::as_str), Some("charge-17"));
+        assert_eq!(context.metadata.get(DUPLICATE_METADATA_KEY).map(String::as_str), Some("completed"));
+    }
+
+    #[test]
+    fn test_store_expires_keys() {
+        let store = InMemoryIdempotencyStore::new(Duration::ZERO);
+        let key = IdempotencyKey::new("job-1");
+        assert_eq!(store.try_claim(&key), IdempotencyStatus::Claimed);
+        assert_eq!(store.try_claim(&key), IdempotencyStatus::Claimed);
+        store.purge_expired();
+        assert!(store.is_empty());
+
+        let store = InMemoryIdempotencyStore::default();
+        assert_eq!(store.try_claim(&key), IdempotencyStatus::Claimed);
+        assert_eq!(store.try_claim(&key), IdempotencyStatus::InProgress);
+    }
+}
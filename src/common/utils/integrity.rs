@@ -0,0 +1,138 @@
+/* src/common/utils/integrity.rs */
+#![warn(missing_docs)]
+//! **Brief:** SHA-256 checksums and verification reporting corrupt files as Validation errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Checksums]
+//!  - [Integrity Verification]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `sha256_file` streams a file through SHA-256 and `verify_checksum`
+//! compares the result with an expected digest, failing with
+//! `AklypseError::Validation` on field `checksum`. The error's context
+//! records the file under `PATH_METADATA_KEY` and both digests under
+//! `EXPECTED_METADATA_KEY` and `ACTUAL_METADATA_KEY`. Download layers can add
+//! `SOURCE_URL_METADATA_KEY` so Decrust suggests fetching the file again.
+
+use crate::common::error::{AklypseError, ErrorContext, Result, ValidationSnafu};
+use crate::common::utils::fs;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+/// Metadata key holding the file whose checksum did not match
+pub const PATH_METADATA_KEY: &str = "integrity.path";
+
+/// Metadata key holding the expected digest
+pub const EXPECTED_METADATA_KEY: &str = "integrity.expected";
+
+/// Metadata key holding the digest the file actually has
+pub const ACTUAL_METADATA_KEY: &str = "integrity.actual";
+
+/// Metadata key holding the URL the file was downloaded from, set by callers
+pub const SOURCE_URL_METADATA_KEY: &str = "integrity.source_url";
+
+// Bytes read per chunk while hashing
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Lowercase hex SHA-256 of `data`
+pub fn sha256_hex(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+/// Lowercase hex SHA-256 of the file at `path`, read in chunks
+#[track_caller]
+pub fn sha256_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let mut file = std::fs::File::open(path).map_err(|error| fs::io_error(error, path, "open"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; CHUNK_SIZE];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => hasher.update(&buffer[..read]),
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(fs::io_error(error, path, "read")),
+        }
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Check that the file at `path` has the SHA-256 digest `expected`
+///
+/// `expected` is hex in either case, optionally prefixed `sha256:`.
+#[track_caller]
+pub fn verify_checksum(path: impl AsRef<Path>, expected: &str) -> Result<()> {
+    let path = path.as_ref();
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected).to_ascii_lowercase();
+    if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+        return ValidationSnafu {
+            field: "checksum",
+            message: format!("'{}' is not a SHA-256 digest (64 hex digits)", expected),
+        }
+        .fail();
+    }
+
+    let actual = sha256_file(path)?;
+    if actual == expected {
+        return Ok(());
+    }
+    let error: AklypseError = ValidationSnafu {
+        field: "checksum",
+        message: format!("SHA-256 of '{}' is {}, expected {}", path.display(), actual, expected),
+    }
+    .build();
+    Err(error.add_context(
+        ErrorContext::new(format!("File '{}' is corrupt or incomplete", path.display()))
+            .with_metadata(PATH_METADATA_KEY, path.display().to_string())
+            .with_metadata(EXPECTED_METADATA_KEY, expected)
+            .with_metadata(ACTUAL_METADATA_KEY, actual),
+    ))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::decrust::Decrust;
+    use crate::common::utils::tempres::TempFileGuard;
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(sha256_hex(b"hello"), HELLO_SHA256);
+        let file = TempFileGuard::new("aklypse-integrity-").unwrap();
+        std::fs::write(file.path(), vec![7u8; CHUNK_SIZE * 2 + 3]).unwrap();
+        assert_eq!(sha256_file(file.path()).unwrap(), sha256_hex(&vec![7u8; CHUNK_SIZE * 2 + 3]));
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let file = TempFileGuard::new("aklypse-integrity-").unwrap();
+        std::fs::write(file.path(), "hello").unwrap();
+        verify_checksum(file.path(), &format!("sha256:{}", HELLO_SHA256.to_uppercase())).unwrap();
+
+        std::fs::write(file.path(), "hellO").unwrap();
+        let error = verify_checksum(file.path(), HELLO_SHA256).unwrap_err();
+        let metadata = &error.get_rich_context().unwrap().metadata;
+        assert_eq!(metadata.get(EXPECTED_METADATA_KEY).map(String::as_str), Some(HELLO_SHA256));
+        assert_eq!(metadata.get(ACTUAL_METADATA_KEY), Some(&sha256_hex(b"hellO")));
+        assert_eq!(metadata.get(PATH_METADATA_KEY), Some(&file.path().display().to_string()));
+
+        let error = error.add_context(ErrorContext::new("Downloading model").with_metadata(SOURCE_URL_METADATA_KEY, "https://example.com/m.bin"));
+        let fix = Decrust::new().suggest_autocorrection(&error, None).unwrap();
+        assert_eq!(fix.commands_to_apply.len(), 2);
+        assert!(fix.commands_to_apply[1].ends_with("\"https://example.com/m.bin\""));
+
+        assert!(matches!(verify_checksum(file.path(), "abc"), Err(AklypseError::Validation { .. })));
+    }
+}
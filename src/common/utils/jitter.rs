@@ -0,0 +1,176 @@
+/* src/common/utils/jitter.rs */
+#![warn(missing_docs)]
+//! **Brief:** Jitter strategies randomizing backoff delays, with a seedable random source.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Backoff Policies]
+//!  - [Jitter]
+//!  - [Deterministic Randomness]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Clients backing off on the same schedule retry in lockstep. A `Jitter`
+//! spreads their delays out: `FullJitter` and `EqualJitter` randomize each
+//! delay independently, while `DecorrelatedJitter` grows from the previous
+//! delay. The retry helpers, the circuit breaker's open-state timeout and
+//! the rate limiter's `retry_after` all take an `Arc<dyn Jitter>`.
+//!
+//! Randomness comes from a `JitterRng`. `SeededRng::new` makes the sequence
+//! reproducible, which is how tests pin jittered delays down.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Source of uniformly distributed random numbers for jitter
+pub trait JitterRng: Send {
+    /// Next 64 random bits
+    fn next_u64(&mut self) -> u64;
+
+    /// Next random number in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Random duration between `low` and `high`, inclusive of `low`
+    fn between(&mut self, low: Duration, high: Duration) -> Duration {
+        if high <= low {
+            return low;
+        }
+        let span = (high - low).as_secs_f64() * self.next_f64();
+        low + Duration::try_from_secs_f64(span).unwrap_or_default()
+    }
+}
+
+/// Small, fast SplitMix64 generator; not suitable for cryptography
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// Generator producing the same sequence for the same `seed`
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generator seeded from the process's hash randomness
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos(),
+        );
+        Self::new(hasher.finish())
+    }
+
+    /// Generator from `seed` when given, else from entropy
+    pub fn from_seed(seed: Option<u64>) -> Self {
+        seed.map_or_else(Self::from_entropy, Self::new)
+    }
+}
+
+impl Default for SeededRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl JitterRng for SeededRng {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Strategy randomizing a backoff delay
+pub trait Jitter: Send + Sync + fmt::Debug {
+    /// Randomized form of `delay`; `previous` is the last delay this strategy returned, or zero
+    fn apply(&self, delay: Duration, previous: Duration, rng: &mut dyn JitterRng) -> Duration;
+}
+
+/// Uniformly random delay between zero and the computed delay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FullJitter;
+
+impl Jitter for FullJitter {
+    fn apply(&self, delay: Duration, _previous: Duration, rng: &mut dyn JitterRng) -> Duration {
+        rng.between(Duration::ZERO, delay)
+    }
+}
+
+/// Half the computed delay plus a random share of the other half
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EqualJitter;
+
+impl Jitter for EqualJitter {
+    fn apply(&self, delay: Duration, _previous: Duration, rng: &mut dyn JitterRng) -> Duration {
+        let half = delay / 2;
+        half + rng.between(Duration::ZERO, delay - half)
+    }
+}
+
+/// Random delay between the computed delay and three times the previous one, capped at `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecorrelatedJitter {
+    /// Upper bound of any delay
+    pub max: Duration,
+}
+
+impl DecorrelatedJitter {
+    /// Decorrelated jitter capped at `max`
+    pub fn new(max: Duration) -> Self {
+        Self { max }
+    }
+}
+
+impl Jitter for DecorrelatedJitter {
+    fn apply(&self, delay: Duration, previous: Duration, rng: &mut dyn JitterRng) -> Duration {
+        let high = previous.saturating_mul(3).max(delay);
+        rng.between(delay, high).min(self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_rng_is_deterministic() {
+        let mut first = SeededRng::new(42);
+        let mut second = SeededRng::new(42);
+        let sequence: Vec<_> = (0..4).map(|_| first.next_u64()).collect();
+        assert_eq!(sequence, (0..4).map(|_| second.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence[0], SeededRng::new(43).next_u64());
+        assert!((0..100).map(|_| first.next_f64()).all(|value| (0.0..1.0).contains(&value)));
+    }
+
+    #[test]
+    fn test_jitter_bounds() {
+        let mut rng = SeededRng::new(7);
+        let delay = Duration::from_millis(100);
+        let decorrelated = DecorrelatedJitter::new(Duration::from_millis(250));
+
+        let mut previous = Duration::ZERO;
+        for _ in 0..200 {
+            assert!(FullJitter.apply(delay, previous, &mut rng) <= delay);
+            let equal = EqualJitter.apply(delay, previous, &mut rng);
+            assert!(equal >= delay / 2 && equal <= delay);
+
+            let next = decorrelated.apply(delay, previous, &mut rng);
+            assert!(next >= delay && next <= Duration::from_millis(250));
+            assert!(next <= previous.saturating_mul(3).max(delay));
+            previous = next;
+        }
+        assert_eq!(FullJitter.apply(Duration::ZERO, previous, &mut rng), Duration::ZERO);
+    }
+}
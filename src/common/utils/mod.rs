@@ -0,0 +1,32 @@
+/* src/common/utils/mod.rs */
+#![warn(missing_docs)]
+//! **Brief:** Utility modules shared across the crate.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Common Utilities]
+//!  - [Clocks and Deadlines]
+//!  - [File System, Network and Process Helpers]
+//!  - [Retries and Shutdown]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+pub mod clock;
+pub mod coalesce;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod deadline;
+pub mod fs;
+pub mod idempotency;
+#[cfg(feature = "integrity")]
+pub mod integrity;
+pub mod jitter;
+pub mod net;
+pub mod num;
+pub mod parse;
+pub mod pool;
+pub mod process;
+pub mod retry;
+pub mod shutdown;
+pub mod tempres;
@@ -0,0 +1,124 @@
+/* src/common/utils/num.rs */
+#![warn(missing_docs)]
+//! **Brief:** Checked arithmetic and conversions failing with Validation errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Checked Arithmetic]
+//!  - [Numeric Conversion]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Config and protocol parsers compute sizes and offsets from untrusted
+//! numbers. These helpers turn an overflow or a lossy conversion into an
+//! `AklypseError::Validation` naming the field and the offending value, in
+//! place of a panic in debug builds, a silent wrap in release builds or a
+//! bare `None`.
+
+use crate::common::error::{Result, ValidationSnafu};
+use std::fmt::Display;
+
+/// Integers with overflow-checked arithmetic
+pub trait CheckedArithmetic: Copy + Display {
+    /// `self + rhs`, or `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// `self * rhs`, or `None` on overflow
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_arithmetic {
+    ($($int:ty),*) => {
+        $(
+            impl CheckedArithmetic for $int {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_add(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$int>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_arithmetic!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// `lhs + rhs`, failing with a Validation error on `field` on overflow
+#[track_caller]
+pub fn checked_add<T: CheckedArithmetic>(field: &str, lhs: T, rhs: T) -> Result<T> {
+    match lhs.checked_add(rhs) {
+        Some(sum) => Ok(sum),
+        None => overflow(field, lhs, "+", rhs),
+    }
+}
+
+/// `lhs * rhs`, failing with a Validation error on `field` on overflow
+#[track_caller]
+pub fn checked_mul<T: CheckedArithmetic>(field: &str, lhs: T, rhs: T) -> Result<T> {
+    match lhs.checked_mul(rhs) {
+        Some(product) => Ok(product),
+        None => overflow(field, lhs, "*", rhs),
+    }
+}
+
+/// Convert `value` to `U`, failing with a Validation error on `field` if it does not fit
+#[track_caller]
+pub fn try_into_with_field<T, U>(field: &str, value: T) -> Result<U>
+where
+    T: Copy + Display + TryInto<U>,
+{
+    value.try_into().or_else(|_| {
+        ValidationSnafu {
+            field,
+            message: format!("value {} is out of range for {}", value, std::any::type_name::<U>()),
+        }
+        .fail()
+    })
+}
+
+#[track_caller]
+fn overflow<T: CheckedArithmetic>(field: &str, lhs: T, op: &str, rhs: T) -> Result<T> {
+    ValidationSnafu {
+        field,
+        message: format!("{} {} {} overflows {}", lhs, op, rhs, std::any::type_name::<T>()),
+    }
+    .fail()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::AklypseError;
+
+    fn violation<T: std::fmt::Debug>(result: Result<T>) -> (String, String) {
+        match result {
+            Err(AklypseError::Validation { field, message, .. }) => (field, message),
+            other => panic!("expected Validation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(checked_add("offset", 250u8, 5).unwrap(), 255);
+        assert_eq!(checked_mul("size", -4i32, 8).unwrap(), -32);
+        assert_eq!(
+            violation(checked_add("offset", 250u8, 6)),
+            ("offset".to_string(), "250 + 6 overflows u8".to_string())
+        );
+        assert_eq!(violation(checked_mul("size", u64::MAX, 2)).1, format!("{} * 2 overflows u64", u64::MAX));
+    }
+
+    #[test]
+    fn test_try_into_with_field() {
+        let port: u16 = try_into_with_field("port", 8080u32).unwrap();
+        assert_eq!(port, 8080);
+        assert_eq!(
+            violation(try_into_with_field::<_, u16>("port", 70_000u32)),
+            ("port".to_string(), "value 70000 is out of range for u16".to_string())
+        );
+        assert!(try_into_with_field::<_, usize>("count", -1i64).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+/* src/common/utils/parse.rs */
+#![warn(missing_docs)]
+//! **Brief:** Deserialization helpers reporting failures as located Parse errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Parsing]
+//!  - [Error Locations]
+//!  - [Input Snippets]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `from_json_str` (feature `json`), `from_yaml_str` (feature `yaml`) and
+//! `from_toml_str` (feature `toml`) deserialize a document and map failures
+//! to `AklypseError::Parse`. The error's `kind` names the format and its
+//! `context_info` shows the offending line with a caret under the column.
+//! The error also carries a context recording the position under
+//! `LINE_METADATA_KEY`, `COLUMN_METADATA_KEY` and `OFFSET_METADATA_KEY`
+//! (1-based line and column, 0-based byte offset).
+//!
+//! `duration("1h30m")` and `bytes("512MiB")` read human-friendly quantities
+//! for settings files. Their Parse errors quote the offending token, record
+//! it under `TOKEN_METADATA_KEY` and list the accepted formats; with feature
+//! `serde` the `deserialize_duration` and `deserialize_bytes` adapters apply
+//! them to fields via `#[serde(deserialize_with = "...")]`.
+
+use crate::common::error::{AklypseError, ErrorContext, ParseSnafu, Result};
+use std::fmt;
+use std::time::Duration;
+
+/// Metadata key holding the 1-based line of a parse failure
+pub const LINE_METADATA_KEY: &str = "parse.line";
+
+/// Metadata key holding the 1-based column of a parse failure
+pub const COLUMN_METADATA_KEY: &str = "parse.column";
+
+/// Metadata key holding the byte offset of a parse failure
+pub const OFFSET_METADATA_KEY: &str = "parse.offset";
+
+/// Metadata key holding the token a quantity failed to parse at
+pub const TOKEN_METADATA_KEY: &str = "parse.token";
+
+/// Formats accepted by `duration`
+pub const DURATION_FORMATS: &str = "<number><unit> parts such as 250ms, 30s, 1.5h or 1h30m (units: ns, us, ms, s, m, h, d)";
+
+/// Formats accepted by `bytes`
+pub const BYTES_FORMATS: &str =
+    "a byte count with an optional unit such as 4096, 10KB or 512MiB (units: B, KB, MB, GB, TB, KiB, MiB, GiB, TiB)";
+
+// Characters of the offending line shown on either side of the column
+const SNIPPET_RADIUS: usize = 40;
+
+/// Position of a parse failure in its input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputPosition {
+    /// 1-based line
+    pub line: usize,
+    /// 1-based column, in characters
+    pub column: usize,
+    /// 0-based byte offset
+    pub offset: usize,
+}
+
+impl InputPosition {
+    /// Position of the byte `offset` in `input`, clamped to its end
+    pub fn from_offset(input: &str, offset: usize) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            offset,
+        }
+    }
+
+    /// Position of the 1-based `line` and `column` in `input`
+    pub fn from_line_column(input: &str, line: usize, column: usize) -> Self {
+        let line_start: usize = input.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum();
+        let column_bytes: usize = input[line_start..]
+            .chars()
+            .take_while(|c| *c != '\n')
+            .take(column.saturating_sub(1))
+            .map(char::len_utf8)
+            .sum();
+        Self { line: line.max(1), column: column.max(1), offset: line_start + column_bytes }
+    }
+
+    /// The offending line of `input` with a caret under the column
+    ///
+    /// Long lines are cut to the characters around the column. A line or
+    /// column of 0, as parsers report for an unknown position, gives an empty
+    /// snippet or the line without a caret.
+    pub fn snippet(&self, input: &str) -> String {
+        let Some(line_index) = self.line.checked_sub(1) else {
+            return String::new();
+        };
+        let text = input.lines().nth(line_index).unwrap_or_default();
+        let skip = self.column.saturating_sub(SNIPPET_RADIUS + 1);
+        let shown: String = text.chars().skip(skip).take(2 * SNIPPET_RADIUS).collect();
+        let gutter = self.line.to_string();
+        if self.column == 0 {
+            return format!("{} | {}", gutter, shown);
+        }
+        format!(
+            "{} | {}{}\n{} | {}^",
+            gutter,
+            if skip > 0 { "..." } else { "" },
+            shown,
+            " ".repeat(gutter.len()),
+            " ".repeat((self.column - 1).saturating_sub(skip) + if skip > 0 { 3 } else { 0 }),
+        )
+    }
+}
+
+/// Deserialize a JSON document
+#[cfg(feature = "json")]
+#[track_caller]
+pub fn from_json_str<T: serde::de::DeserializeOwned>(input: &str) -> Result<T> {
+    serde_json::from_str(input).map_err(|error| {
+        let position = (error.line() > 0).then(|| InputPosition::from_line_column(input, error.line(), error.column()));
+        parse_error("json", input, position, error)
+    })
+}
+
+/// Deserialize a YAML document
+#[cfg(feature = "yaml")]
+#[track_caller]
+pub fn from_yaml_str<T: serde::de::DeserializeOwned>(input: &str) -> Result<T> {
+    serde_yaml::from_str(input).map_err(|error| {
+        let position = error.location().map(|location| InputPosition::from_offset(input, location.index()));
+        parse_error("yaml", input, position, error)
+    })
+}
+
+/// Deserialize a TOML document
+#[cfg(feature = "toml")]
+#[track_caller]
+pub fn from_toml_str<T: serde::de::DeserializeOwned>(input: &str) -> Result<T> {
+    toml::from_str(input).map_err(|error| {
+        let position = error.span().map(|span| InputPosition::from_offset(input, span.start));
+        parse_error("toml", input, position, error)
+    })
+}
+
+/// Parse a duration such as `30s`, `250ms` or `1h30m`
+///
+/// Parts are summed and may be fractional (`1.5h`); a bare number is
+/// rejected because its unit would be a guess.
+#[track_caller]
+pub fn duration(input: &str) -> Result<Duration> {
+    let parts = quantities(input).map_err(|token| quantity_error("duration", input, token, DURATION_FORMATS))?;
+    let mut nanos = 0f64;
+    for (token, value, unit) in parts {
+        let scale = match unit.to_ascii_lowercase().as_str() {
+            "ns" => 1e0,
+            "us" | "µs" => 1e3,
+            "ms" => 1e6,
+            "s" => 1e9,
+            "m" => 60e9,
+            "h" => 3_600e9,
+            "d" => 86_400e9,
+            _ => return Err(quantity_error("duration", input, token, DURATION_FORMATS)),
+        };
+        nanos += value * scale;
+    }
+    if nanos / 1e9 >= u64::MAX as f64 {
+        return Err(quantity_error("duration", input, (0, input.trim()), DURATION_FORMATS));
+    }
+    let nanos = nanos.round();
+    Ok(Duration::new((nanos / 1e9) as u64, (nanos % 1e9) as u32))
+}
+
+/// Parse a byte size such as `4096`, `10KB` or `512MiB`
+///
+/// Decimal units (`KB`) are powers of 1000, binary units (`KiB`) powers of
+/// 1024. Units are case-insensitive and a bare number counts bytes.
+#[track_caller]
+pub fn bytes(input: &str) -> Result<u64> {
+    let token = (0, input.trim());
+    let parts = quantities(input).map_err(|token| quantity_error("bytes", input, token, BYTES_FORMATS))?;
+    let [(token, value, unit)] = parts.as_slice() else {
+        return Err(quantity_error("bytes", input, token, BYTES_FORMATS));
+    };
+    let scale = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1u64,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return Err(quantity_error("bytes", input, *token, BYTES_FORMATS)),
+    };
+    let total = (value * scale as f64).round();
+    if total >= u64::MAX as f64 {
+        return Err(quantity_error("bytes", input, *token, BYTES_FORMATS));
+    }
+    Ok(total as u64)
+}
+
+/// Serde adapter reading a field with `duration`
+#[cfg(feature = "serde")]
+pub fn deserialize_duration<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Duration, D::Error> {
+    let text = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+    duration(&text).map_err(|_| serde::de::Error::custom(format!("invalid duration '{}', expected {}", text, DURATION_FORMATS)))
+}
+
+/// Serde adapter reading a field with `bytes`, also accepting plain integers
+#[cfg(feature = "serde")]
+pub fn deserialize_bytes<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<u64, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Size<'a> {
+        Count(u64),
+        #[serde(borrow)]
+        Text(std::borrow::Cow<'a, str>),
+    }
+
+    match <Size<'de> as serde::Deserialize>::deserialize(deserializer)? {
+        Size::Count(count) => Ok(count),
+        Size::Text(text) => {
+            bytes(&text).map_err(|_| serde::de::Error::custom(format!("invalid size '{}', expected {}", text, BYTES_FORMATS)))
+        }
+    }
+}
+
+// Byte offset and text of one token of a quantity
+type Token<'a> = (usize, &'a str);
+
+// Split `input` into (token, number, unit) parts, or the offending token
+fn quantities(input: &str) -> std::result::Result<Vec<(Token<'_>, f64, &str)>, Token<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = input.trim_start();
+    if rest.is_empty() {
+        return Err((0, input));
+    }
+    while !rest.is_empty() {
+        let start = input.len() - rest.len();
+        let number_len = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let after_number = rest[number_len..].trim_start_matches(' ');
+        let unit_len = after_number.find(|c: char| !c.is_alphabetic()).unwrap_or(after_number.len());
+        // A stray character with neither number nor unit is its own token
+        let token_len = (rest.len() - after_number.len() + unit_len).max(rest.chars().next().map_or(0, char::len_utf8));
+        let token = (start, &rest[..token_len]);
+        let value = rest[..number_len].parse::<f64>().map_err(|_| token)?;
+        parts.push((token, value, &after_number[..unit_len]));
+        rest = after_number[unit_len..].trim_start();
+    }
+    Ok(parts)
+}
+
+#[track_caller]
+fn quantity_error(kind: &str, input: &str, (offset, token): Token<'_>, formats: &str) -> AklypseError {
+    let message = format!("invalid {} token '{}' in '{}'", kind, token, input);
+    let error: AklypseError = ParseSnafu {
+        source: Box::new(QuantityError(message.clone())),
+        kind,
+        context_info: format!("{}; accepted formats: {}", message, formats),
+    }
+    .build();
+    error.add_context(
+        ErrorContext::new(format!("Invalid {} '{}'", kind, input))
+            .with_metadata(TOKEN_METADATA_KEY, token)
+            .with_metadata(OFFSET_METADATA_KEY, offset.to_string()),
+    )
+}
+
+// Source of the Parse errors raised by `duration` and `bytes`
+#[derive(Debug)]
+struct QuantityError(String);
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for QuantityError {}
+
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+#[track_caller]
+fn parse_error(
+    kind: &str,
+    input: &str,
+    position: Option<InputPosition>,
+    source: impl std::error::Error + Send + Sync + 'static,
+) -> AklypseError {
+    let Some(position) = position else {
+        return ParseSnafu { source: Box::new(source), kind, context_info: "position unknown" }.build();
+    };
+    let context_info = format!("line {}, column {}\n{}", position.line, position.column, position.snippet(input));
+    let error: AklypseError = ParseSnafu { source: Box::new(source), kind, context_info }.build();
+    error.add_context(
+        ErrorContext::new(format!("Invalid {} at line {}, column {}", kind, position.line, position.column))
+            .with_metadata(LINE_METADATA_KEY, position.line.to_string())
+            .with_metadata(COLUMN_METADATA_KEY, position.column.to_string())
+            .with_metadata(OFFSET_METADATA_KEY, position.offset.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions_and_snippet() {
+        let input = "name = \"api\"\nport = \"x\"\n";
+        let position = InputPosition::from_offset(input, 20);
+        assert_eq!(position, InputPosition { line: 2, column: 8, offset: 20 });
+        assert_eq!(InputPosition::from_line_column(input, 2, 8), position);
+        assert_eq!(position.snippet(input), "2 | port = \"x\"\n  |        ^");
+
+        let long = format!("{}X", "a".repeat(100));
+        let snippet = InputPosition::from_offset(&long, 100).snippet(&long);
+        assert!(snippet.starts_with("1 | ...aaa"));
+        let lines: Vec<_> = snippet.lines().collect();
+        assert_eq!(lines[0].find('X'), lines[1].find('^'));
+
+        // Unknown lines and columns do not underflow
+        assert_eq!(InputPosition { line: 0, column: 0, offset: 0 }.snippet(input), "");
+        assert_eq!(InputPosition { line: 0, column: 3, offset: 0 }.snippet(input), "");
+        assert_eq!(InputPosition { line: 2, column: 0, offset: 0 }.snippet(input), "2 | port = \"x\"");
+        assert_eq!(InputPosition { line: 9, column: 1, offset: 0 }.snippet(input), "9 | \n  | ^");
+    }
+
+    #[test]
+    fn test_durations_and_sizes() {
+        assert_eq!(duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(duration("1h 30m").unwrap(), Duration::from_secs(5_400));
+        assert_eq!(duration("1.5s").unwrap(), Duration::from_millis(1_500));
+        assert_eq!(duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(bytes("512MiB").unwrap(), 512 << 20);
+        assert_eq!(bytes("10 kb").unwrap(), 10_000);
+        assert_eq!(bytes("4096").unwrap(), 4096);
+
+        let token = |error: AklypseError| error.get_rich_context().unwrap().metadata.get(TOKEN_METADATA_KEY).cloned();
+        assert_eq!(token(duration("1h30x").unwrap_err()).as_deref(), Some("30x"));
+        assert_eq!(token(duration("30").unwrap_err()).as_deref(), Some("30"));
+        assert_eq!(token(bytes("12 parsecs").unwrap_err()).as_deref(), Some("12 parsecs"));
+        assert!(bytes("1KB 2KB").is_err());
+        assert!(duration("").is_err());
+        assert_eq!(token(duration("5s -1s").unwrap_err()).as_deref(), Some("-"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_serde_adapters() {
+        #[derive(Debug, serde::Deserialize)]
+        struct Rotation {
+            #[serde(deserialize_with = "deserialize_duration")]
+            interval: Duration,
+            #[serde(deserialize_with = "deserialize_bytes")]
+            max_size: u64,
+        }
+
+        let rotation: Rotation = from_json_str(r#"{ "interval": "1d", "max_size": "64MiB" }"#).unwrap();
+        assert_eq!((rotation.interval, rotation.max_size), (Duration::from_secs(86_400), 64 << 20));
+        assert!(from_json_str::<Rotation>(r#"{ "interval": "1d", "max_size": 100 }"#).is_ok());
+        assert!(from_json_str::<Rotation>(r#"{ "interval": "soon", "max_size": 100 }"#).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_error_is_located() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Server {
+            port: u16,
+        }
+
+        let error = from_json_str::<Server>("{\n  \"port\": \"x\"\n}").unwrap_err();
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(LINE_METADATA_KEY).map(String::as_str), Some("2"));
+
+        let mut current = &error;
+        while let AklypseError::WithRichContext { source, .. } = current {
+            current = source;
+        }
+        match current {
+            AklypseError::Parse { kind, context_info, .. } => {
+                assert_eq!(kind, "json");
+                assert!(context_info.contains("2 |   \"port\": \"x\""));
+            }
+            other => panic!("expected Parse, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_error_is_located() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Server {
+            port: u16,
+        }
+
+        let error = from_toml_str::<Server>("# server\nport = \"x\"\n").unwrap_err();
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(LINE_METADATA_KEY).map(String::as_str), Some("2"));
+        assert_eq!(context.metadata.get(COLUMN_METADATA_KEY).map(String::as_str), Some("8"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_error_is_located() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Server {
+            port: u16,
+        }
+
+        let error = from_yaml_str::<Server>("# server\nport: x\n").unwrap_err();
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(LINE_METADATA_KEY).map(String::as_str), Some("2"));
+    }
+}
@@ -0,0 +1,369 @@
+/* src/common/utils/pool.rs */
+#![warn(missing_docs)]
+//! **Brief:** Bounded resource pool failing with instrumented ResourceExhausted errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Resource Pooling]
+//!  - [Exhaustion Errors]
+//!  - [Pool Metrics]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ResourcePool` hands out at most `limit` resources at a time, reusing idle
+//! ones and creating new ones with its factory. Checkouts are counted by a
+//! semaphore that both threads and tasks wait on; a caller that cannot get a
+//! permit within its timeout receives `AklypseError::ResourceExhausted` with
+//! the pool name, limit and usage, plus a context recording how long it
+//! waited under `WAIT_MS_METADATA_KEY`. `metrics()` snapshots the counters
+//! and renders them in the Prometheus text format.
+//!
+//! With feature `testing`, checkouts first consult the installed `FaultPlan`
+//! under `pool.<name>`.
+
+use crate::common::error::{AklypseError, ErrorContext, ResourceExhaustedSnafu, Result};
+use std::fmt::{self, Write as _};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Metadata key holding how long the caller waited, in milliseconds
+pub const WAIT_MS_METADATA_KEY: &str = "pool.wait_ms";
+
+/// Metadata key holding the name of the exhausted pool
+pub const POOL_METADATA_KEY: &str = "pool.name";
+
+type Factory<T> = Box<dyn Fn() -> Result<T> + Send + Sync>;
+
+// Checkout counters guarded by the semaphore lock
+#[derive(Debug, Default)]
+struct Usage {
+    in_use: usize,
+    acquired: u64,
+    timeouts: u64,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+struct Shared<T> {
+    name: String,
+    limit: usize,
+    factory: Factory<T>,
+    idle: Mutex<Vec<T>>,
+    usage: Mutex<Usage>,
+    released: Condvar,
+    #[cfg(feature = "tokio")]
+    released_async: tokio::sync::Notify,
+}
+
+/// Pool of at most `limit` resources created on demand
+pub struct ResourcePool<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Clone for ResourcePool<T> {
+    fn clone(&self) -> Self {
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> fmt::Debug for ResourcePool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourcePool").field("name", &self.shared.name).field("limit", &self.shared.limit).finish()
+    }
+}
+
+impl<T: Send + 'static> ResourcePool<T> {
+    /// Pool named `name` lending at most `limit` resources made by `factory`
+    pub fn new(name: impl Into<String>, limit: usize, factory: impl Fn() -> Result<T> + Send + Sync + 'static) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                name: name.into(),
+                limit,
+                factory: Box::new(factory),
+                idle: Mutex::new(Vec::new()),
+                usage: Mutex::new(Usage::default()),
+                released: Condvar::new(),
+                #[cfg(feature = "tokio")]
+                released_async: tokio::sync::Notify::new(),
+            }),
+        }
+    }
+
+    /// Name of the pool
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// Maximum number of resources lent at once
+    pub fn limit(&self) -> usize {
+        self.shared.limit
+    }
+
+    /// Check out a resource without waiting
+    #[track_caller]
+    pub fn try_acquire(&self) -> Result<Pooled<T>> {
+        self.acquire(Duration::ZERO)
+    }
+
+    /// Check out a resource, blocking the thread for up to `timeout`
+    #[track_caller]
+    pub fn acquire(&self, timeout: Duration) -> Result<Pooled<T>> {
+        #[cfg(feature = "testing")]
+        crate::common::testing::faults::inject(&format!("pool.{}", self.shared.name))?;
+        let started = Instant::now();
+        let deadline = started.checked_add(timeout);
+        let mut usage = self.shared.usage.lock().unwrap_or_else(|p| p.into_inner());
+        while usage.in_use >= self.shared.limit {
+            let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if remaining == Some(Duration::ZERO) {
+                return Err(self.exhausted(&mut usage, started.elapsed()));
+            }
+            usage = match remaining {
+                Some(remaining) => self.shared.released.wait_timeout(usage, remaining).unwrap_or_else(|p| p.into_inner()).0,
+                None => self.shared.released.wait(usage).unwrap_or_else(|p| p.into_inner()),
+            };
+        }
+        self.checkout(usage, started.elapsed())
+    }
+
+    /// Check out a resource, waiting asynchronously for up to `timeout`
+    #[cfg(feature = "tokio")]
+    pub async fn acquire_async(&self, timeout: Duration) -> Result<Pooled<T>> {
+        #[cfg(feature = "testing")]
+        crate::common::testing::faults::inject_async(&format!("pool.{}", self.shared.name)).await?;
+        let started = Instant::now();
+        loop {
+            // Registered before checking so a release in between is not missed
+            let released = self.shared.released_async.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+            {
+                let mut usage = self.shared.usage.lock().unwrap_or_else(|p| p.into_inner());
+                if usage.in_use < self.shared.limit {
+                    return self.checkout(usage, started.elapsed());
+                }
+                let remaining = timeout.saturating_sub(started.elapsed());
+                if remaining.is_zero() {
+                    return Err(self.exhausted(&mut usage, started.elapsed()));
+                }
+            }
+            let _ = tokio::time::timeout(timeout.saturating_sub(started.elapsed()), released).await;
+        }
+    }
+
+    /// Snapshot of the pool's counters
+    pub fn metrics(&self) -> PoolMetrics {
+        let usage = self.shared.usage.lock().unwrap_or_else(|p| p.into_inner());
+        PoolMetrics {
+            name: self.shared.name.clone(),
+            limit: self.shared.limit,
+            in_use: usage.in_use,
+            idle: self.shared.idle.lock().unwrap_or_else(|p| p.into_inner()).len(),
+            acquired: usage.acquired,
+            timeouts: usage.timeouts,
+            total_wait: usage.total_wait,
+            max_wait: usage.max_wait,
+        }
+    }
+
+    // Take a permit and a resource; the permit is returned if creation fails
+    #[track_caller]
+    fn checkout(&self, mut usage: std::sync::MutexGuard<'_, Usage>, waited: Duration) -> Result<Pooled<T>> {
+        usage.in_use += 1;
+        usage.acquired += 1;
+        usage.total_wait += waited;
+        usage.max_wait = usage.max_wait.max(waited);
+        drop(usage);
+
+        let idle = self.shared.idle.lock().unwrap_or_else(|p| p.into_inner()).pop();
+        let resource = match idle {
+            Some(resource) => resource,
+            None => match (self.shared.factory)() {
+                Ok(resource) => resource,
+                Err(error) => {
+                    release(&self.shared);
+                    return Err(error);
+                }
+            },
+        };
+        Ok(Pooled { resource: Some(resource), shared: self.shared.clone() })
+    }
+
+    #[track_caller]
+    fn exhausted(&self, usage: &mut Usage, waited: Duration) -> AklypseError {
+        usage.timeouts += 1;
+        let error: AklypseError = ResourceExhaustedSnafu {
+            resource: format!("pool '{}'", self.shared.name),
+            limit: self.shared.limit.to_string(),
+            current: usage.in_use.to_string(),
+        }
+        .build();
+        error.add_context(
+            ErrorContext::new(format!("No resource free in pool '{}' after {:?}", self.shared.name, waited))
+                .with_metadata(POOL_METADATA_KEY, self.shared.name.clone())
+                .with_metadata(WAIT_MS_METADATA_KEY, waited.as_millis().to_string()),
+        )
+    }
+}
+
+fn release<T>(shared: &Shared<T>) {
+    shared.usage.lock().unwrap_or_else(|p| p.into_inner()).in_use -= 1;
+    shared.released.notify_one();
+    #[cfg(feature = "tokio")]
+    shared.released_async.notify_waiters();
+}
+
+/// Resource checked out of a `ResourcePool`, returned to it on drop
+pub struct Pooled<T> {
+    resource: Option<T>,
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Pooled<T> {
+    /// Drop the resource instead of returning it, e.g. after it broke
+    pub fn discard(mut self) {
+        self.resource = None;
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.resource.as_ref().expect("pooled resource is present until drop")
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.resource.as_mut().expect("pooled resource is present until drop")
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pooled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pooled").field(&self.resource).finish()
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        if let Some(resource) = self.resource.take() {
+            self.shared.idle.lock().unwrap_or_else(|p| p.into_inner()).push(resource);
+        }
+        release(&self.shared);
+    }
+}
+
+/// Counters of a `ResourcePool`
+#[derive(Debug, Clone, Default)]
+pub struct PoolMetrics {
+    /// Name of the pool
+    pub name: String,
+    /// Maximum number of resources lent at once
+    pub limit: usize,
+    /// Resources currently checked out
+    pub in_use: usize,
+    /// Resources waiting in the pool for reuse
+    pub idle: usize,
+    /// Successful checkouts
+    pub acquired: u64,
+    /// Checkouts that gave up waiting
+    pub timeouts: u64,
+    /// Time successful checkouts spent waiting, summed
+    pub total_wait: Duration,
+    /// Longest wait of a successful checkout
+    pub max_wait: Duration,
+}
+
+impl PoolMetrics {
+    /// Render the metrics in the Prometheus text exposition format
+    ///
+    /// Series are prefixed `aklypse_pool_` and labelled with the pool name.
+    pub fn to_prometheus(&self) -> String {
+        let label = self.name.replace('\\', "\\\\").replace('"', "\\\"");
+        let series: [(&str, &str, &str, String); 6] = [
+            ("limit", "gauge", "Maximum resources lent at once", self.limit.to_string()),
+            ("in_use", "gauge", "Resources currently checked out", self.in_use.to_string()),
+            ("idle", "gauge", "Resources idle in the pool", self.idle.to_string()),
+            ("acquired_total", "counter", "Successful checkouts", self.acquired.to_string()),
+            ("timeouts_total", "counter", "Checkouts that timed out", self.timeouts.to_string()),
+            ("wait_seconds_total", "counter", "Time spent waiting for checkouts", self.total_wait.as_secs_f64().to_string()),
+        ];
+        let mut out = String::new();
+        for (metric, kind, help, value) in series {
+            let _ = writeln!(out, "# HELP aklypse_pool_{} {}", metric, help);
+            let _ = writeln!(out, "# TYPE aklypse_pool_{} {}", metric, kind);
+            let _ = writeln!(out, "aklypse_pool_{}{{pool=\"{}\"}} {}", metric, label, value);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_reuses_and_bounds_resources() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let pool = {
+            let created = created.clone();
+            ResourcePool::new("db", 2, move || Ok(created.fetch_add(1, Ordering::SeqCst)))
+        };
+
+        let first = pool.acquire(Duration::from_secs(1)).unwrap();
+        let second = pool.try_acquire().unwrap();
+        assert_eq!((*first, *second), (0, 1));
+
+        let error = pool.acquire(Duration::from_millis(20)).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::ResourceExhaustion);
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(POOL_METADATA_KEY).map(String::as_str), Some("db"));
+        assert!(context.metadata.get(WAIT_MS_METADATA_KEY).unwrap().parse::<u64>().unwrap() >= 20);
+
+        drop(first);
+        second.discard();
+        assert_eq!(*pool.try_acquire().unwrap(), 0);
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+
+        let metrics = pool.metrics();
+        assert_eq!((metrics.in_use, metrics.idle, metrics.acquired, metrics.timeouts), (0, 1, 3, 1));
+        assert!(metrics.to_prometheus().contains("aklypse_pool_timeouts_total{pool=\"db\"} 1\n"));
+    }
+
+    #[test]
+    fn test_blocked_acquire_wakes_on_release() {
+        let pool = ResourcePool::new("workers", 1, || Ok(()));
+        let held = pool.acquire(Duration::ZERO).unwrap();
+        let waiter = {
+            let pool = pool.clone();
+            std::thread::spawn(move || pool.acquire(Duration::from_secs(5)).map(|_| ()))
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        drop(held);
+        assert!(waiter.join().unwrap().is_ok());
+        assert!(pool.metrics().max_wait >= Duration::from_millis(10));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_acquire_async() {
+        let pool = ResourcePool::new("http", 1, || Ok(String::from("conn")));
+        let held = pool.acquire_async(Duration::ZERO).await.unwrap();
+        assert!(pool.acquire_async(Duration::from_millis(10)).await.is_err());
+
+        let waiter = {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.acquire_async(Duration::from_secs(5)).await.map(|conn| conn.len()) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(held);
+        assert_eq!(waiter.await.unwrap().unwrap(), 4);
+    }
+}
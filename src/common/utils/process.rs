@@ -0,0 +1,456 @@
+/* src/common/utils/process.rs */
+#![warn(missing_docs)]
+//! **Brief:** External process runner mapping failures to ExternalService and Timeout errors.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Process Execution]
+//!  - [Output Capture]
+//!  - [Process Timeouts]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `run` (and `run_async` with feature `tokio`) executes a `ProcessCommand`,
+//! capturing stdout and stderr. A command that cannot be spawned or exits
+//! unsuccessfully fails with `AklypseError::ExternalService` named after the
+//! program; one that outlives its timeout is killed and fails with
+//! `AklypseError::Timeout`. Commands with a timeout run in their own process
+//! group on Unix, and with feature `libc` the whole group is killed, so
+//! processes they started in the background go too. Output still arriving
+//! once the timeout is over (from descendants holding the pipes open) is
+//! abandoned, keeping what was read by then. Either way the error carries a context recording
+//! the command line under `COMMAND_METADATA_KEY`, the exit code under
+//! `EXIT_CODE_METADATA_KEY` when there is one and the last lines of stderr
+//! under `STDERR_TAIL_METADATA_KEY`.
+//!
+//! With feature `testing`, both consult the installed `FaultPlan` under
+//! `process.<program>` before spawning anything.
+
+use crate::common::error::{AklypseError, ErrorContext, ExternalServiceSnafu, Result, TimeoutSnafu};
+use std::ffi::{OsStr, OsString};
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the command line of a failed process
+pub const COMMAND_METADATA_KEY: &str = "process.command";
+
+/// Metadata key holding the exit code of a failed process
+pub const EXIT_CODE_METADATA_KEY: &str = "process.exit_code";
+
+/// Metadata key holding the last lines a failed process wrote to stderr
+pub const STDERR_TAIL_METADATA_KEY: &str = "process.stderr_tail";
+
+// Lines and characters of stderr kept in error contexts
+const STDERR_TAIL_LINES: usize = 20;
+const STDERR_TAIL_CHARS: usize = 4096;
+
+// Interval at which a blocking run checks whether its child exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// Time left to the pipe readers of a killed process to pick up its last output
+const READER_GRACE: Duration = Duration::from_millis(100);
+
+// Output read so far from a pipe, by a reader that may outlive the run
+type Captured = Arc<Mutex<Vec<u8>>>;
+
+/// Program, arguments and limits of a process to run
+#[derive(Debug, Clone)]
+pub struct ProcessCommand {
+    program: OsString,
+    args: Vec<OsString>,
+    current_dir: Option<PathBuf>,
+    env: Vec<(OsString, OsString)>,
+    timeout: Option<Duration>,
+}
+
+impl ProcessCommand {
+    /// Command running `program` with no arguments and no timeout
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        Self { program: program.as_ref().to_owned(), args: Vec::new(), current_dir: None, env: Vec::new(), timeout: None }
+    }
+
+    /// Append one argument
+    pub fn with_arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_owned());
+        self
+    }
+
+    /// Append several arguments
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl AsRef<OsStr>>) -> Self {
+        self.args.extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    /// Run in `dir` instead of the current directory
+    pub fn with_current_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// Set an environment variable for the process
+    pub fn with_env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.env.push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+        self
+    }
+
+    /// Kill the process and fail if it runs longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Program the command runs
+    pub fn program(&self) -> &OsStr {
+        &self.program
+    }
+
+    /// Command line as a shell would show it, quoting arguments where needed
+    pub fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|part| {
+                let part = part.to_string_lossy();
+                if !part.is_empty() && !part.contains(|c: char| c.is_whitespace() || "'\"\\$`".contains(c)) {
+                    part.into_owned()
+                } else {
+                    format!("'{}'", part.replace('\'', "'\\''"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn to_std(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args).envs(self.env.iter().map(|(key, value)| (key, value)));
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+        command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+        // Its own group lets a timeout kill whatever the process started
+        #[cfg(unix)]
+        if self.timeout.is_some() {
+            std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        }
+        command
+    }
+}
+
+/// Captured result of a process that exited successfully
+#[derive(Debug, Clone)]
+pub struct ProcessOutput {
+    /// Exit status of the process
+    pub status: ExitStatus,
+    /// Everything written to stdout
+    pub stdout: Vec<u8>,
+    /// Everything written to stderr
+    pub stderr: Vec<u8>,
+    /// Time from spawn to exit
+    pub elapsed: Duration,
+}
+
+impl ProcessOutput {
+    /// Stdout decoded as UTF-8, replacing invalid sequences
+    pub fn stdout_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    /// Stderr decoded as UTF-8, replacing invalid sequences
+    pub fn stderr_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+}
+
+// Name under which a `FaultPlan` targets `command`: `process.<program file name>`
+#[cfg(feature = "testing")]
+fn fault_operation(command: &ProcessCommand) -> String {
+    let program = std::path::Path::new(&command.program);
+    format!("process.{}", program.file_name().unwrap_or(program.as_os_str()).to_string_lossy())
+}
+
+/// Run `command` to completion, blocking the thread
+#[track_caller]
+pub fn run(command: &ProcessCommand) -> Result<ProcessOutput> {
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject(&fault_operation(command))?;
+    let started = Instant::now();
+    let mut child = command.to_std().spawn().map_err(|error| spawn_error(command, error))?;
+    let (stdout, stdout_reader) = drain(child.stdout.take());
+    let (stderr, stderr_reader) = drain(child.stderr.take());
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if command.timeout.is_some_and(|timeout| started.elapsed() >= timeout) => {
+                kill_group(command, child.id());
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            Ok(None) => std::thread::sleep(POLL_INTERVAL),
+            Err(error) => {
+                kill_group(command, child.id());
+                let _ = child.kill();
+                return Err(spawn_error(command, error));
+            }
+        }
+    };
+    match readers_deadline(command, started) {
+        Some(deadline) => {
+            while !(stdout_reader.is_finished() && stderr_reader.is_finished()) && Instant::now() < deadline {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+        None => {
+            let _ = stdout_reader.join();
+            let _ = stderr_reader.join();
+        }
+    }
+    finish(command, status, take(&stdout), take(&stderr), started.elapsed())
+}
+
+/// Run `command` to completion on the tokio runtime
+///
+/// The process is killed if the returned future is dropped.
+#[cfg(feature = "tokio")]
+pub async fn run_async(command: &ProcessCommand) -> Result<ProcessOutput> {
+    use tokio::io::AsyncReadExt;
+
+    #[cfg(feature = "testing")]
+    crate::common::testing::faults::inject_async(&fault_operation(command)).await?;
+    let started = Instant::now();
+    let mut child = tokio::process::Command::from(command.to_std())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|error| spawn_error(command, error))?;
+
+    async fn read_into(pipe: Option<impl tokio::io::AsyncRead + Unpin>, captured: Captured) {
+        let Some(mut pipe) = pipe else {
+            return;
+        };
+        let mut chunk = [0u8; 8192];
+        while let Ok(read @ 1..) = pipe.read(&mut chunk).await {
+            captured.lock().unwrap_or_else(PoisonError::into_inner).extend_from_slice(&chunk[..read]);
+        }
+    }
+    let (stdout, stderr) = (Captured::default(), Captured::default());
+    let stdout_reader = tokio::spawn(read_into(child.stdout.take(), stdout.clone()));
+    let stderr_reader = tokio::spawn(read_into(child.stderr.take(), stderr.clone()));
+
+    let status = match command.timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, child.wait()).await {
+            Ok(status) => Some(status),
+            Err(_) => {
+                if let Some(id) = child.id() {
+                    kill_group(command, id);
+                }
+                let _ = child.kill().await;
+                None
+            }
+        },
+        None => Some(child.wait().await),
+    };
+    let status = status.transpose().map_err(|error| spawn_error(command, error))?;
+    let readers = async {
+        let _ = stdout_reader.await;
+        let _ = stderr_reader.await;
+    };
+    match readers_deadline(command, started) {
+        Some(deadline) => {
+            let _ = tokio::time::timeout_at(deadline.into(), readers).await;
+        }
+        None => readers.await,
+    }
+    finish(command, status, take(&stdout), take(&stderr), started.elapsed())
+}
+
+// Read `pipe` on a thread, chunk by chunk so that an abandoned read keeps its output
+fn drain(pipe: Option<impl Read + Send + 'static>) -> (Captured, std::thread::JoinHandle<()>) {
+    let captured = Captured::default();
+    let sink = captured.clone();
+    let reader = std::thread::spawn(move || {
+        let Some(mut pipe) = pipe else {
+            return;
+        };
+        let mut chunk = [0u8; 8192];
+        loop {
+            match pipe.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => sink.lock().unwrap_or_else(PoisonError::into_inner).extend_from_slice(&chunk[..read]),
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+    });
+    (captured, reader)
+}
+
+fn take(captured: &Captured) -> Vec<u8> {
+    std::mem::take(&mut *captured.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+// Until when to wait for the pipe readers: the end of the timeout, or a grace
+// period once it is over; without a timeout, until the pipes close
+fn readers_deadline(command: &ProcessCommand, started: Instant) -> Option<Instant> {
+    command.timeout.map(|timeout| (started + timeout).max(Instant::now() + READER_GRACE))
+}
+
+// Kill the process group a timed process leads, taking its descendants along
+#[cfg(all(unix, feature = "libc"))]
+fn kill_group(command: &ProcessCommand, id: u32) {
+    if command.timeout.is_some() {
+        if let Ok(id) = libc::pid_t::try_from(id) {
+            // SAFETY: killpg takes no pointers; the group was created at spawn
+            unsafe {
+                libc::killpg(id, libc::SIGKILL);
+            }
+        }
+    }
+}
+
+// Without libc only the process itself is killed; descendants are abandoned with the pipes
+#[cfg(not(all(unix, feature = "libc")))]
+fn kill_group(_command: &ProcessCommand, _id: u32) {}
+
+// Map the outcome of a run; `status` is `None` when the process was killed for its timeout
+#[track_caller]
+fn finish(
+    command: &ProcessCommand,
+    status: Option<ExitStatus>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    elapsed: Duration,
+) -> Result<ProcessOutput> {
+    let command_line = command.command_line();
+    let tail = stderr_tail(&stderr);
+    let Some(status) = status else {
+        let error: AklypseError = TimeoutSnafu {
+            operation: format!("process `{}`", command_line),
+            duration: command.timeout.unwrap_or(elapsed),
+        }
+        .build();
+        return Err(error.add_context(failure_context(
+            format!("Process `{}` killed after {:?}", command_line, elapsed),
+            command_line,
+            None,
+            tail,
+        )));
+    };
+    if status.success() {
+        return Ok(ProcessOutput { status, stdout, stderr, elapsed });
+    }
+
+    let outcome = match status.code() {
+        Some(code) => format!("exited with code {}", code),
+        None => "was terminated by a signal".to_string(),
+    };
+    let error: AklypseError = ExternalServiceSnafu {
+        service_name: command.program.to_string_lossy().into_owned(),
+        message: format!("`{}` {}", command_line, outcome),
+        source: None,
+    }
+    .build();
+    Err(error.add_context(failure_context(format!("Process {}", outcome), command_line, status.code(), tail)))
+}
+
+#[track_caller]
+fn spawn_error(command: &ProcessCommand, error: std::io::Error) -> AklypseError {
+    let command_line = command.command_line();
+    let error: AklypseError = ExternalServiceSnafu {
+        service_name: command.program.to_string_lossy().into_owned(),
+        message: format!("failed to run `{}`", command_line),
+        source: Some(Box::new(error) as Box<dyn std::error::Error + Send + Sync>),
+    }
+    .build();
+    error.add_context(failure_context("Process could not be started".to_string(), command_line, None, String::new()))
+}
+
+fn failure_context(message: String, command_line: String, code: Option<i32>, tail: String) -> ErrorContext {
+    let mut context = ErrorContext::new(message).with_metadata(COMMAND_METADATA_KEY, command_line);
+    if let Some(code) = code {
+        context = context.with_metadata(EXIT_CODE_METADATA_KEY, code.to_string());
+    }
+    if !tail.is_empty() {
+        context = context.with_metadata(STDERR_TAIL_METADATA_KEY, tail);
+    }
+    context
+}
+
+fn stderr_tail(stderr: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n");
+    let skip = tail.chars().count().saturating_sub(STDERR_TAIL_CHARS);
+    tail.chars().skip(skip).collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+
+    fn sh(script: &str) -> ProcessCommand {
+        ProcessCommand::new("sh").with_args(["-c", script])
+    }
+
+    #[test]
+    fn test_captures_output_and_maps_exit_codes() {
+        let output = run(&sh("echo out; echo err >&2").with_env("UNUSED", "1")).unwrap();
+        assert_eq!((output.stdout_lossy().as_str(), output.stderr_lossy().as_str()), ("out\n", "err\n"));
+
+        let error = run(&sh("echo first >&2; echo 'config missing' >&2; exit 3")).unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::ExternalService);
+        let metadata = &error.get_rich_context().unwrap().metadata;
+        assert_eq!(metadata.get(COMMAND_METADATA_KEY).unwrap(), "sh -c 'echo first >&2; echo '\\''config missing'\\'' >&2; exit 3'");
+        assert_eq!(metadata.get(EXIT_CODE_METADATA_KEY).map(String::as_str), Some("3"));
+        assert_eq!(metadata.get(STDERR_TAIL_METADATA_KEY).map(String::as_str), Some("first\nconfig missing"));
+
+        let missing = run(&ProcessCommand::new("aklypse-no-such-program")).unwrap_err();
+        assert_eq!(missing.category(), ErrorCategory::ExternalService);
+    }
+
+    #[test]
+    fn test_timeout_kills_process() {
+        let started = Instant::now();
+        let error = run(&sh("echo partial >&2; exec sleep 5").with_timeout(Duration::from_millis(100))).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        let metadata = &error.get_rich_context().unwrap().metadata;
+        assert_eq!(metadata.get(STDERR_TAIL_METADATA_KEY).map(String::as_str), Some("partial"));
+    }
+
+    #[test]
+    fn test_timeout_with_background_grandchild() {
+        // The grandchild holds stdout open long after its parent is killed
+        let started = Instant::now();
+        let error = run(&sh("sleep 5 & echo started >&2; sleep 5").with_timeout(Duration::from_millis(100))).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        let metadata = &error.get_rich_context().unwrap().metadata;
+        assert_eq!(metadata.get(STDERR_TAIL_METADATA_KEY).map(String::as_str), Some("started"));
+
+        // An exiting process does not wait out its background work either
+        let started = Instant::now();
+        let output = run(&sh("sleep 5 & echo done").with_timeout(Duration::from_millis(300))).unwrap();
+        assert_eq!(output.stdout_lossy(), "done\n");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_run_async() {
+        assert_eq!(run_async(&sh("printf hi")).await.unwrap().stdout, b"hi");
+        let error = run_async(&sh("exec sleep 5").with_timeout(Duration::from_millis(50))).await.unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        let started = Instant::now();
+        let error = run_async(&sh("sleep 5 & sleep 5").with_timeout(Duration::from_millis(50))).await.unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Timeout);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(run_async(&sh("exit 1")).await.is_err());
+    }
+}
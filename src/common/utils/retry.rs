@@ -0,0 +1,417 @@
+/* src/common/utils/retry.rs */
+#![warn(missing_docs)]
+//! **Brief:** Standalone retry helpers with fixed, exponential and Fibonacci backoff.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Retry]
+//!  - [Backoff Policies]
+//!  - [Attempt Context]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `retry` and `retry_async` re-run an operation until it succeeds, the
+//! attempt budget or the elapsed-time cap is spent, or the error is not
+//! worth retrying. They need no circuit breaker and suit simple call sites.
+//!
+//! A `Jitter` set with `with_jitter` randomizes every delay computed by the
+//! backoff; `with_jitter_seed` makes that randomness reproducible.
+//!
+//! `retry_idempotent` additionally claims an `IdempotencyKey` around the
+//! whole loop, so a request retried by its caller after it succeeded is not
+//! executed again.
+//!
+//! The error returned after the last attempt carries a context recording the
+//! `attempt` number under `ATTEMPT_METADATA_KEY` and the total time spent.
+
+use super::idempotency::{self, IdempotencyGuard, IdempotencyKey};
+use super::jitter::{Jitter, SeededRng};
+use crate::common::error::{AklypseError, ErrorContext, Result};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Metadata key holding the number of the last attempt made
+pub const ATTEMPT_METADATA_KEY: &str = "attempt";
+
+/// Metadata key holding the milliseconds spent across all attempts
+pub const ELAPSED_METADATA_KEY: &str = "retry.elapsed_ms";
+
+/// Predicate deciding whether an error is worth retrying
+pub type RetryPredicate = Arc<dyn Fn(&AklypseError) -> bool + Send + Sync>;
+
+/// Delay between attempts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// The same delay before every retry
+    Fixed(Duration),
+    /// `initial * multiplier^(retry - 1)`, capped at `max`
+    Exponential {
+        /// Delay before the first retry
+        initial: Duration,
+        /// Growth factor between retries
+        multiplier: f64,
+        /// Upper bound of any delay
+        max: Duration,
+    },
+    /// `initial` times the Fibonacci sequence (1, 1, 2, 3, 5, ...), capped at `max`
+    Fibonacci {
+        /// Delay before the first two retries
+        initial: Duration,
+        /// Upper bound of any delay
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Exponential backoff doubling from `initial` up to `max`
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Backoff::Exponential { initial, multiplier: 2.0, max }
+    }
+
+    /// Fibonacci backoff from `initial` up to `max`
+    pub fn fibonacci(initial: Duration, max: Duration) -> Self {
+        Backoff::Fibonacci { initial, max }
+    }
+
+    /// Delay before retry number `retry` (1 for the delay after the first attempt)
+    pub fn delay(&self, retry: u32) -> Duration {
+        let retry = retry.max(1);
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, multiplier, max } => {
+                let factor = multiplier.max(1.0).powi(retry.saturating_sub(1).min(i32::MAX as u32) as i32);
+                scale(initial, factor).min(max)
+            }
+            Backoff::Fibonacci { initial, max } => {
+                let (mut previous, mut current) = (0_u64, 1_u64);
+                for _ in 1..retry {
+                    (previous, current) = (current, previous.saturating_add(current));
+                }
+                scale(initial, current as f64).min(max)
+            }
+        }
+    }
+}
+
+// `duration * factor`, saturating instead of panicking on overflow
+fn scale(duration: Duration, factor: f64) -> Duration {
+    Duration::try_from_secs_f64(duration.as_secs_f64() * factor).unwrap_or(Duration::MAX)
+}
+
+/// Limits and backoff of `retry` and `retry_async`
+#[derive(Clone)]
+pub struct RetryOptions {
+    /// Delay between attempts
+    pub backoff: Backoff,
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    /// Give up instead of sleeping past this much time since the first attempt
+    pub max_elapsed: Option<Duration>,
+    /// Whether an error is worth retrying; every error is when `None`
+    pub retry_if: Option<RetryPredicate>,
+    /// Randomization applied to every backoff delay
+    pub jitter: Option<Arc<dyn Jitter>>,
+    /// Seed of the jitter's random source; drawn from entropy when `None`
+    pub jitter_seed: Option<u64>,
+}
+
+impl RetryOptions {
+    /// Three attempts with the given backoff
+    pub fn new(backoff: Backoff) -> Self {
+        Self { backoff, max_attempts: 3, max_elapsed: None, retry_if: None, jitter: None, jitter_seed: None }
+    }
+
+    /// Set the total number of attempts (at least 1)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Stop retrying once the next delay would end past `max_elapsed`
+    pub fn with_max_elapsed(mut self, max_elapsed: Duration) -> Self {
+        self.max_elapsed = Some(max_elapsed);
+        self
+    }
+
+    /// Only retry errors matching `predicate`
+    pub fn with_retry_if(mut self, predicate: impl Fn(&AklypseError) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_if = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Randomize every delay with `jitter`
+    pub fn with_jitter(mut self, jitter: impl Jitter + 'static) -> Self {
+        self.jitter = Some(Arc::new(jitter));
+        self
+    }
+
+    /// Seed the jitter's random source, making delays reproducible
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    // Delay before the next attempt, or None to give up with `error`
+    fn next_delay(&self, attempt: u32, started: Instant, error: &AklypseError, state: &mut JitterState) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.retry_if.as_ref().is_none_or(|retry_if| retry_if(error)) {
+            return None;
+        }
+        let mut delay = self.backoff.delay(attempt);
+        if let Some(jitter) = &self.jitter {
+            delay = jitter.apply(delay, state.previous, &mut state.rng);
+            state.previous = delay;
+        }
+        match self.max_elapsed {
+            Some(max_elapsed) if started.elapsed() + delay > max_elapsed => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+impl Default for RetryOptions {
+    fn default() -> Self {
+        Self::new(Backoff::exponential(Duration::from_millis(100), Duration::from_secs(10)))
+    }
+}
+
+impl fmt::Debug for RetryOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryOptions")
+            .field("backoff", &self.backoff)
+            .field("max_attempts", &self.max_attempts)
+            .field("max_elapsed", &self.max_elapsed)
+            .field("retry_if", &self.retry_if.is_some())
+            .field("jitter", &self.jitter)
+            .field("jitter_seed", &self.jitter_seed)
+            .finish()
+    }
+}
+
+// Random source and last delay of one retry loop
+struct JitterState {
+    rng: SeededRng,
+    previous: Duration,
+}
+
+impl JitterState {
+    fn new(options: &RetryOptions) -> Self {
+        Self { rng: SeededRng::from_seed(options.jitter_seed), previous: Duration::ZERO }
+    }
+}
+
+/// Run `operation` until it succeeds or `options` give up, sleeping the thread between attempts
+///
+/// The operation receives the attempt number, starting at 1.
+#[track_caller]
+pub fn retry<T, F>(options: &RetryOptions, mut operation: F) -> Result<T>
+where
+    F: FnMut(u32) -> Result<T>,
+{
+    let started = Instant::now();
+    let mut jitter = JitterState::new(options);
+    let mut attempt = 1;
+    loop {
+        match operation(attempt) {
+            Ok(value) => return Ok(value),
+            Err(error) => match options.next_delay(attempt, started, &error, &mut jitter) {
+                Some(delay) => std::thread::sleep(delay),
+                None => return Err(exhausted(error, attempt, started)),
+            },
+        }
+        attempt += 1;
+    }
+}
+
+/// Run `operation` until it succeeds or `options` give up, sleeping the task between attempts
+///
+/// The operation receives the attempt number, starting at 1.
+#[cfg(feature = "tokio")]
+pub async fn retry_async<T, F, Fut>(options: &RetryOptions, mut operation: F) -> Result<T>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let started = Instant::now();
+    let mut jitter = JitterState::new(options);
+    let mut attempt = 1;
+    loop {
+        match operation(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(error) => match options.next_delay(attempt, started, &error, &mut jitter) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(exhausted(error, attempt, started)),
+            },
+        }
+        attempt += 1;
+    }
+}
+
+/// `retry`, but only when no other execution claimed `key` or already completed it
+///
+/// Duplicates fail with `AklypseError::StateConflict` without running
+/// `operation`. The key is completed on success and released on failure.
+#[track_caller]
+pub fn retry_idempotent<T, F>(
+    options: &RetryOptions,
+    guard: &dyn IdempotencyGuard,
+    key: &IdempotencyKey,
+    operation: F,
+) -> Result<T>
+where
+    F: FnMut(u32) -> Result<T>,
+{
+    idempotency::claim(guard, key)?;
+    idempotency::settle(guard, key, retry(options, operation))
+}
+
+/// `retry_async`, but only when no other execution claimed `key` or already completed it
+#[cfg(feature = "tokio")]
+pub async fn retry_idempotent_async<T, F, Fut>(
+    options: &RetryOptions,
+    guard: &dyn IdempotencyGuard,
+    key: &IdempotencyKey,
+    operation: F,
+) -> Result<T>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    idempotency::claim(guard, key)?;
+    idempotency::settle(guard, key, retry_async(options, operation).await)
+}
+
+#[track_caller]
+fn exhausted(error: AklypseError, attempt: u32, started: Instant) -> AklypseError {
+    let message = if attempt == 1 {
+        "Operation failed on its first attempt".to_string()
+    } else {
+        format!("Operation failed after {} attempts", attempt)
+    };
+    error.add_context(
+        ErrorContext::new(message)
+            .with_metadata(ATTEMPT_METADATA_KEY, attempt.to_string())
+            .with_metadata(ELAPSED_METADATA_KEY, started.elapsed().as_millis().to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::{ErrorCategory, InternalSnafu, ValidationSnafu};
+
+    fn transient() -> AklypseError {
+        InternalSnafu { message: "transient".to_string(), source: None }.build()
+    }
+
+    #[test]
+    fn test_backoff_delays() {
+        let ms = Duration::from_millis;
+        let exponential = Backoff::exponential(ms(10), ms(50));
+        let fibonacci = Backoff::fibonacci(ms(10), ms(1000));
+
+        assert_eq!((1..=4).map(|retry| exponential.delay(retry)).collect::<Vec<_>>(), vec![ms(10), ms(20), ms(40), ms(50)]);
+        assert_eq!((1..=5).map(|retry| fibonacci.delay(retry)).collect::<Vec<_>>(), vec![ms(10), ms(10), ms(20), ms(30), ms(50)]);
+        assert_eq!(Backoff::Fixed(ms(7)).delay(9), ms(7));
+        assert_eq!(exponential.delay(u32::MAX), ms(50));
+    }
+
+    #[test]
+    fn test_retry_succeeds_and_records_attempts() {
+        let options = RetryOptions::new(Backoff::Fixed(Duration::ZERO)).with_max_attempts(4);
+
+        let mut seen = Vec::new();
+        let value = retry(&options, |attempt| {
+            seen.push(attempt);
+            if attempt < 3 { Err(transient()) } else { Ok("done") }
+        });
+        assert_eq!(value.unwrap(), "done");
+        assert_eq!(seen, vec![1, 2, 3]);
+
+        let error = retry(&options, |_| Err::<(), _>(transient())).unwrap_err();
+        let context = error.get_rich_context().unwrap();
+        assert_eq!(context.metadata.get(ATTEMPT_METADATA_KEY).map(String::as_str), Some("4"));
+        assert_eq!(error.category(), ErrorCategory::Internal);
+    }
+
+    #[test]
+    fn test_retry_stops_on_predicate_and_elapsed_cap() {
+        let options = RetryOptions::new(Backoff::Fixed(Duration::ZERO))
+            .with_max_attempts(10)
+            .with_retry_if(|error| error.category() != ErrorCategory::Validation);
+        let mut calls = 0;
+        let error = retry(&options, |_| {
+            calls += 1;
+            Err::<(), _>(ValidationSnafu { field: "id".to_string(), message: "empty".to_string() }.build())
+        })
+        .unwrap_err();
+        assert_eq!(calls, 1);
+        assert_eq!(error.get_rich_context().unwrap().metadata.get(ATTEMPT_METADATA_KEY).map(String::as_str), Some("1"));
+
+        let capped = RetryOptions::new(Backoff::Fixed(Duration::from_secs(60)))
+            .with_max_attempts(10)
+            .with_max_elapsed(Duration::from_secs(1));
+        let mut calls = 0;
+        assert!(retry(&capped, |_| {
+            calls += 1;
+            Err::<(), _>(transient())
+        })
+        .is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_jittered_delays_are_seeded() {
+        use crate::common::utils::jitter::FullJitter;
+
+        let options = RetryOptions::new(Backoff::Fixed(Duration::from_millis(10)))
+            .with_max_attempts(5)
+            .with_jitter(FullJitter)
+            .with_jitter_seed(9);
+        let delays = |options: &RetryOptions| {
+            let mut state = JitterState::new(options);
+            (1..5)
+                .map(|attempt| options.next_delay(attempt, Instant::now(), &transient(), &mut state).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let first = delays(&options);
+        assert_eq!(first, delays(&options));
+        assert!(first.iter().all(|delay| *delay <= Duration::from_millis(10)));
+        assert!(first.iter().any(|delay| *delay != Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn test_retry_idempotent_runs_once_per_key() {
+        use crate::common::utils::idempotency::InMemoryIdempotencyStore;
+
+        let options = RetryOptions::new(Backoff::Fixed(Duration::ZERO));
+        let store = InMemoryIdempotencyStore::default();
+        let key = IdempotencyKey::derive("emails", &["welcome", "user-9"]);
+
+        let mut sent = 0;
+        for _ in 0..2 {
+            let _ = retry_idempotent(&options, &store, &key, |attempt| {
+                if attempt == 1 {
+                    return Err(transient());
+                }
+                sent += 1;
+                Ok(())
+            });
+        }
+        assert_eq!(sent, 1);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_retry_async() {
+        let options = RetryOptions::new(Backoff::Fixed(Duration::from_millis(1)));
+        let value = retry_async(&options, |attempt| async move {
+            if attempt == 1 { Err(transient()) } else { Ok(attempt) }
+        })
+        .await;
+        assert_eq!(value.unwrap(), 2);
+    }
+}
@@ -0,0 +1,297 @@
+/* src/common/utils/shutdown.rs */
+#![warn(missing_docs)]
+//! **Brief:** Orderly shutdown running subsystem hooks with timeouts and aggregated failures.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Graceful Shutdown]
+//!  - [Shutdown Hooks]
+//!  - [Signal Handling]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `ShutdownCoordinator` collects the shutdown hooks of an application's
+//! subsystems and runs them once, newest first, when `shutdown` is called or
+//! (feature `tokio`) when the process receives ctrl-c or SIGTERM. Before the
+//! hooks run it snapshots the registered circuit breakers so their final
+//! state survives in the report; after them it closes the reporter's sinks
+//! so every error raised while stopping is still written out.
+//!
+//! Each hook runs on its own thread and is abandoned, failing with
+//! `AklypseError::Timeout`, if it overruns its timeout. Hook failures, panics,
+//! timeouts and sink failures are all kept in the `ShutdownReport`, whose
+//! `into_result` folds them into one `AklypseError::MultipleErrors`.
+
+use crate::common::error::circuitbreaker::{CircuitBreaker, CircuitMetrics};
+use crate::common::error::{
+    AklypseError, ErrorContext, ErrorReporter, InternalSnafu, MultipleErrorsSnafu, Result, TimeoutSnafu,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Timeout of hooks registered without one
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Hook = Box<dyn FnOnce() -> Result<()> + Send>;
+
+struct RegisteredHook {
+    name: String,
+    timeout: Duration,
+    hook: Hook,
+}
+
+/// Final state of a circuit breaker captured at shutdown
+#[derive(Debug, Clone)]
+pub struct BreakerSnapshot {
+    /// Name of the circuit breaker
+    pub name: String,
+    /// Its metrics, including the state it was left in
+    pub metrics: CircuitMetrics,
+}
+
+/// Outcome of a shutdown
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// Circuit breaker states captured before the hooks ran
+    pub breakers: Vec<BreakerSnapshot>,
+    /// Hooks that finished successfully, in the order they ran
+    pub completed: Vec<String>,
+    /// Every failure, hooks first and sinks last
+    pub failures: Vec<AklypseError>,
+    /// Time the whole shutdown took
+    pub elapsed: Duration,
+}
+
+impl ShutdownReport {
+    /// Whether nothing failed
+    pub fn is_clean(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// `Ok` when clean, otherwise all failures as one `MultipleErrors`
+    pub fn into_result(self) -> Result<()> {
+        if self.failures.is_empty() {
+            Ok(())
+        } else {
+            Err(MultipleErrorsSnafu { errors: self.failures }.build())
+        }
+    }
+}
+
+/// Runs registered shutdown hooks once, in reverse registration order
+pub struct ShutdownCoordinator {
+    hooks: Mutex<Vec<RegisteredHook>>,
+    breakers: Mutex<Vec<Arc<CircuitBreaker>>>,
+    reporter: Option<Arc<ErrorReporter>>,
+    default_timeout: Duration,
+    started: AtomicBool,
+}
+
+impl ShutdownCoordinator {
+    /// Coordinator with no hooks, breakers or reporter
+    pub fn new() -> Self {
+        Self {
+            hooks: Mutex::new(Vec::new()),
+            breakers: Mutex::new(Vec::new()),
+            reporter: None,
+            default_timeout: DEFAULT_HOOK_TIMEOUT,
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Close `reporter`'s sinks once the hooks have run
+    pub fn with_reporter(mut self, reporter: Arc<ErrorReporter>) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    /// Timeout of hooks registered with `register`
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// Register a hook run with the default timeout
+    pub fn register(&self, name: impl Into<String>, hook: impl FnOnce() -> Result<()> + Send + 'static) {
+        self.register_with_timeout(name, self.default_timeout, hook);
+    }
+
+    /// Register a hook abandoned after `timeout`
+    pub fn register_with_timeout(
+        &self,
+        name: impl Into<String>,
+        timeout: Duration,
+        hook: impl FnOnce() -> Result<()> + Send + 'static,
+    ) {
+        let hook = RegisteredHook { name: name.into(), timeout, hook: Box::new(hook) };
+        self.hooks.lock().unwrap_or_else(|p| p.into_inner()).push(hook);
+    }
+
+    /// Snapshot `breaker` at shutdown
+    pub fn watch_circuit_breaker(&self, breaker: Arc<CircuitBreaker>) {
+        self.breakers.lock().unwrap_or_else(|p| p.into_inner()).push(breaker);
+    }
+
+    /// Whether shutdown has begun
+    pub fn is_shutting_down(&self) -> bool {
+        self.started.load(Ordering::SeqCst)
+    }
+
+    /// Run the shutdown sequence, blocking until every hook finished or timed out
+    ///
+    /// Only the first call does anything; later calls return an empty report.
+    pub fn shutdown(&self) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        if self.started.swap(true, Ordering::SeqCst) {
+            return report;
+        }
+        let started = Instant::now();
+        info!("Shutdown started");
+
+        report.breakers = self
+            .breakers
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .iter()
+            .map(|breaker| BreakerSnapshot { name: breaker.name().to_string(), metrics: breaker.metrics() })
+            .collect();
+        for snapshot in &report.breakers {
+            info!("Circuit breaker '{}' is {:?} at shutdown", snapshot.name, snapshot.metrics.state);
+        }
+
+        let hooks = std::mem::take(&mut *self.hooks.lock().unwrap_or_else(|p| p.into_inner()));
+        for hook in hooks.into_iter().rev() {
+            let name = hook.name.clone();
+            match run_hook(hook) {
+                Ok(()) => report.completed.push(name),
+                Err(error) => {
+                    warn!("Shutdown hook '{}' failed: {:?}", name, error);
+                    report.failures.push(error);
+                }
+            }
+        }
+
+        if let Some(reporter) = &self.reporter {
+            if let Err(error) = reporter.close_sinks() {
+                report.failures.push(error.add_context_msg("Closing report sinks at shutdown"));
+            }
+        }
+
+        report.elapsed = started.elapsed();
+        info!("Shutdown finished in {:?} with {} failure(s)", report.elapsed, report.failures.len());
+        report
+    }
+
+    /// Wait for ctrl-c or, on Unix, SIGTERM, then run `shutdown`
+    #[cfg(feature = "tokio")]
+    pub async fn shutdown_on_signal(self: Arc<Self>) -> ShutdownReport {
+        wait_for_signal().await;
+        tokio::task::spawn_blocking(move || self.shutdown()).await.unwrap_or_default()
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolve when the process receives ctrl-c or, on Unix, SIGTERM
+#[cfg(feature = "tokio")]
+pub async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+// Run one hook on its own thread, mapping a panic or overrun to an error
+#[track_caller]
+fn run_hook(hook: RegisteredHook) -> Result<()> {
+    let RegisteredHook { name, timeout, hook } = hook;
+    let (sender, receiver) = mpsc::channel();
+    let spawned = std::thread::Builder::new().name(format!("shutdown-{}", name)).spawn(move || {
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(hook));
+        let _ = sender.send(outcome);
+    });
+    if let Err(error) = spawned {
+        return InternalSnafu {
+            message: format!("Shutdown hook '{}' could not be started", name),
+            source: Some(Box::new(error) as Box<dyn std::error::Error + Send + Sync>),
+        }
+        .fail();
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(result)) => result.map_err(|error| error.add_context(ErrorContext::new(format!("Shutdown hook '{}' failed", name)))),
+        Ok(Err(_)) => InternalSnafu { message: format!("Shutdown hook '{}' panicked", name), source: None }.fail(),
+        Err(_) => TimeoutSnafu { operation: format!("shutdown hook '{}'", name), duration: timeout }.fail(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::circuitbreaker::CircuitBreakerConfig;
+    use crate::common::error::{ErrorCategory, StateConflictSnafu};
+
+    #[test]
+    fn test_hooks_run_newest_first_and_failures_aggregate() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let coordinator = ShutdownCoordinator::new().with_default_timeout(Duration::from_secs(5));
+        for name in ["database", "cache", "http"] {
+            let order = order.clone();
+            coordinator.register(name, move || {
+                order.lock().unwrap().push(name);
+                Ok(())
+            });
+        }
+        coordinator.register("queue", || StateConflictSnafu { message: "messages still pending".to_string() }.fail());
+        coordinator.register("metrics", || panic!("exporter crashed"));
+        coordinator.register_with_timeout("slow", Duration::from_millis(20), || {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(())
+        });
+        coordinator.watch_circuit_breaker(CircuitBreaker::new("payments", CircuitBreakerConfig::default()));
+
+        let report = coordinator.shutdown();
+        assert!(coordinator.is_shutting_down());
+        assert_eq!(*order.lock().unwrap(), vec!["http", "cache", "database"]);
+        assert_eq!(report.breakers[0].name, "payments");
+        let categories: Vec<_> = report.failures.iter().map(AklypseError::category).collect();
+        assert_eq!(categories, vec![ErrorCategory::Timeout, ErrorCategory::Internal, ErrorCategory::StateConflict]);
+        assert!(matches!(report.into_result(), Err(AklypseError::MultipleErrors { errors, .. }) if errors.len() == 3));
+
+        // Hooks are consumed by the first shutdown
+        assert!(coordinator.shutdown().is_clean());
+    }
+
+    #[test]
+    fn test_clean_shutdown_closes_reporter() {
+        let coordinator = ShutdownCoordinator::new().with_reporter(Arc::new(ErrorReporter::new()));
+        coordinator.register("noop", || Ok(()));
+        let report = coordinator.shutdown();
+        assert_eq!(report.completed, vec!["noop".to_string()]);
+        assert!(report.into_result().is_ok());
+    }
+}
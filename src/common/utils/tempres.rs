@@ -0,0 +1,258 @@
+/* src/common/utils/tempres.rs */
+#![warn(missing_docs)]
+//! **Brief:** Scoped temporary files and directories with reported cleanup failures.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Utilities]
+//!  - [Temporary Resources]
+//!  - [Scoped Cleanup]
+//!  - [Error Aggregation]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! `TempDirGuard` and `TempFileGuard` create a uniquely named scratch
+//! directory or file and remove it when dropped. Drop cannot report errors,
+//! so it only logs what it failed to remove; call `close()` instead to get
+//! every failure back as one `AklypseError::MultipleErrors` of Io errors,
+//! one per path that could not be removed.
+
+use crate::common::error::{AklypseError, MultipleErrorsSnafu, Result};
+use crate::common::utils::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+// Distinguishes names created by this process within the same instant
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+// Attempts before giving up on finding an unused name
+const CREATE_ATTEMPTS: usize = 16;
+
+fn unique_path(parent: &Path, prefix: &str) -> PathBuf {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.subsec_nanos());
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    parent.join(format!("{}{}-{}-{:08x}", prefix, std::process::id(), id, nanos))
+}
+
+// Try fresh names until `create` succeeds or fails for another reason than a taken name
+#[track_caller]
+fn create_unique(parent: &Path, prefix: &str, create: impl Fn(&Path) -> std::io::Result<()>, operation: &str) -> Result<PathBuf> {
+    let mut attempt = 0;
+    loop {
+        let path = unique_path(parent, prefix);
+        match create(&path) {
+            Ok(()) => return Ok(path),
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists && attempt + 1 < CREATE_ATTEMPTS => attempt += 1,
+            Err(error) => return Err(fs::io_error(error, &path, operation)),
+        }
+    }
+}
+
+// Remove `path` and everything under it, recording each failure and carrying on
+fn remove_tree(path: &Path, failures: &mut Vec<AklypseError>) {
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries {
+                match entry {
+                    Ok(entry) => {
+                        let child = entry.path();
+                        match entry.file_type() {
+                            Ok(kind) if kind.is_dir() => remove_tree(&child, failures),
+                            _ => failures.extend(fs::remove_file(&child).err()),
+                        }
+                    }
+                    Err(error) => failures.push(fs::io_error(error, path, "read directory entry")),
+                }
+            }
+            failures.extend(fs::remove_dir(path).err());
+        }
+        Err(error) => failures.push(error),
+    }
+}
+
+fn aggregate(failures: Vec<AklypseError>) -> Result<()> {
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(MultipleErrorsSnafu { errors: failures }.build())
+    }
+}
+
+fn log_failures(kind: &str, path: &Path, result: Result<()>) {
+    if let Err(error) = result {
+        warn!("Failed to clean up temporary {} '{}': {:?}", kind, path.display(), error);
+    }
+}
+
+/// Scratch directory removed with its contents on drop or `close`
+#[derive(Debug)]
+pub struct TempDirGuard {
+    path: Option<PathBuf>,
+}
+
+impl TempDirGuard {
+    /// Create a directory named after `prefix` in the system temp directory
+    #[track_caller]
+    pub fn new(prefix: &str) -> Result<Self> {
+        Self::new_in(std::env::temp_dir(), prefix)
+    }
+
+    /// Create a directory named after `prefix` inside `parent`
+    #[track_caller]
+    pub fn new_in(parent: impl AsRef<Path>, prefix: &str) -> Result<Self> {
+        let path = create_unique(parent.as_ref(), prefix, |path| std::fs::create_dir(path), "create directory")?;
+        Ok(Self { path: Some(path) })
+    }
+
+    /// Path of the directory
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().expect("temporary directory is present until closed")
+    }
+
+    /// Stop managing the directory and return its path, leaving it in place
+    pub fn keep(mut self) -> PathBuf {
+        self.path.take().expect("temporary directory is present until closed")
+    }
+
+    /// Remove the directory, reporting every path that could not be removed
+    pub fn close(mut self) -> Result<()> {
+        self.cleanup()
+    }
+
+    fn cleanup(&mut self) -> Result<()> {
+        let Some(path) = self.path.take() else {
+            return Ok(());
+        };
+        let mut failures = Vec::new();
+        remove_tree(&path, &mut failures);
+        aggregate(failures)
+    }
+}
+
+impl Drop for TempDirGuard {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.clone() {
+            log_failures("directory", &path, self.cleanup());
+        }
+    }
+}
+
+/// Scratch file removed on drop or `close`
+#[derive(Debug)]
+pub struct TempFileGuard {
+    path: Option<PathBuf>,
+}
+
+impl TempFileGuard {
+    /// Create an empty file named after `prefix` in the system temp directory
+    #[track_caller]
+    pub fn new(prefix: &str) -> Result<Self> {
+        Self::new_in(std::env::temp_dir(), prefix)
+    }
+
+    /// Create an empty file named after `prefix` inside `parent`
+    #[track_caller]
+    pub fn new_in(parent: impl AsRef<Path>, prefix: &str) -> Result<Self> {
+        let create = |path: &Path| std::fs::OpenOptions::new().write(true).create_new(true).open(path).map(drop);
+        let path = create_unique(parent.as_ref(), prefix, create, "create file")?;
+        Ok(Self { path: Some(path) })
+    }
+
+    /// Path of the file
+    pub fn path(&self) -> &Path {
+        self.path.as_deref().expect("temporary file is present until closed")
+    }
+
+    /// Stop managing the file and return its path, leaving it in place
+    pub fn keep(mut self) -> PathBuf {
+        self.path.take().expect("temporary file is present until closed")
+    }
+
+    /// Remove the file, reporting a failure to do so
+    ///
+    /// A file that is already gone counts as removed.
+    pub fn close(mut self) -> Result<()> {
+        self.cleanup()
+    }
+
+    fn cleanup(&mut self) -> Result<()> {
+        let Some(path) = self.path.take() else {
+            return Ok(());
+        };
+        let failures = match std::fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                vec![fs::io_error(error, &path, "remove file")]
+            }
+            _ => Vec::new(),
+        };
+        aggregate(failures)
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.clone() {
+            log_failures("file", &path, self.cleanup());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guards_clean_up() {
+        let dir = TempDirGuard::new("aklypse-tempres-").unwrap();
+        let dir_path = dir.path().to_path_buf();
+        std::fs::create_dir_all(dir_path.join("a/b")).unwrap();
+        std::fs::write(dir_path.join("a/b/c.txt"), "x").unwrap();
+
+        let file = TempFileGuard::new_in(&dir_path, "scratch-").unwrap();
+        let file_path = file.path().to_path_buf();
+        assert!(file_path.is_file());
+        drop(file);
+        assert!(!file_path.exists());
+
+        dir.close().unwrap();
+        assert!(!dir_path.exists());
+
+        let kept = TempFileGuard::new("aklypse-kept-").unwrap().keep();
+        assert!(kept.exists());
+        std::fs::remove_file(kept).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_close_collects_failures() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDirGuard::new("aklypse-tempres-").unwrap();
+        let locked = dir.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        std::fs::write(locked.join("one"), "1").unwrap();
+        std::fs::write(locked.join("two"), "2").unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o500)).unwrap();
+        // Privileged users can remove entries regardless of permissions
+        if std::fs::remove_file(locked.join("one")).is_ok() {
+            dir.close().unwrap();
+            return;
+        }
+
+        let root = dir.path().to_path_buf();
+        let result = dir.close();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o700)).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+        match result {
+            Err(AklypseError::MultipleErrors { errors, .. }) => {
+                // Both files, their directory and the root itself
+                assert_eq!(errors.len(), 4);
+                assert!(errors.iter().all(|error| matches!(error, AklypseError::Io { path: Some(_), .. })));
+            }
+            other => panic!("expected MultipleErrors, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,530 @@
+/* src/common/validation/mod.rs */
+#![warn(missing_docs)]
+//! **Brief:** Declarative validation aggregating every violation into one error.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Validation]
+//!  - [Validate Trait]
+//!  - [Field Paths]
+//!  - [Error Aggregation]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Types implement `Validate` by checking their fields against `rules` into a
+//! `ValidationErrors`, usually through the `validate_struct!` macro. All
+//! violations are collected rather than stopping at the first one, and
+//! `Validate::validate` turns them into one `AklypseError::MultipleErrors` of
+//! `AklypseError::Validation` errors, each naming the dotted path of the
+//! offending field (`user.address.zip`, `items[2].sku`).
+//!
+//! Beyond single-field rules, `ValidationErrors` validates every element of
+//! a collection under an indexed path (`each`, `check_each`), compares two
+//! fields (`compare`), and `rules::when` applies a rule only under a
+//! condition. `ValidationErrors::tree` groups the violations by path for
+//! APIs that return them as a nested document.
+//!
+//! `validate_struct!` stands in for a `#[derive(Validate)]`: a derive needs a
+//! proc-macro crate of its own, which this crate does not ship, while the
+//! declarative macro gives the same per-field rule lists without one.
+
+pub mod rules;
+
+use crate::common::error::{AklypseError, MultipleErrorsSnafu, Result, ValidationSnafu};
+use rules::Rule;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// One violated rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Dotted path of the field, e.g. `address.zip`
+    pub path: String,
+    /// What is wrong with the value
+    pub message: String,
+}
+
+/// Relation required between two fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `a < b`
+    Less,
+    /// `a <= b`
+    LessOrEqual,
+    /// `a > b`
+    Greater,
+    /// `a >= b`
+    GreaterOrEqual,
+    /// `a == b`
+    Equal,
+    /// `a != b`
+    NotEqual,
+}
+
+impl Comparison {
+    /// Whether `a` and `b` are in this relation
+    pub fn holds<T: PartialOrd + ?Sized>(self, a: &T, b: &T) -> bool {
+        match self {
+            Comparison::Less => a < b,
+            Comparison::LessOrEqual => a <= b,
+            Comparison::Greater => a > b,
+            Comparison::GreaterOrEqual => a >= b,
+            Comparison::Equal => a == b,
+            Comparison::NotEqual => a != b,
+        }
+    }
+
+    /// Wording of the relation in violation messages, e.g. "less than"
+    pub fn describe(self) -> &'static str {
+        match self {
+            Comparison::Less => "less than",
+            Comparison::LessOrEqual => "at most",
+            Comparison::Greater => "greater than",
+            Comparison::GreaterOrEqual => "at least",
+            Comparison::Equal => "equal to",
+            Comparison::NotEqual => "different from",
+        }
+    }
+}
+
+/// Violations collected while validating a value
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    prefix: Vec<String>,
+    violations: Vec<Violation>,
+}
+
+impl ValidationErrors {
+    /// Create an empty collection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Full path of `field` under the fields currently being descended into
+    pub fn path(&self, field: &str) -> String {
+        let mut segments: Vec<&str> = self.prefix.iter().map(String::as_str).collect();
+        if !field.is_empty() {
+            segments.push(field);
+        }
+        segments.join(".")
+    }
+
+    /// Record a violation of `field`
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        let path = self.path(field);
+        self.violations.push(Violation { path, message: message.into() });
+    }
+
+    /// Check `value` of `field` against `rule`
+    pub fn check<T: ?Sized>(&mut self, field: &str, value: &T, rule: &impl Rule<T>) {
+        if let Err(message) = rule.check(value) {
+            self.add(field, message);
+        }
+    }
+
+    /// Validate `value` with its fields' paths under `field`
+    pub fn nested<V: Validate + ?Sized>(&mut self, field: &str, value: &V) {
+        self.prefix.push(field.to_string());
+        value.validate_fields(self);
+        self.prefix.pop();
+    }
+
+    /// Validate every element of the collection `field`, under paths like `items[2].sku`
+    pub fn each<'a, V: Validate + 'a>(&mut self, field: &str, items: impl IntoIterator<Item = &'a V>) {
+        for (index, item) in items.into_iter().enumerate() {
+            self.nested(&format!("{}[{}]", field, index), item);
+        }
+    }
+
+    /// Check every element of the collection `field` against `rule`, under paths like `tags[2]`
+    pub fn check_each<'a, T: 'a>(&mut self, field: &str, items: impl IntoIterator<Item = &'a T>, rule: &impl Rule<T>) {
+        for (index, item) in items.into_iter().enumerate() {
+            self.check(&format!("{}[{}]", field, index), item, rule);
+        }
+    }
+
+    /// Require `field` to be in `comparison` with `other_field`, recording the violation on `field`
+    pub fn compare<T: PartialOrd + ?Sized>(
+        &mut self,
+        field: &str,
+        value: &T,
+        comparison: Comparison,
+        other_field: &str,
+        other_value: &T,
+    ) {
+        if !comparison.holds(value, other_value) {
+            self.add(field, format!("must be {} {}", comparison.describe(), self.path(other_field)));
+        }
+    }
+
+    /// Record `message` on `field` unless `condition` holds
+    pub fn ensure(&mut self, field: &str, condition: bool, message: impl Into<String>) {
+        if !condition {
+            self.add(field, message);
+        }
+    }
+
+    /// Violations grouped by path segment
+    pub fn tree(&self) -> ValidationTree {
+        let mut tree = ValidationTree::default();
+        for violation in &self.violations {
+            let node = violation
+                .path
+                .split('.')
+                .filter(|segment| !segment.is_empty())
+                .fold(&mut tree, |node, segment| node.fields.entry(segment.to_string()).or_default());
+            node.messages.push(violation.message.clone());
+        }
+        tree
+    }
+
+    /// Violations recorded so far
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Number of violations
+    pub fn len(&self) -> usize {
+        self.violations.len()
+    }
+
+    /// Whether nothing was violated
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// `MultipleErrors` of one `Validation` error per violation, or `None` when valid
+    #[track_caller]
+    pub fn into_error(self) -> Option<AklypseError> {
+        if self.violations.is_empty() {
+            return None;
+        }
+        let errors: Vec<AklypseError> = self
+            .violations
+            .into_iter()
+            .map(|violation| ValidationSnafu { field: violation.path, message: violation.message }.build())
+            .collect();
+        Some(MultipleErrorsSnafu { errors }.build())
+    }
+
+    /// `Ok` when valid, else the error of `into_error`
+    #[track_caller]
+    pub fn into_result(self) -> Result<()> {
+        match self.into_error() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Violations of a value and of its fields, nested by path
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationTree {
+    /// Violations of the value itself
+    pub messages: Vec<String>,
+    /// Violations of each field or element, keyed by path segment (`zip`, `items[2]`)
+    pub fields: BTreeMap<String, ValidationTree>,
+}
+
+impl ValidationTree {
+    /// Whether neither the value nor any field has a violation
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty() && self.fields.values().all(ValidationTree::is_empty)
+    }
+
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        for (name, child) in &self.fields {
+            write!(f, "{:indent$}{}", "", name, indent = depth * 2)?;
+            match child.messages.as_slice() {
+                [] => writeln!(f)?,
+                messages => writeln!(f, ": {}", messages.join("; "))?,
+            }
+            child.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ValidationTree {
+    /// One line per field, children indented under their parent
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.messages.is_empty() {
+            writeln!(f, "{}", self.messages.join("; "))?;
+        }
+        self.write_indented(f, 0)
+    }
+}
+
+/// Values that can check themselves
+pub trait Validate {
+    /// Record every violation into `errors`
+    fn validate_fields(&self, errors: &mut ValidationErrors);
+
+    /// Check the value, reporting all violations in one error
+    #[track_caller]
+    fn validate(&self) -> Result<()> {
+        let mut errors = ValidationErrors::new();
+        self.validate_fields(&mut errors);
+        errors.into_result()
+    }
+}
+
+impl<V: Validate + ?Sized> Validate for Box<V> {
+    fn validate_fields(&self, errors: &mut ValidationErrors) {
+        (**self).validate_fields(errors)
+    }
+}
+
+/// Implement `Validate` for a struct from per-field rules
+///
+/// Each entry names a field and what it must satisfy:
+///
+/// - `[rules]`: the `rules` the field must pass
+/// - `nested`: the field implements `Validate` itself
+/// - `each [rules]` / `each nested`: the same for every element of a collection
+/// - `(< other)`: a comparison with another field (`<`, `<=`, `>`, `>=`, `==`, `!=`)
+/// - `if (condition) spec`: any of the above, only when `condition` holds
+///
+/// Conditions refer to the struct through the name given after `as`:
+///
+/// ```text
+/// validate_struct!(Booking as booking {
+///     guest_name => [required(), length(1, 64)],
+///     check_in => (< check_out),
+///     rooms => each nested,
+///     tags => each [length(1, 16)],
+///     company => if (booking.invoice) [required()],
+///     address => nested,
+/// });
+/// ```
+///
+/// The functions of `common::validation::rules` are in scope for the rules.
+#[macro_export]
+macro_rules! validate_struct {
+    ($type:ty as $this:ident { $($body:tt)* }) => {
+        impl $crate::common::validation::Validate for $type {
+            fn validate_fields(&self, errors: &mut $crate::common::validation::ValidationErrors) {
+                #[allow(unused_imports)]
+                use $crate::common::validation::rules::*;
+                #[allow(unused_variables)]
+                let $this = self;
+                $crate::validate_struct!(@fields $this, errors; $($body)*);
+            }
+        }
+    };
+    ($type:ty { $($body:tt)* }) => {
+        $crate::validate_struct!($type as value { $($body)* });
+    };
+    (@fields $this:ident, $errors:ident;) => {};
+    (@fields $this:ident, $errors:ident; $field:ident => if ($condition:expr) each $spec:tt $(, $($rest:tt)*)?) => {
+        if $condition {
+            $crate::validate_struct!(@fields $this, $errors; $field => each $spec);
+        }
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => if ($condition:expr) $spec:tt $(, $($rest:tt)*)?) => {
+        if $condition {
+            $crate::validate_struct!(@fields $this, $errors; $field => $spec);
+        }
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => each nested $(, $($rest:tt)*)?) => {
+        $errors.each(stringify!($field), &$this.$field);
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => each [$($rule:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $( $errors.check_each(stringify!($field), &$this.$field, &$rule); )*
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => nested $(, $($rest:tt)*)?) => {
+        $errors.nested(stringify!($field), &$this.$field);
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => [$($rule:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $( $errors.check(stringify!($field), &$this.$field, &$rule); )*
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@fields $this:ident, $errors:ident; $field:ident => ($op:tt $other:ident) $(, $($rest:tt)*)?) => {
+        $errors.compare(
+            stringify!($field),
+            &$this.$field,
+            $crate::validate_struct!(@op $op),
+            stringify!($other),
+            &$this.$other,
+        );
+        $crate::validate_struct!(@fields $this, $errors; $($($rest)*)?);
+    };
+    (@op <) => { $crate::common::validation::Comparison::Less };
+    (@op <=) => { $crate::common::validation::Comparison::LessOrEqual };
+    (@op >) => { $crate::common::validation::Comparison::Greater };
+    (@op >=) => { $crate::common::validation::Comparison::GreaterOrEqual };
+    (@op ==) => { $crate::common::validation::Comparison::Equal };
+    (@op !=) => { $crate::common::validation::Comparison::NotEqual };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::error::ErrorCategory;
+
+    struct Address {
+        zip: String,
+    }
+
+    struct User {
+        name: String,
+        age: u32,
+        nickname: Option<String>,
+        address: Address,
+    }
+
+    struct Signup {
+        user: User,
+    }
+
+    crate::validate_struct!(Address {
+        zip => [required(), length(5, 5)],
+    });
+    crate::validate_struct!(User {
+        name => [required(), length(1, 8)],
+        age => [range(18, 130)],
+        nickname => [optional(length(2, 32))],
+        address => nested,
+    });
+    crate::validate_struct!(Signup { user => nested });
+
+    fn signup(name: &str, age: u32, zip: &str) -> Signup {
+        Signup {
+            user: User {
+                name: name.to_string(),
+                age,
+                nickname: None,
+                address: Address { zip: zip.to_string() },
+            },
+        }
+    }
+
+    #[test]
+    fn test_valid_struct_passes() {
+        assert!(signup("ada", 36, "94107").validate().is_ok());
+    }
+
+    struct Room {
+        sku: String,
+        guests: u32,
+    }
+
+    struct Booking {
+        check_in: u32,
+        check_out: u32,
+        invoice: bool,
+        company: Option<String>,
+        rooms: Vec<Room>,
+        tags: Vec<String>,
+    }
+
+    crate::validate_struct!(Room {
+        sku => [required()],
+        guests => [range(1, 4)],
+    });
+    crate::validate_struct!(Booking as booking {
+        check_in => (< check_out),
+        company => if (booking.invoice) [required()],
+        rooms => each nested,
+        tags => each [length(1, 8)],
+    });
+
+    #[test]
+    fn test_cross_field_collection_and_conditional_rules() {
+        let room = |sku: &str, guests| Room { sku: sku.to_string(), guests };
+        let mut booking = Booking {
+            check_in: 20,
+            check_out: 18,
+            invoice: true,
+            company: None,
+            rooms: vec![room("DBL", 2), room("", 9)],
+            tags: vec!["late".to_string(), String::new()],
+        };
+
+        let mut errors = ValidationErrors::new();
+        booking.validate_fields(&mut errors);
+        let paths: Vec<_> = errors.violations().iter().map(|violation| violation.path.as_str()).collect();
+        assert_eq!(paths, vec!["check_in", "company", "rooms[1].sku", "rooms[1].guests", "tags[1]"]);
+        assert_eq!(errors.violations()[0].message, "must be less than check_out");
+
+        let tree = errors.tree();
+        assert!(!tree.fields.contains_key("rooms"));
+        assert_eq!(tree.fields["rooms[1]"].fields["guests"].messages, vec!["must be between 1 and 4 (was 9)"]);
+        assert!(tree.to_string().contains("rooms[1]\n  guests: must be between 1 and 4 (was 9)\n"));
+
+        booking.invoice = false;
+        booking.check_out = 22;
+        booking.rooms.pop();
+        booking.tags.pop();
+        assert!(booking.validate().is_ok());
+    }
+
+    struct Parcel {
+        weight: u32,
+    }
+
+    struct Shipment {
+        express: bool,
+        pickup: u32,
+        delivery: u32,
+        note: String,
+        insurance: Parcel,
+        parcels: Vec<Parcel>,
+        labels: Vec<String>,
+    }
+
+    crate::validate_struct!(Parcel { weight => [range(1, 30)] });
+    // Every form of spec under a condition
+    crate::validate_struct!(Shipment as shipment {
+        note => if (shipment.express) [required()],
+        insurance => if (shipment.express) nested,
+        parcels => if (shipment.express) each nested,
+        labels => if (shipment.express) each [length(1, 8)],
+        pickup => if (shipment.express) (< delivery),
+    });
+
+    #[test]
+    fn test_conditions_accept_every_spec() {
+        let mut shipment = Shipment {
+            express: true,
+            pickup: 9,
+            delivery: 7,
+            note: String::new(),
+            insurance: Parcel { weight: 0 },
+            parcels: vec![Parcel { weight: 50 }],
+            labels: vec!["fragile-glass".to_string()],
+        };
+
+        let mut errors = ValidationErrors::new();
+        shipment.validate_fields(&mut errors);
+        let paths: Vec<_> = errors.violations().iter().map(|violation| violation.path.as_str()).collect();
+        assert_eq!(paths, vec!["note", "insurance.weight", "parcels[0].weight", "labels[0]", "pickup"]);
+
+        shipment.express = false;
+        assert!(shipment.validate().is_ok());
+    }
+
+    #[test]
+    fn test_violations_aggregate_with_paths() {
+        let error = signup("", 12, "941").validate().unwrap_err();
+        assert_eq!(error.category(), ErrorCategory::Multiple);
+
+        let AklypseError::MultipleErrors { errors, .. } = &error else {
+            panic!("expected MultipleErrors, got {:?}", error);
+        };
+        let fields: Vec<_> = errors
+            .iter()
+            .map(|error| match error {
+                AklypseError::Validation { field, .. } => field.as_str(),
+                other => panic!("expected Validation, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(fields, vec!["user.name", "user.name", "user.age", "user.address.zip"]);
+    }
+}
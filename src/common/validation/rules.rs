@@ -0,0 +1,304 @@
+/* src/common/validation/rules.rs */
+#![warn(missing_docs)]
+//! **Brief:** Reusable field rules for the validation framework.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Validation]
+//!  - [Field Rules]
+//!  - [Rule Combinators]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+//! Every rule checks one value and explains a violation in a short message
+//! that reads after the field name ("name: is required"). Rules apply to the
+//! field type itself; wrap them in `optional` for `Option` fields so that a
+//! missing value is accepted and a present one is checked, and in `when` to
+//! apply them only under a condition.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Check of a single value
+pub trait Rule<T: ?Sized> {
+    /// `Err` with a message describing the violation, `Ok` when `value` passes
+    fn check(&self, value: &T) -> Result<(), String>;
+}
+
+/// Values that count as missing when empty
+pub trait Presence {
+    /// Whether the value is present
+    fn is_present(&self) -> bool;
+}
+
+impl<T> Presence for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl Presence for String {
+    fn is_present(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl Presence for str {
+    fn is_present(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl<T> Presence for Vec<T> {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+/// Values with a length: characters for strings, elements for collections
+pub trait HasLength {
+    /// Length of the value
+    fn length(&self) -> usize;
+}
+
+impl HasLength for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl HasLength for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> HasLength for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, S> HasLength for HashMap<K, V, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, S> HasLength for HashSet<T, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Rule rejecting missing or blank values
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Required;
+
+/// Value must be present (`Some`, non-blank string, non-empty vector)
+pub fn required() -> Required {
+    Required
+}
+
+impl<T: Presence + ?Sized> Rule<T> for Required {
+    fn check(&self, value: &T) -> Result<(), String> {
+        if value.is_present() {
+            Ok(())
+        } else {
+            Err("is required".to_string())
+        }
+    }
+}
+
+/// Rule bounding the length of a value
+#[derive(Debug, Clone, Copy)]
+pub struct Length {
+    min: usize,
+    max: usize,
+}
+
+/// Length must be within `min..=max`
+pub fn length(min: usize, max: usize) -> Length {
+    Length { min, max }
+}
+
+impl<T: HasLength + ?Sized> Rule<T> for Length {
+    fn check(&self, value: &T) -> Result<(), String> {
+        let length = value.length();
+        if (self.min..=self.max).contains(&length) {
+            Ok(())
+        } else {
+            Err(format!("length must be between {} and {} (was {})", self.min, self.max, length))
+        }
+    }
+}
+
+/// Rule bounding a value
+#[derive(Debug, Clone, Copy)]
+pub struct Range<T> {
+    min: T,
+    max: T,
+}
+
+/// Value must be within `min..=max`
+pub fn range<T>(min: T, max: T) -> Range<T> {
+    Range { min, max }
+}
+
+impl<T: PartialOrd + fmt::Display> Rule<T> for Range<T> {
+    fn check(&self, value: &T) -> Result<(), String> {
+        if *value >= self.min && *value <= self.max {
+            Ok(())
+        } else {
+            Err(format!("must be between {} and {} (was {})", self.min, self.max, value))
+        }
+    }
+}
+
+/// Rule requiring a string to match a regular expression
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct Pattern(regex::Regex);
+
+/// String must match the regular expression `pattern`
+///
+/// Panics when `pattern` does not compile, like a malformed literal would;
+/// use `try_pattern` for expressions read from configuration or users.
+/// The expression is compiled on every call; keep a `Pattern` around (e.g.
+/// in a `OnceLock`) for hot paths.
+#[cfg(feature = "regex")]
+#[track_caller]
+pub fn pattern(pattern: &str) -> Pattern {
+    match try_pattern(pattern) {
+        Ok(rule) => rule,
+        Err(error) => panic!("invalid validation pattern {:?}: {}", pattern, error),
+    }
+}
+
+/// String must match the regular expression `pattern`, which may not compile
+///
+/// # Errors
+///
+/// Returns a `Parse` error of kind `regex` when `pattern` is not a valid expression.
+#[cfg(feature = "regex")]
+#[track_caller]
+pub fn try_pattern(pattern: &str) -> crate::common::error::Result<Pattern> {
+    regex::Regex::new(pattern).map(Pattern).map_err(|error| {
+        crate::common::error::ParseSnafu { source: Box::new(error), kind: "regex", context_info: pattern }.build()
+    })
+}
+
+#[cfg(feature = "regex")]
+impl Pattern {
+    /// Rule from an already compiled expression
+    pub fn from_regex(regex: regex::Regex) -> Self {
+        Self(regex)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<T: AsRef<str> + ?Sized> Rule<T> for Pattern {
+    fn check(&self, value: &T) -> Result<(), String> {
+        if self.0.is_match(value.as_ref()) {
+            Ok(())
+        } else {
+            Err(format!("must match the pattern {}", self.0.as_str()))
+        }
+    }
+}
+
+/// Rule defined by a function
+#[derive(Clone, Copy)]
+pub struct Custom<F>(F);
+
+/// Rule checking values with `check`, which returns the violation message on failure
+pub fn custom<F>(check: F) -> Custom<F> {
+    Custom(check)
+}
+
+impl<T: ?Sized, F: Fn(&T) -> Result<(), String>> Rule<T> for Custom<F> {
+    fn check(&self, value: &T) -> Result<(), String> {
+        (self.0)(value)
+    }
+}
+
+impl<F> fmt::Debug for Custom<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Custom")
+    }
+}
+
+/// Rule applying an inner rule to present `Option` values only
+#[derive(Debug, Clone, Copy)]
+pub struct Optional<R>(R);
+
+/// Accept `None` and check `Some` values with `rule`
+pub fn optional<R>(rule: R) -> Optional<R> {
+    Optional(rule)
+}
+
+impl<T, R: Rule<T>> Rule<Option<T>> for Optional<R> {
+    fn check(&self, value: &Option<T>) -> Result<(), String> {
+        value.as_ref().map_or(Ok(()), |value| self.0.check(value))
+    }
+}
+
+/// Rule applied only when a condition held at construction
+#[derive(Debug, Clone, Copy)]
+pub struct When<R> {
+    active: bool,
+    rule: R,
+}
+
+/// Check values with `rule` only when `active`, e.g. `when(order.express, required())`
+pub fn when<R>(active: bool, rule: R) -> When<R> {
+    When { active, rule }
+}
+
+impl<T: ?Sized, R: Rule<T>> Rule<T> for When<R> {
+    fn check(&self, value: &T) -> Result<(), String> {
+        if self.active { self.rule.check(value) } else { Ok(()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        assert!(required().check("  ").is_err());
+        assert!(required().check(&Some(1)).is_ok());
+        assert!(required().check(&Vec::<u8>::new()).is_err());
+
+        assert!(length(1, 3).check("héé").is_ok());
+        assert_eq!(length(1, 3).check(&vec![1, 2, 3, 4]).unwrap_err(), "length must be between 1 and 3 (was 4)");
+        assert_eq!(range(18, 130).check(&12).unwrap_err(), "must be between 18 and 130 (was 12)");
+
+        let even = custom(|value: &u32| if value.is_multiple_of(2) { Ok(()) } else { Err("must be even".to_string()) });
+        assert!(even.check(&4).is_ok());
+        assert!(optional(even).check(&None).is_ok());
+        assert!(optional(even).check(&Some(3)).is_err());
+        assert!(when(false, even).check(&3).is_ok());
+        assert!(when(true, even).check(&3).is_err());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_pattern() {
+        let zip = pattern(r"^\d{5}$");
+        assert!(zip.check("94107").is_ok());
+        assert_eq!(zip.check("9410").unwrap_err(), r"must match the pattern ^\d{5}$");
+
+        assert!(try_pattern(r"^\d{5}$").unwrap().check("94107").is_ok());
+        let error = try_pattern(r"^(\d{5}$").unwrap_err();
+        assert!(matches!(&error, crate::common::error::AklypseError::Parse { kind, context_info, .. } if kind == "regex" && context_info == r"^(\d{5}$"));
+    }
+}
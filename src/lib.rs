@@ -0,0 +1,15 @@
+/* src/lib.rs */
+//! **Brief:** Crate root of the Aklypse algotrader.
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+//! + [Aklypse]
+//!  - [Error Handling Framework]
+//!  - [Common Utilities]
+// ~=####====A===r===c===M===o===o===n====S===t===u===d===i===o===s====X|0|$>
+// **GitHub:** [ArcMoon Studios](https://github.com/arcmoonstudios)
+// **Copyright:** (c) 2025 ArcMoon Studios
+// **Author:** Lord Xyn
+// **License:** MIT
+
+pub mod common;
+
+pub use common::error;